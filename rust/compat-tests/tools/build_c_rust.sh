@@ -0,0 +1,40 @@
+#!/bin/bash
+#
+# Build the c-to-rust C writer against the exodus-rs C ABI
+#
+# Links writer.c with libexodus from rust/exodus-capi instead of the SEACAS
+# C library, so the C API calls themselves run on exodus-rs.
+#
+
+set -e
+
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+COMPAT_DIR="$(dirname "$SCRIPT_DIR")"
+RUST_ROOT="$(dirname "$COMPAT_DIR")"
+
+echo "===================================="
+echo "  Building C Writer on exodus-rs"
+echo "===================================="
+echo
+
+cd "$RUST_ROOT"
+cargo build --release -p exodus-capi
+
+CAPI_INCLUDE="$RUST_ROOT/exodus-capi/include"
+CAPI_LIB="$RUST_ROOT/target/release"
+
+echo "Using header:  $CAPI_INCLUDE/exodusII.h"
+echo "Using library: $CAPI_LIB"
+echo
+
+cd "$COMPAT_DIR/c-to-rust"
+gcc -o writer writer.c \
+    -I"$CAPI_INCLUDE" \
+    -L"$CAPI_LIB" \
+    -lexodus \
+    -lm \
+    -Wl,-rpath,"$CAPI_LIB" || {
+    echo "Error: Failed to build C writer against exodus-capi"
+    exit 1
+}
+echo "✓ Built: writer (linked with exodus-capi)"
@@ -0,0 +1,35 @@
+//! Generates `exodusII.h` in `OUT_DIR` from the exported functions
+//!
+//! Functions exported only with the `netcdf4` feature are wrapped in
+//! `#if defined(EXODUS_RS_NETCDF4)`, which the generated header defines when
+//! the library is built with that feature. `include/exodusII.h` is a
+//! committed copy without that define, so it never declares functions a
+//! build may lack; `tests/test_header.rs` checks that it is current.
+
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let mut config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("cbindgen.toml is valid");
+    if env::var_os("CARGO_FEATURE_NETCDF4").is_some() {
+        config.after_includes = Some("#define EXODUS_RS_NETCDF4".to_string());
+    }
+
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src");
+
+    match cbindgen::generate_with_config(&crate_dir, config) {
+        Ok(bindings) => {
+            bindings.write_to_file(out_dir.join("exodusII.h"));
+        }
+        // An empty header lets the build report the real error from rustc,
+        // e.g. while the source does not parse mid-edit
+        Err(err) => {
+            println!("cargo:warning=exodusII.h not generated: {}", err);
+            std::fs::write(out_dir.join("exodusII.h"), "").expect("OUT_DIR is writable");
+        }
+    }
+}
@@ -0,0 +1,1058 @@
+/*
+ * Exodus II C API implemented by exodus-rs.
+ *
+ * Generated by cbindgen from rust/exodus-capi; do not edit.
+ * Programs written against the SEACAS libexodus header compile unchanged
+ * for the functions listed here.
+ */
+
+#ifndef EXODUSII_H
+#define EXODUSII_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+/*
+ Fatal error
+ */
+#define EX_FATAL -1
+
+/*
+ No error
+ */
+#define EX_NOERR 0
+
+/*
+ Warning
+ */
+#define EX_WARN 1
+
+/*
+ Memory allocation failure
+ */
+#define EX_MEMFAIL 1000
+
+/*
+ Bad file mode, e.g. writing to a file opened with `EX_READ`
+ */
+#define EX_BADFILEMODE 1001
+
+/*
+ No file open with the given ID
+ */
+#define EX_BADFILEID 1002
+
+/*
+ Wrong file type for the function
+ */
+#define EX_WRONGFILETYPE 1003
+
+/*
+ ID lookup failed
+ */
+#define EX_LOOKUPFAIL 1004
+
+/*
+ Bad parameter
+ */
+#define EX_BADPARAM 1005
+
+/*
+ Internal logic error
+ */
+#define EX_INTERNAL 1006
+
+/*
+ Duplicate ID
+ */
+#define EX_DUPLICATEID 1007
+
+/*
+ File opened more than once
+ */
+#define EX_DUPLICATEOPEN 1008
+
+/*
+ Empty or null filename
+ */
+#define EX_BADFILENAME 1009
+
+/*
+ `ex_err`: message only, no error implied
+ */
+#define EX_MSG -1000
+
+/*
+ `ex_err`: print the last error message
+ */
+#define EX_PRTLASTMSG -1001
+
+/*
+ `ex_err`: keep the last error code
+ */
+#define EX_LASTERR -1003
+
+/*
+ Null entity found
+ */
+#define EX_NULLENTITY -1006
+
+/*
+ No entities of the type in the file
+ */
+#define EX_NOENTITY -1007
+
+/*
+ Variable not found in the file
+ */
+#define EX_NOTFOUND -1008
+
+/*
+ `ex_opts`: errors are not printed
+ */
+#define EX_DEFAULT 0
+
+/*
+ `ex_opts`: print errors and warnings to stderr
+ */
+#define EX_VERBOSE 1
+
+/*
+ `ex_opts`: debug output (accepted, no effect)
+ */
+#define EX_DEBUG 2
+
+/*
+ `ex_opts`: exit on fatal errors
+ */
+#define EX_ABORT 4
+
+/*
+ `ex_opts`: print null entity warnings
+ */
+#define EX_NULLVERBOSE 8
+
+/*
+ Length of QA record strings and element type names
+ */
+#define MAX_STR_LENGTH 32
+
+/*
+ Default length of entity and variable names
+ */
+#define MAX_NAME_LENGTH 32
+
+/*
+ Length of the title and info records
+ */
+#define MAX_LINE_LENGTH 80
+
+/*
+ Length of error messages
+ */
+#define MAX_ERR_LENGTH 512
+
+/*
+ Version of the C API this library implements
+ */
+#define EX_API_VERS 9.06
+
+/*
+ [`EX_API_VERS`] times 100, passed by the `ex_create`/`ex_open` macros
+ */
+#define EX_API_VERS_NODOT 906
+
+/*
+ `ex_open`: open an existing file for appending
+ */
+#define EX_WRITE 1
+
+/*
+ `ex_open`: open an existing file for reading
+ */
+#define EX_READ 2
+
+/*
+ `ex_create`: fail if the file exists (the default)
+ */
+#define EX_NOCLOBBER 4
+
+/*
+ `ex_create`: overwrite the file if it exists
+ */
+#define EX_CLOBBER 8
+
+/*
+ `ex_create`: classic NetCDF-3 format
+ */
+#define EX_NORMAL_MODEL 16
+
+/*
+ `ex_create`: NetCDF-3 format with 64-bit offsets
+ */
+#define EX_64BIT_OFFSET 32
+
+/*
+ `ex_create`: HDF5-based NetCDF-4 format
+ */
+#define EX_NETCDF4 64
+
+/*
+ `ex_create`: CDF-5 format
+ */
+#define EX_64BIT_DATA 4194304
+
+/*
+ `ex_create`: store maps as 64-bit integers
+ */
+#define EX_MAPS_INT64_DB 1024
+
+/*
+ `ex_create`: store entity IDs as 64-bit integers
+ */
+#define EX_IDS_INT64_DB 2048
+
+/*
+ `ex_create`: store bulk integer data as 64-bit integers
+ */
+#define EX_BULK_INT64_DB 4096
+
+/*
+ `ex_create`: store all integer data as 64-bit integers
+ */
+#define EX_ALL_INT64_DB 7168
+
+/*
+ Maps are passed as `int64_t`
+ */
+#define EX_MAPS_INT64_API 8192
+
+/*
+ Entity IDs and properties are passed as `int64_t`
+ */
+#define EX_IDS_INT64_API 16384
+
+/*
+ Counts, connectivity and set entries are passed as `int64_t`
+ */
+#define EX_BULK_INT64_API 32768
+
+/*
+ `ex_inquire` returns `int64_t`
+ */
+#define EX_INQ_INT64_API 65536
+
+/*
+ All integers are passed as `int64_t`
+ */
+#define EX_ALL_INT64_API 122880
+
+/*
+ Quantity queried with `ex_inquire` (`EX_INQ_*`)
+ */
+typedef int ex_inquiry;
+
+/*
+ Integer buffer holding `int` or `int64_t` values (see `EX_*_INT64_API`)
+ */
+typedef void void_int;
+
+/*
+ Model parameters for `ex_get_init_ext`/`ex_put_init_ext`
+ */
+typedef struct {
+  /*
+   Title, `MAX_LINE_LENGTH + 1` characters including the NUL
+   */
+  char title[81];
+  int64_t num_dim;
+  int64_t num_nodes;
+  int64_t num_edge;
+  int64_t num_edge_blk;
+  int64_t num_face;
+  int64_t num_face_blk;
+  int64_t num_elem;
+  int64_t num_elem_blk;
+  int64_t num_node_sets;
+  int64_t num_edge_sets;
+  int64_t num_face_sets;
+  int64_t num_side_sets;
+  int64_t num_elem_sets;
+  int64_t num_node_maps;
+  int64_t num_edge_maps;
+  int64_t num_face_maps;
+  int64_t num_elem_maps;
+  int64_t num_assembly;
+  int64_t num_blob;
+} ex_init_params;
+
+/*
+ Entity type (`EX_ELEM_BLOCK`, `EX_NODE_SET`, ...)
+ */
+typedef int ex_entity_type;
+
+/*
+ Entity (block, set, map) ID
+ */
+typedef int64_t ex_entity_id;
+
+/*
+ Nodal "block" for variables
+ */
+#define EX_NODAL 14
+
+/*
+ Node set
+ */
+#define EX_NODE_SET 2
+
+/*
+ Edge block
+ */
+#define EX_EDGE_BLOCK 6
+
+/*
+ Edge set
+ */
+#define EX_EDGE_SET 7
+
+/*
+ Face block
+ */
+#define EX_FACE_BLOCK 8
+
+/*
+ Face set
+ */
+#define EX_FACE_SET 9
+
+/*
+ Element block
+ */
+#define EX_ELEM_BLOCK 1
+
+/*
+ Element set
+ */
+#define EX_ELEM_SET 10
+
+/*
+ Side set
+ */
+#define EX_SIDE_SET 3
+
+/*
+ Element map
+ */
+#define EX_ELEM_MAP 4
+
+/*
+ Node map
+ */
+#define EX_NODE_MAP 5
+
+/*
+ Edge map
+ */
+#define EX_EDGE_MAP 11
+
+/*
+ Face map
+ */
+#define EX_FACE_MAP 12
+
+/*
+ Global "block" for variables
+ */
+#define EX_GLOBAL 13
+
+/*
+ Coordinates
+ */
+#define EX_COORDINATE 15
+
+/*
+ Assembly
+ */
+#define EX_ASSEMBLY 16
+
+/*
+ Blob
+ */
+#define EX_BLOB 17
+
+/*
+ Invalid entity type
+ */
+#define EX_INVALID -1
+
+/*
+ Version of the library (float)
+ */
+#define EX_INQ_API_VERS 2
+
+/*
+ Version of the file format (float)
+ */
+#define EX_INQ_DB_VERS 3
+
+/*
+ Title (string)
+ */
+#define EX_INQ_TITLE 4
+
+/*
+ Number of dimensions
+ */
+#define EX_INQ_DIM 5
+
+/*
+ Number of nodes
+ */
+#define EX_INQ_NODES 6
+
+/*
+ Number of elements
+ */
+#define EX_INQ_ELEM 7
+
+/*
+ Number of element blocks
+ */
+#define EX_INQ_ELEM_BLK 8
+
+/*
+ Number of node sets
+ */
+#define EX_INQ_NODE_SETS 9
+
+/*
+ Total length of the node set node lists
+ */
+#define EX_INQ_NS_NODE_LEN 10
+
+/*
+ Number of side sets
+ */
+#define EX_INQ_SIDE_SETS 11
+
+/*
+ Total length of the side set element lists
+ */
+#define EX_INQ_SS_ELEM_LEN 13
+
+/*
+ Number of QA records
+ */
+#define EX_INQ_QA 14
+
+/*
+ Number of info records
+ */
+#define EX_INQ_INFO 15
+
+/*
+ Number of time steps
+ */
+#define EX_INQ_TIME 16
+
+/*
+ Total length of the node set distribution factor lists
+ */
+#define EX_INQ_NS_DF_LEN 20
+
+/*
+ Total length of the side set distribution factor lists
+ */
+#define EX_INQ_SS_DF_LEN 21
+
+/*
+ Version of the library (float)
+ */
+#define EX_INQ_LIB_VERS 22
+
+/*
+ Number of element maps
+ */
+#define EX_INQ_ELEM_MAP 25
+
+/*
+ Number of node maps
+ */
+#define EX_INQ_NODE_MAP 26
+
+/*
+ Number of edges
+ */
+#define EX_INQ_EDGE 27
+
+/*
+ Number of edge blocks
+ */
+#define EX_INQ_EDGE_BLK 28
+
+/*
+ Number of edge sets
+ */
+#define EX_INQ_EDGE_SETS 29
+
+/*
+ Total length of the edge set lists
+ */
+#define EX_INQ_ES_LEN 30
+
+/*
+ Total length of the edge set distribution factor lists
+ */
+#define EX_INQ_ES_DF_LEN 31
+
+/*
+ Number of faces
+ */
+#define EX_INQ_FACE 34
+
+/*
+ Number of face blocks
+ */
+#define EX_INQ_FACE_BLK 35
+
+/*
+ Number of face sets
+ */
+#define EX_INQ_FACE_SETS 36
+
+/*
+ Total length of the face set lists
+ */
+#define EX_INQ_FS_LEN 37
+
+/*
+ Total length of the face set distribution factor lists
+ */
+#define EX_INQ_FS_DF_LEN 38
+
+/*
+ Number of element sets
+ */
+#define EX_INQ_ELEM_SETS 41
+
+/*
+ Total length of the element set lists
+ */
+#define EX_INQ_ELS_LEN 42
+
+/*
+ Total length of the element set distribution factor lists
+ */
+#define EX_INQ_ELS_DF_LEN 43
+
+/*
+ Number of edge maps
+ */
+#define EX_INQ_EDGE_MAP 45
+
+/*
+ Number of face maps
+ */
+#define EX_INQ_FACE_MAP 46
+
+/*
+ Length of names returned by the library
+ */
+#define EX_INQ_MAX_READ_NAME_LENGTH 50
+
+/*
+ Size in bytes of stored floating-point values
+ */
+#define EX_INQ_DB_FLOAT_SIZE 51
+
+/*
+ Number of assemblies
+ */
+#define EX_INQ_ASSEMBLY 60
+
+/*
+ Number of blobs
+ */
+#define EX_INQ_BLOB 61
+
+/*
+ Number of nodal variables
+ */
+#define EX_INQ_NUM_NODE_VAR 62
+
+/*
+ Number of edge block variables
+ */
+#define EX_INQ_NUM_EDGE_BLOCK_VAR 63
+
+/*
+ Number of face block variables
+ */
+#define EX_INQ_NUM_FACE_BLOCK_VAR 64
+
+/*
+ Number of element block variables
+ */
+#define EX_INQ_NUM_ELEM_BLOCK_VAR 65
+
+/*
+ Number of node set variables
+ */
+#define EX_INQ_NUM_NODE_SET_VAR 66
+
+/*
+ Number of edge set variables
+ */
+#define EX_INQ_NUM_EDGE_SET_VAR 67
+
+/*
+ Number of face set variables
+ */
+#define EX_INQ_NUM_FACE_SET_VAR 68
+
+/*
+ Number of element set variables
+ */
+#define EX_INQ_NUM_ELEM_SET_VAR 69
+
+/*
+ Number of side set variables
+ */
+#define EX_INQ_NUM_SIDE_SET_VAR 70
+
+/*
+ Number of global variables
+ */
+#define EX_INQ_NUM_GLOBAL_VAR 71
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Text description of an error code (`ex_strerror`)
+
+ The string is valid until the next call on the same thread.
+ */
+const char *ex_strerror(int err_num);
+
+/*
+ Report an error as the library does (`ex_err`)
+
+ `EX_PRTLASTMSG` prints the last error and `EX_LASTERR` keeps its code.
+ */
+void ex_err(const char *module_name, const char *message, int err_num);
+
+/*
+ Retrieve the last error on this thread (`ex_get_err`)
+
+ The strings are valid until the next error on the same thread. Any
+ argument may be NULL.
+ */
+void ex_get_err(const char **msg, const char **func, int *err_num);
+
+/*
+ Set error reporting options (`ex_opts`), returning the previous options
+ */
+int ex_opts(int options);
+
+/*
+ Create a new file (`ex_create`)
+
+ `*comp_ws` is the size of floating-point values passed to the library
+ (0 is replaced with 4) and `*io_ws` the size stored in the file (0 is
+ replaced with `*comp_ws`). Returns the file ID, or `EX_FATAL`.
+ */
+int ex_create_int(const char *rel_path, int cmode, int *comp_ws, int *io_ws, int _run_version);
+
+/*
+ Open an existing file (`ex_open`)
+
+ `mode` is `EX_READ` or `EX_WRITE`, optionally with `EX_*_INT64_API`
+ flags. On return `*io_ws` is the size of floating-point values stored in
+ the file and `*version` the file's format version. Returns the file ID,
+ or `EX_FATAL`.
+ */
+int ex_open_int(const char *rel_path, int mode, int *comp_ws, int *io_ws, float *version, int _run_version);
+
+/*
+ Close a file (`ex_close`)
+ */
+int ex_close(int exoid);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Flush pending writes to disk (`ex_update`)
+ */
+int ex_update(int exoid);
+#endif
+
+/*
+ `EX_*_INT64_API` flags of a file (`ex_int64_status`)
+ */
+uint32_t ex_int64_status(int exoid);
+
+/*
+ Set the `EX_*_INT64_API` flags of a file (`ex_set_int64_status`),
+ returning the previous flags
+ */
+int ex_set_int64_status(int exoid, int mode);
+
+/*
+ Set the length of names returned by the library (`ex_set_max_name_length`)
+ */
+int ex_set_max_name_length(int exoid, int length);
+
+/*
+ Query a property of a file (`ex_inquire`)
+
+ Integer quantities are written to `ret_int`, versions to `ret_float` and
+ the title to `ret_char`; the other pointers may be NULL.
+ */
+int ex_inquire(int exoid, ex_inquiry req_info, void_int *ret_int, float *ret_float, char *ret_char);
+
+/*
+ Query an integer property of a file (`ex_inquire_int`)
+
+ Returns the value, or `EX_FATAL` on error.
+ */
+int64_t ex_inquire_int(int exoid, ex_inquiry req_info);
+
+/*
+ Read the title and main counts of a model (`ex_get_init`)
+ */
+int ex_get_init(int exoid, char *title, void_int *num_dim, void_int *num_nodes, void_int *num_elem, void_int *num_elem_blk, void_int *num_node_sets, void_int *num_side_sets);
+
+/*
+ Read all model counts (`ex_get_init_ext`)
+ */
+int ex_get_init_ext(int exoid, ex_init_params *info);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the title and main counts of a model (`ex_put_init`)
+ */
+int ex_put_init(int exoid, const char *title, int64_t num_dim, int64_t num_nodes, int64_t num_elem, int64_t num_elem_blk, int64_t num_node_sets, int64_t num_side_sets);
+#endif
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write all model counts (`ex_put_init_ext`)
+ */
+int ex_put_init_ext(int exoid, const ex_init_params *model);
+#endif
+
+/*
+ Read the nodal coordinates (`ex_get_coord`)
+
+ Each of the arrays may be NULL to skip that component.
+ */
+int ex_get_coord(int exoid, void *x_coor, void *y_coor, void *z_coor);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the nodal coordinates (`ex_put_coord`)
+
+ `y_coor` and `z_coor` may be NULL for models of lower dimension.
+ */
+int ex_put_coord(int exoid, const void *x_coor, const void *y_coor, const void *z_coor);
+#endif
+
+/*
+ Read the coordinate names (`ex_get_coord_names`)
+ */
+int ex_get_coord_names(int exoid, char *const *coord_names);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the coordinate names, one per dimension (`ex_put_coord_names`)
+ */
+int ex_put_coord_names(int exoid, char *const *coord_names);
+#endif
+
+/*
+ Read the IDs of the blocks, sets, assemblies or blobs of a type
+ (`ex_get_ids`)
+ */
+int ex_get_ids(int exoid, ex_entity_type obj_type, void_int *ids);
+
+/*
+ Read the parameters of a block (`ex_get_block`)
+
+ Any of the output arguments may be NULL.
+ */
+int ex_get_block(int exoid, ex_entity_type blk_type, ex_entity_id blk_id, char *elem_type, void_int *num_entries_this_blk, void_int *num_nodes_per_entry, void_int *num_edges_per_entry, void_int *num_faces_per_entry, void_int *num_attr_per_entry);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Define a block (`ex_put_block`)
+ */
+int ex_put_block(int exoid, ex_entity_type blk_type, ex_entity_id blk_id, const char *entry_descrip, int64_t num_entries_this_blk, int64_t num_nodes_per_entry, int64_t num_edges_per_entry, int64_t num_faces_per_entry, int64_t num_attr_per_entry);
+#endif
+
+/*
+ Read the node connectivity of an element block (`ex_get_conn`)
+
+ Edge and face connectivity are not stored; `edgeconn` and `faceconn`
+ must be NULL.
+ */
+int ex_get_conn(int exoid, ex_entity_type blk_type, ex_entity_id blk_id, void_int *nodeconn, void_int *edgeconn, void_int *faceconn);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the node connectivity of an element block (`ex_put_conn`)
+
+ Edge and face connectivity are not stored; `edgeconn` and `faceconn`
+ must be NULL.
+ */
+int ex_put_conn(int exoid, ex_entity_type blk_type, ex_entity_id blk_id, const void_int *nodeconn, const void_int *edgeconn, const void_int *faceconn);
+#endif
+
+/*
+ Read the attributes of an element block (`ex_get_attr`)
+ */
+int ex_get_attr(int exoid, ex_entity_type obj_type, ex_entity_id obj_id, void *attrib);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the attributes of an element block (`ex_put_attr`)
+ */
+int ex_put_attr(int exoid, ex_entity_type blk_type, ex_entity_id blk_id, const void *attrib);
+#endif
+
+/*
+ Read the attribute names of an element block (`ex_get_attr_names`)
+ */
+int ex_get_attr_names(int exoid, ex_entity_type obj_type, ex_entity_id obj_id, char *const *names);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the attribute names of an element block (`ex_put_attr_names`)
+ */
+int ex_put_attr_names(int exoid, ex_entity_type blk_type, ex_entity_id blk_id, char *const *names);
+#endif
+
+/*
+ Read the number of entries and distribution factors of a set
+ (`ex_get_set_param`)
+ */
+int ex_get_set_param(int exoid, ex_entity_type set_type, ex_entity_id set_id, void_int *num_entry_in_set, void_int *num_dist_fact_in_set);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Define a set (`ex_put_set_param`)
+ */
+int ex_put_set_param(int exoid, ex_entity_type set_type, ex_entity_id set_id, int64_t num_entries_in_set, int64_t num_dist_fact_in_set);
+#endif
+
+/*
+ Read the entries of a set (`ex_get_set`)
+
+ `set_extra_list` receives the sides of a side set and may be NULL.
+ */
+int ex_get_set(int exoid, ex_entity_type set_type, ex_entity_id set_id, void_int *set_entry_list, void_int *set_extra_list);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the entries of a set defined with `ex_put_set_param` (`ex_put_set`)
+
+ `set_extra_list` holds the sides of a side set and is ignored for other
+ sets.
+ */
+int ex_put_set(int exoid, ex_entity_type set_type, ex_entity_id set_id, const void_int *set_entry_list, const void_int *set_extra_list);
+#endif
+
+/*
+ Read the distribution factors of a node or side set
+ (`ex_get_set_dist_fact`)
+ */
+int ex_get_set_dist_fact(int exoid, ex_entity_type set_type, ex_entity_id set_id, void *set_dist_fact);
+
+/*
+ Read the ID map of nodes, edges, faces or elements (`ex_get_id_map`)
+ */
+int ex_get_id_map(int exoid, ex_entity_type map_type, void_int *map);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the ID map of nodes, edges, faces or elements (`ex_put_id_map`)
+ */
+int ex_put_id_map(int exoid, ex_entity_type map_type, const void_int *map);
+#endif
+
+/*
+ Read the names of all blocks, sets or maps of a type (`ex_get_names`)
+ */
+int ex_get_names(int exoid, ex_entity_type obj_type, char *const *names);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the names of all blocks, sets or maps of a type (`ex_put_names`)
+ */
+int ex_put_names(int exoid, ex_entity_type obj_type, char *const *names);
+#endif
+
+/*
+ Read the name of one block, set or map (`ex_get_name`)
+ */
+int ex_get_name(int exoid, ex_entity_type obj_type, ex_entity_id entity_id, char *name);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the name of one block, set or map (`ex_put_name`)
+ */
+int ex_put_name(int exoid, ex_entity_type obj_type, ex_entity_id entity_id, const char *name);
+#endif
+
+/*
+ Read the property names of a type (`ex_get_prop_names`)
+ */
+int ex_get_prop_names(int exoid, ex_entity_type obj_type, char *const *prop_names);
+
+/*
+ Read a property of one block, set or map (`ex_get_prop`)
+ */
+int ex_get_prop(int exoid, ex_entity_type obj_type, ex_entity_id obj_id, const char *prop_name, void_int *value);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write a property of one block, set or map (`ex_put_prop`)
+ */
+int ex_put_prop(int exoid, ex_entity_type obj_type, ex_entity_id obj_id, const char *prop_name, ex_entity_id value);
+#endif
+
+/*
+ Read a property of all blocks, sets or maps of a type
+ (`ex_get_prop_array`)
+ */
+int ex_get_prop_array(int exoid, ex_entity_type obj_type, const char *prop_name, void_int *values);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write a property of all blocks, sets or maps of a type
+ (`ex_put_prop_array`)
+ */
+int ex_put_prop_array(int exoid, ex_entity_type obj_type, const char *prop_name, const void_int *values);
+#endif
+
+/*
+ Read the QA records (`ex_get_qa`)
+ */
+int ex_get_qa(int exoid, char *(*qa_record)[4]);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the QA records (`ex_put_qa`)
+ */
+int ex_put_qa(int exoid, int num_qa_records, char *(*qa_record)[4]);
+#endif
+
+/*
+ Read the information records (`ex_get_info`)
+ */
+int ex_get_info(int exoid, char *const *info);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the information records (`ex_put_info`)
+ */
+int ex_put_info(int exoid, int num_info, char *const *info);
+#endif
+
+/*
+ Read the number of variables of a type (`ex_get_variable_param`)
+ */
+int ex_get_variable_param(int exoid, ex_entity_type obj_type, int *num_vars);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Declare the number of variables of a type (`ex_put_variable_param`)
+
+ The variables are defined by `ex_put_variable_names`.
+ */
+int ex_put_variable_param(int exoid, ex_entity_type obj_type, int num_vars);
+#endif
+
+/*
+ Read the names of the first `num_vars` variables of a type
+ (`ex_get_variable_names`)
+ */
+int ex_get_variable_names(int exoid, ex_entity_type obj_type, int num_vars, char *const *var_names);
+
+/*
+ Read the name of one variable (`ex_get_variable_name`)
+ */
+int ex_get_variable_name(int exoid, ex_entity_type obj_type, int var_num, char *var_name);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Define the variables of a type by name (`ex_put_variable_names`)
+ */
+int ex_put_variable_names(int exoid, ex_entity_type obj_type, int num_vars, char *const *var_names);
+#endif
+
+/*
+ Read the truth table of a block or set variable type
+ (`ex_get_truth_table`)
+
+ `var_tab` holds `num_blk` rows of `num_var` flags.
+ */
+int ex_get_truth_table(int exoid, ex_entity_type obj_type, int num_blk, int num_var, int *var_tab);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the truth table of a block or set variable type
+ (`ex_put_truth_table`)
+ */
+int ex_put_truth_table(int exoid, ex_entity_type obj_type, int num_blk, int num_var, int *var_tab);
+#endif
+
+/*
+ Read the values of one variable on one block or set at a time step
+ (`ex_get_var`)
+
+ Global variables are read `num_entry_this_obj` at a time starting at
+ `var_index`, as in C. `obj_id` is ignored for global and nodal variables.
+ */
+int ex_get_var(int exoid, int time_step, ex_entity_type var_type, int var_index, ex_entity_id obj_id, int64_t num_entry_this_obj, void *var_vals);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the values of one variable on one block or set at a time step
+ (`ex_put_var`)
+
+ Global variables are written `num_entries_this_obj` at a time starting at
+ `var_index`, as in C. `obj_id` is ignored for global and nodal variables.
+ */
+int ex_put_var(int exoid, int time_step, ex_entity_type var_type, int var_index, ex_entity_id obj_id, int64_t num_entries_this_obj, const void *var_vals);
+#endif
+
+/*
+ Read the time value of a time step (`ex_get_time`)
+ */
+int ex_get_time(int exoid, int time_step, void *time_value);
+
+/*
+ Read the time values of all time steps (`ex_get_all_times`)
+ */
+int ex_get_all_times(int exoid, void *time_values);
+
+#if defined(EXODUS_RS_NETCDF4)
+/*
+ Write the time value of a time step (`ex_put_time`)
+ */
+int ex_put_time(int exoid, int time_step, const void *time_value);
+#endif
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* EXODUSII_H */
+
+/* Versioned entry points, as in the SEACAS header */
+#define ex_create(path, mode, comp_ws, io_ws) \
+  ex_create_int(path, mode, comp_ws, io_ws, EX_API_VERS_NODOT)
+#define ex_open(path, mode, comp_ws, io_ws, version) \
+  ex_open_int(path, mode, comp_ws, io_ws, version, EX_API_VERS_NODOT)
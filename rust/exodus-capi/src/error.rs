@@ -0,0 +1,310 @@
+//! C error codes, the last-error record and `ex_opts`
+
+use exodus_rs::{ExodusError, Result};
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// Fatal error
+pub const EX_FATAL: c_int = -1;
+/// No error
+pub const EX_NOERR: c_int = 0;
+/// Warning
+pub const EX_WARN: c_int = 1;
+/// Memory allocation failure
+pub const EX_MEMFAIL: c_int = 1000;
+/// Bad file mode, e.g. writing to a file opened with `EX_READ`
+pub const EX_BADFILEMODE: c_int = 1001;
+/// No file open with the given ID
+pub const EX_BADFILEID: c_int = 1002;
+/// Wrong file type for the function
+pub const EX_WRONGFILETYPE: c_int = 1003;
+/// ID lookup failed
+pub const EX_LOOKUPFAIL: c_int = 1004;
+/// Bad parameter
+pub const EX_BADPARAM: c_int = 1005;
+/// Internal logic error
+pub const EX_INTERNAL: c_int = 1006;
+/// Duplicate ID
+pub const EX_DUPLICATEID: c_int = 1007;
+/// File opened more than once
+pub const EX_DUPLICATEOPEN: c_int = 1008;
+/// Empty or null filename
+pub const EX_BADFILENAME: c_int = 1009;
+/// `ex_err`: message only, no error implied
+pub const EX_MSG: c_int = -1000;
+/// `ex_err`: print the last error message
+pub const EX_PRTLASTMSG: c_int = -1001;
+/// `ex_err`: keep the last error code
+pub const EX_LASTERR: c_int = -1003;
+/// Null entity found
+pub const EX_NULLENTITY: c_int = -1006;
+/// No entities of the type in the file
+pub const EX_NOENTITY: c_int = -1007;
+/// Variable not found in the file
+pub const EX_NOTFOUND: c_int = -1008;
+
+/// `ex_opts`: errors are not printed
+pub const EX_DEFAULT: c_int = 0;
+/// `ex_opts`: print errors and warnings to stderr
+pub const EX_VERBOSE: c_int = 1;
+/// `ex_opts`: debug output (accepted, no effect)
+pub const EX_DEBUG: c_int = 2;
+/// `ex_opts`: exit on fatal errors
+pub const EX_ABORT: c_int = 4;
+/// `ex_opts`: print null entity warnings
+pub const EX_NULLVERBOSE: c_int = 8;
+
+/// Options set with `ex_opts`
+static OPTIONS: AtomicI32 = AtomicI32::new(EX_DEFAULT);
+
+/// Last error reported on a thread
+struct LastError {
+    function: CString,
+    message: CString,
+    code: c_int,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<LastError> = RefCell::new(LastError {
+        function: CString::default(),
+        message: CString::default(),
+        code: EX_NOERR,
+    });
+    static STRERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// C error code of an error
+///
+/// NetCDF and system errors keep their own codes, as in the C library.
+pub(crate) fn error_code(err: &ExodusError) -> c_int {
+    match err {
+        #[cfg(feature = "netcdf4")]
+        ExodusError::NetCdf(netcdf::Error::Netcdf(code)) => *code,
+        ExodusError::Io(io) => io.raw_os_error().unwrap_or(EX_INTERNAL),
+        ExodusError::InvalidHandle(_) => EX_BADFILEID,
+        ExodusError::InvalidMode(_)
+        | ExodusError::WriteOnReadOnly
+        | ExodusError::ReadOnWriteOnly => EX_BADFILEMODE,
+        ExodusError::EntityNotFound { .. } | ExodusError::InvalidEntityId(_) => EX_LOOKUPFAIL,
+        ExodusError::VariableNotDefined(_) => EX_NOTFOUND,
+        ExodusError::InvalidEntityType(_)
+        | ExodusError::InvalidDimension { .. }
+        | ExodusError::InvalidArrayLength { .. }
+        | ExodusError::InvalidTopology(_)
+        | ExodusError::StringTooLong { .. }
+        | ExodusError::IntegerOverflow { .. }
+        | ExodusError::InvalidTimeStep(_)
+        | ExodusError::UnsupportedFeature(_)
+        | ExodusError::NotInitialized
+        | ExodusError::Other(_) => EX_BADPARAM,
+        #[allow(unreachable_patterns)]
+        _ => EX_INTERNAL,
+    }
+}
+
+fn c_string(value: &str) -> CString {
+    CString::new(value.replace('\0', "")).unwrap_or_default()
+}
+
+/// Record an error for `ex_get_err`, printing it if requested with `ex_opts`
+fn report(function: &str, message: &str, code: c_int) {
+    let options = OPTIONS.load(Ordering::Relaxed);
+    if code == EX_NULLENTITY {
+        if options & EX_NULLVERBOSE != 0 {
+            eprintln!("\nExodus Library Warning: [{}]\n\t{}", function, message);
+        }
+    } else if options & EX_VERBOSE != 0 {
+        eprintln!(
+            "\nExodus Library Warning/Error: [{}]\n\t{}\n\t{}",
+            function,
+            message,
+            strerror(code)
+        );
+    }
+    LAST_ERROR.with(|last| {
+        *last.borrow_mut() = LastError {
+            function: c_string(function),
+            message: c_string(message),
+            code,
+        };
+    });
+    if code > 0 && options & EX_ABORT != 0 {
+        std::process::exit(code);
+    }
+}
+
+/// Run an exported function, reporting errors and panics
+///
+/// Returns `failed` if `f` fails.
+pub(crate) fn guard<T>(function: &str, failed: T, f: impl FnOnce() -> Result<T>) -> T {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(err)) => {
+            report(function, &err.to_string(), error_code(&err));
+            failed
+        }
+        Err(_) => {
+            report(function, "Rust panic in exodus-rs", EX_INTERNAL);
+            failed
+        }
+    }
+}
+
+/// Run an exported function returning `EX_NOERR` or `EX_FATAL`
+pub(crate) fn status(function: &str, f: impl FnOnce() -> Result<()>) -> c_int {
+    guard(function, EX_FATAL, || f().map(|()| EX_NOERR))
+}
+
+fn strerror(code: c_int) -> String {
+    match code {
+        EX_NOERR => "No error".into(),
+        EX_MEMFAIL => "Memory allocation failure".into(),
+        EX_BADFILEMODE => "Bad file mode -- cannot specify both EX_READ and EX_WRITE".into(),
+        EX_BADFILEID => "Bad file id. Could not find exodus file associated with file id.".into(),
+        EX_WRONGFILETYPE => {
+            "File does not exist or is not of a supported type (netcdf3, netcdf4, netcdf5).".into()
+        }
+        EX_LOOKUPFAIL => "Id lookup failed for specified entity type. Could not find entity \
+                          with specified id."
+            .into(),
+        EX_BADFILENAME => "Empty or null filename specified.".into(),
+        EX_DUPLICATEID => "Duplicate entity id found.".into(),
+        EX_DUPLICATEOPEN => "File is open multiple times for both read and write.".into(),
+        EX_BADPARAM => "Bad parameter.".into(),
+        EX_INTERNAL => "Internal logic error in exodus library.".into(),
+        EX_NULLENTITY => "Null entity found.".into(),
+        EX_NOTFOUND => "Could not find requested variable on database.".into(),
+        EX_MSG => "Message printed; no error implied.".into(),
+        code if code > 0 => std::io::Error::from_raw_os_error(code).to_string(),
+        code => format!("NetCDF error {}", code),
+    }
+}
+
+/// Text description of an error code (`ex_strerror`)
+///
+/// The string is valid until the next call on the same thread.
+#[no_mangle]
+pub extern "C" fn ex_strerror(err_num: c_int) -> *const c_char {
+    STRERROR.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+        *buffer = c_string(&strerror(err_num));
+        buffer.as_ptr()
+    })
+}
+
+/// Report an error as the library does (`ex_err`)
+///
+/// `EX_PRTLASTMSG` prints the last error and `EX_LASTERR` keeps its code.
+#[no_mangle]
+pub unsafe extern "C" fn ex_err(
+    module_name: *const c_char,
+    message: *const c_char,
+    err_num: c_int,
+) {
+    if err_num == EX_NOERR {
+        return;
+    }
+    if err_num == EX_PRTLASTMSG {
+        LAST_ERROR.with(|last| {
+            let last = last.borrow();
+            eprintln!(
+                "\n[{}] {}\n    exerrval = {}\n\t{}",
+                last.function.to_string_lossy(),
+                last.message.to_string_lossy(),
+                last.code,
+                strerror(last.code)
+            );
+        });
+        return;
+    }
+    let text = |ptr: *const c_char| {
+        if ptr.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(ptr).to_string_lossy().into_owned()
+        }
+    };
+    let code = if err_num == EX_LASTERR {
+        LAST_ERROR.with(|last| last.borrow().code)
+    } else {
+        err_num
+    };
+    report(&text(module_name), &text(message), code);
+}
+
+/// Retrieve the last error on this thread (`ex_get_err`)
+///
+/// The strings are valid until the next error on the same thread. Any
+/// argument may be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_err(
+    msg: *mut *const c_char,
+    func: *mut *const c_char,
+    err_num: *mut c_int,
+) {
+    LAST_ERROR.with(|last| {
+        let last = last.borrow();
+        if !msg.is_null() {
+            *msg = last.message.as_ptr();
+        }
+        if !func.is_null() {
+            *func = last.function.as_ptr();
+        }
+        if !err_num.is_null() {
+            *err_num = last.code;
+        }
+    });
+}
+
+/// Set error reporting options (`ex_opts`), returning the previous options
+#[no_mangle]
+pub extern "C" fn ex_opts(options: c_int) -> c_int {
+    OPTIONS.swap(options, Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(error_code(&ExodusError::InvalidHandle(7)), EX_BADFILEID);
+        assert_eq!(error_code(&ExodusError::WriteOnReadOnly), EX_BADFILEMODE);
+        assert_eq!(
+            error_code(&ExodusError::EntityNotFound {
+                entity_type: "elem_block".into(),
+                id: 3
+            }),
+            EX_LOOKUPFAIL
+        );
+        assert_eq!(
+            error_code(&ExodusError::VariableNotDefined("x".into())),
+            EX_NOTFOUND
+        );
+        assert_eq!(error_code(&ExodusError::InvalidTimeStep(0)), EX_BADPARAM);
+        let missing = std::io::Error::from_raw_os_error(2);
+        assert_eq!(error_code(&ExodusError::Io(missing)), 2);
+    }
+
+    #[test]
+    fn test_last_error() {
+        let result = status("ex_test", || Err(ExodusError::InvalidHandle(42)));
+        assert_eq!(result, EX_FATAL);
+
+        let (mut msg, mut func, mut code) = (std::ptr::null(), std::ptr::null(), 0);
+        unsafe {
+            ex_get_err(&mut msg, &mut func, &mut code);
+            assert_eq!(CStr::from_ptr(func).to_str().unwrap(), "ex_test");
+            assert!(CStr::from_ptr(msg).to_str().unwrap().contains("42"));
+        }
+        assert_eq!(code, EX_BADFILEID);
+
+        assert_eq!(guard("ex_test", -1, || panic!("boom")), -1);
+        unsafe { ex_get_err(std::ptr::null_mut(), std::ptr::null_mut(), &mut code) };
+        assert_eq!(code, EX_INTERNAL);
+        assert_eq!(status("ex_test", || Ok(())), EX_NOERR);
+    }
+}
@@ -0,0 +1,429 @@
+//! C types and constants, and conversions between C and Rust values
+
+use exodus_rs::{EntityType, ExodusError, InitParams, Result};
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+
+/// Integer buffer holding `int` or `int64_t` values (see `EX_*_INT64_API`)
+pub type void_int = c_void;
+/// Entity (block, set, map) ID
+pub type ex_entity_id = i64;
+/// Entity type (`EX_ELEM_BLOCK`, `EX_NODE_SET`, ...)
+pub type ex_entity_type = c_int;
+
+/// Nodal "block" for variables
+pub const EX_NODAL: ex_entity_type = 14;
+/// Node set
+pub const EX_NODE_SET: ex_entity_type = 2;
+/// Edge block
+pub const EX_EDGE_BLOCK: ex_entity_type = 6;
+/// Edge set
+pub const EX_EDGE_SET: ex_entity_type = 7;
+/// Face block
+pub const EX_FACE_BLOCK: ex_entity_type = 8;
+/// Face set
+pub const EX_FACE_SET: ex_entity_type = 9;
+/// Element block
+pub const EX_ELEM_BLOCK: ex_entity_type = 1;
+/// Element set
+pub const EX_ELEM_SET: ex_entity_type = 10;
+/// Side set
+pub const EX_SIDE_SET: ex_entity_type = 3;
+/// Element map
+pub const EX_ELEM_MAP: ex_entity_type = 4;
+/// Node map
+pub const EX_NODE_MAP: ex_entity_type = 5;
+/// Edge map
+pub const EX_EDGE_MAP: ex_entity_type = 11;
+/// Face map
+pub const EX_FACE_MAP: ex_entity_type = 12;
+/// Global "block" for variables
+pub const EX_GLOBAL: ex_entity_type = 13;
+/// Coordinates
+pub const EX_COORDINATE: ex_entity_type = 15;
+/// Assembly
+pub const EX_ASSEMBLY: ex_entity_type = 16;
+/// Blob
+pub const EX_BLOB: ex_entity_type = 17;
+/// Invalid entity type
+pub const EX_INVALID: ex_entity_type = -1;
+
+/// Length of QA record strings and element type names
+pub const MAX_STR_LENGTH: c_int = 32;
+/// Default length of entity and variable names
+pub const MAX_NAME_LENGTH: c_int = 32;
+/// Length of the title and info records
+pub const MAX_LINE_LENGTH: c_int = 80;
+/// Length of error messages
+pub const MAX_ERR_LENGTH: c_int = 512;
+
+/// Version of the C API this library implements
+pub const EX_API_VERS: f32 = 9.06;
+/// [`EX_API_VERS`] times 100, passed by the `ex_create`/`ex_open` macros
+pub const EX_API_VERS_NODOT: c_int = 906;
+
+/// Model parameters for `ex_get_init_ext`/`ex_put_init_ext`
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct ex_init_params {
+    /// Title, `MAX_LINE_LENGTH + 1` characters including the NUL
+    pub title: [c_char; 81],
+    pub num_dim: i64,
+    pub num_nodes: i64,
+    pub num_edge: i64,
+    pub num_edge_blk: i64,
+    pub num_face: i64,
+    pub num_face_blk: i64,
+    pub num_elem: i64,
+    pub num_elem_blk: i64,
+    pub num_node_sets: i64,
+    pub num_edge_sets: i64,
+    pub num_face_sets: i64,
+    pub num_side_sets: i64,
+    pub num_elem_sets: i64,
+    pub num_node_maps: i64,
+    pub num_edge_maps: i64,
+    pub num_face_maps: i64,
+    pub num_elem_maps: i64,
+    pub num_assembly: i64,
+    pub num_blob: i64,
+}
+
+impl ex_init_params {
+    /// C parameters from Rust parameters
+    pub(crate) fn from_params(params: &InitParams) -> Self {
+        let mut title = [0; MAX_LINE_LENGTH as usize + 1];
+        for (dst, src) in title
+            .iter_mut()
+            .zip(params.title.bytes().take(MAX_LINE_LENGTH as usize))
+        {
+            *dst = src as c_char;
+        }
+        Self {
+            title,
+            num_dim: params.num_dim as i64,
+            num_nodes: params.num_nodes as i64,
+            num_edge: params.num_edges as i64,
+            num_edge_blk: params.num_edge_blocks as i64,
+            num_face: params.num_faces as i64,
+            num_face_blk: params.num_face_blocks as i64,
+            num_elem: params.num_elems as i64,
+            num_elem_blk: params.num_elem_blocks as i64,
+            num_node_sets: params.num_node_sets as i64,
+            num_edge_sets: params.num_edge_sets as i64,
+            num_face_sets: params.num_face_sets as i64,
+            num_side_sets: params.num_side_sets as i64,
+            num_elem_sets: params.num_elem_sets as i64,
+            num_node_maps: params.num_node_maps as i64,
+            num_edge_maps: params.num_edge_maps as i64,
+            num_face_maps: params.num_face_maps as i64,
+            num_elem_maps: params.num_elem_maps as i64,
+            num_assembly: params.num_assemblies as i64,
+            num_blob: params.num_blobs as i64,
+        }
+    }
+
+    /// Rust parameters from C parameters
+    pub(crate) fn to_params(&self) -> Result<InitParams> {
+        let title: Vec<u8> = self
+            .title
+            .iter()
+            .take_while(|&&c| c != 0)
+            .map(|&c| c as u8)
+            .collect();
+        Ok(InitParams {
+            title: String::from_utf8_lossy(&title).into_owned(),
+            num_dim: count(self.num_dim, "num_dim")?,
+            num_nodes: count(self.num_nodes, "num_nodes")?,
+            num_edges: count(self.num_edge, "num_edge")?,
+            num_edge_blocks: count(self.num_edge_blk, "num_edge_blk")?,
+            num_faces: count(self.num_face, "num_face")?,
+            num_face_blocks: count(self.num_face_blk, "num_face_blk")?,
+            num_elems: count(self.num_elem, "num_elem")?,
+            num_elem_blocks: count(self.num_elem_blk, "num_elem_blk")?,
+            num_node_sets: count(self.num_node_sets, "num_node_sets")?,
+            num_edge_sets: count(self.num_edge_sets, "num_edge_sets")?,
+            num_face_sets: count(self.num_face_sets, "num_face_sets")?,
+            num_side_sets: count(self.num_side_sets, "num_side_sets")?,
+            num_elem_sets: count(self.num_elem_sets, "num_elem_sets")?,
+            num_node_maps: count(self.num_node_maps, "num_node_maps")?,
+            num_edge_maps: count(self.num_edge_maps, "num_edge_maps")?,
+            num_face_maps: count(self.num_face_maps, "num_face_maps")?,
+            num_elem_maps: count(self.num_elem_maps, "num_elem_maps")?,
+            num_assemblies: count(self.num_assembly, "num_assembly")?,
+            num_blobs: count(self.num_blob, "num_blob")?,
+        })
+    }
+}
+
+/// Rust entity type of a C entity type
+///
+/// The C values of `EX_ELEM_MAP` and `EX_NODE_MAP` differ from the
+/// [`EntityType`] discriminants, so every value is converted explicitly.
+pub(crate) fn entity_type(value: ex_entity_type) -> Result<EntityType> {
+    Ok(match value {
+        EX_NODAL => EntityType::Nodal,
+        EX_NODE_SET => EntityType::NodeSet,
+        EX_EDGE_BLOCK => EntityType::EdgeBlock,
+        EX_EDGE_SET => EntityType::EdgeSet,
+        EX_FACE_BLOCK => EntityType::FaceBlock,
+        EX_FACE_SET => EntityType::FaceSet,
+        EX_ELEM_BLOCK => EntityType::ElemBlock,
+        EX_ELEM_SET => EntityType::ElemSet,
+        EX_SIDE_SET => EntityType::SideSet,
+        EX_ELEM_MAP => EntityType::ElemMap,
+        EX_NODE_MAP => EntityType::NodeMap,
+        EX_EDGE_MAP => EntityType::EdgeMap,
+        EX_FACE_MAP => EntityType::FaceMap,
+        EX_GLOBAL => EntityType::Global,
+        EX_ASSEMBLY => EntityType::Assembly,
+        EX_BLOB => EntityType::Blob,
+        _ => {
+            return Err(ExodusError::InvalidEntityType(format!(
+                "Unsupported ex_entity_type {}",
+                value
+            )))
+        }
+    })
+}
+
+/// Number of blocks, sets or maps of a type in a model
+pub(crate) fn entity_count(params: &InitParams, obj_type: EntityType) -> Result<usize> {
+    Ok(match obj_type {
+        EntityType::ElemBlock => params.num_elem_blocks,
+        EntityType::EdgeBlock => params.num_edge_blocks,
+        EntityType::FaceBlock => params.num_face_blocks,
+        EntityType::NodeSet => params.num_node_sets,
+        EntityType::EdgeSet => params.num_edge_sets,
+        EntityType::FaceSet => params.num_face_sets,
+        EntityType::ElemSet => params.num_elem_sets,
+        EntityType::SideSet => params.num_side_sets,
+        EntityType::NodeMap => params.num_node_maps,
+        EntityType::EdgeMap => params.num_edge_maps,
+        EntityType::FaceMap => params.num_face_maps,
+        EntityType::ElemMap => params.num_elem_maps,
+        EntityType::Assembly => params.num_assemblies,
+        EntityType::Blob => params.num_blobs,
+        EntityType::Global | EntityType::Nodal => {
+            return Err(ExodusError::InvalidEntityType(format!(
+                "{} has no entities",
+                obj_type
+            )))
+        }
+    })
+}
+
+/// A count passed from C as a signed integer
+pub(crate) fn count(value: i64, what: &str) -> Result<usize> {
+    usize::try_from(value)
+        .map_err(|_| ExodusError::Other(format!("{} is negative: {}", what, value)))
+}
+
+/// Buffers of values may only be NULL when there is nothing to copy
+fn non_null<T>(ptr: *const T, len: usize, what: &str) -> Result<()> {
+    if ptr.is_null() && len > 0 {
+        Err(ExodusError::Other(format!("{} is NULL", what)))
+    } else {
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Buffers
+// ============================================================================
+
+/// Read `len` `float`s or `double`s, by compute word size
+pub(crate) unsafe fn read_reals(
+    ptr: *const c_void,
+    len: usize,
+    comp_ws: usize,
+    what: &str,
+) -> Result<Vec<f64>> {
+    non_null(ptr, len, what)?;
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+    Ok(if comp_ws == 4 {
+        std::slice::from_raw_parts(ptr as *const f32, len)
+            .iter()
+            .map(|&v| v as f64)
+            .collect()
+    } else {
+        std::slice::from_raw_parts(ptr as *const f64, len).to_vec()
+    })
+}
+
+/// Write `float`s or `double`s, by compute word size
+pub(crate) unsafe fn write_reals(
+    ptr: *mut c_void,
+    values: &[f64],
+    comp_ws: usize,
+    what: &str,
+) -> Result<()> {
+    non_null(ptr, values.len(), what)?;
+    if values.is_empty() {
+        return Ok(());
+    }
+    if comp_ws == 4 {
+        let dst = std::slice::from_raw_parts_mut(ptr as *mut f32, values.len());
+        for (dst, &src) in dst.iter_mut().zip(values) {
+            *dst = src as f32;
+        }
+    } else {
+        std::slice::from_raw_parts_mut(ptr as *mut f64, values.len()).copy_from_slice(values);
+    }
+    Ok(())
+}
+
+/// Read `len` `int`s or `int64_t`s
+pub(crate) unsafe fn read_ints(
+    ptr: *const void_int,
+    len: usize,
+    int64: bool,
+    what: &str,
+) -> Result<Vec<i64>> {
+    non_null(ptr, len, what)?;
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+    Ok(if int64 {
+        std::slice::from_raw_parts(ptr as *const i64, len).to_vec()
+    } else {
+        std::slice::from_raw_parts(ptr as *const c_int, len)
+            .iter()
+            .map(|&v| v as i64)
+            .collect()
+    })
+}
+
+/// Write `int`s or `int64_t`s
+///
+/// Fails without writing if a value does not fit in an `int`.
+pub(crate) unsafe fn write_ints(
+    ptr: *mut void_int,
+    values: &[i64],
+    int64: bool,
+    what: &str,
+) -> Result<()> {
+    non_null(ptr, values.len(), what)?;
+    if values.is_empty() {
+        return Ok(());
+    }
+    if int64 {
+        std::slice::from_raw_parts_mut(ptr as *mut i64, values.len()).copy_from_slice(values);
+        return Ok(());
+    }
+    let narrowed = values
+        .iter()
+        .map(|&value| {
+            c_int::try_from(value).map_err(|_| ExodusError::IntegerOverflow {
+                context: what.to_string(),
+                value,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    std::slice::from_raw_parts_mut(ptr as *mut c_int, values.len()).copy_from_slice(&narrowed);
+    Ok(())
+}
+
+/// Read a NUL-terminated string
+pub(crate) unsafe fn read_string(ptr: *const c_char, what: &str) -> Result<String> {
+    non_null(ptr, 1, what)?;
+    Ok(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+}
+
+/// Read an array of `len` NUL-terminated strings
+pub(crate) unsafe fn read_strings(
+    ptr: *const *mut c_char,
+    len: usize,
+    what: &str,
+) -> Result<Vec<String>> {
+    non_null(ptr, len, what)?;
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+    std::slice::from_raw_parts(ptr, len)
+        .iter()
+        .map(|&s| read_string(s, what))
+        .collect()
+}
+
+/// Copy a string into a buffer of `max_len + 1` bytes, truncating it
+pub(crate) unsafe fn write_string(
+    ptr: *mut c_char,
+    value: &str,
+    max_len: usize,
+    what: &str,
+) -> Result<()> {
+    non_null(ptr, 1, what)?;
+    let bytes = &value.as_bytes()[..value.len().min(max_len)];
+    let dst = std::slice::from_raw_parts_mut(ptr as *mut u8, bytes.len() + 1);
+    dst[..bytes.len()].copy_from_slice(bytes);
+    dst[bytes.len()] = 0;
+    Ok(())
+}
+
+/// Copy strings into an array of buffers of `max_len + 1` bytes
+pub(crate) unsafe fn write_strings(
+    ptr: *const *mut c_char,
+    values: &[String],
+    max_len: usize,
+    what: &str,
+) -> Result<()> {
+    non_null(ptr, values.len(), what)?;
+    if values.is_empty() {
+        return Ok(());
+    }
+    for (&dst, value) in std::slice::from_raw_parts(ptr, values.len())
+        .iter()
+        .zip(values)
+    {
+        write_string(dst, value, max_len, what)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entity_type_maps_are_swapped() {
+        assert_eq!(entity_type(EX_ELEM_MAP).unwrap(), EntityType::ElemMap);
+        assert_eq!(entity_type(EX_NODE_MAP).unwrap(), EntityType::NodeMap);
+        assert_eq!(entity_type(EX_ELEM_BLOCK).unwrap(), EntityType::ElemBlock);
+        assert!(entity_type(EX_COORDINATE).is_err());
+        assert!(entity_type(EX_INVALID).is_err());
+    }
+
+    #[test]
+    fn test_int_buffers() {
+        let mut ints = [0 as c_int; 2];
+        unsafe {
+            write_ints(ints.as_mut_ptr().cast(), &[3, -4], false, "ints").unwrap();
+            assert_eq!(ints, [3, -4]);
+            assert!(matches!(
+                write_ints(ints.as_mut_ptr().cast(), &[1, 1 << 40], false, "ints"),
+                Err(ExodusError::IntegerOverflow { value, .. }) if value == 1 << 40
+            ));
+            // Nothing is written when a value overflows
+            assert_eq!(ints, [3, -4]);
+
+            let wide = [1i64 << 40, 7];
+            assert_eq!(
+                read_ints(wide.as_ptr().cast(), 2, true, "wide").unwrap(),
+                vec![1 << 40, 7]
+            );
+            assert!(read_ints(std::ptr::null(), 2, true, "null").is_err());
+        }
+    }
+
+    #[test]
+    fn test_strings_are_truncated() {
+        let mut buffer = [1 as c_char; 5];
+        unsafe {
+            write_string(buffer.as_mut_ptr(), "abcdef", 4, "name").unwrap();
+            assert_eq!(read_string(buffer.as_ptr(), "name").unwrap(), "abcd");
+        }
+    }
+}
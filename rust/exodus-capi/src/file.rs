@@ -0,0 +1,552 @@
+//! File creation and opening, integer and name options, and `ex_inquire`
+
+use crate::error::{guard, status, EX_FATAL};
+use crate::ffi::{
+    read_string, void_int, write_ints, write_string, MAX_LINE_LENGTH, MAX_NAME_LENGTH,
+};
+use exodus_rs::raw::{self, ExInquiry};
+use exodus_rs::{ExodusError, Result};
+use std::collections::BTreeMap;
+use std::os::raw::{c_char, c_int};
+use std::sync::{Mutex, PoisonError};
+
+/// `ex_open`: open an existing file for appending
+pub const EX_WRITE: c_int = 0x0001;
+/// `ex_open`: open an existing file for reading
+pub const EX_READ: c_int = 0x0002;
+/// `ex_create`: fail if the file exists (the default)
+pub const EX_NOCLOBBER: c_int = 0x0004;
+/// `ex_create`: overwrite the file if it exists
+pub const EX_CLOBBER: c_int = 0x0008;
+/// `ex_create`: classic NetCDF-3 format
+pub const EX_NORMAL_MODEL: c_int = 0x0010;
+/// `ex_create`: NetCDF-3 format with 64-bit offsets
+pub const EX_64BIT_OFFSET: c_int = 0x0020;
+/// `ex_create`: HDF5-based NetCDF-4 format
+pub const EX_NETCDF4: c_int = 0x0040;
+/// `ex_create`: CDF-5 format
+pub const EX_64BIT_DATA: c_int = 0x400000;
+/// `ex_create`: store maps as 64-bit integers
+pub const EX_MAPS_INT64_DB: c_int = 0x0400;
+/// `ex_create`: store entity IDs as 64-bit integers
+pub const EX_IDS_INT64_DB: c_int = 0x0800;
+/// `ex_create`: store bulk integer data as 64-bit integers
+pub const EX_BULK_INT64_DB: c_int = 0x1000;
+/// `ex_create`: store all integer data as 64-bit integers
+pub const EX_ALL_INT64_DB: c_int = 0x1C00;
+/// Maps are passed as `int64_t`
+pub const EX_MAPS_INT64_API: c_int = 0x2000;
+/// Entity IDs and properties are passed as `int64_t`
+pub const EX_IDS_INT64_API: c_int = 0x4000;
+/// Counts, connectivity and set entries are passed as `int64_t`
+pub const EX_BULK_INT64_API: c_int = 0x8000;
+/// `ex_inquire` returns `int64_t`
+pub const EX_INQ_INT64_API: c_int = 0x10000;
+/// All integers are passed as `int64_t`
+pub const EX_ALL_INT64_API: c_int = 0x1E000;
+
+/// Options kept for each open file
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HandleOptions {
+    /// Compute word size: 4 for `float` buffers, 8 for `double`
+    pub(crate) comp_ws: usize,
+    /// `EX_*_INT64_API` flags
+    pub(crate) int64_status: c_int,
+    /// Length of names returned to the caller
+    pub(crate) max_name_length: usize,
+}
+
+impl HandleOptions {
+    /// Whether integers of a kind are passed as `int64_t`
+    pub(crate) fn int64(&self, flag: c_int) -> bool {
+        self.int64_status & flag != 0
+    }
+}
+
+static OPTIONS: Mutex<BTreeMap<c_int, HandleOptions>> = Mutex::new(BTreeMap::new());
+
+fn all_options() -> std::sync::MutexGuard<'static, BTreeMap<c_int, HandleOptions>> {
+    OPTIONS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Options of an open file
+pub(crate) fn options(exoid: c_int) -> Result<HandleOptions> {
+    all_options()
+        .get(&exoid)
+        .copied()
+        .ok_or(ExodusError::InvalidHandle(exoid))
+}
+
+fn update_options(exoid: c_int, f: impl FnOnce(&mut HandleOptions)) -> Result<HandleOptions> {
+    let mut all = all_options();
+    let options = all
+        .get_mut(&exoid)
+        .ok_or(ExodusError::InvalidHandle(exoid))?;
+    let previous = *options;
+    f(options);
+    Ok(previous)
+}
+
+/// Compute word size requested by the caller, 0 meaning `sizeof(float)`
+unsafe fn compute_word_size(comp_ws: *mut c_int) -> Result<usize> {
+    match if comp_ws.is_null() { 0 } else { *comp_ws } {
+        0 | 4 => Ok(4),
+        8 => Ok(8),
+        other => Err(ExodusError::Other(format!(
+            "Invalid compute word size {}: expected 4 or 8",
+            other
+        ))),
+    }
+}
+
+/// Create a new file (`ex_create`)
+///
+/// `*comp_ws` is the size of floating-point values passed to the library
+/// (0 is replaced with 4) and `*io_ws` the size stored in the file (0 is
+/// replaced with `*comp_ws`). Returns the file ID, or `EX_FATAL`.
+#[no_mangle]
+pub unsafe extern "C" fn ex_create_int(
+    rel_path: *const c_char,
+    cmode: c_int,
+    comp_ws: *mut c_int,
+    io_ws: *mut c_int,
+    _run_version: c_int,
+) -> c_int {
+    guard("ex_create", EX_FATAL, || {
+        let path = read_string(rel_path, "path")?;
+        let comp = compute_word_size(comp_ws)?;
+        let io = match if io_ws.is_null() { 0 } else { *io_ws } {
+            0 => comp as c_int,
+            io => io,
+        };
+        let exoid = raw::ex_create(path, cmode, io)?;
+        if !comp_ws.is_null() {
+            *comp_ws = comp as c_int;
+        }
+        if !io_ws.is_null() {
+            *io_ws = io;
+        }
+        all_options().insert(
+            exoid,
+            HandleOptions {
+                comp_ws: comp,
+                int64_status: cmode & EX_ALL_INT64_API,
+                max_name_length: MAX_NAME_LENGTH as usize,
+            },
+        );
+        Ok(exoid)
+    })
+}
+
+/// Open an existing file (`ex_open`)
+///
+/// `mode` is `EX_READ` or `EX_WRITE`, optionally with `EX_*_INT64_API`
+/// flags. On return `*io_ws` is the size of floating-point values stored in
+/// the file and `*version` the file's format version. Returns the file ID,
+/// or `EX_FATAL`.
+#[no_mangle]
+pub unsafe extern "C" fn ex_open_int(
+    rel_path: *const c_char,
+    mode: c_int,
+    comp_ws: *mut c_int,
+    io_ws: *mut c_int,
+    version: *mut f32,
+    _run_version: c_int,
+) -> c_int {
+    guard("ex_open", EX_FATAL, || {
+        if mode & EX_READ != 0 && mode & EX_WRITE != 0 {
+            return Err(ExodusError::InvalidMode(
+                "cannot specify both EX_READ and EX_WRITE".into(),
+            ));
+        }
+        let path = read_string(rel_path, "path")?;
+        let comp = compute_word_size(comp_ws)?;
+        let exoid = raw::ex_open(path, mode)?;
+        let info = raw::ex_inquire_int(exoid, ExInquiry::DbFloatSize)
+            .and_then(|io| Ok((io, raw::ex_inquire_float(exoid, ExInquiry::DbVers)?)));
+        let (io, db_version) = match info {
+            Ok(info) => info,
+            Err(err) => {
+                let _ = raw::ex_close(exoid);
+                return Err(err);
+            }
+        };
+        if !comp_ws.is_null() {
+            *comp_ws = comp as c_int;
+        }
+        if !io_ws.is_null() {
+            *io_ws = io as c_int;
+        }
+        if !version.is_null() {
+            *version = db_version;
+        }
+        all_options().insert(
+            exoid,
+            HandleOptions {
+                comp_ws: comp,
+                int64_status: mode & EX_ALL_INT64_API,
+                max_name_length: MAX_NAME_LENGTH as usize,
+            },
+        );
+        Ok(exoid)
+    })
+}
+
+/// Close a file (`ex_close`)
+#[no_mangle]
+pub extern "C" fn ex_close(exoid: c_int) -> c_int {
+    status("ex_close", || {
+        all_options().remove(&exoid);
+        raw::ex_close(exoid)
+    })
+}
+
+/// Flush pending writes to disk (`ex_update`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub extern "C" fn ex_update(exoid: c_int) -> c_int {
+    status("ex_update", || raw::ex_update(exoid))
+}
+
+/// `EX_*_INT64_API` flags of a file (`ex_int64_status`)
+#[no_mangle]
+pub extern "C" fn ex_int64_status(exoid: c_int) -> u32 {
+    guard("ex_int64_status", 0, || {
+        Ok(options(exoid)?.int64_status as u32)
+    })
+}
+
+/// Set the `EX_*_INT64_API` flags of a file (`ex_set_int64_status`),
+/// returning the previous flags
+#[no_mangle]
+pub extern "C" fn ex_set_int64_status(exoid: c_int, mode: c_int) -> c_int {
+    guard("ex_set_int64_status", EX_FATAL, || {
+        let previous = update_options(exoid, |options| {
+            options.int64_status = mode & EX_ALL_INT64_API;
+        })?;
+        Ok(previous.int64_status)
+    })
+}
+
+/// Set the length of names returned by the library (`ex_set_max_name_length`)
+#[no_mangle]
+pub extern "C" fn ex_set_max_name_length(exoid: c_int, length: c_int) -> c_int {
+    status("ex_set_max_name_length", || {
+        let length = usize::try_from(length)
+            .ok()
+            .filter(|&length| length > 0)
+            .ok_or_else(|| ExodusError::Other(format!("Invalid name length {}", length)))?;
+        update_options(exoid, |options| options.max_name_length = length)?;
+        Ok(())
+    })
+}
+
+// ============================================================================
+// Inquiry
+// ============================================================================
+
+/// Quantity queried with `ex_inquire` (`EX_INQ_*`)
+pub type ex_inquiry = c_int;
+
+/// Version of the library (float)
+pub const EX_INQ_API_VERS: ex_inquiry = 2;
+/// Version of the file format (float)
+pub const EX_INQ_DB_VERS: ex_inquiry = 3;
+/// Title (string)
+pub const EX_INQ_TITLE: ex_inquiry = 4;
+/// Number of dimensions
+pub const EX_INQ_DIM: ex_inquiry = 5;
+/// Number of nodes
+pub const EX_INQ_NODES: ex_inquiry = 6;
+/// Number of elements
+pub const EX_INQ_ELEM: ex_inquiry = 7;
+/// Number of element blocks
+pub const EX_INQ_ELEM_BLK: ex_inquiry = 8;
+/// Number of node sets
+pub const EX_INQ_NODE_SETS: ex_inquiry = 9;
+/// Total length of the node set node lists
+pub const EX_INQ_NS_NODE_LEN: ex_inquiry = 10;
+/// Number of side sets
+pub const EX_INQ_SIDE_SETS: ex_inquiry = 11;
+/// Total length of the side set element lists
+pub const EX_INQ_SS_ELEM_LEN: ex_inquiry = 13;
+/// Number of QA records
+pub const EX_INQ_QA: ex_inquiry = 14;
+/// Number of info records
+pub const EX_INQ_INFO: ex_inquiry = 15;
+/// Number of time steps
+pub const EX_INQ_TIME: ex_inquiry = 16;
+/// Total length of the node set distribution factor lists
+pub const EX_INQ_NS_DF_LEN: ex_inquiry = 20;
+/// Total length of the side set distribution factor lists
+pub const EX_INQ_SS_DF_LEN: ex_inquiry = 21;
+/// Version of the library (float)
+pub const EX_INQ_LIB_VERS: ex_inquiry = 22;
+/// Number of element maps
+pub const EX_INQ_ELEM_MAP: ex_inquiry = 25;
+/// Number of node maps
+pub const EX_INQ_NODE_MAP: ex_inquiry = 26;
+/// Number of edges
+pub const EX_INQ_EDGE: ex_inquiry = 27;
+/// Number of edge blocks
+pub const EX_INQ_EDGE_BLK: ex_inquiry = 28;
+/// Number of edge sets
+pub const EX_INQ_EDGE_SETS: ex_inquiry = 29;
+/// Total length of the edge set lists
+pub const EX_INQ_ES_LEN: ex_inquiry = 30;
+/// Total length of the edge set distribution factor lists
+pub const EX_INQ_ES_DF_LEN: ex_inquiry = 31;
+/// Number of faces
+pub const EX_INQ_FACE: ex_inquiry = 34;
+/// Number of face blocks
+pub const EX_INQ_FACE_BLK: ex_inquiry = 35;
+/// Number of face sets
+pub const EX_INQ_FACE_SETS: ex_inquiry = 36;
+/// Total length of the face set lists
+pub const EX_INQ_FS_LEN: ex_inquiry = 37;
+/// Total length of the face set distribution factor lists
+pub const EX_INQ_FS_DF_LEN: ex_inquiry = 38;
+/// Number of element sets
+pub const EX_INQ_ELEM_SETS: ex_inquiry = 41;
+/// Total length of the element set lists
+pub const EX_INQ_ELS_LEN: ex_inquiry = 42;
+/// Total length of the element set distribution factor lists
+pub const EX_INQ_ELS_DF_LEN: ex_inquiry = 43;
+/// Number of edge maps
+pub const EX_INQ_EDGE_MAP: ex_inquiry = 45;
+/// Number of face maps
+pub const EX_INQ_FACE_MAP: ex_inquiry = 46;
+/// Length of names returned by the library
+pub const EX_INQ_MAX_READ_NAME_LENGTH: ex_inquiry = 50;
+/// Size in bytes of stored floating-point values
+pub const EX_INQ_DB_FLOAT_SIZE: ex_inquiry = 51;
+/// Number of assemblies
+pub const EX_INQ_ASSEMBLY: ex_inquiry = 60;
+/// Number of blobs
+pub const EX_INQ_BLOB: ex_inquiry = 61;
+/// Number of nodal variables
+pub const EX_INQ_NUM_NODE_VAR: ex_inquiry = 62;
+/// Number of edge block variables
+pub const EX_INQ_NUM_EDGE_BLOCK_VAR: ex_inquiry = 63;
+/// Number of face block variables
+pub const EX_INQ_NUM_FACE_BLOCK_VAR: ex_inquiry = 64;
+/// Number of element block variables
+pub const EX_INQ_NUM_ELEM_BLOCK_VAR: ex_inquiry = 65;
+/// Number of node set variables
+pub const EX_INQ_NUM_NODE_SET_VAR: ex_inquiry = 66;
+/// Number of edge set variables
+pub const EX_INQ_NUM_EDGE_SET_VAR: ex_inquiry = 67;
+/// Number of face set variables
+pub const EX_INQ_NUM_FACE_SET_VAR: ex_inquiry = 68;
+/// Number of element set variables
+pub const EX_INQ_NUM_ELEM_SET_VAR: ex_inquiry = 69;
+/// Number of side set variables
+pub const EX_INQ_NUM_SIDE_SET_VAR: ex_inquiry = 70;
+/// Number of global variables
+pub const EX_INQ_NUM_GLOBAL_VAR: ex_inquiry = 71;
+
+/// Rust inquiry of a C inquiry
+fn inquiry(req_info: ex_inquiry) -> Result<ExInquiry> {
+    use ExInquiry::*;
+
+    Ok(match req_info {
+        EX_INQ_API_VERS | EX_INQ_LIB_VERS => ApiVers,
+        EX_INQ_DB_VERS => DbVers,
+        EX_INQ_DIM => Dim,
+        EX_INQ_NODES => Nodes,
+        EX_INQ_ELEM => Elem,
+        EX_INQ_ELEM_BLK => ElemBlk,
+        EX_INQ_NODE_SETS => NodeSets,
+        EX_INQ_NS_NODE_LEN => NsNodeLen,
+        EX_INQ_SIDE_SETS => SideSets,
+        EX_INQ_SS_ELEM_LEN => SsElemLen,
+        EX_INQ_QA => Qa,
+        EX_INQ_INFO => Info,
+        EX_INQ_TIME => Time,
+        EX_INQ_NS_DF_LEN => NsDfLen,
+        EX_INQ_SS_DF_LEN => SsDfLen,
+        EX_INQ_ELEM_MAP => ElemMap,
+        EX_INQ_NODE_MAP => NodeMap,
+        EX_INQ_EDGE => Edge,
+        EX_INQ_EDGE_BLK => EdgeBlk,
+        EX_INQ_EDGE_SETS => EdgeSets,
+        EX_INQ_ES_LEN => EsLen,
+        EX_INQ_ES_DF_LEN => EsDfLen,
+        EX_INQ_FACE => Face,
+        EX_INQ_FACE_BLK => FaceBlk,
+        EX_INQ_FACE_SETS => FaceSets,
+        EX_INQ_FS_LEN => FsLen,
+        EX_INQ_FS_DF_LEN => FsDfLen,
+        EX_INQ_ELEM_SETS => ElemSets,
+        EX_INQ_ELS_LEN => ElsLen,
+        EX_INQ_ELS_DF_LEN => ElsDfLen,
+        EX_INQ_EDGE_MAP => EdgeMap,
+        EX_INQ_FACE_MAP => FaceMap,
+        EX_INQ_DB_FLOAT_SIZE => DbFloatSize,
+        EX_INQ_ASSEMBLY => Assembly,
+        EX_INQ_BLOB => Blob,
+        EX_INQ_NUM_NODE_VAR => NumNodeVar,
+        EX_INQ_NUM_EDGE_BLOCK_VAR => NumEdgeBlockVar,
+        EX_INQ_NUM_FACE_BLOCK_VAR => NumFaceBlockVar,
+        EX_INQ_NUM_ELEM_BLOCK_VAR => NumElemBlockVar,
+        EX_INQ_NUM_NODE_SET_VAR => NumNodeSetVar,
+        EX_INQ_NUM_EDGE_SET_VAR => NumEdgeSetVar,
+        EX_INQ_NUM_FACE_SET_VAR => NumFaceSetVar,
+        EX_INQ_NUM_ELEM_SET_VAR => NumElemSetVar,
+        EX_INQ_NUM_SIDE_SET_VAR => NumSideSetVar,
+        EX_INQ_NUM_GLOBAL_VAR => NumGlobalVar,
+        _ => {
+            return Err(ExodusError::Other(format!(
+                "Unsupported ex_inquiry {}",
+                req_info
+            )))
+        }
+    })
+}
+
+/// Query a property of a file (`ex_inquire`)
+///
+/// Integer quantities are written to `ret_int`, versions to `ret_float` and
+/// the title to `ret_char`; the other pointers may be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn ex_inquire(
+    exoid: c_int,
+    req_info: ex_inquiry,
+    ret_int: *mut void_int,
+    ret_float: *mut f32,
+    ret_char: *mut c_char,
+) -> c_int {
+    status("ex_inquire", || {
+        let options = options(exoid)?;
+        let int64 = options.int64(EX_INQ_INT64_API);
+        match req_info {
+            EX_INQ_TITLE => {
+                let title = raw::ex_get_init(exoid)?.title;
+                write_string(ret_char, &title, MAX_LINE_LENGTH as usize, "ret_char")
+            }
+            EX_INQ_MAX_READ_NAME_LENGTH => {
+                write_ints(ret_int, &[options.max_name_length as i64], int64, "ret_int")
+            }
+            EX_INQ_API_VERS | EX_INQ_LIB_VERS | EX_INQ_DB_VERS => {
+                let value = raw::ex_inquire_float(exoid, inquiry(req_info)?)?;
+                if ret_float.is_null() {
+                    return Err(ExodusError::Other("ret_float is NULL".into()));
+                }
+                *ret_float = value;
+                Ok(())
+            }
+            _ => {
+                let value = raw::ex_inquire_int(exoid, inquiry(req_info)?)?;
+                write_ints(ret_int, &[value], int64, "ret_int")
+            }
+        }
+    })
+}
+
+/// Query an integer property of a file (`ex_inquire_int`)
+///
+/// Returns the value, or `EX_FATAL` on error.
+#[no_mangle]
+pub extern "C" fn ex_inquire_int(exoid: c_int, req_info: ex_inquiry) -> i64 {
+    guard("ex_inquire_int", EX_FATAL as i64, || {
+        if req_info == EX_INQ_MAX_READ_NAME_LENGTH {
+            return Ok(options(exoid)?.max_name_length as i64);
+        }
+        raw::ex_inquire_int(exoid, inquiry(req_info)?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+    use std::path::Path;
+
+    fn data_path(name: &str) -> CString {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("data")
+            .join(name);
+        CString::new(path.to_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_open_inquire_close() {
+        let path = data_path("two_blocks.e");
+        let (mut comp_ws, mut io_ws, mut version) = (8, 0, 0.0f32);
+        let exoid = unsafe {
+            ex_open_int(
+                path.as_ptr(),
+                EX_READ | EX_INQ_INT64_API,
+                &mut comp_ws,
+                &mut io_ws,
+                &mut version,
+                crate::EX_API_VERS_NODOT,
+            )
+        };
+        assert!(exoid > 0);
+        assert_eq!(comp_ws, 8);
+        assert!(io_ws == 4 || io_ws == 8);
+        assert!(version >= 2.0);
+        assert_eq!(ex_int64_status(exoid), EX_INQ_INT64_API as u32);
+
+        assert_eq!(ex_inquire_int(exoid, EX_INQ_NODES), 2541);
+        assert_eq!(ex_inquire_int(exoid, EX_INQ_ELEM_BLK), 2);
+        let mut num_elem = 0i64;
+        let status = unsafe {
+            ex_inquire(
+                exoid,
+                EX_INQ_ELEM,
+                (&mut num_elem as *mut i64).cast(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        };
+        assert_eq!(status, crate::EX_NOERR);
+        assert_eq!(num_elem, 2000);
+
+        assert_eq!(ex_set_max_name_length(exoid, 64), crate::EX_NOERR);
+        assert_eq!(ex_inquire_int(exoid, EX_INQ_MAX_READ_NAME_LENGTH), 64);
+        assert_eq!(ex_set_int64_status(exoid, 0), EX_INQ_INT64_API);
+
+        assert_eq!(ex_close(exoid), crate::EX_NOERR);
+        assert_eq!(ex_close(exoid), EX_FATAL);
+        let mut code = 0;
+        unsafe { crate::ex_get_err(std::ptr::null_mut(), std::ptr::null_mut(), &mut code) };
+        assert_eq!(code, crate::EX_BADFILEID);
+    }
+
+    #[test]
+    fn test_open_errors() {
+        let path = data_path("absent.e");
+        let mut comp_ws = 0;
+        let exoid = unsafe {
+            ex_open_int(
+                path.as_ptr(),
+                EX_READ,
+                &mut comp_ws,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+        assert_eq!(exoid, EX_FATAL);
+
+        let path = data_path("two_blocks.e");
+        let exoid = unsafe {
+            ex_open_int(
+                path.as_ptr(),
+                EX_READ | EX_WRITE,
+                &mut comp_ws,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+        assert_eq!(exoid, EX_FATAL);
+        let mut code = 0;
+        unsafe { crate::ex_get_err(std::ptr::null_mut(), std::ptr::null_mut(), &mut code) };
+        assert_eq!(code, crate::EX_BADFILEMODE);
+    }
+}
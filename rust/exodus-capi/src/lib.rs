@@ -0,0 +1,57 @@
+//! C ABI for exodus-rs
+//!
+//! This crate builds `libexodus` (`cdylib` and `staticlib`) exporting the
+//! core of the SEACAS Exodus II C API, implemented on top of
+//! [`exodus_rs::raw`]. Legacy C and Fortran solvers can link it in place of
+//! the C library:
+//!
+//! ```sh
+//! cargo build --release -p exodus-capi
+//! cc solver.c -DEXODUS_RS_NETCDF4 -I rust/exodus-capi/include -L rust/target/release -lexodus
+//! ```
+//!
+//! The header is generated by cbindgen when the crate is built;
+//! `include/exodusII.h` is a committed copy. It declares the writing
+//! functions only when `EXODUS_RS_NETCDF4` is defined, which builds without
+//! the `netcdf4` feature must leave out.
+//!
+//! # Conventions
+//!
+//! The functions follow the C library:
+//!
+//! - Functions return [`EX_NOERR`] on success and [`EX_FATAL`] on failure.
+//!   The failure is recorded per thread and can be retrieved with
+//!   [`ex_get_err`]; it is printed to stderr when [`EX_VERBOSE`] is set with
+//!   [`ex_opts`].
+//! - Floating-point buffers hold `float` or `double` according to the
+//!   compute word size given to `ex_create`/`ex_open`.
+//! - Integer buffers (`void_int`) hold `int` unless the matching
+//!   `EX_*_INT64_API` flag was given at creation, open or through
+//!   [`ex_set_int64_status`].
+//! - Output strings are copied into caller-provided buffers, truncated to
+//!   the usual lengths (`MAX_STR_LENGTH`, `MAX_LINE_LENGTH` or the name
+//!   length set with [`ex_set_max_name_length`]).
+//!
+//! Without the `netcdf4` feature only the reading functions are exported.
+//!
+//! # Safety
+//!
+//! Every exported function is `unsafe`: pointers must be null or valid for
+//! the number of values the C API documents for the call, exactly as with
+//! the C library. Rust panics are caught and reported as [`EX_INTERNAL`].
+
+#![allow(non_camel_case_types)]
+#![allow(clippy::missing_safety_doc)]
+#![cfg_attr(not(feature = "netcdf4"), allow(dead_code, unused_imports))]
+
+mod error;
+mod ffi;
+mod file;
+mod mesh;
+mod results;
+
+pub use error::*;
+pub use ffi::*;
+pub use file::*;
+pub use mesh::*;
+pub use results::*;
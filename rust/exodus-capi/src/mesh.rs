@@ -0,0 +1,861 @@
+//! Model description: initialization, coordinates, blocks, sets, maps,
+//! names, properties, QA and info records
+
+use crate::error::status;
+use crate::ffi::{
+    entity_type, ex_entity_id, ex_entity_type, ex_init_params, read_string, void_int, write_ints,
+    write_reals, write_string, write_strings, MAX_LINE_LENGTH, MAX_STR_LENGTH,
+};
+use crate::file::{options, EX_BULK_INT64_API, EX_IDS_INT64_API, EX_MAPS_INT64_API};
+use exodus_rs::raw;
+use exodus_rs::{EntityType, ExodusError};
+use std::os::raw::{c_char, c_int, c_void};
+
+#[cfg(feature = "netcdf4")]
+use crate::ffi::{count, entity_count, read_ints, read_reals, read_strings};
+#[cfg(feature = "netcdf4")]
+use exodus_rs::QaRecord;
+
+/// Entries in each QA record: code name, code version, date and time
+const QA_FIELDS: usize = 4;
+
+// ============================================================================
+// Initialization
+// ============================================================================
+
+/// Read the title and main counts of a model (`ex_get_init`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_init(
+    exoid: c_int,
+    title: *mut c_char,
+    num_dim: *mut void_int,
+    num_nodes: *mut void_int,
+    num_elem: *mut void_int,
+    num_elem_blk: *mut void_int,
+    num_node_sets: *mut void_int,
+    num_side_sets: *mut void_int,
+) -> c_int {
+    status("ex_get_init", || {
+        let int64 = options(exoid)?.int64(EX_BULK_INT64_API);
+        let params = raw::ex_get_init(exoid)?;
+        write_string(title, &params.title, MAX_LINE_LENGTH as usize, "title")?;
+        write_ints(num_dim, &[params.num_dim as i64], int64, "num_dim")?;
+        write_ints(num_nodes, &[params.num_nodes as i64], int64, "num_nodes")?;
+        write_ints(num_elem, &[params.num_elems as i64], int64, "num_elem")?;
+        write_ints(
+            num_elem_blk,
+            &[params.num_elem_blocks as i64],
+            int64,
+            "num_elem_blk",
+        )?;
+        write_ints(
+            num_node_sets,
+            &[params.num_node_sets as i64],
+            int64,
+            "num_node_sets",
+        )?;
+        write_ints(
+            num_side_sets,
+            &[params.num_side_sets as i64],
+            int64,
+            "num_side_sets",
+        )
+    })
+}
+
+/// Read all model counts (`ex_get_init_ext`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_init_ext(exoid: c_int, info: *mut ex_init_params) -> c_int {
+    status("ex_get_init_ext", || {
+        let params = raw::ex_get_init(exoid)?;
+        if info.is_null() {
+            return Err(ExodusError::Other("info is NULL".into()));
+        }
+        *info = ex_init_params::from_params(&params);
+        Ok(())
+    })
+}
+
+/// Write the title and main counts of a model (`ex_put_init`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_init(
+    exoid: c_int,
+    title: *const c_char,
+    num_dim: i64,
+    num_nodes: i64,
+    num_elem: i64,
+    num_elem_blk: i64,
+    num_node_sets: i64,
+    num_side_sets: i64,
+) -> c_int {
+    status("ex_put_init", || {
+        raw::ex_put_init(
+            exoid,
+            &read_string(title, "title")?,
+            count(num_dim, "num_dim")?,
+            count(num_nodes, "num_nodes")?,
+            count(num_elem, "num_elem")?,
+            count(num_elem_blk, "num_elem_blk")?,
+            count(num_node_sets, "num_node_sets")?,
+            count(num_side_sets, "num_side_sets")?,
+        )
+    })
+}
+
+/// Write all model counts (`ex_put_init_ext`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_init_ext(exoid: c_int, model: *const ex_init_params) -> c_int {
+    status("ex_put_init_ext", || {
+        let model = model
+            .as_ref()
+            .ok_or_else(|| ExodusError::Other("model is NULL".into()))?;
+        raw::ex_put_init_ext(exoid, &model.to_params()?)
+    })
+}
+
+// ============================================================================
+// Coordinates
+// ============================================================================
+
+/// Read the nodal coordinates (`ex_get_coord`)
+///
+/// Each of the arrays may be NULL to skip that component.
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_coord(
+    exoid: c_int,
+    x_coor: *mut c_void,
+    y_coor: *mut c_void,
+    z_coor: *mut c_void,
+) -> c_int {
+    status("ex_get_coord", || {
+        let comp_ws = options(exoid)?.comp_ws;
+        let (x, y, z) = raw::ex_get_coord(exoid)?;
+        for (ptr, values, what) in [
+            (x_coor, x, "x_coor"),
+            (y_coor, y, "y_coor"),
+            (z_coor, z, "z_coor"),
+        ] {
+            if !ptr.is_null() && !values.is_empty() {
+                write_reals(ptr, &values, comp_ws, what)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Write the nodal coordinates (`ex_put_coord`)
+///
+/// `y_coor` and `z_coor` may be NULL for models of lower dimension.
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_coord(
+    exoid: c_int,
+    x_coor: *const c_void,
+    y_coor: *const c_void,
+    z_coor: *const c_void,
+) -> c_int {
+    status("ex_put_coord", || {
+        let comp_ws = options(exoid)?.comp_ws;
+        let num_nodes = raw::ex_get_init(exoid)?.num_nodes;
+        let component = |ptr: *const c_void, what| {
+            if ptr.is_null() {
+                Ok(None)
+            } else {
+                read_reals(ptr, num_nodes, comp_ws, what).map(Some)
+            }
+        };
+        let x = read_reals(x_coor, num_nodes, comp_ws, "x_coor")?;
+        let y = component(y_coor, "y_coor")?;
+        let z = component(z_coor, "z_coor")?;
+        raw::ex_put_coord(exoid, &x, y.as_deref(), z.as_deref())
+    })
+}
+
+/// Read the coordinate names (`ex_get_coord_names`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_coord_names(
+    exoid: c_int,
+    coord_names: *const *mut c_char,
+) -> c_int {
+    status("ex_get_coord_names", || {
+        let max_len = options(exoid)?.max_name_length;
+        let names = raw::ex_get_coord_names(exoid)?;
+        write_strings(coord_names, &names, max_len, "coord_names")
+    })
+}
+
+/// Write the coordinate names, one per dimension (`ex_put_coord_names`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_coord_names(
+    exoid: c_int,
+    coord_names: *const *mut c_char,
+) -> c_int {
+    status("ex_put_coord_names", || {
+        let num_dim = raw::ex_get_init(exoid)?.num_dim;
+        let names = read_strings(coord_names, num_dim, "coord_names")?;
+        raw::ex_put_coord_names(exoid, &as_strs(&names))
+    })
+}
+
+// ============================================================================
+// Blocks
+// ============================================================================
+
+/// Read the IDs of the blocks, sets, assemblies or blobs of a type
+/// (`ex_get_ids`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_ids(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    ids: *mut void_int,
+) -> c_int {
+    status("ex_get_ids", || {
+        let int64 = options(exoid)?.int64(EX_IDS_INT64_API);
+        let values = raw::ex_get_ids(exoid, entity_type(obj_type)?)?;
+        write_ints(ids, &values, int64, "ids")
+    })
+}
+
+/// Read the parameters of a block (`ex_get_block`)
+///
+/// Any of the output arguments may be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_block(
+    exoid: c_int,
+    blk_type: ex_entity_type,
+    blk_id: ex_entity_id,
+    elem_type: *mut c_char,
+    num_entries_this_blk: *mut void_int,
+    num_nodes_per_entry: *mut void_int,
+    num_edges_per_entry: *mut void_int,
+    num_faces_per_entry: *mut void_int,
+    num_attr_per_entry: *mut void_int,
+) -> c_int {
+    status("ex_get_block", || {
+        let int64 = options(exoid)?.int64(EX_BULK_INT64_API);
+        let block = raw::ex_get_block(exoid, entity_type(blk_type)?, blk_id)?;
+        if !elem_type.is_null() {
+            write_string(
+                elem_type,
+                &block.topology,
+                MAX_STR_LENGTH as usize,
+                "elem_type",
+            )?;
+        }
+        for (ptr, value, what) in [
+            (
+                num_entries_this_blk,
+                block.num_entries,
+                "num_entries_this_blk",
+            ),
+            (
+                num_nodes_per_entry,
+                block.num_nodes_per_entry,
+                "num_nodes_per_entry",
+            ),
+            (
+                num_edges_per_entry,
+                block.num_edges_per_entry,
+                "num_edges_per_entry",
+            ),
+            (
+                num_faces_per_entry,
+                block.num_faces_per_entry,
+                "num_faces_per_entry",
+            ),
+            (
+                num_attr_per_entry,
+                block.num_attributes,
+                "num_attr_per_entry",
+            ),
+        ] {
+            if !ptr.is_null() {
+                write_ints(ptr, &[value as i64], int64, what)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Define a block (`ex_put_block`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_block(
+    exoid: c_int,
+    blk_type: ex_entity_type,
+    blk_id: ex_entity_id,
+    entry_descrip: *const c_char,
+    num_entries_this_blk: i64,
+    num_nodes_per_entry: i64,
+    num_edges_per_entry: i64,
+    num_faces_per_entry: i64,
+    num_attr_per_entry: i64,
+) -> c_int {
+    status("ex_put_block", || {
+        raw::ex_put_block(
+            exoid,
+            entity_type(blk_type)?,
+            blk_id,
+            &read_string(entry_descrip, "entry_descrip")?,
+            count(num_entries_this_blk, "num_entries_this_blk")?,
+            count(num_nodes_per_entry, "num_nodes_per_entry")?,
+            count(num_edges_per_entry, "num_edges_per_entry")?,
+            count(num_faces_per_entry, "num_faces_per_entry")?,
+            count(num_attr_per_entry, "num_attr_per_entry")?,
+        )
+    })
+}
+
+/// Read the node connectivity of an element block (`ex_get_conn`)
+///
+/// Edge and face connectivity are not stored; `edgeconn` and `faceconn`
+/// must be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_conn(
+    exoid: c_int,
+    blk_type: ex_entity_type,
+    blk_id: ex_entity_id,
+    nodeconn: *mut void_int,
+    edgeconn: *mut void_int,
+    faceconn: *mut void_int,
+) -> c_int {
+    status("ex_get_conn", || {
+        node_connectivity_only(edgeconn, faceconn)?;
+        let int64 = options(exoid)?.int64(EX_BULK_INT64_API);
+        let conn = raw::ex_get_conn(exoid, entity_type(blk_type)?, blk_id)?;
+        write_ints(nodeconn, &conn, int64, "nodeconn")
+    })
+}
+
+/// Write the node connectivity of an element block (`ex_put_conn`)
+///
+/// Edge and face connectivity are not stored; `edgeconn` and `faceconn`
+/// must be NULL.
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_conn(
+    exoid: c_int,
+    blk_type: ex_entity_type,
+    blk_id: ex_entity_id,
+    nodeconn: *const void_int,
+    edgeconn: *const void_int,
+    faceconn: *const void_int,
+) -> c_int {
+    status("ex_put_conn", || {
+        node_connectivity_only(edgeconn, faceconn)?;
+        let int64 = options(exoid)?.int64(EX_BULK_INT64_API);
+        let blk_type = entity_type(blk_type)?;
+        let block = raw::ex_get_block(exoid, blk_type, blk_id)?;
+        let len = block.num_entries * block.num_nodes_per_entry;
+        let conn = read_ints(nodeconn, len, int64, "nodeconn")?;
+        raw::ex_put_conn(exoid, blk_type, blk_id, &conn)
+    })
+}
+
+fn node_connectivity_only(
+    edgeconn: *const void_int,
+    faceconn: *const void_int,
+) -> exodus_rs::Result<()> {
+    if edgeconn.is_null() && faceconn.is_null() {
+        Ok(())
+    } else {
+        Err(ExodusError::UnsupportedFeature(
+            "edge and face connectivity of blocks".into(),
+        ))
+    }
+}
+
+/// Read the attributes of an element block (`ex_get_attr`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_attr(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    obj_id: ex_entity_id,
+    attrib: *mut c_void,
+) -> c_int {
+    status("ex_get_attr", || {
+        let comp_ws = options(exoid)?.comp_ws;
+        let values = raw::ex_get_attr(exoid, entity_type(obj_type)?, obj_id)?;
+        write_reals(attrib, &values, comp_ws, "attrib")
+    })
+}
+
+/// Write the attributes of an element block (`ex_put_attr`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_attr(
+    exoid: c_int,
+    blk_type: ex_entity_type,
+    blk_id: ex_entity_id,
+    attrib: *const c_void,
+) -> c_int {
+    status("ex_put_attr", || {
+        let comp_ws = options(exoid)?.comp_ws;
+        let blk_type = entity_type(blk_type)?;
+        let block = raw::ex_get_block(exoid, blk_type, blk_id)?;
+        let values = read_reals(
+            attrib,
+            block.num_entries * block.num_attributes,
+            comp_ws,
+            "attrib",
+        )?;
+        raw::ex_put_attr(exoid, blk_type, blk_id, &values)
+    })
+}
+
+/// Read the attribute names of an element block (`ex_get_attr_names`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_attr_names(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    obj_id: ex_entity_id,
+    names: *const *mut c_char,
+) -> c_int {
+    status("ex_get_attr_names", || {
+        let max_len = options(exoid)?.max_name_length;
+        let values = raw::ex_get_attr_names(exoid, entity_type(obj_type)?, obj_id)?;
+        write_strings(names, &values, max_len, "names")
+    })
+}
+
+/// Write the attribute names of an element block (`ex_put_attr_names`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_attr_names(
+    exoid: c_int,
+    blk_type: ex_entity_type,
+    blk_id: ex_entity_id,
+    names: *const *mut c_char,
+) -> c_int {
+    status("ex_put_attr_names", || {
+        let blk_type = entity_type(blk_type)?;
+        let block = raw::ex_get_block(exoid, blk_type, blk_id)?;
+        let values = read_strings(names, block.num_attributes, "names")?;
+        raw::ex_put_attr_names(exoid, blk_type, blk_id, &as_strs(&values))
+    })
+}
+
+// ============================================================================
+// Sets
+// ============================================================================
+
+/// Read the number of entries and distribution factors of a set
+/// (`ex_get_set_param`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_set_param(
+    exoid: c_int,
+    set_type: ex_entity_type,
+    set_id: ex_entity_id,
+    num_entry_in_set: *mut void_int,
+    num_dist_fact_in_set: *mut void_int,
+) -> c_int {
+    status("ex_get_set_param", || {
+        let int64 = options(exoid)?.int64(EX_BULK_INT64_API);
+        let (num_entries, num_df) = raw::ex_get_set_param(exoid, entity_type(set_type)?, set_id)?;
+        write_ints(
+            num_entry_in_set,
+            &[num_entries as i64],
+            int64,
+            "num_entry_in_set",
+        )?;
+        if !num_dist_fact_in_set.is_null() {
+            write_ints(
+                num_dist_fact_in_set,
+                &[num_df as i64],
+                int64,
+                "num_dist_fact_in_set",
+            )?;
+        }
+        Ok(())
+    })
+}
+
+/// Define a set (`ex_put_set_param`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_set_param(
+    exoid: c_int,
+    set_type: ex_entity_type,
+    set_id: ex_entity_id,
+    num_entries_in_set: i64,
+    num_dist_fact_in_set: i64,
+) -> c_int {
+    status("ex_put_set_param", || {
+        raw::ex_put_set_param(
+            exoid,
+            entity_type(set_type)?,
+            set_id,
+            count(num_entries_in_set, "num_entries_in_set")?,
+            count(num_dist_fact_in_set, "num_dist_fact_in_set")?,
+        )
+    })
+}
+
+/// Read the entries of a set (`ex_get_set`)
+///
+/// `set_extra_list` receives the sides of a side set and may be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_set(
+    exoid: c_int,
+    set_type: ex_entity_type,
+    set_id: ex_entity_id,
+    set_entry_list: *mut void_int,
+    set_extra_list: *mut void_int,
+) -> c_int {
+    status("ex_get_set", || {
+        let int64 = options(exoid)?.int64(EX_BULK_INT64_API);
+        let (entries, extra) = raw::ex_get_set(exoid, entity_type(set_type)?, set_id)?;
+        write_ints(set_entry_list, &entries, int64, "set_entry_list")?;
+        if let (false, Some(extra)) = (set_extra_list.is_null(), extra) {
+            write_ints(set_extra_list, &extra, int64, "set_extra_list")?;
+        }
+        Ok(())
+    })
+}
+
+/// Write the entries of a set defined with `ex_put_set_param` (`ex_put_set`)
+///
+/// `set_extra_list` holds the sides of a side set and is ignored for other
+/// sets.
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_set(
+    exoid: c_int,
+    set_type: ex_entity_type,
+    set_id: ex_entity_id,
+    set_entry_list: *const void_int,
+    set_extra_list: *const void_int,
+) -> c_int {
+    status("ex_put_set", || {
+        let int64 = options(exoid)?.int64(EX_BULK_INT64_API);
+        let set_type = entity_type(set_type)?;
+        let (len, _) = raw::ex_get_set_param(exoid, set_type, set_id)?;
+        let entries = read_ints(set_entry_list, len, int64, "set_entry_list")?;
+        let extra = if set_type == EntityType::SideSet && !set_extra_list.is_null() {
+            Some(read_ints(set_extra_list, len, int64, "set_extra_list")?)
+        } else {
+            None
+        };
+        raw::ex_put_set(exoid, set_type, set_id, &entries, extra.as_deref())
+    })
+}
+
+/// Read the distribution factors of a node or side set
+/// (`ex_get_set_dist_fact`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_set_dist_fact(
+    exoid: c_int,
+    set_type: ex_entity_type,
+    set_id: ex_entity_id,
+    set_dist_fact: *mut c_void,
+) -> c_int {
+    status("ex_get_set_dist_fact", || {
+        let comp_ws = options(exoid)?.comp_ws;
+        let values = raw::ex_get_set_dist_fact(exoid, entity_type(set_type)?, set_id)?;
+        write_reals(set_dist_fact, &values, comp_ws, "set_dist_fact")
+    })
+}
+
+// ============================================================================
+// Maps and Names
+// ============================================================================
+
+/// Read the ID map of nodes, edges, faces or elements (`ex_get_id_map`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_id_map(
+    exoid: c_int,
+    map_type: ex_entity_type,
+    map: *mut void_int,
+) -> c_int {
+    status("ex_get_id_map", || {
+        let int64 = options(exoid)?.int64(EX_MAPS_INT64_API);
+        let values = raw::ex_get_id_map(exoid, entity_type(map_type)?)?;
+        write_ints(map, &values, int64, "map")
+    })
+}
+
+/// Write the ID map of nodes, edges, faces or elements (`ex_put_id_map`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_id_map(
+    exoid: c_int,
+    map_type: ex_entity_type,
+    map: *const void_int,
+) -> c_int {
+    status("ex_put_id_map", || {
+        let int64 = options(exoid)?.int64(EX_MAPS_INT64_API);
+        let map_type = entity_type(map_type)?;
+        let params = raw::ex_get_init(exoid)?;
+        let len = match map_type {
+            EntityType::NodeMap => params.num_nodes,
+            EntityType::EdgeMap => params.num_edges,
+            EntityType::FaceMap => params.num_faces,
+            EntityType::ElemMap => params.num_elems,
+            _ => {
+                return Err(ExodusError::InvalidEntityType(format!(
+                    "Expected a map type, got {}",
+                    map_type
+                )))
+            }
+        };
+        let values = read_ints(map, len, int64, "map")?;
+        raw::ex_put_id_map(exoid, map_type, &values)
+    })
+}
+
+/// Read the names of all blocks, sets or maps of a type (`ex_get_names`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_names(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    names: *const *mut c_char,
+) -> c_int {
+    status("ex_get_names", || {
+        let max_len = options(exoid)?.max_name_length;
+        let values = raw::ex_get_names(exoid, entity_type(obj_type)?)?;
+        write_strings(names, &values, max_len, "names")
+    })
+}
+
+/// Write the names of all blocks, sets or maps of a type (`ex_put_names`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_names(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    names: *const *mut c_char,
+) -> c_int {
+    status("ex_put_names", || {
+        let obj_type = entity_type(obj_type)?;
+        let len = entity_count(&raw::ex_get_init(exoid)?, obj_type)?;
+        let values = read_strings(names, len, "names")?;
+        raw::ex_put_names(exoid, obj_type, &as_strs(&values))
+    })
+}
+
+/// Read the name of one block, set or map (`ex_get_name`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_name(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    entity_id: ex_entity_id,
+    name: *mut c_char,
+) -> c_int {
+    status("ex_get_name", || {
+        let max_len = options(exoid)?.max_name_length;
+        let value = raw::ex_get_name(exoid, entity_type(obj_type)?, entity_id)?;
+        write_string(name, &value, max_len, "name")
+    })
+}
+
+/// Write the name of one block, set or map (`ex_put_name`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_name(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    entity_id: ex_entity_id,
+    name: *const c_char,
+) -> c_int {
+    status("ex_put_name", || {
+        raw::ex_put_name(
+            exoid,
+            entity_type(obj_type)?,
+            entity_id,
+            &read_string(name, "name")?,
+        )
+    })
+}
+
+// ============================================================================
+// Properties
+// ============================================================================
+
+/// Read the property names of a type (`ex_get_prop_names`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_prop_names(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    prop_names: *const *mut c_char,
+) -> c_int {
+    status("ex_get_prop_names", || {
+        let max_len = options(exoid)?.max_name_length;
+        let values = raw::ex_get_prop_names(exoid, entity_type(obj_type)?)?;
+        write_strings(prop_names, &values, max_len, "prop_names")
+    })
+}
+
+/// Read a property of one block, set or map (`ex_get_prop`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_prop(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    obj_id: ex_entity_id,
+    prop_name: *const c_char,
+    value: *mut void_int,
+) -> c_int {
+    status("ex_get_prop", || {
+        let int64 = options(exoid)?.int64(EX_IDS_INT64_API);
+        let prop = raw::ex_get_prop(
+            exoid,
+            entity_type(obj_type)?,
+            obj_id,
+            &read_string(prop_name, "prop_name")?,
+        )?;
+        write_ints(value, &[prop], int64, "value")
+    })
+}
+
+/// Write a property of one block, set or map (`ex_put_prop`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_prop(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    obj_id: ex_entity_id,
+    prop_name: *const c_char,
+    value: ex_entity_id,
+) -> c_int {
+    status("ex_put_prop", || {
+        raw::ex_put_prop(
+            exoid,
+            entity_type(obj_type)?,
+            obj_id,
+            &read_string(prop_name, "prop_name")?,
+            value,
+        )
+    })
+}
+
+/// Read a property of all blocks, sets or maps of a type
+/// (`ex_get_prop_array`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_prop_array(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    prop_name: *const c_char,
+    values: *mut void_int,
+) -> c_int {
+    status("ex_get_prop_array", || {
+        let int64 = options(exoid)?.int64(EX_IDS_INT64_API);
+        let props = raw::ex_get_prop_array(
+            exoid,
+            entity_type(obj_type)?,
+            &read_string(prop_name, "prop_name")?,
+        )?;
+        write_ints(values, &props, int64, "values")
+    })
+}
+
+/// Write a property of all blocks, sets or maps of a type
+/// (`ex_put_prop_array`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_prop_array(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    prop_name: *const c_char,
+    values: *const void_int,
+) -> c_int {
+    status("ex_put_prop_array", || {
+        let int64 = options(exoid)?.int64(EX_IDS_INT64_API);
+        let obj_type = entity_type(obj_type)?;
+        let len = entity_count(&raw::ex_get_init(exoid)?, obj_type)?;
+        let props = read_ints(values, len, int64, "values")?;
+        raw::ex_put_prop_array(
+            exoid,
+            obj_type,
+            &read_string(prop_name, "prop_name")?,
+            &props,
+        )
+    })
+}
+
+// ============================================================================
+// QA and Info Records
+// ============================================================================
+
+/// Read the QA records (`ex_get_qa`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_qa(exoid: c_int, qa_record: *mut [*mut c_char; 4]) -> c_int {
+    status("ex_get_qa", || {
+        let records = raw::ex_get_qa(exoid)?;
+        if qa_record.is_null() {
+            return Err(ExodusError::Other("qa_record is NULL".into()));
+        }
+        for (i, record) in records.iter().enumerate() {
+            let fields = [
+                &record.code_name,
+                &record.code_version,
+                &record.date,
+                &record.time,
+            ];
+            for (&dst, value) in (*qa_record.add(i)).iter().zip(fields) {
+                write_string(dst, value, MAX_STR_LENGTH as usize, "qa_record")?;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Write the QA records (`ex_put_qa`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_qa(
+    exoid: c_int,
+    num_qa_records: c_int,
+    qa_record: *mut [*mut c_char; 4],
+) -> c_int {
+    status("ex_put_qa", || {
+        let len = count(num_qa_records as i64, "num_qa_records")?;
+        if qa_record.is_null() && len > 0 {
+            return Err(ExodusError::Other("qa_record is NULL".into()));
+        }
+        let records = (0..len)
+            .map(|i| {
+                let fields = read_strings((*qa_record.add(i)).as_ptr(), QA_FIELDS, "qa_record")?;
+                let [code_name, code_version, date, time]: [String; QA_FIELDS] =
+                    fields.try_into().expect("four QA fields");
+                Ok(QaRecord {
+                    code_name,
+                    code_version,
+                    date,
+                    time,
+                })
+            })
+            .collect::<exodus_rs::Result<Vec<_>>>()?;
+        raw::ex_put_qa(exoid, &records)
+    })
+}
+
+/// Read the information records (`ex_get_info`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_info(exoid: c_int, info: *const *mut c_char) -> c_int {
+    status("ex_get_info", || {
+        let records = raw::ex_get_info(exoid)?;
+        write_strings(info, &records, MAX_LINE_LENGTH as usize, "info")
+    })
+}
+
+/// Write the information records (`ex_put_info`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_info(
+    exoid: c_int,
+    num_info: c_int,
+    info: *const *mut c_char,
+) -> c_int {
+    status("ex_put_info", || {
+        let records = read_strings(info, count(num_info as i64, "num_info")?, "info")?;
+        raw::ex_put_info(exoid, &records)
+    })
+}
+
+/// Borrowed strings for the raw API
+#[cfg(feature = "netcdf4")]
+pub(crate) fn as_strs(values: &[String]) -> Vec<&str> {
+    values.iter().map(String::as_str).collect()
+}
@@ -0,0 +1,314 @@
+//! Variables, truth tables and time steps
+
+use crate::error::status;
+use crate::ffi::{
+    entity_type, ex_entity_id, ex_entity_type, write_reals, write_string, write_strings,
+};
+use crate::file::options;
+use exodus_rs::raw;
+use exodus_rs::{EntityType, ExodusError, Result};
+use std::os::raw::{c_char, c_int, c_void};
+
+#[cfg(feature = "netcdf4")]
+use crate::ffi::{read_ints, read_reals, read_strings};
+#[cfg(feature = "netcdf4")]
+use crate::mesh::as_strs;
+#[cfg(feature = "netcdf4")]
+use exodus_rs::TruthTable;
+
+/// 1-based time step or variable index passed from C
+fn index(value: c_int, what: &str) -> Result<usize> {
+    usize::try_from(value)
+        .map_err(|_| ExodusError::Other(format!("{} is negative: {}", what, value)))
+}
+
+/// Number of values passed for one block or set
+fn entries(value: i64) -> Result<usize> {
+    usize::try_from(value)
+        .map_err(|_| ExodusError::Other(format!("num_entry_this_obj is negative: {}", value)))
+}
+
+// ============================================================================
+// Variable Definitions
+// ============================================================================
+
+/// Read the number of variables of a type (`ex_get_variable_param`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_variable_param(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    num_vars: *mut c_int,
+) -> c_int {
+    status("ex_get_variable_param", || {
+        let count = raw::ex_get_variable_param(exoid, entity_type(obj_type)?)?;
+        if num_vars.is_null() {
+            return Err(ExodusError::Other("num_vars is NULL".into()));
+        }
+        *num_vars = count as c_int;
+        Ok(())
+    })
+}
+
+/// Declare the number of variables of a type (`ex_put_variable_param`)
+///
+/// The variables are defined by `ex_put_variable_names`.
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub extern "C" fn ex_put_variable_param(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    num_vars: c_int,
+) -> c_int {
+    status("ex_put_variable_param", || {
+        raw::ex_put_variable_param(exoid, entity_type(obj_type)?, index(num_vars, "num_vars")?)
+    })
+}
+
+/// Read the names of the first `num_vars` variables of a type
+/// (`ex_get_variable_names`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_variable_names(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    num_vars: c_int,
+    var_names: *const *mut c_char,
+) -> c_int {
+    status("ex_get_variable_names", || {
+        let max_len = options(exoid)?.max_name_length;
+        let names = raw::ex_get_variable_names(exoid, entity_type(obj_type)?)?;
+        let num_vars = index(num_vars, "num_vars")?;
+        if num_vars > names.len() {
+            return Err(ExodusError::InvalidArrayLength {
+                expected: names.len(),
+                actual: num_vars,
+            });
+        }
+        write_strings(var_names, &names[..num_vars], max_len, "var_names")
+    })
+}
+
+/// Read the name of one variable (`ex_get_variable_name`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_variable_name(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    var_num: c_int,
+    var_name: *mut c_char,
+) -> c_int {
+    status("ex_get_variable_name", || {
+        let max_len = options(exoid)?.max_name_length;
+        let name =
+            raw::ex_get_variable_name(exoid, entity_type(obj_type)?, index(var_num, "var_num")?)?;
+        write_string(var_name, &name, max_len, "var_name")
+    })
+}
+
+/// Define the variables of a type by name (`ex_put_variable_names`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_variable_names(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    num_vars: c_int,
+    var_names: *const *mut c_char,
+) -> c_int {
+    status("ex_put_variable_names", || {
+        let names = read_strings(var_names, index(num_vars, "num_vars")?, "var_names")?;
+        raw::ex_put_variable_names(exoid, entity_type(obj_type)?, &as_strs(&names))
+    })
+}
+
+/// Read the truth table of a block or set variable type
+/// (`ex_get_truth_table`)
+///
+/// `var_tab` holds `num_blk` rows of `num_var` flags.
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_truth_table(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    num_blk: c_int,
+    num_var: c_int,
+    var_tab: *mut c_int,
+) -> c_int {
+    status("ex_get_truth_table", || {
+        let table = raw::ex_get_truth_table(exoid, entity_type(obj_type)?)?;
+        check_table_size(
+            &table_size(num_blk, num_var)?,
+            table.num_blocks,
+            table.num_vars,
+        )?;
+        if var_tab.is_null() && !table.table.is_empty() {
+            return Err(ExodusError::Other("var_tab is NULL".into()));
+        }
+        for (i, &exists) in table.table.iter().enumerate() {
+            *var_tab.add(i) = exists as c_int;
+        }
+        Ok(())
+    })
+}
+
+/// Write the truth table of a block or set variable type
+/// (`ex_put_truth_table`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_truth_table(
+    exoid: c_int,
+    obj_type: ex_entity_type,
+    num_blk: c_int,
+    num_var: c_int,
+    var_tab: *mut c_int,
+) -> c_int {
+    status("ex_put_truth_table", || {
+        let var_type = entity_type(obj_type)?;
+        let (num_blocks, num_vars) = table_size(num_blk, num_var)?;
+        let flags = read_ints(var_tab.cast(), num_blocks * num_vars, false, "var_tab")?;
+        let mut table = TruthTable::new(var_type, num_blocks, num_vars);
+        for (i, &flag) in flags.iter().enumerate() {
+            table.set(i / num_vars, i % num_vars, flag != 0);
+        }
+        raw::ex_put_truth_table(exoid, var_type, &table)
+    })
+}
+
+fn table_size(num_blk: c_int, num_var: c_int) -> Result<(usize, usize)> {
+    Ok((index(num_blk, "num_blk")?, index(num_var, "num_var")?))
+}
+
+fn check_table_size(
+    &(num_blk, num_var): &(usize, usize),
+    blocks: usize,
+    vars: usize,
+) -> Result<()> {
+    if (num_blk, num_var) != (blocks, vars) {
+        return Err(ExodusError::Other(format!(
+            "Truth table is {} x {}, but {} x {} was requested",
+            blocks, vars, num_blk, num_var
+        )));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Variable Values
+// ============================================================================
+
+/// Read the values of one variable on one block or set at a time step
+/// (`ex_get_var`)
+///
+/// Global variables are read `num_entry_this_obj` at a time starting at
+/// `var_index`, as in C. `obj_id` is ignored for global and nodal variables.
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_var(
+    exoid: c_int,
+    time_step: c_int,
+    var_type: ex_entity_type,
+    var_index: c_int,
+    obj_id: ex_entity_id,
+    num_entry_this_obj: i64,
+    var_vals: *mut c_void,
+) -> c_int {
+    status("ex_get_var", || {
+        let comp_ws = options(exoid)?.comp_ws;
+        let step = index(time_step, "time_step")?;
+        let var_type = entity_type(var_type)?;
+        let var_index = index(var_index, "var_index")?;
+        let num_entries = entries(num_entry_this_obj)?;
+        let values = if var_type == EntityType::Global {
+            let mut values = Vec::with_capacity(num_entries);
+            for var in var_index..var_index + num_entries {
+                values.extend(raw::ex_get_var(exoid, step, var_type, var, obj_id)?);
+            }
+            values
+        } else {
+            raw::ex_get_var(exoid, step, var_type, var_index, obj_id)?
+        };
+        if values.len() != num_entries {
+            return Err(ExodusError::InvalidArrayLength {
+                expected: values.len(),
+                actual: num_entries,
+            });
+        }
+        write_reals(var_vals, &values, comp_ws, "var_vals")
+    })
+}
+
+/// Write the values of one variable on one block or set at a time step
+/// (`ex_put_var`)
+///
+/// Global variables are written `num_entries_this_obj` at a time starting at
+/// `var_index`, as in C. `obj_id` is ignored for global and nodal variables.
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_var(
+    exoid: c_int,
+    time_step: c_int,
+    var_type: ex_entity_type,
+    var_index: c_int,
+    obj_id: ex_entity_id,
+    num_entries_this_obj: i64,
+    var_vals: *const c_void,
+) -> c_int {
+    status("ex_put_var", || {
+        let comp_ws = options(exoid)?.comp_ws;
+        let step = index(time_step, "time_step")?;
+        let var_type = entity_type(var_type)?;
+        let var_index = index(var_index, "var_index")?;
+        let values = read_reals(
+            var_vals,
+            entries(num_entries_this_obj)?,
+            comp_ws,
+            "var_vals",
+        )?;
+        if var_type == EntityType::Global {
+            for (var, value) in (var_index..).zip(values) {
+                raw::ex_put_var(exoid, step, var_type, var, obj_id, &[value])?;
+            }
+            Ok(())
+        } else {
+            raw::ex_put_var(exoid, step, var_type, var_index, obj_id, &values)
+        }
+    })
+}
+
+// ============================================================================
+// Time Steps
+// ============================================================================
+
+/// Read the time value of a time step (`ex_get_time`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_time(
+    exoid: c_int,
+    time_step: c_int,
+    time_value: *mut c_void,
+) -> c_int {
+    status("ex_get_time", || {
+        let comp_ws = options(exoid)?.comp_ws;
+        let time = raw::ex_get_time(exoid, index(time_step, "time_step")?)?;
+        write_reals(time_value, &[time], comp_ws, "time_value")
+    })
+}
+
+/// Read the time values of all time steps (`ex_get_all_times`)
+#[no_mangle]
+pub unsafe extern "C" fn ex_get_all_times(exoid: c_int, time_values: *mut c_void) -> c_int {
+    status("ex_get_all_times", || {
+        let comp_ws = options(exoid)?.comp_ws;
+        let times = raw::ex_get_all_times(exoid)?;
+        write_reals(time_values, &times, comp_ws, "time_values")
+    })
+}
+
+/// Write the time value of a time step (`ex_put_time`)
+#[cfg(feature = "netcdf4")]
+#[no_mangle]
+pub unsafe extern "C" fn ex_put_time(
+    exoid: c_int,
+    time_step: c_int,
+    time_value: *const c_void,
+) -> c_int {
+    status("ex_put_time", || {
+        let comp_ws = options(exoid)?.comp_ws;
+        let time = read_reals(time_value, 1, comp_ws, "time_value")?;
+        raw::ex_put_time(exoid, index(time_step, "time_step")?, time[0])
+    })
+}
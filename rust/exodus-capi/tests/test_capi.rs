@@ -0,0 +1,299 @@
+//! Tests for the C ABI
+//!
+//! A file is written through the exported functions with `float` coordinates
+//! and 32-bit integers, as `compat-tests/c-to-rust/writer.c` does, then read
+//! back through the C ABI and with the high-level API.
+
+#![cfg(feature = "netcdf4")]
+
+use exodus::*;
+use exodus_rs::{mode, EntityType, ExodusFile};
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::path::Path;
+use std::ptr;
+use tempfile::TempDir;
+
+fn c_path(path: &Path) -> CString {
+    CString::new(path.to_str().unwrap()).unwrap()
+}
+
+/// Pointers to NUL-terminated copies of `values`
+fn c_strings(values: &[&str]) -> (Vec<CString>, Vec<*mut c_char>) {
+    let owned: Vec<CString> = values.iter().map(|v| CString::new(*v).unwrap()).collect();
+    let ptrs = owned.iter().map(|v| v.as_ptr() as *mut c_char).collect();
+    (owned, ptrs)
+}
+
+/// Buffers for `count` names read from C
+fn name_buffers(count: usize) -> (Vec<Vec<c_char>>, Vec<*mut c_char>) {
+    let mut buffers = vec![vec![0 as c_char; MAX_NAME_LENGTH as usize + 1]; count];
+    let ptrs = buffers.iter_mut().map(|b| b.as_mut_ptr()).collect();
+    (buffers, ptrs)
+}
+
+fn text(buffer: &[c_char]) -> String {
+    unsafe { CStr::from_ptr(buffer.as_ptr()) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Two QUAD4 elements, a node set, a side set and a global and nodal
+/// variable over two time steps
+unsafe fn write_mesh(path: &Path) {
+    let (mut comp_ws, mut io_ws) = (4, 8);
+    let exoid = ex_create_int(
+        c_path(path).as_ptr(),
+        EX_CLOBBER,
+        &mut comp_ws,
+        &mut io_ws,
+        EX_API_VERS_NODOT,
+    );
+    assert!(exoid >= 0);
+    assert_eq!((comp_ws, io_ws), (4, 8));
+
+    let title = CString::new("C ABI mesh").unwrap();
+    assert_eq!(
+        ex_put_init(exoid, title.as_ptr(), 2, 6, 2, 1, 1, 1),
+        EX_NOERR
+    );
+    let x: [f32; 6] = [0.0, 1.0, 2.0, 0.0, 1.0, 2.0];
+    let y: [f32; 6] = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+    assert_eq!(
+        ex_put_coord(exoid, x.as_ptr().cast(), y.as_ptr().cast(), ptr::null()),
+        EX_NOERR
+    );
+    let (_names, names) = c_strings(&["x", "y"]);
+    assert_eq!(ex_put_coord_names(exoid, names.as_ptr()), EX_NOERR);
+
+    let quad = CString::new("QUAD4").unwrap();
+    assert_eq!(
+        ex_put_block(exoid, EX_ELEM_BLOCK, 10, quad.as_ptr(), 2, 4, 0, 0, 0),
+        EX_NOERR
+    );
+    let conn: [c_int; 8] = [1, 2, 5, 4, 2, 3, 6, 5];
+    assert_eq!(
+        ex_put_conn(
+            exoid,
+            EX_ELEM_BLOCK,
+            10,
+            conn.as_ptr().cast(),
+            ptr::null(),
+            ptr::null()
+        ),
+        EX_NOERR
+    );
+
+    let nodes: [c_int; 2] = [1, 4];
+    assert_eq!(ex_put_set_param(exoid, EX_NODE_SET, 1, 2, 0), EX_NOERR);
+    assert_eq!(
+        ex_put_set(exoid, EX_NODE_SET, 1, nodes.as_ptr().cast(), ptr::null()),
+        EX_NOERR
+    );
+    let (elems, sides): ([c_int; 2], [c_int; 2]) = ([1, 2], [1, 1]);
+    assert_eq!(ex_put_set_param(exoid, EX_SIDE_SET, 2, 2, 0), EX_NOERR);
+    assert_eq!(
+        ex_put_set(
+            exoid,
+            EX_SIDE_SET,
+            2,
+            elems.as_ptr().cast(),
+            sides.as_ptr().cast()
+        ),
+        EX_NOERR
+    );
+
+    let (_qa, qa) = c_strings(&["capi", "1.0", "2024-01-01", "00:00:00"]);
+    let mut record = [qa[0], qa[1], qa[2], qa[3]];
+    assert_eq!(ex_put_qa(exoid, 1, &mut record), EX_NOERR);
+
+    let (_glo, glo) = c_strings(&["energy"]);
+    assert_eq!(ex_put_variable_param(exoid, EX_GLOBAL, 1), EX_NOERR);
+    assert_eq!(
+        ex_put_variable_names(exoid, EX_GLOBAL, 1, glo.as_ptr()),
+        EX_NOERR
+    );
+    let (_nod, nod) = c_strings(&["temp"]);
+    assert_eq!(ex_put_variable_param(exoid, EX_NODAL, 1), EX_NOERR);
+    assert_eq!(
+        ex_put_variable_names(exoid, EX_NODAL, 1, nod.as_ptr()),
+        EX_NOERR
+    );
+    for step in 1..=2 {
+        let time = step as f32 * 0.5;
+        assert_eq!(
+            ex_put_time(exoid, step, (&time as *const f32).cast()),
+            EX_NOERR
+        );
+        let energy = [time * 10.0];
+        assert_eq!(
+            ex_put_var(exoid, step, EX_GLOBAL, 1, 1, 1, energy.as_ptr().cast()),
+            EX_NOERR
+        );
+        let temp = [time; 6];
+        assert_eq!(
+            ex_put_var(exoid, step, EX_NODAL, 1, 1, 6, temp.as_ptr().cast()),
+            EX_NOERR
+        );
+    }
+    assert_eq!(ex_close(exoid), EX_NOERR);
+}
+
+#[test]
+fn test_capi_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("capi.e");
+    unsafe {
+        write_mesh(&path);
+
+        let (mut comp_ws, mut io_ws, mut version) = (8, 0, 0.0f32);
+        let exoid = ex_open_int(
+            c_path(&path).as_ptr(),
+            EX_READ,
+            &mut comp_ws,
+            &mut io_ws,
+            &mut version,
+            EX_API_VERS_NODOT,
+        );
+        assert!(exoid >= 0);
+        assert_eq!(io_ws, 8);
+        assert!(version > 0.0);
+        assert_eq!(ex_set_int64_status(exoid, EX_ALL_INT64_API), 0);
+
+        let mut params: ex_init_params = std::mem::zeroed();
+        assert_eq!(ex_get_init_ext(exoid, &mut params), EX_NOERR);
+        assert_eq!(text(&params.title), "C ABI mesh");
+        assert_eq!(
+            (params.num_dim, params.num_nodes, params.num_elem),
+            (2, 6, 2)
+        );
+        assert_eq!(ex_inquire_int(exoid, EX_INQ_TIME), 2);
+
+        let mut x = [0.0f64; 6];
+        assert_eq!(
+            ex_get_coord(
+                exoid,
+                x.as_mut_ptr().cast(),
+                ptr::null_mut(),
+                ptr::null_mut()
+            ),
+            EX_NOERR
+        );
+        assert_eq!(x[2], 2.0);
+        let (buffers, names) = name_buffers(2);
+        assert_eq!(ex_get_coord_names(exoid, names.as_ptr()), EX_NOERR);
+        assert_eq!(text(&buffers[1]), "y");
+
+        let mut topology = [0 as c_char; MAX_STR_LENGTH as usize + 1];
+        let (mut entries, mut per_entry) = (0i64, 0i64);
+        assert_eq!(
+            ex_get_block(
+                exoid,
+                EX_ELEM_BLOCK,
+                10,
+                topology.as_mut_ptr(),
+                (&mut entries as *mut i64).cast(),
+                (&mut per_entry as *mut i64).cast(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            ),
+            EX_NOERR
+        );
+        assert_eq!(
+            (text(&topology).as_str(), entries, per_entry),
+            ("QUAD4", 2, 4)
+        );
+        let mut conn = [0i64; 8];
+        assert_eq!(
+            ex_get_conn(
+                exoid,
+                EX_ELEM_BLOCK,
+                10,
+                conn.as_mut_ptr().cast(),
+                ptr::null_mut(),
+                ptr::null_mut()
+            ),
+            EX_NOERR
+        );
+        assert_eq!(conn, [1, 2, 5, 4, 2, 3, 6, 5]);
+
+        let (mut elems, mut sides) = ([0i64; 2], [0i64; 2]);
+        assert_eq!(
+            ex_get_set(
+                exoid,
+                EX_SIDE_SET,
+                2,
+                elems.as_mut_ptr().cast(),
+                sides.as_mut_ptr().cast()
+            ),
+            EX_NOERR
+        );
+        assert_eq!((elems, sides), ([1, 2], [1, 1]));
+
+        let mut times = [0.0f64; 2];
+        assert_eq!(ex_get_all_times(exoid, times.as_mut_ptr().cast()), EX_NOERR);
+        assert_eq!(times, [0.5, 1.0]);
+        let mut energy = [0.0f64];
+        assert_eq!(
+            ex_get_var(exoid, 2, EX_GLOBAL, 1, 0, 1, energy.as_mut_ptr().cast()),
+            EX_NOERR
+        );
+        assert_eq!(energy, [10.0]);
+        let mut temp = [0.0f64; 6];
+        assert_eq!(
+            ex_get_var(exoid, 1, EX_NODAL, 1, 0, 6, temp.as_mut_ptr().cast()),
+            EX_NOERR
+        );
+        assert_eq!(temp, [0.5; 6]);
+
+        // Wrong buffer length
+        assert_eq!(
+            ex_get_var(exoid, 1, EX_NODAL, 1, 0, 5, temp.as_mut_ptr().cast()),
+            EX_FATAL
+        );
+        let mut code = 0;
+        ex_get_err(ptr::null_mut(), ptr::null_mut(), &mut code);
+        assert_eq!(code, EX_BADPARAM);
+
+        assert_eq!(ex_close(exoid), EX_NOERR);
+    }
+}
+
+#[test]
+fn test_capi_file_readable_by_high_level_api() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("capi.e");
+    unsafe { write_mesh(&path) };
+    let file = ExodusFile::<mode::Read>::open(&path).unwrap();
+    assert_eq!(file.block(10).unwrap().num_entries, 2);
+    assert_eq!(file.var(1, EntityType::Nodal, 0, 0).unwrap(), vec![1.0; 6]);
+}
+
+#[test]
+fn test_capi_write_on_read_only() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("capi.e");
+    unsafe {
+        write_mesh(&path);
+        let (mut comp_ws, mut io_ws, mut version) = (8, 0, 0.0f32);
+        let exoid = ex_open_int(
+            c_path(&path).as_ptr(),
+            EX_READ,
+            &mut comp_ws,
+            &mut io_ws,
+            &mut version,
+            EX_API_VERS_NODOT,
+        );
+        let time = 1.5f64;
+        assert_eq!(
+            ex_put_time(exoid, 3, (&time as *const f64).cast()),
+            EX_FATAL
+        );
+        let mut code = 0;
+        ex_get_err(ptr::null_mut(), ptr::null_mut(), &mut code);
+        assert_eq!(code, EX_BADFILEMODE);
+        assert_eq!(ex_close(exoid), EX_NOERR);
+        assert_eq!(ex_close(exoid), EX_FATAL);
+    }
+}
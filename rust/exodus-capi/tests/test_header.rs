@@ -0,0 +1,21 @@
+//! The committed header matches the one generated by the build script
+
+/// Line the build script adds to the header of `netcdf4` builds
+const NETCDF4_DEFINE: &str = "#define EXODUS_RS_NETCDF4\n";
+
+#[test]
+fn test_committed_header_is_current() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/exodusII.h"));
+    let committed = include_str!("../include/exodusII.h");
+    assert_eq!(
+        generated.contains(NETCDF4_DEFINE),
+        cfg!(feature = "netcdf4")
+    );
+    assert!(!committed.contains(NETCDF4_DEFINE));
+    assert!(
+        generated.replacen(NETCDF4_DEFINE, "", 1) == committed,
+        "include/exodusII.h is out of date; copy {}/exodusII.h over it without \
+         the EXODUS_RS_NETCDF4 define",
+        env!("OUT_DIR")
+    );
+}
@@ -6,7 +6,9 @@
 //! - Using different creation modes
 //! - Querying file properties
 
-use exodus_rs::{CreateMode, CreateOptions, ExodusFile, FloatSize, Int64Mode};
+use exodus_rs::{
+    CreateMode, CreateOptions, ExodusFile, FileFormat, FloatSize, Int64Mode, VarStorageMode,
+};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== Exodus File Creation Example ===\n");
@@ -25,11 +27,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     {
         let options = CreateOptions {
             mode: CreateMode::Clobber, // Overwrite if exists
+            format: FileFormat::NetCdf4,
             float_size: FloatSize::Float32,
             int64_mode: Int64Mode::Int64,
             compression: None,
+            shuffle: true,
             parallel: false,
             performance: None,
+            nodal_var_storage: VarStorageMode::Separate,
         };
 
         let file = ExodusFile::create("example_custom.exo", options)?;
@@ -0,0 +1,1224 @@
+//! Importing Abaqus `.inp` input decks into Exodus.
+//!
+//! [`AbaqusMesh`] reads the mesh definition of an input deck: the `*NODE`,
+//! `*ELEMENT`, `*NSET`, `*ELSET` and `*SURFACE` keywords. Other keywords
+//! (materials, sections, steps, ...) are skipped. [`import_abaqus`] converts
+//! a whole deck to an Exodus file written through [`MeshBuilder`].
+//!
+//! The deck maps to Exodus as follows:
+//!
+//! - Elements become element blocks, one per `ELSET` parameter of `*ELEMENT`
+//!   and element type, named after the `ELSET`. Element types map to a
+//!   [`Topology`] by family and node count (`C3D8R` is a `HEX8`, `S4` a
+//!   `SHELL4`, `CPS6` a `TRI6`, ...), and element nodes are reordered to
+//!   the Exodus convention.
+//! - Node sets, whether from `*NSET` or the `NSET` parameter of `*NODE`,
+//!   become node sets, and sets defined with `*ELSET` become element sets.
+//! - Element-based `*SURFACE`s become side sets. The face labels `S1` to
+//!   `S6` are translated to Exodus side numbers by matching their corner
+//!   nodes against [`Topology::faces`]. On shells, `SPOS` and `SNEG` are
+//!   sides 1 and 2, and the edges `E1` to `E4` sides 3 to 6.
+//!
+//! Nodes are numbered in the order of their labels and elements in block
+//! order, and the Abaqus labels are kept in the node and element ID maps.
+//! Blocks and sets are numbered from 1 in the order they first appear.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use exodus_rs::abaqus::import_abaqus;
+//!
+//! let summary = import_abaqus("bracket.inp", "bracket.e")?;
+//! println!("{} blocks, {} side sets", summary.num_blocks, summary.num_side_sets);
+//! # Ok::<(), exodus_rs::ExodusError>(())
+//! ```
+//!
+//! The deck is read flat: parts, instances and their transformations are
+//! not applied, so node and element labels must be unique in the whole
+//! deck. Node-based surfaces, surfaces without face labels, and element
+//! types without an Exodus topology (such as `C3D27` or continuum shells)
+//! are not supported.
+
+use crate::builder::{BlockBuilder, ElemSetBuilder, MeshBuilder, NodeSetBuilder, SideSetBuilder};
+use crate::error::{ExodusError, Result};
+use crate::types::Topology;
+use crate::utils::naming::truncate_name;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Nodes numbered alike in Abaqus and Exodus
+static IDENTITY: [usize; 20] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19,
+];
+
+/// Abaqus numbers the top edges of a hexahedron before the vertical ones,
+/// Exodus after
+static HEX20_ORDER: [usize; 20] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 16, 17, 18, 19, 12, 13, 14, 15,
+];
+
+/// As for [`HEX20_ORDER`], the top and vertical edges are swapped
+static WEDGE15_ORDER: [usize; 15] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 13, 14, 9, 10, 11];
+
+/// Corner nodes of the faces `S1` to `S6` of an Abaqus hexahedron
+static HEX_FACES: [&[usize]; 6] = [
+    &[0, 1, 2, 3],
+    &[4, 7, 6, 5],
+    &[0, 4, 5, 1],
+    &[1, 5, 6, 2],
+    &[2, 6, 7, 3],
+    &[3, 7, 4, 0],
+];
+
+/// Corner nodes of the faces `S1` to `S4` of an Abaqus tetrahedron
+static TET_FACES: [&[usize]; 4] = [&[0, 1, 2], &[0, 3, 1], &[1, 3, 2], &[2, 3, 0]];
+
+/// Corner nodes of the faces `S1` to `S5` of an Abaqus wedge
+static WEDGE_FACES: [&[usize]; 5] = [
+    &[0, 1, 2],
+    &[3, 5, 4],
+    &[0, 3, 4, 1],
+    &[1, 4, 5, 2],
+    &[2, 5, 3, 0],
+];
+
+/// Corner nodes of the faces `S1` to `S5` of an Abaqus pyramid
+static PYRAMID_FACES: [&[usize]; 5] = [
+    &[0, 1, 2, 3],
+    &[0, 4, 1],
+    &[1, 4, 2],
+    &[2, 4, 3],
+    &[3, 4, 0],
+];
+
+/// Corner nodes of the edges `S1` to `S4` of a planar quadrilateral
+static QUAD_EDGES: [&[usize]; 4] = [&[0, 1], &[1, 2], &[2, 3], &[3, 0]];
+
+/// Corner nodes of the edges `S1` to `S3` of a planar triangle
+static TRI_EDGES: [&[usize]; 3] = [&[0, 1], &[1, 2], &[2, 0]];
+
+/// How an Abaqus element type is stored in Exodus
+#[derive(Debug, Clone)]
+struct ElementKind {
+    /// Topology name of the Exodus block
+    name: &'static str,
+    topology: Topology,
+    /// Exodus node `i` is Abaqus node `order[i]`
+    order: &'static [usize],
+    /// Whether the element lies in the XY plane (2D continuum, trusses and
+    /// beams)
+    planar: bool,
+    /// Whether the element is a shell or membrane, with sides `SPOS` and
+    /// `SNEG`
+    shell: bool,
+}
+
+impl ElementKind {
+    fn new(name: &'static str, topology: Topology) -> Self {
+        let num_nodes = topology.expected_nodes().unwrap_or(0);
+        Self {
+            name,
+            topology,
+            order: &IDENTITY[..num_nodes],
+            planar: false,
+            shell: false,
+        }
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Exodus side number of an Abaqus face label
+    fn side(&self, label: &str) -> Option<i64> {
+        let label = label.trim().to_uppercase();
+        if self.shell {
+            let num_edges = self.topology.num_faces()?;
+            return match label.as_str() {
+                "SPOS" => Some(1),
+                "SNEG" => Some(2),
+                _ => {
+                    let edge: usize = label.strip_prefix('E')?.parse().ok()?;
+                    (1..=num_edges).contains(&edge).then_some(edge as i64 + 2)
+                }
+            };
+        }
+        let faces: &[&[usize]] = match self.topology {
+            Topology::Hex8 | Topology::Hex20 => &HEX_FACES,
+            Topology::Tet4 | Topology::Tet10 => &TET_FACES,
+            Topology::Wedge6 | Topology::Wedge15 => &WEDGE_FACES,
+            Topology::Pyramid5 => &PYRAMID_FACES,
+            Topology::Quad4 | Topology::Quad8 => &QUAD_EDGES,
+            Topology::Tri3 | Topology::Tri6 => &TRI_EDGES,
+            _ => return None,
+        };
+        let face: usize = label.strip_prefix('S')?.parse().ok()?;
+        let mut corners = faces.get(face.checked_sub(1)?)?.to_vec();
+        corners.sort_unstable();
+        self.topology.faces()?.into_iter().find_map(|def| {
+            let mut nodes = def.node_indices;
+            nodes.sort_unstable();
+            (nodes == corners).then_some(def.side_number as i64)
+        })
+    }
+}
+
+/// Exodus storage of an Abaqus element type, `None` if it has none
+fn element_kind(element_type: &str) -> Option<ElementKind> {
+    let element_type = element_type.trim().to_uppercase();
+    // Node count after a family prefix, ignoring suffixes such as R or H
+    let count = |prefix: &str| -> Option<usize> {
+        let rest = element_type.strip_prefix(prefix)?;
+        let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        digits.parse().ok()
+    };
+    let solid = ["C3D", "DC3D"].iter().find_map(|prefix| count(prefix));
+    let planar = ["CPEG", "CPS", "CPE", "CAX", "DC2D", "DCAX"]
+        .iter()
+        .find_map(|prefix| count(prefix));
+    let shell = ["M3D", "S"].iter().find_map(|prefix| count(prefix));
+    let truss = ["T2D", "T3D"].iter().find_map(|prefix| count(prefix));
+
+    if let Some(nodes) = solid {
+        return match nodes {
+            4 => Some(ElementKind::new("TETRA4", Topology::Tet4)),
+            10 => Some(ElementKind::new("TETRA10", Topology::Tet10)),
+            5 => Some(ElementKind::new("PYRAMID5", Topology::Pyramid5)),
+            6 => Some(ElementKind::new("WEDGE6", Topology::Wedge6)),
+            15 => Some(ElementKind {
+                order: &WEDGE15_ORDER,
+                ..ElementKind::new("WEDGE15", Topology::Wedge15)
+            }),
+            8 => Some(ElementKind::new("HEX8", Topology::Hex8)),
+            20 => Some(ElementKind {
+                order: &HEX20_ORDER,
+                ..ElementKind::new("HEX20", Topology::Hex20)
+            }),
+            _ => None,
+        };
+    }
+    if let Some(nodes) = planar {
+        let kind = match nodes {
+            3 => ElementKind::new("TRI3", Topology::Tri3),
+            6 => ElementKind::new("TRI6", Topology::Tri6),
+            4 => ElementKind::new("QUAD4", Topology::Quad4),
+            8 => ElementKind::new("QUAD8", Topology::Quad8),
+            _ => return None,
+        };
+        return Some(ElementKind {
+            planar: true,
+            ..kind
+        });
+    }
+    if let Some(nodes) = shell {
+        let kind = match nodes {
+            3 => ElementKind::new("TRISHELL3", Topology::Tri3),
+            6 => ElementKind::new("TRISHELL6", Topology::Tri6),
+            4 => ElementKind::new("SHELL4", Topology::Quad4),
+            8 => ElementKind::new("SHELL8", Topology::Quad8),
+            9 => ElementKind::new("SHELL9", Topology::Quad9),
+            _ => return None,
+        };
+        return Some(ElementKind {
+            shell: true,
+            ..kind
+        });
+    }
+    if let Some(nodes) = truss {
+        let kind = match nodes {
+            2 => ElementKind::new("TRUSS2", Topology::Bar2),
+            3 => ElementKind::new("TRUSS3", Topology::Bar3),
+            _ => return None,
+        };
+        return Some(ElementKind {
+            planar: element_type.starts_with("T2D"),
+            ..kind
+        });
+    }
+    // Beams are B<dimension><order>: B21 and B31 have 2 nodes, B22 and B32 3
+    let mut chars = element_type.strip_prefix('B')?.chars();
+    let dimension = chars.next()?;
+    let kind = match (dimension, chars.next()?) {
+        ('2' | '3', '1') => ElementKind::new("BEAM2", Topology::Bar2),
+        ('2' | '3', '2') => ElementKind::new("BEAM3", Topology::Bar3),
+        _ => return None,
+    };
+    Some(ElementKind {
+        planar: dimension == '2',
+        ..kind
+    })
+}
+
+/// An element of an Abaqus deck
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AbaqusElement {
+    /// Element label
+    pub label: i64,
+    /// Abaqus element type, e.g. `C3D8R`
+    pub element_type: String,
+    /// `ELSET` parameter of the `*ELEMENT` keyword, empty if none
+    pub elset: String,
+    /// Node labels in Abaqus order
+    pub nodes: Vec<i64>,
+}
+
+/// A named set of node or element labels
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AbaqusSet {
+    /// Set name, as first written in the deck
+    pub name: String,
+    /// Labels in the order they were added, possibly repeated
+    pub labels: Vec<i64>,
+}
+
+/// An element-based surface
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AbaqusSurface {
+    /// Surface name
+    pub name: String,
+    /// Element labels with their face labels, e.g. `(12, "S3")`
+    pub faces: Vec<(i64, String)>,
+}
+
+/// The mesh definition of an Abaqus input deck
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AbaqusMesh {
+    /// Node coordinates by label
+    pub nodes: BTreeMap<i64, [f64; 3]>,
+    /// Elements in the order they appear
+    pub elements: Vec<AbaqusElement>,
+    /// Node sets in the order they first appear
+    pub node_sets: Vec<AbaqusSet>,
+    /// Element sets defined with `*ELSET`, in the order they first appear
+    pub element_sets: Vec<AbaqusSet>,
+    /// Element-based surfaces
+    pub surfaces: Vec<AbaqusSurface>,
+}
+
+/// What an import read and wrote
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AbaqusSummary {
+    /// Number of nodes
+    pub num_nodes: usize,
+    /// Number of elements
+    pub num_elems: usize,
+    /// Number of element blocks
+    pub num_blocks: usize,
+    /// Number of node sets
+    pub num_node_sets: usize,
+    /// Number of element sets
+    pub num_elem_sets: usize,
+    /// Number of side sets
+    pub num_side_sets: usize,
+}
+
+/// Convert an Abaqus input deck to an Exodus file, overwriting the output
+///
+/// # Errors
+///
+/// Returns an error if the deck cannot be read or converted (see
+/// [`AbaqusMesh::to_builder`]), or the Exodus file cannot be written.
+pub fn import_abaqus(input: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<AbaqusSummary> {
+    let input = input.as_ref();
+    let mesh = AbaqusMesh::read(input)?;
+    let parts = mesh.exodus_parts()?;
+    let summary = parts.summary(mesh.nodes.len());
+    let title = format!(
+        "Converted from {}",
+        input.file_name().unwrap_or_default().to_string_lossy()
+    );
+    parts.into_builder(title, &mesh.nodes).write(output)?;
+    Ok(summary)
+}
+
+impl AbaqusMesh {
+    /// Read an input deck, expanding `*INCLUDE` keywords relative to the
+    /// directory of the including file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read or the deck cannot be
+    /// parsed (see [`AbaqusMesh::parse`]).
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let mut text = String::new();
+        expand_includes(path.as_ref(), &mut text, 0)?;
+        Self::parse(&text)
+    }
+
+    /// Parse the text of an input deck
+    ///
+    /// Keywords, parameter names, element types and set names are case
+    /// insensitive, as in Abaqus.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a line of a mesh keyword is malformed, an element
+    /// type has no Exodus topology, a set or surface refers to an undefined
+    /// set, or the deck contains `*INCLUDE` (see [`AbaqusMesh::read`]).
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut reader = Reader::default();
+        let mut keyword: Option<Keyword> = None;
+        let mut data: Vec<(usize, &str)> = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("**") {
+                continue;
+            }
+            if line.starts_with('*') {
+                if let Some(keyword) = keyword.take() {
+                    reader.keyword(&keyword, &data)?;
+                }
+                keyword = Some(Keyword::parse(index + 1, line));
+                data.clear();
+            } else {
+                data.push((index + 1, line));
+            }
+        }
+        if let Some(keyword) = keyword {
+            reader.keyword(&keyword, &data)?;
+        }
+        Ok(reader.finish())
+    }
+
+    /// Build an Exodus mesh from the deck (see the
+    /// [module documentation](self))
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deck has no elements, a label is defined
+    /// twice, an element, set or surface refers to an undefined node or
+    /// element, or a face label is not a face of its element.
+    pub fn to_builder(&self, title: impl Into<String>) -> Result<MeshBuilder> {
+        Ok(self.exodus_parts()?.into_builder(title, &self.nodes))
+    }
+
+    fn exodus_parts(&self) -> Result<ExodusParts> {
+        if self.elements.is_empty() {
+            return Err(ExodusError::Other(
+                "Abaqus deck has no elements".to_string(),
+            ));
+        }
+        let node_numbers: HashMap<i64, i64> = self
+            .nodes
+            .keys()
+            .enumerate()
+            .map(|(index, &label)| (label, index as i64 + 1))
+            .collect();
+        let node_number = |label: i64, owner: &str| -> Result<i64> {
+            node_numbers.get(&label).copied().ok_or_else(|| {
+                ExodusError::Other(format!("{} refers to undefined node {}", owner, label))
+            })
+        };
+
+        // Group the elements by ELSET and type, in the order they appear
+        let mut groups: Vec<(String, String, ElementKind, Vec<&AbaqusElement>)> = Vec::new();
+        let mut group_index: HashMap<(String, String), usize> = HashMap::new();
+        for element in &self.elements {
+            let key = (
+                element.elset.to_uppercase(),
+                element.element_type.to_uppercase(),
+            );
+            let index = match group_index.get(&key) {
+                Some(&index) => index,
+                None => {
+                    let kind = element_kind(&element.element_type).ok_or_else(|| {
+                        ExodusError::UnsupportedFeature(format!(
+                            "Abaqus element type {}",
+                            element.element_type
+                        ))
+                    })?;
+                    groups.push((element.elset.clone(), key.1.clone(), kind, Vec::new()));
+                    group_index.insert(key, groups.len() - 1);
+                    groups.len() - 1
+                }
+            };
+            groups[index].3.push(element);
+        }
+
+        let mut elem_numbers: HashMap<i64, (i64, &ElementKind)> = HashMap::new();
+        let mut elem_ids = Vec::with_capacity(self.elements.len());
+        let mut blocks = Vec::with_capacity(groups.len());
+        for (index, (elset, element_type, kind, elements)) in groups.iter().enumerate() {
+            let mut connectivity = Vec::with_capacity(elements.len() * kind.num_nodes());
+            for element in elements {
+                let number = elem_ids.len() as i64 + 1;
+                if elem_numbers.insert(element.label, (number, kind)).is_some() {
+                    return Err(ExodusError::Other(format!(
+                        "Abaqus element {} is defined twice",
+                        element.label
+                    )));
+                }
+                elem_ids.push(element.label);
+                if element.nodes.len() != kind.num_nodes() {
+                    return Err(ExodusError::Other(format!(
+                        "Abaqus element {} has {} nodes, not the {} of a {}",
+                        element.label,
+                        element.nodes.len(),
+                        kind.num_nodes(),
+                        element_type
+                    )));
+                }
+                let owner = format!("Element {}", element.label);
+                for &node in kind.order {
+                    connectivity.push(node_number(element.nodes[node], &owner)?);
+                }
+            }
+            // Name blocks after their ELSET, with the type if it has several
+            let shared = groups
+                .iter()
+                .filter(|group| group.0.eq_ignore_ascii_case(elset))
+                .count()
+                > 1;
+            let name = if elset.is_empty() {
+                String::new()
+            } else if shared {
+                format!("{}_{}", elset, element_type)
+            } else {
+                elset.clone()
+            };
+            blocks.push(ExodusBlock {
+                id: index as i64 + 1,
+                name: truncate_name(&name),
+                topology: kind.name,
+                connectivity,
+            });
+        }
+
+        let numbered = |set: &AbaqusSet, numbers: &dyn Fn(i64) -> Option<i64>, what: &str| {
+            let mut entries = set
+                .labels
+                .iter()
+                .map(|&label| {
+                    numbers(label).ok_or_else(|| {
+                        ExodusError::Other(format!(
+                            "Set {} refers to undefined {} {}",
+                            set.name, what, label
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<i64>>>()?;
+            entries.sort_unstable();
+            entries.dedup();
+            Ok::<_, ExodusError>(entries)
+        };
+        let node_sets = self
+            .node_sets
+            .iter()
+            .enumerate()
+            .map(|(index, set)| {
+                Ok(ExodusSet {
+                    id: index as i64 + 1,
+                    name: truncate_name(&set.name),
+                    entries: numbered(set, &|label| node_numbers.get(&label).copied(), "node")?,
+                    sides: Vec::new(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let elem_sets = self
+            .element_sets
+            .iter()
+            .enumerate()
+            .map(|(index, set)| {
+                Ok(ExodusSet {
+                    id: index as i64 + 1,
+                    name: truncate_name(&set.name),
+                    entries: numbered(
+                        set,
+                        &|label| elem_numbers.get(&label).map(|(number, _)| *number),
+                        "element",
+                    )?,
+                    sides: Vec::new(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut side_sets = Vec::with_capacity(self.surfaces.len());
+        for (index, surface) in self.surfaces.iter().enumerate() {
+            let mut set = ExodusSet {
+                id: index as i64 + 1,
+                name: truncate_name(&surface.name),
+                entries: Vec::with_capacity(surface.faces.len()),
+                sides: Vec::with_capacity(surface.faces.len()),
+            };
+            for (label, face) in &surface.faces {
+                let (number, kind) = elem_numbers.get(label).ok_or_else(|| {
+                    ExodusError::Other(format!(
+                        "Surface {} refers to undefined element {}",
+                        surface.name, label
+                    ))
+                })?;
+                let side = kind.side(face).ok_or_else(|| {
+                    ExodusError::Other(format!(
+                        "Surface {}: {} is not a face of element {} ({})",
+                        surface.name, face, label, kind.name
+                    ))
+                })?;
+                set.entries.push(*number);
+                set.sides.push(side);
+            }
+            side_sets.push(set);
+        }
+
+        Ok(ExodusParts {
+            num_dim: if groups.iter().all(|group| group.2.planar) {
+                2
+            } else {
+                3
+            },
+            node_ids: self.nodes.keys().copied().collect(),
+            elem_ids,
+            blocks,
+            node_sets,
+            elem_sets,
+            side_sets,
+        })
+    }
+}
+
+/// An Exodus element block built from an Abaqus deck
+#[derive(Debug)]
+struct ExodusBlock {
+    id: i64,
+    name: String,
+    topology: &'static str,
+    /// 1-based node numbers in Exodus order
+    connectivity: Vec<i64>,
+}
+
+/// An Exodus node set or element set (entries are nodes or elements), or
+/// side set (entries are elements)
+#[derive(Debug)]
+struct ExodusSet {
+    id: i64,
+    name: String,
+    entries: Vec<i64>,
+    sides: Vec<i64>,
+}
+
+/// An Exodus mesh built from an Abaqus deck
+#[derive(Debug)]
+struct ExodusParts {
+    num_dim: usize,
+    node_ids: Vec<i64>,
+    elem_ids: Vec<i64>,
+    blocks: Vec<ExodusBlock>,
+    node_sets: Vec<ExodusSet>,
+    elem_sets: Vec<ExodusSet>,
+    side_sets: Vec<ExodusSet>,
+}
+
+impl ExodusParts {
+    fn summary(&self, num_nodes: usize) -> AbaqusSummary {
+        AbaqusSummary {
+            num_nodes,
+            num_elems: self.elem_ids.len(),
+            num_blocks: self.blocks.len(),
+            num_node_sets: self.node_sets.len(),
+            num_elem_sets: self.elem_sets.len(),
+            num_side_sets: self.side_sets.len(),
+        }
+    }
+
+    fn into_builder(
+        self,
+        title: impl Into<String>,
+        nodes: &BTreeMap<i64, [f64; 3]>,
+    ) -> MeshBuilder {
+        let axis = |axis: usize| -> Vec<f64> {
+            if axis < self.num_dim {
+                nodes.values().map(|node| node[axis]).collect()
+            } else {
+                Vec::new()
+            }
+        };
+        let mut builder = MeshBuilder::new(title)
+            .dimensions(self.num_dim)
+            .coordinates(axis(0), axis(1), axis(2))
+            .node_id_map(self.node_ids)
+            .elem_id_map(self.elem_ids);
+        for block in self.blocks {
+            let mut block_builder =
+                BlockBuilder::new(block.id, block.topology).connectivity(block.connectivity);
+            if !block.name.is_empty() {
+                block_builder = block_builder.name(block.name);
+            }
+            builder = builder.add_block(block_builder.build());
+        }
+        for set in self.node_sets {
+            builder = builder.add_node_set(
+                NodeSetBuilder::new(set.id)
+                    .nodes(set.entries)
+                    .name(set.name)
+                    .build(),
+            );
+        }
+        for set in self.elem_sets {
+            builder = builder.add_elem_set(
+                ElemSetBuilder::new(set.id)
+                    .elements(set.entries)
+                    .name(set.name)
+                    .build(),
+            );
+        }
+        for set in self.side_sets {
+            builder = builder.add_side_set(
+                SideSetBuilder::new(set.id)
+                    .elements_and_sides(set.entries, set.sides)
+                    .name(set.name)
+                    .build(),
+            );
+        }
+        builder
+    }
+}
+
+/// Nesting limit of `*INCLUDE`, against files including themselves
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Append the text of a deck to `text`, replacing each `*INCLUDE` keyword
+/// with the text of its `INPUT` file
+fn expand_includes(path: &Path, text: &mut String, depth: usize) -> Result<()> {
+    if depth > MAX_INCLUDE_DEPTH {
+        return Err(ExodusError::Other(format!(
+            "Abaqus *INCLUDE nested more than {} deep at {}",
+            MAX_INCLUDE_DEPTH,
+            path.display()
+        )));
+    }
+    let bytes = std::fs::read(path)?;
+    for (index, line) in String::from_utf8_lossy(&bytes).lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('*') && !trimmed.starts_with("**") {
+            let keyword = Keyword::parse(index + 1, trimmed);
+            if keyword.name == "INCLUDE" {
+                let input = keyword.required("INPUT")?;
+                let parent = path.parent().unwrap_or_else(|| Path::new(""));
+                expand_includes(&parent.join(input), text, depth + 1)?;
+                continue;
+            }
+        }
+        text.push_str(line);
+        text.push('\n');
+    }
+    Ok(())
+}
+
+/// A keyword line: `*NAME, PARAMETER=value, FLAG, ...`
+#[derive(Debug)]
+struct Keyword {
+    line: usize,
+    /// Upper-case name without the `*`
+    name: String,
+    /// Upper-case parameter names with their values as written
+    parameters: Vec<(String, String)>,
+}
+
+impl Keyword {
+    fn parse(line: usize, text: &str) -> Self {
+        let mut fields = text.trim_start_matches('*').split(',');
+        let name = fields.next().unwrap_or_default().trim().to_uppercase();
+        let parameters = fields
+            .map(|field| match field.split_once('=') {
+                Some((key, value)) => (key.trim().to_uppercase(), value.trim().to_string()),
+                None => (field.trim().to_uppercase(), String::new()),
+            })
+            .filter(|(key, _)| !key.is_empty())
+            .collect();
+        Self {
+            line,
+            name,
+            parameters,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn required(&self, key: &str) -> Result<&str> {
+        self.get(key)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| {
+                ExodusError::Other(format!(
+                    "Abaqus line {}: *{} needs {}=",
+                    self.line, self.name, key
+                ))
+            })
+    }
+
+    fn flag(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+/// Comma-separated fields of a data line, without a trailing empty field
+fn fields(line: &str) -> Vec<&str> {
+    let mut fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    if fields.last() == Some(&"") {
+        fields.pop();
+    }
+    fields
+}
+
+fn parse_label(line: usize, field: &str) -> Result<i64> {
+    field.parse().map_err(|_| {
+        ExodusError::Other(format!(
+            "Abaqus line {}: expected a label, found '{}'",
+            line, field
+        ))
+    })
+}
+
+/// Parse a real, accepting Fortran `D` exponents
+fn parse_real(line: usize, field: &str) -> Result<f64> {
+    if field.is_empty() {
+        return Ok(0.0);
+    }
+    field.replace(['D', 'd'], "e").parse().map_err(|_| {
+        ExodusError::Other(format!(
+            "Abaqus line {}: expected a number, found '{}'",
+            line, field
+        ))
+    })
+}
+
+/// Named sets by upper-case name
+#[derive(Debug, Default)]
+struct Sets {
+    sets: Vec<AbaqusSet>,
+    /// Whether each set becomes an Exodus set
+    exported: Vec<bool>,
+    index: HashMap<String, usize>,
+}
+
+impl Sets {
+    fn add(&mut self, name: &str, labels: &[i64], export: bool) {
+        let index = *self.index.entry(name.to_uppercase()).or_insert_with(|| {
+            self.sets.push(AbaqusSet {
+                name: name.to_string(),
+                labels: Vec::new(),
+            });
+            self.exported.push(false);
+            self.sets.len() - 1
+        });
+        self.sets[index].labels.extend_from_slice(labels);
+        self.exported[index] |= export;
+    }
+
+    fn labels(&self, name: &str) -> Option<&[i64]> {
+        self.index
+            .get(&name.to_uppercase())
+            .map(|&index| self.sets[index].labels.as_slice())
+    }
+
+    fn into_exported(self) -> Vec<AbaqusSet> {
+        self.sets
+            .into_iter()
+            .zip(self.exported)
+            .filter_map(|(set, exported)| exported.then_some(set))
+            .collect()
+    }
+}
+
+/// Mesh keywords read so far
+#[derive(Debug, Default)]
+struct Reader {
+    nodes: BTreeMap<i64, [f64; 3]>,
+    elements: Vec<AbaqusElement>,
+    nsets: Sets,
+    elsets: Sets,
+    surfaces: Vec<AbaqusSurface>,
+}
+
+impl Reader {
+    fn keyword(&mut self, keyword: &Keyword, data: &[(usize, &str)]) -> Result<()> {
+        match keyword.name.as_str() {
+            "NODE" => self.nodes(keyword, data),
+            "ELEMENT" => self.elements(keyword, data),
+            "NSET" => {
+                let labels = self.set_labels(keyword, data, false)?;
+                self.nsets.add(keyword.required("NSET")?, &labels, true);
+                Ok(())
+            }
+            "ELSET" => {
+                let labels = self.set_labels(keyword, data, true)?;
+                self.elsets.add(keyword.required("ELSET")?, &labels, true);
+                Ok(())
+            }
+            "SURFACE" => self.surface(keyword, data),
+            "INCLUDE" => Err(ExodusError::UnsupportedFeature(format!(
+                "Abaqus *INCLUDE at line {} (read the deck from a file to expand it)",
+                keyword.line
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn nodes(&mut self, keyword: &Keyword, data: &[(usize, &str)]) -> Result<()> {
+        let mut labels = Vec::with_capacity(data.len());
+        for &(line, text) in data {
+            let fields = fields(text);
+            let label = parse_label(line, fields.first().copied().unwrap_or_default())?;
+            let mut coords = [0.0; 3];
+            for (coord, field) in coords.iter_mut().zip(fields.iter().skip(1)) {
+                *coord = parse_real(line, field)?;
+            }
+            if self.nodes.insert(label, coords).is_some() {
+                return Err(ExodusError::Other(format!(
+                    "Abaqus line {}: node {} is defined twice",
+                    line, label
+                )));
+            }
+            labels.push(label);
+        }
+        if let Some(name) = keyword.get("NSET") {
+            self.nsets.add(name, &labels, true);
+        }
+        Ok(())
+    }
+
+    fn elements(&mut self, keyword: &Keyword, data: &[(usize, &str)]) -> Result<()> {
+        let element_type = keyword.required("TYPE")?;
+        let kind = element_kind(element_type).ok_or_else(|| {
+            ExodusError::UnsupportedFeature(format!("Abaqus element type {}", element_type))
+        })?;
+        let elset = keyword.get("ELSET").unwrap_or_default();
+
+        // Elements with many nodes continue on the following lines
+        let mut values: Vec<i64> = Vec::new();
+        let mut labels = Vec::new();
+        let mut last_line = keyword.line;
+        for &(line, text) in data {
+            for field in fields(text) {
+                values.push(parse_label(line, field)?);
+            }
+            last_line = line;
+            if values.len() > kind.num_nodes() + 1 {
+                return Err(ExodusError::Other(format!(
+                    "Abaqus line {}: {} element with more than {} nodes",
+                    line,
+                    element_type,
+                    kind.num_nodes()
+                )));
+            }
+            if values.len() == kind.num_nodes() + 1 {
+                labels.push(values[0]);
+                self.elements.push(AbaqusElement {
+                    label: values[0],
+                    element_type: element_type.to_string(),
+                    elset: elset.to_string(),
+                    nodes: values.split_off(1),
+                });
+                values.clear();
+            }
+        }
+        if !values.is_empty() {
+            return Err(ExodusError::Other(format!(
+                "Abaqus line {}: {} element with fewer than {} nodes",
+                last_line,
+                element_type,
+                kind.num_nodes()
+            )));
+        }
+        if !elset.is_empty() {
+            self.elsets.add(elset, &labels, false);
+        }
+        Ok(())
+    }
+
+    /// Labels of a `*NSET` or `*ELSET`: labels and names of other sets, or
+    /// `first, last, increment` with `GENERATE`
+    fn set_labels(
+        &self,
+        keyword: &Keyword,
+        data: &[(usize, &str)],
+        elements: bool,
+    ) -> Result<Vec<i64>> {
+        let mut labels = Vec::new();
+        for &(line, text) in data {
+            let fields = fields(text);
+            if keyword.flag("GENERATE") {
+                if fields.len() < 2 {
+                    return Err(ExodusError::Other(format!(
+                        "Abaqus line {}: GENERATE needs first, last[, increment]",
+                        line
+                    )));
+                }
+                let first = parse_label(line, fields[0])?;
+                let last = parse_label(line, fields[1])?;
+                let step = fields
+                    .get(2)
+                    .map_or(Ok(1), |field| parse_label(line, field))?;
+                if step <= 0 {
+                    return Err(ExodusError::Other(format!(
+                        "Abaqus line {}: GENERATE increment must be positive",
+                        line
+                    )));
+                }
+                labels.extend((first..=last).step_by(step as usize));
+                continue;
+            }
+            for field in fields {
+                if let Ok(label) = field.parse() {
+                    labels.push(label);
+                    continue;
+                }
+                let sets = if elements { &self.elsets } else { &self.nsets };
+                let set = sets.labels(field).ok_or_else(|| {
+                    ExodusError::Other(format!("Abaqus line {}: undefined set {}", line, field))
+                })?;
+                labels.extend_from_slice(set);
+            }
+        }
+        Ok(labels)
+    }
+
+    fn surface(&mut self, keyword: &Keyword, data: &[(usize, &str)]) -> Result<()> {
+        let name = keyword.required("NAME")?;
+        let surface_type = keyword.get("TYPE").unwrap_or("ELEMENT");
+        if !surface_type.eq_ignore_ascii_case("ELEMENT") {
+            return Err(ExodusError::UnsupportedFeature(format!(
+                "Abaqus *SURFACE of TYPE={} at line {}",
+                surface_type, keyword.line
+            )));
+        }
+        let mut faces = Vec::new();
+        for &(line, text) in data {
+            let fields = fields(text);
+            let (Some(target), Some(face)) = (fields.first(), fields.get(1)) else {
+                return Err(ExodusError::UnsupportedFeature(format!(
+                    "Abaqus surface {} without a face label at line {}",
+                    name, line
+                )));
+            };
+            match target.parse::<i64>() {
+                Ok(label) => faces.push((label, face.to_uppercase())),
+                Err(_) => {
+                    let set = self.elsets.labels(target).ok_or_else(|| {
+                        ExodusError::Other(format!(
+                            "Abaqus line {}: undefined element set {}",
+                            line, target
+                        ))
+                    })?;
+                    faces.extend(set.iter().map(|&label| (label, face.to_uppercase())));
+                }
+            }
+        }
+        self.surfaces.push(AbaqusSurface {
+            name: name.to_string(),
+            faces,
+        });
+        Ok(())
+    }
+
+    fn finish(self) -> AbaqusMesh {
+        AbaqusMesh {
+            nodes: self.nodes,
+            elements: self.elements,
+            node_sets: self.nsets.into_exported(),
+            element_sets: self.elsets.into_exported(),
+            surfaces: self.surfaces,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two unit cubes along x, a HEX8 and a HEX20 (with its mid-edge nodes
+    /// numbered 100 + position), with sets and surfaces
+    const BAR: &str = "\
+*HEADING
+Two cubes
+** Nodes
+*NODE, NSET=ALL
+1, 0., 0., 0.
+2, 1., 0., 0.
+3, 1., 1., 0.
+4, 0., 1., 0.
+5, 0., 0., 1.
+6, 1., 0., 1.
+7, 1., 1., 1.
+8, 0., 1., 1.
+9, 2., 0., 0.
+10, 2., 1., 0.
+11, 2., 0., 1.
+12, 2., 1., 1.
+*Node
+109, 1.5, 0., 0.
+110, 2., 0.5, 0.
+111, 1.5, 1., 0.
+112, 1., 0.5, 0.
+113, 1.5, 0., 1.
+114, 2., 0.5, 1.
+115, 1.5, 1., 1.
+116, 1., 0.5, 1.
+117, 1., 0., 0.5
+118, 2., 0., 0.5
+119, 2., 1., 0.5
+120, 1., 1., 0.5
+*ELEMENT, TYPE=C3D8R, ELSET=Steel
+10, 1, 2, 3, 4, 5, 6, 7, 8
+*Element, type=C3D20R, elset=Copper
+20, 2, 9, 10, 3, 6, 11, 12, 7, 109, 110, 111, 112, 113, 114, 115,
+116, 117, 118, 119, 120
+*NSET, NSET=Left
+1, 4, 5, 8
+*NSET, NSET=corners, GENERATE
+1, 12, 1
+*ELSET, ELSET=Both
+Steel, 20
+*SURFACE, NAME=Ends
+10, S6
+Copper, S4
+*Surface, name=Bottom, type=ELEMENT
+Both, S1
+*Solid Section, elset=Steel, material=STEEL
+*STEP
+*STATIC
+*END STEP
+";
+
+    #[test]
+    fn test_parse_deck() {
+        let mesh = AbaqusMesh::parse(BAR).unwrap();
+        assert_eq!(mesh.nodes.len(), 24);
+        assert_eq!(mesh.nodes[&110], [2.0, 0.5, 0.0]);
+        assert_eq!(mesh.elements.len(), 2);
+        assert_eq!(mesh.elements[1].nodes.len(), 20);
+        assert_eq!(mesh.elements[1].elset, "Copper");
+
+        let names: Vec<&str> = mesh.node_sets.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["ALL", "Left", "corners"]);
+        assert_eq!(mesh.node_sets[2].labels, (1..=12).collect::<Vec<_>>());
+        // Only *ELSET definitions are element sets
+        assert_eq!(mesh.element_sets.len(), 1);
+        assert_eq!(mesh.element_sets[0].labels, vec![10, 20]);
+        assert_eq!(
+            mesh.surfaces[0].faces,
+            vec![(10, "S6".to_string()), (20, "S4".to_string())]
+        );
+        assert_eq!(mesh.surfaces[1].faces.len(), 2);
+    }
+
+    #[test]
+    fn test_exodus_parts() {
+        let parts = AbaqusMesh::parse(BAR).unwrap().exodus_parts().unwrap();
+        assert_eq!(parts.num_dim, 3);
+        assert_eq!(parts.elem_ids, vec![10, 20]);
+        assert_eq!(parts.node_ids[12], 109);
+
+        let blocks: Vec<(&str, &str)> = parts
+            .blocks
+            .iter()
+            .map(|b| (b.name.as_str(), b.topology))
+            .collect();
+        assert_eq!(blocks, [("Steel", "HEX8"), ("Copper", "HEX20")]);
+        // Nodes 109.. are numbered 13..; vertical edges come before the top
+        // edges in Exodus
+        assert_eq!(
+            parts.blocks[1].connectivity[8..],
+            [13, 14, 15, 16, 21, 22, 23, 24, 17, 18, 19, 20]
+        );
+
+        // S6 is the -x face of the hex (Exodus side 4), S4 the +x face
+        // (side 2), and S1 the bottom (side 5)
+        assert_eq!(parts.side_sets[0].entries, vec![1, 2]);
+        assert_eq!(parts.side_sets[0].sides, vec![4, 2]);
+        assert_eq!(parts.side_sets[1].sides, vec![5, 5]);
+        assert_eq!(parts.elem_sets[0].entries, vec![1, 2]);
+        assert_eq!(parts.node_sets[1].entries, vec![1, 4, 5, 8]);
+    }
+
+    #[test]
+    fn test_face_labels_match_exodus_sides() {
+        let hex = element_kind("C3D8").unwrap();
+        let sides: Vec<i64> = (1..=6)
+            .map(|face| hex.side(&format!("S{}", face)).unwrap())
+            .collect();
+        assert_eq!(sides, [5, 6, 1, 2, 3, 4]);
+
+        let tet = element_kind("C3D10M").unwrap();
+        let sides: Vec<i64> = (1..=4)
+            .map(|face| tet.side(&format!("S{}", face)).unwrap())
+            .collect();
+        assert_eq!(sides, [4, 1, 2, 3]);
+
+        let wedge = element_kind("C3D15").unwrap();
+        let sides: Vec<i64> = (1..=5)
+            .map(|face| wedge.side(&format!("S{}", face)).unwrap())
+            .collect();
+        assert_eq!(sides, [4, 5, 1, 2, 3]);
+
+        let shell = element_kind("S4R").unwrap();
+        assert_eq!(shell.name, "SHELL4");
+        assert_eq!(
+            (shell.side("SPOS"), shell.side("SNEG"), shell.side("E4")),
+            (Some(1), Some(2), Some(6))
+        );
+        assert_eq!(hex.side("S7"), None);
+
+        let quad = element_kind("CPS4").unwrap();
+        assert!(quad.planar);
+        assert_eq!(quad.side("S3"), Some(3));
+    }
+
+    #[test]
+    fn test_element_types() {
+        let names: Vec<Option<&str>> = [
+            "C3D4", "DC3D8", "CAX8R", "S3", "T3D2", "B32", "C3D27", "SC8R",
+        ]
+        .iter()
+        .map(|t| element_kind(t).map(|kind| kind.name))
+        .collect();
+        assert_eq!(
+            names,
+            [
+                Some("TETRA4"),
+                Some("HEX8"),
+                Some("QUAD8"),
+                Some("TRISHELL3"),
+                Some("TRUSS2"),
+                Some("BEAM3"),
+                None,
+                None
+            ]
+        );
+        for kind in ["C3D20", "C3D15"].iter().filter_map(|t| element_kind(t)) {
+            let mut order = kind.order.to_vec();
+            order.sort_unstable();
+            assert_eq!(order, (0..kind.num_nodes()).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = AbaqusMesh::parse("*ELEMENT, TYPE=C3D27\n1, 2, 3\n").unwrap_err();
+        assert!(matches!(err, ExodusError::UnsupportedFeature(_)));
+
+        let short = "*NODE\n1, 0, 0\n*ELEMENT, TYPE=CPS3\n1, 1, 1\n";
+        assert!(AbaqusMesh::parse(short).is_err());
+
+        let undefined = "*NODE\n1, 0, 0\n*ELEMENT, TYPE=CPS3\n1, 1, 1, 2\n";
+        let mesh = AbaqusMesh::parse(undefined).unwrap();
+        assert!(mesh.exodus_parts().is_err());
+
+        let face = "*NODE\n1\n2\n3\n*ELEMENT, TYPE=CPS3\n1, 1, 2, 3\n*SURFACE, NAME=S\n1, S3\n";
+        let parts = AbaqusMesh::parse(face).unwrap().exodus_parts().unwrap();
+        assert_eq!(
+            (parts.num_dim, parts.side_sets[0].sides.clone()),
+            (2, vec![3])
+        );
+        let mesh = AbaqusMesh::parse(&face.replace("1, S3", "1, S4")).unwrap();
+        assert!(mesh.exodus_parts().is_err());
+    }
+}
@@ -3,9 +3,10 @@
 //! Assemblies provide hierarchical grouping of entities (blocks, sets, etc.)
 //! for organizing complex meshes.
 
-use crate::error::{ExodusError, Result};
-use crate::types::{Assembly, EntityType};
-use crate::utils::constants::MAX_NAME_LENGTH;
+use crate::error::Result;
+use crate::types::Assembly;
+#[cfg(feature = "netcdf4")]
+use crate::{error::ExodusError, types::EntityType, utils::constants::MAX_NAME_LENGTH};
 use crate::{mode, ExodusFile};
 
 #[cfg(feature = "netcdf4")]
@@ -50,6 +51,8 @@ impl ExodusFile<mode::Write> {
             });
         }
 
+        self.metadata.require_netcdf4("Assemblies")?;
+
         // Count existing assemblies by checking for assembly variables
         let mut num_assemblies = 0;
         while self
@@ -274,6 +277,19 @@ impl ExodusFile<mode::Read> {
 // Tests
 // ============================================================================
 
+#[cfg(not(feature = "netcdf4"))]
+impl ExodusFile<mode::Read> {
+    /// Get all assembly IDs (requires the `netcdf4` feature)
+    pub fn assembly_ids(&self) -> Result<Vec<i64>> {
+        Err(crate::backend::netcdf4_only("Assemblies"))
+    }
+
+    /// Get an assembly (requires the `netcdf4` feature)
+    pub fn assembly(&self, _assembly_id: i64) -> Result<Assembly> {
+        Err(crate::backend::netcdf4_only("Assemblies"))
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "netcdf4")]
 mod tests {
@@ -6,14 +6,14 @@
 //! This implementation stores attributes as NetCDF variables, allowing full
 //! support for all data types and multi-value attributes.
 
-use crate::error::{ExodusError, Result};
-use crate::types::{AttributeType, EntityType};
-use crate::utils::constants::MAX_NAME_LENGTH;
-use crate::{mode, ExodusFile};
-
+use crate::types::AttributeType;
 #[cfg(feature = "netcdf4")]
+use crate::{error::ExodusError, utils::constants::MAX_NAME_LENGTH};
+use crate::{error::Result, mode, types::EntityType, ExodusFile};
+
 /// Attribute value data
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AttributeData {
     /// Integer attribute values
     Integer(Vec<i64>),
@@ -110,6 +110,8 @@ impl ExodusFile<mode::Write> {
             });
         }
 
+        self.metadata.require_netcdf4("Entity attributes")?;
+
         // Create unique variable name for this attribute
         let entity_type_str = match entity_type {
             EntityType::ElemBlock => "eb",
@@ -415,6 +417,28 @@ impl ExodusFile<mode::Read> {
 // Tests
 // ============================================================================
 
+#[cfg(not(feature = "netcdf4"))]
+impl ExodusFile<mode::Read> {
+    /// Get an entity attribute (requires the `netcdf4` feature)
+    pub fn attribute(
+        &self,
+        _entity_type: EntityType,
+        _entity_id: i64,
+        _name: &str,
+    ) -> Result<AttributeData> {
+        Err(crate::backend::netcdf4_only("Entity attributes"))
+    }
+
+    /// Get entity attribute names (requires the `netcdf4` feature)
+    pub fn attribute_names(
+        &self,
+        _entity_type: EntityType,
+        _entity_id: i64,
+    ) -> Result<Vec<String>> {
+        Err(crate::backend::netcdf4_only("Entity attributes"))
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "netcdf4")]
 mod tests {
@@ -0,0 +1,985 @@
+//! Pure-Rust reader for NetCDF-3 files
+//!
+//! Implements the classic (CDF-1), 64-bit offset (CDF-2) and 64-bit data
+//! (CDF-5) formats from the NetCDF file format specification. The header is
+//! parsed once when the file is opened; variable data is read on demand, one
+//! contiguous run at a time.
+
+use super::{AttrValue, Backend};
+use crate::error::{ExodusError, Result};
+use crate::types::FileFormat;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Header tag introducing the dimension list
+const NC_DIMENSION: u32 = 0x0A;
+/// Header tag introducing a variable list
+const NC_VARIABLE: u32 = 0x0B;
+/// Header tag introducing an attribute list
+const NC_ATTRIBUTE: u32 = 0x0C;
+/// Magic bytes at the start of HDF5-based (NetCDF-4) files
+const HDF5_MAGIC: &[u8; 4] = b"\x89HDF";
+
+/// External data types of NetCDF-3 variables and attributes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NcType {
+    Byte,
+    Char,
+    Short,
+    Int,
+    Float,
+    Double,
+    UByte,
+    UShort,
+    UInt,
+    Int64,
+    UInt64,
+}
+
+impl NcType {
+    /// Decode a type code; the unsigned and 64-bit types only exist in CDF-5
+    fn from_code(code: u32, version: u8) -> Result<Self> {
+        let ty = match code {
+            1 => NcType::Byte,
+            2 => NcType::Char,
+            3 => NcType::Short,
+            4 => NcType::Int,
+            5 => NcType::Float,
+            6 => NcType::Double,
+            7 if version == 5 => NcType::UByte,
+            8 if version == 5 => NcType::UShort,
+            9 if version == 5 => NcType::UInt,
+            10 if version == 5 => NcType::Int64,
+            11 if version == 5 => NcType::UInt64,
+            _ => return Err(malformed(format!("unknown data type code {}", code))),
+        };
+        Ok(ty)
+    }
+
+    /// Size of one value in bytes
+    fn size(self) -> usize {
+        match self {
+            NcType::Byte | NcType::Char | NcType::UByte => 1,
+            NcType::Short | NcType::UShort => 2,
+            NcType::Int | NcType::UInt | NcType::Float => 4,
+            NcType::Double | NcType::Int64 | NcType::UInt64 => 8,
+        }
+    }
+}
+
+/// Dimension definition; a length of zero marks the record dimension
+#[derive(Debug)]
+struct Dim {
+    name: String,
+    len: usize,
+}
+
+/// Variable definition
+#[derive(Debug)]
+struct Var {
+    name: String,
+    dim_ids: Vec<usize>,
+    attrs: Vec<(String, AttrValue)>,
+    nc_type: NcType,
+    begin: u64,
+    is_record: bool,
+}
+
+/// Where the file bytes come from
+#[derive(Debug)]
+enum Source {
+    File(Mutex<File>),
+    Memory(Vec<u8>),
+}
+
+impl Source {
+    /// Fill `buf` with the bytes starting at `offset`
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<()> {
+        match self {
+            Source::File(file) => {
+                let mut file = file
+                    .lock()
+                    .map_err(|_| ExodusError::Other("NetCDF file handle poisoned".to_string()))?;
+                file.seek(SeekFrom::Start(offset))?;
+                file.read_exact(buf)?;
+                Ok(())
+            }
+            Source::Memory(bytes) => {
+                let start = usize::try_from(offset).map_err(|_| truncated())?;
+                let end = start.checked_add(buf.len()).ok_or_else(truncated)?;
+                let data = bytes.get(start..end).ok_or_else(truncated)?;
+                buf.copy_from_slice(data);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A NetCDF-3 file opened for reading without the NetCDF C library
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use exodus_rs::backend::{Backend, ClassicFile};
+///
+/// let file = ClassicFile::open("mesh.exo")?;
+/// let num_nodes = file.dimension_len("num_nodes").unwrap_or(0);
+/// let x = file.read_all_f64("coordx")?;
+/// # Ok::<(), exodus_rs::ExodusError>(())
+/// ```
+#[derive(Debug)]
+pub struct ClassicFile {
+    source: Source,
+    /// Length of the file in bytes
+    file_len: u64,
+    version: u8,
+    num_records: usize,
+    record_size: u64,
+    dims: Vec<Dim>,
+    attrs: Vec<(String, AttrValue)>,
+    vars: Vec<Var>,
+}
+
+impl ClassicFile {
+    /// Open a NetCDF-3 file on disk
+    ///
+    /// # Errors
+    ///
+    /// - The file cannot be read
+    /// - The file is a NetCDF-4 (HDF5) file ([`ExodusError::UnsupportedFeature`])
+    /// - The header is malformed
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut file = File::open(path.as_ref())?;
+        let file_len = file.metadata()?.len();
+        let header = parse_header(BufReader::new(&mut file), file_len)?;
+        Self::from_header(header, Source::File(Mutex::new(file)), file_len)
+    }
+
+    /// Parse a NetCDF-3 file held in memory
+    ///
+    /// # Errors
+    ///
+    /// - The buffer holds a NetCDF-4 (HDF5) file ([`ExodusError::UnsupportedFeature`])
+    /// - The header is malformed
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        let len = bytes.len() as u64;
+        let header = parse_header(bytes.as_slice(), len)?;
+        Self::from_header(header, Source::Memory(bytes), len)
+    }
+
+    /// Consume the reader and return the complete file contents
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file on disk cannot be read back
+    pub fn into_bytes(self) -> Result<Vec<u8>> {
+        match self.source {
+            Source::File(file) => {
+                let mut file = file
+                    .into_inner()
+                    .map_err(|_| ExodusError::Other("NetCDF file handle poisoned".to_string()))?;
+                let mut bytes = Vec::new();
+                file.seek(SeekFrom::Start(0))?;
+                file.read_to_end(&mut bytes)?;
+                Ok(bytes)
+            }
+            Source::Memory(bytes) => Ok(bytes),
+        }
+    }
+
+    /// On-disk format of the file
+    pub fn format(&self) -> FileFormat {
+        match self.version {
+            1 => FileFormat::NetCdf3Classic,
+            2 => FileFormat::NetCdf364BitOffset,
+            _ => FileFormat::NetCdfCdf5,
+        }
+    }
+
+    /// Number of records along the unlimited dimension
+    pub fn num_records(&self) -> usize {
+        self.num_records
+    }
+
+    /// Storage precision of a floating-point variable, or `None` if the
+    /// variable is missing or not `NC_FLOAT`/`NC_DOUBLE`
+    #[cfg(not(feature = "netcdf4"))]
+    pub(crate) fn float_size(&self, name: &str) -> Option<crate::types::FloatSize> {
+        match self.var(name).ok()?.nc_type {
+            NcType::Float => Some(crate::types::FloatSize::Float32),
+            NcType::Double => Some(crate::types::FloatSize::Float64),
+            _ => None,
+        }
+    }
+
+    fn from_header(header: Header, source: Source, file_len: u64) -> Result<Self> {
+        let Header {
+            version,
+            num_records,
+            dims,
+            attrs,
+            mut vars,
+        } = header;
+
+        let record_dim = dims.iter().position(|d| d.len == 0);
+        for var in &mut vars {
+            if let Some(&id) = var.dim_ids.iter().find(|&&id| id >= dims.len()) {
+                return Err(malformed(format!(
+                    "variable '{}' references undefined dimension {}",
+                    var.name, id
+                )));
+            }
+            var.is_record = record_dim.is_some() && var.dim_ids.first().copied() == record_dim;
+        }
+
+        // Bytes of one record of a record variable, or of a whole fixed
+        // variable. Checking every variable here keeps the offsets computed
+        // by `read_raw` from overflowing.
+        let slab_size = |var: &Var| -> Result<u64> {
+            let fixed_dims = if var.is_record {
+                &var.dim_ids[1..]
+            } else {
+                &var.dim_ids[..]
+            };
+            fixed_dims
+                .iter()
+                .try_fold(var.nc_type.size(), |size, &id| {
+                    size.checked_mul(dims[id].len)
+                })
+                .and_then(|size| u64::try_from(size).ok())
+                .and_then(checked_pad4)
+                .ok_or_else(|| malformed(format!("variable '{}' is too large", var.name)))
+        };
+        for var in &vars {
+            slab_size(var)?;
+        }
+
+        // A record holds one slab of every record variable, each padded to four
+        // bytes unless there is only a single record variable.
+        let record_vars: Vec<&Var> = vars.iter().filter(|v| v.is_record).collect();
+        let record_size = match record_vars.as_slice() {
+            [] => 0,
+            [only] => {
+                let elems: usize = only.dim_ids[1..].iter().map(|&id| dims[id].len).product();
+                (elems * only.nc_type.size()) as u64
+            }
+            many => many.iter().try_fold(0u64, |total, v| {
+                total
+                    .checked_add(slab_size(v)?)
+                    .ok_or_else(|| malformed("record size is too large".to_string()))
+            })?,
+        };
+
+        // Streaming files leave the record count unset; derive it from the file size
+        let num_records = match num_records {
+            Some(n) => n,
+            None if record_size > 0 => {
+                let first = record_vars
+                    .iter()
+                    .map(|v| v.begin)
+                    .min()
+                    .unwrap_or(file_len);
+                (file_len.saturating_sub(first) / record_size) as usize
+            }
+            None => 0,
+        };
+
+        Ok(Self {
+            source,
+            file_len,
+            version,
+            num_records,
+            record_size,
+            dims,
+            attrs,
+            vars,
+        })
+    }
+
+    fn var(&self, name: &str) -> Result<&Var> {
+        self.vars
+            .iter()
+            .find(|v| v.name == name)
+            .ok_or_else(|| ExodusError::VariableNotDefined(name.to_string()))
+    }
+
+    fn shape(&self, var: &Var) -> Vec<usize> {
+        var.dim_ids
+            .iter()
+            .map(|&id| match self.dims[id].len {
+                0 => self.num_records,
+                len => len,
+            })
+            .collect()
+    }
+
+    /// Read the raw big-endian bytes of a hyperslab
+    fn read_raw(&self, var: &Var, start: &[usize], count: &[usize]) -> Result<Vec<u8>> {
+        let shape = self.shape(var);
+        if start.len() != shape.len() || count.len() != shape.len() {
+            return Err(ExodusError::InvalidDimension {
+                expected: format!("{} hyperslab dimensions for '{}'", shape.len(), var.name),
+                actual: start.len().max(count.len()),
+            });
+        }
+        for ((&s, &c), &len) in start.iter().zip(count).zip(&shape) {
+            if s.checked_add(c).map_or(true, |end| end > len) {
+                return Err(ExodusError::Other(format!(
+                    "Hyperslab starting at {} with {} values out of bounds for dimension \
+                     of length {} in '{}'",
+                    s, c, len, var.name
+                )));
+            }
+        }
+
+        // A slab can't hold more bytes than the file, which also bounds the
+        // allocation below for headers declaring huge dimensions
+        let elem_size = var.nc_type.size();
+        let total_bytes = count
+            .iter()
+            .try_fold(elem_size, |size, &c| size.checked_mul(c))
+            .filter(|&size| size as u64 <= self.file_len)
+            .ok_or_else(truncated)?;
+        let mut out = vec![0u8; total_bytes];
+        let mut pos = 0;
+        let mut read_runs = |base: u64, shape: &[usize], start: &[usize], count: &[usize]| {
+            for (offset, len) in contiguous_runs(shape, start, count) {
+                let bytes = len * elem_size;
+                let at = base
+                    .checked_add((offset * elem_size) as u64)
+                    .ok_or_else(truncated)?;
+                self.source.read_at(at, &mut out[pos..pos + bytes])?;
+                pos += bytes;
+            }
+            Ok::<(), ExodusError>(())
+        };
+
+        if var.is_record {
+            for record in start[0]..start[0] + count[0] {
+                let base = (record as u64)
+                    .checked_mul(self.record_size)
+                    .and_then(|offset| offset.checked_add(var.begin))
+                    .ok_or_else(truncated)?;
+                read_runs(base, &shape[1..], &start[1..], &count[1..])?;
+            }
+        } else {
+            read_runs(var.begin, &shape, start, count)?;
+        }
+        Ok(out)
+    }
+}
+
+impl Backend for ClassicFile {
+    fn dimension_len(&self, name: &str) -> Option<usize> {
+        self.dims
+            .iter()
+            .find(|d| d.name == name)
+            .map(|d| match d.len {
+                0 => self.num_records,
+                len => len,
+            })
+    }
+
+    fn variable_names(&self) -> Vec<String> {
+        self.vars.iter().map(|v| v.name.clone()).collect()
+    }
+
+    fn variable_shape(&self, name: &str) -> Option<Vec<usize>> {
+        self.var(name).ok().map(|v| self.shape(v))
+    }
+
+    fn global_attribute(&self, name: &str) -> Option<AttrValue> {
+        find_attr(&self.attrs, name)
+    }
+
+    fn variable_attribute(&self, var: &str, name: &str) -> Option<AttrValue> {
+        self.var(var).ok().and_then(|v| find_attr(&v.attrs, name))
+    }
+
+    fn read_f64(&self, var: &str, start: &[usize], count: &[usize]) -> Result<Vec<f64>> {
+        let var = self.var(var)?;
+        let raw = self.read_raw(var, start, count)?;
+        decode_numeric(var.nc_type, &var.name, &raw, |v| v, |v| v as f64)
+    }
+
+    fn read_i64(&self, var: &str, start: &[usize], count: &[usize]) -> Result<Vec<i64>> {
+        let var = self.var(var)?;
+        let raw = self.read_raw(var, start, count)?;
+        decode_numeric(var.nc_type, &var.name, &raw, |v| v as i64, |v| v)
+    }
+
+    fn read_bytes(&self, var: &str, start: &[usize], count: &[usize]) -> Result<Vec<u8>> {
+        let var = self.var(var)?;
+        if var.nc_type.size() != 1 {
+            return Err(ExodusError::Other(format!(
+                "Variable '{}' is not a character or byte variable",
+                var.name
+            )));
+        }
+        self.read_raw(var, start, count)
+    }
+}
+
+fn find_attr(attrs: &[(String, AttrValue)], name: &str) -> Option<AttrValue> {
+    attrs
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, value)| value.clone())
+}
+
+/// Decode big-endian values, converting floats with `from_float` and integers
+/// with `from_int`
+fn decode_numeric<T>(
+    nc_type: NcType,
+    name: &str,
+    raw: &[u8],
+    from_float: impl Fn(f64) -> T,
+    from_int: impl Fn(i64) -> T,
+) -> Result<Vec<T>> {
+    let chunks = raw.chunks_exact(nc_type.size());
+    let values = match nc_type {
+        NcType::Float => chunks
+            .map(|c| from_float(f32::from_be_bytes([c[0], c[1], c[2], c[3]]) as f64))
+            .collect(),
+        NcType::Double => chunks
+            .map(|c| from_float(f64::from_be_bytes(c.try_into().expect("8-byte chunk"))))
+            .collect(),
+        NcType::Char => {
+            return Err(ExodusError::Other(format!(
+                "Variable '{}' holds characters, not numbers",
+                name
+            )))
+        }
+        ty => chunks.map(|c| from_int(decode_int(ty, c))).collect(),
+    };
+    Ok(values)
+}
+
+/// Decode one big-endian integer of the given type
+fn decode_int(ty: NcType, c: &[u8]) -> i64 {
+    match ty {
+        NcType::Byte => c[0] as i8 as i64,
+        NcType::UByte | NcType::Char => c[0] as i64,
+        NcType::Short => i16::from_be_bytes([c[0], c[1]]) as i64,
+        NcType::UShort => u16::from_be_bytes([c[0], c[1]]) as i64,
+        NcType::Int => i32::from_be_bytes([c[0], c[1], c[2], c[3]]) as i64,
+        NcType::UInt => u32::from_be_bytes([c[0], c[1], c[2], c[3]]) as i64,
+        NcType::Int64 => i64::from_be_bytes(c.try_into().expect("8-byte chunk")),
+        NcType::UInt64 => u64::from_be_bytes(c.try_into().expect("8-byte chunk")) as i64,
+        NcType::Float | NcType::Double => unreachable!("floating-point type decoded as integer"),
+    }
+}
+
+/// Split a hyperslab of a row-major array into contiguous runs
+///
+/// Returns `(element offset, element count)` pairs in slab order. Trailing
+/// dimensions that are read in full are merged into a single run.
+fn contiguous_runs(shape: &[usize], start: &[usize], count: &[usize]) -> Vec<(usize, usize)> {
+    if count.contains(&0) {
+        return Vec::new();
+    }
+    let n = shape.len();
+    if n == 0 {
+        return vec![(0, 1)];
+    }
+
+    let mut strides = vec![1usize; n];
+    for i in (0..n - 1).rev() {
+        strides[i] = strides[i + 1] * shape[i + 1];
+    }
+
+    // Dimensions after `k` are read in full, so each run spans count[k] rows
+    let mut k = n - 1;
+    while k > 0 && start[k] == 0 && count[k] == shape[k] {
+        k -= 1;
+    }
+    let run_len = count[k] * strides[k];
+
+    let mut runs = Vec::new();
+    let mut index = start[..k].to_vec();
+    loop {
+        let offset: usize = index
+            .iter()
+            .zip(&strides)
+            .map(|(i, s)| i * s)
+            .sum::<usize>()
+            + start[k] * strides[k];
+        runs.push((offset, run_len));
+
+        // Advance the outer index like an odometer
+        let mut d = k;
+        loop {
+            if d == 0 {
+                return runs;
+            }
+            d -= 1;
+            index[d] += 1;
+            if index[d] < start[d] + count[d] {
+                break;
+            }
+            index[d] = start[d];
+        }
+    }
+}
+
+fn pad4(n: u64) -> u64 {
+    (n + 3) & !3
+}
+
+/// [`pad4`], or `None` on overflow
+fn checked_pad4(n: u64) -> Option<u64> {
+    n.checked_add(3).map(|n| n & !3)
+}
+
+fn malformed(detail: String) -> ExodusError {
+    ExodusError::Other(format!("Malformed NetCDF-3 header: {}", detail))
+}
+
+fn truncated() -> ExodusError {
+    malformed("unexpected end of file".to_string())
+}
+
+/// Parsed header contents
+struct Header {
+    version: u8,
+    /// `None` for files written in streaming mode
+    num_records: Option<usize>,
+    dims: Vec<Dim>,
+    attrs: Vec<(String, AttrValue)>,
+    vars: Vec<Var>,
+}
+
+/// Sequential reader over the header bytes
+struct HeaderReader<R> {
+    reader: R,
+    version: u8,
+    /// Bytes left in the file, which bounds every count and length read
+    remaining: u64,
+}
+
+impl<R: Read> HeaderReader<R> {
+    fn bytes(&mut self, n: usize) -> Result<Vec<u8>> {
+        if n as u64 > self.remaining {
+            return Err(truncated());
+        }
+        self.remaining -= n as u64;
+        let mut buf = vec![0u8; n];
+        self.reader
+            .read_exact(&mut buf)
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::UnexpectedEof => truncated(),
+                _ => ExodusError::Io(e),
+            })?;
+        Ok(buf)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        let b = self.bytes(8)?;
+        Ok(u64::from_be_bytes(b.try_into().expect("8 bytes")))
+    }
+
+    /// A count or length: 32-bit, or 64-bit in CDF-5
+    fn size(&mut self) -> Result<u64> {
+        if self.version == 5 {
+            self.u64()
+        } else {
+            self.u32().map(u64::from)
+        }
+    }
+
+    fn usize(&mut self) -> Result<usize> {
+        let value = self.size()?;
+        usize::try_from(value).map_err(|_| malformed(format!("size {} too large", value)))
+    }
+
+    /// A file offset: 32-bit in CDF-1, 64-bit otherwise
+    fn offset(&mut self) -> Result<u64> {
+        if self.version == 1 {
+            self.u32().map(u64::from)
+        } else {
+            self.u64()
+        }
+    }
+
+    /// Read `n` bytes followed by padding to a four-byte boundary
+    fn padded(&mut self, n: usize) -> Result<Vec<u8>> {
+        let data = self.bytes(n)?;
+        let padding = pad4(n as u64) as usize - n;
+        if padding > 0 {
+            self.bytes(padding)?;
+        }
+        Ok(data)
+    }
+
+    fn name(&mut self) -> Result<String> {
+        let len = self.usize()?;
+        let bytes = self.padded(len)?;
+        String::from_utf8(bytes).map_err(|_| malformed("name is not valid UTF-8".to_string()))
+    }
+
+    /// Check that `count` entries of at least four bytes each fit in the
+    /// rest of the file
+    fn check_count(&self, count: usize) -> Result<usize> {
+        if count as u64 > self.remaining / 4 {
+            return Err(malformed(format!(
+                "count {} exceeds the size of the file",
+                count
+            )));
+        }
+        Ok(count)
+    }
+
+    /// Read a list header, returning the number of entries
+    fn list(&mut self, tag: u32) -> Result<usize> {
+        let found = self.u32()?;
+        let count = self.usize()?;
+        let count = self.check_count(count)?;
+        match found {
+            0 if count == 0 => Ok(0),
+            t if t == tag => Ok(count),
+            t => Err(malformed(format!(
+                "expected list tag {:#x}, found {:#x}",
+                tag, t
+            ))),
+        }
+    }
+
+    fn attributes(&mut self) -> Result<Vec<(String, AttrValue)>> {
+        let count = self.list(NC_ATTRIBUTE)?;
+        let mut attrs = Vec::new();
+        for _ in 0..count {
+            let name = self.name()?;
+            let ty = NcType::from_code(self.u32()?, self.version)?;
+            let nelems = self.usize()?;
+            let len = nelems
+                .checked_mul(ty.size())
+                .ok_or_else(|| malformed(format!("attribute '{}' is too large", name)))?;
+            let raw = self.padded(len)?;
+            let value = match ty {
+                NcType::Char => {
+                    let text = String::from_utf8_lossy(&raw);
+                    AttrValue::Text(text.trim_end_matches('\0').to_string())
+                }
+                NcType::Float | NcType::Double => {
+                    AttrValue::Floats(decode_numeric(ty, &name, &raw, |v| v, |v| v as f64)?)
+                }
+                _ => AttrValue::Ints(
+                    raw.chunks_exact(ty.size())
+                        .map(|c| decode_int(ty, c))
+                        .collect(),
+                ),
+            };
+            attrs.push((name, value));
+        }
+        Ok(attrs)
+    }
+}
+
+/// Parse the header of a file of `file_len` bytes
+fn parse_header<R: Read>(mut reader: R, file_len: u64) -> Result<Header> {
+    let mut magic = [0u8; 4];
+    reader
+        .read_exact(&mut magic)
+        .map_err(|_| malformed("file too short".to_string()))?;
+    if &magic == HDF5_MAGIC {
+        return Err(ExodusError::UnsupportedFeature(
+            "NetCDF-4 (HDF5) files require the 'netcdf4' feature".to_string(),
+        ));
+    }
+    if &magic[..3] != b"CDF" || !matches!(magic[3], 1 | 2 | 5) {
+        return Err(ExodusError::Other(
+            "Not a NetCDF-3 file (bad magic number)".to_string(),
+        ));
+    }
+
+    let mut r = HeaderReader {
+        reader,
+        version: magic[3],
+        remaining: file_len.saturating_sub(magic.len() as u64),
+    };
+
+    let num_records = r.size()?;
+    let streaming = if r.version == 5 {
+        num_records == u64::MAX
+    } else {
+        num_records == u64::from(u32::MAX)
+    };
+    let num_records = if streaming {
+        None
+    } else {
+        Some(num_records as usize)
+    };
+
+    let num_dims = r.list(NC_DIMENSION)?;
+    let mut dims = Vec::new();
+    for _ in 0..num_dims {
+        let name = r.name()?;
+        let len = r.usize()?;
+        dims.push(Dim { name, len });
+    }
+
+    let attrs = r.attributes()?;
+
+    let num_vars = r.list(NC_VARIABLE)?;
+    let mut vars = Vec::new();
+    for _ in 0..num_vars {
+        let name = r.name()?;
+        let ndims = r.usize()?;
+        let ndims = r.check_count(ndims)?;
+        let dim_ids = (0..ndims).map(|_| r.usize()).collect::<Result<Vec<_>>>()?;
+        let attrs = r.attributes()?;
+        let nc_type = NcType::from_code(r.u32()?, r.version)?;
+        // vsize is redundant (and truncated for very large variables), so recompute it
+        let _vsize = r.size()?;
+        let begin = r.offset()?;
+        vars.push(Var {
+            name,
+            dim_ids,
+            attrs,
+            nc_type,
+            begin,
+            is_record: false,
+        });
+    }
+
+    Ok(Header {
+        version: r.version,
+        num_records,
+        dims,
+        attrs,
+        vars,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal CDF-1 encoder for building test files
+    #[derive(Default)]
+    struct Cdf1 {
+        buf: Vec<u8>,
+    }
+
+    impl Cdf1 {
+        fn u32(&mut self, v: u32) -> &mut Self {
+            self.buf.extend_from_slice(&v.to_be_bytes());
+            self
+        }
+
+        fn padded(&mut self, bytes: &[u8]) -> &mut Self {
+            self.buf.extend_from_slice(bytes);
+            while self.buf.len() % 4 != 0 {
+                self.buf.push(0);
+            }
+            self
+        }
+
+        fn name(&mut self, name: &str) -> &mut Self {
+            self.u32(name.len() as u32).padded(name.as_bytes())
+        }
+    }
+
+    /// Build a file with dims (time = UNLIMITED, n = 3, len = 4), a global
+    /// title, a fixed `double x(n)`, a fixed `char s(len)` and two record
+    /// variables `double t(time)` and `int v(time, n)` with two records.
+    fn sample_file() -> Vec<u8> {
+        let mut f = Cdf1::default();
+        f.buf.extend_from_slice(b"CDF\x01");
+        f.u32(2); // numrecs
+
+        f.u32(NC_DIMENSION).u32(3);
+        f.name("time").u32(0);
+        f.name("n").u32(3);
+        f.name("len").u32(4);
+
+        f.u32(NC_ATTRIBUTE).u32(1);
+        f.name("title").u32(2).u32(5).padded(b"hello");
+
+        // Header size is fixed, so compute data offsets after writing it
+        let vars: [(&str, &[u32], u32, u32); 4] = [
+            ("x", &[1], 6, 24),
+            ("s", &[2], 2, 4),
+            ("t", &[0], 6, 8),
+            ("v", &[0, 1], 4, 12),
+        ];
+        let mut begin_positions = Vec::new();
+        f.u32(NC_VARIABLE).u32(vars.len() as u32);
+        for (name, dims, ty, vsize) in vars {
+            f.name(name).u32(dims.len() as u32);
+            for &d in dims {
+                f.u32(d);
+            }
+            f.u32(0).u32(0); // no attributes
+            f.u32(ty).u32(vsize);
+            begin_positions.push(f.buf.len());
+            f.u32(0);
+        }
+
+        let header_len = f.buf.len() as u32;
+        let begins = [
+            header_len,
+            header_len + 24,
+            header_len + 28,
+            header_len + 36,
+        ];
+        for (pos, begin) in begin_positions.iter().zip(begins) {
+            f.buf[*pos..*pos + 4].copy_from_slice(&begin.to_be_bytes());
+        }
+
+        for x in [1.0f64, 2.5, -3.0] {
+            f.buf.extend_from_slice(&x.to_be_bytes());
+        }
+        f.padded(b"abc\0");
+        // Each record holds t (8 bytes) followed by v (12 bytes)
+        for (t, v) in [(0.5f64, [1i32, 2, 3]), (1.5, [4, 5, 6])] {
+            f.buf.extend_from_slice(&t.to_be_bytes());
+            for x in v {
+                f.buf.extend_from_slice(&x.to_be_bytes());
+            }
+        }
+        f.buf
+    }
+
+    #[test]
+    fn test_contiguous_runs() {
+        assert_eq!(contiguous_runs(&[2, 3], &[0, 0], &[2, 3]), vec![(0, 6)]);
+        assert_eq!(contiguous_runs(&[2, 3], &[1, 0], &[1, 3]), vec![(3, 3)]);
+        assert_eq!(
+            contiguous_runs(&[2, 3], &[0, 1], &[2, 2]),
+            vec![(1, 2), (4, 2)]
+        );
+        assert_eq!(
+            contiguous_runs(&[2, 2, 2], &[0, 1, 0], &[2, 1, 2]),
+            vec![(2, 2), (6, 2)]
+        );
+        assert_eq!(contiguous_runs(&[], &[], &[]), vec![(0, 1)]);
+        assert!(contiguous_runs(&[4], &[0], &[0]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let file = ClassicFile::from_bytes(sample_file()).unwrap();
+        assert_eq!(file.format(), FileFormat::NetCdf3Classic);
+        assert_eq!(file.num_records(), 2);
+        assert_eq!(file.dimension_len("time"), Some(2));
+        assert_eq!(file.dimension_len("n"), Some(3));
+        assert_eq!(file.dimension_len("missing"), None);
+        assert_eq!(file.variable_names(), vec!["x", "s", "t", "v"]);
+        assert_eq!(file.variable_shape("v"), Some(vec![2, 3]));
+        assert_eq!(
+            file.global_attribute("title"),
+            Some(AttrValue::Text("hello".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_read_fixed_and_record_variables() {
+        let file = ClassicFile::from_bytes(sample_file()).unwrap();
+        assert_eq!(file.read_all_f64("x").unwrap(), vec![1.0, 2.5, -3.0]);
+        assert_eq!(file.read_all_i64("x").unwrap(), vec![1, 2, -3]);
+        assert_eq!(file.read_all_bytes("s").unwrap(), b"abc\0".to_vec());
+        assert_eq!(file.read_all_f64("t").unwrap(), vec![0.5, 1.5]);
+        assert_eq!(file.read_all_i64("v").unwrap(), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(file.read_i64("v", &[1, 1], &[1, 2]).unwrap(), vec![5, 6]);
+        assert_eq!(
+            file.read_f64("v", &[0, 2], &[2, 1]).unwrap(),
+            vec![3.0, 6.0]
+        );
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let file = ClassicFile::from_bytes(sample_file()).unwrap();
+        assert!(matches!(
+            file.read_all_f64("missing"),
+            Err(ExodusError::VariableNotDefined(_))
+        ));
+        assert!(file.read_i64("v", &[2, 0], &[1, 3]).is_err());
+        assert!(file.read_f64("s", &[0], &[4]).is_err());
+        assert!(file.read_bytes("x", &[0], &[3]).is_err());
+    }
+
+    #[test]
+    fn test_reject_non_netcdf3() {
+        assert!(matches!(
+            ClassicFile::from_bytes(b"\x89HDF\r\n\x1a\n".to_vec()),
+            Err(ExodusError::UnsupportedFeature(_))
+        ));
+        assert!(ClassicFile::from_bytes(b"NOPE".to_vec()).is_err());
+        assert!(ClassicFile::from_bytes(sample_file()[..40].to_vec()).is_err());
+    }
+
+    /// Build a file with one fixed `double x` over dimensions of the given
+    /// lengths and no data
+    fn file_with_dims(lens: &[u32]) -> Vec<u8> {
+        let mut f = Cdf1::default();
+        f.buf.extend_from_slice(b"CDF\x01");
+        f.u32(0);
+        f.u32(NC_DIMENSION).u32(lens.len() as u32);
+        for (i, &len) in lens.iter().enumerate() {
+            f.name(&format!("d{}", i)).u32(len);
+        }
+        f.u32(0).u32(0);
+        f.u32(NC_VARIABLE).u32(1);
+        f.name("x").u32(lens.len() as u32);
+        for i in 0..lens.len() as u32 {
+            f.u32(i);
+        }
+        f.u32(0).u32(0);
+        f.u32(6).u32(0);
+        let begin = f.buf.len() as u32 + 4;
+        f.u32(begin);
+        f.buf
+    }
+
+    #[test]
+    fn test_reject_oversized_counts() {
+        // CDF-5 header declaring 2^60 dimensions
+        let mut header = b"CDF\x05".to_vec();
+        header.extend_from_slice(&0u64.to_be_bytes());
+        header.extend_from_slice(&NC_DIMENSION.to_be_bytes());
+        header.extend_from_slice(&(1u64 << 60).to_be_bytes());
+        assert!(matches!(
+            ClassicFile::from_bytes(header),
+            Err(ExodusError::Other(_))
+        ));
+
+        // CDF-5 global attribute whose byte length overflows
+        let mut header = b"CDF\x05".to_vec();
+        header.extend_from_slice(&0u64.to_be_bytes());
+        header.extend_from_slice(&[0; 12]);
+        header.extend_from_slice(&NC_ATTRIBUTE.to_be_bytes());
+        header.extend_from_slice(&1u64.to_be_bytes());
+        header.extend_from_slice(&1u64.to_be_bytes());
+        header.extend_from_slice(b"a\0\0\0");
+        header.extend_from_slice(&6u32.to_be_bytes());
+        header.extend_from_slice(&(u64::MAX / 4).to_be_bytes());
+        assert!(ClassicFile::from_bytes(header).is_err());
+
+        // Dimension name longer than the file
+        let mut f = Cdf1::default();
+        f.buf.extend_from_slice(b"CDF\x01");
+        f.u32(0).u32(NC_DIMENSION).u32(1).u32(u32::MAX);
+        assert!(ClassicFile::from_bytes(f.buf.clone()).is_err());
+    }
+
+    #[test]
+    fn test_reject_oversized_variables() {
+        // The element count overflows
+        assert!(ClassicFile::from_bytes(file_with_dims(&[u32::MAX; 3])).is_err());
+
+        // The variable fits in memory arithmetic but not in the file
+        let file = ClassicFile::from_bytes(file_with_dims(&[1 << 30])).unwrap();
+        assert!(file.read_all_f64("x").is_err());
+        assert_eq!(file.read_f64("x", &[4], &[0]).unwrap(), Vec::<f64>::new());
+        assert!(file.read_f64("x", &[usize::MAX], &[2]).is_err());
+    }
+}
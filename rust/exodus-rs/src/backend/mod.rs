@@ -0,0 +1,313 @@
+//! Storage backends for Exodus files
+//!
+//! Exodus II is layered on NetCDF. By default the crate uses the `netcdf`
+//! crate, which links the C NetCDF/HDF5 libraries and supports every on-disk
+//! format. This module also provides [`ClassicFile`], a pure-Rust reader for
+//! the NetCDF-3 formats (classic, 64-bit offset and CDF5) that needs no
+//! system libraries.
+//!
+//! When the `netcdf4` feature is disabled, `ExodusFile<mode::Read>` is backed
+//! by [`ClassicFile`] and the read API works on NetCDF-3 Exodus files. Creating
+//! or appending to files, and reading NetCDF-4 only data (assemblies, blobs and
+//! entity attributes), return [`ExodusError::UnsupportedFeature`].
+//!
+//! Both backends implement the [`Backend`] trait, which exposes the small set
+//! of read primitives that Exodus needs. The read API of [`ExodusFile`] is
+//! written once against this trait and works with either backend.
+//!
+//! [`ExodusFile`]: crate::ExodusFile
+//!
+//! [`ExodusError::UnsupportedFeature`]: crate::ExodusError::UnsupportedFeature
+
+use crate::error::Result;
+
+mod classic;
+
+pub use classic::ClassicFile;
+
+/// Error for data that only NetCDF-4 files can hold
+#[cfg(not(feature = "netcdf4"))]
+pub(crate) fn netcdf4_only(feature: &str) -> crate::ExodusError {
+    crate::ExodusError::UnsupportedFeature(format!(
+        "{} require NetCDF-4 files and the 'netcdf4' feature",
+        feature
+    ))
+}
+
+/// Value of a NetCDF attribute
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AttrValue {
+    /// Text (`NC_CHAR` or `NC_STRING`) attribute
+    Text(String),
+    /// Integer attribute of any width
+    Ints(Vec<i64>),
+    /// Floating-point attribute
+    Floats(Vec<f64>),
+}
+
+impl AttrValue {
+    /// Text value, if this is a text attribute
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttrValue::Text(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// First value as an integer, if this is a numeric attribute
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            AttrValue::Ints(v) => v.first().copied(),
+            AttrValue::Floats(v) => v.first().map(|&x| x as i64),
+            AttrValue::Text(_) => None,
+        }
+    }
+
+    /// First value as a float, if this is a numeric attribute
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            AttrValue::Ints(v) => v.first().map(|&x| x as f64),
+            AttrValue::Floats(v) => v.first().copied(),
+            AttrValue::Text(_) => None,
+        }
+    }
+}
+
+/// Read primitives shared by all storage backends
+///
+/// Hyperslabs are given as `start` and `count` arrays with one entry per
+/// variable dimension, in NetCDF (row-major) order. Numeric data is converted
+/// to the requested type regardless of its on-disk type.
+pub trait Backend {
+    /// Length of a dimension, or `None` if it is not defined
+    fn dimension_len(&self, name: &str) -> Option<usize>;
+
+    /// Names of all variables, in definition order
+    fn variable_names(&self) -> Vec<String>;
+
+    /// Shape of a variable, or `None` if it is not defined
+    fn variable_shape(&self, name: &str) -> Option<Vec<usize>>;
+
+    /// Value of a global attribute
+    fn global_attribute(&self, name: &str) -> Option<AttrValue>;
+
+    /// Value of a variable attribute
+    fn variable_attribute(&self, var: &str, name: &str) -> Option<AttrValue>;
+
+    /// Read a hyperslab of a numeric variable as `f64`
+    fn read_f64(&self, var: &str, start: &[usize], count: &[usize]) -> Result<Vec<f64>>;
+
+    /// Read a hyperslab of a numeric variable as `i64`
+    fn read_i64(&self, var: &str, start: &[usize], count: &[usize]) -> Result<Vec<i64>>;
+
+    /// Read a hyperslab of a character or byte variable
+    fn read_bytes(&self, var: &str, start: &[usize], count: &[usize]) -> Result<Vec<u8>>;
+
+    /// Whether a variable is defined
+    fn has_variable(&self, name: &str) -> bool {
+        self.variable_shape(name).is_some()
+    }
+
+    /// Read an entire numeric variable as `f64`
+    fn read_all_f64(&self, var: &str) -> Result<Vec<f64>> {
+        let count = self.require_shape(var)?;
+        self.read_f64(var, &vec![0; count.len()], &count)
+    }
+
+    /// Read an entire numeric variable as `i64`
+    fn read_all_i64(&self, var: &str) -> Result<Vec<i64>> {
+        let count = self.require_shape(var)?;
+        self.read_i64(var, &vec![0; count.len()], &count)
+    }
+
+    /// Read an entire character or byte variable
+    fn read_all_bytes(&self, var: &str) -> Result<Vec<u8>> {
+        let count = self.require_shape(var)?;
+        self.read_bytes(var, &vec![0; count.len()], &count)
+    }
+
+    /// Read a text variable as one trimmed string per row
+    ///
+    /// Character arrays are split along their last dimension, which holds the
+    /// string length.
+    fn read_strings(&self, var: &str) -> Result<Vec<String>> {
+        let shape = self.require_shape(var)?;
+        let width = shape.last().copied().unwrap_or(0);
+        Ok(char_rows(&self.read_all_bytes(var)?, width))
+    }
+
+    /// Shape of a variable, or [`ExodusError::VariableNotDefined`]
+    ///
+    /// [`ExodusError::VariableNotDefined`]: crate::ExodusError::VariableNotDefined
+    fn require_shape(&self, var: &str) -> Result<Vec<usize>> {
+        self.variable_shape(var)
+            .ok_or_else(|| crate::ExodusError::VariableNotDefined(var.to_string()))
+    }
+}
+
+#[cfg(feature = "netcdf4")]
+impl Backend for netcdf::File {
+    fn dimension_len(&self, name: &str) -> Option<usize> {
+        self.dimension(name).map(|d| d.len())
+    }
+
+    fn variable_names(&self) -> Vec<String> {
+        self.variables().map(|v| v.name()).collect()
+    }
+
+    fn variable_shape(&self, name: &str) -> Option<Vec<usize>> {
+        self.variable(name)
+            .map(|v| v.dimensions().iter().map(|d| d.len()).collect())
+    }
+
+    fn global_attribute(&self, name: &str) -> Option<AttrValue> {
+        self.attribute(name)
+            .and_then(|attr| attr.value().ok())
+            .and_then(netcdf_attr_value)
+    }
+
+    fn variable_attribute(&self, var: &str, name: &str) -> Option<AttrValue> {
+        let var = self.variable(var)?;
+        let value = var.attribute(name)?.value().ok()?;
+        netcdf_attr_value(value)
+    }
+
+    fn read_f64(&self, var: &str, start: &[usize], count: &[usize]) -> Result<Vec<f64>> {
+        let v = self
+            .variable(var)
+            .ok_or_else(|| crate::ExodusError::VariableNotDefined(var.to_string()))?;
+        Ok(v.get_values::<f64, _>((start, count))?)
+    }
+
+    fn read_i64(&self, var: &str, start: &[usize], count: &[usize]) -> Result<Vec<i64>> {
+        let v = self
+            .variable(var)
+            .ok_or_else(|| crate::ExodusError::VariableNotDefined(var.to_string()))?;
+        Ok(v.get_values::<i64, _>((start, count))?)
+    }
+
+    fn read_bytes(&self, var: &str, start: &[usize], count: &[usize]) -> Result<Vec<u8>> {
+        let v = self
+            .variable(var)
+            .ok_or_else(|| crate::ExodusError::VariableNotDefined(var.to_string()))?;
+        Ok(v.get_raw_values((start, count))?)
+    }
+
+    fn read_strings(&self, var: &str) -> Result<Vec<String>> {
+        let v = self
+            .variable(var)
+            .ok_or_else(|| crate::ExodusError::VariableNotDefined(var.to_string()))?;
+        // NetCDF-4 files may store names as NC_STRING instead of NC_CHAR
+        if v.vartype() == netcdf::types::NcVariableType::String {
+            return (0..v.len())
+                .map(|i| {
+                    let text = v.get_string(i..i + 1)?;
+                    Ok(text.trim_end_matches('\0').trim().to_string())
+                })
+                .collect();
+        }
+        let width = v.dimensions().last().map(|d| d.len()).unwrap_or(0);
+        Ok(char_rows(&v.get_raw_values(..)?, width))
+    }
+}
+
+/// Split a fixed-width character array into trimmed strings
+fn char_rows(bytes: &[u8], width: usize) -> Vec<String> {
+    if width == 0 {
+        return Vec::new();
+    }
+    bytes
+        .chunks(width)
+        .map(|row| {
+            let end = row.iter().position(|&b| b == 0).unwrap_or(row.len());
+            String::from_utf8_lossy(&row[..end]).trim().to_string()
+        })
+        .collect()
+}
+
+/// Forward [`Backend`] to the `netcdf::File` behind a handle type
+#[cfg(feature = "netcdf4")]
+macro_rules! deref_backend {
+    ($handle:ty) => {
+        impl Backend for $handle {
+            fn dimension_len(&self, name: &str) -> Option<usize> {
+                Backend::dimension_len(&**self, name)
+            }
+
+            fn variable_names(&self) -> Vec<String> {
+                Backend::variable_names(&**self)
+            }
+
+            fn variable_shape(&self, name: &str) -> Option<Vec<usize>> {
+                Backend::variable_shape(&**self, name)
+            }
+
+            fn global_attribute(&self, name: &str) -> Option<AttrValue> {
+                Backend::global_attribute(&**self, name)
+            }
+
+            fn variable_attribute(&self, var: &str, name: &str) -> Option<AttrValue> {
+                Backend::variable_attribute(&**self, var, name)
+            }
+
+            fn read_f64(&self, var: &str, start: &[usize], count: &[usize]) -> Result<Vec<f64>> {
+                Backend::read_f64(&**self, var, start, count)
+            }
+
+            fn read_i64(&self, var: &str, start: &[usize], count: &[usize]) -> Result<Vec<i64>> {
+                Backend::read_i64(&**self, var, start, count)
+            }
+
+            fn read_bytes(&self, var: &str, start: &[usize], count: &[usize]) -> Result<Vec<u8>> {
+                Backend::read_bytes(&**self, var, start, count)
+            }
+
+            fn read_strings(&self, var: &str) -> Result<Vec<String>> {
+                Backend::read_strings(&**self, var)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "netcdf4")]
+deref_backend!(netcdf::FileMut);
+#[cfg(feature = "netcdf4")]
+deref_backend!(crate::file::ReadOnlyHandle);
+
+/// Convert a `netcdf` attribute value to an [`AttrValue`]
+#[cfg(feature = "netcdf4")]
+fn netcdf_attr_value(value: netcdf::AttributeValue) -> Option<AttrValue> {
+    use netcdf::AttributeValue as V;
+
+    fn ints<T: Into<i64>>(values: Vec<T>) -> Option<AttrValue> {
+        Some(AttrValue::Ints(
+            values.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    match value {
+        V::Str(s) => Some(AttrValue::Text(s)),
+        V::Strs(s) => Some(AttrValue::Text(s.join("\n"))),
+        V::Uchar(v) => ints(vec![v]),
+        V::Uchars(v) => ints(v),
+        V::Schar(v) => ints(vec![v]),
+        V::Schars(v) => ints(v),
+        V::Ushort(v) => ints(vec![v]),
+        V::Ushorts(v) => ints(v),
+        V::Short(v) => ints(vec![v]),
+        V::Shorts(v) => ints(v),
+        V::Uint(v) => ints(vec![v]),
+        V::Uints(v) => ints(v),
+        V::Int(v) => ints(vec![v]),
+        V::Ints(v) => ints(v),
+        V::Longlong(v) => ints(vec![v]),
+        V::Longlongs(v) => ints(v),
+        V::Ulonglong(v) => Some(AttrValue::Ints(vec![v as i64])),
+        V::Ulonglongs(v) => Some(AttrValue::Ints(v.into_iter().map(|x| x as i64).collect())),
+        V::Float(v) => Some(AttrValue::Floats(vec![v as f64])),
+        V::Floats(v) => Some(AttrValue::Floats(v.into_iter().map(f64::from).collect())),
+        V::Double(v) => Some(AttrValue::Floats(vec![v])),
+        V::Doubles(v) => Some(AttrValue::Floats(v)),
+    }
+}
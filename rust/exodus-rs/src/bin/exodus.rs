@@ -0,0 +1,414 @@
+//! `exodus`: inspect the contents of an Exodus II file
+//!
+//! Prints what a file holds, like the SEACAS `explore` tool or `ncdump -h`:
+//! the initialization parameters, the blocks and sets with their sizes, the
+//! ID maps, the variables with their truth tables, the time steps, the QA and
+//! info records, and the assemblies and blobs. Bulk data such as coordinates
+//! and variable values is not printed.
+//!
+//! With `--json` the same information is printed as one JSON document, built
+//! on [`MeshManifest`].
+//!
+//! ```text
+//! exodus mesh.e
+//! exodus results.e --json --maps > results.json
+//! ```
+
+use clap::Parser;
+use exodus_rs::manifest::MeshManifest;
+use exodus_rs::{mode, Assembly, Blob, EntityType, ExodusError, ExodusFile, Result, TruthTable};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// ID maps listed by the inspector
+const MAP_TYPES: [EntityType; 4] = [
+    EntityType::NodeMap,
+    EntityType::ElemMap,
+    EntityType::EdgeMap,
+    EntityType::FaceMap,
+];
+
+/// Variable types that have truth tables
+const TRUTH_TABLE_TYPES: [EntityType; 8] = [
+    EntityType::ElemBlock,
+    EntityType::EdgeBlock,
+    EntityType::FaceBlock,
+    EntityType::NodeSet,
+    EntityType::SideSet,
+    EntityType::ElemSet,
+    EntityType::EdgeSet,
+    EntityType::FaceSet,
+];
+
+/// Inspect the contents of an Exodus II file
+#[derive(Parser, Debug)]
+#[command(name = "exodus", version)]
+struct Cli {
+    /// Exodus file to inspect
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+
+    /// Print a JSON document instead of text
+    #[arg(long)]
+    json: bool,
+
+    /// Print the full ID maps instead of their ranges
+    #[arg(long)]
+    maps: bool,
+
+    /// Print every time value instead of the first and last
+    #[arg(long)]
+    times: bool,
+}
+
+/// Everything printed about a file
+#[derive(Debug, Serialize)]
+struct Inspection {
+    path: PathBuf,
+    version: String,
+    #[serde(flatten)]
+    manifest: MeshManifest,
+    truth_tables: Vec<TruthTable>,
+    maps: Vec<MapSummary>,
+    assemblies: Vec<Assembly>,
+    blobs: Vec<BlobSummary>,
+}
+
+/// Range of one ID map, with the IDs themselves if requested
+#[derive(Debug, Serialize)]
+struct MapSummary {
+    entity_type: EntityType,
+    len: usize,
+    min: Option<i64>,
+    max: Option<i64>,
+    /// Whether the map is 1, 2, ..., len
+    sequential: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ids: Option<Vec<i64>>,
+}
+
+/// A blob and the size of its data
+#[derive(Debug, Serialize)]
+struct BlobSummary {
+    #[serde(flatten)]
+    blob: Blob,
+    size: usize,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("exodus: {}: {}", cli.file.display(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<()> {
+    let inspection = inspect(cli)?;
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(&inspection)?);
+    } else {
+        print_text(&inspection, cli.times);
+    }
+    Ok(())
+}
+
+fn inspect(cli: &Cli) -> Result<Inspection> {
+    let file = ExodusFile::<mode::Read>::open(&cli.file)?;
+    let manifest = MeshManifest::from_file(&file)?;
+    let (major, minor) = file.version()?;
+
+    let mut truth_tables = Vec::new();
+    for var_type in TRUTH_TABLE_TYPES {
+        if !manifest.variable_names(var_type).is_empty() {
+            truth_tables.push(file.truth_table(var_type)?);
+        }
+    }
+
+    let mut maps = Vec::new();
+    for entity_type in MAP_TYPES {
+        let ids = match file.id_map(entity_type) {
+            Err(ExodusError::VariableNotDefined(_)) => continue,
+            result => result?,
+        };
+        maps.push(MapSummary {
+            entity_type,
+            len: ids.len(),
+            min: ids.iter().min().copied(),
+            max: ids.iter().max().copied(),
+            sequential: ids.iter().zip(1..).all(|(&id, n)| id == n),
+            ids: cli.maps.then_some(ids),
+        });
+    }
+
+    let mut assemblies = Vec::new();
+    for id in netcdf4_ids(file.assembly_ids())? {
+        assemblies.push(file.assembly(id)?);
+    }
+    let mut blobs = Vec::new();
+    for id in netcdf4_ids(file.blob_ids())? {
+        let (blob, data) = file.blob(id)?;
+        blobs.push(BlobSummary {
+            blob,
+            size: data.len(),
+        });
+    }
+
+    Ok(Inspection {
+        path: cli.file.clone(),
+        version: format!("{}.{}", major, minor),
+        manifest,
+        truth_tables,
+        maps,
+        assemblies,
+        blobs,
+    })
+}
+
+/// IDs of entities only NetCDF-4 files can hold, none if unsupported
+fn netcdf4_ids(ids: Result<Vec<i64>>) -> Result<Vec<i64>> {
+    match ids {
+        Err(ExodusError::UnsupportedFeature(_)) => Ok(Vec::new()),
+        result => result,
+    }
+}
+
+fn print_text(inspection: &Inspection, all_times: bool) {
+    let manifest = &inspection.manifest;
+    let params = &manifest.params;
+    println!("File:       {}", inspection.path.display());
+    println!("Version:    {}", inspection.version);
+    println!("Title:      {}", params.title);
+    println!("Dimensions: {}", params.num_dim);
+    println!("Nodes:      {}", params.num_nodes);
+    println!("Elements:   {}", params.num_elems);
+    for (what, count) in [
+        ("Edges", params.num_edges),
+        ("Faces", params.num_faces),
+        ("Edge blocks", params.num_edge_blocks),
+        ("Face blocks", params.num_face_blocks),
+        ("Edge sets", params.num_edge_sets),
+        ("Face sets", params.num_face_sets),
+        ("Element sets", params.num_elem_sets),
+    ] {
+        if count > 0 {
+            println!("{:<11} {}", format!("{}:", what), count);
+        }
+    }
+
+    println!();
+    println!("Element blocks: {}", manifest.blocks.len());
+    for entry in &manifest.blocks {
+        let block = &entry.block;
+        println!(
+            "  {:>8} {:<16} {:<8} {} elements, {} nodes/element, {} attributes",
+            block.id,
+            display_name(&entry.name),
+            block.topology,
+            block.num_entries,
+            block.num_nodes_per_entry,
+            block.num_attributes
+        );
+        if !entry.attribute_names.is_empty() {
+            println!(
+                "           attributes: {}",
+                entry.attribute_names.join(", ")
+            );
+        }
+    }
+
+    for set_type in [
+        EntityType::NodeSet,
+        EntityType::SideSet,
+        EntityType::ElemSet,
+        EntityType::EdgeSet,
+        EntityType::FaceSet,
+    ] {
+        let sets: Vec<_> = manifest
+            .sets
+            .iter()
+            .filter(|entry| entry.set.entity_type == set_type)
+            .collect();
+        if sets.is_empty() && !matches!(set_type, EntityType::NodeSet | EntityType::SideSet) {
+            continue;
+        }
+        println!("{}s: {}", title(set_type), sets.len());
+        for entry in sets {
+            println!(
+                "  {:>8} {:<16} {} entries, {} distribution factors",
+                entry.set.id,
+                display_name(&entry.name),
+                entry.set.num_entries,
+                entry.set.num_dist_factors
+            );
+        }
+    }
+
+    if !inspection.maps.is_empty() {
+        println!();
+        println!("ID maps:");
+        for map in &inspection.maps {
+            match (map.min, map.max) {
+                (Some(min), Some(max)) => println!(
+                    "  {:<10} {} IDs, {} to {}{}",
+                    map.entity_type.as_str(),
+                    map.len,
+                    min,
+                    max,
+                    if map.sequential { " (sequential)" } else { "" }
+                ),
+                _ => println!("  {:<10} empty", map.entity_type.as_str()),
+            }
+            if let Some(ids) = &map.ids {
+                for chunk in ids.chunks(10) {
+                    let line: Vec<String> = chunk.iter().map(i64::to_string).collect();
+                    println!("    {}", line.join(" "));
+                }
+            }
+        }
+    }
+
+    println!();
+    if manifest.variables.is_empty() {
+        println!("Variables: none");
+    }
+    for variables in &manifest.variables {
+        println!(
+            "{} variables: {}",
+            title(variables.var_type),
+            variables.names.len()
+        );
+        for (index, name) in variables.names.iter().enumerate() {
+            println!("  {:>4} {}", index + 1, name);
+        }
+        if let Some(table) = inspection
+            .truth_tables
+            .iter()
+            .find(|table| table.var_type == variables.var_type)
+        {
+            print_truth_table(inspection, table);
+        }
+    }
+
+    println!();
+    let times = &manifest.times;
+    match (times.first(), times.last()) {
+        (Some(first), Some(last)) => {
+            println!("Time steps: {} (time {} to {})", times.len(), first, last)
+        }
+        _ => println!("Time steps: 0"),
+    }
+    if all_times {
+        for (step, time) in times.iter().enumerate() {
+            println!("  {:>6} {}", step + 1, time);
+        }
+    }
+
+    if !manifest.qa_records.is_empty() {
+        println!();
+        println!("QA records:");
+        for qa in &manifest.qa_records {
+            println!(
+                "  {:<32} {:<16} {} {}",
+                qa.code_name, qa.code_version, qa.date, qa.time
+            );
+        }
+    }
+    if !manifest.info_records.is_empty() {
+        println!();
+        println!("Info records:");
+        for info in &manifest.info_records {
+            println!("  {}", info);
+        }
+    }
+
+    if !inspection.assemblies.is_empty() {
+        println!();
+        println!("Assemblies: {}", inspection.assemblies.len());
+        for assembly in &inspection.assemblies {
+            println!(
+                "  {:>8} {:<16} {} {}: {:?}",
+                assembly.id,
+                display_name(&assembly.name),
+                assembly.entity_list.len(),
+                assembly.entity_type,
+                assembly.entity_list
+            );
+        }
+    }
+    if !inspection.blobs.is_empty() {
+        println!();
+        println!("Blobs: {}", inspection.blobs.len());
+        for blob in &inspection.blobs {
+            println!(
+                "  {:>8} {:<16} {} bytes",
+                blob.blob.id,
+                display_name(&blob.blob.name),
+                blob.size
+            );
+        }
+    }
+}
+
+/// Print a truth table as one row per block or set, `x` marking the
+/// variables it defines
+fn print_truth_table(inspection: &Inspection, table: &TruthTable) {
+    let ids: Vec<i64> = if matches!(
+        table.var_type,
+        EntityType::ElemBlock | EntityType::EdgeBlock | EntityType::FaceBlock
+    ) {
+        inspection
+            .manifest
+            .blocks
+            .iter()
+            .filter(|entry| entry.block.entity_type == table.var_type)
+            .map(|entry| entry.block.id)
+            .collect()
+    } else {
+        inspection
+            .manifest
+            .sets
+            .iter()
+            .filter(|entry| entry.set.entity_type == table.var_type)
+            .map(|entry| entry.set.id)
+            .collect()
+    };
+    println!("  truth table:");
+    for row in 0..table.num_blocks {
+        let marks: String = (0..table.num_vars)
+            .map(|var| if table.get(row, var) { 'x' } else { '.' })
+            .collect();
+        match ids.get(row) {
+            Some(id) => println!("  {:>8} {}", id, marks),
+            None => println!("  {:>8} {}", format!("#{}", row + 1), marks),
+        }
+    }
+}
+
+fn title(entity_type: EntityType) -> &'static str {
+    match entity_type {
+        EntityType::Global => "Global",
+        EntityType::Nodal => "Nodal",
+        EntityType::ElemBlock => "Element block",
+        EntityType::EdgeBlock => "Edge block",
+        EntityType::FaceBlock => "Face block",
+        EntityType::NodeSet => "Node set",
+        EntityType::SideSet => "Side set",
+        EntityType::ElemSet => "Element set",
+        EntityType::EdgeSet => "Edge set",
+        EntityType::FaceSet => "Face set",
+        _ => entity_type.as_str(),
+    }
+}
+
+fn display_name(name: &str) -> String {
+    if name.is_empty() {
+        "-".to_string()
+    } else {
+        format!("\"{}\"", name)
+    }
+}
@@ -3,9 +3,10 @@
 //! Blobs provide storage for arbitrary binary data associated with the mesh,
 //! such as images, embedded documents, or custom application data.
 
-use crate::error::{ExodusError, Result};
+use crate::error::Result;
 use crate::types::Blob;
-use crate::utils::constants::MAX_NAME_LENGTH;
+#[cfg(feature = "netcdf4")]
+use crate::{error::ExodusError, utils::constants::MAX_NAME_LENGTH};
 use crate::{mode, ExodusFile};
 
 #[cfg(feature = "netcdf4")]
@@ -49,6 +50,8 @@ impl ExodusFile<mode::Write> {
             });
         }
 
+        self.metadata.require_netcdf4("Blobs")?;
+
         // Count existing blobs by checking for blob variables
         let mut num_blobs = 0;
         while self
@@ -239,6 +242,19 @@ impl ExodusFile<mode::Read> {
 // Tests
 // ============================================================================
 
+#[cfg(not(feature = "netcdf4"))]
+impl ExodusFile<mode::Read> {
+    /// Get all blob IDs (requires the `netcdf4` feature)
+    pub fn blob_ids(&self) -> Result<Vec<i64>> {
+        Err(crate::backend::netcdf4_only("Blobs"))
+    }
+
+    /// Get a blob and its data (requires the `netcdf4` feature)
+    pub fn blob(&self, _blob_id: i64) -> Result<(Blob, Vec<u8>)> {
+        Err(crate::backend::netcdf4_only("Blobs"))
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "netcdf4")]
 mod tests {
@@ -3,13 +3,15 @@
 //! This module provides operations for element, edge, and face blocks.
 //! Blocks define the topology and connectivity of mesh entities.
 
+use crate::backend::Backend;
 use crate::error::{EntityId, ExodusError, Result};
+#[cfg(feature = "netcdf4")]
+use crate::mode;
 use crate::types::{Block, Connectivity, EntityType, Topology};
 use crate::utils::naming;
-use crate::{mode, ExodusFile, FileMode};
+use crate::{ExodusFile, FileMode};
 
 // Common block operations (available in all modes)
-#[cfg(feature = "netcdf4")]
 impl<M: FileMode> ExodusFile<M> {
     /// Get all block IDs of a given type
     ///
@@ -27,9 +29,8 @@ impl<M: FileMode> ExodusFile<M> {
     pub fn block_ids(&self, entity_type: EntityType) -> Result<Vec<EntityId>> {
         let id_var_name = naming::prop_id_var(entity_type);
 
-        if let Some(var) = self.nc_file.variable(id_var_name) {
-            let ids: Vec<i64> = var.get_values(..)?;
-            Ok(ids)
+        if self.nc_file.has_variable(id_var_name) {
+            self.nc_file.read_all_i64(id_var_name)
         } else {
             Ok(Vec::new())
         }
@@ -76,18 +77,10 @@ impl<M: FileMode> ExodusFile<M> {
     ///
     /// Flat array of node IDs
     pub fn connectivity(&self, block_id: EntityId) -> Result<Vec<i64>> {
-        let block_index = self.find_block_index(EntityType::ElemBlock, block_id)?;
+        let block_index = self.entity_index(EntityType::ElemBlock, block_id)?;
         let conn_var_name = naming::connectivity_var(block_index);
 
-        let var = self
-            .nc_file
-            .variable(&conn_var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(conn_var_name.clone()))?;
-
-        let conn_i32: Vec<i32> = var.get_values(..)?;
-        let conn: Vec<i64> = conn_i32.iter().map(|&x| x as i64).collect();
-
-        Ok(conn)
+        self.nc_file.read_all_i64(&conn_var_name)
     }
 
     /// Get connectivity as structured data
@@ -169,12 +162,11 @@ impl<M: FileMode> ExodusFile<M> {
     ///
     /// Flat array of attribute values
     pub fn block_attributes(&self, block_id: EntityId) -> Result<Vec<f64>> {
-        let block_index = self.find_block_index(EntityType::ElemBlock, block_id)?;
+        let block_index = self.entity_index(EntityType::ElemBlock, block_id)?;
         let attr_var_name = naming::block_attribute_var(block_index);
 
-        if let Some(var) = self.nc_file.variable(&attr_var_name) {
-            let attrs: Vec<f64> = var.get_values(..)?;
-            Ok(attrs)
+        if self.nc_file.has_variable(&attr_var_name) {
+            self.nc_file.read_all_f64(&attr_var_name)
         } else {
             Ok(Vec::new())
         }
@@ -190,28 +182,11 @@ impl<M: FileMode> ExodusFile<M> {
     ///
     /// Vector of attribute names
     pub fn block_attribute_names(&self, block_id: EntityId) -> Result<Vec<String>> {
-        let block_index = self.find_block_index(EntityType::ElemBlock, block_id)?;
+        let block_index = self.entity_index(EntityType::ElemBlock, block_id)?;
         let attr_name_var = naming::block_attribute_name_var(block_index);
 
-        if let Some(var) = self.nc_file.variable(&attr_name_var) {
-            let block = self.block(block_id)?;
-            let mut names = Vec::with_capacity(block.num_attributes);
-
-            for i in 0..block.num_attributes {
-                let bytes: Vec<i8> = var.get_values((i..i + 1, 0..33))?;
-                let name = String::from_utf8_lossy(
-                    &bytes
-                        .iter()
-                        .take_while(|&&b| b != 0)
-                        .map(|&b| b as u8)
-                        .collect::<Vec<u8>>(),
-                )
-                .trim()
-                .to_string();
-                names.push(name);
-            }
-
-            Ok(names)
+        if self.nc_file.has_variable(&attr_name_var) {
+            self.nc_file.read_strings(&attr_name_var)
         } else {
             Ok(Vec::new())
         }
@@ -219,38 +194,25 @@ impl<M: FileMode> ExodusFile<M> {
 
     // Internal helper to get block info
     fn get_block_info(&self, entity_type: EntityType, block_id: EntityId) -> Result<Block> {
-        let block_index = self.find_block_index(entity_type, block_id)?;
+        let block_index = self.entity_index(entity_type, block_id)?;
         let conn_var_name = naming::connectivity_var(block_index);
 
-        let var = self
-            .nc_file
-            .variable(&conn_var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(conn_var_name.clone()))?;
+        let dims = self.nc_file.require_shape(&conn_var_name)?;
 
         // Get topology from attribute
-        let topology = var
-            .attribute("elem_type")
-            .and_then(|attr| {
-                if let Ok(netcdf::AttributeValue::Str(s)) = attr.value() {
-                    Some(s)
-                } else {
-                    None
-                }
-            })
+        let topology = self
+            .nc_file
+            .variable_attribute(&conn_var_name, "elem_type")
+            .and_then(|value| value.as_str().map(str::to_string))
             .unwrap_or_else(|| "UNKNOWN".to_string());
 
         // Get dimensions
-        let dims = var.dimensions();
-        let num_entries = dims.first().map(|d| d.len()).unwrap_or(0);
-        let num_nodes_per_entry = dims.get(1).map(|d| d.len()).unwrap_or(0);
+        let num_entries = dims.first().copied().unwrap_or(0);
+        let num_nodes_per_entry = dims.get(1).copied().unwrap_or(0);
 
         // Check for attributes
         let attr_dim_name = naming::block_attributes_dim(block_index);
-        let num_attributes = self
-            .nc_file
-            .dimension(&attr_dim_name)
-            .map(|d| d.len())
-            .unwrap_or(0);
+        let num_attributes = self.nc_file.dimension_len(&attr_dim_name).unwrap_or(0);
 
         Ok(Block {
             id: block_id,
@@ -263,26 +225,6 @@ impl<M: FileMode> ExodusFile<M> {
             num_attributes,
         })
     }
-
-    // Internal helper to find block index by ID
-    fn find_block_index(&self, entity_type: EntityType, block_id: EntityId) -> Result<usize> {
-        let id_var_name = naming::prop_id_var(entity_type);
-
-        if let Some(var) = self.nc_file.variable(id_var_name) {
-            let ids: Vec<i64> = var.get_values(..)?;
-            ids.iter()
-                .position(|&id| id == block_id)
-                .ok_or_else(|| ExodusError::EntityNotFound {
-                    entity_type: entity_type.to_string(),
-                    id: block_id,
-                })
-        } else {
-            Err(ExodusError::EntityNotFound {
-                entity_type: entity_type.to_string(),
-                id: block_id,
-            })
-        }
-    }
 }
 
 // Block operations for write mode
@@ -329,6 +271,9 @@ impl ExodusFile<mode::Write> {
             return Err(ExodusError::NotInitialized);
         }
 
+        self.metadata
+            .check_int_range(&format!("{} ID", block.entity_type), &[block.id])?;
+
         // Validate block type
         match block.entity_type {
             EntityType::ElemBlock | EntityType::EdgeBlock | EntityType::FaceBlock => {}
@@ -364,9 +309,11 @@ impl ExodusFile<mode::Write> {
 
         // Create connectivity variable
         let conn_var_name = naming::connectivity_var(block_index);
-        let mut conn_var = self
-            .nc_file
-            .add_variable::<i32>(&conn_var_name, &[&dim_name_entries, &dim_name_nodes])?;
+        let mut conn_var = self.nc_file.add_variable_with_type(
+            &conn_var_name,
+            &[&dim_name_entries, &dim_name_nodes],
+            &self.metadata.int_type(),
+        )?;
 
         // Apply chunking if configured (clamp to dimension size to avoid NC_EBADCHUNK)
         let requested_chunk = self
@@ -385,17 +332,11 @@ impl ExodusFile<mode::Write> {
             // Second dimension is typically small (nodes per element), so use full size
             conn_var.set_chunking(&[chunk_size, block.num_nodes_per_entry])?;
         }
+        self.metadata.apply_compression(&mut conn_var)?;
 
         // Set topology attribute
         conn_var.put_attribute("elem_type", block.topology.as_str())?;
 
-        // Set block ID
-        let id_var_name = naming::prop_id_var(block.entity_type);
-        if let Some(mut id_var) = self.nc_file.variable_mut(id_var_name) {
-            // Use put_values with a slice instead of put_value
-            id_var.put_values(&[block.id], block_index..block_index + 1)?;
-        }
-
         // Create attribute variable if needed
         if block.num_attributes > 0 {
             let attr_dim_name = naming::block_attributes_dim(block_index);
@@ -403,15 +344,28 @@ impl ExodusFile<mode::Write> {
                 .add_dimension(&attr_dim_name, block.num_attributes)?;
 
             let attr_var_name = naming::block_attribute_var(block_index);
-            let mut attr_var = self
-                .nc_file
-                .add_variable::<f64>(&attr_var_name, &[&dim_name_entries, &attr_dim_name])?;
+            let mut attr_var = self.nc_file.add_variable_with_type(
+                &attr_var_name,
+                &[&dim_name_entries, &attr_dim_name],
+                &self.metadata.float_type(),
+            )?;
 
             // Apply same chunking to attributes as connectivity (chunk_size already clamped above)
             if chunk_size > 0 {
                 attr_var.set_chunking(&[chunk_size, block.num_attributes])?;
             }
+            self.metadata.apply_compression(&mut attr_var)?;
+        }
+
+        self.finish_definitions()?;
+
+        // Set block ID
+        let id_var_name = naming::prop_id_var(block.entity_type);
+        if let Some(mut id_var) = self.nc_file.variable_mut(id_var_name) {
+            // Use put_values with a slice instead of put_value
+            id_var.put_values(&[block.id], block_index..block_index + 1)?;
         }
+        self.metadata.invalidate_entity_indices(block.entity_type);
 
         Ok(())
     }
@@ -445,15 +399,15 @@ impl ExodusFile<mode::Write> {
         let (block_index, _entity_type) = self.find_block_in_any_type_write(block_id)?;
         let conn_var_name = naming::connectivity_var(block_index);
 
+        self.metadata
+            .check_int_range(&format!("connectivity of block {}", block_id), connectivity)?;
+
         let mut var = self.nc_file.variable_mut(&conn_var_name).ok_or_else(|| {
             ExodusError::VariableNotDefined(format!("Connectivity variable {}", conn_var_name))
         })?;
 
-        // Convert i64 to i32 for NetCDF
-        let conn_i32: Vec<i32> = connectivity.iter().map(|&x| x as i32).collect();
-
         // Write the full connectivity array
-        var.put_values(&conn_i32, ..)?;
+        var.put_values(connectivity, ..)?;
 
         Ok(())
     }
@@ -471,6 +425,7 @@ impl ExodusFile<mode::Write> {
     /// - Array length mismatch
     /// - Block has no attributes defined
     pub fn put_block_attributes(&mut self, block_id: EntityId, attributes: &[f64]) -> Result<()> {
+        self.ensure_data_mode()?;
         let (block_index, _entity_type) = self.find_block_in_any_type_write(block_id)?;
         let attr_var_name = naming::block_attribute_var(block_index);
 
@@ -494,11 +449,12 @@ impl ExodusFile<mode::Write> {
         block_id: EntityId,
         names: &[impl AsRef<str>],
     ) -> Result<()> {
-        let block_index = self.find_block_index_write(EntityType::ElemBlock, block_id)?;
+        let block_index = self.entity_index(EntityType::ElemBlock, block_id)?;
         let attr_name_var = naming::block_attribute_name_var(block_index);
 
         // Create the variable if it doesn't exist
         if self.nc_file.variable(&attr_name_var).is_none() {
+            self.ensure_define_mode()?;
             let dim_name = naming::block_attributes_dim(block_index);
             let len_string = "len_string";
 
@@ -512,6 +468,7 @@ impl ExodusFile<mode::Write> {
         }
 
         // Write attribute names
+        self.ensure_data_mode()?;
         let mut var = self.nc_file.variable_mut(&attr_name_var).unwrap();
         for (i, name) in names.iter().enumerate() {
             let name_str = name.as_ref();
@@ -541,38 +498,18 @@ impl ExodusFile<mode::Write> {
         Ok(count)
     }
 
-    /// Find block index by ID (Write mode specific - uses naming helper)
-    fn find_block_index_write(&self, entity_type: EntityType, block_id: EntityId) -> Result<usize> {
-        let id_var_name = naming::prop_id_var(entity_type);
-
-        if let Some(var) = self.nc_file.variable(id_var_name) {
-            let ids: Vec<i64> = var.get_values(..)?;
-            ids.iter()
-                .position(|&id| id == block_id)
-                .ok_or_else(|| ExodusError::EntityNotFound {
-                    entity_type: entity_type.to_string(),
-                    id: block_id,
-                })
-        } else {
-            Err(ExodusError::EntityNotFound {
-                entity_type: entity_type.to_string(),
-                id: block_id,
-            })
-        }
-    }
-
     /// Find a block by ID across all block types (ElemBlock, EdgeBlock, FaceBlock)
     fn find_block_in_any_type_write(&self, block_id: EntityId) -> Result<(usize, EntityType)> {
         // Try ElemBlock first
-        if let Ok(index) = self.find_block_index_write(EntityType::ElemBlock, block_id) {
+        if let Ok(index) = self.entity_index(EntityType::ElemBlock, block_id) {
             return Ok((index, EntityType::ElemBlock));
         }
         // Try EdgeBlock
-        if let Ok(index) = self.find_block_index_write(EntityType::EdgeBlock, block_id) {
+        if let Ok(index) = self.entity_index(EntityType::EdgeBlock, block_id) {
             return Ok((index, EntityType::EdgeBlock));
         }
         // Try FaceBlock
-        if let Ok(index) = self.find_block_index_write(EntityType::FaceBlock, block_id) {
+        if let Ok(index) = self.entity_index(EntityType::FaceBlock, block_id) {
             return Ok((index, EntityType::FaceBlock));
         }
         // Not found in any block type
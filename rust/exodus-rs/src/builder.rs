@@ -3,9 +3,13 @@
 //! This module provides a fluent, ergonomic interface for creating Exodus meshes
 //! without dealing with low-level file operations.
 
-use crate::error::Result;
-use crate::types::{Block, CreateMode, CreateOptions, EntityType, InitParams};
+use crate::error::{ExodusError, Result};
+#[cfg(feature = "netcdf4")]
+use crate::types::{Block, EntityType, InitParams, Set};
+use crate::types::{CreateMode, CreateOptions};
+use crate::utils::constants::MAX_TITLE_LENGTH;
 use crate::{mode, ExodusFile};
+use std::collections::HashSet;
 use std::path::Path;
 
 /// High-level mesh builder with fluent API
@@ -31,6 +35,11 @@ pub struct MeshBuilder {
     num_dim: usize,
     coords: Option<(Vec<f64>, Vec<f64>, Vec<f64>)>,
     blocks: Vec<BlockBuilder>,
+    node_sets: Vec<NodeSetBuilder>,
+    side_sets: Vec<SideSetBuilder>,
+    elem_sets: Vec<ElemSetBuilder>,
+    node_id_map: Option<Vec<i64>>,
+    elem_id_map: Option<Vec<i64>>,
     qa_records: Vec<(String, String, String, String)>,
     info_records: Vec<String>,
 }
@@ -49,6 +58,11 @@ impl MeshBuilder {
             num_dim: 3,
             coords: None,
             blocks: Vec::new(),
+            node_sets: Vec::new(),
+            side_sets: Vec::new(),
+            elem_sets: Vec::new(),
+            node_id_map: None,
+            elem_id_map: None,
             qa_records: Vec::new(),
             info_records: Vec::new(),
         }
@@ -97,6 +111,74 @@ impl MeshBuilder {
         self
     }
 
+    /// Add a node set
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder.add_node_set(
+    ///     NodeSetBuilder::new(10)
+    ///         .nodes(vec![1, 2, 3, 4])
+    ///         .name("inlet")
+    ///         .build()
+    /// )
+    /// ```
+    pub fn add_node_set(mut self, node_set: NodeSetBuilder) -> Self {
+        self.node_sets.push(node_set);
+        self
+    }
+
+    /// Add a side set
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder.add_side_set(
+    ///     SideSetBuilder::new(20)
+    ///         .sides(vec![(1, 1), (1, 2)])
+    ///         .name("wall")
+    ///         .build()
+    /// )
+    /// ```
+    pub fn add_side_set(mut self, side_set: SideSetBuilder) -> Self {
+        self.side_sets.push(side_set);
+        self
+    }
+
+    /// Add an element set
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder.add_elem_set(
+    ///     ElemSetBuilder::new(30)
+    ///         .elements(vec![1, 2])
+    ///         .name("weld")
+    ///         .build()
+    /// )
+    /// ```
+    pub fn add_elem_set(mut self, elem_set: ElemSetBuilder) -> Self {
+        self.elem_sets.push(elem_set);
+        self
+    }
+
+    /// Set the node ID map: the user-facing ID of each node, in node order
+    ///
+    /// Without a map, nodes are identified by their 1-based position.
+    pub fn node_id_map(mut self, ids: Vec<i64>) -> Self {
+        self.node_id_map = Some(ids);
+        self
+    }
+
+    /// Set the element ID map: the user-facing ID of each element, in block
+    /// order
+    ///
+    /// Without a map, elements are identified by their 1-based position.
+    pub fn elem_id_map(mut self, ids: Vec<i64>) -> Self {
+        self.elem_id_map = Some(ids);
+        self
+    }
+
     /// Add a QA record for provenance tracking
     ///
     /// # Arguments
@@ -149,8 +231,48 @@ impl MeshBuilder {
     ///
     /// Allows specifying file format, compression, etc.
     pub fn write_with_options<P: AsRef<Path>>(self, path: P, options: CreateOptions) -> Result<()> {
-        let mut file = ExodusFile::<mode::Write>::create(path, options)?;
+        self.validate()?;
+        let file = ExodusFile::<mode::Write>::create(path, options)?;
+        self.write_to(file)
+    }
+
+    /// Check the mesh before any file is created
+    ///
+    /// Rejects titles longer than the Exodus limit, connectivity that is not a
+    /// whole number of elements, and IDs repeated within blocks or a set type.
+    fn validate(&self) -> Result<()> {
+        if self.title.len() > MAX_TITLE_LENGTH {
+            return Err(ExodusError::StringTooLong {
+                max: MAX_TITLE_LENGTH,
+                actual: self.title.len(),
+            });
+        }
+        for block in &self.blocks {
+            if block.nodes_per_elem == 0 || block.connectivity.len() % block.nodes_per_elem != 0 {
+                return Err(ExodusError::Other(format!(
+                    "Block {} ({}): connectivity length {} is not a multiple of {} nodes per element",
+                    block.id,
+                    block.topology,
+                    block.connectivity.len(),
+                    block.nodes_per_elem
+                )));
+            }
+        }
+        check_unique_ids(self.blocks.iter().map(|b| b.id))?;
+        check_unique_ids(self.node_sets.iter().map(|s| s.id))?;
+        check_unique_ids(self.side_sets.iter().map(|s| s.id))?;
+        check_unique_ids(self.elem_sets.iter().map(|s| s.id))
+    }
 
+    /// Without NetCDF no writable file can be created, so this is unreachable
+    #[cfg(not(feature = "netcdf4"))]
+    fn write_to(self, file: ExodusFile<mode::Write>) -> Result<()> {
+        match file.nc_file {}
+    }
+
+    /// Write the mesh into a newly created file
+    #[cfg(feature = "netcdf4")]
+    fn write_to(self, mut file: ExodusFile<mode::Write>) -> Result<()> {
         // Determine counts
         let num_nodes = self.coords.as_ref().map(|(x, _, _)| x.len()).unwrap_or(0);
 
@@ -166,6 +288,9 @@ impl MeshBuilder {
             num_nodes,
             num_elems,
             num_elem_blocks: self.blocks.len(),
+            num_node_sets: self.node_sets.len(),
+            num_side_sets: self.side_sets.len(),
+            num_elem_sets: self.elem_sets.len(),
             ..Default::default()
         };
         file.init(&params)?;
@@ -176,8 +301,16 @@ impl MeshBuilder {
             let z_opt = if z.is_empty() { None } else { Some(&z[..]) };
             file.put_coords(&x, y_opt, z_opt)?;
         }
+        if let Some(ids) = &self.node_id_map {
+            file.put_id_map(EntityType::NodeMap, ids)?;
+        }
 
         // Write blocks
+        let block_names: Vec<String> = self
+            .blocks
+            .iter()
+            .map(|block| block.name.clone().unwrap_or_default())
+            .collect();
         for block_builder in self.blocks {
             let block = Block {
                 id: block_builder.id,
@@ -203,6 +336,54 @@ impl MeshBuilder {
             }
         }
 
+        if block_names.iter().any(|name| !name.is_empty()) {
+            file.put_names(EntityType::ElemBlock, &block_names)?;
+        }
+        if let Some(ids) = &self.elem_id_map {
+            file.put_id_map(EntityType::ElemMap, ids)?;
+        }
+
+        // Write sets, then their names
+        for ns in &self.node_sets {
+            file.put_node_set(ns.id, &ns.nodes, ns.dist_factors.as_deref())?;
+        }
+        let names: Vec<String> = self
+            .node_sets
+            .iter()
+            .map(|ns| ns.name.clone().unwrap_or_default())
+            .collect();
+        if names.iter().any(|name| !name.is_empty()) {
+            file.put_names(EntityType::NodeSet, &names)?;
+        }
+        for ss in &self.side_sets {
+            file.put_side_set(ss.id, &ss.elements, &ss.sides, ss.dist_factors.as_deref())?;
+        }
+        let names: Vec<String> = self
+            .side_sets
+            .iter()
+            .map(|ss| ss.name.clone().unwrap_or_default())
+            .collect();
+        if names.iter().any(|name| !name.is_empty()) {
+            file.put_names(EntityType::SideSet, &names)?;
+        }
+        for es in &self.elem_sets {
+            file.put_set(&Set {
+                id: es.id,
+                entity_type: EntityType::ElemSet,
+                num_entries: es.elements.len(),
+                num_dist_factors: 0,
+            })?;
+            file.put_entity_set(EntityType::ElemSet, es.id, &es.elements)?;
+        }
+        let names: Vec<String> = self
+            .elem_sets
+            .iter()
+            .map(|es| es.name.clone().unwrap_or_default())
+            .collect();
+        if names.iter().any(|name| !name.is_empty()) {
+            file.put_names(EntityType::ElemSet, &names)?;
+        }
+
         // Write QA records if any (skip for now - not fully implemented)
         #[cfg(feature = "netcdf4")]
         if !self.qa_records.is_empty() {
@@ -231,6 +412,17 @@ impl MeshBuilder {
     }
 }
 
+/// Fail if an ID appears more than once
+fn check_unique_ids(ids: impl Iterator<Item = i64>) -> Result<()> {
+    let mut seen = HashSet::new();
+    for id in ids {
+        if !seen.insert(id) {
+            return Err(ExodusError::Other(format!("Duplicate ID {}", id)));
+        }
+    }
+    Ok(())
+}
+
 /// Builder for element blocks
 ///
 /// # Example
@@ -248,6 +440,7 @@ pub struct BlockBuilder {
     topology: String,
     connectivity: Vec<i64>,
     nodes_per_elem: usize,
+    name: Option<String>,
     attributes: Vec<f64>,
     attribute_names: Vec<String>,
 }
@@ -268,6 +461,7 @@ impl BlockBuilder {
             topology,
             connectivity: Vec::new(),
             nodes_per_elem,
+            name: None,
             attributes: Vec::new(),
             attribute_names: Vec::new(),
         }
@@ -291,6 +485,12 @@ impl BlockBuilder {
         self
     }
 
+    /// Set the name of this block
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
     /// Set element attributes
     ///
     /// Attributes are floating-point values associated with each element.
@@ -488,6 +688,57 @@ impl SideSetBuilder {
     }
 }
 
+/// Builder for element sets
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let elem_set = ElemSetBuilder::new(1)
+///     .elements(vec![1, 2, 5])
+///     .name("weld")
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ElemSetBuilder {
+    id: i64,
+    elements: Vec<i64>,
+    name: Option<String>,
+}
+
+impl ElemSetBuilder {
+    /// Create a new element set builder
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Element set ID (must be unique)
+    pub fn new(id: i64) -> Self {
+        Self {
+            id,
+            elements: Vec::new(),
+            name: None,
+        }
+    }
+
+    /// Set the element numbers in this set
+    ///
+    /// Element numbers are 1-based positions in block order.
+    pub fn elements(mut self, elements: Vec<i64>) -> Self {
+        self.elements = elements;
+        self
+    }
+
+    /// Set the name of this element set
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Build the element set (consumes the builder)
+    pub fn build(self) -> Self {
+        self
+    }
+}
+
 /// Builder for appending data to existing Exodus files
 ///
 /// This builder provides a fluent API for modifying existing Exodus files,
@@ -614,6 +865,15 @@ impl AppendBuilder {
         self
     }
 
+    /// Apply all pending changes to the file (requires the `netcdf4` feature)
+    #[cfg(not(feature = "netcdf4"))]
+    pub fn apply(self) -> Result<ExodusFile<mode::Append>> {
+        match self.file.nc_file {}
+    }
+}
+
+#[cfg(feature = "netcdf4")]
+impl AppendBuilder {
     /// Apply all pending changes to the file
     ///
     /// This method writes all accumulated changes (node sets, side sets,
@@ -678,8 +938,7 @@ impl AppendBuilder {
         // Set name if provided
         if let Some(ref name) = ns.name {
             // Get the index of the node set we just added
-            let ids = self.file.set_ids(EntityType::NodeSet)?;
-            if let Some(index) = ids.iter().position(|&id| id == ns.id) {
+            if let Ok(index) = self.file.entity_index(EntityType::NodeSet, ns.id) {
                 writer.put_name(EntityType::NodeSet, index, name)?;
             }
         }
@@ -701,8 +960,7 @@ impl AppendBuilder {
         // Set name if provided
         if let Some(ref name) = ss.name {
             // Get the index of the side set we just added
-            let ids = self.file.set_ids(EntityType::SideSet)?;
-            if let Some(index) = ids.iter().position(|&id| id == ss.id) {
+            if let Ok(index) = self.file.entity_index(EntityType::SideSet, ss.id) {
                 writer.put_name(EntityType::SideSet, index, name)?;
             }
         }
@@ -712,6 +970,7 @@ impl AppendBuilder {
 }
 
 #[cfg(test)]
+#[cfg(feature = "netcdf4")]
 mod tests {
     use super::*;
     use tempfile::NamedTempFile;
@@ -765,6 +1024,50 @@ mod tests {
         assert_eq!(params.num_elem_blocks, 1);
     }
 
+    #[test]
+    fn test_mesh_builder_sets_and_names() {
+        let tmp = NamedTempFile::new().unwrap();
+
+        MeshBuilder::new("Single Hex With Sets")
+            .dimensions(3)
+            .coordinates(
+                vec![0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0],
+                vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0],
+                vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0],
+            )
+            .add_block(
+                BlockBuilder::new(100, "HEX8")
+                    .connectivity(vec![1, 2, 3, 4, 5, 6, 7, 8])
+                    .name("steel")
+                    .build(),
+            )
+            .add_node_set(NodeSetBuilder::new(1).nodes(vec![1, 2, 3, 4]).build())
+            .add_side_set(
+                SideSetBuilder::new(2)
+                    .sides(vec![(1, 6)])
+                    .name("top")
+                    .build(),
+            )
+            .add_elem_set(ElemSetBuilder::new(3).elements(vec![1]).name("all").build())
+            .node_id_map((101..=108).collect())
+            .elem_id_map(vec![7])
+            .write(tmp.path())
+            .unwrap();
+
+        let file = ExodusFile::<mode::Read>::open(tmp.path()).unwrap();
+        let params = file.init_params().unwrap();
+        assert_eq!((params.num_node_sets, params.num_side_sets), (1, 1));
+        assert_eq!(file.node_set(1).unwrap().nodes, vec![1, 2, 3, 4]);
+        assert_eq!(file.side_set(2).unwrap().sides, vec![6]);
+        assert_eq!(file.names(EntityType::ElemBlock).unwrap(), vec!["steel"]);
+        assert_eq!(file.names(EntityType::SideSet).unwrap(), vec!["top"]);
+        let elem_set = file.entity_set(EntityType::ElemSet, 3).unwrap();
+        assert_eq!(elem_set.entities, vec![1]);
+        assert_eq!(file.names(EntityType::ElemSet).unwrap(), vec!["all"]);
+        assert_eq!(file.id_map(EntityType::NodeMap).unwrap()[7], 108);
+        assert_eq!(file.id_map(EntityType::ElemMap).unwrap(), vec![7]);
+    }
+
     #[test]
     fn test_multiple_blocks() {
         let tmp = NamedTempFile::new().unwrap();
@@ -3,8 +3,11 @@
 //! This module provides functionality for reading and writing nodal coordinates
 //! in Exodus files. Coordinates can be stored as either f32 or f64 values.
 
+use crate::backend::Backend;
 use crate::error::{ExodusError, Result};
 use crate::utils::constants::*;
+#[cfg(feature = "netcdf4")]
+use crate::utils::netcdf_ext::{to_nc_chars, NcChar};
 use crate::{mode, ExodusFile};
 
 /// Trait for coordinate value types
@@ -79,6 +82,7 @@ fn coord_var_name(dim: usize) -> Result<&'static str> {
 /// assert_eq!(coord, [0.0, 0.0, 0.0]);
 /// ```
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coordinates<T: CoordValue> {
     /// X coordinates for all nodes
     pub x: Vec<T>,
@@ -317,6 +321,7 @@ impl ExodusFile<mode::Write> {
 
     /// Write coordinates for a specific dimension
     fn put_coord_dim<T: CoordValue>(&mut self, dim: usize, coords: &[T]) -> Result<()> {
+        self.ensure_data_mode()?;
         let var_name = coord_var_name(dim)?;
 
         // Get or create the variable
@@ -444,6 +449,7 @@ impl ExodusFile<mode::Write> {
         start: usize,
         coords: &[T],
     ) -> Result<()> {
+        self.ensure_data_mode()?;
         let var_name = coord_var_name(dim)?;
 
         let mut var = self
@@ -517,23 +523,26 @@ impl ExodusFile<mode::Write> {
 
         // Create or get len_name dimension
         if self.nc_file.dimension(DIM_LEN_NAME).is_none() {
+            self.ensure_define_mode()?;
             self.nc_file.add_dimension(DIM_LEN_NAME, MAX_NAME_LENGTH)?;
         }
 
         // Create coordinate names variable: coor_names(num_dim, len_name)
         if self.nc_file.variable(VAR_COOR_NAMES).is_none() {
+            self.ensure_define_mode()?;
             self.nc_file
-                .add_variable::<u8>(VAR_COOR_NAMES, &[DIM_NUM_DIM, DIM_LEN_NAME])?;
+                .add_variable::<NcChar>(VAR_COOR_NAMES, &[DIM_NUM_DIM, DIM_LEN_NAME])?;
         }
 
         // Write each coordinate name
+        self.ensure_data_mode()?;
         if let Some(mut var) = self.nc_file.variable_mut(VAR_COOR_NAMES) {
             for (i, name) in names.iter().enumerate() {
                 let mut buf = vec![0u8; MAX_NAME_LENGTH];
                 let bytes = name.as_bytes();
                 let copy_len = bytes.len().min(MAX_NAME_LENGTH);
                 buf[..copy_len].copy_from_slice(&bytes[..copy_len]);
-                var.put_values(&buf, (i..i + 1, ..))?;
+                var.put_values(&to_nc_chars(&buf), (i..i + 1, ..))?;
             }
         }
 
@@ -542,7 +551,6 @@ impl ExodusFile<mode::Write> {
 }
 
 // Reader methods
-#[cfg(feature = "netcdf4")]
 impl ExodusFile<mode::Read> {
     /// Read all coordinates
     ///
@@ -570,17 +578,12 @@ impl ExodusFile<mode::Read> {
     /// ```
     pub fn coords<T: CoordValue>(&self) -> Result<Coordinates<T>> {
         // Handle the case where num_nodes is 0 (dimension may not exist)
-        let num_nodes = self
-            .nc_file
-            .dimension(DIM_NUM_NODES)
-            .map(|d| d.len())
-            .unwrap_or(0);
+        let num_nodes = self.nc_file.dimension_len(DIM_NUM_NODES).unwrap_or(0);
 
         let num_dim = self
             .nc_file
-            .dimension("num_dim")
-            .ok_or_else(|| ExodusError::Other("num_dim dimension not found".to_string()))?
-            .len();
+            .dimension_len(DIM_NUM_DIM)
+            .ok_or_else(|| ExodusError::Other("num_dim dimension not found".to_string()))?;
 
         // Return empty vectors if num_nodes is 0
         if num_nodes == 0 {
@@ -649,17 +652,12 @@ impl ExodusFile<mode::Read> {
         use ndarray::{Array2, Zip};
 
         // Get dimensions directly from NetCDF to avoid intermediate allocations
-        let num_nodes = self
-            .nc_file
-            .dimension(DIM_NUM_NODES)
-            .map(|d| d.len())
-            .unwrap_or(0);
+        let num_nodes = self.nc_file.dimension_len(DIM_NUM_NODES).unwrap_or(0);
 
         let num_dim = self
             .nc_file
-            .dimension("num_dim")
-            .ok_or_else(|| ExodusError::Other("num_dim dimension not found".to_string()))?
-            .len();
+            .dimension_len(DIM_NUM_DIM)
+            .ok_or_else(|| ExodusError::Other("num_dim dimension not found".to_string()))?;
 
         // If no nodes, return empty array
         if num_nodes == 0 {
@@ -717,15 +715,13 @@ impl ExodusFile<mode::Read> {
         // Get num_nodes and num_dim
         let num_nodes = self
             .nc_file
-            .dimension(DIM_NUM_NODES)
-            .ok_or_else(|| ExodusError::Other(format!("{} dimension not found", DIM_NUM_NODES)))?
-            .len();
+            .dimension_len(DIM_NUM_NODES)
+            .ok_or_else(|| ExodusError::Other(format!("{} dimension not found", DIM_NUM_NODES)))?;
 
         let num_dim = self
             .nc_file
-            .dimension(DIM_NUM_DIM)
-            .ok_or_else(|| ExodusError::Other(format!("{} dimension not found", DIM_NUM_DIM)))?
-            .len();
+            .dimension_len(DIM_NUM_DIM)
+            .ok_or_else(|| ExodusError::Other(format!("{} dimension not found", DIM_NUM_DIM)))?;
 
         // Validate buffer sizes
         if x.len() != num_nodes {
@@ -809,13 +805,8 @@ impl ExodusFile<mode::Read> {
     fn get_coord_dim<T: CoordValue>(&self, dim: usize) -> Result<Vec<T>> {
         let var_name = coord_var_name(dim)?;
 
-        let var = self
-            .nc_file
-            .variable(var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.to_string()))?;
-
-        // Read as f64 from NetCDF (use .. for full range)
-        let data: Vec<f64> = var.get_values(..)?;
+        // Read as f64 from NetCDF
+        let data = self.nc_file.read_all_f64(var_name)?;
 
         // Convert to target type
         Ok(data.iter().map(|&v| T::from_f64(v)).collect())
@@ -838,20 +829,18 @@ impl ExodusFile<mode::Read> {
     ) -> Result<Coordinates<T>> {
         let num_dim = self
             .nc_file
-            .dimension("num_dim")
-            .ok_or_else(|| ExodusError::Other("num_dim dimension not found".to_string()))?
-            .len();
+            .dimension_len(DIM_NUM_DIM)
+            .ok_or_else(|| ExodusError::Other("num_dim dimension not found".to_string()))?;
 
         let num_nodes = self
             .nc_file
-            .dimension(DIM_NUM_NODES)
-            .ok_or_else(|| ExodusError::Other("num_nodes dimension not found".to_string()))?
-            .len();
+            .dimension_len(DIM_NUM_NODES)
+            .ok_or_else(|| ExodusError::Other("num_nodes dimension not found".to_string()))?;
 
         // Validate range
         if start + count > num_nodes {
             return Err(ExodusError::InvalidArrayLength {
-                expected: num_nodes - start,
+                expected: num_nodes.saturating_sub(start),
                 actual: count,
             });
         }
@@ -885,13 +874,8 @@ impl ExodusFile<mode::Read> {
     ) -> Result<Vec<T>> {
         let var_name = coord_var_name(dim)?;
 
-        let var = self
-            .nc_file
-            .variable(var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.to_string()))?;
-
-        // Read as f64 from NetCDF (use range)
-        let data: Vec<f64> = var.get_values(start..(start + count))?;
+        // Read as f64 from NetCDF
+        let data = self.nc_file.read_f64(var_name, &[start], &[count])?;
 
         // Convert to target type
         Ok(data.iter().map(|&v| T::from_f64(v)).collect())
@@ -920,47 +904,11 @@ impl ExodusFile<mode::Read> {
     /// ```
     pub fn coord_names(&self) -> Result<Vec<String>> {
         // Check if coor_names variable exists
-        match self.nc_file.variable(VAR_COOR_NAMES) {
-            Some(var) => {
-                let num_dim = self.metadata.num_dim.unwrap_or(3);
-
-                // Support both classic NetCDF fixed-length char arrays (NC_CHAR) and
-                // NetCDF-4 NC_STRING variables for name storage.
-                let dims = var.dimensions();
-
-                // If 1D, likely NC_STRING [num_dim]
-                if dims.len() == 1 {
-                    let mut names = Vec::with_capacity(num_dim);
-                    for i in 0..num_dim {
-                        let s = var.get_string(i..i + 1)?;
-                        names.push(s.trim_end_matches('\0').trim().to_string());
-                    }
-                    return Ok(names);
-                }
-
-                // Otherwise, expect 2D [num_dim, len_string] of NC_CHAR
-                let mut names = Vec::with_capacity(num_dim);
-
-                // Get len_string for fixed-length names
-                let len_string = dims.get(1).map(|d| d.len()).unwrap_or(33);
-
-                // Read raw bytes directly (more reliable for 2D char arrays)
-                let raw_bytes: Vec<u8> = var.get_raw_values(..)?;
-
-                for i in 0..num_dim {
-                    let start = i * len_string;
-                    let end = start + len_string;
-                    let name_bytes = &raw_bytes[start..end];
-                    let name = String::from_utf8_lossy(name_bytes)
-                        .trim_end_matches('\0')
-                        .trim()
-                        .to_string();
-                    names.push(name);
-                }
-
-                Ok(names)
-            }
-            None => Ok(Vec::new()),
+        if self.nc_file.has_variable(VAR_COOR_NAMES) {
+            // Names may be fixed-length char arrays or NetCDF-4 NC_STRING
+            self.nc_file.read_strings(VAR_COOR_NAMES)
+        } else {
+            Ok(Vec::new())
         }
     }
 }
@@ -1059,6 +1007,7 @@ impl ExodusFile<mode::Append> {
 
     /// Write coordinates for a specific dimension
     fn put_coord_dim<T: CoordValue>(&mut self, dim: usize, coords: &[T]) -> Result<()> {
+        self.ensure_data_mode()?;
         let var_name = coord_var_name(dim)?;
 
         let mut var = self
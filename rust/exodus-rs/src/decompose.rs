@@ -0,0 +1,1012 @@
+//! Decomposing a serial mesh into file-per-processor parts.
+//!
+//! Parallel solvers read one Exodus file per processor (`mesh.e.4.0` through
+//! `mesh.e.4.3`). [`partition_elements`] assigns every element of a serial
+//! mesh to a processor and [`decompose_file`] writes the parts, the
+//! equivalent of the SEACAS `nem_slice` and `nem_spread` tools.
+//!
+//! Each part holds the processor's elements (in every element block, possibly
+//! empty) and the nodes they use, numbered with the internal nodes first and
+//! the border nodes shared with other processors last. Node and element ID
+//! maps give the global IDs of the local entities, node and side sets are
+//! clipped to the local entities, and the Nemesis metadata describes the
+//! global mesh, the load balance and the node and element communication maps.
+//! Nodes not used by any element are placed on processor 0.
+//!
+//! Three partitioning methods are available (see [`DecompositionMethod`]):
+//! recursive coordinate bisection and inertial bisection of the element
+//! centroids, and whole element blocks per processor.
+//!
+//! Only the mesh is decomposed; transient results are not copied. Files with
+//! edge or face blocks, or with edge, face or element sets, are rejected.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use exodus_rs::decompose::{decompose_file, DecomposeOptions, DecompositionMethod};
+//!
+//! // Writes mesh.e.4.0 through mesh.e.4.3
+//! let options = DecomposeOptions {
+//!     method: DecompositionMethod::Inertial,
+//!     ..Default::default()
+//! };
+//! let summary = decompose_file("mesh.e", "mesh.e", 4, &options)?;
+//! println!("Elements per processor: {:?}", summary.num_elems);
+//! # Ok::<(), exodus_rs::ExodusError>(())
+//! ```
+
+use crate::error::{ExodusError, Result};
+use crate::mesh::MeshData;
+use crate::{mode, ExodusFile};
+
+#[cfg(feature = "netcdf4")]
+use crate::join::decomposed_file_name;
+#[cfg(feature = "netcdf4")]
+use crate::types::{Block, EntityType, NodeSet, SideSet, Topology};
+#[cfg(feature = "netcdf4")]
+use crate::types::{
+    CommMapParams, CreateOptions, ElemCommMap, GlobalEntityParams, GlobalInitParams, InitParams,
+    LoadBalanceParams, NemesisFileType, NemesisInitInfo, NodeCommMap, ProcessorElemMaps,
+    ProcessorNodeMaps,
+};
+#[cfg(feature = "netcdf4")]
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "netcdf4")]
+use std::path::{Path, PathBuf};
+
+/// Strategy used to assign elements to processors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DecompositionMethod {
+    /// Recursive coordinate bisection: split the element centroids at the
+    /// median of the longest bounding-box axis until every
+    /// processor has its share
+    #[default]
+    Rcb,
+    /// Inertial bisection: like [`Rcb`](Self::Rcb), but split along the
+    /// principal axis of inertia of the centroids, which follows meshes that
+    /// are not aligned with the coordinate axes
+    Inertial,
+    /// Whole element blocks per processor, largest blocks first onto the
+    /// least loaded processor. Needs at least as many non-empty blocks as
+    /// processors.
+    Block,
+}
+
+/// Options controlling [`decompose_file`]
+#[cfg(feature = "netcdf4")]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecomposeOptions {
+    /// How elements are assigned to processors
+    pub method: DecompositionMethod,
+    /// Options used to create each processor file
+    pub create_options: CreateOptions,
+}
+
+/// Sizes of the parts written by [`decompose_file`]
+#[cfg(feature = "netcdf4")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecomposeSummary {
+    /// Path of each processor file, ordered by rank
+    pub paths: Vec<PathBuf>,
+    /// Number of nodes on each processor
+    pub num_nodes: Vec<usize>,
+    /// Number of border nodes (shared with other processors) on each processor
+    pub num_border_nodes: Vec<usize>,
+    /// Number of elements on each processor
+    pub num_elems: Vec<usize>,
+}
+
+/// Assign every element of a serial mesh to one of `num_procs` processors
+///
+/// Returns the 0-based processor of each element, in global element order
+/// (the elements of the first block, then the second, and so on). Bisection
+/// splits are proportional, so every processor receives `num_elems /
+/// num_procs` elements, give or take one.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - `num_procs` is zero or larger than the number of elements
+/// - The block method has fewer non-empty blocks than processors
+/// - NetCDF read fails
+pub fn partition_elements(
+    file: &ExodusFile<mode::Read>,
+    num_procs: usize,
+    method: DecompositionMethod,
+) -> Result<Vec<usize>> {
+    let mesh = read_serial_mesh(file)?;
+    partition(&mesh, num_procs, method)
+}
+
+/// Decompose the serial mesh `input` into `num_procs` processor files
+///
+/// The parts are written as `<output_base>.<num_procs>.<rank>` (see
+/// [`decomposed_file_name`]) and can be merged back with
+/// [`join_files`](crate::join::join_files).
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The mesh cannot be partitioned (see [`partition_elements`])
+/// - The mesh has edge or face blocks, or edge, face or element sets
+/// - A side set has a varying number of distribution factors per side
+/// - NetCDF read or write fails
+#[cfg(feature = "netcdf4")]
+pub fn decompose_file(
+    input: impl AsRef<Path>,
+    output_base: impl AsRef<Path>,
+    num_procs: usize,
+    options: &DecomposeOptions,
+) -> Result<DecomposeSummary> {
+    let file = ExodusFile::<mode::Read>::open(input)?;
+    let mesh = read_serial_mesh(&file)?;
+    let assignment = partition(&mesh, num_procs, options.method)?;
+    write_parts(
+        &file,
+        &mesh,
+        &assignment,
+        num_procs,
+        output_base.as_ref(),
+        &options.create_options,
+    )
+}
+
+/// Write the processor files of an existing element assignment
+///
+/// `assignment` holds the 0-based processor of each element in global
+/// element order, as returned by [`partition_elements`]. This lets callers
+/// decompose with a partitioner of their own.
+///
+/// # Errors
+///
+/// Returns an error if the assignment does not cover every element, names a
+/// processor outside `0..num_procs` or leaves a processor without elements,
+/// and for the reasons listed on [`decompose_file`].
+#[cfg(feature = "netcdf4")]
+pub fn write_decomposition(
+    file: &ExodusFile<mode::Read>,
+    assignment: &[usize],
+    num_procs: usize,
+    output_base: impl AsRef<Path>,
+    create_options: &CreateOptions,
+) -> Result<DecomposeSummary> {
+    let mesh = read_serial_mesh(file)?;
+    write_parts(
+        file,
+        &mesh,
+        assignment,
+        num_procs,
+        output_base.as_ref(),
+        create_options,
+    )
+}
+
+/// Read the serial mesh and check that it can be decomposed
+fn read_serial_mesh(file: &ExodusFile<mode::Read>) -> Result<MeshData> {
+    let params = file.init_params()?;
+    if params.num_edge_blocks
+        + params.num_face_blocks
+        + params.num_edge_sets
+        + params.num_face_sets
+        + params.num_elem_sets
+        > 0
+    {
+        return Err(ExodusError::UnsupportedFeature(
+            "Decomposing edge or face blocks and edge, face or element sets".into(),
+        ));
+    }
+
+    let mesh = MeshData::read(file)?;
+    check_connectivity(&mesh)?;
+    Ok(mesh)
+}
+
+/// Check that every block has complete connectivity within the node range
+fn check_connectivity(mesh: &MeshData) -> Result<()> {
+    let num_nodes = mesh.num_nodes();
+    for (block, conn) in mesh.blocks.iter().zip(&mesh.connectivity) {
+        let expected = block.num_entries * block.num_nodes_per_entry;
+        if conn.len() != expected {
+            return Err(ExodusError::InvalidArrayLength {
+                expected,
+                actual: conn.len(),
+            });
+        }
+        if let Some(&node) = conn
+            .iter()
+            .find(|&&node| node < 1 || node as usize > num_nodes)
+        {
+            return Err(ExodusError::Other(format!(
+                "Block {} references node {} outside of 1..={}",
+                block.id, node, num_nodes
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Assign the elements of `mesh` to processors with `method`
+fn partition(mesh: &MeshData, num_procs: usize, method: DecompositionMethod) -> Result<Vec<usize>> {
+    let num_elems = mesh.num_elems();
+    if num_procs == 0 || num_procs > num_elems {
+        return Err(ExodusError::Other(format!(
+            "Cannot decompose {} elements onto {} processors",
+            num_elems, num_procs
+        )));
+    }
+    match method {
+        DecompositionMethod::Rcb => Ok(bisect(&mesh.centroids(), num_procs, longest_axis)),
+        DecompositionMethod::Inertial => Ok(bisect(&mesh.centroids(), num_procs, principal_axis)),
+        DecompositionMethod::Block => {
+            let sizes: Vec<usize> = mesh.blocks.iter().map(|b| b.num_entries).collect();
+            let procs = block_procs(&sizes, num_procs)?;
+            Ok(sizes
+                .iter()
+                .zip(procs)
+                .flat_map(|(&size, proc)| std::iter::repeat(proc).take(size))
+                .collect())
+        }
+    }
+}
+
+/// Recursively bisect `points` along the direction chosen by `axis`
+fn bisect(
+    points: &[[f64; 3]],
+    num_procs: usize,
+    axis: fn(&[[f64; 3]], &[usize]) -> [f64; 3],
+) -> Vec<usize> {
+    fn split(
+        points: &[[f64; 3]],
+        indices: &mut [usize],
+        first: usize,
+        num_procs: usize,
+        axis: fn(&[[f64; 3]], &[usize]) -> [f64; 3],
+        assignment: &mut [usize],
+    ) {
+        if num_procs == 1 {
+            for &i in indices.iter() {
+                assignment[i] = first;
+            }
+            return;
+        }
+        // Proportional split, so an odd processor count still balances
+        let left_procs = num_procs / 2;
+        let mid = indices.len() * left_procs / num_procs;
+        let direction = axis(points, indices);
+        let project = |i: usize| dot(points[i], direction);
+        indices.select_nth_unstable_by(mid, |&a, &b| {
+            project(a).total_cmp(&project(b)).then(a.cmp(&b))
+        });
+        let (left, right) = indices.split_at_mut(mid);
+        split(points, left, first, left_procs, axis, assignment);
+        split(
+            points,
+            right,
+            first + left_procs,
+            num_procs - left_procs,
+            axis,
+            assignment,
+        );
+    }
+
+    let mut indices: Vec<usize> = (0..points.len()).collect();
+    let mut assignment = vec![0; points.len()];
+    split(points, &mut indices, 0, num_procs, axis, &mut assignment);
+    assignment
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Unit vector along the longest bounding-box extent of the selected points
+fn longest_axis(points: &[[f64; 3]], indices: &[usize]) -> [f64; 3] {
+    let mut min = [f64::INFINITY; 3];
+    let mut max = [f64::NEG_INFINITY; 3];
+    for &i in indices {
+        for d in 0..3 {
+            min[d] = min[d].min(points[i][d]);
+            max[d] = max[d].max(points[i][d]);
+        }
+    }
+    let mut best = 0;
+    for d in 1..3 {
+        if max[d] - min[d] > max[best] - min[best] {
+            best = d;
+        }
+    }
+    let mut axis = [0.0; 3];
+    axis[best] = 1.0;
+    axis
+}
+
+/// Principal axis of inertia (largest variance) of the selected points
+///
+/// Found by power iteration on the covariance matrix, starting from the
+/// longest bounding-box axis, which is also the fallback for degenerate sets.
+fn principal_axis(points: &[[f64; 3]], indices: &[usize]) -> [f64; 3] {
+    let start = longest_axis(points, indices);
+    let n = indices.len().max(1) as f64;
+    let mut mean = [0.0; 3];
+    for &i in indices {
+        for d in 0..3 {
+            mean[d] += points[i][d] / n;
+        }
+    }
+    let mut cov = [[0.0; 3]; 3];
+    for &i in indices {
+        let r = [
+            points[i][0] - mean[0],
+            points[i][1] - mean[1],
+            points[i][2] - mean[2],
+        ];
+        for a in 0..3 {
+            for b in 0..3 {
+                cov[a][b] += r[a] * r[b];
+            }
+        }
+    }
+
+    let mut axis = start;
+    for _ in 0..100 {
+        let next = [dot(cov[0], axis), dot(cov[1], axis), dot(cov[2], axis)];
+        let norm = dot(next, next).sqrt();
+        if norm.is_nan() || norm <= f64::EPSILON {
+            return start;
+        }
+        let next = next.map(|c| c / norm);
+        let converged = (dot(next, axis).abs() - 1.0).abs() < 1e-12;
+        axis = next;
+        if converged {
+            break;
+        }
+    }
+    axis
+}
+
+/// Processor of each block, placing the largest blocks first onto the least
+/// loaded processor
+fn block_procs(sizes: &[usize], num_procs: usize) -> Result<Vec<usize>> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|&a, &b| sizes[b].cmp(&sizes[a]).then(a.cmp(&b)));
+    let mut loads = vec![0; num_procs];
+    let mut procs = vec![0; sizes.len()];
+    for b in order {
+        let (proc, _) = loads
+            .iter()
+            .enumerate()
+            .min_by_key(|&(proc, &load)| (load, proc))
+            .expect("at least one processor");
+        procs[b] = proc;
+        loads[proc] += sizes[b];
+    }
+    if loads.contains(&0) {
+        let non_empty = sizes.iter().filter(|&&size| size > 0).count();
+        return Err(ExodusError::Other(format!(
+            "Block decomposition needs at least {} non-empty element blocks, found {}",
+            num_procs, non_empty
+        )));
+    }
+    Ok(procs)
+}
+
+/// Local mesh and communication data of one processor
+#[cfg(feature = "netcdf4")]
+#[derive(Debug, Default)]
+struct ProcessorPlan {
+    /// 0-based serial nodes, internal nodes first, each group in serial order
+    nodes: Vec<usize>,
+    num_internal_nodes: usize,
+    /// Local index of each serial node on this processor
+    node_local: HashMap<usize, usize>,
+    /// 0-based serial elements in serial order
+    elems: Vec<usize>,
+    /// Number of local elements in each block
+    block_counts: Vec<usize>,
+    /// 1-based local elements with no border node
+    internal_elems: Vec<i64>,
+    /// 1-based local elements with at least one border node
+    border_elems: Vec<i64>,
+    /// Shared 1-based local nodes, keyed by neighbouring processor
+    node_cmaps: BTreeMap<usize, Vec<i64>>,
+    /// 1-based local elements and sides facing each neighbouring processor
+    elem_cmaps: BTreeMap<usize, (Vec<i64>, Vec<i64>)>,
+}
+
+/// Plan the local meshes for an element assignment
+///
+/// Returns the plan of every processor and the local index of every serial
+/// element on its processor.
+#[cfg(feature = "netcdf4")]
+fn plan_decomposition(
+    mesh: &MeshData,
+    assignment: &[usize],
+    num_procs: usize,
+) -> Result<(Vec<ProcessorPlan>, Vec<usize>)> {
+    let num_elems = mesh.num_elems();
+    if assignment.len() != num_elems {
+        return Err(ExodusError::InvalidArrayLength {
+            expected: num_elems,
+            actual: assignment.len(),
+        });
+    }
+    if let Some(&proc) = assignment.iter().find(|&&proc| proc >= num_procs) {
+        return Err(ExodusError::Other(format!(
+            "Element assigned to processor {} of {}",
+            proc, num_procs
+        )));
+    }
+
+    // Processors using each node
+    let mut node_procs: Vec<Vec<usize>> = vec![Vec::new(); mesh.num_nodes()];
+    for (nodes, &proc) in mesh.elements().zip(assignment) {
+        for &node in nodes {
+            let procs = &mut node_procs[node as usize - 1];
+            if !procs.contains(&proc) {
+                procs.push(proc);
+            }
+        }
+    }
+    for procs in node_procs.iter_mut() {
+        if procs.is_empty() {
+            procs.push(0);
+        }
+        procs.sort_unstable();
+    }
+
+    let mut plans: Vec<ProcessorPlan> = (0..num_procs)
+        .map(|_| ProcessorPlan {
+            block_counts: vec![0; mesh.blocks.len()],
+            ..Default::default()
+        })
+        .collect();
+    let mut elem_local = vec![0; num_elems];
+    let mut elem = 0;
+    for (b, block) in mesh.blocks.iter().enumerate() {
+        for _ in 0..block.num_entries {
+            let plan = &mut plans[assignment[elem]];
+            elem_local[elem] = plan.elems.len();
+            plan.elems.push(elem);
+            plan.block_counts[b] += 1;
+            elem += 1;
+        }
+    }
+    if let Some(empty) = plans.iter().position(|plan| plan.elems.is_empty()) {
+        return Err(ExodusError::Other(format!(
+            "Processor {} has no elements",
+            empty
+        )));
+    }
+
+    // Local nodes, internal before border
+    let mut border_nodes: Vec<Vec<usize>> = vec![Vec::new(); num_procs];
+    for (node, procs) in node_procs.iter().enumerate() {
+        for &proc in procs {
+            if procs.len() == 1 {
+                plans[proc].nodes.push(node);
+            } else {
+                border_nodes[proc].push(node);
+            }
+        }
+    }
+    for (plan, border) in plans.iter_mut().zip(border_nodes) {
+        plan.num_internal_nodes = plan.nodes.len();
+        plan.nodes.extend(border);
+        plan.node_local = plan
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(local, &node)| (node, local))
+            .collect();
+    }
+
+    // Node communication maps
+    for (proc, plan) in plans.iter_mut().enumerate() {
+        for local in plan.num_internal_nodes..plan.nodes.len() {
+            for &other in &node_procs[plan.nodes[local]] {
+                if other != proc {
+                    plan.node_cmaps
+                        .entry(other)
+                        .or_default()
+                        .push(local as i64 + 1);
+                }
+            }
+        }
+    }
+
+    // Internal and border elements
+    for (elem, nodes) in mesh.elements().enumerate() {
+        let plan = &mut plans[assignment[elem]];
+        let local = elem_local[elem] as i64 + 1;
+        if nodes
+            .iter()
+            .any(|&node| node_procs[node as usize - 1].len() > 1)
+        {
+            plan.border_elems.push(local);
+        } else {
+            plan.internal_elems.push(local);
+        }
+    }
+
+    // Element communication maps from faces shared across processors
+    let mut open_faces: HashMap<Vec<i64>, (usize, usize)> = HashMap::new();
+    let mut elem = 0;
+    for (block, conn) in mesh.blocks.iter().zip(&mesh.connectivity) {
+        let faces = Topology::from_string(&block.topology)
+            .faces()
+            .unwrap_or_default();
+        let npe = block.num_nodes_per_entry.max(1);
+        for nodes in conn.chunks_exact(npe) {
+            for face in &faces {
+                let Some(mut key) = face
+                    .node_indices
+                    .iter()
+                    .map(|&i| nodes.get(i).copied())
+                    .collect::<Option<Vec<i64>>>()
+                else {
+                    continue;
+                };
+                key.sort_unstable();
+                match open_faces.remove(&key) {
+                    Some((other, other_side)) => {
+                        let (p, q) = (assignment[elem], assignment[other]);
+                        if p != q {
+                            for (proc, neighbour, e, side) in
+                                [(p, q, elem, face.side_number), (q, p, other, other_side)]
+                            {
+                                let (elems, sides) =
+                                    plans[proc].elem_cmaps.entry(neighbour).or_default();
+                                elems.push(elem_local[e] as i64 + 1);
+                                sides.push(side as i64);
+                            }
+                        }
+                    }
+                    None => {
+                        open_faces.insert(key, (elem, face.side_number));
+                    }
+                }
+            }
+            elem += 1;
+        }
+    }
+    for plan in plans.iter_mut() {
+        for (elems, sides) in plan.elem_cmaps.values_mut() {
+            let mut pairs: Vec<(i64, i64)> =
+                elems.iter().copied().zip(sides.iter().copied()).collect();
+            pairs.sort_unstable();
+            (*elems, *sides) = pairs.into_iter().unzip();
+        }
+    }
+
+    Ok((plans, elem_local))
+}
+
+/// Node set entries on one processor: 1-based local nodes and their factors
+#[cfg(feature = "netcdf4")]
+fn clip_node_set(set: &NodeSet, plan: &ProcessorPlan) -> (Vec<i64>, Vec<f64>) {
+    let mut nodes = Vec::new();
+    let mut df = Vec::new();
+    for (i, &node) in set.nodes.iter().enumerate() {
+        if let Some(&local) = usize::try_from(node - 1)
+            .ok()
+            .and_then(|node| plan.node_local.get(&node))
+        {
+            nodes.push(local as i64 + 1);
+            if let Some(&value) = set.dist_factors.get(i) {
+                df.push(value);
+            }
+        }
+    }
+    (nodes, df)
+}
+
+/// Side set entries on processor `proc`: 1-based local elements, sides and
+/// their factors
+#[cfg(feature = "netcdf4")]
+fn clip_side_set(
+    set: &SideSet,
+    proc: usize,
+    assignment: &[usize],
+    elem_local: &[usize],
+) -> Result<(Vec<i64>, Vec<i64>, Vec<f64>)> {
+    let per_side = match set.elements.len() {
+        0 => 0,
+        n if set.dist_factors.len() % n == 0 => set.dist_factors.len() / n,
+        _ => {
+            return Err(ExodusError::UnsupportedFeature(format!(
+                "Decomposing side set {} with a varying number of distribution factors per side",
+                set.id
+            )))
+        }
+    };
+    let mut elems = Vec::new();
+    let mut sides = Vec::new();
+    let mut df = Vec::new();
+    for (i, (&elem, &side)) in set.elements.iter().zip(&set.sides).enumerate() {
+        let index = usize::try_from(elem - 1)
+            .ok()
+            .filter(|&index| index < assignment.len())
+            .ok_or_else(|| {
+                ExodusError::Other(format!(
+                    "Side set {} references element {} outside of 1..={}",
+                    set.id,
+                    elem,
+                    assignment.len()
+                ))
+            })?;
+        if assignment[index] == proc {
+            elems.push(elem_local[index] as i64 + 1);
+            sides.push(side);
+            df.extend_from_slice(&set.dist_factors[i * per_side..(i + 1) * per_side]);
+        }
+    }
+    Ok((elems, sides, df))
+}
+
+/// Write every processor file of a planned decomposition
+#[cfg(feature = "netcdf4")]
+fn write_parts(
+    file: &ExodusFile<mode::Read>,
+    mesh: &MeshData,
+    assignment: &[usize],
+    num_procs: usize,
+    output_base: &Path,
+    create_options: &CreateOptions,
+) -> Result<DecomposeSummary> {
+    let (plans, elem_local) = plan_decomposition(mesh, assignment, num_procs)?;
+    let params = file.init_params()?;
+    let num_dim = params.num_dim;
+    let num_elems = mesh.num_elems();
+
+    let qa_records = file.qa_records()?;
+    let info_records = file.info_records()?;
+    let coord_names = file.coord_names()?;
+    let mut attributes = Vec::with_capacity(mesh.blocks.len());
+    for block in &mesh.blocks {
+        attributes.push(if block.num_attributes > 0 && block.num_entries > 0 {
+            (
+                file.block_attributes(block.id)?,
+                file.block_attribute_names(block.id)?,
+            )
+        } else {
+            Default::default()
+        });
+    }
+    let mut names = Vec::new();
+    for entity_type in [
+        EntityType::ElemBlock,
+        EntityType::NodeSet,
+        EntityType::SideSet,
+    ] {
+        match file.names(entity_type) {
+            Err(ExodusError::VariableNotDefined(_)) => {}
+            result => {
+                let entity_names = result?;
+                if entity_names.iter().any(|name| !name.is_empty()) {
+                    names.push((entity_type, entity_names));
+                }
+            }
+        }
+    }
+
+    let block_ids: Vec<i64> = mesh.blocks.iter().map(|b| b.id).collect();
+    let global_blocks = GlobalEntityParams {
+        ids: block_ids,
+        entry_counts: mesh.blocks.iter().map(|b| b.num_entries).collect(),
+        dist_fact_counts: Vec::new(),
+    };
+    let global_node_sets = GlobalEntityParams {
+        ids: mesh.node_sets.iter().map(|s| s.id).collect(),
+        entry_counts: mesh.node_sets.iter().map(|s| s.nodes.len()).collect(),
+        dist_fact_counts: mesh
+            .node_sets
+            .iter()
+            .map(|s| s.dist_factors.len())
+            .collect(),
+    };
+    let global_side_sets = GlobalEntityParams {
+        ids: mesh.side_sets.iter().map(|s| s.id).collect(),
+        entry_counts: mesh.side_sets.iter().map(|s| s.elements.len()).collect(),
+        dist_fact_counts: mesh
+            .side_sets
+            .iter()
+            .map(|s| s.dist_factors.len())
+            .collect(),
+    };
+
+    let mut summary = DecomposeSummary::default();
+    for (rank, plan) in plans.iter().enumerate() {
+        let path = decomposed_file_name(output_base, num_procs, rank);
+        let mut out = ExodusFile::create(&path, create_options.clone())?;
+        out.init(&InitParams {
+            title: params.title.clone(),
+            num_dim,
+            num_nodes: plan.nodes.len(),
+            num_elems: plan.elems.len(),
+            num_elem_blocks: mesh.blocks.len(),
+            num_node_sets: mesh.node_sets.len(),
+            num_side_sets: mesh.side_sets.len(),
+            ..Default::default()
+        })?;
+        if !qa_records.is_empty() {
+            out.put_qa_records(&qa_records)?;
+        }
+        if !info_records.is_empty() {
+            out.put_info_records(&info_records)?;
+        }
+
+        for (block, &count) in mesh.blocks.iter().zip(&plan.block_counts) {
+            out.put_block(&Block {
+                num_entries: count,
+                ..block.clone()
+            })?;
+        }
+
+        let local_coords: Vec<Vec<f64>> = mesh
+            .coords
+            .iter()
+            .take(num_dim)
+            .map(|coord| plan.nodes.iter().map(|&node| coord[node]).collect())
+            .collect();
+        out.put_coords(
+            &local_coords[0],
+            local_coords.get(1).map(Vec::as_slice),
+            local_coords.get(2).map(Vec::as_slice),
+        )?;
+        if !coord_names.is_empty() {
+            let names: Vec<&str> = coord_names.iter().map(String::as_str).collect();
+            out.put_coord_names(&names)?;
+        }
+        let local_node_ids: Vec<i64> = plan.nodes.iter().map(|&node| mesh.node_ids[node]).collect();
+        let local_elem_ids: Vec<i64> = plan.elems.iter().map(|&elem| mesh.elem_ids[elem]).collect();
+        out.put_id_map(EntityType::NodeMap, &local_node_ids)?;
+        out.put_id_map(EntityType::ElemMap, &local_elem_ids)?;
+
+        let mut start = 0;
+        let mut offset = 0;
+        for (b, block) in mesh.blocks.iter().enumerate() {
+            let count = plan.block_counts[b];
+            let elems = &plan.elems[start..start + count];
+            start += count;
+            if count > 0 {
+                let npe = block.num_nodes_per_entry;
+                let mut connectivity = Vec::with_capacity(count * npe);
+                for &elem in elems {
+                    let e = elem - offset;
+                    for &node in &mesh.connectivity[b][e * npe..(e + 1) * npe] {
+                        connectivity.push(plan.node_local[&(node as usize - 1)] as i64 + 1);
+                    }
+                }
+                out.put_connectivity(block.id, &connectivity)?;
+
+                let (values, attribute_names) = &attributes[b];
+                let nattr = block.num_attributes;
+                if nattr > 0 {
+                    let local: Vec<f64> = elems
+                        .iter()
+                        .flat_map(|&elem| {
+                            let e = elem - offset;
+                            values[e * nattr..(e + 1) * nattr].iter().copied()
+                        })
+                        .collect();
+                    out.put_block_attributes(block.id, &local)?;
+                    if !attribute_names.is_empty() {
+                        let names: Vec<&str> = attribute_names.iter().map(String::as_str).collect();
+                        out.put_block_attribute_names(block.id, &names)?;
+                    }
+                }
+            }
+            offset += block.num_entries;
+        }
+
+        for set in &mesh.node_sets {
+            let (nodes, df) = clip_node_set(set, plan);
+            let df = (!set.dist_factors.is_empty() && !nodes.is_empty()).then_some(df);
+            out.put_node_set(set.id, &nodes, df.as_deref())?;
+        }
+        for set in &mesh.side_sets {
+            let (elems, sides, df) = clip_side_set(set, rank, assignment, &elem_local)?;
+            let df = (!df.is_empty()).then_some(df);
+            out.put_side_set(set.id, &elems, &sides, df.as_deref())?;
+        }
+        for (entity_type, entity_names) in &names {
+            out.put_names(*entity_type, entity_names)?;
+        }
+
+        // Nemesis metadata
+        out.put_init_info(&NemesisInitInfo {
+            num_procs,
+            num_procs_in_file: 1,
+            file_type: NemesisFileType::Parallel,
+        })?;
+        out.put_init_global(&GlobalInitParams {
+            num_nodes: mesh.num_nodes(),
+            num_elems,
+            num_elem_blocks: mesh.blocks.len(),
+            num_node_sets: mesh.node_sets.len(),
+            num_side_sets: mesh.side_sets.len(),
+        })?;
+        out.put_global_params(EntityType::ElemBlock, &global_blocks)?;
+        out.put_global_params(EntityType::NodeSet, &global_node_sets)?;
+        out.put_global_params(EntityType::SideSet, &global_side_sets)?;
+
+        out.put_load_balance_params(&LoadBalanceParams {
+            num_internal_nodes: plan.num_internal_nodes,
+            num_border_nodes: plan.nodes.len() - plan.num_internal_nodes,
+            num_external_nodes: 0,
+            num_internal_elems: plan.internal_elems.len(),
+            num_border_elems: plan.border_elems.len(),
+            num_node_cmaps: plan.node_cmaps.len(),
+            num_elem_cmaps: plan.elem_cmaps.len(),
+        })?;
+        out.put_processor_node_maps(&ProcessorNodeMaps {
+            internal: (1..=plan.num_internal_nodes as i64).collect(),
+            border: (plan.num_internal_nodes as i64 + 1..=plan.nodes.len() as i64).collect(),
+            external: Vec::new(),
+        })?;
+        out.put_processor_elem_maps(&ProcessorElemMaps {
+            internal: plan.internal_elems.clone(),
+            border: plan.border_elems.clone(),
+        })?;
+        out.put_comm_map_params(&CommMapParams {
+            node_cmap_ids: plan.node_cmaps.keys().map(|&q| q as i64).collect(),
+            node_cmap_node_counts: plan.node_cmaps.values().map(Vec::len).collect(),
+            elem_cmap_ids: plan.elem_cmaps.keys().map(|&q| q as i64).collect(),
+            elem_cmap_elem_counts: plan.elem_cmaps.values().map(|(e, _)| e.len()).collect(),
+        })?;
+        for (&other, nodes) in &plan.node_cmaps {
+            out.put_node_comm_map(&NodeCommMap {
+                id: other as i64,
+                node_ids: nodes.clone(),
+                proc_ids: vec![other as i64; nodes.len()],
+            })?;
+        }
+        for (&other, (elems, sides)) in &plan.elem_cmaps {
+            out.put_elem_comm_map(&ElemCommMap {
+                id: other as i64,
+                elem_ids: elems.clone(),
+                side_ids: sides.clone(),
+                proc_ids: vec![other as i64; elems.len()],
+            })?;
+        }
+        out.sync()?;
+
+        summary.paths.push(path);
+        summary.num_nodes.push(plan.nodes.len());
+        summary
+            .num_border_nodes
+            .push(plan.nodes.len() - plan.num_internal_nodes);
+        summary.num_elems.push(plan.elems.len());
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Block, EntityType, NodeSet, SideSet};
+
+    /// 4x1 row of QUAD4 elements in one block, with a node set on the left
+    /// edge and a side set on the bottom
+    ///
+    /// ```text
+    ///   6---7---8---9--10
+    ///   | 1 | 2 | 3 | 4 |
+    ///   1---2---3---4---5
+    /// ```
+    fn strip() -> MeshData {
+        let x: Vec<f64> = (0..10).map(|n| (n % 5) as f64).collect();
+        let y: Vec<f64> = (0..10).map(|n| (n / 5) as f64).collect();
+        MeshData {
+            num_dim: 2,
+            coords: [x, y, vec![0.0; 10]],
+            node_ids: (1..=10).collect(),
+            elem_ids: (1..=4).collect(),
+            blocks: vec![Block {
+                id: 1,
+                entity_type: EntityType::ElemBlock,
+                topology: "QUAD4".into(),
+                num_entries: 4,
+                num_nodes_per_entry: 4,
+                num_edges_per_entry: 0,
+                num_faces_per_entry: 0,
+                num_attributes: 0,
+            }],
+            offsets: vec![0],
+            connectivity: vec![(1..=4).flat_map(|i| [i, i + 1, i + 6, i + 5]).collect()],
+            node_sets: vec![NodeSet {
+                id: 10,
+                nodes: vec![1, 6],
+                dist_factors: vec![1.0, 6.0],
+            }],
+            side_sets: vec![SideSet {
+                id: 20,
+                elements: vec![1, 2, 3, 4],
+                sides: vec![1, 1, 1, 1],
+                dist_factors: (1..=8).map(f64::from).collect(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_rcb_splits_along_longest_axis() {
+        let mesh = strip();
+        assert_eq!(
+            partition(&mesh, 2, DecompositionMethod::Rcb).unwrap(),
+            vec![0, 0, 1, 1]
+        );
+        assert_eq!(
+            partition(&mesh, 4, DecompositionMethod::Rcb).unwrap(),
+            vec![0, 1, 2, 3]
+        );
+        // Odd counts split proportionally
+        let counts = |assignment: Vec<usize>| {
+            (0..3)
+                .map(|p| assignment.iter().filter(|&&q| q == p).count())
+                .collect::<Vec<_>>()
+        };
+        let points: Vec<[f64; 3]> = (0..10).map(|i| [i as f64, 0.0, 0.0]).collect();
+        assert_eq!(counts(bisect(&points, 3, longest_axis)), vec![3, 3, 4]);
+        assert!(partition(&mesh, 5, DecompositionMethod::Rcb).is_err());
+        assert!(partition(&mesh, 0, DecompositionMethod::Rcb).is_err());
+    }
+
+    #[test]
+    fn test_inertial_follows_rotated_points() {
+        // Points along the diagonal y = x split at the middle of the line
+        let points: Vec<[f64; 3]> = (0..8)
+            .map(|i| [i as f64, i as f64 + 0.1 * (i % 2) as f64, 0.0])
+            .collect();
+        let axis = principal_axis(&points, &(0..8).collect::<Vec<_>>());
+        assert!((axis[0].abs() - axis[1].abs()).abs() < 0.05);
+        assert_eq!(
+            bisect(&points, 2, principal_axis),
+            vec![0, 0, 0, 0, 1, 1, 1, 1]
+        );
+        // Coincident points fall back to the bounding-box axis
+        assert_eq!(principal_axis(&[[1.0; 3]; 3], &[0, 1, 2]), [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_block_procs() {
+        assert_eq!(block_procs(&[5, 10, 3, 4], 2).unwrap(), vec![1, 0, 1, 1]);
+        assert!(block_procs(&[5, 0, 3], 3).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "netcdf4")]
+    fn test_plan_maps_and_clipping() {
+        let mesh = strip();
+        let (plans, elem_local) = plan_decomposition(&mesh, &[1, 1, 0, 0], 2).unwrap();
+        assert_eq!(elem_local, vec![0, 1, 0, 1]);
+
+        // Processor 1 owns elements 1-2; nodes 3 and 8 are shared
+        let plan = &plans[1];
+        assert_eq!(plan.nodes, vec![0, 1, 5, 6, 2, 7]);
+        assert_eq!(plan.num_internal_nodes, 4);
+        assert_eq!(plan.internal_elems, vec![1]);
+        assert_eq!(plan.border_elems, vec![2]);
+        assert_eq!(plan.node_cmaps[&0], vec![5, 6]);
+        assert_eq!(plan.elem_cmaps[&0], (vec![2], vec![2]));
+        assert_eq!(plans[0].elem_cmaps[&1], (vec![1], vec![4]));
+
+        assert_eq!(
+            clip_node_set(&mesh.node_sets[0], plan),
+            (vec![1, 3], vec![1.0, 6.0])
+        );
+        assert_eq!(
+            clip_node_set(&mesh.node_sets[0], &plans[0]).0,
+            Vec::<i64>::new()
+        );
+        let (elems, sides, df) =
+            clip_side_set(&mesh.side_sets[0], 0, &[1, 1, 0, 0], &elem_local).unwrap();
+        assert_eq!((elems, sides), (vec![1, 2], vec![1, 1]));
+        assert_eq!(df, vec![5.0, 6.0, 7.0, 8.0]);
+
+        assert!(plan_decomposition(&mesh, &[0, 0, 0, 0], 2).is_err());
+        assert!(plan_decomposition(&mesh, &[0, 1, 2, 0], 2).is_err());
+    }
+}
@@ -0,0 +1,1114 @@
+//! Comparing two Exodus files within tolerances.
+//!
+//! [`diff_files`] compares a file against a reference the way the SEACAS
+//! `exodiff` tool does and returns a structured [`DiffReport`]. The mesh
+//! structure (sizes, blocks, connectivity and sets), the coordinates, the
+//! time values, and the global, nodal, element block, node set and side set
+//! variables are compared.
+//!
+//! Nodes and elements of the two files are paired by the global IDs of their
+//! ID maps, or by position (node coordinates and element centroids) when the
+//! files were numbered differently (see [`EntityMatching`]). Blocks and sets
+//! are paired by ID and variables by name, ignoring case. Time steps are
+//! paired by index, by time value, or by interpolating the second file to
+//! the times of the first (see [`TimeMatching`]).
+//!
+//! Every variable can have its own [`Tolerance`]: absolute, relative,
+//! combined (absolute below 1 and relative above), or ignored, together with
+//! a floor below which values compare equal.
+//!
+//! Edge and face blocks and edge, face and element sets are not compared.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use exodus_rs::diff::{diff_files, DiffOptions, Tolerance};
+//!
+//! let mut options = DiffOptions::default();
+//! options
+//!     .variable_tolerances
+//!     .insert("temperature".into(), Tolerance::absolute(1e-3).with_floor(1e-9));
+//! let report = diff_files("results.e", "gold.e", &options)?;
+//! if report.has_differences() {
+//!     println!("{}", report);
+//! }
+//! # Ok::<(), exodus_rs::ExodusError>(())
+//! ```
+
+use crate::error::{EntityId, Result};
+use crate::mesh::MeshData;
+use crate::time::{bracket, interpolate, nearest_step, TimeBracket};
+use crate::types::{EntityType, TruthTable};
+use crate::{mode, ExodusFile};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// Variable types compared by [`diff`]
+const VAR_TYPES: [EntityType; 5] = [
+    EntityType::Global,
+    EntityType::Nodal,
+    EntityType::ElemBlock,
+    EntityType::NodeSet,
+    EntityType::SideSet,
+];
+
+/// How the difference between two values is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ToleranceKind {
+    /// `|a - b|`
+    Absolute,
+    /// `|a - b| / max(|a|, |b|)`
+    #[default]
+    Relative,
+    /// `|a - b| / max(1, |a|, |b|)`: absolute for values below 1 and
+    /// relative above
+    Combined,
+    /// Values are not compared
+    Ignore,
+}
+
+/// Allowed difference between two values
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tolerance {
+    /// How the difference is measured
+    pub kind: ToleranceKind,
+    /// Largest difference that still compares equal
+    pub value: f64,
+    /// Values whose magnitudes are both at most the floor compare equal
+    pub floor: f64,
+}
+
+impl Default for Tolerance {
+    /// Relative tolerance of 1e-6 without a floor, as in `exodiff`
+    fn default() -> Self {
+        Self::relative(1e-6)
+    }
+}
+
+impl Tolerance {
+    /// Absolute tolerance
+    pub fn absolute(value: f64) -> Self {
+        Self {
+            kind: ToleranceKind::Absolute,
+            value,
+            floor: 0.0,
+        }
+    }
+
+    /// Relative tolerance
+    pub fn relative(value: f64) -> Self {
+        Self {
+            kind: ToleranceKind::Relative,
+            value,
+            floor: 0.0,
+        }
+    }
+
+    /// Combined tolerance: absolute for values below 1, relative above
+    pub fn combined(value: f64) -> Self {
+        Self {
+            kind: ToleranceKind::Combined,
+            value,
+            floor: 0.0,
+        }
+    }
+
+    /// Skip the comparison entirely
+    pub fn ignore() -> Self {
+        Self {
+            kind: ToleranceKind::Ignore,
+            value: 0.0,
+            floor: 0.0,
+        }
+    }
+
+    /// Set the floor below which values compare equal
+    pub fn with_floor(mut self, floor: f64) -> Self {
+        self.floor = floor;
+        self
+    }
+
+    /// Difference between two values, measured as this tolerance measures it
+    ///
+    /// Values both at or below the floor differ by 0. A NaN differs
+    /// infinitely from anything but another NaN.
+    pub fn difference(&self, first: f64, second: f64) -> f64 {
+        if first.is_nan() || second.is_nan() {
+            return if first.is_nan() && second.is_nan() {
+                0.0
+            } else {
+                f64::INFINITY
+            };
+        }
+        if self.kind == ToleranceKind::Ignore
+            || (first.abs() <= self.floor && second.abs() <= self.floor)
+            || first == second
+        {
+            return 0.0;
+        }
+        let delta = (first - second).abs();
+        let scale = first.abs().max(second.abs());
+        match self.kind {
+            ToleranceKind::Absolute => delta,
+            ToleranceKind::Relative => delta / scale,
+            ToleranceKind::Combined => delta / scale.max(1.0),
+            ToleranceKind::Ignore => 0.0,
+        }
+    }
+
+    /// Check whether two values differ by more than this tolerance
+    pub fn exceeded(&self, first: f64, second: f64) -> bool {
+        self.kind != ToleranceKind::Ignore && self.difference(first, second) > self.value
+    }
+}
+
+/// How nodes and elements of the two files are paired
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EntityMatching {
+    /// By the global IDs of the node and element ID maps (storage order for
+    /// files without maps)
+    #[default]
+    Ids,
+    /// Nodes by coordinates and elements by centroid, pairing each entity
+    /// with the nearest one of the second file within `tolerance` distance
+    Coordinates {
+        /// Largest distance between paired entities
+        tolerance: f64,
+    },
+}
+
+/// How time steps of the two files are paired
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeMatching {
+    /// Step `i` of the first file with step `i` of the second, comparing the
+    /// time values too
+    Index,
+    /// Each step of the first file with the step of the second file nearest
+    /// in time, if within the time tolerance
+    #[default]
+    Time,
+    /// Each step of the first file with the second file linearly
+    /// interpolated to its time, if inside the second file's time range
+    Interpolate,
+}
+
+/// Options controlling [`diff`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiffOptions {
+    /// Tolerance of variables without an entry in `variable_tolerances`
+    pub tolerance: Tolerance,
+    /// Tolerances of individual variables, keyed by name (matched ignoring
+    /// case)
+    pub variable_tolerances: HashMap<String, Tolerance>,
+    /// Tolerance of the nodal coordinates
+    pub coordinate_tolerance: Tolerance,
+    /// Tolerance of the time values
+    pub time_tolerance: Tolerance,
+    /// How nodes and elements are paired
+    pub entity_matching: EntityMatching,
+    /// How time steps are paired
+    pub time_matching: TimeMatching,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: Tolerance::default(),
+            variable_tolerances: HashMap::new(),
+            coordinate_tolerance: Tolerance::combined(1e-6),
+            time_tolerance: Tolerance::combined(1e-6),
+            entity_matching: EntityMatching::default(),
+            time_matching: TimeMatching::default(),
+        }
+    }
+}
+
+impl DiffOptions {
+    /// Tolerance applied to the variable `name`
+    pub fn tolerance_for(&self, name: &str) -> Tolerance {
+        self.variable_tolerances
+            .iter()
+            .find(|(var, _)| var.eq_ignore_ascii_case(name))
+            .map_or(self.tolerance, |(_, &tolerance)| tolerance)
+    }
+}
+
+/// One pair of compared values
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValueDiff {
+    /// 0-based position of the value in the first file (node, element within
+    /// its block, set entry, or time step)
+    pub index: usize,
+    /// Global ID of the node or element the value belongs to (0 for global
+    /// variables and time values)
+    pub id: i64,
+    /// Value in the first file
+    pub first: f64,
+    /// Value in the second file
+    pub second: f64,
+    /// Difference as measured by the tolerance
+    pub difference: f64,
+}
+
+/// Values of one quantity that exceed their tolerance
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldDiff {
+    /// Variable type (`Nodal` for coordinates)
+    pub var_type: EntityType,
+    /// Block or set ID (0 for global, nodal and coordinate values)
+    pub entity_id: EntityId,
+    /// Variable or coordinate name
+    pub name: String,
+    /// Time step of the first file (`None` for coordinates)
+    pub step: Option<usize>,
+    /// Number of values exceeding the tolerance
+    pub num_exceeded: usize,
+    /// Value with the largest difference
+    pub max: ValueDiff,
+}
+
+/// Result of comparing two files
+///
+/// Empty when the files agree within tolerances.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiffReport {
+    /// Differences in the mesh structure and variable definitions
+    pub structure: Vec<String>,
+    /// Coordinates exceeding the coordinate tolerance
+    pub coordinates: Vec<FieldDiff>,
+    /// Time values exceeding the time tolerance (`index` is the step)
+    pub times: Vec<ValueDiff>,
+    /// Steps of the first file without a counterpart in the second
+    pub unmatched_steps: Vec<usize>,
+    /// Variables exceeding their tolerance
+    pub variables: Vec<FieldDiff>,
+    /// Number of time steps compared
+    pub num_steps_compared: usize,
+}
+
+impl DiffReport {
+    /// Check whether any difference was found
+    pub fn has_differences(&self) -> bool {
+        !(self.structure.is_empty()
+            && self.coordinates.is_empty()
+            && self.times.is_empty()
+            && self.unmatched_steps.is_empty()
+            && self.variables.is_empty())
+    }
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(step) = self.step {
+            write!(f, "step {} ", step + 1)?;
+        }
+        match self.var_type {
+            EntityType::Global | EntityType::Nodal => write!(f, "{} {}", self.var_type, self.name)?,
+            _ => write!(f, "{} {} {}", self.var_type, self.entity_id, self.name)?,
+        }
+        write!(
+            f,
+            ": {} value{} differ, max {:e} ({} vs {})",
+            self.num_exceeded,
+            if self.num_exceeded == 1 { "" } else { "s" },
+            self.max.difference,
+            self.max.first,
+            self.max.second
+        )?;
+        match self.var_type {
+            EntityType::Global => Ok(()),
+            EntityType::Nodal | EntityType::NodeSet => write!(f, " at node {}", self.max.id),
+            _ => write!(f, " at element {}", self.max.id),
+        }
+    }
+}
+
+impl fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.structure.is_empty() {
+            writeln!(f, "Structure:")?;
+            for message in &self.structure {
+                writeln!(f, "  {}", message)?;
+            }
+        }
+        if !self.coordinates.is_empty() {
+            writeln!(f, "Coordinates:")?;
+            for diff in &self.coordinates {
+                writeln!(f, "  {}", diff)?;
+            }
+        }
+        if !self.times.is_empty() || !self.unmatched_steps.is_empty() {
+            writeln!(f, "Time steps:")?;
+            for diff in &self.times {
+                writeln!(
+                    f,
+                    "  step {}: time {} vs {}",
+                    diff.index + 1,
+                    diff.first,
+                    diff.second
+                )?;
+            }
+            for step in &self.unmatched_steps {
+                writeln!(
+                    f,
+                    "  step {}: no matching step in the second file",
+                    step + 1
+                )?;
+            }
+        }
+        if !self.variables.is_empty() {
+            writeln!(f, "Variables:")?;
+            for diff in &self.variables {
+                writeln!(f, "  {}", diff)?;
+            }
+        }
+        if self.has_differences() {
+            write!(f, "Files differ")
+        } else {
+            write!(
+                f,
+                "Files are the same ({} time steps compared)",
+                self.num_steps_compared
+            )
+        }
+    }
+}
+
+/// Compare the files at `first` and `second`
+///
+/// # Errors
+///
+/// Returns an error if either file cannot be opened or read. Differences
+/// between the files are reported, not returned as errors.
+pub fn diff_files(
+    first: impl AsRef<Path>,
+    second: impl AsRef<Path>,
+    options: &DiffOptions,
+) -> Result<DiffReport> {
+    let first = ExodusFile::<mode::Read>::open(first)?;
+    let second = ExodusFile::<mode::Read>::open(second)?;
+    diff(&first, &second, options)
+}
+
+/// Compare two open files
+///
+/// # Errors
+///
+/// Returns an error if either file cannot be read
+pub fn diff(
+    first: &ExodusFile<mode::Read>,
+    second: &ExodusFile<mode::Read>,
+    options: &DiffOptions,
+) -> Result<DiffReport> {
+    let mut report = DiffReport::default();
+    let a = MeshData::read(first)?;
+    let b = MeshData::read(second)?;
+    let structure = &mut report.structure;
+
+    for (what, first_count, second_count) in [
+        ("Dimensions", a.num_dim, b.num_dim),
+        ("Nodes", a.num_nodes(), b.num_nodes()),
+        ("Elements", a.elem_ids.len(), b.elem_ids.len()),
+    ] {
+        if first_count != second_count {
+            structure.push(format!("{}: {} vs {}", what, first_count, second_count));
+        }
+    }
+
+    // Pair nodes and elements
+    let (node_match, elem_match) = match options.entity_matching {
+        EntityMatching::Ids => (
+            match_ids(&a.node_ids, &b.node_ids),
+            match_ids(&a.elem_ids, &b.elem_ids),
+        ),
+        EntityMatching::Coordinates { tolerance } => (
+            match_points(&a.points(), &b.points(), tolerance),
+            match_points(&a.centroids(), &b.centroids(), tolerance),
+        ),
+    };
+    for (what, matches) in [("nodes", &node_match), ("elements", &elem_match)] {
+        let unmatched = matches.iter().filter(|m| m.is_none()).count();
+        if unmatched > 0 {
+            structure.push(format!(
+                "{} {} of the first file have no match in the second",
+                unmatched, what
+            ));
+        }
+    }
+
+    // Coordinates
+    for (d, name) in ["x", "y", "z"]
+        .iter()
+        .enumerate()
+        .take(a.num_dim.min(b.num_dim))
+    {
+        if let Some((num_exceeded, max)) = compare_values(
+            &a.coords[d],
+            &b.coords[d],
+            &node_match,
+            &a.node_ids,
+            options.coordinate_tolerance,
+        ) {
+            report.coordinates.push(FieldDiff {
+                var_type: EntityType::Nodal,
+                entity_id: 0,
+                name: name.to_string(),
+                step: None,
+                num_exceeded,
+                max,
+            });
+        }
+    }
+
+    // Blocks, with each block's element pairing
+    let block_pairs = pair_ids(
+        a.blocks.iter().map(|block| block.id),
+        b.blocks.iter().map(|block| block.id),
+        "Element block",
+        structure,
+    );
+    let mut block_targets = Vec::with_capacity(block_pairs.len());
+    for &(i, j) in &block_pairs {
+        let (block, other) = (&a.blocks[i], &b.blocks[j]);
+        if !block.topology.eq_ignore_ascii_case(&other.topology) {
+            structure.push(format!(
+                "Element block {} topology: {} vs {}",
+                block.id, block.topology, other.topology
+            ));
+        }
+        if block.num_entries != other.num_entries {
+            structure.push(format!(
+                "Element block {} elements: {} vs {}",
+                block.id, block.num_entries, other.num_entries
+            ));
+        }
+
+        let npe = block.num_nodes_per_entry;
+        let same_shape = npe == other.num_nodes_per_entry;
+        let offset = b.offsets[j];
+        let mut targets = Vec::with_capacity(block.num_entries);
+        let mut moved = 0;
+        let mut different = 0;
+        for e in 0..block.num_entries {
+            let target = elem_match[a.offsets[i] + e]
+                .and_then(|global| global.checked_sub(offset))
+                .filter(|&local| local < other.num_entries);
+            if elem_match[a.offsets[i] + e].is_some() && target.is_none() {
+                moved += 1;
+            }
+            if let (Some(t), true) = (target, same_shape) {
+                let nodes = &a.connectivity[i][e * npe..(e + 1) * npe];
+                let other_nodes = &b.connectivity[j][t * npe..(t + 1) * npe];
+                let same = nodes.iter().zip(other_nodes).all(|(&n, &m)| {
+                    usize::try_from(n - 1)
+                        .ok()
+                        .and_then(|n| node_match.get(n).copied().flatten())
+                        .is_some_and(|n| n as i64 + 1 == m)
+                });
+                if !same {
+                    different += 1;
+                }
+            }
+            targets.push(target);
+        }
+        if !same_shape {
+            structure.push(format!(
+                "Element block {} nodes per element: {} vs {}",
+                block.id, npe, other.num_nodes_per_entry
+            ));
+        }
+        if moved > 0 {
+            structure.push(format!(
+                "Element block {}: {} elements are in another block of the second file",
+                block.id, moved
+            ));
+        }
+        if different > 0 {
+            structure.push(format!(
+                "Element block {}: {} elements have different connectivity",
+                block.id, different
+            ));
+        }
+        block_targets.push(targets);
+    }
+    let block_ids: Vec<Vec<i64>> = block_pairs
+        .iter()
+        .map(|&(i, _)| {
+            let start = a.offsets[i];
+            a.elem_ids[start..start + a.blocks[i].num_entries].to_vec()
+        })
+        .collect();
+
+    // Sets, pairing entries through the node and element pairing
+    let node_set_pairs = pair_ids(
+        a.node_sets.iter().map(|set| set.id),
+        b.node_sets.iter().map(|set| set.id),
+        "Node set",
+        structure,
+    );
+    let mut node_set_targets = Vec::with_capacity(node_set_pairs.len());
+    let mut node_set_ids = Vec::with_capacity(node_set_pairs.len());
+    for &(i, j) in &node_set_pairs {
+        let (set, other) = (&a.node_sets[i], &b.node_sets[j]);
+        let keys: Vec<Option<usize>> = set
+            .nodes
+            .iter()
+            .map(|&n| entry_target(n, &node_match))
+            .collect();
+        let other_keys: Vec<Option<usize>> = other
+            .nodes
+            .iter()
+            .map(|&n| usize::try_from(n - 1).ok())
+            .collect();
+        node_set_targets.push(pair_entries(
+            "Node set",
+            set.id,
+            &keys,
+            &other_keys,
+            structure,
+        ));
+        node_set_ids.push(entity_ids(&set.nodes, &a.node_ids));
+    }
+    let side_set_pairs = pair_ids(
+        a.side_sets.iter().map(|set| set.id),
+        b.side_sets.iter().map(|set| set.id),
+        "Side set",
+        structure,
+    );
+    let mut side_set_targets = Vec::with_capacity(side_set_pairs.len());
+    let mut side_set_ids = Vec::with_capacity(side_set_pairs.len());
+    for &(i, j) in &side_set_pairs {
+        let (set, other) = (&a.side_sets[i], &b.side_sets[j]);
+        let keys: Vec<Option<(usize, i64)>> = set
+            .elements
+            .iter()
+            .zip(&set.sides)
+            .map(|(&e, &side)| entry_target(e, &elem_match).map(|e| (e, side)))
+            .collect();
+        let other_keys: Vec<Option<(usize, i64)>> = other
+            .elements
+            .iter()
+            .zip(&other.sides)
+            .map(|(&e, &side)| usize::try_from(e - 1).ok().map(|e| (e, side)))
+            .collect();
+        side_set_targets.push(pair_entries(
+            "Side set",
+            set.id,
+            &keys,
+            &other_keys,
+            structure,
+        ));
+        side_set_ids.push(entity_ids(&set.elements, &a.elem_ids));
+    }
+
+    let blocks = PairedEntities {
+        pairs: block_pairs,
+        targets: block_targets,
+        ids: block_ids,
+    };
+    let node_sets = PairedEntities {
+        pairs: node_set_pairs,
+        targets: node_set_targets,
+        ids: node_set_ids,
+    };
+    let side_sets = PairedEntities {
+        pairs: side_set_pairs,
+        targets: side_set_targets,
+        ids: side_set_ids,
+    };
+
+    // Variables paired by name, expanded over the paired blocks and sets
+    let global_target = [Some(0)];
+    let mut comparisons = Vec::new();
+    for var_type in VAR_TYPES {
+        let names = first.variable_names(var_type)?;
+        let other_names = second.variable_names(var_type)?;
+        let mut var_pairs = Vec::with_capacity(names.len());
+        for (v, name) in names.iter().enumerate() {
+            match other_names
+                .iter()
+                .position(|other| other.eq_ignore_ascii_case(name))
+            {
+                Some(w) => var_pairs.push((v, w, name.as_str())),
+                None => structure.push(format!(
+                    "{} variable {} is missing from the second file",
+                    var_type, name
+                )),
+            }
+        }
+        for name in &other_names {
+            if !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+                structure.push(format!(
+                    "{} variable {} is missing from the first file",
+                    var_type, name
+                ));
+            }
+        }
+        if var_pairs.is_empty() {
+            continue;
+        }
+
+        let paired = match var_type {
+            EntityType::ElemBlock => &blocks,
+            EntityType::NodeSet => &node_sets,
+            EntityType::SideSet => &side_sets,
+            _ => {
+                for &(v, w, name) in &var_pairs {
+                    let (targets, ids): (&[Option<usize>], &[i64]) =
+                        if var_type == EntityType::Nodal {
+                            (&node_match, &a.node_ids)
+                        } else {
+                            (&global_target, &[])
+                        };
+                    comparisons.push(Comparison {
+                        var_type,
+                        entity_id: 0,
+                        name: name.to_string(),
+                        first_var: v,
+                        second_var: w,
+                        targets,
+                        ids,
+                        tolerance: options.tolerance_for(name),
+                    });
+                }
+                continue;
+            }
+        };
+        let table = first.truth_table(var_type)?;
+        let other_table = second.truth_table(var_type)?;
+        let entity_id_of = |index: usize| match var_type {
+            EntityType::ElemBlock => a.blocks[index].id,
+            EntityType::NodeSet => a.node_sets[index].id,
+            _ => a.side_sets[index].id,
+        };
+        for (p, &(i, j)) in paired.pairs.iter().enumerate() {
+            for &(v, w, name) in &var_pairs {
+                let defined = truth(&table, i, v);
+                if defined != truth(&other_table, j, w) {
+                    structure.push(format!(
+                        "{} {} variable {} is only defined in the {} file",
+                        var_type,
+                        entity_id_of(i),
+                        name,
+                        if defined { "first" } else { "second" }
+                    ));
+                }
+                if defined && truth(&other_table, j, w) {
+                    comparisons.push(Comparison {
+                        var_type,
+                        entity_id: entity_id_of(i),
+                        name: name.to_string(),
+                        first_var: v,
+                        second_var: w,
+                        targets: &paired.targets[p],
+                        ids: &paired.ids[p],
+                        tolerance: options.tolerance_for(name),
+                    });
+                }
+            }
+        }
+    }
+
+    // Time steps
+    let times = first.times()?;
+    let other_times = second.times()?;
+    if times.len() != other_times.len() {
+        structure.push(format!(
+            "Time steps: {} vs {}",
+            times.len(),
+            other_times.len()
+        ));
+    }
+    let steps = pair_steps(&times, &other_times, options, &mut report);
+
+    for (step, source) in &steps {
+        for comparison in &comparisons {
+            let values = first.var(
+                *step,
+                comparison.var_type,
+                comparison.entity_id,
+                comparison.first_var,
+            )?;
+            let read = |s: usize| {
+                second.var(
+                    s,
+                    comparison.var_type,
+                    comparison.entity_id,
+                    comparison.second_var,
+                )
+            };
+            let other_values = match source {
+                StepSource::Step(s) => read(*s)?,
+                StepSource::Interpolated(bracket) => interpolate(bracket, read)?,
+            };
+            if let Some((num_exceeded, max)) = compare_values(
+                &values,
+                &other_values,
+                comparison.targets,
+                comparison.ids,
+                comparison.tolerance,
+            ) {
+                report.variables.push(FieldDiff {
+                    var_type: comparison.var_type,
+                    entity_id: comparison.entity_id,
+                    name: comparison.name.clone(),
+                    step: Some(*step),
+                    num_exceeded,
+                    max,
+                });
+            }
+        }
+    }
+    report.num_steps_compared = steps.len();
+    Ok(report)
+}
+
+/// Position in `second` of each ID of `first`
+fn match_ids(first: &[i64], second: &[i64]) -> Vec<Option<usize>> {
+    let index: HashMap<i64, usize> = second
+        .iter()
+        .enumerate()
+        .map(|(position, &id)| (id, position))
+        .collect();
+    first.iter().map(|id| index.get(id).copied()).collect()
+}
+
+/// Nearest point of `second` to each point of `first`, within `tolerance`
+fn match_points(first: &[[f64; 3]], second: &[[f64; 3]], tolerance: f64) -> Vec<Option<usize>> {
+    // Candidates are found in a window of the x-sorted points
+    let mut sorted: Vec<usize> = (0..second.len()).collect();
+    sorted.sort_by(|&i, &j| second[i][0].total_cmp(&second[j][0]));
+    let xs: Vec<f64> = sorted.iter().map(|&i| second[i][0]).collect();
+
+    first
+        .iter()
+        .map(|p| {
+            let start = xs.partition_point(|&x| x < p[0] - tolerance);
+            let mut best: Option<(usize, f64)> = None;
+            for &i in sorted[start..]
+                .iter()
+                .take_while(|&&i| second[i][0] <= p[0] + tolerance)
+            {
+                let q = second[i];
+                let distance =
+                    ((p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2) + (p[2] - q[2]).powi(2)).sqrt();
+                if distance <= tolerance && best.map_or(true, |(_, d)| distance < d) {
+                    best = Some((i, distance));
+                }
+            }
+            best.map(|(i, _)| i)
+        })
+        .collect()
+}
+
+/// Pair blocks or sets by ID, reporting the unpaired ones
+fn pair_ids(
+    first: impl Iterator<Item = EntityId>,
+    second: impl Iterator<Item = EntityId>,
+    what: &str,
+    structure: &mut Vec<String>,
+) -> Vec<(usize, usize)> {
+    let first: Vec<EntityId> = first.collect();
+    let second: Vec<EntityId> = second.collect();
+    let mut pairs = Vec::with_capacity(first.len());
+    for (i, id) in first.iter().enumerate() {
+        match second.iter().position(|other| other == id) {
+            Some(j) => pairs.push((i, j)),
+            None => structure.push(format!("{} {} is missing from the second file", what, id)),
+        }
+    }
+    for id in second.iter().filter(|id| !first.contains(id)) {
+        structure.push(format!("{} {} is missing from the first file", what, id));
+    }
+    pairs
+}
+
+/// 0-based entity of the second file paired with a 1-based entity of the first
+fn entry_target(entity: i64, matches: &[Option<usize>]) -> Option<usize> {
+    usize::try_from(entity - 1)
+        .ok()
+        .and_then(|index| matches.get(index).copied().flatten())
+}
+
+/// Global IDs of the 1-based entities of a set
+fn entity_ids(entities: &[i64], ids: &[i64]) -> Vec<i64> {
+    entities
+        .iter()
+        .map(|&e| {
+            usize::try_from(e - 1)
+                .ok()
+                .and_then(|index| ids.get(index).copied())
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Position in the second set of each entry of the first, reporting entries
+/// present in only one of them
+fn pair_entries<K: Copy + Eq + std::hash::Hash>(
+    what: &str,
+    id: EntityId,
+    keys: &[Option<K>],
+    other_keys: &[Option<K>],
+    structure: &mut Vec<String>,
+) -> Vec<Option<usize>> {
+    if keys.len() != other_keys.len() {
+        structure.push(format!(
+            "{} {} entries: {} vs {}",
+            what,
+            id,
+            keys.len(),
+            other_keys.len()
+        ));
+    }
+    let index: HashMap<K, usize> = other_keys
+        .iter()
+        .enumerate()
+        .filter_map(|(position, key)| key.map(|key| (key, position)))
+        .collect();
+    let targets: Vec<Option<usize>> = keys
+        .iter()
+        .map(|key| key.and_then(|key| index.get(&key).copied()))
+        .collect();
+    let unmatched = targets.iter().filter(|t| t.is_none()).count();
+    if unmatched > 0 {
+        structure.push(format!(
+            "{} {}: {} entries of the first file are not in the second",
+            what, id, unmatched
+        ));
+    }
+    targets
+}
+
+fn truth(table: &TruthTable, entity: usize, var: usize) -> bool {
+    entity < table.num_blocks && var < table.num_vars && table.get(entity, var)
+}
+
+/// Blocks or sets of one type paired by ID, with the pairing of their entries
+struct PairedEntities {
+    /// Storage indices in the first and second file
+    pairs: Vec<(usize, usize)>,
+    /// Position in the second file of each entry of the first, per pair
+    targets: Vec<Vec<Option<usize>>>,
+    /// Global IDs of the entries of the first file, per pair
+    ids: Vec<Vec<i64>>,
+}
+
+/// Source of the second file's values for one step of the first
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StepSource {
+    Step(usize),
+    Interpolated(TimeBracket),
+}
+
+/// Pair the steps of the first file with the second, recording time value
+/// differences and unpaired steps in `report`
+fn pair_steps(
+    times: &[f64],
+    other_times: &[f64],
+    options: &DiffOptions,
+    report: &mut DiffReport,
+) -> Vec<(usize, StepSource)> {
+    let tolerance = options.time_tolerance;
+    let mut steps = Vec::with_capacity(times.len());
+    for (step, &time) in times.iter().enumerate() {
+        let source = match options.time_matching {
+            TimeMatching::Index => other_times.get(step).map(|&other| {
+                if tolerance.exceeded(time, other) {
+                    report.times.push(ValueDiff {
+                        index: step,
+                        id: 0,
+                        first: time,
+                        second: other,
+                        difference: tolerance.difference(time, other),
+                    });
+                }
+                StepSource::Step(step)
+            }),
+            TimeMatching::Time => nearest_step(other_times, time)
+                .ok()
+                .filter(|&other| !tolerance.exceeded(time, other_times[other]))
+                .map(StepSource::Step),
+            TimeMatching::Interpolate => bracket(other_times, time).ok().map(|bracket| {
+                if bracket.lower == bracket.upper {
+                    StepSource::Step(bracket.lower)
+                } else {
+                    StepSource::Interpolated(bracket)
+                }
+            }),
+        };
+        match source {
+            Some(source) => steps.push((step, source)),
+            None => report.unmatched_steps.push(step),
+        }
+    }
+    steps
+}
+
+/// Comparison of one variable on one block or set, repeated every step
+struct Comparison<'a> {
+    var_type: EntityType,
+    entity_id: EntityId,
+    name: String,
+    first_var: usize,
+    second_var: usize,
+    /// Position in the second file's values of each of the first file's
+    targets: &'a [Option<usize>],
+    /// Global IDs of the first file's entities, for the report
+    ids: &'a [i64],
+    tolerance: Tolerance,
+}
+
+/// Count the paired values exceeding `tolerance` and find the largest
+/// difference, or `None` if all agree
+fn compare_values(
+    first: &[f64],
+    second: &[f64],
+    targets: &[Option<usize>],
+    ids: &[i64],
+    tolerance: Tolerance,
+) -> Option<(usize, ValueDiff)> {
+    let mut num_exceeded = 0;
+    let mut max: Option<ValueDiff> = None;
+    for (index, (&value, target)) in first.iter().zip(targets).enumerate() {
+        let Some(&other) = target.and_then(|t| second.get(t)) else {
+            continue;
+        };
+        if !tolerance.exceeded(value, other) {
+            continue;
+        }
+        num_exceeded += 1;
+        let difference = tolerance.difference(value, other);
+        if max.map_or(true, |m| difference > m.difference) {
+            max = Some(ValueDiff {
+                index,
+                id: ids.get(index).copied().unwrap_or(0),
+                first: value,
+                second: other,
+                difference,
+            });
+        }
+    }
+    max.map(|max| (num_exceeded, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tolerance_kinds() {
+        let absolute = Tolerance::absolute(0.1);
+        assert!(!absolute.exceeded(100.0, 100.05));
+        assert!(absolute.exceeded(1.0, 1.2));
+
+        let relative = Tolerance::relative(1e-3);
+        assert!(!relative.exceeded(1000.0, 1000.5));
+        assert!(relative.exceeded(1e-9, 2e-9));
+        assert_eq!(relative.difference(0.0, 0.0), 0.0);
+
+        // Combined is absolute below 1 and relative above
+        let combined = Tolerance::combined(1e-3);
+        assert!(!combined.exceeded(1e-9, 2e-9));
+        assert!(!combined.exceeded(1000.0, 1000.5));
+        assert!(combined.exceeded(0.5, 0.502));
+
+        let floored = Tolerance::relative(1e-6).with_floor(1e-8);
+        assert!(!floored.exceeded(1e-9, -1e-9));
+        assert!(floored.exceeded(1e-9, 1e-7));
+
+        assert!(!Tolerance::ignore().exceeded(0.0, 1e30));
+        assert!(relative.exceeded(f64::NAN, 1.0));
+        assert!(!relative.exceeded(f64::NAN, f64::NAN));
+    }
+
+    #[test]
+    fn test_tolerance_lookup() {
+        let mut options = DiffOptions::default();
+        options
+            .variable_tolerances
+            .insert("Temp".into(), Tolerance::absolute(0.5));
+        assert_eq!(options.tolerance_for("TEMP"), Tolerance::absolute(0.5));
+        assert_eq!(options.tolerance_for("stress"), Tolerance::default());
+    }
+
+    #[test]
+    fn test_entity_matching() {
+        assert_eq!(
+            match_ids(&[10, 20, 30], &[30, 10, 40]),
+            vec![Some(1), None, Some(0)]
+        );
+
+        let first = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [5.0, 5.0, 0.0]];
+        let second = [[1.0, 1e-9, 0.0], [0.0, 0.0, 1e-9], [0.5, 0.0, 0.0]];
+        assert_eq!(
+            match_points(&first, &second, 1e-6),
+            vec![Some(1), Some(0), None]
+        );
+    }
+
+    #[test]
+    fn test_step_pairing() {
+        let times = [0.0, 0.5, 1.0, 2.0];
+        let other = [0.0, 1.0, 1.5];
+        let mut report = DiffReport::default();
+        let mut options = DiffOptions {
+            time_matching: TimeMatching::Index,
+            ..Default::default()
+        };
+        let steps = pair_steps(&times, &other, &options, &mut report);
+        assert_eq!(steps.len(), 3);
+        assert_eq!(report.times.len(), 2);
+        assert_eq!(report.unmatched_steps, vec![3]);
+
+        options.time_matching = TimeMatching::Time;
+        let mut report = DiffReport::default();
+        let steps = pair_steps(&times, &other, &options, &mut report);
+        assert_eq!(
+            steps,
+            vec![(0, StepSource::Step(0)), (2, StepSource::Step(1))]
+        );
+        assert_eq!(report.unmatched_steps, vec![1, 3]);
+
+        options.time_matching = TimeMatching::Interpolate;
+        let mut report = DiffReport::default();
+        let steps = pair_steps(&times, &other, &options, &mut report);
+        assert_eq!(steps.len(), 3);
+        assert_eq!(
+            steps[1].1,
+            StepSource::Interpolated(TimeBracket {
+                lower: 0,
+                upper: 1,
+                fraction: 0.5
+            })
+        );
+        assert_eq!(report.unmatched_steps, vec![3]);
+    }
+
+    #[test]
+    fn test_compare_values_reports_largest() {
+        let tolerance = Tolerance::absolute(0.1);
+        let targets = [Some(2), Some(1), Some(0), None];
+        let result = compare_values(
+            &[1.0, 2.0, 3.0, 4.0],
+            &[3.5, 2.05, 1.0],
+            &targets,
+            &[11, 12, 13, 14],
+            tolerance,
+        );
+        let (num_exceeded, max) = result.unwrap();
+        assert_eq!(num_exceeded, 1);
+        assert_eq!((max.index, max.id), (2, 13));
+        assert_eq!((max.first, max.second), (3.0, 3.5));
+        assert!(compare_values(&[1.0], &[1.05], &[Some(0)], &[], tolerance).is_none());
+    }
+}
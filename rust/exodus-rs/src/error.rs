@@ -68,6 +68,15 @@ pub enum ExodusError {
         actual: usize,
     },
 
+    /// Integer value does not fit the file's integer storage
+    #[error("Integer overflow: {context} value {value} does not fit in 32-bit storage (use Int64Mode::Int64)")]
+    IntegerOverflow {
+        /// What was being written (e.g. "connectivity of block 10")
+        context: String,
+        /// The offending value
+        value: i64,
+    },
+
     /// Invalid time step
     #[error("Invalid time step: {0}")]
     InvalidTimeStep(usize),
@@ -84,14 +93,27 @@ pub enum ExodusError {
     #[error("Read operation on write-only file")]
     ReadOnWriteOnly,
 
+    /// Feature not supported by the file format or NetCDF backend
+    #[error("Unsupported feature: {0}")]
+    UnsupportedFeature(String),
+
     /// File not initialized
     #[error("File not initialized")]
     NotInitialized,
 
+    /// Handle passed to the low-level API is not open
+    #[error("Invalid file handle: {0}")]
+    InvalidHandle(i32),
+
     /// I/O error
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
+    /// JSON serialization error
+    #[cfg(feature = "serde")]
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
     /// Other error
     #[error("{0}")]
     Other(String),
@@ -3,17 +3,27 @@
 //! This module provides the core file operations including creating, opening,
 //! and closing Exodus files.
 
-use crate::error::Result;
+use crate::backend::Backend;
+#[cfg(not(feature = "netcdf4"))]
+use crate::backend::ClassicFile;
+use crate::error::{EntityId, ExodusError, Result};
+#[cfg(feature = "netcdf4")]
+use crate::types::CreateMode;
 use crate::types::{
-    CreateMode, CreateOptions, FileFormat, FileStorageFormat, FloatSize, Int64Mode, VarStorageMode,
+    Compression, CreateOptions, EntityType, FileFormat, FileStorageFormat, FloatSize, Int64Mode,
+    VarStorageMode,
 };
 use crate::utils::constants::*;
-use crate::{mode, FileMode, WritableMode};
+use crate::utils::naming;
+#[cfg(feature = "netcdf4")]
+use crate::WritableMode;
+use crate::{mode, FileMode};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
 
-#[cfg(feature = "netcdf4")]
 /// NetCDF define mode state
+#[cfg(feature = "netcdf4")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum DefineMode {
     /// In define mode (can add dimensions, variables, attributes)
@@ -26,33 +36,397 @@ pub(crate) enum DefineMode {
 #[derive(Debug)]
 pub(crate) struct FileMetadata {
     /// Whether the file has been initialized with database parameters
+    #[cfg(feature = "netcdf4")]
     pub initialized: bool,
     /// Cached title
+    #[cfg(feature = "netcdf4")]
     pub title: Option<String>,
     /// Cached number of dimensions
+    #[cfg(feature = "netcdf4")]
     pub num_dim: Option<usize>,
     /// Cache for dimension IDs (dimension name -> size)
     pub dim_cache: HashMap<String, usize>,
     /// Current NetCDF define/data mode (only tracked for Write/Append modes)
+    #[cfg(feature = "netcdf4")]
     pub define_mode: DefineMode,
     /// Performance configuration for HDF5/NetCDF optimization
+    #[cfg(feature = "netcdf4")]
     pub performance: Option<crate::performance::PerformanceConfig>,
+    /// Number of variables whose chunk cache has been sized from `performance`
+    #[cfg(feature = "netcdf4")]
+    pub chunk_cache_vars: usize,
     /// Detected storage format for variable data
     pub storage_format: FileStorageFormat,
+    /// Compression applied to newly created bulk-data variables
+    pub compression: Option<Compression>,
+    /// Whether the shuffle filter accompanies compression
+    #[cfg(feature = "netcdf4")]
+    pub shuffle: bool,
+    /// NetCDF id of the writable handle, used for filters the `netcdf` crate lacks
+    #[cfg(feature = "netcdf4")]
+    pub ncid: Option<std::os::raw::c_int>,
+    /// Storage precision for floating-point bulk data
+    pub float_size: FloatSize,
+    /// On-disk NetCDF format
+    pub format: FileFormat,
+    /// Integer width used for connectivity, maps, set entries and IDs
+    pub int64_mode: Int64Mode,
+    /// ID to index maps of block and set types, built on first lookup
+    pub entity_indices: Mutex<HashMap<EntityType, Arc<HashMap<EntityId, usize>>>>,
+    /// Number of leading time steps visible through this handle, if truncated
+    pub time_step_limit: Option<usize>,
 }
 
 impl FileMetadata {
     /// Create a new empty metadata cache
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
+            #[cfg(feature = "netcdf4")]
             initialized: false,
+            #[cfg(feature = "netcdf4")]
             title: None,
+            #[cfg(feature = "netcdf4")]
             num_dim: None,
             dim_cache: HashMap::new(),
+            #[cfg(feature = "netcdf4")]
             define_mode: DefineMode::Define,
+            #[cfg(feature = "netcdf4")]
             performance: None,
+            #[cfg(feature = "netcdf4")]
+            chunk_cache_vars: 0,
             storage_format: FileStorageFormat::default(),
+            compression: None,
+            #[cfg(feature = "netcdf4")]
+            shuffle: false,
+            #[cfg(feature = "netcdf4")]
+            ncid: None,
+            float_size: FloatSize::Float64,
+            format: FileFormat::NetCdf4,
+            int64_mode: Int64Mode::Int64,
+            entity_indices: Mutex::new(HashMap::new()),
+            time_step_limit: None,
+        }
+    }
+
+    /// Number of time steps visible out of `stored`, honoring a logical truncation
+    pub(crate) fn visible_time_steps(&self, stored: usize) -> usize {
+        self.time_step_limit
+            .map_or(stored, |limit| limit.min(stored))
+    }
+
+    /// Reject reads of a step hidden by a logical truncation
+    pub(crate) fn check_time_step(&self, step: usize) -> Result<()> {
+        match self.time_step_limit {
+            Some(limit) if step >= limit => Err(ExodusError::InvalidTimeStep(step)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Index of a block or set within its type, from its ID
+    ///
+    /// The ID variable of each type is read once and the resulting map cached
+    /// until [`invalidate_entity_indices`](Self::invalidate_entity_indices) is
+    /// called, so repeated lookups cost a hash probe instead of a file read.
+    ///
+    /// # Errors
+    ///
+    /// - `entity_type` is not a block or set type
+    /// - No entity of this type has the given ID
+    pub(crate) fn entity_index<B: Backend + ?Sized>(
+        &self,
+        nc_file: &B,
+        entity_type: EntityType,
+        id: EntityId,
+    ) -> Result<usize> {
+        let indices = self.entity_indices(nc_file, entity_type)?;
+        indices
+            .get(&id)
+            .copied()
+            .ok_or_else(|| ExodusError::EntityNotFound {
+                entity_type: entity_type.to_string(),
+                id,
+            })
+    }
+
+    /// Cached ID to index map of a block or set type
+    fn entity_indices<B: Backend + ?Sized>(
+        &self,
+        nc_file: &B,
+        entity_type: EntityType,
+    ) -> Result<Arc<HashMap<EntityId, usize>>> {
+        let is_set = match entity_type {
+            EntityType::ElemBlock | EntityType::EdgeBlock | EntityType::FaceBlock => false,
+            EntityType::NodeSet
+            | EntityType::EdgeSet
+            | EntityType::FaceSet
+            | EntityType::ElemSet
+            | EntityType::SideSet => true,
+            _ => {
+                return Err(ExodusError::InvalidEntityType(format!(
+                    "Expected a block or set type, got {}",
+                    entity_type
+                )))
+            }
+        };
+
+        let mut cache = self
+            .entity_indices
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(indices) = cache.get(&entity_type) {
+            return Ok(Arc::clone(indices));
+        }
+
+        let id_var = naming::prop_id_var(entity_type);
+        let ids = if nc_file.has_variable(id_var) {
+            nc_file.read_all_i64(id_var)?
+        } else {
+            Vec::new()
+        };
+        let mut indices = HashMap::with_capacity(ids.len());
+        for (index, id) in ids.into_iter().enumerate() {
+            // Unused set slots hold zero or the NetCDF fill value
+            if is_set && id <= 0 {
+                continue;
+            }
+            indices.entry(id).or_insert(index);
+        }
+
+        let indices = Arc::new(indices);
+        cache.insert(entity_type, Arc::clone(&indices));
+        Ok(indices)
+    }
+
+    /// Drop the cached ID to index map of an entity type
+    ///
+    /// Must be called whenever the type's ID variable is written.
+    #[cfg(feature = "netcdf4")]
+    pub(crate) fn invalidate_entity_indices(&self, entity_type: EntityType) {
+        self.entity_indices
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&entity_type);
+    }
+
+    /// NetCDF type used for floating-point bulk data
+    ///
+    /// Coordinates, block attributes, distribution factors, times and results
+    /// are stored as `NC_FLOAT` for [`FloatSize::Float32`] files and as
+    /// `NC_DOUBLE` otherwise.
+    #[cfg(feature = "netcdf4")]
+    pub(crate) fn float_type(&self) -> netcdf::types::NcVariableType {
+        use netcdf::types::{FloatType, NcVariableType};
+        match self.float_size {
+            FloatSize::Float32 => NcVariableType::Float(FloatType::F32),
+            FloatSize::Float64 => NcVariableType::Float(FloatType::F64),
+        }
+    }
+
+    /// NetCDF type used for integer bulk data
+    ///
+    /// Connectivity, ID maps, set entry lists and entity IDs are stored as
+    /// `NC_INT` for [`Int64Mode::Int32`] files and as `NC_INT64` otherwise.
+    #[cfg(feature = "netcdf4")]
+    pub(crate) fn int_type(&self) -> netcdf::types::NcVariableType {
+        use netcdf::types::{IntType, NcVariableType};
+        match self.int64_mode {
+            Int64Mode::Int32 => NcVariableType::Int(IntType::I32),
+            Int64Mode::Int64 => NcVariableType::Int(IntType::I64),
+        }
+    }
+
+    /// Check that integer values fit the file's integer storage
+    ///
+    /// Always succeeds for [`Int64Mode::Int64`] files. `context` describes the
+    /// data being written and is included in the error.
+    #[cfg(feature = "netcdf4")]
+    pub(crate) fn check_int_range(&self, context: &str, values: &[i64]) -> Result<()> {
+        if self.int64_mode == Int64Mode::Int64 {
+            return Ok(());
+        }
+        match values.iter().find(|&&v| i32::try_from(v).is_err()) {
+            Some(&value) => Err(ExodusError::IntegerOverflow {
+                context: context.to_string(),
+                value,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether define/data mode transitions must be made explicitly
+    ///
+    /// Only NetCDF-4 files allow definitions in data mode and writes in define
+    /// mode; NetCDF-3 and NetCDF-4 classic-model files require `nc_redef` and
+    /// `nc_enddef` calls.
+    #[cfg(feature = "netcdf4")]
+    pub(crate) fn explicit_define_mode(&self) -> bool {
+        self.format != FileFormat::NetCdf4
+    }
+
+    /// Fail unless the file uses the NetCDF-4 data model
+    ///
+    /// Assemblies, blobs and entity attributes are stored with types and
+    /// in-place definitions that classic-model files cannot represent.
+    #[cfg(feature = "netcdf4")]
+    pub(crate) fn require_netcdf4(&self, feature: &str) -> Result<()> {
+        if self.format == FileFormat::NetCdf4 {
+            Ok(())
+        } else {
+            Err(ExodusError::UnsupportedFeature(format!(
+                "{} require a NetCDF-4 file, but this file is {:?}",
+                feature, self.format
+            )))
+        }
+    }
+
+    /// Apply the configured compression filter to a newly defined variable
+    ///
+    /// Must be called in define mode, before any data is written to the variable.
+    #[cfg(feature = "netcdf4")]
+    pub(crate) fn apply_compression(&self, var: &mut netcdf::VariableMut<'_>) -> Result<()> {
+        match self.compression {
+            None | Some(Compression::None) => {}
+            Some(Compression::Gzip(level)) => {
+                var.set_compression(i32::from(level), self.shuffle)?;
+            }
+            Some(compression) => {
+                let ncid = self.ncid.ok_or(ExodusError::WriteOnReadOnly)?;
+                crate::utils::nc_sys::def_var_filter(ncid, &var.name(), compression, self.shuffle)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Validate requested compression and reduce it to the filter actually applied
+///
+/// `Compression::None` and a gzip or zstandard level of 0 all mean "no filter".
+#[cfg(feature = "netcdf4")]
+fn resolve_compression(
+    compression: Option<Compression>,
+    format: FileFormat,
+) -> Result<Option<Compression>> {
+    let compression = match compression {
+        None
+        | Some(Compression::None)
+        | Some(Compression::Gzip(0))
+        | Some(Compression::Zstd(0)) => return Ok(None),
+        Some(Compression::Gzip(level)) if level <= 9 => Compression::Gzip(level),
+        Some(Compression::Gzip(level)) => {
+            return Err(ExodusError::Other(format!(
+                "Gzip compression level must be between 1 and 9, got {}",
+                level
+            )))
+        }
+        Some(Compression::Zstd(level)) if level <= 22 => Compression::Zstd(level),
+        Some(Compression::Zstd(level)) => {
+            return Err(ExodusError::Other(format!(
+                "Zstandard compression level must be between 1 and 22, got {}",
+                level
+            )))
+        }
+        Some(Compression::Szip) => Compression::Szip,
+    };
+
+    if !format.supports_compression() {
+        return Err(ExodusError::UnsupportedFeature(format!(
+            "compression requires a NetCDF-4 file, but {:?} was requested",
+            format
+        )));
+    }
+    Ok(Some(compression))
+}
+
+/// Translate creation options into NetCDF creation flags
+///
+/// Fails for option combinations the requested format cannot represent.
+#[cfg(feature = "netcdf4")]
+fn netcdf_create_options(options: &CreateOptions) -> Result<netcdf::Options> {
+    if options.int64_mode == Int64Mode::Int64 && !options.format.supports_int64() {
+        return Err(ExodusError::UnsupportedFeature(format!(
+            "{:?} files cannot store 64-bit integers; use Int64Mode::Int32",
+            options.format
+        )));
+    }
+
+    let mut nc_options = match options.format {
+        FileFormat::NetCdf3Classic => netcdf::Options::empty(),
+        FileFormat::NetCdf364BitOffset => netcdf::Options::_64BIT_OFFSET,
+        FileFormat::NetCdfCdf5 => netcdf::Options::_64BIT_DATA,
+        FileFormat::NetCdf4 => netcdf::Options::NETCDF4,
+        FileFormat::NetCdf4Classic => netcdf::Options::NETCDF4 | netcdf::Options::CLASSIC,
+    };
+
+    // Set creation mode
+    match options.mode {
+        CreateMode::Clobber => {
+            // Clobber is the default (no NOCLOBBER flag)
         }
+        CreateMode::NoClobber => {
+            nc_options |= netcdf::Options::NOCLOBBER;
+        }
+    }
+
+    Ok(nc_options)
+}
+
+/// Read-only NetCDF handle held by [`mode::Read`] files
+///
+/// Opened without write access, so read-only filesystems, files with read-only
+/// permissions and immutable snapshots can all be read.
+#[cfg(feature = "netcdf4")]
+#[derive(Debug)]
+pub struct ReadOnlyHandle(netcdf::File);
+
+#[cfg(feature = "netcdf4")]
+impl std::ops::Deref for ReadOnlyHandle {
+    type Target = netcdf::File;
+
+    fn deref(&self) -> &netcdf::File {
+        &self.0
+    }
+}
+
+/// Handle type of [`mode::Write`] and [`mode::Append`] files without the
+/// `netcdf4` feature
+///
+/// Uninhabited: files can only be created or appended to through NetCDF.
+#[cfg(not(feature = "netcdf4"))]
+#[derive(Debug)]
+pub enum NoWriteHandle {}
+
+#[cfg(not(feature = "netcdf4"))]
+impl Backend for NoWriteHandle {
+    fn dimension_len(&self, _name: &str) -> Option<usize> {
+        match *self {}
+    }
+
+    fn variable_names(&self) -> Vec<String> {
+        match *self {}
+    }
+
+    fn variable_shape(&self, _name: &str) -> Option<Vec<usize>> {
+        match *self {}
+    }
+
+    fn global_attribute(&self, _name: &str) -> Option<crate::backend::AttrValue> {
+        match *self {}
+    }
+
+    fn variable_attribute(&self, _var: &str, _name: &str) -> Option<crate::backend::AttrValue> {
+        match *self {}
+    }
+
+    fn read_f64(&self, _var: &str, _start: &[usize], _count: &[usize]) -> Result<Vec<f64>> {
+        match *self {}
+    }
+
+    fn read_i64(&self, _var: &str, _start: &[usize], _count: &[usize]) -> Result<Vec<i64>> {
+        match *self {}
+    }
+
+    fn read_bytes(&self, _var: &str, _start: &[usize], _count: &[usize]) -> Result<Vec<u8>> {
+        match *self {}
     }
 }
 
@@ -62,14 +436,14 @@ impl FileMetadata {
 /// correct usage at compile time.
 #[derive(Debug)]
 pub struct ExodusFile<M: FileMode> {
-    #[cfg(feature = "netcdf4")]
-    pub(crate) nc_file: netcdf::FileMut,
-    #[cfg(not(feature = "netcdf4"))]
-    _phantom_nc: std::marker::PhantomData<()>,
-
+    pub(crate) nc_file: M::Handle,
     pub(crate) path: PathBuf,
     pub(crate) metadata: FileMetadata,
-    _mode: std::marker::PhantomData<M>,
+    /// Memory behind an in-memory file; declared after `nc_file` so the
+    /// handle is closed before a borrowed buffer is freed
+    #[cfg(feature = "netcdf4")]
+    pub(crate) memory: Option<crate::memory::MemoryImage>,
+    pub(crate) _mode: std::marker::PhantomData<M>,
 }
 
 #[cfg(feature = "netcdf4")]
@@ -103,48 +477,61 @@ impl ExodusFile<mode::Write> {
     /// let file = ExodusFile::create("mesh.exo", options)?;
     /// # Ok::<(), exodus_rs::ExodusError>(())
     /// ```
-    pub fn create<P: AsRef<Path>>(path: P, mut options: CreateOptions) -> Result<Self> {
+    pub fn create<P: AsRef<Path>>(path: P, options: CreateOptions) -> Result<Self> {
         let path = path.as_ref();
+        Self::create_with(path.to_path_buf(), options, |nc_options| {
+            Ok(netcdf::create_with(path, nc_options)?)
+        })
+    }
+
+    /// Create a file through `create`, which receives the NetCDF creation mode
+    pub(crate) fn create_with(
+        path: PathBuf,
+        mut options: CreateOptions,
+        create: impl FnOnce(netcdf::Options) -> Result<netcdf::FileMut>,
+    ) -> Result<Self> {
+        // Validate format-dependent options before touching the filesystem
+        let compression = resolve_compression(options.compression, options.format)?;
+        let nc_options = netcdf_create_options(&options)?;
 
         // Get or auto-detect performance configuration
-        let perf_config = if options.performance.is_none() {
+        let mut perf_config = if options.performance.is_none() {
             Some(crate::performance::PerformanceConfig::auto())
         } else {
             options.performance.take()
         };
 
-        // Apply HDF5 performance tuning via environment variables if config is present
-        if let Some(ref config) = perf_config {
-            Self::apply_hdf5_env_vars(config);
-        }
-
-        // Convert options to NetCDF creation flags
-        let mut nc_options = netcdf::Options::NETCDF4;
-
-        // Set creation mode
-        match options.mode {
-            CreateMode::Clobber => {
-                // Clobber is the default (no NOCLOBBER flag)
-            }
-            CreateMode::NoClobber => {
-                nc_options |= netcdf::Options::NOCLOBBER;
+        // NetCDF-3 variables are always contiguous, so disable chunking
+        if !options.format.is_netcdf4() {
+            if let Some(ref mut config) = perf_config {
+                config.chunks.node_chunk_size = 0;
+                config.chunks.element_chunk_size = 0;
+                config.chunks.time_chunk_size = 0;
             }
         }
 
         // Create the NetCDF file
-        let mut nc_file = netcdf::create_with(path, nc_options)?;
+        let mut nc_file = create(nc_options)?;
 
         // Write global attributes to mark this as an Exodus file
         Self::write_global_attributes(&mut nc_file, &options)?;
 
-        // Create metadata and store performance config
+        // Create metadata and store performance and compression config
         let mut metadata = FileMetadata::new();
         metadata.performance = perf_config;
+        metadata.compression = compression;
+        metadata.shuffle = compression.is_some() && options.shuffle;
+        metadata.ncid = Some(crate::utils::nc_sys::ncid(&nc_file));
+        metadata.float_size = options.float_size;
+        metadata.int64_mode = options.int64_mode;
+        metadata.format = options.format;
+        metadata.storage_format.nodal = options.nodal_var_storage;
 
         Ok(Self {
             nc_file,
-            path: path.to_path_buf(),
+            path,
             metadata,
+            memory: None,
             _mode: std::marker::PhantomData,
         })
     }
@@ -173,45 +560,6 @@ impl ExodusFile<mode::Write> {
         Self::create(path, CreateOptions::default())
     }
 
-    /// Apply HDF5 performance tuning via environment variables
-    ///
-    /// Sets HDF5_CHUNK_CACHE_* environment variables that HDF5 will respect.
-    /// Note: These must be set before the HDF5 library is initialized.
-    ///
-    /// For more fine-grained control, users can set these environment variables
-    /// manually before running their program.
-    fn apply_hdf5_env_vars(config: &crate::performance::PerformanceConfig) {
-        use std::env;
-
-        // HDF5 respects these environment variables (if set before library init)
-        // HDF5_CHUNK_CACHE_NBYTES - cache size in bytes
-        // HDF5_CHUNK_CACHE_NSLOTS - number of hash slots
-        // HDF5_CHUNK_CACHE_W0 - preemption policy (0.0-1.0)
-
-        // Only set if not already set by user
-        if env::var("HDF5_CHUNK_CACHE_NBYTES").is_err() {
-            env::set_var(
-                "HDF5_CHUNK_CACHE_NBYTES",
-                config.cache.cache_size.to_string(),
-            );
-        }
-
-        if env::var("HDF5_CHUNK_CACHE_W0").is_err() {
-            env::set_var("HDF5_CHUNK_CACHE_W0", config.cache.preemption.to_string());
-        }
-
-        // Calculate or use provided num_slots
-        if env::var("HDF5_CHUNK_CACHE_NSLOTS").is_err() {
-            let num_slots = if config.cache.num_slots > 0 {
-                config.cache.num_slots
-            } else {
-                // Auto-calculate based on typical chunk size (1MB for coordinates)
-                config.cache.auto_slots(1024 * 1024)
-            };
-            env::set_var("HDF5_CHUNK_CACHE_NSLOTS", num_slots.to_string());
-        }
-    }
-
     /// Write global attributes to the NetCDF file
     fn write_global_attributes(
         nc_file: &mut netcdf::FileMut,
@@ -228,7 +576,7 @@ impl ExodusFile<mode::Write> {
             FloatSize::Float32 => 4_i32,
             FloatSize::Float64 => 8_i32,
         };
-        nc_file.add_attribute("floating_point_word_size", fp_word_size)?;
+        nc_file.add_attribute(ATTR_FLOATING_POINT_WORD_SIZE, fp_word_size)?;
 
         // File size mode (0 = normal, 1 = large model)
         // Always use 1 for large model support with NetCDF-4
@@ -239,7 +587,7 @@ impl ExodusFile<mode::Write> {
             Int64Mode::Int32 => 0_i32,
             Int64Mode::Int64 => 1_i32,
         };
-        nc_file.add_attribute("int64_status", int64_status)?;
+        nc_file.add_attribute(ATTR_INT64_STATUS, int64_status)?;
 
         Ok(())
     }
@@ -273,21 +621,37 @@ impl ExodusFile<mode::Read> {
     /// # Ok::<(), exodus_rs::ExodusError>(())
     /// ```
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        // Open the NetCDF file without write access
         let path = path.as_ref();
+        Self::from_handle(netcdf::open(path)?, path.to_path_buf())
+    }
 
-        // Open the NetCDF file (use append to get FileMut for variable reads)
-        let nc_file = netcdf::append(path)?;
+    /// Wrap a NetCDF file opened for reading
+    pub(crate) fn from_handle(nc_file: netcdf::File, path: PathBuf) -> Result<Self> {
+        let nc_file = ReadOnlyHandle(nc_file);
+
+        // Inspect format and compression filters
+        let storage = crate::utils::nc_sys::inquire_storage(&nc_file)?;
 
         // Detect storage format for this file
-        let storage_format = detect_storage_format(&nc_file);
+        let storage_format = detect_storage_format(&*nc_file);
 
         let mut metadata = FileMetadata::new();
         metadata.storage_format = storage_format;
+        metadata.float_size = detect_float_size(&nc_file);
+        metadata.int64_mode = detect_int64_mode(&*nc_file);
+        metadata.format = storage.format;
+        metadata.time_step_limit = stored_time_step_limit(&*nc_file);
+        if let Some((compression, shuffle)) = storage.compression {
+            metadata.compression = Some(compression);
+            metadata.shuffle = shuffle;
+        }
 
         Ok(Self {
             nc_file,
-            path: path.to_path_buf(),
+            path,
             metadata,
+            memory: None,
             _mode: std::marker::PhantomData,
         })
     }
@@ -315,6 +679,10 @@ impl<M: WritableMode> ExodusFile<M> {
     /// - Define mode for: `init()`, `put_block()`, `define_variables()`
     /// - Data mode for: `put_coords()`, `put_connectivity()`, `put_var()`
     pub fn sync(&mut self) -> Result<()> {
+        // Classic-model headers are only written when define mode ends
+        if self.metadata.explicit_define_mode() && self.is_define_mode() {
+            return Ok(());
+        }
         self.nc_file.sync()?;
         Ok(())
     }
@@ -334,8 +702,29 @@ impl<M: WritableMode> ExodusFile<M> {
     /// You only need to call this method if you want explicit control over mode
     /// transitions for performance reasons or code clarity.
     pub fn end_define(&mut self) -> Result<()> {
-        self.nc_file.sync()?;
+        if !self.metadata.explicit_define_mode() {
+            self.nc_file.sync()?;
+        } else if self.is_define_mode() {
+            self.nc_file.enddef()?;
+        }
         self.metadata.define_mode = DefineMode::Data;
+        self.apply_chunk_cache()
+    }
+
+    /// Size the chunk cache of variables defined since the last call
+    ///
+    /// The cache settings of the performance configuration apply to each
+    /// variable while this handle is open. NetCDF-3 files have no chunk cache.
+    fn apply_chunk_cache(&mut self) -> Result<()> {
+        if let Some(config) = &self.metadata.performance {
+            if self.metadata.format.is_netcdf4() {
+                self.metadata.chunk_cache_vars = crate::utils::nc_sys::set_chunk_caches(
+                    crate::utils::nc_sys::ncid(&self.nc_file),
+                    self.metadata.chunk_cache_vars,
+                    &config.cache,
+                )?;
+            }
+        }
         Ok(())
     }
 
@@ -352,7 +741,11 @@ impl<M: WritableMode> ExodusFile<M> {
     /// **This method is now optional** thanks to automatic mode management.
     /// exodus-rs automatically switches back to define mode when needed.
     pub fn reenter_define(&mut self) -> Result<()> {
-        self.nc_file.sync()?;
+        if !self.metadata.explicit_define_mode() {
+            self.nc_file.sync()?;
+        } else if !self.is_define_mode() {
+            self.nc_file.redef()?;
+        }
         self.metadata.define_mode = DefineMode::Define;
         Ok(())
     }
@@ -389,6 +782,18 @@ impl<M: WritableMode> ExodusFile<M> {
         }
         Ok(())
     }
+
+    /// Leave define mode after a group of definitions on classic-model files
+    ///
+    /// NetCDF-4 files switch modes implicitly and stay in define mode until data
+    /// is written. Classic-model files cannot read or write variables in define
+    /// mode, so writers return them to data mode as soon as definitions end.
+    pub(crate) fn finish_definitions(&mut self) -> Result<()> {
+        if self.metadata.explicit_define_mode() {
+            self.ensure_data_mode()?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(feature = "netcdf4")]
@@ -421,17 +826,32 @@ impl ExodusFile<mode::Append> {
     /// # Ok::<(), exodus_rs::ExodusError>(())
     /// ```
     pub fn append<P: AsRef<Path>>(path: P) -> Result<Self> {
+        // Open the NetCDF file in append mode (read-write)
         let path = path.as_ref();
+        Self::from_handle(netcdf::append(path)?, path.to_path_buf())
+    }
 
-        // Open the NetCDF file in append mode (read-write)
-        let nc_file = netcdf::append(path)?;
+    /// Wrap a NetCDF file opened for appending
+    pub(crate) fn from_handle(nc_file: netcdf::FileMut, path: PathBuf) -> Result<Self> {
+        // Inspect format and compression filters so new variables match the existing ones
+        let storage = crate::utils::nc_sys::inquire_storage(&nc_file)?;
 
         // Detect storage format for this file
-        let storage_format = detect_storage_format(&nc_file);
+        let storage_format = detect_storage_format(&*nc_file);
 
         // Load metadata from the existing file
         let mut metadata = FileMetadata::new();
         metadata.storage_format = storage_format;
+        // Existing files open in data mode
+        metadata.define_mode = DefineMode::Data;
+        metadata.float_size = detect_float_size(&nc_file);
+        metadata.int64_mode = detect_int64_mode(&*nc_file);
+        metadata.format = storage.format;
+        metadata.time_step_limit = stored_time_step_limit(&*nc_file);
+        if let Some((compression, shuffle)) = storage.compression {
+            metadata.compression = Some(compression);
+            metadata.shuffle = shuffle;
+        }
 
         // Check if file is initialized by checking for num_dim dimension
         if let Some(dim) = nc_file.dimension("num_dim") {
@@ -448,8 +868,9 @@ impl ExodusFile<mode::Append> {
 
         Ok(Self {
             nc_file,
-            path: path.to_path_buf(),
+            path,
             metadata,
+            memory: None,
             _mode: std::marker::PhantomData,
         })
     }
@@ -689,17 +1110,7 @@ impl ExodusFile<mode::Append> {
         name: &str,
     ) -> Result<()> {
         // Find the index of the sideset
-        // SAFETY: The Append mode guarantees both read and write access to the file.
-        // This cast temporarily reinterprets self as ExodusFile<mode::Read> to query
-        // sideset IDs for finding the index of the target sideset.
-        let reader = unsafe { &*(self as *const _ as *const ExodusFile<mode::Read>) };
-        let ss_ids = reader.set_ids(crate::EntityType::SideSet)?;
-        let ss_index = ss_ids
-            .iter()
-            .position(|&id| id == sideset_id)
-            .ok_or_else(|| {
-                crate::ExodusError::Other(format!("Sideset with ID {} not found", sideset_id))
-            })?;
+        let ss_index = self.entity_index(EntityType::SideSet, sideset_id)?;
 
         // Set the name
         // SAFETY: The Append mode guarantees both read and write access to the file.
@@ -713,7 +1124,6 @@ impl ExodusFile<mode::Append> {
     }
 }
 
-#[cfg(feature = "netcdf4")]
 impl<M: FileMode> ExodusFile<M> {
     /// Get the file path
     ///
@@ -743,10 +1153,7 @@ impl<M: FileMode> ExodusFile<M> {
             return len;
         }
         // Fall back to querying the NetCDF file
-        self.nc_file
-            .dimension(dim_name)
-            .map(|d| d.len())
-            .unwrap_or(0)
+        self.nc_file.dimension_len(dim_name).unwrap_or(0)
     }
 
     /// Get dimension length, returning an error if the dimension is required.
@@ -773,28 +1180,31 @@ impl<M: FileMode> ExodusFile<M> {
         }
         // Fall back to querying the NetCDF file
         self.nc_file
-            .dimension(dim_name)
-            .map(|d| d.len())
+            .dimension_len(dim_name)
             .ok_or_else(|| crate::error::ExodusError::VariableNotDefined(dim_name.to_string()))
     }
 
+    /// Index of a block or set within its type, from its ID
+    ///
+    /// Uses the metadata cache; see [`FileMetadata::entity_index`].
+    pub(crate) fn entity_index(&self, entity_type: EntityType, id: EntityId) -> Result<usize> {
+        self.metadata.entity_index(&self.nc_file, entity_type, id)
+    }
+
     /// Get the NetCDF file format
     ///
+    /// The format is determined when the file is created or opened; opening
+    /// fails if the NetCDF library cannot report it.
+    ///
     /// # Returns
     ///
     /// The format of the underlying NetCDF file
     ///
     /// # Errors
     ///
-    /// Returns an error if the format cannot be determined
+    /// Never fails for an open file; the `Result` is kept for API stability
     pub fn format(&self) -> Result<FileFormat> {
-        // Query the NetCDF file format
-        // Note: The netcdf crate doesn't expose format directly,
-        // so we'll infer it from file attributes
-
-        // For now, if we can read the file, assume it's NetCDF-4
-        // This can be enhanced later with more sophisticated detection
-        Ok(FileFormat::NetCdf4)
+        Ok(self.metadata.format)
     }
 
     /// Get the Exodus file version
@@ -808,24 +1218,17 @@ impl<M: FileMode> ExodusFile<M> {
     /// Returns an error if the version attribute cannot be read
     pub fn version(&self) -> Result<(u32, u32)> {
         // Read the version attribute
-        match self.nc_file.attribute(ATTR_VERSION) {
-            Some(attr) => {
-                use netcdf::AttributeValue;
-                match attr.value()? {
-                    AttributeValue::Float(val) => {
-                        // Version is stored as decimal (e.g., 2.0)
-                        let major = val as u32;
-                        let minor = ((val - major as f32) * 10.0).round() as u32;
-                        Ok((major, minor))
-                    }
-                    AttributeValue::Double(val) => {
-                        // Version is stored as decimal (e.g., 2.0)
-                        let major = val as u32;
-                        let minor = ((val - major as f64) * 10.0).round() as u32;
-                        Ok((major, minor))
-                    }
-                    _ => Ok((2, 0)), // Default version
-                }
+        match self
+            .nc_file
+            .global_attribute(ATTR_VERSION)
+            .and_then(|value| value.as_f64())
+        {
+            Some(val) => {
+                // Version is stored as a 32-bit decimal (e.g., 2.0)
+                let val = val as f32;
+                let major = val as u32;
+                let minor = ((val - major as f32) * 10.0).round() as u32;
+                Ok((major, minor))
             }
             None => Ok((2, 0)), // Default version if attribute not found
         }
@@ -873,25 +1276,117 @@ impl<M: FileMode> ExodusFile<M> {
     pub fn storage_format(&self) -> &FileStorageFormat {
         &self.metadata.storage_format
     }
+
+    /// Get the compression filter used for bulk-data variables.
+    ///
+    /// For files being written this is the compression requested in
+    /// [`CreateOptions`]. For files opened for reading or appending it is the
+    /// filter detected on the file's variables, or `None` if the data is
+    /// stored uncompressed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use exodus_rs::{ExodusFile, mode};
+    ///
+    /// let file = ExodusFile::<mode::Read>::open("results.exo")?;
+    /// if let Some(compression) = file.compression() {
+    ///     println!("Compressed with {:?}", compression);
+    /// }
+    /// # Ok::<(), exodus_rs::ExodusError>(())
+    /// ```
+    pub fn compression(&self) -> Option<Compression> {
+        self.metadata.compression
+    }
+
+    /// Get the floating-point storage precision of this file.
+    ///
+    /// Files created with [`FloatSize::Float32`] store coordinates, attributes,
+    /// distribution factors and results as 32-bit floats. Values are converted
+    /// to and from the caller's type (`f32` or `f64`) transparently.
+    pub fn float_size(&self) -> FloatSize {
+        self.metadata.float_size
+    }
+
+    /// Get the integer storage width of this file.
+    ///
+    /// [`Int64Mode::Int32`] files store connectivity, ID maps, set entries and
+    /// entity IDs as 32-bit integers; writes that do not fit fail with
+    /// [`ExodusError::IntegerOverflow`].
+    pub fn int64_mode(&self) -> Int64Mode {
+        self.metadata.int64_mode
+    }
+}
+
+/// Detect the floating-point storage precision of an existing file.
+///
+/// The type of the coordinate variables is authoritative; files without
+/// coordinates fall back to the `floating_point_word_size` global attribute.
+#[cfg(feature = "netcdf4")]
+fn detect_float_size(nc_file: &netcdf::File) -> FloatSize {
+    use netcdf::types::{FloatType, NcVariableType};
+
+    if let Some(var) = nc_file.variable(VAR_COORD_X) {
+        match var.vartype() {
+            NcVariableType::Float(FloatType::F32) => return FloatSize::Float32,
+            NcVariableType::Float(FloatType::F64) => return FloatSize::Float64,
+            _ => {}
+        }
+    }
+
+    float_size_from_attribute(nc_file)
+}
+
+/// Floating-point storage precision from the `floating_point_word_size`
+/// global attribute, for files without coordinates
+fn float_size_from_attribute<B: Backend + ?Sized>(nc_file: &B) -> FloatSize {
+    let word_size = nc_file
+        .global_attribute(ATTR_FLOATING_POINT_WORD_SIZE)
+        .and_then(|value| value.as_i64());
+    match word_size {
+        Some(4) => FloatSize::Float32,
+        _ => FloatSize::Float64,
+    }
+}
+
+/// Logical truncation stored in the `time_step_limit` global attribute
+fn stored_time_step_limit<B: Backend + ?Sized>(nc_file: &B) -> Option<usize> {
+    nc_file
+        .global_attribute(ATTR_TIME_STEP_LIMIT)
+        .and_then(|value| value.as_i64())
+        .and_then(|limit| usize::try_from(limit).ok())
+}
+
+/// Detect the integer storage width of an existing file.
+///
+/// Files without an `int64_status` attribute predate 64-bit integer support
+/// and are treated as 32-bit.
+pub(crate) fn detect_int64_mode<B: Backend + ?Sized>(nc_file: &B) -> Int64Mode {
+    let status = nc_file
+        .global_attribute(ATTR_INT64_STATUS)
+        .and_then(|value| value.as_i64());
+    match status {
+        Some(status) if status != 0 => Int64Mode::Int64,
+        _ => Int64Mode::Int32,
+    }
 }
 
 /// Detect the storage format for a single variable type.
 ///
 /// Checks if the combined variable exists first (e.g., `vals_nod_var`),
 /// then checks for the separate format (e.g., `vals_nod_var1`).
-#[cfg(feature = "netcdf4")]
-fn detect_var_storage(
-    nc_file: &netcdf::FileMut,
+fn detect_var_storage<B: Backend + ?Sized>(
+    nc_file: &B,
     combined_name: &str,
     separate_prefix: &str,
 ) -> VarStorageMode {
     // Check for combined 3D format first
-    if nc_file.variable(combined_name).is_some() {
+    if nc_file.has_variable(combined_name) {
         return VarStorageMode::Combined;
     }
     // Check for separate format by looking for the first variable
     let separate_name = format!("{}1", separate_prefix);
-    if nc_file.variable(&separate_name).is_some() {
+    if nc_file.has_variable(&separate_name) {
         return VarStorageMode::Separate;
     }
     VarStorageMode::None
@@ -901,14 +1396,13 @@ fn detect_var_storage(
 ///
 /// Element variables have a more complex naming pattern: `vals_elem_var{var}eb{block}`
 /// for separate format, or `vals_elem_var` for combined format.
-#[cfg(feature = "netcdf4")]
-fn detect_elem_var_storage(nc_file: &netcdf::FileMut) -> VarStorageMode {
+fn detect_elem_var_storage<B: Backend + ?Sized>(nc_file: &B) -> VarStorageMode {
     // Check for combined format
-    if nc_file.variable("vals_elem_var").is_some() {
+    if nc_file.has_variable("vals_elem_var") {
         return VarStorageMode::Combined;
     }
     // Check for separate format (vals_elem_var1eb1)
-    if nc_file.variable("vals_elem_var1eb1").is_some() {
+    if nc_file.has_variable("vals_elem_var1eb1") {
         return VarStorageMode::Separate;
     }
     VarStorageMode::None
@@ -917,26 +1411,24 @@ fn detect_elem_var_storage(nc_file: &netcdf::FileMut) -> VarStorageMode {
 /// Detect the storage format for set variables.
 ///
 /// Set variables have patterns like `vals_nset_var{var}ns{set}` for separate format.
-#[cfg(feature = "netcdf4")]
-fn detect_set_var_storage(
-    nc_file: &netcdf::FileMut,
+fn detect_set_var_storage<B: Backend + ?Sized>(
+    nc_file: &B,
     combined_name: &str,
     separate_pattern: &str,
 ) -> VarStorageMode {
     // Check for combined format
-    if nc_file.variable(combined_name).is_some() {
+    if nc_file.has_variable(combined_name) {
         return VarStorageMode::Combined;
     }
     // Check for separate format
-    if nc_file.variable(separate_pattern).is_some() {
+    if nc_file.has_variable(separate_pattern) {
         return VarStorageMode::Separate;
     }
     VarStorageMode::None
 }
 
 /// Detect the storage format for all entity types in a file.
-#[cfg(feature = "netcdf4")]
-fn detect_storage_format(nc_file: &netcdf::FileMut) -> FileStorageFormat {
+pub(crate) fn detect_storage_format<B: Backend + ?Sized>(nc_file: &B) -> FileStorageFormat {
     FileStorageFormat {
         // Nodal: vals_nod_var (combined) vs vals_nod_var1 (separate)
         nodal: detect_var_storage(nc_file, "vals_nod_var", "vals_nod_var"),
@@ -957,7 +1449,7 @@ fn detect_storage_format(nc_file: &netcdf::FileMut) -> FileStorageFormat {
         // Element set: vals_elset_var (combined) vs vals_elset_var1els1 (separate)
         elem_set: detect_set_var_storage(nc_file, "vals_elset_var", "vals_elset_var1els1"),
         // Global: always uses vals_glo_var (combined format with shape time_step x num_glo_var)
-        global: if nc_file.variable("vals_glo_var").is_some() {
+        global: if nc_file.has_variable("vals_glo_var") {
             VarStorageMode::Combined
         } else {
             VarStorageMode::None
@@ -972,37 +1464,36 @@ impl<M: FileMode> Drop for ExodusFile<M> {
         // This is especially important for NetCDF define mode changes
         // For read-only files, this will be a no-op
         // Ignore errors in drop - we're already cleaning up
-        let _ = self.nc_file.sync();
+        <M as crate::private::Sealed>::flush(&self.nc_file);
 
         // NetCDF file is automatically closed by its Drop implementation
     }
 }
 
-// Stub implementations when netcdf4 feature is not enabled
+// Without the netcdf4 feature files can only be read (see `crate::backend`)
+#[cfg(not(feature = "netcdf4"))]
+fn netcdf_required(operation: &str) -> ExodusError {
+    ExodusError::UnsupportedFeature(format!(
+        "{} Exodus files requires the 'netcdf4' feature",
+        operation
+    ))
+}
+
 #[cfg(not(feature = "netcdf4"))]
 impl ExodusFile<mode::Write> {
     /// Create a new Exodus file (requires netcdf4 feature)
     pub fn create<P: AsRef<Path>>(_path: P, _options: CreateOptions) -> Result<Self> {
-        Err(ExodusError::Other(
-            "NetCDF support not enabled. Enable the 'netcdf4' feature.".to_string(),
-        ))
+        Err(netcdf_required("Creating"))
     }
 
     /// Create a new Exodus file with default options (requires netcdf4 feature)
     pub fn create_default<P: AsRef<Path>>(_path: P) -> Result<Self> {
-        Err(ExodusError::Other(
-            "NetCDF support not enabled. Enable the 'netcdf4' feature.".to_string(),
-        ))
+        Err(netcdf_required("Creating"))
     }
-}
 
-#[cfg(not(feature = "netcdf4"))]
-impl ExodusFile<mode::Read> {
-    /// Open an existing Exodus file (requires netcdf4 feature)
-    pub fn open<P: AsRef<Path>>(_path: P) -> Result<Self> {
-        Err(ExodusError::Other(
-            "NetCDF support not enabled. Enable the 'netcdf4' feature.".to_string(),
-        ))
+    /// Create a new in-memory Exodus file (requires netcdf4 feature)
+    pub fn create_in_memory(_options: CreateOptions) -> Result<Self> {
+        Err(netcdf_required("Creating"))
     }
 }
 
@@ -1010,9 +1501,73 @@ impl ExodusFile<mode::Read> {
 impl ExodusFile<mode::Append> {
     /// Open an existing Exodus file for appending (requires netcdf4 feature)
     pub fn append<P: AsRef<Path>>(_path: P) -> Result<Self> {
-        Err(ExodusError::Other(
-            "NetCDF support not enabled. Enable the 'netcdf4' feature.".to_string(),
-        ))
+        Err(netcdf_required("Appending to"))
+    }
+
+    /// Open an in-memory Exodus file for appending (requires netcdf4 feature)
+    pub fn open_from_bytes(_bytes: Vec<u8>) -> Result<Self> {
+        Err(netcdf_required("Appending to"))
+    }
+}
+
+#[cfg(not(feature = "netcdf4"))]
+impl ExodusFile<mode::Read> {
+    /// Open an existing NetCDF-3 Exodus file for reading
+    ///
+    /// Without the `netcdf4` feature only the classic, 64-bit offset and CDF5
+    /// formats can be read; NetCDF-4 files fail with
+    /// [`ExodusError::UnsupportedFeature`].
+    ///
+    /// # Errors
+    ///
+    /// - File does not exist or cannot be read
+    /// - File is not a NetCDF-3 file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        Self::from_classic(ClassicFile::open(path)?, path.to_path_buf())
+    }
+
+    /// Open a NetCDF-3 Exodus file held in memory
+    ///
+    /// The returned file has an empty [`path`](ExodusFile::path).
+    ///
+    /// # Errors
+    ///
+    /// - The buffer does not hold a NetCDF-3 file
+    pub fn open_from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        Self::from_classic(ClassicFile::from_bytes(bytes)?, PathBuf::new())
+    }
+
+    /// Consume the file and return its complete contents
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file on disk cannot be read back
+    pub fn into_bytes(self) -> Result<Vec<u8>> {
+        self.nc_file.into_bytes()
+    }
+
+    /// Whether the file was opened from a byte buffer
+    pub fn is_in_memory(&self) -> bool {
+        self.path.as_os_str().is_empty()
+    }
+
+    fn from_classic(nc_file: ClassicFile, path: PathBuf) -> Result<Self> {
+        let mut metadata = FileMetadata::new();
+        metadata.format = nc_file.format();
+        metadata.storage_format = detect_storage_format(&nc_file);
+        metadata.int64_mode = detect_int64_mode(&nc_file);
+        metadata.float_size = nc_file
+            .float_size(VAR_COORD_X)
+            .unwrap_or_else(|| float_size_from_attribute(&nc_file));
+        metadata.time_step_limit = stored_time_step_limit(&nc_file);
+
+        Ok(Self {
+            nc_file,
+            path,
+            metadata,
+            _mode: std::marker::PhantomData,
+        })
     }
 }
 
@@ -1163,6 +1718,118 @@ mod tests {
         assert!(file.is_define_mode());
     }
 
+    #[test]
+    fn test_compression_roundtrip() {
+        let tmp = NamedTempFile::new().unwrap();
+        {
+            let mut file = ExodusFile::create(
+                tmp.path(),
+                CreateOptions {
+                    mode: CreateMode::Clobber,
+                    compression: Some(Compression::Gzip(4)),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            assert_eq!(file.compression(), Some(Compression::Gzip(4)));
+
+            let params = crate::InitParams {
+                title: "Compressed".into(),
+                num_dim: 3,
+                num_nodes: 4,
+                ..Default::default()
+            };
+            file.init(&params).unwrap();
+            let x = vec![0.0, 1.0, 0.0, 0.0];
+            let y = vec![0.0, 0.0, 1.0, 0.0];
+            let z = vec![0.0, 0.0, 0.0, 1.0];
+            file.put_coords(&x, Some(&y), Some(&z)).unwrap();
+        }
+
+        let file = ExodusFile::<mode::Read>::open(tmp.path()).unwrap();
+        assert_eq!(file.compression(), Some(Compression::Gzip(4)));
+        let coords = file.coords::<f64>().unwrap();
+        assert_eq!(coords.y, vec![0.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_no_compression_reported() {
+        let tmp = NamedTempFile::new().unwrap();
+        {
+            let _file = create_test_file(tmp.path()).unwrap();
+        }
+
+        let file = ExodusFile::<mode::Read>::open(tmp.path()).unwrap();
+        assert_eq!(file.compression(), None);
+    }
+
+    #[test]
+    fn test_read_handle_rejects_writes() {
+        let tmp = NamedTempFile::new().unwrap();
+        {
+            let mut file = create_test_file(tmp.path()).unwrap();
+            let params = crate::InitParams {
+                title: "Read-only".into(),
+                num_dim: 2,
+                num_nodes: 3,
+                ..Default::default()
+            };
+            file.init(&params).unwrap();
+            file.put_coords(&[0.0, 1.0, 0.0], Some(&[0.0, 0.0, 1.0]), None)
+                .unwrap();
+        }
+
+        let file = ExodusFile::<mode::Read>::open(tmp.path()).unwrap();
+        let handle: &ReadOnlyHandle = &file.nc_file;
+        assert_eq!(file.coords::<f64>().unwrap().x, vec![0.0, 1.0, 0.0]);
+
+        // The handle is opened with NC_NOWRITE, so the library refuses writes
+        // even when file permissions would allow them (e.g. when run as root)
+        let ncid = crate::utils::nc_sys::ncid(handle);
+        let status = {
+            let _guard = netcdf_sys::libnetcdf_lock.lock();
+            // SAFETY: ncid belongs to the open read-only handle above.
+            unsafe { netcdf_sys::nc_redef(ncid) }
+        };
+        assert_eq!(status, netcdf_sys::NC_EPERM);
+    }
+
+    #[test]
+    fn test_unsupported_compression() {
+        let tmp = NamedTempFile::new().unwrap();
+        let result = ExodusFile::create(
+            tmp.path(),
+            CreateOptions {
+                mode: CreateMode::Clobber,
+                format: FileFormat::NetCdf3Classic,
+                int64_mode: Int64Mode::Int32,
+                compression: Some(Compression::Szip),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(result, Err(ExodusError::UnsupportedFeature(_))));
+
+        let result = ExodusFile::create(
+            tmp.path(),
+            CreateOptions {
+                mode: CreateMode::Clobber,
+                compression: Some(Compression::Gzip(12)),
+                ..Default::default()
+            },
+        );
+        assert!(result.is_err());
+
+        let result = ExodusFile::create(
+            tmp.path(),
+            CreateOptions {
+                mode: CreateMode::Clobber,
+                compression: Some(Compression::Zstd(23)),
+                ..Default::default()
+            },
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_sync() {
         let tmp = NamedTempFile::new().unwrap();
@@ -1171,4 +1838,56 @@ mod tests {
         // Sync should succeed
         file.sync().unwrap();
     }
+
+    #[test]
+    fn test_entity_index_cache() {
+        use crate::types::{Block, InitParams, Set};
+
+        let tmp = NamedTempFile::new().unwrap();
+        let mut file = create_test_file(tmp.path()).unwrap();
+        file.init(&InitParams {
+            num_dim: 2,
+            num_nodes: 4,
+            num_elems: 2,
+            num_elem_blocks: 2,
+            num_node_sets: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        for id in [10, 20] {
+            file.put_block(&Block {
+                id,
+                entity_type: EntityType::ElemBlock,
+                topology: "QUAD4".into(),
+                num_entries: 1,
+                num_nodes_per_entry: 4,
+                num_edges_per_entry: 0,
+                num_faces_per_entry: 0,
+                num_attributes: 0,
+            })
+            .unwrap();
+        }
+        assert_eq!(file.entity_index(EntityType::ElemBlock, 20).unwrap(), 1);
+        assert!(matches!(
+            file.entity_index(EntityType::ElemBlock, 30),
+            Err(ExodusError::EntityNotFound { id: 30, .. })
+        ));
+        assert!(matches!(
+            file.entity_index(EntityType::Nodal, 1),
+            Err(ExodusError::InvalidEntityType(_))
+        ));
+
+        // A cached map is replaced once another set is defined
+        let set = |id| Set {
+            id,
+            entity_type: EntityType::NodeSet,
+            num_entries: 1,
+            num_dist_factors: 0,
+        };
+        file.put_set(&set(7)).unwrap();
+        assert_eq!(file.entity_index(EntityType::NodeSet, 7).unwrap(), 0);
+        assert!(file.entity_index(EntityType::NodeSet, 3).is_err());
+        file.put_set(&set(3)).unwrap();
+        assert_eq!(file.entity_index(EntityType::NodeSet, 3).unwrap(), 1);
+    }
 }
@@ -641,7 +641,7 @@ impl ExodusFile<mode::Read> {
 
 /// Vector subtraction: a - b
 #[inline]
-fn sub(a: Vec3, b: Vec3) -> Vec3 {
+pub(crate) fn sub(a: Vec3, b: Vec3) -> Vec3 {
     [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
 }
 
@@ -653,7 +653,7 @@ fn dot(a: Vec3, b: Vec3) -> f64 {
 
 /// Cross product: a × b
 #[inline]
-fn cross(a: Vec3, b: Vec3) -> Vec3 {
+pub(crate) fn cross(a: Vec3, b: Vec3) -> Vec3 {
     [
         a[1] * b[2] - a[2] * b[1],
         a[2] * b[0] - a[0] * b[2],
@@ -663,13 +663,13 @@ fn cross(a: Vec3, b: Vec3) -> Vec3 {
 
 /// Vector magnitude (length)
 #[inline]
-fn magnitude(v: Vec3) -> f64 {
+pub(crate) fn magnitude(v: Vec3) -> f64 {
     (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
 }
 
 /// Normalize a vector to unit length
 #[inline]
-fn normalize(v: Vec3) -> Vec3 {
+pub(crate) fn normalize(v: Vec3) -> Vec3 {
     let mag = magnitude(v);
     if mag < 1e-12 {
         // Degenerate case: zero or near-zero vector
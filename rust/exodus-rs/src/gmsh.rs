@@ -0,0 +1,1597 @@
+//! Converting between Gmsh `.msh` meshes and Exodus.
+//!
+//! [`GmshMesh`] reads and writes ASCII Gmsh files in formats 2.2 and 4.1 and
+//! converts to and from Exodus through physical groups. [`import_gmsh`] and
+//! [`export_gmsh`] convert whole files.
+//!
+//! When importing a mesh whose elements have at most `D` dimensions (3 for a
+//! volume mesh):
+//!
+//! - Physical groups of dimension `D` (volume groups) become element blocks,
+//!   one per element type in the group
+//! - Groups of dimension `D - 1` (surface groups) become side sets. Each
+//!   surface element is matched to the side of an element with the same
+//!   corner nodes, numbered as in [`Topology::faces`].
+//! - Groups of lower dimension (point and curve groups) become node sets of
+//!   the nodes of their elements
+//!
+//! Group tags become block and set IDs, and group names their names. A set
+//! whose tag is already used by a set of another dimension gets the next
+//! free ID. When no element of dimension `D` is in a physical group, blocks
+//! are made from the elementary entities instead. Nodes are numbered in the
+//! order of their Gmsh tags, and element nodes are reordered from the Gmsh
+//! convention to the Exodus convention of [`Topology`].
+//!
+//! Exporting writes each element block as a physical group of the
+//! dimension of its elements, each side set as a group of face elements,
+//! and each node set as a group of point elements. Face elements are linear,
+//! with the corner nodes of the side.
+//!
+//! Binary files, format 4.0, and element types without an Exodus topology
+//! are not supported, and results are not converted.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use exodus_rs::gmsh::{export_gmsh, import_gmsh, GmshVersion};
+//!
+//! let summary = import_gmsh("part.msh", "part.e")?;
+//! println!("{} blocks, {} side sets", summary.num_blocks, summary.num_side_sets);
+//!
+//! export_gmsh("part.e", "part_v2.msh", GmshVersion::V2)?;
+//! # Ok::<(), exodus_rs::ExodusError>(())
+//! ```
+
+use crate::builder::{BlockBuilder, MeshBuilder, NodeSetBuilder, SideSetBuilder};
+use crate::error::{ExodusError, Result};
+use crate::types::{EntityType, Topology};
+use crate::utils::naming::truncate_name;
+use crate::{mode, ExodusFile};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::{FromStr, SplitWhitespace};
+
+/// Nodes numbered alike in Gmsh and Exodus
+static IDENTITY: [usize; 9] = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+
+/// Gmsh numbers the last two edges of a tetrahedron (2,3) then (1,3)
+static TET10_ORDER: [usize; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 9, 8];
+
+/// Gmsh numbers hex edges by their first corner, Exodus bottom, vertical,
+/// then top
+static HEX20_ORDER: [usize; 20] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 11, 13, 9, 10, 12, 14, 15, 16, 18, 19, 17,
+];
+
+/// [`HEX20_ORDER`], then the center and face nodes: Exodus has the center
+/// first, then faces -z, +z, -x, +x, -y, +y; Gmsh the faces -z, -y, -x, +x,
+/// +y, +z, then the center
+static HEX27_ORDER: [usize; 27] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 11, 13, 9, 10, 12, 14, 15, 16, 18, 19, 17, 26, 20, 25, 22, 23, 21,
+    24,
+];
+
+/// Wedge edges, numbered by first corner in Gmsh
+static WEDGE15_ORDER: [usize; 15] = [0, 1, 2, 3, 4, 5, 6, 9, 7, 8, 10, 11, 12, 14, 13];
+
+/// [`WEDGE15_ORDER`], then the quadrilateral face nodes
+static WEDGE18_ORDER: [usize; 18] = [0, 1, 2, 3, 4, 5, 6, 9, 7, 8, 10, 11, 12, 14, 13, 15, 17, 16];
+
+/// Pyramid edges, numbered by first corner in Gmsh
+static PYRAMID13_ORDER: [usize; 13] = [0, 1, 2, 3, 4, 5, 8, 10, 6, 7, 9, 11, 12];
+
+/// [`PYRAMID13_ORDER`], then the base center
+static PYRAMID14_ORDER: [usize; 14] = [0, 1, 2, 3, 4, 5, 8, 10, 6, 7, 9, 11, 12, 13];
+
+/// Highest Gmsh element type with an Exodus topology
+const MAX_ELEMENT_TYPE: u32 = 19;
+
+/// The Exodus topology of a Gmsh element type, and the Gmsh node of each
+/// Exodus node: Exodus node `i` is Gmsh node `order[i]`
+fn element_type(gmsh_type: u32) -> Option<(Topology, &'static [usize])> {
+    let (topology, order): (Topology, &'static [usize]) = match gmsh_type {
+        1 => (Topology::Bar2, &IDENTITY[..2]),
+        2 => (Topology::Tri3, &IDENTITY[..3]),
+        3 => (Topology::Quad4, &IDENTITY[..4]),
+        4 => (Topology::Tet4, &IDENTITY[..4]),
+        5 => (Topology::Hex8, &IDENTITY[..8]),
+        6 => (Topology::Wedge6, &IDENTITY[..6]),
+        7 => (Topology::Pyramid5, &IDENTITY[..5]),
+        8 => (Topology::Bar3, &IDENTITY[..3]),
+        9 => (Topology::Tri6, &IDENTITY[..6]),
+        10 => (Topology::Quad9, &IDENTITY[..9]),
+        11 => (Topology::Tet10, &TET10_ORDER),
+        12 => (Topology::Hex27, &HEX27_ORDER),
+        13 => (Topology::Wedge18, &WEDGE18_ORDER),
+        14 => (Topology::Pyramid14, &PYRAMID14_ORDER),
+        15 => (Topology::Sphere, &IDENTITY[..1]),
+        16 => (Topology::Quad8, &IDENTITY[..8]),
+        17 => (Topology::Hex20, &HEX20_ORDER),
+        18 => (Topology::Wedge15, &WEDGE15_ORDER),
+        19 => (Topology::Pyramid13, &PYRAMID13_ORDER),
+        _ => return None,
+    };
+    Some((topology, order))
+}
+
+/// The Gmsh element type of an Exodus topology
+fn gmsh_type(topology: &Topology) -> Option<u32> {
+    (1..=MAX_ELEMENT_TYPE)
+        .find(|&t| element_type(t).is_some_and(|(candidate, _)| candidate == *topology))
+}
+
+/// Dimension of the elements of a topology
+fn dimension(topology: &Topology) -> usize {
+    match topology {
+        Topology::Sphere => 0,
+        Topology::Bar2 | Topology::Bar3 => 1,
+        Topology::Tri3
+        | Topology::Tri6
+        | Topology::Tri7
+        | Topology::Quad4
+        | Topology::Quad8
+        | Topology::Quad9 => 2,
+        _ => 3,
+    }
+}
+
+/// Number of corner nodes of a face element
+fn corner_count(topology: &Topology) -> usize {
+    match topology {
+        Topology::Bar2 | Topology::Bar3 => 2,
+        Topology::Tri3 | Topology::Tri6 | Topology::Tri7 => 3,
+        Topology::Quad4 | Topology::Quad8 | Topology::Quad9 => 4,
+        _ => 1,
+    }
+}
+
+/// Gmsh file format version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GmshVersion {
+    /// Format 2.2
+    V2,
+    /// Format 4.1, written by current Gmsh releases
+    #[default]
+    V4,
+}
+
+impl GmshVersion {
+    /// The version as written in the `$MeshFormat` section
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V2 => "2.2",
+            Self::V4 => "4.1",
+        }
+    }
+}
+
+/// A Gmsh physical group
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhysicalGroup {
+    /// Dimension of the group's elements (0 to 3)
+    pub dim: usize,
+    /// Group tag, unique among the groups of a dimension
+    pub tag: i64,
+    /// Group name, empty if the group is unnamed
+    pub name: String,
+}
+
+/// An element of a Gmsh mesh
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GmshElement {
+    /// Gmsh element type, e.g. 5 for an 8-node hexahedron
+    pub element_type: u32,
+    /// Tag of the elementary entity holding the element
+    pub entity: i64,
+    /// Tags of the physical groups holding the element
+    pub physical_tags: Vec<i64>,
+    /// Nodes in Gmsh order, as 0-based indices into [`GmshMesh::nodes`]
+    pub nodes: Vec<usize>,
+}
+
+impl GmshElement {
+    /// Exodus topology of the element
+    pub fn topology(&self) -> Result<Topology> {
+        element_type(self.element_type)
+            .map(|(topology, _)| topology)
+            .ok_or_else(|| {
+                ExodusError::UnsupportedFeature(format!("Gmsh element type {}", self.element_type))
+            })
+    }
+}
+
+/// A mesh read from or written to a Gmsh file
+///
+/// Node and element tags are not kept: nodes are sorted by tag and numbered
+/// by their position in `nodes`, and elements by their position in
+/// `elements`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GmshMesh {
+    /// Node coordinates
+    pub nodes: Vec<[f64; 3]>,
+    /// Elements of all dimensions
+    pub elements: Vec<GmshElement>,
+    /// Physical groups, named or not, sorted by dimension and tag
+    pub physical_groups: Vec<PhysicalGroup>,
+}
+
+/// What a conversion read and wrote
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GmshSummary {
+    /// Number of nodes
+    pub num_nodes: usize,
+    /// Number of Exodus elements
+    pub num_elems: usize,
+    /// Number of element blocks
+    pub num_blocks: usize,
+    /// Number of node sets
+    pub num_node_sets: usize,
+    /// Number of side sets
+    pub num_side_sets: usize,
+}
+
+/// Convert a Gmsh file to an Exodus file, overwriting the output
+///
+/// # Errors
+///
+/// Returns an error if the Gmsh file cannot be read or converted (see
+/// [`GmshMesh::to_builder`]), or the Exodus file cannot be written.
+pub fn import_gmsh(input: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<GmshSummary> {
+    let input = input.as_ref();
+    let mesh = GmshMesh::read(input)?;
+    let parts = mesh.exodus_parts()?;
+    let summary = parts.summary(mesh.nodes.len());
+    let title = format!(
+        "Converted from {}",
+        input.file_name().unwrap_or_default().to_string_lossy()
+    );
+    parts.into_builder(title, &mesh.nodes).write(output)?;
+    Ok(summary)
+}
+
+/// Convert an Exodus file to a Gmsh file, overwriting the output
+///
+/// # Errors
+///
+/// Returns an error if the Exodus file cannot be read or converted (see
+/// [`GmshMesh::from_exodus`]), or the Gmsh file cannot be written.
+pub fn export_gmsh(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    version: GmshVersion,
+) -> Result<GmshSummary> {
+    let file = ExodusFile::<mode::Read>::open(input)?;
+    let mesh = GmshMesh::from_exodus(&file)?;
+    mesh.write(output, version)?;
+
+    let params = file.init_params()?;
+    Ok(GmshSummary {
+        num_nodes: params.num_nodes,
+        num_elems: params.num_elems,
+        num_blocks: params.num_elem_blocks,
+        num_node_sets: params.num_node_sets,
+        num_side_sets: params.num_side_sets,
+    })
+}
+
+impl GmshMesh {
+    /// Read an ASCII Gmsh file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed (see
+    /// [`GmshMesh::parse`]).
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::parse(&String::from_utf8_lossy(&bytes))
+    }
+
+    /// Parse the contents of an ASCII Gmsh file
+    ///
+    /// Sections other than `$MeshFormat`, `$PhysicalNames`, `$Entities`,
+    /// `$Nodes` and `$Elements` are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is binary, in a format other than 2.x or
+    /// 4.1, malformed, or holds an element type without an Exodus topology.
+    pub fn parse(text: &str) -> Result<Self> {
+        let sections = sections(text)?;
+        let format = sections
+            .iter()
+            .find(|(name, _)| *name == "MeshFormat")
+            .map(|(_, body)| *body)
+            .ok_or_else(|| ExodusError::Other("Gmsh file has no $MeshFormat section".into()))?;
+        let mut header = format.split_whitespace();
+        let version = header.next().unwrap_or_default();
+        if header.next() != Some("0") {
+            return Err(ExodusError::UnsupportedFeature(
+                "Binary Gmsh files".to_string(),
+            ));
+        }
+        let v4 = match version {
+            "4.1" => true,
+            _ if version.starts_with("2.") || version == "2" => false,
+            _ => {
+                return Err(ExodusError::UnsupportedFeature(format!(
+                    "Gmsh format {}",
+                    version
+                )))
+            }
+        };
+
+        let mut reader = Reader {
+            mesh: Self::default(),
+            names: BTreeMap::new(),
+            entity_groups: HashMap::new(),
+            node_indices: HashMap::new(),
+        };
+        for (name, body) in sections {
+            match (name, v4) {
+                ("PhysicalNames", _) => reader.physical_names(body)?,
+                ("Entities", true) => reader.entities_v4(body)?,
+                ("Nodes", false) => reader.nodes_v2(body)?,
+                ("Nodes", true) => reader.nodes_v4(body)?,
+                ("Elements", false) => reader.elements_v2(body)?,
+                ("Elements", true) => reader.elements_v4(body)?,
+                _ => {}
+            }
+        }
+        Ok(reader.finish())
+    }
+
+    /// Write the mesh to a Gmsh file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an element has an unknown type or the file cannot
+    /// be written.
+    pub fn write(&self, path: impl AsRef<Path>, version: GmshVersion) -> Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write_to(&mut out, version)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Write the mesh in Gmsh format
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an element has an unknown type or writing fails.
+    pub fn write_to(&self, out: &mut impl Write, version: GmshVersion) -> Result<()> {
+        let dims = self
+            .elements
+            .iter()
+            .map(|element| element.topology().map(|topology| dimension(&topology)))
+            .collect::<Result<Vec<_>>>()?;
+
+        writeln!(out, "$MeshFormat")?;
+        writeln!(out, "{} 0 8", version.as_str())?;
+        writeln!(out, "$EndMeshFormat")?;
+
+        let named: Vec<&PhysicalGroup> = self
+            .physical_groups
+            .iter()
+            .filter(|group| !group.name.is_empty())
+            .collect();
+        if !named.is_empty() {
+            writeln!(out, "$PhysicalNames")?;
+            writeln!(out, "{}", named.len())?;
+            for group in named {
+                writeln!(out, "{} {} \"{}\"", group.dim, group.tag, group.name)?;
+            }
+            writeln!(out, "$EndPhysicalNames")?;
+        }
+
+        match version {
+            GmshVersion::V2 => self.write_v2(out),
+            GmshVersion::V4 => self.write_v4(out, &dims),
+        }
+    }
+
+    fn write_v2(&self, out: &mut impl Write) -> Result<()> {
+        writeln!(out, "$Nodes")?;
+        writeln!(out, "{}", self.nodes.len())?;
+        for (i, [x, y, z]) in self.nodes.iter().enumerate() {
+            writeln!(out, "{} {} {} {}", i + 1, x, y, z)?;
+        }
+        writeln!(out, "$EndNodes")?;
+
+        // An element in several physical groups is written once for each
+        let count: usize = self
+            .elements
+            .iter()
+            .map(|element| element.physical_tags.len().max(1))
+            .sum();
+        writeln!(out, "$Elements")?;
+        writeln!(out, "{}", count)?;
+        let mut tag = 0;
+        for element in &self.elements {
+            let physicals: &[i64] = if element.physical_tags.is_empty() {
+                &[0]
+            } else {
+                &element.physical_tags
+            };
+            for physical in physicals {
+                tag += 1;
+                write!(
+                    out,
+                    "{} {} 2 {} {}",
+                    tag, element.element_type, physical, element.entity
+                )?;
+                for node in &element.nodes {
+                    write!(out, " {}", node + 1)?;
+                }
+                writeln!(out)?;
+            }
+        }
+        writeln!(out, "$EndElements")?;
+        Ok(())
+    }
+
+    fn write_v4(&self, out: &mut impl Write, dims: &[usize]) -> Result<()> {
+        // Elementary entities, with their physical groups, elements and
+        // bounding boxes
+        let mut entities: BTreeMap<(usize, i64), Entity> = BTreeMap::new();
+        for (index, element) in self.elements.iter().enumerate() {
+            let entity = entities.entry((dims[index], element.entity)).or_default();
+            for tag in &element.physical_tags {
+                if !entity.physical_tags.contains(tag) {
+                    entity.physical_tags.push(*tag);
+                }
+            }
+            entity.elements.push(index);
+            for &node in &element.nodes {
+                entity.include(self.node(node)?);
+            }
+        }
+
+        // Each node is listed under one entity, preferring the highest
+        // dimension; nodes of no element go with the first entity
+        let mut owners: Vec<Option<(usize, i64)>> = vec![None; self.nodes.len()];
+        for (&key, entity) in entities.iter().rev() {
+            for &index in &entity.elements {
+                for &node in &self.elements[index].nodes {
+                    owners[node].get_or_insert(key);
+                }
+            }
+        }
+        if owners.iter().any(Option::is_none) {
+            let key = match entities.keys().next() {
+                Some(&key) => key,
+                None => {
+                    entities.insert((0, 1), Entity::default());
+                    (0, 1)
+                }
+            };
+            for (node, owner) in owners.iter_mut().enumerate() {
+                if owner.is_none() {
+                    *owner = Some(key);
+                    let coords = self.nodes[node];
+                    if let Some(entity) = entities.get_mut(&key) {
+                        entity.include(coords);
+                    }
+                }
+            }
+        }
+        let mut owned: BTreeMap<(usize, i64), Vec<usize>> = BTreeMap::new();
+        for (node, owner) in owners.iter().enumerate() {
+            if let Some(key) = owner {
+                owned.entry(*key).or_default().push(node);
+            }
+        }
+
+        writeln!(out, "$Entities")?;
+        let counts: Vec<usize> = (0..4)
+            .map(|dim| entities.keys().filter(|(d, _)| *d == dim).count())
+            .collect();
+        writeln!(
+            out,
+            "{} {} {} {}",
+            counts[0], counts[1], counts[2], counts[3]
+        )?;
+        for (&(dim, tag), entity) in &entities {
+            let [min, max] = entity.bounds();
+            if dim == 0 {
+                write!(out, "{} {} {} {}", tag, min[0], min[1], min[2])?;
+            } else {
+                write!(
+                    out,
+                    "{} {} {} {} {} {} {}",
+                    tag, min[0], min[1], min[2], max[0], max[1], max[2]
+                )?;
+            }
+            write!(out, " {}", entity.physical_tags.len())?;
+            for physical in &entity.physical_tags {
+                write!(out, " {}", physical)?;
+            }
+            if dim > 0 {
+                // No bounding entities
+                write!(out, " 0")?;
+            }
+            writeln!(out)?;
+        }
+        writeln!(out, "$EndEntities")?;
+
+        writeln!(out, "$Nodes")?;
+        writeln!(
+            out,
+            "{} {} {} {}",
+            owned.len(),
+            self.nodes.len(),
+            self.nodes.len().min(1),
+            self.nodes.len()
+        )?;
+        for (&(dim, tag), nodes) in &owned {
+            writeln!(out, "{} {} 0 {}", dim, tag, nodes.len())?;
+            for node in nodes {
+                writeln!(out, "{}", node + 1)?;
+            }
+            for &node in nodes {
+                let [x, y, z] = self.nodes[node];
+                writeln!(out, "{} {} {}", x, y, z)?;
+            }
+        }
+        writeln!(out, "$EndNodes")?;
+
+        // Element blocks of one type within an entity, tagged in order
+        let mut blocks: BTreeMap<(usize, i64, u32), Vec<usize>> = BTreeMap::new();
+        for (&(dim, tag), entity) in &entities {
+            for &index in &entity.elements {
+                blocks
+                    .entry((dim, tag, self.elements[index].element_type))
+                    .or_default()
+                    .push(index);
+            }
+        }
+        writeln!(out, "$Elements")?;
+        writeln!(
+            out,
+            "{} {} {} {}",
+            blocks.len(),
+            self.elements.len(),
+            self.elements.len().min(1),
+            self.elements.len()
+        )?;
+        let mut element_tag = 0;
+        for (&(dim, tag, element_type), elements) in &blocks {
+            writeln!(out, "{} {} {} {}", dim, tag, element_type, elements.len())?;
+            for &index in elements {
+                element_tag += 1;
+                write!(out, "{}", element_tag)?;
+                for node in &self.elements[index].nodes {
+                    write!(out, " {}", node + 1)?;
+                }
+                writeln!(out)?;
+            }
+        }
+        writeln!(out, "$EndElements")?;
+        Ok(())
+    }
+
+    fn node(&self, index: usize) -> Result<[f64; 3]> {
+        self.nodes.get(index).copied().ok_or_else(|| {
+            ExodusError::Other(format!(
+                "Gmsh element node {} is outside the {} nodes of the mesh",
+                index,
+                self.nodes.len()
+            ))
+        })
+    }
+
+    /// Build an Exodus mesh from the physical groups (see the
+    /// [module documentation](self))
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mesh has no elements, an element has no
+    /// Exodus topology, or an element of a side set group is not the side of
+    /// an element.
+    pub fn to_builder(&self, title: impl Into<String>) -> Result<MeshBuilder> {
+        Ok(self.exodus_parts()?.into_builder(title, &self.nodes))
+    }
+
+    /// Build a Gmsh mesh from the element blocks, node sets and side sets of
+    /// an Exodus file (see the [module documentation](self))
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, a block has a topology
+    /// without a Gmsh element type, or a side set refers to a missing
+    /// element or side.
+    pub fn from_exodus(file: &ExodusFile<mode::Read>) -> Result<Self> {
+        let coords = file.coords::<f64>()?;
+        let num_nodes = coords.x.len();
+        let mut mesh = Self {
+            nodes: (0..num_nodes)
+                .map(|i| {
+                    [
+                        coords.x[i],
+                        coords.y.get(i).copied().unwrap_or(0.0),
+                        coords.z.get(i).copied().unwrap_or(0.0),
+                    ]
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let mut entities = [0i64; 4];
+        let mut next_entity = |dim: usize| {
+            entities[dim] += 1;
+            entities[dim]
+        };
+
+        // Blocks, kept for the side lookup
+        struct BlockElements {
+            topology: Topology,
+            nodes_per_elem: usize,
+            connectivity: Vec<i64>,
+            first_elem: usize,
+        }
+        let mut blocks = Vec::new();
+        let mut num_elems = 0;
+        let names = optional_names(file, EntityType::ElemBlock)?;
+        for (index, id) in file
+            .block_ids(EntityType::ElemBlock)?
+            .into_iter()
+            .enumerate()
+        {
+            let block = file.block(id)?;
+            let topology = Topology::of_block(&block);
+            let element_type = gmsh_type(&topology).ok_or_else(|| {
+                ExodusError::UnsupportedFeature(format!(
+                    "Block {} has topology {}, which has no Gmsh element type",
+                    id, block.topology
+                ))
+            })?;
+            let order = element_type_order(element_type);
+            if block.num_nodes_per_entry < order.len() {
+                return Err(ExodusError::InvalidTopology(format!(
+                    "Block {} is {} with {} nodes per element",
+                    id, block.topology, block.num_nodes_per_entry
+                )));
+            }
+            let dim = dimension(&topology);
+            let entity = next_entity(dim);
+            let connectivity = file.connectivity(id)?;
+            for element in connectivity.chunks(block.num_nodes_per_entry.max(1)) {
+                let mut nodes = vec![0; order.len()];
+                for (exodus, &gmsh) in order.iter().enumerate() {
+                    nodes[gmsh] = node_index(element[exodus], num_nodes)?;
+                }
+                mesh.elements.push(GmshElement {
+                    element_type,
+                    entity,
+                    physical_tags: vec![id],
+                    nodes,
+                });
+            }
+            mesh.physical_groups.push(PhysicalGroup {
+                dim,
+                tag: id,
+                name: names.get(index).cloned().unwrap_or_default(),
+            });
+            blocks.push(BlockElements {
+                topology,
+                nodes_per_elem: block.num_nodes_per_entry.max(1),
+                connectivity,
+                first_elem: num_elems,
+            });
+            num_elems += block.num_entries;
+        }
+
+        let names = optional_names(file, EntityType::SideSet)?;
+        for (index, id) in file.set_ids(EntityType::SideSet)?.into_iter().enumerate() {
+            let set = file.side_set(id)?;
+            let mut dims = BTreeMap::new();
+            for (&elem, &side) in set.elements.iter().zip(&set.sides) {
+                let missing = || {
+                    ExodusError::Other(format!(
+                        "Side set {} refers to side {} of element {}, which does not exist",
+                        id, side, elem
+                    ))
+                };
+                let elem = usize::try_from(elem)
+                    .ok()
+                    .filter(|&elem| (1..=num_elems).contains(&elem))
+                    .ok_or_else(missing)?
+                    - 1;
+                let block = &blocks[blocks.partition_point(|block| block.first_elem <= elem) - 1];
+                let start = (elem - block.first_elem) * block.nodes_per_elem;
+                let element = &block.connectivity[start..start + block.nodes_per_elem];
+                let face = block
+                    .topology
+                    .faces()
+                    .and_then(|faces| faces.into_iter().find(|f| f.side_number as i64 == side))
+                    .ok_or_else(missing)?;
+                let element_type = match face.node_indices.len() {
+                    2 => 1,
+                    3 => 2,
+                    _ => 3,
+                };
+                let dim = dimension(&block.topology) - 1;
+                let entity = *dims.entry(dim).or_insert_with(|| next_entity(dim));
+                mesh.elements.push(GmshElement {
+                    element_type,
+                    entity,
+                    physical_tags: vec![id],
+                    nodes: face
+                        .node_indices
+                        .iter()
+                        .map(|&i| node_index(element[i], num_nodes))
+                        .collect::<Result<_>>()?,
+                });
+            }
+            for dim in dims.into_keys() {
+                mesh.physical_groups.push(PhysicalGroup {
+                    dim,
+                    tag: id,
+                    name: names.get(index).cloned().unwrap_or_default(),
+                });
+            }
+        }
+
+        let names = optional_names(file, EntityType::NodeSet)?;
+        for (index, id) in file.set_ids(EntityType::NodeSet)?.into_iter().enumerate() {
+            let set = file.node_set(id)?;
+            if set.nodes.is_empty() {
+                continue;
+            }
+            let entity = next_entity(0);
+            for &node in &set.nodes {
+                mesh.elements.push(GmshElement {
+                    element_type: 15,
+                    entity,
+                    physical_tags: vec![id],
+                    nodes: vec![node_index(node, num_nodes)?],
+                });
+            }
+            mesh.physical_groups.push(PhysicalGroup {
+                dim: 0,
+                tag: id,
+                name: names.get(index).cloned().unwrap_or_default(),
+            });
+        }
+
+        mesh.physical_groups
+            .sort_by_key(|group| (group.dim, group.tag));
+        mesh.physical_groups
+            .dedup_by_key(|group| (group.dim, group.tag));
+        Ok(mesh)
+    }
+
+    /// Blocks and sets of the mesh in Exodus numbering
+    fn exodus_parts(&self) -> Result<ExodusParts> {
+        let topologies = self
+            .elements
+            .iter()
+            .map(GmshElement::topology)
+            .collect::<Result<Vec<_>>>()?;
+        let dims: Vec<usize> = topologies.iter().map(dimension).collect();
+        let top = *dims
+            .iter()
+            .max()
+            .ok_or_else(|| ExodusError::Other("Gmsh mesh has no elements".into()))?;
+        let group_name = |dim: usize, tag: i64| -> String {
+            self.physical_groups
+                .iter()
+                .find(|group| group.dim == dim && group.tag == tag)
+                .map(|group| truncate_name(&group.name))
+                .unwrap_or_default()
+        };
+
+        // Blocks by group tag (or entity) and element type. Gmsh 2 files repeat
+        // elements for each of their physical groups; the first group wins.
+        let by_physical = self
+            .elements
+            .iter()
+            .zip(&dims)
+            .any(|(element, &dim)| dim == top && !element.physical_tags.is_empty());
+        let mut blocks: Vec<ExodusBlock> = Vec::new();
+        let mut block_index: HashMap<(i64, u32), usize> = HashMap::new();
+        let mut seen: HashSet<&[usize]> = HashSet::new();
+        for (element, &dim) in self.elements.iter().zip(&dims) {
+            if dim != top {
+                continue;
+            }
+            let key = if by_physical {
+                match element.physical_tags.first() {
+                    Some(&tag) => tag,
+                    None => continue,
+                }
+            } else {
+                element.entity
+            };
+            if !seen.insert(&element.nodes) {
+                continue;
+            }
+            let (topology, order) = element_type(element.element_type).ok_or_else(|| {
+                ExodusError::UnsupportedFeature(format!(
+                    "Gmsh element type {}",
+                    element.element_type
+                ))
+            })?;
+            let index = *block_index
+                .entry((key, element.element_type))
+                .or_insert_with(|| {
+                    blocks.push(ExodusBlock {
+                        key,
+                        id: key,
+                        name: if by_physical {
+                            group_name(top, key)
+                        } else {
+                            String::new()
+                        },
+                        topology,
+                        connectivity: Vec::new(),
+                    });
+                    blocks.len() - 1
+                });
+            for &i in order {
+                let node = element.nodes.get(i).ok_or_else(|| {
+                    ExodusError::Other(format!(
+                        "Gmsh element of type {} has {} nodes",
+                        element.element_type,
+                        element.nodes.len()
+                    ))
+                })?;
+                blocks[index].connectivity.push(*node as i64 + 1);
+            }
+        }
+        blocks.sort_by_key(|block| block.key);
+        // Further element types of a group get IDs after the last group
+        let mut next_id = blocks.iter().map(|block| block.key).max().unwrap_or(0) + 1;
+        let mut used = HashSet::new();
+        for block in &mut blocks {
+            if !used.insert(block.key) {
+                block.id = next_id;
+                next_id += 1;
+            }
+        }
+
+        // Sides of the blocks' elements by their sorted corner nodes
+        let mut sides: HashMap<Vec<i64>, (i64, i64)> = HashMap::new();
+        let mut elem = 0;
+        for block in &blocks {
+            let nodes_per_elem = block.topology.expected_nodes().unwrap_or(1);
+            let faces = block.topology.faces().unwrap_or_default();
+            for element in block.connectivity.chunks(nodes_per_elem) {
+                elem += 1;
+                for face in &faces {
+                    let mut key: Vec<i64> = face.node_indices.iter().map(|&i| element[i]).collect();
+                    key.sort_unstable();
+                    sides.entry(key).or_insert((elem, face.side_number as i64));
+                }
+            }
+        }
+
+        let mut side_sets = Vec::new();
+        let mut node_sets: Vec<ExodusSet> = Vec::new();
+        for group in &self.physical_groups {
+            if group.dim >= top {
+                continue;
+            }
+            let members = self
+                .elements
+                .iter()
+                .zip(&topologies)
+                .zip(&dims)
+                .filter(|((element, _), &dim)| {
+                    dim == group.dim && element.physical_tags.contains(&group.tag)
+                })
+                .map(|((element, topology), _)| (element, topology));
+
+            if top >= 2 && group.dim == top - 1 {
+                let mut set = ExodusSet {
+                    id: group.tag,
+                    name: truncate_name(&group.name),
+                    entries: Vec::new(),
+                    sides: Vec::new(),
+                };
+                let mut added = HashSet::new();
+                for (element, topology) in members {
+                    let corners = corner_count(topology).min(element.nodes.len());
+                    let mut key: Vec<i64> = element.nodes[..corners]
+                        .iter()
+                        .map(|&node| node as i64 + 1)
+                        .collect();
+                    key.sort_unstable();
+                    let &(elem, side) = sides.get(&key).ok_or_else(|| {
+                        ExodusError::Other(format!(
+                            "An element of physical group {} ({}D) is not a side of any {}D element",
+                            group.tag, group.dim, top
+                        ))
+                    })?;
+                    if added.insert((elem, side)) {
+                        set.entries.push(elem);
+                        set.sides.push(side);
+                    }
+                }
+                if !set.entries.is_empty() {
+                    side_sets.push(set);
+                }
+            } else {
+                let nodes: BTreeSet<i64> = members
+                    .flat_map(|(element, _)| element.nodes.iter().map(|&node| node as i64 + 1))
+                    .collect();
+                if !nodes.is_empty() {
+                    node_sets.push(ExodusSet {
+                        id: group.tag,
+                        name: truncate_name(&group.name),
+                        entries: nodes.into_iter().collect(),
+                        sides: Vec::new(),
+                    });
+                }
+            }
+        }
+        // Point and curve groups may share a tag
+        let mut next_id = node_sets.iter().map(|set| set.id).max().unwrap_or(0) + 1;
+        let mut used = HashSet::new();
+        for set in &mut node_sets {
+            if !used.insert(set.id) {
+                set.id = next_id;
+                next_id += 1;
+            }
+        }
+
+        // Dimensions spanned by the elements or the coordinates
+        let spanned = (0..3)
+            .rev()
+            .find(|&axis| self.nodes.iter().any(|node| node[axis] != 0.0))
+            .map_or(1, |axis| axis + 1);
+        Ok(ExodusParts {
+            num_dim: top.max(spanned),
+            blocks,
+            node_sets,
+            side_sets,
+        })
+    }
+}
+
+/// An Exodus element block built from a Gmsh mesh
+#[derive(Debug)]
+struct ExodusBlock {
+    /// Physical group or entity tag the block came from
+    key: i64,
+    id: i64,
+    name: String,
+    topology: Topology,
+    /// 1-based node numbers in Exodus order
+    connectivity: Vec<i64>,
+}
+
+/// An Exodus node set (entries are nodes) or side set (entries are elements)
+#[derive(Debug)]
+struct ExodusSet {
+    id: i64,
+    name: String,
+    entries: Vec<i64>,
+    sides: Vec<i64>,
+}
+
+/// An Exodus mesh built from a Gmsh mesh
+#[derive(Debug)]
+struct ExodusParts {
+    num_dim: usize,
+    blocks: Vec<ExodusBlock>,
+    node_sets: Vec<ExodusSet>,
+    side_sets: Vec<ExodusSet>,
+}
+
+impl ExodusParts {
+    fn summary(&self, num_nodes: usize) -> GmshSummary {
+        GmshSummary {
+            num_nodes,
+            num_elems: self
+                .blocks
+                .iter()
+                .map(|block| {
+                    block.connectivity.len() / block.topology.expected_nodes().unwrap_or(1)
+                })
+                .sum(),
+            num_blocks: self.blocks.len(),
+            num_node_sets: self.node_sets.len(),
+            num_side_sets: self.side_sets.len(),
+        }
+    }
+
+    fn into_builder(self, title: impl Into<String>, nodes: &[[f64; 3]]) -> MeshBuilder {
+        let axis = |axis: usize| -> Vec<f64> {
+            if axis < self.num_dim {
+                nodes.iter().map(|node| node[axis]).collect()
+            } else {
+                Vec::new()
+            }
+        };
+        let mut builder = MeshBuilder::new(title)
+            .dimensions(self.num_dim)
+            .coordinates(axis(0), axis(1), axis(2));
+        for block in self.blocks {
+            let mut block_builder = BlockBuilder::new(block.id, block.topology.as_str())
+                .connectivity(block.connectivity);
+            if !block.name.is_empty() {
+                block_builder = block_builder.name(block.name);
+            }
+            builder = builder.add_block(block_builder.build());
+        }
+        for set in self.node_sets {
+            let mut set_builder = NodeSetBuilder::new(set.id).nodes(set.entries);
+            if !set.name.is_empty() {
+                set_builder = set_builder.name(set.name);
+            }
+            builder = builder.add_node_set(set_builder.build());
+        }
+        for set in self.side_sets {
+            let mut set_builder =
+                SideSetBuilder::new(set.id).elements_and_sides(set.entries, set.sides);
+            if !set.name.is_empty() {
+                set_builder = set_builder.name(set.name);
+            }
+            builder = builder.add_side_set(set_builder.build());
+        }
+        builder
+    }
+}
+
+/// Node order of a known Gmsh element type
+fn element_type_order(gmsh_type: u32) -> &'static [usize] {
+    element_type(gmsh_type).map_or(&[], |(_, order)| order)
+}
+
+/// Names of the blocks or sets of a type, empty if the file stores none
+fn optional_names(file: &ExodusFile<mode::Read>, entity_type: EntityType) -> Result<Vec<String>> {
+    match file.names(entity_type) {
+        Err(ExodusError::VariableNotDefined(_)) => Ok(Vec::new()),
+        result => result,
+    }
+}
+
+/// 0-based index of a 1-based node number
+fn node_index(node: i64, num_nodes: usize) -> Result<usize> {
+    match usize::try_from(node) {
+        Ok(node) if (1..=num_nodes).contains(&node) => Ok(node - 1),
+        _ => Err(ExodusError::Other(format!(
+            "Node {} is outside the {} nodes of the mesh",
+            node, num_nodes
+        ))),
+    }
+}
+
+/// The `$Name` ... `$EndName` sections of a Gmsh file with their contents
+fn sections(text: &str) -> Result<Vec<(&str, &str)>> {
+    let mut sections = Vec::new();
+    let mut open: Option<(&str, usize)> = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let line = line.trim();
+        match open {
+            None => {
+                if let Some(name) = line.strip_prefix('$') {
+                    open = Some((name, offset));
+                }
+            }
+            Some((name, body)) => {
+                if line.strip_prefix("$End") == Some(name) {
+                    sections.push((name, &text[body..start]));
+                    open = None;
+                }
+            }
+        }
+    }
+    match open {
+        Some((name, _)) => Err(ExodusError::Other(format!(
+            "Gmsh section ${} has no $End{}",
+            name, name
+        ))),
+        None => Ok(sections),
+    }
+}
+
+/// Whitespace-separated values of a section
+struct Tokens<'a> {
+    section: &'static str,
+    tokens: SplitWhitespace<'a>,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(section: &'static str, body: &'a str) -> Self {
+        Self {
+            section,
+            tokens: body.split_whitespace(),
+        }
+    }
+
+    fn next<T: FromStr>(&mut self) -> Result<T> {
+        let token = self.tokens.next().ok_or_else(|| {
+            ExodusError::Other(format!("Gmsh section ${} ends early", self.section))
+        })?;
+        token.parse().map_err(|_| {
+            ExodusError::Other(format!(
+                "Invalid value '{}' in Gmsh section ${}",
+                token, self.section
+            ))
+        })
+    }
+}
+
+/// An elementary entity being written in format 4.1
+#[derive(Debug, Default)]
+struct Entity {
+    physical_tags: Vec<i64>,
+    elements: Vec<usize>,
+    bounds: Option<[[f64; 3]; 2]>,
+}
+
+impl Entity {
+    fn include(&mut self, point: [f64; 3]) {
+        let [min, max] = self.bounds.get_or_insert([point, point]);
+        for axis in 0..3 {
+            min[axis] = min[axis].min(point[axis]);
+            max[axis] = max[axis].max(point[axis]);
+        }
+    }
+
+    fn bounds(&self) -> [[f64; 3]; 2] {
+        self.bounds.unwrap_or_default()
+    }
+}
+
+/// State while parsing a Gmsh file
+struct Reader {
+    mesh: GmshMesh,
+    names: BTreeMap<(usize, i64), String>,
+    /// Physical groups of the elementary entities (format 4.1)
+    entity_groups: HashMap<(usize, i64), Vec<i64>>,
+    /// Node tag to position in the mesh
+    node_indices: HashMap<i64, usize>,
+}
+
+impl Reader {
+    fn physical_names(&mut self, body: &str) -> Result<()> {
+        for line in body.lines().skip(1) {
+            let mut tokens = Tokens::new("PhysicalNames", line);
+            if line.trim().is_empty() {
+                continue;
+            }
+            let dim: usize = tokens.next()?;
+            let tag: i64 = tokens.next()?;
+            let name = match (line.find('"'), line.rfind('"')) {
+                (Some(first), Some(last)) if last > first => &line[first + 1..last],
+                _ => "",
+            };
+            self.names.insert((dim, tag), name.to_string());
+        }
+        Ok(())
+    }
+
+    fn entities_v4(&mut self, body: &str) -> Result<()> {
+        let mut tokens = Tokens::new("Entities", body);
+        let counts: [usize; 4] = [
+            tokens.next()?,
+            tokens.next()?,
+            tokens.next()?,
+            tokens.next()?,
+        ];
+        for (dim, &count) in counts.iter().enumerate() {
+            for _ in 0..count {
+                let tag: i64 = tokens.next()?;
+                // A point, or the corners of a bounding box
+                let num_coords = if dim == 0 { 3 } else { 6 };
+                for _ in 0..num_coords {
+                    tokens.next::<f64>()?;
+                }
+                let num_physicals: usize = tokens.next()?;
+                let physicals = (0..num_physicals)
+                    .map(|_| tokens.next::<i64>())
+                    .collect::<Result<Vec<_>>>()?;
+                if dim > 0 {
+                    let num_bounding: usize = tokens.next()?;
+                    for _ in 0..num_bounding {
+                        tokens.next::<i64>()?;
+                    }
+                }
+                self.entity_groups.insert((dim, tag), physicals);
+            }
+        }
+        Ok(())
+    }
+
+    fn add_node(&mut self, tag: i64, coords: [f64; 3]) -> Result<()> {
+        if self
+            .node_indices
+            .insert(tag, self.mesh.nodes.len())
+            .is_some()
+        {
+            return Err(ExodusError::Other(format!(
+                "Gmsh node {} is defined twice",
+                tag
+            )));
+        }
+        self.mesh.nodes.push(coords);
+        Ok(())
+    }
+
+    fn nodes_v2(&mut self, body: &str) -> Result<()> {
+        let mut tokens = Tokens::new("Nodes", body);
+        let count: usize = tokens.next()?;
+        for _ in 0..count {
+            let tag = tokens.next()?;
+            let coords = [tokens.next()?, tokens.next()?, tokens.next()?];
+            self.add_node(tag, coords)?;
+        }
+        Ok(())
+    }
+
+    fn nodes_v4(&mut self, body: &str) -> Result<()> {
+        let mut tokens = Tokens::new("Nodes", body);
+        let num_blocks: usize = tokens.next()?;
+        let _num_nodes: usize = tokens.next()?;
+        let _min_tag: i64 = tokens.next()?;
+        let _max_tag: i64 = tokens.next()?;
+        for _ in 0..num_blocks {
+            let dim: usize = tokens.next()?;
+            let _entity: i64 = tokens.next()?;
+            let parametric: usize = tokens.next()?;
+            let count: usize = tokens.next()?;
+            let tags = (0..count)
+                .map(|_| tokens.next::<i64>())
+                .collect::<Result<Vec<_>>>()?;
+            // Parametric nodes also give their position on a curve or surface
+            let num_params = if parametric == 1 { dim.min(2) } else { 0 };
+            for tag in tags {
+                let coords = [tokens.next()?, tokens.next()?, tokens.next()?];
+                for _ in 0..num_params {
+                    tokens.next::<f64>()?;
+                }
+                self.add_node(tag, coords)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the nodes of an element of a known type
+    fn element_nodes(&self, tokens: &mut Tokens<'_>, element_type: u32) -> Result<Vec<usize>> {
+        let count = element_type_order(element_type).len();
+        if count == 0 {
+            return Err(ExodusError::UnsupportedFeature(format!(
+                "Gmsh element type {}",
+                element_type
+            )));
+        }
+        (0..count)
+            .map(|_| {
+                let tag: i64 = tokens.next()?;
+                self.node_indices.get(&tag).copied().ok_or_else(|| {
+                    ExodusError::Other(format!("Gmsh element refers to undefined node {}", tag))
+                })
+            })
+            .collect()
+    }
+
+    fn elements_v2(&mut self, body: &str) -> Result<()> {
+        let mut tokens = Tokens::new("Elements", body);
+        let count: usize = tokens.next()?;
+        for _ in 0..count {
+            let _tag: i64 = tokens.next()?;
+            let element_type: u32 = tokens.next()?;
+            let num_tags: usize = tokens.next()?;
+            let tags = (0..num_tags)
+                .map(|_| tokens.next::<i64>())
+                .collect::<Result<Vec<_>>>()?;
+            let nodes = self.element_nodes(&mut tokens, element_type)?;
+            self.mesh.elements.push(GmshElement {
+                element_type,
+                entity: tags.get(1).copied().unwrap_or(0),
+                physical_tags: tags
+                    .first()
+                    .copied()
+                    .filter(|&tag| tag != 0)
+                    .into_iter()
+                    .collect(),
+                nodes,
+            });
+        }
+        Ok(())
+    }
+
+    fn elements_v4(&mut self, body: &str) -> Result<()> {
+        let mut tokens = Tokens::new("Elements", body);
+        let num_blocks: usize = tokens.next()?;
+        let _num_elements: usize = tokens.next()?;
+        let _min_tag: i64 = tokens.next()?;
+        let _max_tag: i64 = tokens.next()?;
+        for _ in 0..num_blocks {
+            let dim: usize = tokens.next()?;
+            let entity: i64 = tokens.next()?;
+            let element_type: u32 = tokens.next()?;
+            let count: usize = tokens.next()?;
+            let physical_tags = self
+                .entity_groups
+                .get(&(dim, entity))
+                .cloned()
+                .unwrap_or_default();
+            for _ in 0..count {
+                let _tag: i64 = tokens.next()?;
+                let nodes = self.element_nodes(&mut tokens, element_type)?;
+                self.mesh.elements.push(GmshElement {
+                    element_type,
+                    entity,
+                    physical_tags: physical_tags.clone(),
+                    nodes,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The mesh, with nodes in tag order and a group for every physical tag
+    /// used by an element
+    fn finish(mut self) -> GmshMesh {
+        let mut tags: Vec<(i64, usize)> = self.node_indices.into_iter().collect();
+        tags.sort_unstable();
+        let mut positions = vec![0; tags.len()];
+        let mut nodes = Vec::with_capacity(tags.len());
+        for (position, &(_, index)) in tags.iter().enumerate() {
+            positions[index] = position;
+            nodes.push(self.mesh.nodes[index]);
+        }
+        self.mesh.nodes = nodes;
+        for element in &mut self.mesh.elements {
+            for node in &mut element.nodes {
+                *node = positions[*node];
+            }
+        }
+
+        for element in &self.mesh.elements {
+            if let Ok(topology) = element.topology() {
+                for &tag in &element.physical_tags {
+                    self.names.entry((dimension(&topology), tag)).or_default();
+                }
+            }
+        }
+        self.mesh.physical_groups = self
+            .names
+            .into_iter()
+            .map(|((dim, tag), name)| PhysicalGroup { dim, tag, name })
+            .collect();
+        self.mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A unit cube HEX8 with a named volume, a surface on the bottom face
+    /// and a point group on the origin
+    const CUBE_V2: &str = r#"$MeshFormat
+2.2 0 8
+$EndMeshFormat
+$PhysicalNames
+3
+0 3 "origin"
+2 2 "bottom"
+3 1 "solid"
+$EndPhysicalNames
+$Nodes
+8
+1 0 0 0
+2 1 0 0
+3 1 1 0
+4 0 1 0
+5 0 0 1
+6 1 0 1
+7 1 1 1
+8 0 1 1
+$EndNodes
+$Elements
+3
+1 15 2 3 1 1
+2 3 2 2 1 1 4 3 2
+3 5 2 1 1 1 2 3 4 5 6 7 8
+$EndElements
+"#;
+
+    /// A TET10 with node tags from 11, in an unnamed volume group and a
+    /// named surface group
+    const TET10_V4: &str = r#"$MeshFormat
+4.1 0 8
+$EndMeshFormat
+$PhysicalNames
+1
+2 7 "face"
+$EndPhysicalNames
+$Entities
+0 0 1 1
+1 0 0 0 1 1 0 1 7 0
+1 0 0 0 1 1 1 1 4 1 1
+$EndEntities
+$Nodes
+2 10 11 20
+3 1 0 7
+11
+12
+13
+14
+15
+16
+17
+0 0 0
+1 0 0
+0 1 0
+0 0 1
+0.5 0 0
+0.5 0.5 0
+0 0.5 0
+2 1 0 3
+18
+19
+20
+0 0 0.5
+0 0.5 0.5
+0.5 0 0.5
+$EndNodes
+$Elements
+2 2 1 2
+3 1 11 1
+1 11 12 13 14 15 16 17 18 19 20
+2 1 2 1
+2 11 13 12
+$EndElements
+"#;
+
+    #[test]
+    fn test_import_v2_groups() {
+        let mesh = GmshMesh::parse(CUBE_V2).unwrap();
+        assert_eq!(mesh.nodes.len(), 8);
+        assert_eq!(mesh.physical_groups.len(), 3);
+        let mut v4 = Vec::new();
+        mesh.write_to(&mut v4, GmshVersion::V4).unwrap();
+        assert_eq!(
+            GmshMesh::parse(std::str::from_utf8(&v4).unwrap()).unwrap(),
+            mesh
+        );
+
+        let parts = mesh.exodus_parts().unwrap();
+        assert_eq!(parts.num_dim, 3);
+        assert_eq!(parts.blocks.len(), 1);
+        assert_eq!(parts.blocks[0].id, 1);
+        assert_eq!(parts.blocks[0].name, "solid");
+        assert_eq!(parts.blocks[0].topology, Topology::Hex8);
+        assert_eq!(parts.blocks[0].connectivity, (1..=8).collect::<Vec<_>>());
+
+        // The bottom face is side 5 of the hex
+        assert_eq!(parts.side_sets.len(), 1);
+        assert_eq!(parts.side_sets[0].id, 2);
+        assert_eq!(parts.side_sets[0].name, "bottom");
+        assert_eq!(
+            (
+                &parts.side_sets[0].entries[..],
+                &parts.side_sets[0].sides[..]
+            ),
+            (&[1][..], &[5][..])
+        );
+        assert_eq!(parts.node_sets.len(), 1);
+        assert_eq!(
+            (parts.node_sets[0].id, &parts.node_sets[0].entries[..]),
+            (3, &[1][..])
+        );
+    }
+
+    #[test]
+    fn test_import_v4_reorders_nodes() {
+        let mesh = GmshMesh::parse(TET10_V4).unwrap();
+        assert_eq!(mesh.nodes.len(), 10);
+        assert_eq!(mesh.nodes[7], [0.0, 0.0, 0.5]);
+
+        let parts = mesh.exodus_parts().unwrap();
+        let block = &parts.blocks[0];
+        assert_eq!((block.id, block.name.as_str()), (4, ""));
+        // Gmsh edge nodes (2,3) and (1,3) swap places
+        assert_eq!(block.connectivity, vec![1, 2, 3, 4, 5, 6, 7, 8, 10, 9]);
+        assert_eq!(parts.side_sets[0].name, "face");
+        assert_eq!(parts.side_sets[0].sides, vec![4]);
+
+        let mut binary = TET10_V4.replacen("4.1 0 8", "4.1 1 8", 1);
+        assert!(matches!(
+            GmshMesh::parse(&binary),
+            Err(ExodusError::UnsupportedFeature(_))
+        ));
+        binary = TET10_V4.replacen("4.1 0 8", "4 0 8", 1);
+        assert!(matches!(
+            GmshMesh::parse(&binary),
+            Err(ExodusError::UnsupportedFeature(_))
+        ));
+    }
+
+    #[test]
+    fn test_node_orders_are_permutations() {
+        for gmsh in 1..=MAX_ELEMENT_TYPE {
+            let (topology, order) = element_type(gmsh).unwrap();
+            let mut sorted = order.to_vec();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..order.len()).collect::<Vec<_>>());
+            assert_eq!(topology.expected_nodes(), Some(order.len()));
+            assert_eq!(gmsh_type(&topology), Some(gmsh));
+        }
+        // Gmsh's HEX20 edge 9 runs from corner 0 to 3, Exodus edge 11
+        assert_eq!(HEX20_ORDER[11], 9);
+    }
+
+    /// Sorted corner nodes of each side of a side set
+    fn side_keys(file: &ExodusFile<mode::Read>, elements: &[i64], sides: &[i64]) -> Vec<Vec<i64>> {
+        let mut connectivity = Vec::new();
+        for id in file.block_ids(EntityType::ElemBlock).unwrap() {
+            let block = file.block(id).unwrap();
+            let conn = file.connectivity(id).unwrap();
+            for element in conn.chunks(block.num_nodes_per_entry) {
+                connectivity.push((Topology::of_block(&block), element.to_vec()));
+            }
+        }
+        let mut keys: Vec<Vec<i64>> = elements
+            .iter()
+            .zip(sides)
+            .map(|(&elem, &side)| {
+                let (topology, element) = &connectivity[elem as usize - 1];
+                let face = &topology.faces().unwrap()[side as usize - 1];
+                let mut key: Vec<i64> = face.node_indices.iter().map(|&i| element[i]).collect();
+                key.sort_unstable();
+                key
+            })
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_roundtrip_through_both_versions() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../packages/seacas/scripts/tests/exomerge_unit_test.e");
+        let file = ExodusFile::<mode::Read>::open(path).unwrap();
+        let mesh = GmshMesh::from_exodus(&file).unwrap();
+
+        for version in [GmshVersion::V2, GmshVersion::V4] {
+            let mut text = Vec::new();
+            mesh.write_to(&mut text, version).unwrap();
+            let read = GmshMesh::parse(std::str::from_utf8(&text).unwrap()).unwrap();
+            assert_eq!(read.nodes, mesh.nodes);
+            let parts = read.exodus_parts().unwrap();
+
+            let ids: Vec<i64> = parts.blocks.iter().map(|block| block.id).collect();
+            assert_eq!(ids, file.block_ids(EntityType::ElemBlock).unwrap());
+            for block in &parts.blocks {
+                assert_eq!(block.connectivity, file.connectivity(block.id).unwrap());
+            }
+
+            let ids: Vec<i64> = parts.side_sets.iter().map(|set| set.id).collect();
+            let expected: Vec<i64> = file
+                .set_ids(EntityType::SideSet)
+                .unwrap()
+                .into_iter()
+                .filter(|&id| !file.side_set(id).unwrap().elements.is_empty())
+                .collect();
+            assert_eq!(ids, expected);
+            for set in &parts.side_sets {
+                let original = file.side_set(set.id).unwrap();
+                assert_eq!(
+                    side_keys(&file, &set.entries, &set.sides),
+                    side_keys(&file, &original.elements, &original.sides)
+                );
+            }
+
+            for set in &parts.node_sets {
+                let original: BTreeSet<i64> =
+                    file.node_set(set.id).unwrap().nodes.into_iter().collect();
+                assert_eq!(set.entries, original.into_iter().collect::<Vec<_>>());
+            }
+        }
+    }
+}
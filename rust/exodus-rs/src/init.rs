@@ -3,10 +3,13 @@
 //! This module provides initialization functionality for Exodus files,
 //! including parameter setup and builder pattern support.
 
-use crate::error::{ExodusError, Result};
+use crate::backend::Backend;
+use crate::error::Result;
 use crate::types::InitParams;
 use crate::utils::constants::*;
-use crate::{mode, ExodusFile};
+use crate::ExodusFile;
+#[cfg(feature = "netcdf4")]
+use crate::{error::ExodusError, mode};
 
 #[cfg(feature = "netcdf4")]
 /// Builder for fluent initialization API
@@ -34,6 +37,7 @@ pub struct InitBuilder<'a> {
     params: InitParams,
 }
 
+#[cfg(feature = "netcdf4")]
 impl<'a> InitBuilder<'a> {
     /// Create a new builder for the given file
     pub(crate) fn new(file: &'a mut ExodusFile<mode::Write>) -> Self {
@@ -241,6 +245,8 @@ impl ExodusFile<mode::Write> {
         self.metadata.title = Some(params.title.clone());
         self.metadata.num_dim = Some(params.num_dim);
 
+        self.finish_definitions()?;
+
         Ok(())
     }
 
@@ -516,7 +522,7 @@ impl ExodusFile<mode::Write> {
         // Create coordx variable
         let mut var_x = self
             .nc_file
-            .add_variable::<f64>(VAR_COORD_X, &[DIM_NUM_NODES])
+            .add_variable_with_type(VAR_COORD_X, &[DIM_NUM_NODES], &self.metadata.float_type())
             .map_err(ExodusError::NetCdf)?;
 
         // Apply chunking if specified and valid
@@ -525,11 +531,12 @@ impl ExodusFile<mode::Write> {
                 .set_chunking(&[chunk_size])
                 .map_err(ExodusError::NetCdf)?;
         }
+        self.metadata.apply_compression(&mut var_x)?;
 
         // Create coordy variable
         let mut var_y = self
             .nc_file
-            .add_variable::<f64>(VAR_COORD_Y, &[DIM_NUM_NODES])
+            .add_variable_with_type(VAR_COORD_Y, &[DIM_NUM_NODES], &self.metadata.float_type())
             .map_err(ExodusError::NetCdf)?;
 
         if chunk_size > 0 {
@@ -537,11 +544,12 @@ impl ExodusFile<mode::Write> {
                 .set_chunking(&[chunk_size])
                 .map_err(ExodusError::NetCdf)?;
         }
+        self.metadata.apply_compression(&mut var_y)?;
 
         // Create coordz variable
         let mut var_z = self
             .nc_file
-            .add_variable::<f64>(VAR_COORD_Z, &[DIM_NUM_NODES])
+            .add_variable_with_type(VAR_COORD_Z, &[DIM_NUM_NODES], &self.metadata.float_type())
             .map_err(ExodusError::NetCdf)?;
 
         if chunk_size > 0 {
@@ -549,6 +557,7 @@ impl ExodusFile<mode::Write> {
                 .set_chunking(&[chunk_size])
                 .map_err(ExodusError::NetCdf)?;
         }
+        self.metadata.apply_compression(&mut var_z)?;
 
         Ok(())
     }
@@ -557,7 +566,7 @@ impl ExodusFile<mode::Write> {
     fn create_block_id_variable(&mut self, var_name: &str, dim_name: &str) -> Result<()> {
         let mut var = self
             .nc_file
-            .add_variable::<i64>(var_name, &[dim_name])
+            .add_variable_with_type(var_name, &[dim_name], &self.metadata.int_type())
             .map_err(ExodusError::NetCdf)?;
 
         // Add attribute to identify this as a property variable
@@ -572,7 +581,6 @@ impl ExodusFile<mode::Write> {
 // Read Operations (available in all modes)
 // =============================================================================
 
-#[cfg(feature = "netcdf4")]
 impl<M: crate::FileMode> ExodusFile<M> {
     /// Get initialization parameters from the file
     ///
@@ -605,9 +613,9 @@ impl<M: crate::FileMode> ExodusFile<M> {
         let mut params = InitParams::default();
 
         // Read title from global attribute
-        if let Some(attr) = self.nc_file.attribute(ATTR_TITLE) {
-            if let Ok(netcdf::AttributeValue::Str(s)) = attr.value() {
-                params.title = s;
+        if let Some(title) = self.nc_file.global_attribute(ATTR_TITLE) {
+            if let Some(s) = title.as_str() {
+                params.title = s.to_string();
             }
         }
 
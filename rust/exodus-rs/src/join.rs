@@ -0,0 +1,871 @@
+//! Joining decomposed file-per-processor results into a single file.
+//!
+//! Parallel analyses write one Exodus file per processor (`mesh.e.4.0`
+//! through `mesh.e.4.3`). Each file holds the local part of the mesh and the
+//! node and element ID maps back to the global mesh. [`join_files`] merges the
+//! parts into one file, the equivalent of the SEACAS `epu` tool. Nodes shared
+//! between processors are merged by global ID. Nodes, the elements of each
+//! block, and set entries are written in global ID order.
+//!
+//! Coordinates, element blocks (connectivity and attributes), node sets, side
+//! sets and their names are joined. Global, nodal, element block, node set and
+//! side set variables are joined too. Files with edge or face blocks, or with
+//! edge, face or element sets, are rejected.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use exodus_rs::join::{find_decomposed_files, join_files, JoinOptions};
+//!
+//! // Join only the last step's displacements
+//! let parts = find_decomposed_files("results.e")?;
+//! let options = JoinOptions {
+//!     steps: Some(vec![99]),
+//!     variables: Some(vec!["displ_x".into(), "displ_y".into()]),
+//!     ..Default::default()
+//! };
+//! let summary = join_files(&parts, "results.e", &options)?;
+//! println!("{} nodes from {} files", summary.num_nodes, summary.num_parts);
+//! # Ok::<(), exodus_rs::ExodusError>(())
+//! ```
+
+use crate::error::{EntityId, ExodusError, Result};
+use crate::types::{Block, CreateOptions, EntityType, InitParams, NemesisFileType, TruthTable};
+use crate::{mode, ExodusFile};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Transient variable types carried over by [`join_files`]
+const VAR_TYPES: [EntityType; 5] = [
+    EntityType::Global,
+    EntityType::Nodal,
+    EntityType::ElemBlock,
+    EntityType::NodeSet,
+    EntityType::SideSet,
+];
+
+/// Options controlling what [`join_files`] copies
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JoinOptions {
+    /// Time steps to join (0-based, in output order), or `None` for all steps
+    pub steps: Option<Vec<usize>>,
+    /// Transient variables to join, or `None` for all variables. Names are
+    /// matched case-insensitively against global, nodal, element block, node
+    /// set and side set variables.
+    pub variables: Option<Vec<String>>,
+    /// Options used to create the joined file
+    pub create_options: CreateOptions,
+}
+
+/// Sizes of a joined file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JoinSummary {
+    /// Number of decomposed files read
+    pub num_parts: usize,
+    /// Number of nodes in the joined mesh
+    pub num_nodes: usize,
+    /// Number of elements in the joined mesh
+    pub num_elems: usize,
+    /// Number of time steps written
+    pub num_time_steps: usize,
+}
+
+/// Path of the file written by processor `rank` of `num_procs`
+///
+/// Follows the Nemesis convention `<base>.<num_procs>.<rank>`, with the rank
+/// zero-padded to the width of `num_procs` (`mesh.e.16.03`).
+pub fn decomposed_file_name(base: impl AsRef<Path>, num_procs: usize, rank: usize) -> PathBuf {
+    let width = num_procs.to_string().len();
+    let mut name = base.as_ref().as_os_str().to_owned();
+    name.push(format!(".{}.{:0width$}", num_procs, rank, width = width));
+    PathBuf::from(name)
+}
+
+/// Find the complete set of decomposed files for `base`
+///
+/// Looks next to `base` for files named `<base>.<num_procs>.<rank>` and
+/// returns them ordered by rank.
+///
+/// # Errors
+///
+/// Returns an error if no decomposed files exist, if files for more than one
+/// processor count are present, or if any rank is missing.
+pub fn find_decomposed_files(base: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+    let base = base.as_ref();
+    let prefix = base
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| format!("{}.", name))
+        .ok_or_else(|| ExodusError::Other(format!("Invalid base name: {}", base.display())))?;
+    let dir = match base.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let mut num_procs = None;
+    for entry in std::fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let Some((count, rank)) = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(&prefix))
+            .and_then(|suffix| suffix.split_once('.'))
+        else {
+            continue;
+        };
+        let (Ok(count), Ok(_)) = (count.parse::<usize>(), rank.parse::<usize>()) else {
+            continue;
+        };
+        match num_procs {
+            None if count > 0 => num_procs = Some(count),
+            Some(existing) if existing != count => {
+                return Err(ExodusError::Other(format!(
+                    "Decompositions for both {} and {} processors found for {}",
+                    existing,
+                    count,
+                    base.display()
+                )))
+            }
+            _ => {}
+        }
+    }
+
+    let num_procs = num_procs.ok_or_else(|| {
+        ExodusError::Other(format!("No decomposed files found for {}", base.display()))
+    })?;
+    let paths: Vec<PathBuf> = (0..num_procs)
+        .map(|rank| decomposed_file_name(base, num_procs, rank))
+        .collect();
+    if let Some(missing) = paths.iter().find(|path| !path.exists()) {
+        return Err(ExodusError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Missing decomposed file {}", missing.display()),
+        )));
+    }
+    Ok(paths)
+}
+
+/// Join decomposed files into a single Exodus file
+///
+/// Every part must store node and element ID maps; they define the global
+/// numbering of the joined mesh. A single part without maps is numbered
+/// sequentially, which makes this usable for extracting steps or variables
+/// from a serial file.
+///
+/// # Arguments
+///
+/// * `parts` - The decomposed files, ordered by rank
+/// * `output` - Path of the joined file
+/// * `options` - Step and variable selection, and creation options
+///
+/// # Returns
+///
+/// The sizes of the joined file
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - No parts are given, or the parts disagree on dimensions, variable names
+///   or number of time steps
+/// - A part contains edge or face blocks, or edge, face or element sets
+/// - A selected step or variable does not exist
+/// - The joined counts differ from the global counts stored in the Nemesis
+///   metadata (usually a missing part)
+/// - NetCDF read or write fails
+pub fn join_files<P: AsRef<Path>>(
+    parts: &[P],
+    output: impl AsRef<Path>,
+    options: &JoinOptions,
+) -> Result<JoinSummary> {
+    if parts.is_empty() {
+        return Err(ExodusError::Other("No decomposed files to join".into()));
+    }
+    let files = parts
+        .iter()
+        .map(ExodusFile::<mode::Read>::open)
+        .collect::<Result<Vec<_>>>()?;
+    let params = files
+        .iter()
+        .map(|file| file.init_params())
+        .collect::<Result<Vec<_>>>()?;
+
+    let num_dim = params[0].num_dim;
+    for p in &params {
+        if p.num_dim != num_dim {
+            return Err(ExodusError::InvalidDimension {
+                expected: num_dim.to_string(),
+                actual: p.num_dim,
+            });
+        }
+        if p.num_edge_blocks
+            + p.num_face_blocks
+            + p.num_edge_sets
+            + p.num_face_sets
+            + p.num_elem_sets
+            > 0
+        {
+            return Err(ExodusError::UnsupportedFeature(
+                "Joining edge or face blocks and edge, face or element sets".into(),
+            ));
+        }
+    }
+
+    // Global node numbering: the sorted union of every part's node IDs
+    let single = files.len() == 1;
+    let mut node_ids = Vec::with_capacity(files.len());
+    let mut elem_ids = Vec::with_capacity(files.len());
+    for (file, p) in files.iter().zip(&params) {
+        node_ids.push(local_ids(file, EntityType::NodeMap, p.num_nodes, single)?);
+        elem_ids.push(local_ids(file, EntityType::ElemMap, p.num_elems, single)?);
+    }
+    let mut node_map: Vec<i64> = node_ids.iter().flatten().copied().collect();
+    node_map.sort_unstable();
+    node_map.dedup();
+    let node_index: HashMap<i64, usize> = node_map
+        .iter()
+        .enumerate()
+        .map(|(index, &id)| (id, index))
+        .collect();
+    let node_targets: Vec<Vec<usize>> = node_ids
+        .iter()
+        .map(|ids| ids.iter().map(|id| node_index[id]).collect())
+        .collect();
+    let num_nodes = node_map.len();
+
+    // Element blocks, with each block's elements in global ID order
+    let block_ids = PartIds::new(&files, |file| file.block_ids(EntityType::ElemBlock))?;
+    let part_blocks = files
+        .iter()
+        .zip(&block_ids.parts)
+        .map(|(file, ids)| {
+            let mut offset = 0;
+            ids.iter()
+                .map(|&id| {
+                    let block = file.block(id)?;
+                    let start = offset;
+                    offset += block.num_entries;
+                    Ok((start, block))
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut elem_map = Vec::new();
+    let mut elem_targets: Vec<Vec<usize>> = params.iter().map(|p| vec![0; p.num_elems]).collect();
+    let mut blocks = Vec::with_capacity(block_ids.joined.len());
+    for &id in &block_ids.joined {
+        let mut entries = Vec::new();
+        let mut source: Option<(usize, &Block)> = None;
+        for (part, part_block_list) in part_blocks.iter().enumerate() {
+            let Some((offset, block)) = block_ids.position(part, id).map(|i| &part_block_list[i])
+            else {
+                continue;
+            };
+            if source.map_or(true, |(_, b)| b.num_entries == 0 && block.num_entries > 0) {
+                source = Some((part, block));
+            }
+            for local in 0..block.num_entries {
+                entries.push((elem_ids[part][offset + local], part, local));
+            }
+        }
+        entries.sort_unstable();
+
+        let start = elem_map.len();
+        let mut plan = EntityPlan::new(id, &block_ids, |part| {
+            block_ids
+                .position(part, id)
+                .map_or(0, |i| part_blocks[part][i].1.num_entries)
+        });
+        for (i, &(global_id, part, local)) in entries.iter().enumerate() {
+            if i == 0 || entries[i - 1].0 != global_id {
+                elem_map.push(global_id);
+                plan.len += 1;
+            }
+            plan.targets[part][local] = plan.len - 1;
+            let offset = part_blocks[part][block_ids.position(part, id).unwrap()].0;
+            elem_targets[part][offset + local] = start + plan.len - 1;
+        }
+
+        let (source, template) = source.expect("joined block ids come from the parts");
+        let block = Block {
+            num_entries: plan.len,
+            ..template.clone()
+        };
+        blocks.push((block, source, plan));
+    }
+    let num_elems = elem_map.len();
+
+    // Node sets, merged by global node
+    let node_set_ids = PartIds::new(&files, |file| file.set_ids(EntityType::NodeSet))?;
+    let mut node_sets = Vec::with_capacity(node_set_ids.joined.len());
+    for &id in &node_set_ids.joined {
+        let mut entries = Vec::new();
+        let mut parts_df = Vec::with_capacity(files.len());
+        for (part, file) in files.iter().enumerate() {
+            if node_set_ids.position(part, id).is_none() {
+                parts_df.push(Vec::new());
+                continue;
+            }
+            let set = file.node_set(id)?;
+            for (local, &node) in set.nodes.iter().enumerate() {
+                entries.push((
+                    local_target(&node_targets[part], node, "Node set", id)?,
+                    part,
+                    local,
+                ));
+            }
+            parts_df.push(set.dist_factors);
+        }
+        entries.sort_unstable();
+
+        let mut plan = EntityPlan::new(id, &node_set_ids, |part| {
+            entries.iter().filter(|entry| entry.1 == part).count()
+        });
+        let has_df = !entries.is_empty()
+            && entries
+                .iter()
+                .all(|&(_, part, _)| !parts_df[part].is_empty());
+        let mut nodes = Vec::new();
+        let mut df = Vec::new();
+        for (i, &(node, part, local)) in entries.iter().enumerate() {
+            if i == 0 || entries[i - 1].0 != node {
+                nodes.push(node as i64 + 1);
+                if has_df {
+                    df.push(parts_df[part][local]);
+                }
+                plan.len += 1;
+            }
+            plan.targets[part][local] = plan.len - 1;
+        }
+        node_sets.push((nodes, has_df.then_some(df), plan));
+    }
+
+    // Side sets, merged by global element and side
+    let side_set_ids = PartIds::new(&files, |file| file.set_ids(EntityType::SideSet))?;
+    let mut side_sets = Vec::with_capacity(side_set_ids.joined.len());
+    for &id in &side_set_ids.joined {
+        let mut entries = Vec::new();
+        let mut parts_df = Vec::with_capacity(files.len());
+        for (part, file) in files.iter().enumerate() {
+            if side_set_ids.position(part, id).is_none() {
+                parts_df.push((Vec::new(), 0));
+                continue;
+            }
+            let set = file.side_set(id)?;
+            for (local, (&elem, &side)) in set.elements.iter().zip(&set.sides).enumerate() {
+                let elem = local_target(&elem_targets[part], elem, "Side set", id)?;
+                entries.push((elem, side, part, local));
+            }
+            let per_side = match set.elements.len() {
+                0 => 0,
+                n if set.dist_factors.len() % n == 0 => set.dist_factors.len() / n,
+                _ => {
+                    return Err(ExodusError::UnsupportedFeature(format!(
+                    "Joining side set {} with a varying number of distribution factors per side",
+                    id
+                )))
+                }
+            };
+            parts_df.push((set.dist_factors, per_side));
+        }
+        entries.sort_unstable();
+
+        let mut plan = EntityPlan::new(id, &side_set_ids, |part| {
+            entries.iter().filter(|entry| entry.2 == part).count()
+        });
+        let has_df =
+            !entries.is_empty() && entries.iter().all(|&(_, _, part, _)| parts_df[part].1 > 0);
+        let mut elems = Vec::new();
+        let mut sides = Vec::new();
+        let mut df = Vec::new();
+        for (i, &(elem, side, part, local)) in entries.iter().enumerate() {
+            if i == 0 || (entries[i - 1].0, entries[i - 1].1) != (elem, side) {
+                elems.push(elem as i64 + 1);
+                sides.push(side);
+                if has_df {
+                    let (values, per_side) = &parts_df[part];
+                    df.extend_from_slice(&values[local * per_side..(local + 1) * per_side]);
+                }
+                plan.len += 1;
+            }
+            plan.targets[part][local] = plan.len - 1;
+        }
+        side_sets.push((elems, sides, has_df.then_some(df), plan));
+    }
+
+    check_global_counts(&files[0], files.len(), num_nodes, num_elems)?;
+
+    // Variable selection and time steps
+    let variables = select_variables(&files, options.variables.as_deref())?;
+    let times = files[0].times()?;
+    for (part, file) in files.iter().enumerate().skip(1) {
+        let count = file.num_time_steps()?;
+        if count != times.len() {
+            return Err(ExodusError::Other(format!(
+                "Part {} has {} time steps, expected {}",
+                part,
+                count,
+                times.len()
+            )));
+        }
+    }
+    let steps = match &options.steps {
+        Some(steps) => {
+            if let Some(&step) = steps.iter().find(|&&step| step >= times.len()) {
+                return Err(ExodusError::InvalidTimeStep(step));
+            }
+            steps.clone()
+        }
+        None => (0..times.len()).collect(),
+    };
+
+    // Write the joined mesh
+    let mut out = ExodusFile::create(output, options.create_options.clone())?;
+    out.init(&InitParams {
+        title: params[0].title.clone(),
+        num_dim,
+        num_nodes,
+        num_elems,
+        num_elem_blocks: blocks.len(),
+        num_node_sets: node_sets.len(),
+        num_side_sets: side_sets.len(),
+        ..Default::default()
+    })?;
+
+    let qa_records = files[0].qa_records()?;
+    if !qa_records.is_empty() {
+        out.put_qa_records(&qa_records)?;
+    }
+    let info_records = files[0].info_records()?;
+    if !info_records.is_empty() {
+        out.put_info_records(&info_records)?;
+    }
+
+    for (block, _, _) in &blocks {
+        out.put_block(block)?;
+    }
+
+    let mut coords = vec![vec![0.0; num_nodes]; num_dim];
+    for (file, targets) in files.iter().zip(&node_targets) {
+        if targets.is_empty() {
+            continue;
+        }
+        let local = file.coords::<f64>()?;
+        for (joined, values) in coords.iter_mut().zip([&local.x, &local.y, &local.z]) {
+            scatter(joined, targets, values, 1)?;
+        }
+    }
+    if num_nodes > 0 {
+        out.put_coords(
+            &coords[0],
+            coords.get(1).map(Vec::as_slice),
+            coords.get(2).map(Vec::as_slice),
+        )?;
+    }
+    let coord_names = files[0].coord_names()?;
+    if !coord_names.is_empty() {
+        let names: Vec<&str> = coord_names.iter().map(String::as_str).collect();
+        out.put_coord_names(&names)?;
+    }
+    out.put_id_map(EntityType::NodeMap, &node_map)?;
+    out.put_id_map(EntityType::ElemMap, &elem_map)?;
+
+    for (block, source, plan) in &blocks {
+        let npe = block.num_nodes_per_entry;
+        let nattr = block.num_attributes;
+        let mut connectivity = vec![0; block.num_entries * npe];
+        let mut attributes = vec![0.0; block.num_entries * nattr];
+        for (part, file) in files.iter().enumerate() {
+            let targets = &plan.targets[part];
+            if targets.is_empty() {
+                continue;
+            }
+            let local = file
+                .connectivity(block.id)?
+                .iter()
+                .map(|&node| {
+                    local_target(&node_targets[part], node, "Block", block.id).map(|n| n as i64 + 1)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            scatter(&mut connectivity, targets, &local, npe)?;
+            if nattr > 0 {
+                scatter(
+                    &mut attributes,
+                    targets,
+                    &file.block_attributes(block.id)?,
+                    nattr,
+                )?;
+            }
+        }
+        if !connectivity.is_empty() {
+            out.put_connectivity(block.id, &connectivity)?;
+        }
+        if nattr > 0 && block.num_entries > 0 {
+            out.put_block_attributes(block.id, &attributes)?;
+            let names = files[*source].block_attribute_names(block.id)?;
+            if !names.is_empty() {
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                out.put_block_attribute_names(block.id, &names)?;
+            }
+        }
+    }
+    for (nodes, df, plan) in &node_sets {
+        out.put_node_set(plan.id, nodes, df.as_deref())?;
+    }
+    for (elems, sides, df, plan) in &side_sets {
+        out.put_side_set(plan.id, elems, sides, df.as_deref())?;
+    }
+
+    // Variable definitions, with each truth table the union of the parts'
+    let block_plans: Vec<&EntityPlan> = blocks.iter().map(|(_, _, plan)| plan).collect();
+    let node_set_plans: Vec<&EntityPlan> = node_sets.iter().map(|(_, _, plan)| plan).collect();
+    let side_set_plans: Vec<&EntityPlan> = side_sets.iter().map(|(.., plan)| plan).collect();
+    let entity_vars = [
+        (EntityType::ElemBlock, &block_ids, block_plans),
+        (EntityType::NodeSet, &node_set_ids, node_set_plans),
+        (EntityType::SideSet, &side_set_ids, side_set_plans),
+    ];
+    for (entity_type, ids, _) in &entity_vars {
+        copy_names(&files, &mut out, *entity_type, ids)?;
+    }
+    for (var_type, selected) in &variables {
+        if !selected.is_empty() {
+            let names: Vec<&str> = selected.iter().map(|(_, name)| name.as_str()).collect();
+            out.define_variables(*var_type, &names)?;
+        }
+    }
+    let mut entity_tables = Vec::with_capacity(entity_vars.len());
+    for (var_type, ids, plans) in &entity_vars {
+        let part_tables = files
+            .iter()
+            .map(|file| file.truth_table(*var_type))
+            .collect::<Result<Vec<_>>>()?;
+        let selected = &variables[var_index(*var_type)].1;
+        let mut table = TruthTable::new(*var_type, plans.len(), selected.len());
+        for (e, plan) in plans.iter().enumerate() {
+            for (v, &(var, _)) in selected.iter().enumerate() {
+                let defined = (0..files.len()).any(|part| {
+                    ids.position(part, plan.id)
+                        .is_some_and(|index| part_tables[part].get(index, var))
+                });
+                table.set(e, v, defined);
+            }
+        }
+        if !selected.is_empty() && !plans.is_empty() {
+            out.put_truth_table(*var_type, &table)?;
+        }
+        entity_tables.push((part_tables, table));
+    }
+
+    // Transient data, one step at a time
+    for (k, &step) in steps.iter().enumerate() {
+        out.put_time(k, times[step])?;
+        for (v, &(var, _)) in variables[var_index(EntityType::Global)]
+            .1
+            .iter()
+            .enumerate()
+        {
+            let value = files[0].var(step, EntityType::Global, 0, var)?;
+            out.put_var(k, EntityType::Global, 0, v, &value)?;
+        }
+        for (v, &(var, _)) in variables[var_index(EntityType::Nodal)].1.iter().enumerate() {
+            let mut values = vec![0.0; num_nodes];
+            for (part, file) in files.iter().enumerate() {
+                if !node_targets[part].is_empty() {
+                    let local = file.var(step, EntityType::Nodal, 0, var)?;
+                    scatter(&mut values, &node_targets[part], &local, 1)?;
+                }
+            }
+            out.put_var(k, EntityType::Nodal, 0, v, &values)?;
+        }
+        for ((var_type, ids, plans), (part_tables, table)) in entity_vars.iter().zip(&entity_tables)
+        {
+            let selected = &variables[var_index(*var_type)].1;
+            for (e, plan) in plans.iter().enumerate() {
+                for (v, &(var, _)) in selected.iter().enumerate() {
+                    if !table.get(e, v) {
+                        continue;
+                    }
+                    let mut values = vec![0.0; plan.len];
+                    for (part, file) in files.iter().enumerate() {
+                        let defined = ids
+                            .position(part, plan.id)
+                            .is_some_and(|index| part_tables[part].get(index, var));
+                        if defined && !plan.targets[part].is_empty() {
+                            let local = file.var(step, *var_type, plan.id, var)?;
+                            scatter(&mut values, &plan.targets[part], &local, 1)?;
+                        }
+                    }
+                    out.put_var(k, *var_type, plan.id, v, &values)?;
+                }
+            }
+        }
+    }
+    out.sync()?;
+
+    Ok(JoinSummary {
+        num_parts: files.len(),
+        num_nodes,
+        num_elems,
+        num_time_steps: steps.len(),
+    })
+}
+
+/// IDs of one block or set type in every part, and their joined order
+struct PartIds {
+    /// Union of the part IDs in order of first appearance
+    joined: Vec<EntityId>,
+    /// IDs stored in each part, in storage order
+    parts: Vec<Vec<EntityId>>,
+    /// Storage index of each ID in each part
+    indices: Vec<HashMap<EntityId, usize>>,
+}
+
+impl PartIds {
+    fn new(
+        files: &[ExodusFile<mode::Read>],
+        ids: impl Fn(&ExodusFile<mode::Read>) -> Result<Vec<EntityId>>,
+    ) -> Result<Self> {
+        let parts = files.iter().map(ids).collect::<Result<Vec<_>>>()?;
+        let indices: Vec<HashMap<EntityId, usize>> = parts
+            .iter()
+            .map(|ids| ids.iter().enumerate().map(|(i, &id)| (id, i)).collect())
+            .collect();
+        let mut joined = Vec::new();
+        for id in parts.iter().flatten() {
+            if !joined.contains(id) {
+                joined.push(*id);
+            }
+        }
+        Ok(Self {
+            joined,
+            parts,
+            indices,
+        })
+    }
+
+    /// Storage index of `id` in `part`, if the part has the entity
+    fn position(&self, part: usize, id: EntityId) -> Option<usize> {
+        self.indices[part].get(&id).copied()
+    }
+}
+
+/// Placement of the entries of one joined block or set
+struct EntityPlan {
+    id: EntityId,
+    /// Number of entries in the joined entity
+    len: usize,
+    /// Joined position of each local entry, per part
+    targets: Vec<Vec<usize>>,
+}
+
+impl EntityPlan {
+    fn new(id: EntityId, ids: &PartIds, part_len: impl Fn(usize) -> usize) -> Self {
+        Self {
+            id,
+            len: 0,
+            targets: (0..ids.parts.len())
+                .map(|part| vec![0; part_len(part)])
+                .collect(),
+        }
+    }
+}
+
+/// Local-to-global ID map of a part, numbered sequentially for a lone file
+/// without maps
+fn local_ids(
+    file: &ExodusFile<mode::Read>,
+    map_type: EntityType,
+    count: usize,
+    single: bool,
+) -> Result<Vec<i64>> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    let ids = match file.id_map(map_type) {
+        Err(ExodusError::VariableNotDefined(_)) if single => (1..=count as i64).collect(),
+        result => result?,
+    };
+    if ids.len() != count {
+        return Err(ExodusError::InvalidArrayLength {
+            expected: count,
+            actual: ids.len(),
+        });
+    }
+    Ok(ids)
+}
+
+/// Joined 0-based position of a 1-based local entry
+fn local_target(targets: &[usize], local: i64, context: &str, id: EntityId) -> Result<usize> {
+    usize::try_from(local - 1)
+        .ok()
+        .and_then(|index| targets.get(index).copied())
+        .ok_or_else(|| {
+            ExodusError::Other(format!(
+                "{} {} references entry {} outside of 1..={}",
+                context,
+                id,
+                local,
+                targets.len()
+            ))
+        })
+}
+
+/// Copy `stride` values per local entry to their joined positions
+fn scatter<T: Copy>(joined: &mut [T], targets: &[usize], local: &[T], stride: usize) -> Result<()> {
+    if local.len() != targets.len() * stride {
+        return Err(ExodusError::InvalidArrayLength {
+            expected: targets.len() * stride,
+            actual: local.len(),
+        });
+    }
+    for (&target, values) in targets.iter().zip(local.chunks_exact(stride.max(1))) {
+        joined[target * stride..(target + 1) * stride].copy_from_slice(values);
+    }
+    Ok(())
+}
+
+/// Index and name of a variable selected for joining
+type SelectedVar = (usize, String);
+
+/// Position of `var_type` in [`VAR_TYPES`]
+fn var_index(var_type: EntityType) -> usize {
+    VAR_TYPES
+        .iter()
+        .position(|&t| t == var_type)
+        .expect("joined variable type")
+}
+
+/// Selected `(index, name)` pairs for each of [`VAR_TYPES`]
+fn select_variables(
+    files: &[ExodusFile<mode::Read>],
+    wanted: Option<&[String]>,
+) -> Result<Vec<(EntityType, Vec<SelectedVar>)>> {
+    let mut selection = Vec::with_capacity(VAR_TYPES.len());
+    for var_type in VAR_TYPES {
+        let names = files[0].variable_names(var_type)?;
+        for (part, file) in files.iter().enumerate().skip(1) {
+            if file.variable_names(var_type)? != names {
+                return Err(ExodusError::Other(format!(
+                    "Part {} has different {} variables than part 0",
+                    part, var_type
+                )));
+            }
+        }
+        let selected = names
+            .into_iter()
+            .enumerate()
+            .filter(|(_, name)| {
+                wanted.map_or(true, |wanted| {
+                    wanted.iter().any(|w| w.eq_ignore_ascii_case(name))
+                })
+            })
+            .collect();
+        selection.push((var_type, selected));
+    }
+
+    for name in wanted.unwrap_or_default() {
+        let found = selection
+            .iter()
+            .flat_map(|(_, selected)| selected)
+            .any(|(_, selected): &SelectedVar| selected.eq_ignore_ascii_case(name));
+        if !found {
+            return Err(ExodusError::VariableNotDefined(name.clone()));
+        }
+    }
+    Ok(selection)
+}
+
+/// Copy the names of the joined blocks or sets of a type
+fn copy_names(
+    files: &[ExodusFile<mode::Read>],
+    out: &mut ExodusFile<mode::Write>,
+    entity_type: EntityType,
+    ids: &PartIds,
+) -> Result<()> {
+    let mut names: HashMap<EntityId, String> = HashMap::new();
+    for (file, part_ids) in files.iter().zip(&ids.parts) {
+        let part_names = match file.names(entity_type) {
+            Err(ExodusError::VariableNotDefined(_)) => continue,
+            result => result?,
+        };
+        for (&id, name) in part_ids.iter().zip(part_names) {
+            if !name.is_empty() {
+                names.entry(id).or_insert(name);
+            }
+        }
+    }
+    if names.is_empty() {
+        return Ok(());
+    }
+    let joined: Vec<String> = ids
+        .joined
+        .iter()
+        .map(|id| names.remove(id).unwrap_or_default())
+        .collect();
+    out.put_names(entity_type, &joined)
+}
+
+/// Compare the joined sizes with the Nemesis metadata of the first part
+fn check_global_counts(
+    file: &ExodusFile<mode::Read>,
+    num_parts: usize,
+    num_nodes: usize,
+    num_elems: usize,
+) -> Result<()> {
+    if let Ok(info) = file.init_info() {
+        if info.file_type == NemesisFileType::Parallel && info.num_procs != num_parts {
+            return Err(ExodusError::Other(format!(
+                "Decomposition has {} processors but {} files were given",
+                info.num_procs, num_parts
+            )));
+        }
+    }
+    if let Ok(global) = file.init_global() {
+        if (global.num_nodes, global.num_elems) != (num_nodes, num_elems) {
+            return Err(ExodusError::Other(format!(
+                "Joined mesh has {} nodes and {} elements, but the decomposition has {} and {}",
+                num_nodes, num_elems, global.num_nodes, global.num_elems
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decomposed_file_name() {
+        assert_eq!(
+            decomposed_file_name("mesh.e", 4, 3),
+            PathBuf::from("mesh.e.4.3")
+        );
+        assert_eq!(
+            decomposed_file_name("out/mesh.e", 16, 3),
+            PathBuf::from("out/mesh.e.16.03")
+        );
+        assert_eq!(
+            decomposed_file_name("mesh.e", 100, 7),
+            PathBuf::from("mesh.e.100.007")
+        );
+    }
+
+    #[test]
+    fn test_scatter() {
+        let mut joined = vec![0; 6];
+        scatter(&mut joined, &[2, 0], &[5, 6, 1, 2], 2).unwrap();
+        assert_eq!(joined, vec![1, 2, 0, 0, 5, 6]);
+        assert!(scatter(&mut joined, &[1], &[1, 2, 3], 2).is_err());
+    }
+
+    #[test]
+    fn test_local_target() {
+        let targets = [4, 2, 7];
+        assert_eq!(local_target(&targets, 2, "block", 1).unwrap(), 2);
+        assert!(local_target(&targets, 0, "block", 1).is_err());
+        assert!(local_target(&targets, 4, "block", 1).is_err());
+    }
+}
@@ -12,7 +12,14 @@
 //! - **Type Safe**: Leverages Rust's type system for compile-time correctness
 //! - **Memory Safe**: No unsafe code in public API
 //! - **NetCDF Backend**: Built on the mature `netcdf` crate
+//! - **Native NetCDF-3 Reader**: Classic and 64-bit offset files can be read
+//!   without system libraries by disabling the `netcdf4` feature
+//! - **In-Memory Files**: Create, append to and read files held in memory with
+//!   `create_in_memory`, `open_from_bytes` and `into_bytes`
 //! - **Dual API**: Low-level C-compatible and high-level Rust idiomatic interfaces
+//! - **Serde**: The `serde` feature derives `Serialize` and `Deserialize` for
+//!   the public data types, and JSON metadata manifests can be written with
+//!   [`manifest::MeshManifest`]
 //!
 //! ## Quick Start
 //!
@@ -40,6 +47,7 @@
 //! - [`error`] - Error types and result aliases
 //! - [`types`] - Core type definitions (EntityType, InitParams, etc.)
 //! - [`file`] - File handle and mode types
+//! - [`backend`] - Storage backends, including the pure-Rust NetCDF-3 reader
 //! - [`performance`] - Performance tuning (cache, chunks, node detection)
 //! - [`init`] - Database initialization
 //! - [`coord`] - Coordinate operations
@@ -57,12 +65,14 @@
 //! - [`sideset_utils`] - Utilities for converting nodesets to sidesets
 //! - [`search`] - Spatial search for nodes and elements by location
 //! - [`transformations`] - Coordinate and field transformations (translate, rotate, scale)
+//! - [`raw`] - Low-level C-compatible API with integer handles
 
 #![deny(missing_docs)]
 #![deny(missing_debug_implementations)]
 #![warn(rust_2018_idioms)]
 
 // Public modules
+pub mod backend;
 pub mod builder;
 pub mod coord;
 pub mod error;
@@ -77,9 +87,14 @@ mod block;
 mod file;
 mod init;
 mod map;
+#[cfg(feature = "netcdf4")]
+mod memory;
+mod mesh;
 mod metadata;
+mod nemesis;
 mod set;
 mod time;
+#[cfg(feature = "netcdf4")]
 mod transform_ops;
 mod variable;
 
@@ -106,20 +121,53 @@ pub mod views;
 // Transformation utilities
 pub mod transformations;
 
+// Joining decomposed file-per-processor results
+#[cfg(feature = "netcdf4")]
+pub mod join;
+
+// Decomposing a serial mesh into file-per-processor parts
+pub mod decompose;
+
+// Comparing files within tolerances
+pub mod diff;
+
+// Metadata summaries of files, serializable to JSON
+pub mod manifest;
+
+// Exporting meshes and results to VTK
+pub mod vtk;
+
+// Converting between Gmsh meshes and Exodus
+pub mod gmsh;
+
+// Importing Abaqus input decks
+pub mod abaqus;
+
+// Importing Nastran bulk data
+pub mod nastran;
+
+// Exporting boundary surfaces to STL, OBJ and PLY
+pub mod surface;
+
 // Re-exports for convenience
 pub use attribute::AttributeData;
-pub use builder::{AppendBuilder, BlockBuilder, MeshBuilder, NodeSetBuilder, SideSetBuilder};
+pub use builder::{
+    AppendBuilder, BlockBuilder, ElemSetBuilder, MeshBuilder, NodeSetBuilder, SideSetBuilder,
+};
 pub use coord::{CoordValue, Coordinates};
 pub use error::{EntityId, ExodusError, Result};
 pub use file::ExodusFile;
 pub use performance::{CacheConfig, ChunkConfig, NodeType, PerformanceConfig};
 pub use search::SpatialSearchResult;
 pub use set::SetIterator;
+pub use time::TimeBracket;
 pub use types::{
-    Assembly, Attribute, AttributeType, Blob, Block, Compression, Connectivity,
-    ConnectivityIterator, CreateMode, CreateOptions, EntitySet, EntityType, FileFormat,
-    FileStorageFormat, FloatSize, InfoRecord, InitParams, Int64Mode, NodeSet, QaRecord, Set,
-    SideSet, Topology, TruthTable, VarStorageMode,
+    Assembly, Attribute, AttributeType, Blob, Block, CommMapParams, Compression, Connectivity,
+    ConnectivityIterator, CreateMode, CreateOptions, ElemCommMap, EntitySet, EntityType,
+    FileFormat, FileStorageFormat, FloatSize, GlobalEntityParams, GlobalInitParams, InfoRecord,
+    InitParams, Int64Mode, LoadBalanceParams, NemesisFileType, NemesisInitInfo, NodeCommMap,
+    NodeSet, ProcessorElemMaps, ProcessorNodeMaps, QaRecord, Set, SideSet, Topology, TruthTable,
+    VarStorageMode,
 };
 
 // File mode types
@@ -146,7 +194,7 @@ impl FileMode for mode::Write {}
 impl FileMode for mode::Append {}
 
 /// Trait for file modes that support write operations (Write and Append)
-pub trait WritableMode: FileMode {}
+pub trait WritableMode: FileMode + private::SealedWritable {}
 
 impl WritableMode for mode::Write {}
 impl WritableMode for mode::Append {}
@@ -158,10 +206,58 @@ impl ReadableMode for mode::Read {}
 impl ReadableMode for mode::Append {}
 
 mod private {
-    pub trait Sealed {}
-    impl Sealed for super::mode::Read {}
-    impl Sealed for super::mode::Write {}
-    impl Sealed for super::mode::Append {}
+    pub trait Sealed {
+        /// NetCDF handle type held by files in this mode
+        #[cfg(feature = "netcdf4")]
+        type Handle: std::ops::Deref<Target = netcdf::File>
+            + crate::backend::Backend
+            + std::fmt::Debug;
+
+        /// Storage handle type held by files in this mode
+        #[cfg(not(feature = "netcdf4"))]
+        type Handle: crate::backend::Backend + std::fmt::Debug;
+
+        /// Flush pending writes before the handle is closed
+        #[cfg(feature = "netcdf4")]
+        fn flush(_handle: &Self::Handle) {}
+    }
+
+    /// Modes that hold a writable NetCDF handle
+    #[cfg(feature = "netcdf4")]
+    pub trait SealedWritable: Sealed<Handle = netcdf::FileMut> {}
+    #[cfg(not(feature = "netcdf4"))]
+    pub trait SealedWritable: Sealed {}
+
+    impl Sealed for super::mode::Read {
+        #[cfg(feature = "netcdf4")]
+        type Handle = crate::file::ReadOnlyHandle;
+        #[cfg(not(feature = "netcdf4"))]
+        type Handle = crate::backend::ClassicFile;
+    }
+    impl Sealed for super::mode::Write {
+        #[cfg(feature = "netcdf4")]
+        type Handle = netcdf::FileMut;
+        #[cfg(not(feature = "netcdf4"))]
+        type Handle = crate::file::NoWriteHandle;
+
+        #[cfg(feature = "netcdf4")]
+        fn flush(handle: &Self::Handle) {
+            let _ = handle.sync();
+        }
+    }
+    impl Sealed for super::mode::Append {
+        #[cfg(feature = "netcdf4")]
+        type Handle = netcdf::FileMut;
+        #[cfg(not(feature = "netcdf4"))]
+        type Handle = crate::file::NoWriteHandle;
+
+        #[cfg(feature = "netcdf4")]
+        fn flush(handle: &Self::Handle) {
+            let _ = handle.sync();
+        }
+    }
+    impl SealedWritable for super::mode::Write {}
+    impl SealedWritable for super::mode::Append {}
 }
 
 /// Type alias for read-only Exodus files
@@ -194,7 +290,12 @@ mod tests {
     fn test_create_options_default() {
         let opts = CreateOptions::default();
         assert_eq!(opts.mode, CreateMode::NoClobber);
+        assert_eq!(opts.format, FileFormat::NetCdf4);
         assert_eq!(opts.float_size, FloatSize::Float64);
         assert_eq!(opts.int64_mode, Int64Mode::Int64);
     }
 }
+
+// Exporting results to XDMF with HDF5 heavy data
+#[cfg(feature = "netcdf4")]
+pub mod xdmf;
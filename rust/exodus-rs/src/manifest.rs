@@ -0,0 +1,298 @@
+//! Mesh metadata manifests.
+//!
+//! A [`MeshManifest`] summarizes a file without its bulk data: the
+//! initialization parameters, the element blocks and sets with their names
+//! and sizes, the variable names, the time values, and the QA and info
+//! records. With the `serde` feature a manifest can be written to and read
+//! from JSON, e.g. to keep file summaries in a database.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use exodus_rs::manifest::MeshManifest;
+//! use exodus_rs::{mode, ExodusFile};
+//!
+//! let file = ExodusFile::<mode::Read>::open("results.e")?;
+//! let manifest = MeshManifest::from_file(&file)?;
+//! manifest.write_json("results.json")?;
+//!
+//! let loaded = MeshManifest::read_json("results.json")?;
+//! assert_eq!(loaded.params.num_nodes, manifest.params.num_nodes);
+//! # Ok::<(), exodus_rs::ExodusError>(())
+//! ```
+
+use crate::error::{ExodusError, Result};
+use crate::types::{Block, EntityType, InitParams, QaRecord, Set};
+use crate::{mode, ExodusFile};
+#[cfg(feature = "serde")]
+use std::path::Path;
+
+/// Set types listed in a manifest
+const SET_TYPES: [EntityType; 5] = [
+    EntityType::NodeSet,
+    EntityType::SideSet,
+    EntityType::ElemSet,
+    EntityType::EdgeSet,
+    EntityType::FaceSet,
+];
+
+/// Variable types listed in a manifest
+const VAR_TYPES: [EntityType; 10] = [
+    EntityType::Global,
+    EntityType::Nodal,
+    EntityType::ElemBlock,
+    EntityType::EdgeBlock,
+    EntityType::FaceBlock,
+    EntityType::NodeSet,
+    EntityType::SideSet,
+    EntityType::ElemSet,
+    EntityType::EdgeSet,
+    EntityType::FaceSet,
+];
+
+/// Summary of a file's metadata
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeshManifest {
+    /// Title and entity counts
+    pub params: InitParams,
+    /// Element blocks in storage order
+    pub blocks: Vec<BlockManifest>,
+    /// Node, side, element, edge and face sets, by type and then storage order
+    pub sets: Vec<SetManifest>,
+    /// Variable names of each type that has variables
+    pub variables: Vec<VariableManifest>,
+    /// Time value of every step
+    pub times: Vec<f64>,
+    /// QA records
+    pub qa_records: Vec<QaRecord>,
+    /// Info records
+    pub info_records: Vec<String>,
+}
+
+/// Element block entry of a [`MeshManifest`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockManifest {
+    /// Block name (empty if unnamed)
+    pub name: String,
+    /// Block parameters
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub block: Block,
+    /// Names of the block attributes
+    pub attribute_names: Vec<String>,
+}
+
+/// Set entry of a [`MeshManifest`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetManifest {
+    /// Set name (empty if unnamed)
+    pub name: String,
+    /// Set parameters
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub set: Set,
+}
+
+/// Variable names of one type in a [`MeshManifest`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariableManifest {
+    /// Variable type (global, nodal, or a block or set type)
+    pub var_type: EntityType,
+    /// Variable names in storage order
+    pub names: Vec<String>,
+}
+
+impl MeshManifest {
+    /// Summarize an open file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read
+    pub fn from_file(file: &ExodusFile<mode::Read>) -> Result<Self> {
+        let params = file.init_params()?;
+
+        let block_ids = file.block_ids(EntityType::ElemBlock)?;
+        let block_names = optional_names(file, EntityType::ElemBlock)?;
+        let mut blocks = Vec::with_capacity(block_ids.len());
+        for (index, &id) in block_ids.iter().enumerate() {
+            blocks.push(BlockManifest {
+                name: block_names.get(index).cloned().unwrap_or_default(),
+                block: file.block(id)?,
+                attribute_names: file.block_attribute_names(id)?,
+            });
+        }
+
+        let mut sets = Vec::new();
+        for set_type in SET_TYPES {
+            let names = optional_names(file, set_type)?;
+            for (index, id) in file.set_ids(set_type)?.into_iter().enumerate() {
+                sets.push(SetManifest {
+                    name: names.get(index).cloned().unwrap_or_default(),
+                    set: file.set(set_type, id)?,
+                });
+            }
+        }
+
+        let mut variables = Vec::new();
+        for var_type in VAR_TYPES {
+            let names = file.variable_names(var_type)?;
+            if !names.is_empty() {
+                variables.push(VariableManifest { var_type, names });
+            }
+        }
+
+        Ok(Self {
+            params,
+            blocks,
+            sets,
+            variables,
+            times: file.times()?,
+            qa_records: file.qa_records()?,
+            info_records: file.info_records()?,
+        })
+    }
+
+    /// Variable names of one type, empty if it has none
+    pub fn variable_names(&self, var_type: EntityType) -> &[String] {
+        self.variables
+            .iter()
+            .find(|variables| variables.var_type == var_type)
+            .map_or(&[], |variables| variables.names.as_slice())
+    }
+
+    /// Serialize to pretty-printed JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Deserialize from JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not a valid manifest
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Write as JSON to `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or writing fails
+    #[cfg(feature = "serde")]
+    pub fn write_json(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Read from a JSON file at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid manifest
+    #[cfg(feature = "serde")]
+    pub fn read_json(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+}
+
+/// Names of the blocks or sets of a type, empty if the file stores none
+fn optional_names(file: &ExodusFile<mode::Read>, entity_type: EntityType) -> Result<Vec<String>> {
+    match file.names(entity_type) {
+        Err(ExodusError::VariableNotDefined(_)) => Ok(Vec::new()),
+        result => result,
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::types::Topology;
+
+    fn manifest() -> MeshManifest {
+        MeshManifest {
+            params: InitParams {
+                title: "Manifest".into(),
+                num_dim: 2,
+                num_nodes: 4,
+                num_elems: 1,
+                num_elem_blocks: 1,
+                num_node_sets: 1,
+                ..Default::default()
+            },
+            blocks: vec![BlockManifest {
+                name: "plate".into(),
+                block: Block {
+                    id: 10,
+                    entity_type: EntityType::ElemBlock,
+                    topology: "QUAD4".into(),
+                    num_entries: 1,
+                    num_nodes_per_entry: 4,
+                    num_edges_per_entry: 0,
+                    num_faces_per_entry: 0,
+                    num_attributes: 1,
+                },
+                attribute_names: vec!["thickness".into()],
+            }],
+            sets: vec![SetManifest {
+                name: String::new(),
+                set: Set {
+                    id: 1,
+                    entity_type: EntityType::NodeSet,
+                    num_entries: 2,
+                    num_dist_factors: 0,
+                },
+            }],
+            variables: vec![VariableManifest {
+                var_type: EntityType::Nodal,
+                names: vec!["temp".into()],
+            }],
+            times: vec![0.0, 0.5],
+            qa_records: vec![QaRecord {
+                code_name: "exodus-rs".into(),
+                code_version: "0.1.0".into(),
+                date: "2024-01-01".into(),
+                time: "12:00:00".into(),
+            }],
+            info_records: vec!["info".into()],
+        }
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let json = manifest().to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        // Entity types use their Exodus names and block fields are inlined
+        assert_eq!(value["blocks"][0]["entity_type"], "elem_block");
+        assert_eq!(value["blocks"][0]["topology"], "QUAD4");
+        assert_eq!(value["sets"][0]["num_entries"], 2);
+        assert_eq!(value["variables"][0]["var_type"], "nodal");
+
+        let loaded = MeshManifest::from_json(&json).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), json);
+        assert_eq!(loaded.blocks[0].block.id, 10);
+        assert_eq!(loaded.variable_names(EntityType::Nodal), ["temp"]);
+        assert!(loaded.variable_names(EntityType::Global).is_empty());
+        assert!(matches!(
+            MeshManifest::from_json("{\"params\": 1}"),
+            Err(ExodusError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_topology_serializes_by_name() {
+        let json =
+            serde_json::to_string(&[Topology::Hex8, Topology::Custom("SHELL3".into())]).unwrap();
+        assert_eq!(json, r#"["HEX8","SHELL3"]"#);
+        let topologies: Vec<Topology> = serde_json::from_str(r#"["tetra10","beam2"]"#).unwrap();
+        assert_eq!(topologies, vec![Topology::Tet10, Topology::Bar2]);
+    }
+}
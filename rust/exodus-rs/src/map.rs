@@ -6,9 +6,13 @@
 //! - Entity naming
 //! - Property arrays
 
+use crate::backend::Backend;
 use crate::error::{ExodusError, Result};
 use crate::types::EntityType;
+#[cfg(feature = "netcdf4")]
 use crate::utils::constants::*;
+#[cfg(feature = "netcdf4")]
+use crate::utils::netcdf_ext::{to_nc_chars, NcChar};
 use crate::{mode, ExodusFile, FileMode};
 
 // ============================================================================
@@ -32,6 +36,7 @@ impl<M: FileMode> ExodusFile<M> {
     }
 
     /// Get the NetCDF dimension name for an entity ID map
+    #[cfg(feature = "netcdf4")]
     fn id_map_dim_name(entity_type: EntityType) -> Result<&'static str> {
         match entity_type {
             EntityType::NodeMap | EntityType::Nodal => Ok(DIM_NUM_NODES),
@@ -92,20 +97,25 @@ impl ExodusFile<mode::Write> {
                 actual: map.len(),
             });
         }
+        self.metadata
+            .check_int_range(&format!("{} ID map", entity_type), map)?;
 
         // Create the variable if it doesn't exist
         if self.nc_file.variable(var_name).is_none() {
+            self.ensure_define_mode()?;
+
             // Verify dimension exists
             self.nc_file
                 .dimension(dim_name)
                 .ok_or_else(|| ExodusError::Other(format!("Dimension {} not found", dim_name)))?;
 
             self.nc_file
-                .add_variable::<i64>(var_name, &[dim_name])
+                .add_variable_with_type(var_name, &[dim_name], &self.metadata.int_type())
                 .map_err(ExodusError::NetCdf)?;
         }
 
         // Write the map
+        self.ensure_data_mode()?;
         let mut var = self
             .nc_file
             .variable_mut(var_name)
@@ -157,20 +167,24 @@ impl ExodusFile<mode::Write> {
                 actual: order.len(),
             });
         }
+        self.metadata.check_int_range("element order map", order)?;
 
         // Create the variable if it doesn't exist
         if self.nc_file.variable(var_name).is_none() {
+            self.ensure_define_mode()?;
+
             // Verify dimension exists
             self.nc_file
                 .dimension(dim_name)
                 .ok_or_else(|| ExodusError::Other(format!("Dimension {} not found", dim_name)))?;
 
             self.nc_file
-                .add_variable::<i64>(var_name, &[dim_name])
+                .add_variable_with_type(var_name, &[dim_name], &self.metadata.int_type())
                 .map_err(ExodusError::NetCdf)?;
         }
 
         // Write the order map
+        self.ensure_data_mode()?;
         let mut var = self
             .nc_file
             .variable_mut(var_name)
@@ -182,7 +196,6 @@ impl ExodusFile<mode::Write> {
     }
 }
 
-#[cfg(feature = "netcdf4")]
 impl ExodusFile<mode::Read> {
     /// Get entity ID map
     ///
@@ -208,16 +221,8 @@ impl ExodusFile<mode::Read> {
     pub fn id_map(&self, entity_type: EntityType) -> Result<Vec<i64>> {
         let var_name = Self::id_map_var_name(entity_type)?;
 
-        // Check if the variable exists
-        let var = self
-            .nc_file
-            .variable(var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.to_string()))?;
-
-        // Read the map
-        let map: Vec<i64> = var.get_values(..).map_err(ExodusError::NetCdf)?;
-
-        Ok(map)
+        // Read the map (fails if the variable does not exist)
+        self.nc_file.read_all_i64(var_name)
     }
 
     /// Get element order map
@@ -237,18 +242,8 @@ impl ExodusFile<mode::Read> {
     /// # Ok::<(), ExodusError>(())
     /// ```
     pub fn elem_order_map(&self) -> Result<Vec<i64>> {
-        let var_name = "elem_order_map";
-
-        // Check if the variable exists
-        let var = self
-            .nc_file
-            .variable(var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.to_string()))?;
-
-        // Read the order map
-        let order: Vec<i64> = var.get_values(..).map_err(ExodusError::NetCdf)?;
-
-        Ok(order)
+        // Read the order map (fails if the variable does not exist)
+        self.nc_file.read_all_i64("elem_order_map")
     }
 }
 
@@ -280,6 +275,7 @@ impl<M: FileMode> ExodusFile<M> {
     }
 
     /// Get the NetCDF dimension name for entity count
+    #[cfg(feature = "netcdf4")]
     fn entity_count_dim_name(entity_type: EntityType) -> Result<&'static str> {
         match entity_type {
             EntityType::NodeSet => Ok("num_node_sets"),
@@ -362,7 +358,7 @@ impl ExodusFile<mode::Write> {
             return Err(ExodusError::Other(format!(
                 "Entity index {} out of bounds (max {})",
                 entity_index,
-                names.len() - 1
+                names.len().saturating_sub(1)
             )));
         }
 
@@ -426,6 +422,7 @@ impl ExodusFile<mode::Write> {
 
         // Create dimensions if they don't exist
         if self.nc_file.dimension(DIM_LEN_NAME).is_none() {
+            self.ensure_define_mode()?;
             self.nc_file
                 .add_dimension(DIM_LEN_NAME, MAX_NAME_LENGTH + 1)
                 .map_err(ExodusError::NetCdf)?;
@@ -433,6 +430,8 @@ impl ExodusFile<mode::Write> {
 
         // Create the variable if it doesn't exist
         if self.nc_file.variable(&var_name).is_none() {
+            self.ensure_define_mode()?;
+
             // Verify dimensions exist
             self.nc_file
                 .dimension(dim_name)
@@ -442,7 +441,7 @@ impl ExodusFile<mode::Write> {
                 .ok_or_else(|| ExodusError::Other("Dimension len_name not found".to_string()))?;
 
             self.nc_file
-                .add_variable::<u8>(&var_name, &[dim_name, DIM_LEN_NAME])
+                .add_variable::<NcChar>(&var_name, &[dim_name, DIM_LEN_NAME])
                 .map_err(ExodusError::NetCdf)?;
         }
 
@@ -456,12 +455,13 @@ impl ExodusFile<mode::Write> {
         }
 
         // Write the names
+        self.ensure_data_mode()?;
         let mut var = self
             .nc_file
             .variable_mut(&var_name)
             .ok_or_else(|| ExodusError::VariableNotDefined(var_name.clone()))?;
 
-        var.put_values(&name_bytes, ..)
+        var.put_values(&to_nc_chars(&name_bytes), ..)
             .map_err(ExodusError::NetCdf)?;
 
         Ok(())
@@ -472,55 +472,15 @@ impl ExodusFile<mode::Write> {
         let var_name = Self::names_var_name(entity_type)?;
 
         // Check if the variable exists
-        let var = self
-            .nc_file
-            .variable(&var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.clone()))?;
-
-        // Support both classic NetCDF fixed-length char arrays (NC_CHAR) and
-        // NetCDF-4 NC_STRING variables for name storage.
-        let dims = var.dimensions();
-
-        // If 1D, likely NC_STRING [num_names]
-        if dims.len() == 1 {
-            let num_names = dims[0].len();
-            let mut names = Vec::with_capacity(num_names);
-            for i in 0..num_names {
-                let s = var.get_string(i..i + 1).map_err(ExodusError::NetCdf)?;
-                names.push(s.trim_end_matches('\0').trim().to_string());
-            }
-            return Ok(names);
-        }
-
-        // Otherwise, expect 2D [num_names, len_name] of NC_CHAR
-        let num_names = dims.first().map(|d| d.len()).unwrap_or(0);
-        let len_name = dims.get(1).map(|d| d.len()).unwrap_or(MAX_NAME_LENGTH + 1);
-
-        let mut names = Vec::with_capacity(num_names);
-
-        // Read raw bytes directly (more reliable for 2D char arrays)
-        let raw_bytes: Vec<u8> = var.get_raw_values(..).map_err(ExodusError::NetCdf)?;
-
-        // Read each name (NC_CHAR stored as i8 in older files)
-        for i in 0..num_names {
-            let start = i * len_name;
-            let end_idx = start + len_name;
-            let name_bytes = &raw_bytes[start..end_idx];
-
-            // Find the null terminator or end of string
-            let end = name_bytes
-                .iter()
-                .position(|&b| b == 0)
-                .unwrap_or(name_bytes.len());
-            let name = String::from_utf8_lossy(&name_bytes[..end]).to_string();
-            names.push(name);
+        if !self.nc_file.has_variable(&var_name) {
+            return Err(ExodusError::VariableNotDefined(var_name));
         }
 
-        Ok(names)
+        // Names are fixed-length char arrays, or NC_STRING in NetCDF-4 files
+        self.nc_file.read_strings(&var_name)
     }
 }
 
-#[cfg(feature = "netcdf4")]
 impl ExodusFile<mode::Read> {
     /// Get name for a single entity
     ///
@@ -548,7 +508,7 @@ impl ExodusFile<mode::Read> {
             ExodusError::Other(format!(
                 "Entity index {} out of bounds (max {})",
                 entity_index,
-                names.len() - 1
+                names.len().saturating_sub(1)
             ))
         })
     }
@@ -575,51 +535,12 @@ impl ExodusFile<mode::Read> {
         let var_name = Self::names_var_name(entity_type)?;
 
         // Check if the variable exists
-        let var = self
-            .nc_file
-            .variable(&var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.clone()))?;
-
-        // Support both classic NetCDF fixed-length char arrays (NC_CHAR) and
-        // NetCDF-4 NC_STRING variables for name storage.
-        let dims = var.dimensions();
-
-        // If 1D, likely NC_STRING [num_names]
-        if dims.len() == 1 {
-            let num_names = dims[0].len();
-            let mut names = Vec::with_capacity(num_names);
-            for i in 0..num_names {
-                let s = var.get_string(i..i + 1).map_err(ExodusError::NetCdf)?;
-                names.push(s.trim_end_matches('\0').trim().to_string());
-            }
-            return Ok(names);
-        }
-
-        // Otherwise, expect 2D [num_names, len_name] of NC_CHAR
-        let num_names = dims.first().map(|d| d.len()).unwrap_or(0);
-        let len_name = dims.get(1).map(|d| d.len()).unwrap_or(MAX_NAME_LENGTH + 1);
-
-        let mut names = Vec::with_capacity(num_names);
-
-        // Read raw bytes directly (more reliable for 2D char arrays)
-        let raw_bytes: Vec<u8> = var.get_raw_values(..).map_err(ExodusError::NetCdf)?;
-
-        // Read each name (NC_CHAR stored as i8 in older files)
-        for i in 0..num_names {
-            let start = i * len_name;
-            let end_idx = start + len_name;
-            let name_bytes = &raw_bytes[start..end_idx];
-
-            // Find the null terminator or end of string
-            let end = name_bytes
-                .iter()
-                .position(|&b| b == 0)
-                .unwrap_or(name_bytes.len());
-            let name = String::from_utf8_lossy(&name_bytes[..end]).to_string();
-            names.push(name);
+        if !self.nc_file.has_variable(&var_name) {
+            return Err(ExodusError::VariableNotDefined(var_name));
         }
 
-        Ok(names)
+        // Names are fixed-length char arrays, or NC_STRING in NetCDF-4 files
+        self.nc_file.read_strings(&var_name)
     }
 }
 
@@ -676,13 +597,7 @@ impl ExodusFile<mode::Write> {
         };
 
         // Find the entity index from ID
-        let ids = self.entity_ids_internal(entity_type)?;
-        let index = ids.iter().position(|&id| id == entity_id).ok_or_else(|| {
-            ExodusError::EntityNotFound {
-                entity_type: entity_type.to_string(),
-                id: entity_id,
-            }
-        })?;
+        let index = self.entity_index(entity_type, entity_id)?;
 
         props[index] = value;
         self.put_property_array(entity_type, prop_name, &props)?;
@@ -733,9 +648,13 @@ impl ExodusFile<mode::Write> {
                 actual: values.len(),
             });
         }
+        self.metadata
+            .check_int_range(&format!("property {}", prop_name), values)?;
 
         // Create the variable if it doesn't exist
         if self.nc_file.variable(&var_name).is_none() {
+            self.ensure_define_mode()?;
+
             // Verify dimension exists
             self.nc_file
                 .dimension(dim_name)
@@ -743,7 +662,7 @@ impl ExodusFile<mode::Write> {
 
             let mut var = self
                 .nc_file
-                .add_variable::<i64>(&var_name, &[dim_name])
+                .add_variable_with_type(&var_name, &[dim_name], &self.metadata.int_type())
                 .map_err(ExodusError::NetCdf)?;
 
             // Set the name attribute
@@ -752,6 +671,7 @@ impl ExodusFile<mode::Write> {
         }
 
         // Write the properties
+        self.ensure_data_mode()?;
         let mut var = self
             .nc_file
             .variable_mut(&var_name)
@@ -796,47 +716,10 @@ impl ExodusFile<mode::Write> {
     ) -> Result<Vec<i64>> {
         let var_name = self.property_var_name(entity_type, prop_name)?;
 
-        let var = self
-            .nc_file
-            .variable(&var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.clone()))?;
-
-        let props: Vec<i64> = var.get_values(..).map_err(ExodusError::NetCdf)?;
-
-        Ok(props)
-    }
-
-    /// Internal helper to get entity IDs
-    fn entity_ids_internal(&self, entity_type: EntityType) -> Result<Vec<i64>> {
-        let var_name = match entity_type {
-            EntityType::ElemBlock => "eb_prop1",
-            EntityType::EdgeBlock => "ed_prop1",
-            EntityType::FaceBlock => "fa_prop1",
-            EntityType::NodeSet => "ns_prop1",
-            EntityType::SideSet => "ss_prop1",
-            EntityType::ElemSet => "els_prop1",
-            EntityType::EdgeSet => "es_prop1",
-            EntityType::FaceSet => "fs_prop1",
-            _ => {
-                return Err(ExodusError::InvalidEntityType(format!(
-                    "Cannot get IDs for entity type {:?}",
-                    entity_type
-                )))
-            }
-        };
-
-        // Try to get the variable
-        if let Some(var) = self.nc_file.variable(var_name) {
-            let ids: Vec<i64> = var.get_values(..).map_err(ExodusError::NetCdf)?;
-            // Filter out zeros and NetCDF fill values
-            Ok(ids.into_iter().filter(|&id| id > 0).collect())
-        } else {
-            Ok(Vec::new())
-        }
+        self.nc_file.read_all_i64(&var_name)
     }
 }
 
-#[cfg(feature = "netcdf4")]
 impl ExodusFile<mode::Read> {
     /// Get property value for a single entity
     ///
@@ -866,14 +749,8 @@ impl ExodusFile<mode::Read> {
     ) -> Result<i64> {
         let props = self.property_array(entity_type, prop_name)?;
 
-        // Get entity IDs to find the index
-        let ids = self.entity_ids_internal(entity_type)?;
-        let index = ids.iter().position(|&id| id == entity_id).ok_or_else(|| {
-            ExodusError::EntityNotFound {
-                entity_type: entity_type.to_string(),
-                id: entity_id,
-            }
-        })?;
+        // Find the entity index from ID
+        let index = self.entity_index(entity_type, entity_id)?;
 
         props
             .get(index)
@@ -911,14 +788,7 @@ impl ExodusFile<mode::Read> {
         let prop_name = prop_name.as_ref();
         let var_name = self.property_var_name(entity_type, prop_name)?;
 
-        let var = self
-            .nc_file
-            .variable(&var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.clone()))?;
-
-        let props: Vec<i64> = var.get_values(..).map_err(ExodusError::NetCdf)?;
-
-        Ok(props)
+        self.nc_file.read_all_i64(&var_name)
     }
 
     /// Get all property names for an entity type
@@ -959,12 +829,10 @@ impl ExodusFile<mode::Read> {
 
         // Find all variables with the matching prefix
         let mut prop_names = Vec::new();
-        for var in self.nc_file.variables() {
-            if var.name().starts_with(prefix) {
-                // Extract property name from variable name
-                if let Some(name) = var.name().strip_prefix(&format!("{}_", prefix)) {
-                    prop_names.push(name.to_string());
-                }
+        for var_name in self.nc_file.variable_names() {
+            // Extract property name from variable name
+            if let Some(name) = var_name.strip_prefix(&format!("{}_", prefix)) {
+                prop_names.push(name.to_string());
             }
         }
 
@@ -996,35 +864,6 @@ impl ExodusFile<mode::Read> {
             prop_name.to_lowercase().replace(' ', "_")
         ))
     }
-
-    /// Internal helper to get entity IDs
-    fn entity_ids_internal(&self, entity_type: EntityType) -> Result<Vec<i64>> {
-        let var_name = match entity_type {
-            EntityType::ElemBlock => "eb_prop1",
-            EntityType::EdgeBlock => "ed_prop1",
-            EntityType::FaceBlock => "fa_prop1",
-            EntityType::NodeSet => "ns_prop1",
-            EntityType::SideSet => "ss_prop1",
-            EntityType::ElemSet => "els_prop1",
-            EntityType::EdgeSet => "es_prop1",
-            EntityType::FaceSet => "fs_prop1",
-            _ => {
-                return Err(ExodusError::InvalidEntityType(format!(
-                    "Cannot get IDs for entity type {:?}",
-                    entity_type
-                )))
-            }
-        };
-
-        // Try to get the variable
-        if let Some(var) = self.nc_file.variable(var_name) {
-            let ids: Vec<i64> = var.get_values(..).map_err(ExodusError::NetCdf)?;
-            // Filter out zeros and NetCDF fill values
-            Ok(ids.into_iter().filter(|&id| id > 0).collect())
-        } else {
-            Ok(Vec::new())
-        }
-    }
 }
 
 // ============================================================================
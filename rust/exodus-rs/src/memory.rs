@@ -0,0 +1,166 @@
+//! In-memory Exodus files
+//!
+//! [`ExodusFile::create_in_memory`] and `open_from_bytes` keep the whole file
+//! in memory using the NetCDF in-memory API (`NC_INMEMORY`), so meshes can be
+//! built and handed to the next stage of a pipeline without temporary files.
+//! [`ExodusFile::into_bytes`] returns the finished file, ready to be persisted
+//! or streamed.
+//!
+//! Read-only files are opened on the caller's buffer with `nc_open_mem`, which
+//! reads it in place. Writable files live in memory owned by the NetCDF
+//! library: `nc_create_mem` starts an empty one and `nc_open_memio` a copy of
+//! the caller's buffer, and `nc_close_memio` hands back the final contents.
+//! Nothing is written to disk at any point.
+//!
+//! Without the `netcdf4` feature, `ExodusFile::<mode::Read>::open_from_bytes`
+//! parses NetCDF-3 buffers directly with [`ClassicFile`](crate::backend::ClassicFile).
+
+use crate::error::Result;
+use crate::types::{CreateMode, CreateOptions};
+use crate::utils::nc_sys;
+use crate::{mode, ExodusFile, FileMode};
+use std::path::PathBuf;
+
+/// Memory behind an in-memory file
+#[derive(Debug)]
+pub(crate) enum MemoryImage {
+    /// Buffer a read-only file was opened on; NetCDF reads it in place, so it
+    /// must outlive the handle
+    Buffer(Vec<u8>),
+    /// Memory owned by the NetCDF library, returned by `nc_close_memio`
+    Library,
+}
+
+impl ExodusFile<mode::Write> {
+    /// Create a new Exodus file held entirely in memory
+    ///
+    /// Supports the full write API. Use [`into_bytes`](ExodusFile::into_bytes)
+    /// to retrieve the finished file. `options.mode` is ignored since there is
+    /// no existing file to protect.
+    ///
+    /// # Errors
+    ///
+    /// - Invalid option combinations (as for [`create`](ExodusFile::create))
+    /// - NetCDF library errors
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use exodus_rs::{ExodusFile, CreateOptions, InitParams};
+    ///
+    /// let mut file = ExodusFile::create_in_memory(CreateOptions::default())?;
+    /// file.init(&InitParams { num_dim: 2, num_nodes: 4, ..Default::default() })?;
+    /// file.put_coords(&[0.0, 1.0, 1.0, 0.0], Some(&[0.0, 0.0, 1.0, 1.0]), None)?;
+    /// let bytes = file.into_bytes()?;
+    /// # Ok::<(), exodus_rs::ExodusError>(())
+    /// ```
+    pub fn create_in_memory(mut options: CreateOptions) -> Result<Self> {
+        options.mode = CreateMode::Clobber;
+        let mut file = Self::create_with(PathBuf::new(), options, |nc_options| {
+            nc_sys::create_mem(nc_options.bits())
+        })?;
+        file.memory = Some(MemoryImage::Library);
+        Ok(file)
+    }
+}
+
+impl ExodusFile<mode::Read> {
+    /// Open an Exodus file held in memory for reading
+    ///
+    /// `bytes` holds a complete file in any NetCDF format, e.g. the result of
+    /// [`into_bytes`](ExodusFile::into_bytes) or a file read from disk. NetCDF
+    /// reads the buffer in place; it is returned unchanged by `into_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// - The buffer is not a valid NetCDF/Exodus file
+    /// - NetCDF library errors
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use exodus_rs::{mode, ExodusFile};
+    ///
+    /// let bytes = std::fs::read("mesh.exo")?;
+    /// let file = ExodusFile::<mode::Read>::open_from_bytes(bytes)?;
+    /// let params = file.init_params()?;
+    /// # Ok::<(), exodus_rs::ExodusError>(())
+    /// ```
+    pub fn open_from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        // SAFETY: the buffer moves into `file.memory`, which keeps its heap
+        // allocation in place and is dropped after the handle.
+        let nc_file = unsafe { nc_sys::open_mem(&bytes)? };
+        let mut file = Self::from_handle(nc_file, PathBuf::new())?;
+        file.memory = Some(MemoryImage::Buffer(bytes));
+        Ok(file)
+    }
+}
+
+impl ExodusFile<mode::Append> {
+    /// Open an Exodus file held in memory for appending
+    ///
+    /// Supports the full append API. NetCDF works on its own copy of `bytes`;
+    /// the modified file is returned by [`into_bytes`](ExodusFile::into_bytes).
+    ///
+    /// # Errors
+    ///
+    /// - The buffer is not a valid NetCDF/Exodus file
+    /// - NetCDF library errors
+    pub fn open_from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        let mut file = Self::from_handle(nc_sys::open_memio(&bytes)?, PathBuf::new())?;
+        file.memory = Some(MemoryImage::Library);
+        Ok(file)
+    }
+}
+
+impl<M: FileMode> ExodusFile<M> {
+    /// Whether the file is held in memory rather than on disk
+    ///
+    /// In-memory files have an empty [`path`](ExodusFile::path).
+    pub fn is_in_memory(&self) -> bool {
+        self.memory.is_some()
+    }
+
+    /// Close the file and return its complete contents
+    ///
+    /// Works for in-memory and on-disk files alike; pending writes are flushed
+    /// before the contents are collected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be closed or, for files on disk,
+    /// read back
+    pub fn into_bytes(mut self) -> Result<Vec<u8>> {
+        match self.memory.take() {
+            None => {
+                let path = self.path.clone();
+                drop(self);
+                Ok(std::fs::read(path)?)
+            }
+            // Dropping the file closes the handle before the buffer is returned
+            Some(MemoryImage::Buffer(bytes)) => {
+                drop(self);
+                Ok(bytes)
+            }
+            Some(MemoryImage::Library) => {
+                <M as crate::private::Sealed>::flush(&self.nc_file);
+                let ncid = nc_sys::ncid(&self.nc_file);
+                self.forget_handle();
+                // SAFETY: the handle owning ncid was forgotten above.
+                unsafe { nc_sys::close_memio(ncid) }
+            }
+        }
+    }
+
+    /// Drop the file without closing its NetCDF handle
+    fn forget_handle(self) {
+        let mut file = std::mem::ManuallyDrop::new(self);
+        // SAFETY: `file` is never used again and its destructor never runs, so
+        // each field other than the handle is dropped exactly once.
+        unsafe {
+            std::ptr::drop_in_place(&mut file.path);
+            std::ptr::drop_in_place(&mut file.metadata);
+            std::ptr::drop_in_place(&mut file.memory);
+        }
+    }
+}
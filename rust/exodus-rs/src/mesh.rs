@@ -0,0 +1,158 @@
+//! Serial mesh snapshot shared by whole-file operations
+//!
+//! Comparing files, decomposing a mesh and exporting to XDMF all need the
+//! same view of a file: coordinates, node and element IDs, element blocks
+//! with their connectivity, and node and side sets. [`MeshData::read`] loads
+//! it in one pass, and [`id_map_or_sequence`] reads an ID map the same way
+//! for every caller.
+
+use crate::error::{ExodusError, Result};
+use crate::types::{Block, EntityType, NodeSet, SideSet};
+use crate::{mode, ExodusFile};
+
+/// Mesh of a file, held in memory
+#[derive(Debug, Clone)]
+pub(crate) struct MeshData {
+    pub(crate) num_dim: usize,
+    /// Coordinates, with zeros for the missing dimensions
+    pub(crate) coords: [Vec<f64>; 3],
+    /// Global node IDs, 1..=num_nodes when the file has no node map
+    pub(crate) node_ids: Vec<i64>,
+    /// Global element IDs in block order, 1..=num_elems without an element map
+    pub(crate) elem_ids: Vec<i64>,
+    /// Element blocks in storage order
+    pub(crate) blocks: Vec<Block>,
+    /// Index of the first element of each block
+    pub(crate) offsets: Vec<usize>,
+    /// 1-based node connectivity of each block
+    pub(crate) connectivity: Vec<Vec<i64>>,
+    pub(crate) node_sets: Vec<NodeSet>,
+    pub(crate) side_sets: Vec<SideSet>,
+}
+
+impl MeshData {
+    /// Read the mesh of a file
+    ///
+    /// Edge and face blocks and edge, face and element sets are not read.
+    pub(crate) fn read(file: &ExodusFile<mode::Read>) -> Result<Self> {
+        let params = file.init_params()?;
+        let num_nodes = params.num_nodes;
+        let coords = if num_nodes > 0 {
+            let coords = file.coords::<f64>()?;
+            let fill = |values: Vec<f64>| {
+                if values.len() == num_nodes {
+                    values
+                } else {
+                    vec![0.0; num_nodes]
+                }
+            };
+            [fill(coords.x), fill(coords.y), fill(coords.z)]
+        } else {
+            Default::default()
+        };
+
+        let mut blocks = Vec::with_capacity(params.num_elem_blocks);
+        let mut offsets = Vec::with_capacity(params.num_elem_blocks);
+        let mut connectivity = Vec::with_capacity(params.num_elem_blocks);
+        let mut num_elems = 0;
+        for id in file.block_ids(EntityType::ElemBlock)? {
+            let block = file.block(id)?;
+            offsets.push(num_elems);
+            num_elems += block.num_entries;
+            connectivity.push(if block.num_entries > 0 && block.num_nodes_per_entry > 0 {
+                file.connectivity(id)?
+            } else {
+                Vec::new()
+            });
+            blocks.push(block);
+        }
+        let node_sets = file
+            .set_ids(EntityType::NodeSet)?
+            .into_iter()
+            .map(|id| file.node_set(id))
+            .collect::<Result<Vec<_>>>()?;
+        let side_sets = file
+            .set_ids(EntityType::SideSet)?
+            .into_iter()
+            .map(|id| file.side_set(id))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            num_dim: params.num_dim,
+            coords,
+            node_ids: id_map_or_sequence(file, EntityType::NodeMap, num_nodes)?,
+            elem_ids: id_map_or_sequence(file, EntityType::ElemMap, num_elems)?,
+            blocks,
+            offsets,
+            connectivity,
+            node_sets,
+            side_sets,
+        })
+    }
+
+    pub(crate) fn num_nodes(&self) -> usize {
+        self.node_ids.len()
+    }
+
+    pub(crate) fn num_elems(&self) -> usize {
+        self.elem_ids.len()
+    }
+
+    /// 1-based node list of every element in global element order
+    pub(crate) fn elements(&self) -> impl Iterator<Item = &[i64]> + '_ {
+        self.blocks
+            .iter()
+            .zip(&self.connectivity)
+            .flat_map(|(block, conn)| conn.chunks_exact(block.num_nodes_per_entry.max(1)))
+    }
+
+    /// Position of every node
+    pub(crate) fn points(&self) -> Vec<[f64; 3]> {
+        (0..self.num_nodes())
+            .map(|n| [self.coords[0][n], self.coords[1][n], self.coords[2][n]])
+            .collect()
+    }
+
+    /// Centroid of every element in global element order, averaging its nodes
+    pub(crate) fn centroids(&self) -> Vec<[f64; 3]> {
+        self.elements()
+            .map(|nodes| {
+                let mut centroid = [0.0; 3];
+                for &node in nodes {
+                    let index = usize::try_from(node - 1).ok();
+                    for (c, coord) in centroid.iter_mut().zip(&self.coords) {
+                        *c += index.and_then(|n| coord.get(n)).copied().unwrap_or(0.0);
+                    }
+                }
+                centroid.map(|c| c / nodes.len().max(1) as f64)
+            })
+            .collect()
+    }
+}
+
+/// ID map of a file, numbered 1..=count when the file has none
+///
+/// # Errors
+///
+/// Returns `InvalidArrayLength` if the stored map does not have `count`
+/// entries, or an error if NetCDF read fails
+pub(crate) fn id_map_or_sequence(
+    file: &ExodusFile<mode::Read>,
+    map_type: EntityType,
+    count: usize,
+) -> Result<Vec<i64>> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    let ids = match file.id_map(map_type) {
+        Err(ExodusError::VariableNotDefined(_)) => (1..=count as i64).collect(),
+        result => result?,
+    };
+    if ids.len() != count {
+        return Err(ExodusError::InvalidArrayLength {
+            expected: count,
+            actual: ids.len(),
+        });
+    }
+    Ok(ids)
+}
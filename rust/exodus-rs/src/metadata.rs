@@ -2,13 +2,20 @@
 //!
 //! This module provides QA records, info records, and name operations for Exodus files.
 
-use crate::error::{ExodusError, Result};
+use crate::backend::Backend;
+#[cfg(feature = "netcdf4")]
+use crate::error::ExodusError;
+use crate::error::Result;
 use crate::types::QaRecord;
 use crate::utils::constants::*;
+#[cfg(feature = "netcdf4")]
+use crate::utils::netcdf_ext::{to_nc_chars, NcChar};
 use crate::{mode, ExodusFile};
 
 // Import MAX_STR_LENGTH and MAX_LINE_LENGTH from constants
+#[cfg(feature = "netcdf4")]
 const MAX_STR_LENGTH: usize = MAX_QA_STRING_LENGTH;
+#[cfg(feature = "netcdf4")]
 const MAX_LINE_LENGTH: usize = MAX_INFO_STRING_LENGTH;
 
 #[cfg(feature = "netcdf4")]
@@ -78,6 +85,7 @@ impl ExodusFile<mode::Write> {
         }
 
         // Create dimension for number of QA records
+        self.ensure_define_mode()?;
         self.nc_file.add_dimension(DIM_NUM_QA_REC, num_qa)?;
 
         // Create dimension for 4 fields (code_name, code_version, date, time)
@@ -89,12 +97,13 @@ impl ExodusFile<mode::Write> {
         }
 
         // Create QA records variable: qa_records(num_qa_rec, num_qa_dim, len_string)
-        self.nc_file.add_variable::<u8>(
+        self.nc_file.add_variable::<NcChar>(
             VAR_QA_RECORDS,
             &[DIM_NUM_QA_REC, DIM_NUM_QA_DIM, DIM_LEN_STRING],
         )?;
 
         // Write each QA record
+        self.ensure_data_mode()?;
         if let Some(mut var) = self.nc_file.variable_mut(VAR_QA_RECORDS) {
             for (qa_idx, qa) in qa_records.iter().enumerate() {
                 // Write each of the 4 fields
@@ -105,7 +114,10 @@ impl ExodusFile<mode::Write> {
                     let bytes = field.as_bytes();
                     let copy_len = bytes.len().min(MAX_STR_LENGTH);
                     buf[..copy_len].copy_from_slice(&bytes[..copy_len]);
-                    var.put_values(&buf, (qa_idx..qa_idx + 1, field_idx..field_idx + 1, ..))?;
+                    var.put_values(
+                        &to_nc_chars(&buf),
+                        (qa_idx..qa_idx + 1, field_idx..field_idx + 1, ..),
+                    )?;
                 }
             }
         }
@@ -158,6 +170,7 @@ impl ExodusFile<mode::Write> {
         }
 
         // Create dimension for number of info records
+        self.ensure_define_mode()?;
         self.nc_file.add_dimension(DIM_NUM_INFO, num_info)?;
 
         // Create dimension for line length
@@ -167,16 +180,17 @@ impl ExodusFile<mode::Write> {
 
         // Create info records variable: info(num_info, len_line)
         self.nc_file
-            .add_variable::<u8>(VAR_INFO_RECORDS, &[DIM_NUM_INFO, DIM_LEN_LINE])?;
+            .add_variable::<NcChar>(VAR_INFO_RECORDS, &[DIM_NUM_INFO, DIM_LEN_LINE])?;
 
         // Write each info record
+        self.ensure_data_mode()?;
         if let Some(mut var) = self.nc_file.variable_mut(VAR_INFO_RECORDS) {
             for (i, line) in info_records.iter().enumerate() {
                 let mut buf = vec![0u8; MAX_LINE_LENGTH];
                 let bytes = line.as_bytes();
                 let copy_len = bytes.len().min(MAX_LINE_LENGTH);
                 buf[..copy_len].copy_from_slice(&bytes[..copy_len]);
-                var.put_values(&buf, (i..i + 1, ..))?;
+                var.put_values(&to_nc_chars(&buf), (i..i + 1, ..))?;
             }
         }
 
@@ -184,7 +198,6 @@ impl ExodusFile<mode::Write> {
     }
 }
 
-#[cfg(feature = "netcdf4")]
 impl ExodusFile<mode::Read> {
     /// Read QA records
     ///
@@ -209,60 +222,21 @@ impl ExodusFile<mode::Read> {
     /// ```
     pub fn qa_records(&self) -> Result<Vec<QaRecord>> {
         // Check if qa_records variable exists
-        match self.nc_file.variable(VAR_QA_RECORDS) {
-            Some(var) => {
-                let num_qa = self
-                    .nc_file
-                    .dimension("num_qa_rec")
-                    .map(|d| d.len())
-                    .unwrap_or(0);
-
-                if num_qa == 0 {
-                    return Ok(Vec::new());
-                }
-
-                // Get the len_string dimension for field length
-                let len_string = self
-                    .nc_file
-                    .dimension(DIM_LEN_STRING)
-                    .map(|d| d.len())
-                    .unwrap_or(MAX_STR_LENGTH);
-
-                let mut qa_records = Vec::with_capacity(num_qa);
-
-                // Read each QA record
-                for qa_idx in 0..num_qa {
-                    let mut fields = Vec::with_capacity(4);
-
-                    // Read each of the 4 fields (NC_CHAR stored as i8 in older files)
-                    for field_idx in 0..4 {
-                        let field_chars_i8: Vec<i8> = var.get_values((
-                            qa_idx..qa_idx + 1,
-                            field_idx..field_idx + 1,
-                            0..len_string,
-                        ))?;
-                        // Convert i8 bytes to u8 slice for UTF-8 decoding
-                        let field_bytes: Vec<u8> =
-                            field_chars_i8.iter().map(|&b| b as u8).collect();
-                        let field = String::from_utf8_lossy(&field_bytes)
-                            .trim_end_matches('\0')
-                            .trim()
-                            .to_string();
-                        fields.push(field);
-                    }
-
-                    qa_records.push(QaRecord {
-                        code_name: fields[0].clone(),
-                        code_version: fields[1].clone(),
-                        date: fields[2].clone(),
-                        time: fields[3].clone(),
-                    });
-                }
-
-                Ok(qa_records)
-            }
-            None => Ok(Vec::new()),
+        if !self.nc_file.has_variable(VAR_QA_RECORDS) {
+            return Ok(Vec::new());
         }
+
+        // Each record is [num_qa_rec, 4, len_string]: one string per field
+        let fields = self.nc_file.read_strings(VAR_QA_RECORDS)?;
+        Ok(fields
+            .chunks_exact(4)
+            .map(|f| QaRecord {
+                code_name: f[0].clone(),
+                code_version: f[1].clone(),
+                date: f[2].clone(),
+                time: f[3].clone(),
+            })
+            .collect())
     }
 
     /// Read info records
@@ -288,45 +262,10 @@ impl ExodusFile<mode::Read> {
     /// ```
     pub fn info_records(&self) -> Result<Vec<String>> {
         // Check if info_records variable exists
-        match self.nc_file.variable(VAR_INFO_RECORDS) {
-            Some(var) => {
-                let num_info = self
-                    .nc_file
-                    .dimension("num_info")
-                    .map(|d| d.len())
-                    .unwrap_or(0);
-
-                if num_info == 0 {
-                    return Ok(Vec::new());
-                }
-
-                // Get len_line dimension for line length
-                let len_line = self
-                    .nc_file
-                    .dimension("len_line")
-                    .map(|d| d.len())
-                    .unwrap_or(MAX_LINE_LENGTH);
-
-                let mut info_records = Vec::with_capacity(num_info);
-
-                // Read raw bytes directly (more reliable for 2D char arrays)
-                let raw_bytes: Vec<u8> = var.get_raw_values(..)?;
-
-                // Read each info record
-                for i in 0..num_info {
-                    let start = i * len_line;
-                    let end = start + len_line;
-                    let line_bytes = &raw_bytes[start..end];
-                    let line = String::from_utf8_lossy(line_bytes)
-                        .trim_end_matches('\0')
-                        .trim()
-                        .to_string();
-                    info_records.push(line);
-                }
-
-                Ok(info_records)
-            }
-            None => Ok(Vec::new()),
+        if self.nc_file.has_variable(VAR_INFO_RECORDS) {
+            self.nc_file.read_strings(VAR_INFO_RECORDS)
+        } else {
+            Ok(Vec::new())
         }
     }
 }
@@ -0,0 +1,784 @@
+//! Importing Nastran bulk data into Exodus.
+//!
+//! [`NastranMesh`] reads the mesh cards of a bulk data file, in small-field,
+//! large-field or free-field format with continuation lines:
+//!
+//! - `GRID` points, with their coordinates transformed from the `CP`
+//!   coordinate system to the basic system
+//! - `CORD2R` coordinate systems, and `CORD2C` and `CORD2S` (cylindrical and
+//!   spherical, angles in degrees), each defined by three points in its
+//!   reference system
+//! - `CTETRA`, `CHEXA` and `CPENTA` solids, linear or with all midside
+//!   nodes, `CQUAD4` and `CTRIA3` shells, and `CBAR` beams
+//!
+//! Other cards are skipped, as is everything before `BEGIN BULK` when the
+//! file has a case control section. [`import_nastran`] converts a whole file
+//! to an Exodus file written through [`MeshBuilder`].
+//!
+//! Each property ID (`PID`) becomes an element block with that ID. When
+//! several element types share a property, the first type keeps the
+//! property ID and the others get IDs above the largest property ID, and
+//! all of its blocks are named `property_<PID>_<TOPOLOGY>`. Nodes are
+//! numbered in the order of their grid IDs and elements in block order, and
+//! the grid and element IDs are kept in the node and element ID maps.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use exodus_rs::nastran::import_nastran;
+//!
+//! let summary = import_nastran("wing.bdf", "wing.e")?;
+//! println!("{} nodes, {} blocks", summary.num_nodes, summary.num_blocks);
+//! # Ok::<(), exodus_rs::ExodusError>(())
+//! ```
+//!
+//! `INCLUDE` statements, `CORD1` coordinate systems (defined by grid
+//! points), and superelements are not supported. Displacement coordinate
+//! systems (`CD`) do not affect the mesh and are ignored.
+
+use crate::builder::{BlockBuilder, MeshBuilder};
+use crate::error::{ExodusError, Result};
+use crate::geometry::{cross, magnitude, normalize, sub, Vec3};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// Node counts of the supported element cards: linear, then quadratic
+fn node_counts(card: &str) -> Option<&'static [usize]> {
+    match card {
+        "CTETRA" => Some(&[4, 10]),
+        "CHEXA" => Some(&[8, 20]),
+        "CPENTA" => Some(&[6, 15]),
+        "CQUAD4" => Some(&[4]),
+        "CTRIA3" => Some(&[3]),
+        "CBAR" => Some(&[2]),
+        _ => None,
+    }
+}
+
+/// Exodus topology of an element card with a number of nodes
+///
+/// Nastran numbers midside nodes as Exodus does, so no reordering is needed.
+fn block_topology(card: &str, num_nodes: usize) -> Option<&'static str> {
+    match (card, num_nodes) {
+        ("CTETRA", 4) => Some("TETRA4"),
+        ("CTETRA", 10) => Some("TETRA10"),
+        ("CHEXA", 8) => Some("HEX8"),
+        ("CHEXA", 20) => Some("HEX20"),
+        ("CPENTA", 6) => Some("WEDGE6"),
+        ("CPENTA", 15) => Some("WEDGE15"),
+        ("CQUAD4", 4) => Some("SHELL4"),
+        ("CTRIA3", 3) => Some("TRISHELL3"),
+        ("CBAR", 2) => Some("BAR2"),
+        _ => None,
+    }
+}
+
+/// An element of a bulk data file
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NastranElement {
+    /// Element ID
+    pub id: i64,
+    /// Element card, e.g. `CHEXA`
+    pub card: String,
+    /// Property ID, the element ID when the card leaves it blank
+    pub property: i64,
+    /// Grid IDs of the element
+    pub nodes: Vec<i64>,
+}
+
+/// The mesh cards of a Nastran bulk data file
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NastranMesh {
+    /// Grid point coordinates in the basic coordinate system, by grid ID
+    pub nodes: BTreeMap<i64, [f64; 3]>,
+    /// Elements in the order they appear
+    pub elements: Vec<NastranElement>,
+}
+
+/// What an import read and wrote
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NastranSummary {
+    /// Number of nodes
+    pub num_nodes: usize,
+    /// Number of elements
+    pub num_elems: usize,
+    /// Number of element blocks
+    pub num_blocks: usize,
+}
+
+/// Convert a Nastran bulk data file to an Exodus file, overwriting the
+/// output
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or converted (see
+/// [`NastranMesh::to_builder`]), or the Exodus file cannot be written.
+pub fn import_nastran(input: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<NastranSummary> {
+    let input = input.as_ref();
+    let mesh = NastranMesh::read(input)?;
+    let parts = mesh.exodus_parts()?;
+    let summary = NastranSummary {
+        num_nodes: mesh.nodes.len(),
+        num_elems: parts.elem_ids.len(),
+        num_blocks: parts.blocks.len(),
+    };
+    let title = format!(
+        "Converted from {}",
+        input.file_name().unwrap_or_default().to_string_lossy()
+    );
+    parts.into_builder(title, &mesh.nodes).write(output)?;
+    Ok(summary)
+}
+
+impl NastranMesh {
+    /// Read a bulk data file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed (see
+    /// [`NastranMesh::parse`]).
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::parse(&String::from_utf8_lossy(&bytes))
+    }
+
+    /// Parse the text of a bulk data file, resolving grid coordinates to
+    /// the basic coordinate system
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a field of a mesh card is malformed, a grid or
+    /// coordinate system is defined twice, a grid refers to an undefined or
+    /// degenerate coordinate system, an element has an unsupported number
+    /// of grids, or the file contains `INCLUDE`.
+    pub fn parse(text: &str) -> Result<Self> {
+        let cards = cards(text)?;
+        let mut systems: HashMap<i64, CoordinateSystem> = HashMap::new();
+        let mut grids: Vec<(&Card, i64, i64, Vec3)> = Vec::new();
+        let mut elements = Vec::new();
+        for card in &cards {
+            match card.name.as_str() {
+                "GRID" => {
+                    let id = card.required(0)?;
+                    let system = card.integer(1)?.unwrap_or(0);
+                    let point = [card.real(2)?, card.real(3)?, card.real(4)?];
+                    grids.push((card, id, system, point));
+                }
+                "CORD2R" | "CORD2C" | "CORD2S" => {
+                    let id = card.required(0)?;
+                    let system = CoordinateSystem {
+                        kind: match card.name.as_str() {
+                            "CORD2R" => SystemKind::Rectangular,
+                            "CORD2C" => SystemKind::Cylindrical,
+                            _ => SystemKind::Spherical,
+                        },
+                        reference: card.integer(1)?.unwrap_or(0),
+                        points: [
+                            [card.real(2)?, card.real(3)?, card.real(4)?],
+                            [card.real(5)?, card.real(6)?, card.real(7)?],
+                            [card.real(8)?, card.real(9)?, card.real(10)?],
+                        ],
+                    };
+                    if id <= 0 || systems.insert(id, system).is_some() {
+                        return Err(card.invalid(&format!(
+                            "coordinate system {} is defined twice or not positive",
+                            id
+                        )));
+                    }
+                }
+                name => {
+                    if let Some(counts) = node_counts(name) {
+                        elements.push(card.element(counts)?);
+                    }
+                }
+            }
+        }
+
+        let mut frames = HashMap::new();
+        let mut nodes = BTreeMap::new();
+        for (card, id, system, point) in grids {
+            let frame = resolve_frame(system, &systems, &mut frames, 0)?;
+            if nodes.insert(id, frame.basic_point(point)).is_some() {
+                return Err(card.invalid(&format!("grid {} is defined twice", id)));
+            }
+        }
+        Ok(Self { nodes, elements })
+    }
+
+    /// Build an Exodus mesh from the bulk data (see the
+    /// [module documentation](self))
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no elements, an element ID is used
+    /// twice, an element refers to an undefined grid, or an element has no
+    /// Exodus topology.
+    pub fn to_builder(&self, title: impl Into<String>) -> Result<MeshBuilder> {
+        Ok(self.exodus_parts()?.into_builder(title, &self.nodes))
+    }
+
+    fn exodus_parts(&self) -> Result<ExodusParts> {
+        if self.elements.is_empty() {
+            return Err(ExodusError::Other(
+                "Nastran bulk data has no elements".to_string(),
+            ));
+        }
+        let node_numbers: HashMap<i64, i64> = self
+            .nodes
+            .keys()
+            .enumerate()
+            .map(|(index, &id)| (id, index as i64 + 1))
+            .collect();
+
+        // Group the elements by property and topology, in property order
+        let mut groups: Vec<(i64, &'static str, Vec<&NastranElement>)> = Vec::new();
+        for element in &self.elements {
+            let topology = block_topology(&element.card, element.nodes.len()).ok_or_else(|| {
+                ExodusError::UnsupportedFeature(format!(
+                    "Nastran {} element {} with {} grids",
+                    element.card,
+                    element.id,
+                    element.nodes.len()
+                ))
+            })?;
+            match groups
+                .iter_mut()
+                .find(|group| group.0 == element.property && group.1 == topology)
+            {
+                Some(group) => group.2.push(element),
+                None => groups.push((element.property, topology, vec![element])),
+            }
+        }
+        groups.sort_by_key(|group| group.0);
+
+        let mut next_id = groups.iter().map(|group| group.0).max().unwrap_or(0) + 1;
+        let mut seen = HashSet::new();
+        let mut elem_ids = Vec::with_capacity(self.elements.len());
+        let mut blocks = Vec::with_capacity(groups.len());
+        for (index, (property, topology, elements)) in groups.iter().enumerate() {
+            let id = if index > 0 && groups[index - 1].0 == *property {
+                next_id += 1;
+                next_id - 1
+            } else {
+                *property
+            };
+            let shared = groups.iter().filter(|group| group.0 == *property).count() > 1;
+            let mut connectivity = Vec::with_capacity(elements.len() * elements[0].nodes.len());
+            for element in elements {
+                if !seen.insert(element.id) {
+                    return Err(ExodusError::Other(format!(
+                        "Nastran element {} is defined twice",
+                        element.id
+                    )));
+                }
+                elem_ids.push(element.id);
+                for node in &element.nodes {
+                    connectivity.push(*node_numbers.get(node).ok_or_else(|| {
+                        ExodusError::Other(format!(
+                            "Nastran element {} refers to undefined grid {}",
+                            element.id, node
+                        ))
+                    })?);
+                }
+            }
+            blocks.push(ExodusBlock {
+                id,
+                name: if shared {
+                    format!("property_{}_{}", property, topology)
+                } else {
+                    String::new()
+                },
+                topology,
+                connectivity,
+            });
+        }
+
+        Ok(ExodusParts {
+            node_ids: self.nodes.keys().copied().collect(),
+            elem_ids,
+            blocks,
+        })
+    }
+}
+
+/// An Exodus element block built from bulk data
+#[derive(Debug)]
+struct ExodusBlock {
+    id: i64,
+    name: String,
+    topology: &'static str,
+    /// 1-based node numbers
+    connectivity: Vec<i64>,
+}
+
+/// An Exodus mesh built from bulk data
+#[derive(Debug)]
+struct ExodusParts {
+    node_ids: Vec<i64>,
+    elem_ids: Vec<i64>,
+    blocks: Vec<ExodusBlock>,
+}
+
+impl ExodusParts {
+    fn into_builder(
+        self,
+        title: impl Into<String>,
+        nodes: &BTreeMap<i64, [f64; 3]>,
+    ) -> MeshBuilder {
+        let axis = |axis: usize| -> Vec<f64> { nodes.values().map(|node| node[axis]).collect() };
+        let mut builder = MeshBuilder::new(title)
+            .dimensions(3)
+            .coordinates(axis(0), axis(1), axis(2))
+            .node_id_map(self.node_ids)
+            .elem_id_map(self.elem_ids);
+        for block in self.blocks {
+            let mut block_builder =
+                BlockBuilder::new(block.id, block.topology).connectivity(block.connectivity);
+            if !block.name.is_empty() {
+                block_builder = block_builder.name(block.name);
+            }
+            builder = builder.add_block(block_builder.build());
+        }
+        builder
+    }
+}
+
+/// Kind of a `CORD2` coordinate system
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SystemKind {
+    /// Coordinates x, y, z
+    Rectangular,
+    /// Coordinates r, theta (degrees), z
+    Cylindrical,
+    /// Coordinates r, theta, phi (degrees)
+    Spherical,
+}
+
+/// A `CORD2R`, `CORD2C` or `CORD2S` card
+#[derive(Debug, Clone)]
+struct CoordinateSystem {
+    kind: SystemKind,
+    /// System the points are given in, 0 for the basic system
+    reference: i64,
+    /// The origin, a point on the z axis, and a point in the x-z plane
+    points: [Vec3; 3],
+}
+
+/// A coordinate system resolved to the basic system
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    kind: SystemKind,
+    origin: Vec3,
+    /// Unit x, y and z axes in the basic system
+    axes: [Vec3; 3],
+}
+
+impl Frame {
+    const BASIC: Frame = Frame {
+        kind: SystemKind::Rectangular,
+        origin: [0.0; 3],
+        axes: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+    };
+
+    /// Basic coordinates of a point given in this system
+    fn basic_point(&self, point: Vec3) -> Vec3 {
+        let local = match self.kind {
+            SystemKind::Rectangular => point,
+            SystemKind::Cylindrical => {
+                let (radius, theta) = (point[0], point[1].to_radians());
+                [radius * theta.cos(), radius * theta.sin(), point[2]]
+            }
+            SystemKind::Spherical => {
+                let (radius, theta, phi) = (point[0], point[1].to_radians(), point[2].to_radians());
+                [
+                    radius * theta.sin() * phi.cos(),
+                    radius * theta.sin() * phi.sin(),
+                    radius * theta.cos(),
+                ]
+            }
+        };
+        let mut basic = self.origin;
+        for (axis, value) in self.axes.iter().zip(local) {
+            for (coord, component) in basic.iter_mut().zip(axis) {
+                *coord += component * value;
+            }
+        }
+        basic
+    }
+}
+
+/// Resolve a coordinate system and the systems it is defined in
+fn resolve_frame(
+    id: i64,
+    systems: &HashMap<i64, CoordinateSystem>,
+    frames: &mut HashMap<i64, Frame>,
+    depth: usize,
+) -> Result<Frame> {
+    if id == 0 {
+        return Ok(Frame::BASIC);
+    }
+    if let Some(frame) = frames.get(&id) {
+        return Ok(*frame);
+    }
+    let system = systems.get(&id).ok_or_else(|| {
+        ExodusError::Other(format!("Nastran coordinate system {} is not defined", id))
+    })?;
+    if depth > systems.len() {
+        return Err(ExodusError::Other(format!(
+            "Nastran coordinate system {} is defined in terms of itself",
+            id
+        )));
+    }
+    let reference = resolve_frame(system.reference, systems, frames, depth + 1)?;
+    let [origin, on_z, in_xz] = system.points.map(|point| reference.basic_point(point));
+    let z = sub(on_z, origin);
+    let y = cross(z, sub(in_xz, origin));
+    if magnitude(y) <= 1e-12 * magnitude(z) * magnitude(sub(in_xz, origin)) {
+        return Err(ExodusError::Other(format!(
+            "Nastran coordinate system {}: its three points are collinear",
+            id
+        )));
+    }
+    let (y, z) = (normalize(y), normalize(z));
+    let frame = Frame {
+        kind: system.kind,
+        origin,
+        axes: [cross(y, z), y, z],
+    };
+    frames.insert(id, frame);
+    Ok(frame)
+}
+
+/// A bulk data card with its continuation lines joined
+#[derive(Debug)]
+struct Card {
+    line: usize,
+    /// Upper-case card name, without the `*` of large-field cards
+    name: String,
+    large: bool,
+    /// Data fields, from field 2 of the first line on
+    fields: Vec<String>,
+}
+
+impl Card {
+    fn field(&self, index: usize) -> &str {
+        self.fields.get(index).map_or("", String::as_str)
+    }
+
+    fn invalid(&self, message: &str) -> ExodusError {
+        ExodusError::Other(format!(
+            "Nastran {} card at line {}: {}",
+            self.name, self.line, message
+        ))
+    }
+
+    fn expected(&self, index: usize, what: &str) -> ExodusError {
+        self.invalid(&format!(
+            "field {} is '{}', expected {}",
+            index + 2,
+            self.field(index),
+            what
+        ))
+    }
+
+    /// An integer field, `None` if blank
+    fn integer(&self, index: usize) -> Result<Option<i64>> {
+        let field = self.field(index);
+        if field.is_empty() {
+            return Ok(None);
+        }
+        field
+            .parse()
+            .map(Some)
+            .map_err(|_| self.expected(index, "an integer"))
+    }
+
+    fn required(&self, index: usize) -> Result<i64> {
+        self.integer(index)?
+            .ok_or_else(|| self.expected(index, "an integer"))
+    }
+
+    /// A real field, 0 if blank
+    fn real(&self, index: usize) -> Result<f64> {
+        let field = self.field(index);
+        if field.is_empty() {
+            return Ok(0.0);
+        }
+        parse_real(field).ok_or_else(|| self.expected(index, "a number"))
+    }
+
+    /// An element card: `EID, PID, G1, G2, ...`
+    fn element(&self, counts: &[usize]) -> Result<NastranElement> {
+        let id = self.required(0)?;
+        let max = counts.iter().copied().max().unwrap_or(0);
+        let mut nodes = Vec::with_capacity(max);
+        for index in 2..2 + max {
+            match self.integer(index)? {
+                Some(node) if nodes.len() == index - 2 => nodes.push(node),
+                Some(_) => return Err(self.invalid("grids are missing before the last")),
+                None => {}
+            }
+        }
+        if !counts.contains(&nodes.len()) {
+            return Err(self.invalid(&format!(
+                "element {} has {} grids, expected {:?}",
+                id,
+                nodes.len(),
+                counts
+            )));
+        }
+        Ok(NastranElement {
+            id,
+            card: self.name.clone(),
+            property: self.integer(1)?.unwrap_or(id),
+            nodes,
+        })
+    }
+}
+
+/// Parse a real, accepting the Nastran forms without `E` such as `1.5+3`
+/// and `-2.-4`, and Fortran `D` exponents
+fn parse_real(field: &str) -> Option<f64> {
+    let field = field.replace(['D', 'd'], "E");
+    if let Ok(value) = field.parse() {
+        return Some(value);
+    }
+    let (split, _) = field
+        .char_indices()
+        .skip(1)
+        .filter(|(_, c)| *c == '+' || *c == '-')
+        .last()?;
+    format!("{}E{}", &field[..split], &field[split..])
+        .parse()
+        .ok()
+}
+
+/// Split the bulk data section into cards
+fn cards(text: &str) -> Result<Vec<Card>> {
+    let is_begin_bulk = |line: &str| {
+        let line = line.trim_start().to_uppercase();
+        line.starts_with("BEGIN") && line.contains("BULK")
+    };
+    let mut in_bulk = !text.lines().any(is_begin_bulk);
+    let mut cards: Vec<Card> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.split('$').next().unwrap_or_default().trim_end();
+        if line.trim().is_empty() {
+            continue;
+        }
+        if !in_bulk {
+            in_bulk = is_begin_bulk(line);
+            continue;
+        }
+        let upper = line.trim_start().to_uppercase();
+        if upper.starts_with("ENDDATA") {
+            break;
+        }
+        if upper.starts_with("INCLUDE") {
+            return Err(ExodusError::UnsupportedFeature(format!(
+                "Nastran INCLUDE at line {}",
+                index + 1
+            )));
+        }
+
+        let free = line.contains(',');
+        let first: String = if free {
+            line.split(',')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        } else {
+            line.chars().take(8).collect::<String>().trim().to_string()
+        };
+        let continuation = first.is_empty() || first.starts_with('+') || first.starts_with('*');
+        let large = match cards.last() {
+            Some(card) if continuation => card.large,
+            _ => first.ends_with('*'),
+        };
+        let fields = line_fields(line, free, large);
+        if continuation {
+            let card = cards.last_mut().ok_or_else(|| {
+                ExodusError::Other(format!(
+                    "Nastran line {}: continuation without a card",
+                    index + 1
+                ))
+            })?;
+            card.fields.extend(fields);
+        } else {
+            cards.push(Card {
+                line: index + 1,
+                name: first.trim_end_matches('*').to_uppercase(),
+                large,
+                fields,
+            });
+        }
+    }
+    Ok(cards)
+}
+
+/// Data fields of a line: fields 2 to 9 of 8 characters (small field),
+/// fields 2 to 5 of 16 characters (large field), or the comma-separated
+/// fields after the first (free field), padded to a full line
+fn line_fields(line: &str, free: bool, large: bool) -> Vec<String> {
+    let count = if large { 4 } else { 8 };
+    let mut fields: Vec<String> = if free {
+        let mut fields: Vec<String> = line
+            .split(',')
+            .skip(1)
+            .map(|field| field.trim().to_string())
+            .collect();
+        // A tenth field is the continuation marker
+        if fields.len() == count + 1 {
+            fields.pop();
+        }
+        fields
+    } else {
+        let width = if large { 16 } else { 8 };
+        let chars: Vec<char> = line.chars().collect();
+        (0..count)
+            .map(|field| {
+                let start = (8 + field * width).min(chars.len());
+                let end = (start + width).min(chars.len());
+                chars[start..end]
+                    .iter()
+                    .collect::<String>()
+                    .trim()
+                    .to_string()
+            })
+            .collect()
+    };
+    if fields.len() < count {
+        fields.resize(count, String::new());
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small-field line
+    fn small(fields: &[&str]) -> String {
+        fields
+            .iter()
+            .map(|field| format!("{:<8}", field))
+            .collect::<String>()
+            + "\n"
+    }
+
+    /// A large-field card on two lines
+    fn large(name: &str, fields: &[&str]) -> String {
+        let line = |first: &str, fields: &[&str]| {
+            format!("{:<8}", first)
+                + &fields
+                    .iter()
+                    .map(|field| format!("{:<16}", field))
+                    .collect::<String>()
+                + "\n"
+        };
+        line(&format!("{}*", name), &fields[..4]) + &line("*", &fields[4..])
+    }
+
+    /// A unit cube HEX8 in property 3 whose grids are written in all three
+    /// formats, a CQUAD4 and a CTRIA3 sharing property 5, and a CBAR between
+    /// grids in a rotated rectangular and a cylindrical system
+    fn deck() -> String {
+        let mut deck = String::from("SOL 101\nCEND\nBEGIN BULK\n$ Grids\n");
+        deck += &small(&["GRID", "1", "", "0.", "0.", "0."]);
+        deck += &small(&["GRID", "2", "", "1.", "0.", "0."]);
+        deck += &small(&["GRID", "3", "", "1.", "1.", "0."]);
+        deck += &small(&["GRID", "4", "", "0.", "1.", "0."]);
+        deck += &large("GRID", &["5", "", "0.0", "0.0", "1.0"]);
+        deck += &large("GRID", &["6", "", "1.0", "0.0", "1.0"]);
+        deck += "GRID,7,,1.,1.,1.+0\nGRID,8,,0.,1.,10.-1\n";
+        deck += &small(&["GRID", "100", "10", "1.", "0.", "0."]);
+        deck += &small(&["GRID", "101", "20", "2.", "90.", "1."]);
+        deck += &small(&[
+            "CORD2R", "10", "", "1.", "0.", "0.", "1.", "0.", "1.", "+C1",
+        ]);
+        deck += &small(&["+C1", "1.", "1.", "0."]);
+        deck += "CORD2C,20,,0.,0.,0.,0.,0.,1.,+\n+,1.,0.,0.\n";
+        deck += &small(&["CHEXA", "1", "3", "1", "2", "3", "4", "5", "6", "+"]);
+        deck += &small(&["+", "7", "8"]);
+        deck += &small(&["CQUAD4", "10", "5", "1", "2", "3", "4"]);
+        deck += &small(&["CTRIA3", "11", "5", "5", "6", "7"]);
+        deck += &small(&["CBAR", "12", "", "100", "101", "0.", "0.", "1."]);
+        deck += &small(&["PSOLID", "3", "1"]);
+        deck + "ENDDATA\n"
+    }
+
+    fn assert_close(actual: [f64; 3], expected: [f64; 3]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-12, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_formats_and_systems() {
+        let mesh = NastranMesh::parse(&deck()).unwrap();
+        assert_eq!(mesh.nodes.len(), 10);
+        assert_close(mesh.nodes[&6], [1.0, 0.0, 1.0]);
+        assert_close(mesh.nodes[&8], [0.0, 1.0, 1.0]);
+        // System 10 is rotated 90 degrees about z and moved to x = 1
+        assert_close(mesh.nodes[&100], [1.0, 1.0, 0.0]);
+        assert_close(mesh.nodes[&101], [0.0, 2.0, 1.0]);
+
+        assert_eq!(mesh.elements.len(), 4);
+        assert_eq!(mesh.elements[0].nodes, (1..=8).collect::<Vec<_>>());
+        // A blank property is the element ID
+        assert_eq!(mesh.elements[3].property, 12);
+        assert_eq!(mesh.elements[3].nodes, vec![100, 101]);
+    }
+
+    #[test]
+    fn test_properties_become_blocks() {
+        let parts = NastranMesh::parse(&deck()).unwrap().exodus_parts().unwrap();
+        let blocks: Vec<(i64, &str, &str)> = parts
+            .blocks
+            .iter()
+            .map(|block| (block.id, block.name.as_str(), block.topology))
+            .collect();
+        assert_eq!(
+            blocks,
+            [
+                (3, "", "HEX8"),
+                (5, "property_5_SHELL4", "SHELL4"),
+                (13, "property_5_TRISHELL3", "TRISHELL3"),
+                (12, "", "BAR2"),
+            ]
+        );
+        assert_eq!(parts.elem_ids, vec![1, 10, 11, 12]);
+        assert_eq!(parts.node_ids[8..], [100, 101]);
+        assert_eq!(parts.blocks[3].connectivity, vec![9, 10]);
+    }
+
+    #[test]
+    fn test_parse_real() {
+        assert_eq!(parse_real("1.5+3"), Some(1500.0));
+        assert_eq!(parse_real("-2.-4"), Some(-2e-4));
+        assert_eq!(parse_real("1.0D2"), Some(100.0));
+        assert_eq!(parse_real(".5E-1"), Some(0.05));
+        assert_eq!(parse_real("x"), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let undefined = small(&["GRID", "1", "7", "0.", "0.", "0."]);
+        assert!(NastranMesh::parse(&undefined).is_err());
+
+        let mut collinear = small(&["CORD2R", "7", "", "0.", "0.", "0.", "0.", "0.", "1."]);
+        collinear += &small(&["", "0.", "0.", "2."]);
+        assert!(NastranMesh::parse(&(collinear + &undefined)).is_err());
+
+        let partial = small(&["CTETRA", "1", "1", "1", "2", "3", "4", "5"]);
+        assert!(NastranMesh::parse(&partial).is_err());
+
+        let missing = small(&["CTRIA3", "1", "1", "1", "2", "3"]);
+        let mesh = NastranMesh::parse(&missing).unwrap();
+        assert!(mesh.exodus_parts().is_err());
+    }
+}
@@ -0,0 +1,883 @@
+//! Nemesis parallel metadata
+//!
+//! Decomposed meshes are written file-per-processor (`mesh.e.N.M`), each file
+//! holding the local mesh of one processor plus the Nemesis (`ne_*`) data
+//! that ties it to the global mesh: global sizes and block/set parameters,
+//! load-balance parameters, internal/border/external node and element maps,
+//! and node/element communication maps. The NetCDF layout matches the C
+//! Exodus library so the files interoperate with `epu`, `nem_spread` and
+//! parallel IOSS readers.
+
+use crate::backend::Backend;
+use crate::error::{EntityId, ExodusError, Result};
+use crate::types::{
+    CommMapParams, ElemCommMap, EntityType, GlobalEntityParams, GlobalInitParams,
+    LoadBalanceParams, NemesisFileType, NemesisInitInfo, NodeCommMap, ProcessorElemMaps,
+    ProcessorNodeMaps,
+};
+use crate::utils::constants::*;
+#[cfg(feature = "netcdf4")]
+use crate::WritableMode;
+use crate::{ExodusFile, ReadableMode};
+use std::ops::Range;
+
+/// Global variable names (IDs, entry counts, distribution factor counts) and
+/// dimension of an entity type
+fn global_param_names(
+    entity_type: EntityType,
+) -> Result<(
+    &'static str,
+    &'static str,
+    Option<&'static str>,
+    &'static str,
+)> {
+    match entity_type {
+        EntityType::ElemBlock => Ok((
+            VAR_ELBLK_IDS_GLOBAL,
+            VAR_ELBLK_CNT_GLOBAL,
+            None,
+            DIM_NUM_ELBLK_GLOBAL,
+        )),
+        EntityType::NodeSet => Ok((
+            VAR_NS_IDS_GLOBAL,
+            VAR_NS_NODE_CNT_GLOBAL,
+            Some(VAR_NS_DF_CNT_GLOBAL),
+            DIM_NUM_NS_GLOBAL,
+        )),
+        EntityType::SideSet => Ok((
+            VAR_SS_IDS_GLOBAL,
+            VAR_SS_SIDE_CNT_GLOBAL,
+            Some(VAR_SS_DF_CNT_GLOBAL),
+            DIM_NUM_SS_GLOBAL,
+        )),
+        _ => Err(ExodusError::InvalidEntityType(format!(
+            "Global parameters are only stored for element blocks, node sets and side sets, got {}",
+            entity_type
+        ))),
+    }
+}
+
+/// Slice of the concatenated entries belonging to map `index`
+///
+/// `ends` holds the cumulative end offset of each map, as stored in
+/// `n_comm_data_idx` and `e_comm_data_idx`.
+fn map_range(ends: &[i64], index: usize) -> Range<usize> {
+    let start = if index == 0 {
+        0
+    } else {
+        ends[index - 1] as usize
+    };
+    start..ends[index] as usize
+}
+
+/// Per-map sizes from cumulative end offsets
+fn map_counts(ends: &[i64]) -> Vec<usize> {
+    (0..ends.len()).map(|i| map_range(ends, i).len()).collect()
+}
+
+// ====================
+// Read Operations
+// ====================
+
+/// Length of a dimension, 0 when it is not defined
+fn dim<B: Backend + ?Sized>(nc_file: &B, name: &str) -> usize {
+    nc_file.dimension_len(name).unwrap_or(0)
+}
+
+/// Entire integer variable, empty when it is not defined
+fn optional_i64<B: Backend + ?Sized>(nc_file: &B, name: &str) -> Result<Vec<i64>> {
+    if nc_file.has_variable(name) {
+        nc_file.read_all_i64(name)
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+fn to_counts(values: Vec<i64>) -> Vec<usize> {
+    values.into_iter().map(|v| v as usize).collect()
+}
+
+pub(crate) fn read_init_info<B: Backend + ?Sized>(nc_file: &B) -> Result<NemesisInitInfo> {
+    let file_type = match nc_file.read_all_i64(VAR_FILE_TYPE)?.first() {
+        Some(1) => NemesisFileType::Scalar,
+        _ => NemesisFileType::Parallel,
+    };
+    Ok(NemesisInitInfo {
+        num_procs: dim(nc_file, DIM_NUM_PROCS),
+        num_procs_in_file: dim(nc_file, DIM_NUM_PROCS_F),
+        file_type,
+    })
+}
+
+pub(crate) fn read_init_global<B: Backend + ?Sized>(nc_file: &B) -> Result<GlobalInitParams> {
+    let num_nodes = nc_file.dimension_len(DIM_NUM_NODES_GLOBAL).ok_or_else(|| {
+        ExodusError::Other(format!("Dimension {} not found", DIM_NUM_NODES_GLOBAL))
+    })?;
+    Ok(GlobalInitParams {
+        num_nodes,
+        num_elems: dim(nc_file, DIM_NUM_ELEMS_GLOBAL),
+        num_elem_blocks: dim(nc_file, DIM_NUM_ELBLK_GLOBAL),
+        num_node_sets: dim(nc_file, DIM_NUM_NS_GLOBAL),
+        num_side_sets: dim(nc_file, DIM_NUM_SS_GLOBAL),
+    })
+}
+
+pub(crate) fn read_global_params<B: Backend + ?Sized>(
+    nc_file: &B,
+    entity_type: EntityType,
+) -> Result<GlobalEntityParams> {
+    let (ids_var, counts_var, df_var, dim_name) = global_param_names(entity_type)?;
+    if dim(nc_file, dim_name) == 0 {
+        return Ok(GlobalEntityParams::default());
+    }
+    Ok(GlobalEntityParams {
+        ids: nc_file.read_all_i64(ids_var)?,
+        entry_counts: to_counts(nc_file.read_all_i64(counts_var)?),
+        dist_fact_counts: match df_var {
+            Some(name) => to_counts(nc_file.read_all_i64(name)?),
+            None => Vec::new(),
+        },
+    })
+}
+
+pub(crate) fn read_load_balance_params<B: Backend + ?Sized>(
+    nc_file: &B,
+) -> Result<LoadBalanceParams> {
+    Ok(LoadBalanceParams {
+        num_internal_nodes: dim(nc_file, DIM_NUM_INT_NODES),
+        num_border_nodes: dim(nc_file, DIM_NUM_BOR_NODES),
+        num_external_nodes: dim(nc_file, DIM_NUM_EXT_NODES),
+        num_internal_elems: dim(nc_file, DIM_NUM_INT_ELEMS),
+        num_border_elems: dim(nc_file, DIM_NUM_BOR_ELEMS),
+        num_node_cmaps: dim(nc_file, DIM_NUM_N_CMAPS),
+        num_elem_cmaps: dim(nc_file, DIM_NUM_E_CMAPS),
+    })
+}
+
+pub(crate) fn read_processor_node_maps<B: Backend + ?Sized>(
+    nc_file: &B,
+) -> Result<ProcessorNodeMaps> {
+    Ok(ProcessorNodeMaps {
+        internal: optional_i64(nc_file, VAR_NODE_MAP_INT)?,
+        border: optional_i64(nc_file, VAR_NODE_MAP_BOR)?,
+        external: optional_i64(nc_file, VAR_NODE_MAP_EXT)?,
+    })
+}
+
+pub(crate) fn read_processor_elem_maps<B: Backend + ?Sized>(
+    nc_file: &B,
+) -> Result<ProcessorElemMaps> {
+    Ok(ProcessorElemMaps {
+        internal: optional_i64(nc_file, VAR_ELEM_MAP_INT)?,
+        border: optional_i64(nc_file, VAR_ELEM_MAP_BOR)?,
+    })
+}
+
+pub(crate) fn read_comm_map_params<B: Backend + ?Sized>(nc_file: &B) -> Result<CommMapParams> {
+    Ok(CommMapParams {
+        node_cmap_ids: optional_i64(nc_file, VAR_N_COMM_IDS)?,
+        node_cmap_node_counts: map_counts(&optional_i64(nc_file, VAR_N_COMM_DATA_IDX)?),
+        elem_cmap_ids: optional_i64(nc_file, VAR_E_COMM_IDS)?,
+        elem_cmap_elem_counts: map_counts(&optional_i64(nc_file, VAR_E_COMM_DATA_IDX)?),
+    })
+}
+
+/// Entry range of the communication map with the given ID
+fn cmap_range<B: Backend + ?Sized>(
+    nc_file: &B,
+    ids_var: &str,
+    idx_var: &str,
+    map_type: &str,
+    id: EntityId,
+) -> Result<Range<usize>> {
+    let not_found = || ExodusError::EntityNotFound {
+        entity_type: map_type.to_string(),
+        id,
+    };
+    let index = optional_i64(nc_file, ids_var)?
+        .iter()
+        .position(|&map_id| map_id == id)
+        .ok_or_else(not_found)?;
+    let ends = nc_file.read_all_i64(idx_var)?;
+    if index >= ends.len() {
+        return Err(not_found());
+    }
+    Ok(map_range(&ends, index))
+}
+
+/// Slice of a concatenated communication map variable
+fn read_range<B: Backend + ?Sized>(
+    nc_file: &B,
+    var: &str,
+    range: &Range<usize>,
+) -> Result<Vec<i64>> {
+    if range.is_empty() {
+        return Ok(Vec::new());
+    }
+    nc_file.read_i64(var, &[range.start], &[range.len()])
+}
+
+pub(crate) fn read_node_comm_map<B: Backend + ?Sized>(
+    nc_file: &B,
+    id: EntityId,
+) -> Result<NodeCommMap> {
+    let range = cmap_range(
+        nc_file,
+        VAR_N_COMM_IDS,
+        VAR_N_COMM_DATA_IDX,
+        "node communication map",
+        id,
+    )?;
+    Ok(NodeCommMap {
+        id,
+        node_ids: read_range(nc_file, VAR_N_COMM_NIDS, &range)?,
+        proc_ids: read_range(nc_file, VAR_N_COMM_PROC, &range)?,
+    })
+}
+
+pub(crate) fn read_elem_comm_map<B: Backend + ?Sized>(
+    nc_file: &B,
+    id: EntityId,
+) -> Result<ElemCommMap> {
+    let range = cmap_range(
+        nc_file,
+        VAR_E_COMM_IDS,
+        VAR_E_COMM_DATA_IDX,
+        "element communication map",
+        id,
+    )?;
+    Ok(ElemCommMap {
+        id,
+        elem_ids: read_range(nc_file, VAR_E_COMM_EIDS, &range)?,
+        side_ids: read_range(nc_file, VAR_E_COMM_SIDS, &range)?,
+        proc_ids: read_range(nc_file, VAR_E_COMM_PROC, &range)?,
+    })
+}
+
+impl<M: ReadableMode> ExodusFile<M> {
+    /// Read Nemesis initialization information
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file has no Nemesis data (`nem_ftype` is
+    /// missing) or NetCDF read fails
+    pub fn init_info(&self) -> Result<NemesisInitInfo> {
+        read_init_info(&self.nc_file)
+    }
+
+    /// Read the sizes of the undecomposed (global) mesh
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the global parameters were never written
+    pub fn init_global(&self) -> Result<GlobalInitParams> {
+        read_init_global(&self.nc_file)
+    }
+
+    /// Read global IDs and sizes of element blocks, node sets or side sets
+    ///
+    /// # Arguments
+    ///
+    /// * `entity_type` - `ElemBlock`, `NodeSet` or `SideSet`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for other entity types or if NetCDF read fails
+    pub fn global_params(&self, entity_type: EntityType) -> Result<GlobalEntityParams> {
+        read_global_params(&self.nc_file, entity_type)
+    }
+
+    /// Read the load-balance parameters of this processor
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if NetCDF read fails
+    pub fn load_balance_params(&self) -> Result<LoadBalanceParams> {
+        read_load_balance_params(&self.nc_file)
+    }
+
+    /// Read the internal, border and external node maps
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if NetCDF read fails
+    pub fn processor_node_maps(&self) -> Result<ProcessorNodeMaps> {
+        read_processor_node_maps(&self.nc_file)
+    }
+
+    /// Read the internal and border element maps
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if NetCDF read fails
+    pub fn processor_elem_maps(&self) -> Result<ProcessorElemMaps> {
+        read_processor_elem_maps(&self.nc_file)
+    }
+
+    /// Read the IDs and sizes of all communication maps
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if NetCDF read fails
+    pub fn comm_map_params(&self) -> Result<CommMapParams> {
+        read_comm_map_params(&self.nc_file)
+    }
+
+    /// Read a node communication map
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if no node communication map has this ID
+    pub fn node_comm_map(&self, id: EntityId) -> Result<NodeCommMap> {
+        read_node_comm_map(&self.nc_file, id)
+    }
+
+    /// Read an element communication map
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if no element communication map has this ID
+    pub fn elem_comm_map(&self, id: EntityId) -> Result<ElemCommMap> {
+        read_elem_comm_map(&self.nc_file, id)
+    }
+}
+
+// ====================
+// Write Operations
+// ====================
+
+#[cfg(feature = "netcdf4")]
+impl<M: WritableMode> ExodusFile<M> {
+    /// Write Nemesis initialization information
+    ///
+    /// Must be called before [`put_load_balance_params`](Self::put_load_balance_params).
+    /// For file-per-processor output use `num_procs_in_file: 1` and
+    /// [`NemesisFileType::Parallel`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if NetCDF write fails
+    pub fn put_init_info(&mut self, info: &NemesisInitInfo) -> Result<()> {
+        self.ensure_define_mode()?;
+        self.put_nemesis_version()?;
+        self.add_nemesis_dim(DIM_NUM_PROCS, info.num_procs)?;
+        self.add_nemesis_dim(DIM_NUM_PROCS_F, info.num_procs_in_file)?;
+        if self.nc_file.variable(VAR_FILE_TYPE).is_none() {
+            self.nc_file.add_variable::<i32>(VAR_FILE_TYPE, &[])?;
+        }
+
+        self.ensure_data_mode()?;
+        let file_type: i32 = match info.file_type {
+            NemesisFileType::Parallel => 0,
+            NemesisFileType::Scalar => 1,
+        };
+        self.put_nemesis_ints(VAR_FILE_TYPE, &[file_type])
+    }
+
+    /// Write the sizes of the undecomposed (global) mesh
+    ///
+    /// Defines the variables filled by [`put_global_params`](Self::put_global_params).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the global parameters were already written or
+    /// NetCDF write fails
+    pub fn put_init_global(&mut self, params: &GlobalInitParams) -> Result<()> {
+        if self.nc_file.dimension(DIM_NUM_NODES_GLOBAL).is_some() {
+            return Err(ExodusError::Other(
+                "Global Nemesis parameters already written".to_string(),
+            ));
+        }
+
+        self.ensure_define_mode()?;
+        self.put_nemesis_version()?;
+        self.nc_file
+            .add_dimension(DIM_NUM_NODES_GLOBAL, params.num_nodes)?;
+        self.add_nemesis_dim(DIM_NUM_ELEMS_GLOBAL, params.num_elems)?;
+        for (entity_type, count) in [
+            (EntityType::ElemBlock, params.num_elem_blocks),
+            (EntityType::NodeSet, params.num_node_sets),
+            (EntityType::SideSet, params.num_side_sets),
+        ] {
+            if count == 0 {
+                continue;
+            }
+            let (ids_var, counts_var, df_var, dim_name) = global_param_names(entity_type)?;
+            self.nc_file.add_dimension(dim_name, count)?;
+            for name in [Some(ids_var), Some(counts_var), df_var]
+                .into_iter()
+                .flatten()
+            {
+                self.nc_file.add_variable_with_type(
+                    name,
+                    &[dim_name],
+                    &self.metadata.int_type(),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write global IDs and sizes of element blocks, node sets or side sets
+    ///
+    /// # Arguments
+    ///
+    /// * `entity_type` - `ElemBlock`, `NodeSet` or `SideSet`
+    /// * `params` - One entry per global entity; `dist_fact_counts` may be
+    ///   empty, which writes zeros for sets and is required for blocks
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - [`put_init_global`](Self::put_init_global) has not been called
+    /// - Array lengths do not match the global entity count
+    /// - NetCDF write fails
+    pub fn put_global_params(
+        &mut self,
+        entity_type: EntityType,
+        params: &GlobalEntityParams,
+    ) -> Result<()> {
+        let (ids_var, counts_var, df_var, dim_name) = global_param_names(entity_type)?;
+        let count = dim(&self.nc_file, dim_name);
+        for len in [params.ids.len(), params.entry_counts.len()] {
+            if len != count {
+                return Err(ExodusError::InvalidArrayLength {
+                    expected: count,
+                    actual: len,
+                });
+            }
+        }
+        let df_len = params.dist_fact_counts.len();
+        if df_len != 0 && (df_var.is_none() || df_len != count) {
+            return Err(ExodusError::InvalidArrayLength {
+                expected: if df_var.is_some() { count } else { 0 },
+                actual: df_len,
+            });
+        }
+        if count == 0 {
+            return Ok(());
+        }
+        self.metadata
+            .check_int_range(&format!("global {} ID", entity_type), &params.ids)?;
+
+        self.ensure_data_mode()?;
+        self.put_nemesis_i64s(ids_var, &params.ids)?;
+        self.put_nemesis_i64s(counts_var, &to_i64(&params.entry_counts))?;
+        if let Some(df_var) = df_var {
+            let df_counts = if df_len == 0 {
+                vec![0; count]
+            } else {
+                to_i64(&params.dist_fact_counts)
+            };
+            self.put_nemesis_i64s(df_var, &df_counts)?;
+        }
+        Ok(())
+    }
+
+    /// Write the load-balance parameters of this processor
+    ///
+    /// Defines the node and element maps and the communication map IDs
+    /// filled by the other `put_*` methods of this module.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - [`put_init_info`](Self::put_init_info) has not been called
+    /// - The file is not a parallel (file-per-processor) file
+    /// - The parameters were already written
+    /// - NetCDF write fails
+    pub fn put_load_balance_params(&mut self, params: &LoadBalanceParams) -> Result<()> {
+        let info = read_init_info(&self.nc_file)?;
+        if info.file_type != NemesisFileType::Parallel {
+            return Err(ExodusError::UnsupportedFeature(
+                "Load-balance parameters can only be written to parallel Nemesis files".to_string(),
+            ));
+        }
+        if self.nc_file.variable(VAR_INT_N_STAT).is_some() {
+            return Err(ExodusError::Other(
+                "Load-balance parameters already written".to_string(),
+            ));
+        }
+
+        self.ensure_define_mode()?;
+        self.put_nemesis_version()?;
+        for name in [
+            VAR_INT_N_STAT,
+            VAR_BOR_N_STAT,
+            VAR_EXT_N_STAT,
+            VAR_INT_E_STAT,
+            VAR_BOR_E_STAT,
+        ] {
+            self.nc_file.add_variable::<i32>(name, &[DIM_NUM_PROCS_F])?;
+        }
+        for (dim_name, var_name, len) in [
+            (
+                DIM_NUM_INT_ELEMS,
+                VAR_ELEM_MAP_INT,
+                params.num_internal_elems,
+            ),
+            (DIM_NUM_BOR_ELEMS, VAR_ELEM_MAP_BOR, params.num_border_elems),
+            (
+                DIM_NUM_INT_NODES,
+                VAR_NODE_MAP_INT,
+                params.num_internal_nodes,
+            ),
+            (DIM_NUM_BOR_NODES, VAR_NODE_MAP_BOR, params.num_border_nodes),
+            (
+                DIM_NUM_EXT_NODES,
+                VAR_NODE_MAP_EXT,
+                params.num_external_nodes,
+            ),
+        ] {
+            if len > 0 {
+                self.nc_file.add_dimension(dim_name, len)?;
+                let mut var = self.nc_file.add_variable_with_type(
+                    var_name,
+                    &[dim_name],
+                    &self.metadata.int_type(),
+                )?;
+                self.metadata.apply_compression(&mut var)?;
+            }
+        }
+        for (dim_name, ids_var, stat_var, len) in [
+            (
+                DIM_NUM_N_CMAPS,
+                VAR_N_COMM_IDS,
+                VAR_N_COMM_STAT,
+                params.num_node_cmaps,
+            ),
+            (
+                DIM_NUM_E_CMAPS,
+                VAR_E_COMM_IDS,
+                VAR_E_COMM_STAT,
+                params.num_elem_cmaps,
+            ),
+        ] {
+            if len > 0 {
+                self.nc_file.add_dimension(dim_name, len)?;
+                self.nc_file.add_variable_with_type(
+                    ids_var,
+                    &[dim_name],
+                    &self.metadata.int_type(),
+                )?;
+                self.nc_file.add_variable::<i32>(stat_var, &[dim_name])?;
+            }
+        }
+
+        self.ensure_data_mode()?;
+        let num_procs_in_file = info.num_procs_in_file;
+        for (name, len) in [
+            (VAR_INT_N_STAT, params.num_internal_nodes),
+            (VAR_BOR_N_STAT, params.num_border_nodes),
+            (VAR_EXT_N_STAT, params.num_external_nodes),
+            (VAR_INT_E_STAT, params.num_internal_elems),
+            (VAR_BOR_E_STAT, params.num_border_elems),
+        ] {
+            self.put_nemesis_ints(name, &vec![i32::from(len > 0); num_procs_in_file])?;
+        }
+        Ok(())
+    }
+
+    /// Write the internal, border and external node maps
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if map lengths differ from the load-balance
+    /// parameters or NetCDF write fails
+    pub fn put_processor_node_maps(&mut self, maps: &ProcessorNodeMaps) -> Result<()> {
+        self.put_processor_map(DIM_NUM_INT_NODES, VAR_NODE_MAP_INT, &maps.internal)?;
+        self.put_processor_map(DIM_NUM_BOR_NODES, VAR_NODE_MAP_BOR, &maps.border)?;
+        self.put_processor_map(DIM_NUM_EXT_NODES, VAR_NODE_MAP_EXT, &maps.external)
+    }
+
+    /// Write the internal and border element maps
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if map lengths differ from the load-balance
+    /// parameters or NetCDF write fails
+    pub fn put_processor_elem_maps(&mut self, maps: &ProcessorElemMaps) -> Result<()> {
+        self.put_processor_map(DIM_NUM_INT_ELEMS, VAR_ELEM_MAP_INT, &maps.internal)?;
+        self.put_processor_map(DIM_NUM_BOR_ELEMS, VAR_ELEM_MAP_BOR, &maps.border)
+    }
+
+    /// Write the IDs and sizes of the communication maps
+    ///
+    /// Defines the storage filled by [`put_node_comm_map`](Self::put_node_comm_map)
+    /// and [`put_elem_comm_map`](Self::put_elem_comm_map).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Map counts differ from the load-balance parameters
+    /// - The parameters were already written
+    /// - NetCDF write fails
+    pub fn put_comm_map_params(&mut self, params: &CommMapParams) -> Result<()> {
+        if self.nc_file.variable(VAR_N_COMM_DATA_IDX).is_some()
+            || self.nc_file.variable(VAR_E_COMM_DATA_IDX).is_some()
+        {
+            return Err(ExodusError::Other(
+                "Communication map parameters already written".to_string(),
+            ));
+        }
+        let num_node_cmaps = dim(&self.nc_file, DIM_NUM_N_CMAPS);
+        let num_elem_cmaps = dim(&self.nc_file, DIM_NUM_E_CMAPS);
+        for (expected, actual) in [
+            (num_node_cmaps, params.node_cmap_ids.len()),
+            (num_node_cmaps, params.node_cmap_node_counts.len()),
+            (num_elem_cmaps, params.elem_cmap_ids.len()),
+            (num_elem_cmaps, params.elem_cmap_elem_counts.len()),
+        ] {
+            if expected != actual {
+                return Err(ExodusError::InvalidArrayLength { expected, actual });
+            }
+        }
+        self.metadata
+            .check_int_range("node communication map ID", &params.node_cmap_ids)?;
+        self.metadata
+            .check_int_range("element communication map ID", &params.elem_cmap_ids)?;
+
+        let node_total: usize = params.node_cmap_node_counts.iter().sum();
+        let elem_total: usize = params.elem_cmap_elem_counts.iter().sum();
+
+        self.ensure_define_mode()?;
+        if num_node_cmaps > 0 {
+            self.nc_file.add_variable_with_type(
+                VAR_N_COMM_DATA_IDX,
+                &[DIM_NUM_N_CMAPS],
+                &self.metadata.int_type(),
+            )?;
+            if node_total > 0 {
+                self.nc_file.add_dimension(DIM_NCNT_CMAP, node_total)?;
+                let mut var = self.nc_file.add_variable_with_type(
+                    VAR_N_COMM_NIDS,
+                    &[DIM_NCNT_CMAP],
+                    &self.metadata.int_type(),
+                )?;
+                self.metadata.apply_compression(&mut var)?;
+                let mut var = self
+                    .nc_file
+                    .add_variable::<i32>(VAR_N_COMM_PROC, &[DIM_NCNT_CMAP])?;
+                self.metadata.apply_compression(&mut var)?;
+            }
+        }
+        if num_elem_cmaps > 0 {
+            self.nc_file.add_variable_with_type(
+                VAR_E_COMM_DATA_IDX,
+                &[DIM_NUM_E_CMAPS],
+                &self.metadata.int_type(),
+            )?;
+            if elem_total > 0 {
+                self.nc_file.add_dimension(DIM_ECNT_CMAP, elem_total)?;
+                let mut var = self.nc_file.add_variable_with_type(
+                    VAR_E_COMM_EIDS,
+                    &[DIM_ECNT_CMAP],
+                    &self.metadata.int_type(),
+                )?;
+                self.metadata.apply_compression(&mut var)?;
+                for name in [VAR_E_COMM_PROC, VAR_E_COMM_SIDS] {
+                    let mut var = self.nc_file.add_variable::<i32>(name, &[DIM_ECNT_CMAP])?;
+                    self.metadata.apply_compression(&mut var)?;
+                }
+            }
+        }
+
+        self.ensure_data_mode()?;
+        for (ids_var, stat_var, idx_var, ids, counts) in [
+            (
+                VAR_N_COMM_IDS,
+                VAR_N_COMM_STAT,
+                VAR_N_COMM_DATA_IDX,
+                &params.node_cmap_ids,
+                &params.node_cmap_node_counts,
+            ),
+            (
+                VAR_E_COMM_IDS,
+                VAR_E_COMM_STAT,
+                VAR_E_COMM_DATA_IDX,
+                &params.elem_cmap_ids,
+                &params.elem_cmap_elem_counts,
+            ),
+        ] {
+            if ids.is_empty() {
+                continue;
+            }
+            let status: Vec<i32> = counts.iter().map(|&c| i32::from(c > 0)).collect();
+            let ends: Vec<i64> = counts
+                .iter()
+                .scan(0_i64, |end, &c| {
+                    *end += c as i64;
+                    Some(*end)
+                })
+                .collect();
+            self.put_nemesis_i64s(ids_var, ids)?;
+            self.put_nemesis_ints(stat_var, &status)?;
+            self.put_nemesis_i64s(idx_var, &ends)?;
+        }
+        Ok(())
+    }
+
+    /// Write a node communication map
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No node communication map has this ID
+    /// - Array lengths differ from the size given in [`put_comm_map_params`](Self::put_comm_map_params)
+    /// - NetCDF write fails
+    pub fn put_node_comm_map(&mut self, map: &NodeCommMap) -> Result<()> {
+        let range = cmap_range(
+            &*self.nc_file,
+            VAR_N_COMM_IDS,
+            VAR_N_COMM_DATA_IDX,
+            "node communication map",
+            map.id,
+        )?;
+        check_len(range.len(), map.node_ids.len())?;
+        check_len(range.len(), map.proc_ids.len())?;
+        self.metadata
+            .check_int_range("node communication map", &map.node_ids)?;
+
+        self.ensure_data_mode()?;
+        self.put_nemesis_range(VAR_N_COMM_NIDS, &range, &map.node_ids)?;
+        self.put_nemesis_range(VAR_N_COMM_PROC, &range, &map.proc_ids)
+    }
+
+    /// Write an element communication map
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No element communication map has this ID
+    /// - Array lengths differ from the size given in [`put_comm_map_params`](Self::put_comm_map_params)
+    /// - NetCDF write fails
+    pub fn put_elem_comm_map(&mut self, map: &ElemCommMap) -> Result<()> {
+        let range = cmap_range(
+            &*self.nc_file,
+            VAR_E_COMM_IDS,
+            VAR_E_COMM_DATA_IDX,
+            "element communication map",
+            map.id,
+        )?;
+        check_len(range.len(), map.elem_ids.len())?;
+        check_len(range.len(), map.side_ids.len())?;
+        check_len(range.len(), map.proc_ids.len())?;
+        self.metadata
+            .check_int_range("element communication map", &map.elem_ids)?;
+
+        self.ensure_data_mode()?;
+        self.put_nemesis_range(VAR_E_COMM_EIDS, &range, &map.elem_ids)?;
+        self.put_nemesis_range(VAR_E_COMM_SIDS, &range, &map.side_ids)?;
+        self.put_nemesis_range(VAR_E_COMM_PROC, &range, &map.proc_ids)
+    }
+
+    /// Record the Nemesis file and API versions (define mode only)
+    fn put_nemesis_version(&mut self) -> Result<()> {
+        if self.nc_file.attribute(ATTR_NEM_FILE_VERSION).is_none() {
+            self.nc_file
+                .add_attribute(ATTR_NEM_FILE_VERSION, NEMESIS_FILE_VERSION)?;
+            self.nc_file
+                .add_attribute(ATTR_NEM_API_VERSION, API_VERSION)?;
+        }
+        Ok(())
+    }
+
+    /// Define a dimension unless it already exists (define mode only)
+    fn add_nemesis_dim(&mut self, name: &str, len: usize) -> Result<()> {
+        if self.nc_file.dimension(name).is_none() {
+            self.nc_file.add_dimension(name, len)?;
+        }
+        Ok(())
+    }
+
+    /// Write one processor map, checking it against its dimension
+    fn put_processor_map(&mut self, dim_name: &str, var_name: &str, map: &[i64]) -> Result<()> {
+        check_len(dim(&self.nc_file, dim_name), map.len())?;
+        if map.is_empty() {
+            return Ok(());
+        }
+        self.metadata.check_int_range(var_name, map)?;
+        self.ensure_data_mode()?;
+        self.put_nemesis_i64s(var_name, map)
+    }
+
+    fn put_nemesis_ints(&mut self, var_name: &str, values: &[i32]) -> Result<()> {
+        let mut var = self
+            .nc_file
+            .variable_mut(var_name)
+            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.to_string()))?;
+        var.put_values(values, ..)?;
+        Ok(())
+    }
+
+    fn put_nemesis_i64s(&mut self, var_name: &str, values: &[i64]) -> Result<()> {
+        let mut var = self
+            .nc_file
+            .variable_mut(var_name)
+            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.to_string()))?;
+        var.put_values(values, ..)?;
+        Ok(())
+    }
+
+    fn put_nemesis_range(
+        &mut self,
+        var_name: &str,
+        range: &Range<usize>,
+        values: &[i64],
+    ) -> Result<()> {
+        if range.is_empty() {
+            return Ok(());
+        }
+        let mut var = self
+            .nc_file
+            .variable_mut(var_name)
+            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.to_string()))?;
+        var.put_values(values, range.clone())?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "netcdf4")]
+fn to_i64(values: &[usize]) -> Vec<i64> {
+    values.iter().map(|&v| v as i64).collect()
+}
+
+#[cfg(feature = "netcdf4")]
+fn check_len(expected: usize, actual: usize) -> Result<()> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(ExodusError::InvalidArrayLength { expected, actual })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_range() {
+        let ends = [3, 3, 7];
+        assert_eq!(map_range(&ends, 0), 0..3);
+        assert_eq!(map_range(&ends, 1), 3..3);
+        assert_eq!(map_range(&ends, 2), 3..7);
+        assert_eq!(map_counts(&ends), vec![3, 0, 4]);
+        assert!(map_counts(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_global_param_names() {
+        let (ids, counts, df, dim) = global_param_names(EntityType::SideSet).unwrap();
+        assert_eq!(
+            (ids, counts, df, dim),
+            (
+                "ss_ids_global",
+                "ss_side_cnt_global",
+                Some("ss_df_cnt_global"),
+                "num_ss_global"
+            )
+        );
+        assert_eq!(global_param_names(EntityType::ElemBlock).unwrap().2, None);
+        assert!(global_param_names(EntityType::ElemSet).is_err());
+    }
+}
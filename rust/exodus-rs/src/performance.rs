@@ -7,6 +7,7 @@ use std::env;
 
 /// Type of compute node
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NodeType {
     /// Login node (shared, limited resources)
     Login,
@@ -134,6 +135,7 @@ impl NodeType {
 ///     .with_preemption(0.5);  // Favor writes
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CacheConfig {
     /// Size of chunk cache in bytes
     pub cache_size: usize,
@@ -284,6 +286,7 @@ impl Default for CacheConfig {
 ///     .with_node_chunk_size(10_000);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChunkConfig {
     /// Number of nodes per chunk (0 = auto-calculate)
     pub node_chunk_size: usize,
@@ -393,6 +396,7 @@ impl Default for ChunkConfig {
 /// let file = ExodusFile::create("mesh.exo", options)?;
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PerformanceConfig {
     /// Node type (auto-detected or manual)
     pub node_type: NodeType,
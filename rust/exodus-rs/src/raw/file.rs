@@ -0,0 +1,482 @@
+//! Handles, file creation and opening, and `ex_inquire`
+
+use crate::error::{ExodusError, Result};
+use crate::types::{CreateMode, CreateOptions, EntityType, FileFormat, FloatSize, Int64Mode};
+use crate::{mode, ExodusFile};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+/// `ex_open()`: open an existing file for appending
+pub const EX_WRITE: i32 = 0x0001;
+/// `ex_open()`: open an existing file for reading
+pub const EX_READ: i32 = 0x0002;
+/// `ex_create()`: fail if the file exists (the default)
+pub const EX_NOCLOBBER: i32 = 0x0004;
+/// `ex_create()`: overwrite the file if it exists
+pub const EX_CLOBBER: i32 = 0x0008;
+/// `ex_create()`: classic NetCDF-3 format
+pub const EX_NORMAL_MODEL: i32 = 0x0010;
+/// `ex_create()`: NetCDF-3 format with 64-bit offsets
+pub const EX_64BIT_OFFSET: i32 = 0x0020;
+/// `ex_create()`: HDF5-based NetCDF-4 format
+pub const EX_NETCDF4: i32 = 0x0040;
+/// `ex_create()`: CDF-5 format
+pub const EX_64BIT_DATA: i32 = 0x400000;
+/// `ex_create()`: store maps as 64-bit integers
+pub const EX_MAPS_INT64_DB: i32 = 0x0400;
+/// `ex_create()`: store entity IDs as 64-bit integers
+pub const EX_IDS_INT64_DB: i32 = 0x0800;
+/// `ex_create()`: store bulk integer data as 64-bit integers
+pub const EX_BULK_INT64_DB: i32 = 0x1000;
+/// `ex_create()`: store all integer data as 64-bit integers
+pub const EX_ALL_INT64_DB: i32 = EX_MAPS_INT64_DB | EX_IDS_INT64_DB | EX_BULK_INT64_DB;
+
+/// Open files, by handle
+static HANDLES: Mutex<Handles> = Mutex::new(Handles {
+    next: 1,
+    open: BTreeMap::new(),
+});
+
+#[derive(Debug)]
+struct Handles {
+    next: i32,
+    open: BTreeMap<i32, OpenHandle>,
+}
+
+/// A file behind a handle
+#[derive(Debug)]
+pub(crate) struct OpenHandle {
+    pub(crate) file: OpenFile,
+    /// Variable counts given to `ex_put_variable_param` whose names have not
+    /// been written yet
+    #[cfg(feature = "netcdf4")]
+    pub(crate) pending_vars: Vec<(EntityType, usize)>,
+}
+
+/// A file in the mode it was opened in
+#[derive(Debug)]
+pub(crate) enum OpenFile {
+    /// Opened with [`EX_READ`]
+    Read(ExodusFile<mode::Read>),
+    /// Created with [`ex_create`]
+    Write(ExodusFile<mode::Write>),
+    /// Opened with [`EX_WRITE`]
+    Append(ExodusFile<mode::Append>),
+}
+
+impl OpenFile {
+    /// Error for calling `function` on a file in this mode
+    pub(crate) fn unsupported(&self, function: &str) -> ExodusError {
+        match self {
+            OpenFile::Read(_) => ExodusError::WriteOnReadOnly,
+            OpenFile::Write(_) => ExodusError::ReadOnWriteOnly,
+            OpenFile::Append(_) => ExodusError::InvalidMode(format!(
+                "{} is not supported on files opened with EX_WRITE",
+                function
+            )),
+        }
+    }
+}
+
+fn handles() -> std::sync::MutexGuard<'static, Handles> {
+    HANDLES.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn register(file: OpenFile) -> i32 {
+    let mut handles = handles();
+    let exoid = handles.next;
+    handles.next += 1;
+    handles.open.insert(
+        exoid,
+        OpenHandle {
+            file,
+            #[cfg(feature = "netcdf4")]
+            pending_vars: Vec::new(),
+        },
+    );
+    exoid
+}
+
+/// Run `f` on the file behind a handle
+pub(crate) fn with_file<T>(exoid: i32, f: impl FnOnce(&mut OpenHandle) -> Result<T>) -> Result<T> {
+    let mut handles = handles();
+    let open = handles
+        .open
+        .get_mut(&exoid)
+        .ok_or(ExodusError::InvalidHandle(exoid))?;
+    f(open)
+}
+
+/// Open an existing file (`ex_open`)
+///
+/// `mode` is [`EX_READ`] or [`EX_WRITE`]; the latter opens the file for
+/// appending results.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened
+pub fn ex_open(path: impl AsRef<Path>, mode: i32) -> Result<i32> {
+    let file = if mode & EX_WRITE != 0 {
+        OpenFile::Append(ExodusFile::append(path)?)
+    } else {
+        OpenFile::Read(ExodusFile::open(path)?)
+    };
+    Ok(register(file))
+}
+
+/// Create a new file (`ex_create`)
+///
+/// `cmode` combines [`EX_CLOBBER`] or [`EX_NOCLOBBER`], a format flag and
+/// the `EX_*_INT64_DB` flags; without a format flag the file is NetCDF-4.
+/// As in C, integers are stored in 32 bits unless an `EX_*_INT64_DB` flag is
+/// given. `io_ws` is the size of stored floating-point values: 4, or 8 (also
+/// used for 0).
+///
+/// # Errors
+///
+/// Returns an error if `io_ws` is invalid or the file cannot be created
+pub fn ex_create(path: impl AsRef<Path>, cmode: i32, io_ws: i32) -> Result<i32> {
+    let float_size = match io_ws {
+        4 => FloatSize::Float32,
+        0 | 8 => FloatSize::Float64,
+        _ => {
+            return Err(ExodusError::Other(format!(
+                "Invalid I/O word size {}: expected 4 or 8",
+                io_ws
+            )))
+        }
+    };
+    let format = if cmode & EX_NETCDF4 != 0 {
+        FileFormat::NetCdf4
+    } else if cmode & EX_64BIT_DATA != 0 {
+        FileFormat::NetCdfCdf5
+    } else if cmode & EX_64BIT_OFFSET != 0 {
+        FileFormat::NetCdf364BitOffset
+    } else if cmode & EX_NORMAL_MODEL != 0 {
+        FileFormat::NetCdf3Classic
+    } else {
+        FileFormat::NetCdf4
+    };
+    let options = CreateOptions {
+        mode: if cmode & EX_CLOBBER != 0 {
+            CreateMode::Clobber
+        } else {
+            CreateMode::NoClobber
+        },
+        format,
+        float_size,
+        int64_mode: if cmode & EX_ALL_INT64_DB != 0 {
+            Int64Mode::Int64
+        } else {
+            Int64Mode::Int32
+        },
+        ..Default::default()
+    };
+    let file = ExodusFile::create(path, options)?;
+    Ok(register(OpenFile::Write(file)))
+}
+
+/// Close a file and release its handle (`ex_close`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open or closing fails
+pub fn ex_close(exoid: i32) -> Result<()> {
+    let open = handles()
+        .open
+        .remove(&exoid)
+        .ok_or(ExodusError::InvalidHandle(exoid))?;
+    match open.file {
+        OpenFile::Read(file) => file.close(),
+        OpenFile::Write(file) => file.close(),
+        OpenFile::Append(file) => file.close(),
+    }
+}
+
+/// Flush pending writes to disk (`ex_update`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open or writing fails
+#[cfg(feature = "netcdf4")]
+pub fn ex_update(exoid: i32) -> Result<()> {
+    with_file(exoid, |open| match &mut open.file {
+        OpenFile::Read(_) => Ok(()),
+        OpenFile::Write(file) => file.sync(),
+        OpenFile::Append(file) => file.sync(),
+    })
+}
+
+/// Quantities that can be queried with [`ex_inquire_int`]
+///
+/// The discriminants are the C `ex_inquiry` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExInquiry {
+    /// Version of the library that wrote the file (`EX_INQ_API_VERS`)
+    ApiVers = 2,
+    /// Version of the file format (`EX_INQ_DB_VERS`)
+    DbVers = 3,
+    /// Number of dimensions (`EX_INQ_DIM`)
+    Dim = 5,
+    /// Number of nodes (`EX_INQ_NODES`)
+    Nodes = 6,
+    /// Number of elements (`EX_INQ_ELEM`)
+    Elem = 7,
+    /// Number of element blocks (`EX_INQ_ELEM_BLK`)
+    ElemBlk = 8,
+    /// Number of node sets (`EX_INQ_NODE_SETS`)
+    NodeSets = 9,
+    /// Total length of the node set node lists (`EX_INQ_NS_NODE_LEN`)
+    NsNodeLen = 10,
+    /// Number of side sets (`EX_INQ_SIDE_SETS`)
+    SideSets = 11,
+    /// Total length of the side set element lists (`EX_INQ_SS_ELEM_LEN`)
+    SsElemLen = 13,
+    /// Number of QA records (`EX_INQ_QA`)
+    Qa = 14,
+    /// Number of info records (`EX_INQ_INFO`)
+    Info = 15,
+    /// Number of time steps (`EX_INQ_TIME`)
+    Time = 16,
+    /// Total length of the node set distribution factor lists (`EX_INQ_NS_DF_LEN`)
+    NsDfLen = 20,
+    /// Total length of the side set distribution factor lists (`EX_INQ_SS_DF_LEN`)
+    SsDfLen = 21,
+    /// Number of element maps (`EX_INQ_ELEM_MAP`)
+    ElemMap = 25,
+    /// Number of node maps (`EX_INQ_NODE_MAP`)
+    NodeMap = 26,
+    /// Number of edges (`EX_INQ_EDGE`)
+    Edge = 27,
+    /// Number of edge blocks (`EX_INQ_EDGE_BLK`)
+    EdgeBlk = 28,
+    /// Number of edge sets (`EX_INQ_EDGE_SETS`)
+    EdgeSets = 29,
+    /// Total length of the edge set lists (`EX_INQ_ES_LEN`)
+    EsLen = 30,
+    /// Total length of the edge set distribution factor lists (`EX_INQ_ES_DF_LEN`)
+    EsDfLen = 31,
+    /// Number of faces (`EX_INQ_FACE`)
+    Face = 34,
+    /// Number of face blocks (`EX_INQ_FACE_BLK`)
+    FaceBlk = 35,
+    /// Number of face sets (`EX_INQ_FACE_SETS`)
+    FaceSets = 36,
+    /// Total length of the face set lists (`EX_INQ_FS_LEN`)
+    FsLen = 37,
+    /// Total length of the face set distribution factor lists (`EX_INQ_FS_DF_LEN`)
+    FsDfLen = 38,
+    /// Number of element sets (`EX_INQ_ELEM_SETS`)
+    ElemSets = 41,
+    /// Total length of the element set lists (`EX_INQ_ELS_LEN`)
+    ElsLen = 42,
+    /// Total length of the element set distribution factor lists (`EX_INQ_ELS_DF_LEN`)
+    ElsDfLen = 43,
+    /// Number of edge maps (`EX_INQ_EDGE_MAP`)
+    EdgeMap = 45,
+    /// Number of face maps (`EX_INQ_FACE_MAP`)
+    FaceMap = 46,
+    /// Size in bytes of stored floating-point values (`EX_INQ_DB_FLOAT_SIZE`)
+    DbFloatSize = 51,
+    /// Number of assemblies (`EX_INQ_ASSEMBLY`)
+    Assembly = 60,
+    /// Number of blobs (`EX_INQ_BLOB`)
+    Blob = 61,
+    /// Number of nodal variables (`EX_INQ_NUM_NODE_VAR`)
+    NumNodeVar = 62,
+    /// Number of edge block variables (`EX_INQ_NUM_EDGE_BLOCK_VAR`)
+    NumEdgeBlockVar = 63,
+    /// Number of face block variables (`EX_INQ_NUM_FACE_BLOCK_VAR`)
+    NumFaceBlockVar = 64,
+    /// Number of element block variables (`EX_INQ_NUM_ELEM_BLOCK_VAR`)
+    NumElemBlockVar = 65,
+    /// Number of node set variables (`EX_INQ_NUM_NODE_SET_VAR`)
+    NumNodeSetVar = 66,
+    /// Number of edge set variables (`EX_INQ_NUM_EDGE_SET_VAR`)
+    NumEdgeSetVar = 67,
+    /// Number of face set variables (`EX_INQ_NUM_FACE_SET_VAR`)
+    NumFaceSetVar = 68,
+    /// Number of element set variables (`EX_INQ_NUM_ELEM_SET_VAR`)
+    NumElemSetVar = 69,
+    /// Number of side set variables (`EX_INQ_NUM_SIDE_SET_VAR`)
+    NumSideSetVar = 70,
+    /// Number of global variables (`EX_INQ_NUM_GLOBAL_VAR`)
+    NumGlobalVar = 71,
+}
+
+/// Query an integer property of a file (`ex_inquire_int`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open or the file cannot be read
+pub fn ex_inquire_int(exoid: i32, request: ExInquiry) -> Result<i64> {
+    use ExInquiry::*;
+
+    let value = match request {
+        Qa => dispatch!(exoid, read, "ex_inquire_int", |file| Ok(file
+            .qa_records()?
+            .len()))?,
+        Info => dispatch!(exoid, read, "ex_inquire_int", |file| Ok(file
+            .info_records()?
+            .len()))?,
+        Time => dispatch!(exoid, readable, "ex_inquire_int", |file| Ok(file
+            .times()?
+            .len()))?,
+        DbFloatSize => dispatch!(exoid, any, "ex_inquire_int", |file| Ok(
+            match file.float_size() {
+                FloatSize::Float32 => 4,
+                FloatSize::Float64 => 8,
+            }
+        ))?,
+        NsNodeLen => set_list_len(exoid, EntityType::NodeSet, false)?,
+        NsDfLen => set_list_len(exoid, EntityType::NodeSet, true)?,
+        SsElemLen => set_list_len(exoid, EntityType::SideSet, false)?,
+        SsDfLen => set_list_len(exoid, EntityType::SideSet, true)?,
+        EsLen => set_list_len(exoid, EntityType::EdgeSet, false)?,
+        EsDfLen => set_list_len(exoid, EntityType::EdgeSet, true)?,
+        FsLen => set_list_len(exoid, EntityType::FaceSet, false)?,
+        FsDfLen => set_list_len(exoid, EntityType::FaceSet, true)?,
+        ElsLen => set_list_len(exoid, EntityType::ElemSet, false)?,
+        ElsDfLen => set_list_len(exoid, EntityType::ElemSet, true)?,
+        NumNodeVar => num_vars(exoid, EntityType::Nodal)?,
+        NumEdgeBlockVar => num_vars(exoid, EntityType::EdgeBlock)?,
+        NumFaceBlockVar => num_vars(exoid, EntityType::FaceBlock)?,
+        NumElemBlockVar => num_vars(exoid, EntityType::ElemBlock)?,
+        NumNodeSetVar => num_vars(exoid, EntityType::NodeSet)?,
+        NumEdgeSetVar => num_vars(exoid, EntityType::EdgeSet)?,
+        NumFaceSetVar => num_vars(exoid, EntityType::FaceSet)?,
+        NumElemSetVar => num_vars(exoid, EntityType::ElemSet)?,
+        NumSideSetVar => num_vars(exoid, EntityType::SideSet)?,
+        NumGlobalVar => num_vars(exoid, EntityType::Global)?,
+        ApiVers | DbVers => {
+            return Err(ExodusError::Other(format!(
+                "{:?} is a floating-point quantity; use ex_inquire_float",
+                request
+            )))
+        }
+        _ => {
+            let params = dispatch!(exoid, any, "ex_inquire_int", |file| file.init_params())?;
+            match request {
+                Dim => params.num_dim,
+                Nodes => params.num_nodes,
+                Elem => params.num_elems,
+                ElemBlk => params.num_elem_blocks,
+                NodeSets => params.num_node_sets,
+                SideSets => params.num_side_sets,
+                ElemMap => params.num_elem_maps,
+                NodeMap => params.num_node_maps,
+                Edge => params.num_edges,
+                EdgeBlk => params.num_edge_blocks,
+                EdgeSets => params.num_edge_sets,
+                Face => params.num_faces,
+                FaceBlk => params.num_face_blocks,
+                FaceSets => params.num_face_sets,
+                ElemSets => params.num_elem_sets,
+                Assembly => params.num_assemblies,
+                Blob => params.num_blobs,
+                _ => unreachable!(),
+            }
+        }
+    };
+    Ok(value as i64)
+}
+
+/// Query a floating-point property of a file (`ex_inquire` with `ret_float`)
+///
+/// Versions are returned as written by the C library (e.g. `9.04`); integer
+/// quantities are converted.
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open or the file cannot be read
+pub fn ex_inquire_float(exoid: i32, request: ExInquiry) -> Result<f32> {
+    match request {
+        ExInquiry::ApiVers => Ok(crate::utils::constants::API_VERSION),
+        ExInquiry::DbVers => {
+            let (major, minor) = dispatch!(exoid, any, "ex_inquire_float", |file| file.version())?;
+            Ok(major as f32 + minor as f32 / 10.0)
+        }
+        _ => Ok(ex_inquire_int(exoid, request)? as f32),
+    }
+}
+
+/// Total number of entries, or distribution factors, in the sets of a type
+fn set_list_len(exoid: i32, set_type: EntityType, dist_factors: bool) -> Result<usize> {
+    dispatch!(exoid, any, "ex_inquire_int", |file| {
+        let mut len = 0;
+        for id in file.set_ids(set_type)? {
+            let set = file.set(set_type, id)?;
+            len += if dist_factors {
+                set.num_dist_factors
+            } else {
+                set.num_entries
+            };
+        }
+        Ok(len)
+    })
+}
+
+fn num_vars(exoid: i32, var_type: EntityType) -> Result<usize> {
+    dispatch!(exoid, any, "ex_inquire_int", |file| Ok(file
+        .variable_names(var_type)?
+        .len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data_path(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("data")
+            .join(name)
+    }
+
+    #[test]
+    fn test_open_inquire_close() {
+        let exoid = ex_open(data_path("two_blocks.e"), EX_READ).unwrap();
+        assert_eq!(ex_inquire_int(exoid, ExInquiry::Dim).unwrap(), 3);
+        assert_eq!(ex_inquire_int(exoid, ExInquiry::Nodes).unwrap(), 2541);
+        assert_eq!(ex_inquire_int(exoid, ExInquiry::Elem).unwrap(), 2000);
+        assert_eq!(ex_inquire_int(exoid, ExInquiry::ElemBlk).unwrap(), 2);
+        assert_eq!(ex_inquire_int(exoid, ExInquiry::SideSets).unwrap(), 2);
+        assert_eq!(ex_inquire_int(exoid, ExInquiry::Time).unwrap(), 0);
+        assert!(ex_inquire_int(exoid, ExInquiry::Qa).unwrap() > 0);
+        assert!(ex_inquire_float(exoid, ExInquiry::DbVers).unwrap() >= 2.0);
+        assert_eq!(ex_inquire_float(exoid, ExInquiry::Dim).unwrap(), 3.0);
+        assert!(ex_inquire_int(exoid, ExInquiry::DbVers).is_err());
+        assert!(
+            ex_inquire_int(exoid, ExInquiry::SsElemLen).unwrap()
+                >= ex_inquire_int(exoid, ExInquiry::SideSets).unwrap()
+        );
+
+        ex_close(exoid).unwrap();
+        assert!(matches!(
+            ex_inquire_int(exoid, ExInquiry::Dim),
+            Err(ExodusError::InvalidHandle(id)) if id == exoid
+        ));
+        assert!(matches!(
+            ex_close(exoid),
+            Err(ExodusError::InvalidHandle(_))
+        ));
+    }
+
+    #[test]
+    fn test_handles_are_distinct() {
+        let path = data_path("two_blocks.e");
+        let first = ex_open(&path, EX_READ).unwrap();
+        let second = ex_open(&path, EX_READ).unwrap();
+        assert_ne!(first, second);
+        ex_close(first).unwrap();
+        assert_eq!(ex_inquire_int(second, ExInquiry::ElemBlk).unwrap(), 2);
+        ex_close(second).unwrap();
+    }
+
+    #[test]
+    fn test_open_missing_file() {
+        assert!(ex_open(data_path("absent.e"), EX_READ).is_err());
+    }
+}
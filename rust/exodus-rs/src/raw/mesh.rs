@@ -0,0 +1,696 @@
+//! Initialization, coordinates, blocks, sets, maps, names, properties, and
+//! QA and info records
+
+use crate::error::{EntityId, ExodusError, Result};
+#[cfg(feature = "netcdf4")]
+use crate::types::Set;
+use crate::types::{Block, EntityType, InitParams, QaRecord};
+
+// ============================================================================
+// Initialization
+// ============================================================================
+
+/// Read the title and entity counts (`ex_get_init_ext`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open or the file cannot be read
+pub fn ex_get_init(exoid: i32) -> Result<InitParams> {
+    dispatch!(exoid, any, "ex_get_init", |file| file.init_params())
+}
+
+/// Write the title and the basic entity counts (`ex_put_init`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create` or the
+/// file is already initialized
+#[cfg(feature = "netcdf4")]
+#[allow(clippy::too_many_arguments)]
+pub fn ex_put_init(
+    exoid: i32,
+    title: &str,
+    num_dim: usize,
+    num_nodes: usize,
+    num_elem: usize,
+    num_elem_blk: usize,
+    num_node_sets: usize,
+    num_side_sets: usize,
+) -> Result<()> {
+    ex_put_init_ext(
+        exoid,
+        &InitParams {
+            title: title.to_string(),
+            num_dim,
+            num_nodes,
+            num_elems: num_elem,
+            num_elem_blocks: num_elem_blk,
+            num_node_sets,
+            num_side_sets,
+            ..Default::default()
+        },
+    )
+}
+
+/// Write the title and all entity counts (`ex_put_init_ext`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create` or the
+/// file is already initialized
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_init_ext(exoid: i32, params: &InitParams) -> Result<()> {
+    dispatch!(exoid, create, "ex_put_init_ext", |file| file.init(params))
+}
+
+// ============================================================================
+// Coordinates
+// ============================================================================
+
+/// Read the nodal coordinates (`ex_get_coord`)
+///
+/// Returns the x, y and z coordinates; those beyond the spatial dimension
+/// are empty.
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open or the file cannot be read
+pub fn ex_get_coord(exoid: i32) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    let coords = dispatch!(exoid, readable, "ex_get_coord", |file| file.coords::<f64>())?;
+    Ok((coords.x, coords.y, coords.z))
+}
+
+/// Write the nodal coordinates (`ex_put_coord`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is open for reading or the lengths do not
+/// match the number of nodes
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_coord(exoid: i32, x: &[f64], y: Option<&[f64]>, z: Option<&[f64]>) -> Result<()> {
+    dispatch!(exoid, writable, "ex_put_coord", |file| file
+        .put_coords(x, y, z))
+}
+
+/// Read the coordinate names (`ex_get_coord_names`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not opened with `EX_READ` or the file
+/// cannot be read
+pub fn ex_get_coord_names(exoid: i32) -> Result<Vec<String>> {
+    dispatch!(exoid, read, "ex_get_coord_names", |file| file.coord_names())
+}
+
+/// Write the coordinate names (`ex_put_coord_names`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create`
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_coord_names(exoid: i32, names: &[&str]) -> Result<()> {
+    dispatch!(exoid, create, "ex_put_coord_names", |file| file
+        .put_coord_names(names))
+}
+
+// ============================================================================
+// Blocks
+// ============================================================================
+
+/// IDs of the blocks, sets, assemblies or blobs of a type (`ex_get_ids`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open, `obj_type` has no IDs, or the
+/// file cannot be read
+pub fn ex_get_ids(exoid: i32, obj_type: EntityType) -> Result<Vec<EntityId>> {
+    match obj_type {
+        EntityType::ElemBlock | EntityType::EdgeBlock | EntityType::FaceBlock => {
+            dispatch!(exoid, any, "ex_get_ids", |file| file.block_ids(obj_type))
+        }
+        EntityType::Assembly => dispatch!(exoid, read, "ex_get_ids", |file| file.assembly_ids()),
+        EntityType::Blob => dispatch!(exoid, read, "ex_get_ids", |file| file.blob_ids()),
+        _ => dispatch!(exoid, any, "ex_get_ids", |file| file.set_ids(obj_type)),
+    }
+}
+
+/// Read the parameters of a block (`ex_get_block`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open or the block does not exist
+pub fn ex_get_block(exoid: i32, blk_type: EntityType, blk_id: EntityId) -> Result<Block> {
+    let block = dispatch!(exoid, any, "ex_get_block", |file| file.block(blk_id))?;
+    if block.entity_type != blk_type {
+        return Err(ExodusError::EntityNotFound {
+            entity_type: blk_type.to_string(),
+            id: blk_id,
+        });
+    }
+    Ok(block)
+}
+
+/// Define a block (`ex_put_block`)
+///
+/// `entry_descrip` is the topology name, e.g. `"HEX8"`.
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create` or the
+/// block cannot be defined
+#[cfg(feature = "netcdf4")]
+#[allow(clippy::too_many_arguments)]
+pub fn ex_put_block(
+    exoid: i32,
+    blk_type: EntityType,
+    blk_id: EntityId,
+    entry_descrip: &str,
+    num_entries_this_blk: usize,
+    num_nodes_per_entry: usize,
+    num_edges_per_entry: usize,
+    num_faces_per_entry: usize,
+    num_attr_per_entry: usize,
+) -> Result<()> {
+    let block = Block {
+        id: blk_id,
+        entity_type: blk_type,
+        topology: entry_descrip.to_string(),
+        num_entries: num_entries_this_blk,
+        num_nodes_per_entry,
+        num_edges_per_entry,
+        num_faces_per_entry,
+        num_attributes: num_attr_per_entry,
+    };
+    dispatch!(exoid, create, "ex_put_block", |file| file.put_block(&block))
+}
+
+/// Read the node connectivity of an element block (`ex_get_conn`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open, `blk_type` is not
+/// [`EntityType::ElemBlock`], or the block does not exist
+pub fn ex_get_conn(exoid: i32, blk_type: EntityType, blk_id: EntityId) -> Result<Vec<i64>> {
+    element_blocks_only(blk_type)?;
+    dispatch!(exoid, any, "ex_get_conn", |file| file.connectivity(blk_id))
+}
+
+/// Write the node connectivity of an element block (`ex_put_conn`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create`,
+/// `blk_type` is not [`EntityType::ElemBlock`], or the length does not match
+/// the block
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_conn(
+    exoid: i32,
+    blk_type: EntityType,
+    blk_id: EntityId,
+    node_conn: &[i64],
+) -> Result<()> {
+    element_blocks_only(blk_type)?;
+    dispatch!(exoid, create, "ex_put_conn", |file| file
+        .put_connectivity(blk_id, node_conn))
+}
+
+/// Read the attributes of an element block (`ex_get_attr`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open, `blk_type` is not
+/// [`EntityType::ElemBlock`], or the block does not exist
+pub fn ex_get_attr(exoid: i32, blk_type: EntityType, blk_id: EntityId) -> Result<Vec<f64>> {
+    element_blocks_only(blk_type)?;
+    dispatch!(exoid, any, "ex_get_attr", |file| file
+        .block_attributes(blk_id))
+}
+
+/// Write the attributes of an element block (`ex_put_attr`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create`,
+/// `blk_type` is not [`EntityType::ElemBlock`], or the length does not match
+/// the block
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_attr(
+    exoid: i32,
+    blk_type: EntityType,
+    blk_id: EntityId,
+    attrib: &[f64],
+) -> Result<()> {
+    element_blocks_only(blk_type)?;
+    dispatch!(exoid, create, "ex_put_attr", |file| file
+        .put_block_attributes(blk_id, attrib))
+}
+
+/// Read the attribute names of an element block (`ex_get_attr_names`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open, `blk_type` is not
+/// [`EntityType::ElemBlock`], or the block does not exist
+pub fn ex_get_attr_names(
+    exoid: i32,
+    blk_type: EntityType,
+    blk_id: EntityId,
+) -> Result<Vec<String>> {
+    element_blocks_only(blk_type)?;
+    dispatch!(exoid, any, "ex_get_attr_names", |file| file
+        .block_attribute_names(blk_id))
+}
+
+/// Write the attribute names of an element block (`ex_put_attr_names`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create`,
+/// `blk_type` is not [`EntityType::ElemBlock`], or the block does not exist
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_attr_names(
+    exoid: i32,
+    blk_type: EntityType,
+    blk_id: EntityId,
+    names: &[&str],
+) -> Result<()> {
+    element_blocks_only(blk_type)?;
+    dispatch!(exoid, create, "ex_put_attr_names", |file| file
+        .put_block_attribute_names(blk_id, names))
+}
+
+/// Connectivity and attributes are only stored for element blocks
+fn element_blocks_only(blk_type: EntityType) -> Result<()> {
+    match blk_type {
+        EntityType::ElemBlock => Ok(()),
+        _ => Err(ExodusError::InvalidEntityType(format!(
+            "Expected elem_block, got {}",
+            blk_type
+        ))),
+    }
+}
+
+// ============================================================================
+// Sets
+// ============================================================================
+
+/// Read the number of entries and distribution factors of a set
+/// (`ex_get_set_param`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open or the set does not exist
+pub fn ex_get_set_param(
+    exoid: i32,
+    set_type: EntityType,
+    set_id: EntityId,
+) -> Result<(usize, usize)> {
+    let set = dispatch!(exoid, any, "ex_get_set_param", |file| file
+        .set(set_type, set_id))?;
+    Ok((set.num_entries, set.num_dist_factors))
+}
+
+/// Define a set (`ex_put_set_param`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create` or the
+/// set cannot be defined
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_set_param(
+    exoid: i32,
+    set_type: EntityType,
+    set_id: EntityId,
+    num_entries_in_set: usize,
+    num_dist_fact_in_set: usize,
+) -> Result<()> {
+    let set = Set {
+        id: set_id,
+        entity_type: set_type,
+        num_entries: num_entries_in_set,
+        num_dist_factors: num_dist_fact_in_set,
+    };
+    dispatch!(exoid, create, "ex_put_set_param", |file| file.put_set(&set))
+}
+
+/// Read the entries of a set (`ex_get_set`)
+///
+/// Returns the entry list and, for side sets, the side list.
+///
+/// # Errors
+///
+/// Returns an error if the handle was not opened with `EX_READ` or the set
+/// does not exist
+pub fn ex_get_set(
+    exoid: i32,
+    set_type: EntityType,
+    set_id: EntityId,
+) -> Result<(Vec<i64>, Option<Vec<i64>>)> {
+    dispatch!(exoid, read, "ex_get_set", |file| match set_type {
+        EntityType::NodeSet => Ok((file.node_set(set_id)?.nodes, None)),
+        EntityType::SideSet => {
+            let set = file.side_set(set_id)?;
+            Ok((set.elements, Some(set.sides)))
+        }
+        _ => Ok((file.entity_set(set_type, set_id)?.entities, None)),
+    })
+}
+
+/// Write the entries of a set (`ex_put_set`)
+///
+/// `set_extra_list` holds the sides of a side set and is ignored for other
+/// sets. Distribution factors cannot be written through this API; use
+/// [`ExodusFile::put_node_set`](crate::ExodusFile::put_node_set) or
+/// [`ExodusFile::put_side_set`](crate::ExodusFile::put_side_set).
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create`, a side
+/// set is given no side list, or the lengths do not match
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_set(
+    exoid: i32,
+    set_type: EntityType,
+    set_id: EntityId,
+    set_entry_list: &[i64],
+    set_extra_list: Option<&[i64]>,
+) -> Result<()> {
+    dispatch!(exoid, create, "ex_put_set", |file| match set_type {
+        EntityType::NodeSet => file.put_node_set(set_id, set_entry_list, None),
+        EntityType::SideSet => {
+            let sides = set_extra_list.ok_or_else(|| {
+                ExodusError::Other(format!("Side set {} requires a side list", set_id))
+            })?;
+            file.put_side_set(set_id, set_entry_list, sides, None)
+        }
+        _ => file.put_entity_set(set_type, set_id, set_entry_list),
+    })
+}
+
+/// Read the distribution factors of a node or side set
+/// (`ex_get_set_dist_fact`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not opened with `EX_READ`, `set_type`
+/// is not a node or side set, or the set does not exist
+pub fn ex_get_set_dist_fact(
+    exoid: i32,
+    set_type: EntityType,
+    set_id: EntityId,
+) -> Result<Vec<f64>> {
+    dispatch!(exoid, read, "ex_get_set_dist_fact", |file| match set_type {
+        EntityType::NodeSet => Ok(file.node_set(set_id)?.dist_factors),
+        EntityType::SideSet => Ok(file.side_set(set_id)?.dist_factors),
+        _ => Err(ExodusError::InvalidEntityType(format!(
+            "Expected node_set or side_set, got {}",
+            set_type
+        ))),
+    })
+}
+
+// ============================================================================
+// Maps
+// ============================================================================
+
+/// Read an ID map (`ex_get_id_map`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not opened with `EX_READ` or the file
+/// stores no such map
+pub fn ex_get_id_map(exoid: i32, map_type: EntityType) -> Result<Vec<i64>> {
+    dispatch!(exoid, read, "ex_get_id_map", |file| file.id_map(map_type))
+}
+
+/// Write an ID map (`ex_put_id_map`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create` or the
+/// length does not match the number of entities
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_id_map(exoid: i32, map_type: EntityType, map: &[i64]) -> Result<()> {
+    dispatch!(exoid, create, "ex_put_id_map", |file| file
+        .put_id_map(map_type, map))
+}
+
+// ============================================================================
+// Names
+// ============================================================================
+
+/// Read the names of all blocks or sets of a type (`ex_get_names`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not opened with `EX_READ` or the file
+/// stores no names for the type
+pub fn ex_get_names(exoid: i32, obj_type: EntityType) -> Result<Vec<String>> {
+    dispatch!(exoid, read, "ex_get_names", |file| file.names(obj_type))
+}
+
+/// Write the names of all blocks or sets of a type (`ex_put_names`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create` or the
+/// number of names does not match
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_names(exoid: i32, obj_type: EntityType, names: &[&str]) -> Result<()> {
+    dispatch!(exoid, create, "ex_put_names", |file| file
+        .put_names(obj_type, names))
+}
+
+/// Read the name of one block or set (`ex_get_name`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not opened with `EX_READ` or the
+/// entity does not exist
+pub fn ex_get_name(exoid: i32, obj_type: EntityType, entity_id: EntityId) -> Result<String> {
+    let index = entity_index(exoid, obj_type, entity_id)?;
+    dispatch!(exoid, read, "ex_get_name", |file| file
+        .name(obj_type, index))
+}
+
+/// Write the name of one block or set (`ex_put_name`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create` or the
+/// entity does not exist
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_name(
+    exoid: i32,
+    obj_type: EntityType,
+    entity_id: EntityId,
+    name: &str,
+) -> Result<()> {
+    let index = entity_index(exoid, obj_type, entity_id)?;
+    dispatch!(exoid, create, "ex_put_name", |file| file
+        .put_name(obj_type, index, name))
+}
+
+/// Storage position of a block or set from its ID
+fn entity_index(exoid: i32, obj_type: EntityType, entity_id: EntityId) -> Result<usize> {
+    ex_get_ids(exoid, obj_type)?
+        .iter()
+        .position(|&id| id == entity_id)
+        .ok_or_else(|| ExodusError::EntityNotFound {
+            entity_type: obj_type.to_string(),
+            id: entity_id,
+        })
+}
+
+// ============================================================================
+// Properties
+// ============================================================================
+
+/// Read the names of the integer properties of a type (`ex_get_prop_names`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not opened with `EX_READ` or the type
+/// has no properties
+pub fn ex_get_prop_names(exoid: i32, obj_type: EntityType) -> Result<Vec<String>> {
+    dispatch!(exoid, read, "ex_get_prop_names", |file| file
+        .property_names(obj_type))
+}
+
+/// Read a property of one block or set (`ex_get_prop`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not opened with `EX_READ` or the
+/// property or entity does not exist
+pub fn ex_get_prop(
+    exoid: i32,
+    obj_type: EntityType,
+    obj_id: EntityId,
+    prop_name: &str,
+) -> Result<i64> {
+    dispatch!(exoid, read, "ex_get_prop", |file| file
+        .property(obj_type, obj_id, prop_name))
+}
+
+/// Write a property of one block or set (`ex_put_prop`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create` or the
+/// entity does not exist
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_prop(
+    exoid: i32,
+    obj_type: EntityType,
+    obj_id: EntityId,
+    prop_name: &str,
+    value: i64,
+) -> Result<()> {
+    dispatch!(exoid, create, "ex_put_prop", |file| file
+        .put_property(obj_type, obj_id, prop_name, value))
+}
+
+/// Read a property of all blocks or sets of a type (`ex_get_prop_array`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not opened with `EX_READ` or the
+/// property does not exist
+pub fn ex_get_prop_array(exoid: i32, obj_type: EntityType, prop_name: &str) -> Result<Vec<i64>> {
+    dispatch!(exoid, read, "ex_get_prop_array", |file| file
+        .property_array(obj_type, prop_name))
+}
+
+/// Write a property of all blocks or sets of a type (`ex_put_prop_array`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create` or the
+/// number of values does not match
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_prop_array(
+    exoid: i32,
+    obj_type: EntityType,
+    prop_name: &str,
+    values: &[i64],
+) -> Result<()> {
+    dispatch!(exoid, create, "ex_put_prop_array", |file| file
+        .put_property_array(obj_type, prop_name, values))
+}
+
+// ============================================================================
+// QA and Info Records
+// ============================================================================
+
+/// Read the QA records (`ex_get_qa`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not opened with `EX_READ` or the file
+/// cannot be read
+pub fn ex_get_qa(exoid: i32) -> Result<Vec<QaRecord>> {
+    dispatch!(exoid, read, "ex_get_qa", |file| file.qa_records())
+}
+
+/// Write the QA records (`ex_put_qa`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create` or a
+/// field is too long
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_qa(exoid: i32, qa_records: &[QaRecord]) -> Result<()> {
+    dispatch!(exoid, create, "ex_put_qa", |file| file
+        .put_qa_records(qa_records))
+}
+
+/// Read the info records (`ex_get_info`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not opened with `EX_READ` or the file
+/// cannot be read
+pub fn ex_get_info(exoid: i32) -> Result<Vec<String>> {
+    dispatch!(exoid, read, "ex_get_info", |file| file.info_records())
+}
+
+/// Write the info records (`ex_put_info`)
+///
+/// # Errors
+///
+/// Returns an error if the handle was not created with `ex_create` or a
+/// record is too long
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_info(exoid: i32, info: &[String]) -> Result<()> {
+    dispatch!(exoid, create, "ex_put_info", |file| file
+        .put_info_records(info))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::{ex_close, ex_open, EX_READ};
+    use std::path::Path;
+
+    fn open(name: &str) -> i32 {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("data")
+            .join(name);
+        ex_open(path, EX_READ).unwrap()
+    }
+
+    #[test]
+    fn test_read_mesh() {
+        let exoid = open("two_blocks.e");
+        let params = ex_get_init(exoid).unwrap();
+        assert_eq!(params.num_nodes, 2541);
+
+        let (x, y, z) = ex_get_coord(exoid).unwrap();
+        assert_eq!((x.len(), y.len(), z.len()), (2541, 2541, 2541));
+
+        let ids = ex_get_ids(exoid, EntityType::ElemBlock).unwrap();
+        assert_eq!(ids.len(), 2);
+        let block = ex_get_block(exoid, EntityType::ElemBlock, ids[0]).unwrap();
+        assert_eq!(block.topology, "HEX8");
+        assert_eq!(block.num_entries, 1000);
+        let conn = ex_get_conn(exoid, EntityType::ElemBlock, ids[0]).unwrap();
+        assert_eq!(conn.len(), 8000);
+        assert!(ex_get_conn(exoid, EntityType::EdgeBlock, ids[0]).is_err());
+        assert!(matches!(
+            ex_get_block(exoid, EntityType::FaceBlock, ids[0]),
+            Err(ExodusError::EntityNotFound { .. })
+        ));
+
+        let map = ex_get_id_map(exoid, EntityType::ElemMap).unwrap();
+        assert_eq!(map.len(), 2000);
+        assert!(!ex_get_qa(exoid).unwrap().is_empty());
+        ex_close(exoid).unwrap();
+    }
+
+    #[test]
+    fn test_read_sets() {
+        let exoid = open("two_blocks.e");
+        let ids = ex_get_ids(exoid, EntityType::SideSet).unwrap();
+        assert_eq!(ids.len(), 2);
+        let (num_entries, num_df) = ex_get_set_param(exoid, EntityType::SideSet, ids[0]).unwrap();
+        let (elements, sides) = ex_get_set(exoid, EntityType::SideSet, ids[0]).unwrap();
+        assert_eq!(elements.len(), num_entries);
+        assert_eq!(sides.unwrap().len(), num_entries);
+        let df = ex_get_set_dist_fact(exoid, EntityType::SideSet, ids[0]).unwrap();
+        assert_eq!(df.len(), num_df);
+
+        let names = ex_get_names(exoid, EntityType::SideSet).unwrap();
+        assert_eq!(
+            ex_get_name(exoid, EntityType::SideSet, ids[1]).unwrap(),
+            names[1]
+        );
+        assert!(matches!(
+            ex_get_name(exoid, EntityType::SideSet, -1),
+            Err(ExodusError::EntityNotFound { .. })
+        ));
+        ex_close(exoid).unwrap();
+    }
+}
@@ -4,3 +4,106 @@
 //! It is intended for users migrating from C code or who need maximum control.
 //!
 //! For most users, the high-level API in the root module is recommended.
+//!
+//! # Conventions
+//!
+//! Files are referred to by integer handles returned from [`ex_open`] and
+//! [`ex_create`] and released with [`ex_close`], and the functions carry the
+//! names of their C counterparts. Values the C functions return through
+//! pointer arguments are returned instead, and `NULL` arguments become
+//! `None`. Status codes are replaced by [`Result`](crate::Result).
+//!
+//! As in C, time steps and variable indices start at 1. Entity types are
+//! given as [`EntityType`](crate::EntityType) in place of `ex_entity_type`.
+//!
+//! Every function is implemented on top of the [`ExodusFile`] methods, so
+//! what a handle supports follows its open mode: reading mesh definitions
+//! and results works in every mode, while the set lists, ID maps, names,
+//! properties and QA and info records are read from files opened with
+//! [`EX_READ`] and written to files created with [`ex_create`]. Results can
+//! be appended to files opened with [`EX_WRITE`]. Without the `netcdf4`
+//! feature only [`EX_READ`] is supported and the writing functions are not
+//! compiled.
+//!
+//! Calls through handles are serialized by a process-wide lock.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use exodus_rs::raw::*;
+//! use exodus_rs::EntityType;
+//!
+//! let exoid = ex_open("results.e", EX_READ)?;
+//! let num_steps = ex_inquire_int(exoid, ExInquiry::Time)?;
+//! let num_vars = ex_get_variable_param(exoid, EntityType::Nodal)?;
+//! for step in 1..=num_steps as usize {
+//!     let time = ex_get_time(exoid, step)?;
+//!     for var in 1..=num_vars {
+//!         let values = ex_get_var(exoid, step, EntityType::Nodal, var, 0)?;
+//!         println!("{} {} {:?}", time, var, &values[..3]);
+//!     }
+//! }
+//! ex_close(exoid)?;
+//! # Ok::<(), exodus_rs::ExodusError>(())
+//! ```
+//!
+//! [`ExodusFile`]: crate::ExodusFile
+
+/// Evaluate `$body` with `$file` bound to the file behind a handle, for the
+/// handles whose open mode supports it:
+///
+/// - `any`: every mode
+/// - `readable`: [`EX_READ`] and [`EX_WRITE`] handles
+/// - `read`: [`EX_READ`] handles
+/// - `writable`: [`ex_create`] and [`EX_WRITE`] handles
+/// - `create`: [`ex_create`] handles
+///
+/// Handles in other modes give an error naming `$function`.
+macro_rules! dispatch {
+    ($exoid:expr, any, $function:literal, |$file:ident| $body:expr) => {
+        $crate::raw::file::with_file($exoid, |open| match &mut open.file {
+            $crate::raw::file::OpenFile::Read($file) => $body,
+            #[cfg(feature = "netcdf4")]
+            $crate::raw::file::OpenFile::Write($file) => $body,
+            #[cfg(feature = "netcdf4")]
+            $crate::raw::file::OpenFile::Append($file) => $body,
+            #[allow(unreachable_patterns)]
+            file => Err(file.unsupported($function)),
+        })
+    };
+    ($exoid:expr, readable, $function:literal, |$file:ident| $body:expr) => {
+        $crate::raw::file::with_file($exoid, |open| match &mut open.file {
+            $crate::raw::file::OpenFile::Read($file) => $body,
+            #[cfg(feature = "netcdf4")]
+            $crate::raw::file::OpenFile::Append($file) => $body,
+            file => Err(file.unsupported($function)),
+        })
+    };
+    ($exoid:expr, read, $function:literal, |$file:ident| $body:expr) => {
+        $crate::raw::file::with_file($exoid, |open| match &mut open.file {
+            $crate::raw::file::OpenFile::Read($file) => $body,
+            file => Err(file.unsupported($function)),
+        })
+    };
+    ($exoid:expr, writable, $function:literal, |$file:ident| $body:expr) => {
+        $crate::raw::file::with_file($exoid, |open| match &mut open.file {
+            $crate::raw::file::OpenFile::Write($file) => $body,
+            $crate::raw::file::OpenFile::Append($file) => $body,
+            file => Err(file.unsupported($function)),
+        })
+    };
+    ($exoid:expr, create, $function:literal, |$file:ident| $body:expr) => {
+        $crate::raw::file::with_file($exoid, |open| match &mut open.file {
+            $crate::raw::file::OpenFile::Write($file) => $body,
+            file => Err(file.unsupported($function)),
+        })
+    };
+}
+
+mod file;
+mod mesh;
+mod results;
+
+pub use file::*;
+pub use mesh::*;
+pub use results::*;
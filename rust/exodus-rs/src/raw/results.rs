@@ -0,0 +1,285 @@
+//! Variables, truth tables and time steps
+//!
+//! Time steps and variable indices start at 1, as in C.
+
+use crate::error::{EntityId, ExodusError, Result};
+use crate::types::{EntityType, TruthTable};
+
+/// 0-based index of a 1-based time step
+fn step_index(time_step: usize) -> Result<usize> {
+    time_step
+        .checked_sub(1)
+        .ok_or(ExodusError::InvalidTimeStep(time_step))
+}
+
+/// 0-based index of a 1-based variable index
+fn var_position(var_type: EntityType, var_index: usize) -> Result<usize> {
+    var_index.checked_sub(1).ok_or_else(|| {
+        ExodusError::VariableNotDefined(format!("{} variable 0 (indices start at 1)", var_type))
+    })
+}
+
+// ============================================================================
+// Variable Definitions
+// ============================================================================
+
+/// Number of variables of a type (`ex_get_variable_param`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open or the file cannot be read
+pub fn ex_get_variable_param(exoid: i32, var_type: EntityType) -> Result<usize> {
+    Ok(ex_get_variable_names(exoid, var_type)?.len())
+}
+
+/// Declare the number of variables of a type (`ex_put_variable_param`)
+///
+/// The variables are defined when their names are written with
+/// [`ex_put_variable_names`], which must then be given this many names.
+///
+/// # Errors
+///
+/// Returns an error if the handle is open for reading
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_variable_param(exoid: i32, var_type: EntityType, num_vars: usize) -> Result<()> {
+    crate::raw::file::with_file(exoid, |open| {
+        if let crate::raw::file::OpenFile::Read(_) = open.file {
+            return Err(ExodusError::WriteOnReadOnly);
+        }
+        open.pending_vars
+            .retain(|(pending, _)| *pending != var_type);
+        open.pending_vars.push((var_type, num_vars));
+        Ok(())
+    })
+}
+
+/// Read the names of the variables of a type (`ex_get_variable_names`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open or the file cannot be read
+pub fn ex_get_variable_names(exoid: i32, var_type: EntityType) -> Result<Vec<String>> {
+    dispatch!(exoid, any, "ex_get_variable_names", |file| file
+        .variable_names(var_type))
+}
+
+/// Read the name of one variable (`ex_get_variable_name`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open or the variable does not exist
+pub fn ex_get_variable_name(exoid: i32, var_type: EntityType, var_index: usize) -> Result<String> {
+    let position = var_position(var_type, var_index)?;
+    ex_get_variable_names(exoid, var_type)?
+        .into_iter()
+        .nth(position)
+        .ok_or_else(|| {
+            ExodusError::VariableNotDefined(format!("{} variable {}", var_type, var_index))
+        })
+}
+
+/// Define the variables of a type by name (`ex_put_variable_names`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is open for reading, the number of names
+/// differs from the count given to [`ex_put_variable_param`], or the
+/// variables cannot be defined
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_variable_names(exoid: i32, var_type: EntityType, names: &[&str]) -> Result<()> {
+    crate::raw::file::with_file(exoid, |open| {
+        if let Some(&(_, expected)) = open
+            .pending_vars
+            .iter()
+            .find(|(pending, _)| *pending == var_type)
+        {
+            if expected != names.len() {
+                return Err(ExodusError::InvalidArrayLength {
+                    expected,
+                    actual: names.len(),
+                });
+            }
+        }
+        match &mut open.file {
+            crate::raw::file::OpenFile::Write(file) => file.define_variables(var_type, names)?,
+            crate::raw::file::OpenFile::Append(file) => file.define_variables(var_type, names)?,
+            file => return Err(file.unsupported("ex_put_variable_names")),
+        }
+        open.pending_vars
+            .retain(|(pending, _)| *pending != var_type);
+        Ok(())
+    })
+}
+
+/// Read the truth table of a block or set variable type
+/// (`ex_get_truth_table`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open or the file cannot be read
+pub fn ex_get_truth_table(exoid: i32, var_type: EntityType) -> Result<TruthTable> {
+    dispatch!(exoid, any, "ex_get_truth_table", |file| file
+        .truth_table(var_type))
+}
+
+/// Write the truth table of a block or set variable type
+/// (`ex_put_truth_table`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is open for reading or the table does not
+/// match the blocks or sets and variables
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_truth_table(exoid: i32, var_type: EntityType, table: &TruthTable) -> Result<()> {
+    dispatch!(exoid, writable, "ex_put_truth_table", |file| file
+        .put_truth_table(var_type, table))
+}
+
+// ============================================================================
+// Variable Values
+// ============================================================================
+
+/// Read the values of one variable on one block or set at a time step
+/// (`ex_get_var`)
+///
+/// `obj_id` is ignored for global and nodal variables.
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open for reading, the time step or
+/// variable does not exist, or the file cannot be read
+pub fn ex_get_var(
+    exoid: i32,
+    time_step: usize,
+    var_type: EntityType,
+    var_index: usize,
+    obj_id: EntityId,
+) -> Result<Vec<f64>> {
+    let step = step_index(time_step)?;
+    let position = var_position(var_type, var_index)?;
+    dispatch!(exoid, readable, "ex_get_var", |file| file
+        .var(step, var_type, obj_id, position))
+}
+
+/// Write the values of one variable on one block or set at a time step
+/// (`ex_put_var`)
+///
+/// `obj_id` is ignored for global and nodal variables.
+///
+/// # Errors
+///
+/// Returns an error if the handle is open for reading, the variable does not
+/// exist, or the length does not match
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_var(
+    exoid: i32,
+    time_step: usize,
+    var_type: EntityType,
+    var_index: usize,
+    obj_id: EntityId,
+    var_vals: &[f64],
+) -> Result<()> {
+    let step = step_index(time_step)?;
+    let position = var_position(var_type, var_index)?;
+    dispatch!(exoid, writable, "ex_put_var", |file| file
+        .put_var(step, var_type, obj_id, position, var_vals))
+}
+
+// ============================================================================
+// Time Steps
+// ============================================================================
+
+/// Read the time value of a time step (`ex_get_time`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open for reading or the time step
+/// does not exist
+pub fn ex_get_time(exoid: i32, time_step: usize) -> Result<f64> {
+    let step = step_index(time_step)?;
+    dispatch!(exoid, readable, "ex_get_time", |file| file
+        .time(step)
+        .map_err(|_| ExodusError::InvalidTimeStep(time_step)))
+}
+
+/// Read the time values of all time steps (`ex_get_all_times`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is not open for reading or the file cannot
+/// be read
+pub fn ex_get_all_times(exoid: i32) -> Result<Vec<f64>> {
+    dispatch!(exoid, readable, "ex_get_all_times", |file| file.times())
+}
+
+/// Write the time value of a time step (`ex_put_time`)
+///
+/// # Errors
+///
+/// Returns an error if the handle is open for reading or the time step is 0
+#[cfg(feature = "netcdf4")]
+pub fn ex_put_time(exoid: i32, time_step: usize, time_value: f64) -> Result<()> {
+    let step = step_index(time_step)?;
+    dispatch!(exoid, writable, "ex_put_time", |file| file
+        .put_time(step, time_value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::{ex_close, ex_inquire_int, ex_open, ExInquiry, EX_READ};
+    use std::path::Path;
+
+    /// Results file from the SEACAS script tests
+    fn open_results() -> i32 {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../packages/seacas/scripts/tests/exomerge_unit_test.e");
+        ex_open(path, EX_READ).unwrap()
+    }
+
+    #[test]
+    fn test_read_results() {
+        let exoid = open_results();
+        let num_steps = ex_inquire_int(exoid, ExInquiry::Time).unwrap() as usize;
+        assert_eq!(ex_get_all_times(exoid).unwrap().len(), num_steps);
+
+        let num_vars = ex_get_variable_param(exoid, EntityType::Nodal).unwrap();
+        assert!(num_vars > 0);
+        assert_eq!(
+            ex_inquire_int(exoid, ExInquiry::NumNodeVar).unwrap(),
+            num_vars as i64
+        );
+        let names = ex_get_variable_names(exoid, EntityType::Nodal).unwrap();
+        assert_eq!(
+            ex_get_variable_name(exoid, EntityType::Nodal, num_vars).unwrap(),
+            names[num_vars - 1]
+        );
+        assert!(ex_get_variable_name(exoid, EntityType::Nodal, 0).is_err());
+        assert!(ex_get_variable_name(exoid, EntityType::Nodal, num_vars + 1).is_err());
+
+        let num_nodes = ex_inquire_int(exoid, ExInquiry::Nodes).unwrap() as usize;
+        for step in 1..=num_steps {
+            let values = ex_get_var(exoid, step, EntityType::Nodal, 1, 0).unwrap();
+            assert_eq!(values.len(), num_nodes);
+        }
+        assert_eq!(ex_get_time(exoid, num_steps).unwrap(), 0.04);
+
+        // Element variables are read per block where the truth table allows
+        let block_id = crate::raw::ex_get_ids(exoid, EntityType::ElemBlock).unwrap()[0];
+        let table = ex_get_truth_table(exoid, EntityType::ElemBlock).unwrap();
+        let var = (0..table.num_vars).find(|&var| table.get(0, var)).unwrap();
+        let values = ex_get_var(exoid, 1, EntityType::ElemBlock, var + 1, block_id).unwrap();
+        let block = crate::raw::ex_get_block(exoid, EntityType::ElemBlock, block_id).unwrap();
+        assert_eq!(values.len(), block.num_entries);
+
+        assert!(matches!(
+            ex_get_var(exoid, 0, EntityType::Nodal, 1, 0),
+            Err(ExodusError::InvalidTimeStep(0))
+        ));
+        assert!(matches!(
+            ex_get_time(exoid, num_steps + 1),
+            Err(ExodusError::InvalidTimeStep(step)) if step == num_steps + 1
+        ));
+        ex_close(exoid).unwrap();
+    }
+}
@@ -16,6 +16,7 @@ use crate::{mode, ExodusFile};
 /// Contains the matched entity ID, distance from search point,
 /// and the complete time-history data for the variable.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpatialSearchResult {
     /// ID of the matched node or element (1-based as per Exodus convention)
     pub id: i64,
@@ -517,8 +518,11 @@ fn distance(a: Vec3, b: Vec3) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "netcdf4")]
     use crate::types::{Block, CreateMode, CreateOptions, InitParams};
+    #[cfg(feature = "netcdf4")]
     use crate::ExodusFile;
+    #[cfg(feature = "netcdf4")]
     use tempfile::NamedTempFile;
 
     const EPSILON: f64 = 1e-10;
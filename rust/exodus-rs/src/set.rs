@@ -3,6 +3,7 @@
 //! This module contains operations for node, edge, face, element, and side sets.
 //! Implemented in Phase 5.
 
+use crate::backend::Backend;
 use crate::error::{EntityId, ExodusError, Result};
 use crate::types::{EntitySet, EntityType, NodeSet, Set, SideSet};
 use crate::{mode, ExodusFile, FileMode};
@@ -55,18 +56,15 @@ impl<M: FileMode> ExodusFile<M> {
         };
 
         // Try to get the variable
-        match self.nc_file.variable(var_name) {
-            Some(var) => {
-                // Read the IDs
-                let ids: Vec<i64> = var.get_values(..)?;
-                // Filter out zeros and NetCDF fill values (uninitialized values)
-                // NetCDF uses NC_FILL_INT64 = -9223372036854775806 as the default fill value
-                Ok(ids.into_iter().filter(|&id| id > 0).collect())
-            }
-            None => {
-                // Variable doesn't exist, return empty vector
-                Ok(Vec::new())
-            }
+        if self.nc_file.has_variable(var_name) {
+            // Read the IDs
+            let ids = self.nc_file.read_all_i64(var_name)?;
+            // Filter out zeros and NetCDF fill values (uninitialized values)
+            // NetCDF uses NC_FILL_INT64 = -9223372036854775806 as the default fill value
+            Ok(ids.into_iter().filter(|&id| id > 0).collect())
+        } else {
+            // Variable doesn't exist, return empty vector
+            Ok(Vec::new())
         }
     }
 
@@ -88,14 +86,7 @@ impl<M: FileMode> ExodusFile<M> {
     /// - NetCDF read fails
     pub fn set(&self, entity_type: EntityType, set_id: EntityId) -> Result<Set> {
         // Find the index of this set
-        let ids = self.set_ids(entity_type)?;
-        let index =
-            ids.iter()
-                .position(|&id| id == set_id)
-                .ok_or_else(|| ExodusError::EntityNotFound {
-                    entity_type: entity_type.to_string(),
-                    id: set_id,
-                })?;
+        let index = self.entity_index(entity_type, set_id)?;
 
         // Get dimension name for number of entries
         let dim_name = match entity_type {
@@ -113,10 +104,7 @@ impl<M: FileMode> ExodusFile<M> {
         };
 
         // Get the number of entries
-        let num_entries = match self.nc_file.dimension(&dim_name) {
-            Some(dim) => dim.len(),
-            None => 0,
-        };
+        let num_entries = self.nc_file.dimension_len(&dim_name).unwrap_or(0);
 
         // Get dimension name for distribution factors
         let df_dim_name = match entity_type {
@@ -129,10 +117,7 @@ impl<M: FileMode> ExodusFile<M> {
         };
 
         // Get the number of distribution factors
-        let num_dist_factors = match self.nc_file.dimension(&df_dim_name) {
-            Some(dim) => dim.len(),
-            None => 0,
-        };
+        let num_dist_factors = self.nc_file.dimension_len(&df_dim_name).unwrap_or(0);
 
         Ok(Set {
             id: set_id,
@@ -143,6 +128,7 @@ impl<M: FileMode> ExodusFile<M> {
     }
 }
 
+#[cfg(feature = "netcdf4")]
 impl ExodusFile<mode::Write> {
     /// Define a set
     ///
@@ -159,6 +145,9 @@ impl ExodusFile<mode::Write> {
     /// - Invalid entity type (not a set type)
     /// - NetCDF write fails
     pub fn put_set(&mut self, set: &Set) -> Result<()> {
+        self.metadata
+            .check_int_range(&format!("{} ID", set.entity_type), &[set.id])?;
+
         // Get the current number of sets of this type
         let ids = self.set_ids(set.entity_type)?;
         let index = ids.len();
@@ -178,6 +167,8 @@ impl ExodusFile<mode::Write> {
             }
         };
 
+        self.ensure_define_mode()?;
+
         if set.num_entries > 0 {
             // Try to add dimension, ignore error if it already exists
             if self.nc_file.dimension(&entries_dim_name).is_none() {
@@ -245,16 +236,20 @@ impl ExodusFile<mode::Write> {
             }
 
             // Create the property variable
-            let mut var = self
-                .nc_file
-                .add_variable::<i64>(prop_var_name, &[dim_name])?;
+            let mut var = self.nc_file.add_variable_with_type(
+                prop_var_name,
+                &[dim_name],
+                &self.metadata.int_type(),
+            )?;
             var.put_attribute("name", "ID")?;
         }
 
         // Update the ID array at the current index
+        self.ensure_data_mode()?;
         if let Some(mut var) = self.nc_file.variable_mut(prop_var_name) {
             var.put_value(set.id, index..index + 1)?;
         }
+        self.metadata.invalidate_entity_indices(set.entity_type);
 
         Ok(())
     }
@@ -288,12 +283,13 @@ impl ExodusFile<mode::Write> {
                 });
             }
         }
+        self.metadata
+            .check_int_range(&format!("nodes of node set {}", set_id), nodes)?;
 
         // Find the set index, or create the set if it doesn't exist
-        let ids = self.set_ids(EntityType::NodeSet)?;
-        let index = match ids.iter().position(|&id| id == set_id) {
-            Some(idx) => idx,
-            None => {
+        let index = match self.entity_index(EntityType::NodeSet, set_id) {
+            Ok(idx) => idx,
+            Err(ExodusError::EntityNotFound { .. }) => {
                 // Auto-create the set if it doesn't exist
                 let set = Set {
                     id: set_id,
@@ -302,19 +298,9 @@ impl ExodusFile<mode::Write> {
                     num_dist_factors: dist_factors.map_or(0, |df| df.len()),
                 };
                 self.put_set(&set)?;
-
-                // Count dimensions directly to get the actual index
-                // (set_ids() filters fill values, so its length doesn't match the array index)
-                let mut count: usize = 0;
-                while self
-                    .nc_file
-                    .dimension(&format!("num_nod_ns{}", count + 1))
-                    .is_some()
-                {
-                    count += 1;
-                }
-                count.saturating_sub(1)
+                self.entity_index(EntityType::NodeSet, set_id)?
             }
+            Err(e) => return Err(e),
         };
 
         // Only create and write variables if the set is not empty
@@ -323,20 +309,32 @@ impl ExodusFile<mode::Write> {
             let node_var_name = format!("node_ns{}", index + 1);
             let entries_dim_name = format!("num_nod_ns{}", index + 1);
 
-            let mut node_var = self
-                .nc_file
-                .add_variable::<i64>(&node_var_name, &[&entries_dim_name])?;
-            node_var.put_values(nodes, ..)?;
+            let df_var_name = format!("dist_fact_ns{}", index + 1);
+            let df_dim_name = format!("num_df_ns{}", index + 1);
+
+            self.ensure_define_mode()?;
+            let mut node_var = self.nc_file.add_variable_with_type(
+                &node_var_name,
+                &[&entries_dim_name],
+                &self.metadata.int_type(),
+            )?;
+            self.metadata.apply_compression(&mut node_var)?;
+
+            if dist_factors.is_some() {
+                let mut df_var = self.nc_file.add_variable_with_type(
+                    &df_var_name,
+                    &[&df_dim_name],
+                    &self.metadata.float_type(),
+                )?;
+                self.metadata.apply_compression(&mut df_var)?;
+            }
+
+            self.ensure_data_mode()?;
+            self.put_int_values(&node_var_name, nodes)?;
 
             // Write distribution factors if provided
             if let Some(df) = dist_factors {
-                let df_var_name = format!("dist_fact_ns{}", index + 1);
-                let df_dim_name = format!("num_df_ns{}", index + 1);
-
-                let mut df_var = self
-                    .nc_file
-                    .add_variable::<f64>(&df_var_name, &[&df_dim_name])?;
-                df_var.put_values(df, ..)?;
+                self.put_float_values(&df_var_name, df)?;
             }
         }
 
@@ -372,12 +370,15 @@ impl ExodusFile<mode::Write> {
                 actual: sides.len(),
             });
         }
+        self.metadata
+            .check_int_range(&format!("elements of side set {}", set_id), elements)?;
+        self.metadata
+            .check_int_range(&format!("sides of side set {}", set_id), sides)?;
 
         // Find the set index, or create the set if it doesn't exist
-        let ids = self.set_ids(EntityType::SideSet)?;
-        let index = match ids.iter().position(|&id| id == set_id) {
-            Some(idx) => idx,
-            None => {
+        let index = match self.entity_index(EntityType::SideSet, set_id) {
+            Ok(idx) => idx,
+            Err(ExodusError::EntityNotFound { .. }) => {
                 // Auto-create the set if it doesn't exist
                 let set = Set {
                     id: set_id,
@@ -386,19 +387,9 @@ impl ExodusFile<mode::Write> {
                     num_dist_factors: dist_factors.map_or(0, |df| df.len()),
                 };
                 self.put_set(&set)?;
-
-                // Count dimensions directly to get the actual index
-                // (set_ids() filters fill values, so its length doesn't match the array index)
-                let mut count: usize = 0;
-                while self
-                    .nc_file
-                    .dimension(&format!("num_side_ss{}", count + 1))
-                    .is_some()
-                {
-                    count += 1;
-                }
-                count.saturating_sub(1)
+                self.entity_index(EntityType::SideSet, set_id)?
             }
+            Err(e) => return Err(e),
         };
 
         // Only create and write variables if the set is not empty
@@ -408,25 +399,40 @@ impl ExodusFile<mode::Write> {
             let side_var_name = format!("side_ss{}", index + 1);
             let entries_dim_name = format!("num_side_ss{}", index + 1);
 
-            let mut elem_var = self
-                .nc_file
-                .add_variable::<i64>(&elem_var_name, &[&entries_dim_name])?;
-            elem_var.put_values(elements, ..)?;
+            let df_var_name = format!("dist_fact_ss{}", index + 1);
+            let df_dim_name = format!("num_df_ss{}", index + 1);
+
+            self.ensure_define_mode()?;
+            let mut elem_var = self.nc_file.add_variable_with_type(
+                &elem_var_name,
+                &[&entries_dim_name],
+                &self.metadata.int_type(),
+            )?;
+            self.metadata.apply_compression(&mut elem_var)?;
+
+            let mut side_var = self.nc_file.add_variable_with_type(
+                &side_var_name,
+                &[&entries_dim_name],
+                &self.metadata.int_type(),
+            )?;
+            self.metadata.apply_compression(&mut side_var)?;
+
+            if dist_factors.is_some() {
+                let mut df_var = self.nc_file.add_variable_with_type(
+                    &df_var_name,
+                    &[&df_dim_name],
+                    &self.metadata.float_type(),
+                )?;
+                self.metadata.apply_compression(&mut df_var)?;
+            }
 
-            let mut side_var = self
-                .nc_file
-                .add_variable::<i64>(&side_var_name, &[&entries_dim_name])?;
-            side_var.put_values(sides, ..)?;
+            self.ensure_data_mode()?;
+            self.put_int_values(&elem_var_name, elements)?;
+            self.put_int_values(&side_var_name, sides)?;
 
             // Write distribution factors if provided
             if let Some(df) = dist_factors {
-                let df_var_name = format!("dist_fact_ss{}", index + 1);
-                let df_dim_name = format!("num_df_ss{}", index + 1);
-
-                let mut df_var = self
-                    .nc_file
-                    .add_variable::<f64>(&df_var_name, &[&df_dim_name])?;
-                df_var.put_values(df, ..)?;
+                self.put_float_values(&df_var_name, df)?;
             }
         }
 
@@ -466,14 +472,7 @@ impl ExodusFile<mode::Write> {
         }
 
         // Find the set index
-        let ids = self.set_ids(entity_type)?;
-        let index =
-            ids.iter()
-                .position(|&id| id == set_id)
-                .ok_or_else(|| ExodusError::EntityNotFound {
-                    entity_type: entity_type.to_string(),
-                    id: set_id,
-                })?;
+        let index = self.entity_index(entity_type, set_id)?;
 
         // Get the set parameters to validate
         let set = self.set(entity_type, set_id)?;
@@ -485,6 +484,8 @@ impl ExodusFile<mode::Write> {
                 actual: entities.len(),
             });
         }
+        self.metadata
+            .check_int_range(&format!("entries of {} {}", entity_type, set_id), entities)?;
 
         // Only create and write variables if the set is not empty
         if !entities.is_empty() {
@@ -505,12 +506,40 @@ impl ExodusFile<mode::Write> {
                 _ => unreachable!(),
             };
 
-            let mut var = self.nc_file.add_variable::<i64>(&var_name, &[&dim_name])?;
-            var.put_values(entities, ..)?;
+            self.ensure_define_mode()?;
+            let mut var = self.nc_file.add_variable_with_type(
+                &var_name,
+                &[&dim_name],
+                &self.metadata.int_type(),
+            )?;
+            self.metadata.apply_compression(&mut var)?;
+
+            self.ensure_data_mode()?;
+            self.put_int_values(&var_name, entities)?;
         }
 
         Ok(())
     }
+
+    /// Write a complete integer set variable
+    fn put_int_values(&mut self, var_name: &str, values: &[i64]) -> Result<()> {
+        let mut var = self
+            .nc_file
+            .variable_mut(var_name)
+            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.to_string()))?;
+        var.put_values(values, ..)?;
+        Ok(())
+    }
+
+    /// Write a complete distribution factor variable
+    fn put_float_values(&mut self, var_name: &str, values: &[f64]) -> Result<()> {
+        let mut var = self
+            .nc_file
+            .variable_mut(var_name)
+            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.to_string()))?;
+        var.put_values(values, ..)?;
+        Ok(())
+    }
 }
 
 // ====================
@@ -535,27 +564,22 @@ impl ExodusFile<mode::Read> {
     /// - NetCDF read fails
     pub fn node_set(&self, set_id: EntityId) -> Result<NodeSet> {
         // Find the set index (also validates that the set exists)
-        let ids = self.set_ids(EntityType::NodeSet)?;
-        let index =
-            ids.iter()
-                .position(|&id| id == set_id)
-                .ok_or_else(|| ExodusError::EntityNotFound {
-                    entity_type: EntityType::NodeSet.to_string(),
-                    id: set_id,
-                })?;
+        let index = self.entity_index(EntityType::NodeSet, set_id)?;
 
         // Read node IDs (empty if variable doesn't exist for empty sets)
         let node_var_name = format!("node_ns{}", index + 1);
-        let nodes: Vec<i64> = match self.nc_file.variable(&node_var_name) {
-            Some(var) => var.get_values(..)?,
-            None => Vec::new(), // Empty set
+        let nodes = if self.nc_file.has_variable(&node_var_name) {
+            self.nc_file.read_all_i64(&node_var_name)?
+        } else {
+            Vec::new() // Empty set
         };
 
         // Read distribution factors if present
         let df_var_name = format!("dist_fact_ns{}", index + 1);
-        let dist_factors = match self.nc_file.variable(&df_var_name) {
-            Some(var) => var.get_values(..)?,
-            None => Vec::new(),
+        let dist_factors = if self.nc_file.has_variable(&df_var_name) {
+            self.nc_file.read_all_f64(&df_var_name)?
+        } else {
+            Vec::new()
         };
 
         Ok(NodeSet {
@@ -582,34 +606,30 @@ impl ExodusFile<mode::Read> {
     /// - NetCDF read fails
     pub fn side_set(&self, set_id: EntityId) -> Result<SideSet> {
         // Find the set index (also validates that the set exists)
-        let ids = self.set_ids(EntityType::SideSet)?;
-        let index =
-            ids.iter()
-                .position(|&id| id == set_id)
-                .ok_or_else(|| ExodusError::EntityNotFound {
-                    entity_type: EntityType::SideSet.to_string(),
-                    id: set_id,
-                })?;
+        let index = self.entity_index(EntityType::SideSet, set_id)?;
 
         // Read element IDs (empty if variable doesn't exist for empty sets)
         let elem_var_name = format!("elem_ss{}", index + 1);
-        let elements: Vec<i64> = match self.nc_file.variable(&elem_var_name) {
-            Some(var) => var.get_values(..)?,
-            None => Vec::new(), // Empty set
+        let elements = if self.nc_file.has_variable(&elem_var_name) {
+            self.nc_file.read_all_i64(&elem_var_name)?
+        } else {
+            Vec::new() // Empty set
         };
 
         // Read side IDs (empty if variable doesn't exist for empty sets)
         let side_var_name = format!("side_ss{}", index + 1);
-        let sides: Vec<i64> = match self.nc_file.variable(&side_var_name) {
-            Some(var) => var.get_values(..)?,
-            None => Vec::new(), // Empty set
+        let sides = if self.nc_file.has_variable(&side_var_name) {
+            self.nc_file.read_all_i64(&side_var_name)?
+        } else {
+            Vec::new() // Empty set
         };
 
         // Read distribution factors if present
         let df_var_name = format!("dist_fact_ss{}", index + 1);
-        let dist_factors = match self.nc_file.variable(&df_var_name) {
-            Some(var) => var.get_values(..)?,
-            None => Vec::new(),
+        let dist_factors = if self.nc_file.has_variable(&df_var_name) {
+            self.nc_file.read_all_f64(&df_var_name)?
+        } else {
+            Vec::new()
         };
 
         Ok(SideSet {
@@ -650,14 +670,7 @@ impl ExodusFile<mode::Read> {
         }
 
         // Find the set index
-        let ids = self.set_ids(entity_type)?;
-        let index =
-            ids.iter()
-                .position(|&id| id == set_id)
-                .ok_or_else(|| ExodusError::EntityNotFound {
-                    entity_type: entity_type.to_string(),
-                    id: set_id,
-                })?;
+        let index = self.entity_index(entity_type, set_id)?;
 
         // Read entity IDs (empty if variable doesn't exist for empty sets)
         let var_name = match entity_type {
@@ -667,9 +680,10 @@ impl ExodusFile<mode::Read> {
             _ => unreachable!(),
         };
 
-        let entities: Vec<i64> = match self.nc_file.variable(&var_name) {
-            Some(var) => var.get_values(..)?,
-            None => Vec::new(), // Empty set
+        let entities = if self.nc_file.has_variable(&var_name) {
+            self.nc_file.read_all_i64(&var_name)?
+        } else {
+            Vec::new() // Empty set
         };
 
         Ok(EntitySet {
@@ -678,7 +692,9 @@ impl ExodusFile<mode::Read> {
             entities,
         })
     }
+}
 
+impl ExodusFile<mode::Read> {
     /// Convert a nodeset to a sideset with explicit sideset ID.
     ///
     /// Creates a new sideset containing all element faces where every node belongs
@@ -0,0 +1,771 @@
+//! Exporting boundary surfaces to STL, OBJ and PLY.
+//!
+//! [`export_surface`] writes the exterior skin of a mesh, or the faces of
+//! chosen side sets, as a triangulated surface for CAD comparisons and 3D
+//! printing. The format follows the extension of the output path:
+//!
+//! - `.stl`: binary STL, or ASCII STL with [`SurfaceOptions::ascii`]
+//! - `.obj`: Wavefront OBJ
+//! - `.ply`: binary little-endian PLY, or ASCII PLY with
+//!   [`SurfaceOptions::ascii`]
+//!
+//! The faces are taken from [`Topology::faces`]:
+//!
+//! - The skin is every face of a solid element (tetrahedron, hexahedron,
+//!   wedge or pyramid) that belongs to no other element, together with every
+//!   triangle, quadrilateral and shell element
+//! - A side set contributes the faces of its solid elements, and sides 1
+//!   (the front) and 2 (the back) of its shell elements. Edge sides cannot be
+//!   written as a surface.
+//!
+//! Faces of solid elements are oriented with their normal pointing out of the
+//! element, checked against the element centroid with
+//! [`geometry::is_outward_facing`], so inverted elements still give an
+//! outward skin. Quadrilateral faces are split into two triangles fanned from
+//! their first node, and only the corner nodes of quadratic faces are used.
+//!
+//! One nodal variable at one time step can be carried as per-vertex data.
+//! PLY stores it as an extra vertex property named after the variable. OBJ
+//! has no scalar vertex data, so the values are scaled to [0, 1] and written
+//! as texture coordinates, with the range in a comment. STL has no
+//! per-vertex data, and asking for a variable is an error.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use exodus_rs::surface::{export_surface, SurfaceOptions};
+//!
+//! // Side set 10, colored by temperature at the last step
+//! let options = SurfaceOptions {
+//!     side_sets: Some(vec![10]),
+//!     variable: Some("temperature".into()),
+//!     ..Default::default()
+//! };
+//! let summary = export_surface("results.e", "wall.ply", &options)?;
+//! println!("{} triangles", summary.num_triangles);
+//! # Ok::<(), exodus_rs::ExodusError>(())
+//! ```
+
+use crate::error::{EntityId, ExodusError, Result};
+use crate::geometry::{self, Vec3};
+use crate::topology::FaceDef;
+use crate::types::{Block, EntityType, Topology};
+use crate::{mode, ExodusFile};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Options controlling what [`export_surface`] writes
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SurfaceOptions {
+    /// Side sets to export, or `None` for the exterior skin
+    pub side_sets: Option<Vec<EntityId>>,
+    /// Nodal variable written as per-vertex data, matched
+    /// case-insensitively
+    pub variable: Option<String>,
+    /// Time step of the variable (0-based), or `None` for the last step
+    pub step: Option<usize>,
+    /// Write ASCII instead of binary STL and PLY
+    pub ascii: bool,
+}
+
+/// What [`export_surface`] wrote
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SurfaceSummary {
+    /// Number of vertices, one per mesh node on the surface
+    pub num_vertices: usize,
+    /// Number of triangles
+    pub num_triangles: usize,
+}
+
+/// A triangulated surface of a mesh
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Surface {
+    /// 1-based node number of each vertex
+    pub nodes: Vec<i64>,
+    /// Coordinates of each vertex
+    pub points: Vec<Vec3>,
+    /// 0-based vertex indices of each triangle, counterclockwise seen from
+    /// outside
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl Surface {
+    /// Extract the faces of side sets, or the exterior skin when
+    /// `side_sets` is `None` (see the [module documentation](self))
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, a side set does not
+    /// exist, or a side set names an element or side that is not a face.
+    pub fn read(file: &ExodusFile<mode::Read>, side_sets: Option<&[EntityId]>) -> Result<Self> {
+        let coords = file.coords::<f64>()?;
+        let points: Vec<Vec3> = (0..coords.x.len())
+            .map(|i| {
+                [
+                    coords.x[i],
+                    coords.y.get(i).copied().unwrap_or(0.0),
+                    coords.z.get(i).copied().unwrap_or(0.0),
+                ]
+            })
+            .collect();
+        let blocks = file
+            .block_ids(EntityType::ElemBlock)?
+            .into_iter()
+            .map(|id| ElementBlock::read(file, id))
+            .collect::<Result<Vec<_>>>()?;
+
+        let faces = match side_sets {
+            Some(ids) => side_set_faces(file, &blocks, ids, &points)?,
+            None => skin_faces(&blocks, &points)?,
+        };
+
+        let mut surface = Self::default();
+        let mut vertices: HashMap<i64, usize> = HashMap::new();
+        for face in faces {
+            let mut corners = Vec::with_capacity(face.len());
+            for node in face {
+                let vertex = *vertices.entry(node).or_insert_with(|| {
+                    surface.nodes.push(node);
+                    surface.points.push(points[node as usize - 1]);
+                    surface.nodes.len() - 1
+                });
+                corners.push(vertex);
+            }
+            for i in 1..corners.len() - 1 {
+                surface
+                    .triangles
+                    .push([corners[0], corners[i], corners[i + 1]]);
+            }
+        }
+        Ok(surface)
+    }
+
+    /// Unit normal of each triangle, by the right-hand rule
+    pub fn normals(&self) -> Vec<Vec3> {
+        self.triangles
+            .iter()
+            .map(|triangle| geometry::compute_face_normal(&triangle.map(|v| self.points[v])))
+            .collect()
+    }
+}
+
+/// Export the surface of an Exodus file to STL, OBJ or PLY
+///
+/// The format is chosen by the extension of `output` (see the
+/// [module documentation](self)). An existing file is overwritten.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read, the output extension is
+/// not `.stl`, `.obj` or `.ply`, a variable is asked for with STL output,
+/// the variable or time step does not exist, or the file cannot be written.
+pub fn export_surface(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    options: &SurfaceOptions,
+) -> Result<SurfaceSummary> {
+    let file = ExodusFile::<mode::Read>::open(input)?;
+    write_surface(&file, output, options)
+}
+
+/// Export the surface of an open Exodus file to STL, OBJ or PLY
+///
+/// See [`export_surface`].
+pub fn write_surface(
+    file: &ExodusFile<mode::Read>,
+    output: impl AsRef<Path>,
+    options: &SurfaceOptions,
+) -> Result<SurfaceSummary> {
+    let output = output.as_ref();
+    let format = Format::of(output)?;
+    if format == Format::Stl && options.variable.is_some() {
+        return Err(ExodusError::UnsupportedFeature(
+            "STL has no per-vertex data; write the variable to OBJ or PLY".into(),
+        ));
+    }
+
+    let surface = Surface::read(file, options.side_sets.as_deref())?;
+    let field = match &options.variable {
+        Some(name) => Some(read_field(file, &surface, name, options.step)?),
+        None => None,
+    };
+    let title = file.init_params()?.title;
+    let title = title.lines().next().unwrap_or_default().trim();
+    let title = if title.is_empty() {
+        "Exodus surface"
+    } else {
+        title
+    };
+
+    let mut out = BufWriter::new(File::create(output)?);
+    match (format, options.ascii) {
+        (Format::Stl, false) => write_stl_binary(&mut out, title, &surface)?,
+        (Format::Stl, true) => write_stl_ascii(&mut out, title, &surface)?,
+        (Format::Obj, _) => write_obj(&mut out, title, &surface, field.as_ref())?,
+        (Format::Ply, ascii) => write_ply(&mut out, title, &surface, field.as_ref(), ascii)?,
+    }
+    out.flush()?;
+
+    Ok(SurfaceSummary {
+        num_vertices: surface.points.len(),
+        num_triangles: surface.triangles.len(),
+    })
+}
+
+/// Output format, from the output extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Stl,
+    Obj,
+    Ply,
+}
+
+impl Format {
+    fn of(path: &Path) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("stl") => Ok(Self::Stl),
+            Some("obj") => Ok(Self::Obj),
+            Some("ply") => Ok(Self::Ply),
+            _ => Err(ExodusError::Other(format!(
+                "Surface output must end in .stl, .obj or .ply: {}",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// An element block with the faces its elements contribute to a surface
+#[derive(Debug)]
+struct ElementBlock {
+    block: Block,
+    connectivity: Vec<i64>,
+    /// Solid elements, whose faces bound a volume
+    solid: bool,
+    /// Faces by side number: those of [`Topology::faces`] for solid
+    /// elements, the front and back of shells, and none for other elements
+    faces: Vec<FaceDef>,
+    /// Corner nodes of triangles, quadrilaterals and shells, which are
+    /// themselves part of the skin
+    corners: usize,
+}
+
+impl ElementBlock {
+    fn read(file: &ExodusFile<mode::Read>, id: EntityId) -> Result<Self> {
+        let block = file.block(id)?;
+        let topology = Topology::of_block(&block);
+        let name = block.topology.trim().to_uppercase();
+        let shell = name.starts_with("SHELL") || name.starts_with("TRISHELL");
+        let corners = match topology {
+            Topology::Tri3 | Topology::Tri6 | Topology::Tri7 => 3,
+            Topology::Quad4 | Topology::Quad8 | Topology::Quad9 => 4,
+            _ => 0,
+        };
+        let solid = corners == 0 && topology.faces().is_some();
+        let faces = if solid {
+            topology.faces().unwrap_or_default()
+        } else if shell && corners > 0 {
+            vec![
+                FaceDef {
+                    side_number: 1,
+                    node_indices: (0..corners).collect(),
+                },
+                FaceDef {
+                    side_number: 2,
+                    node_indices: (0..corners).rev().collect(),
+                },
+            ]
+        } else {
+            Vec::new()
+        };
+        if block.num_nodes_per_entry < corners
+            || faces
+                .iter()
+                .flat_map(|face| &face.node_indices)
+                .any(|&i| i >= block.num_nodes_per_entry)
+        {
+            return Err(ExodusError::InvalidTopology(format!(
+                "Block {} is {} with {} nodes per element",
+                id, block.topology, block.num_nodes_per_entry
+            )));
+        }
+        let connectivity = file.connectivity(id)?;
+        Ok(Self {
+            block,
+            connectivity,
+            solid,
+            faces,
+            corners,
+        })
+    }
+
+    /// Nodes of the element at a 0-based index in the block
+    fn element(&self, index: usize) -> &[i64] {
+        let n = self.block.num_nodes_per_entry;
+        &self.connectivity[index * n..(index + 1) * n]
+    }
+}
+
+/// Coordinates of 1-based nodes
+fn node_points(nodes: &[i64], points: &[Vec3]) -> Result<Vec<Vec3>> {
+    nodes
+        .iter()
+        .map(|&node| match usize::try_from(node) {
+            Ok(i) if (1..=points.len()).contains(&i) => Ok(points[i - 1]),
+            _ => Err(ExodusError::Other(format!(
+                "Node {} is outside the {} nodes of the mesh",
+                node,
+                points.len()
+            ))),
+        })
+        .collect()
+}
+
+/// Nodes of a face of an element, reversed if needed so that the face of a
+/// solid element points out of it
+fn oriented_face(
+    block: &ElementBlock,
+    element: &[i64],
+    face: &FaceDef,
+    points: &[Vec3],
+) -> Result<Vec<i64>> {
+    let mut nodes: Vec<i64> = face.node_indices.iter().map(|&i| element[i]).collect();
+    let face_points = node_points(&nodes, points)?;
+    if block.solid {
+        let centroid = geometry::element_centroid(&node_points(element, points)?);
+        let normal = geometry::compute_face_normal(&face_points);
+        let center = geometry::compute_face_center(&face_points);
+        if !geometry::is_outward_facing(center, normal, centroid) {
+            nodes.reverse();
+        }
+    }
+    Ok(nodes)
+}
+
+/// Faces of solid elements not shared with another element, and the
+/// triangles, quadrilaterals and shells, in element order
+fn skin_faces(blocks: &[ElementBlock], points: &[Vec3]) -> Result<Vec<Vec<i64>>> {
+    let mut faces = Vec::new();
+    // Number of solid elements having each face, by its sorted nodes
+    let mut counts: HashMap<Vec<i64>, usize> = HashMap::new();
+    for block in blocks {
+        for index in 0..block.block.num_entries {
+            let element = block.element(index);
+            if block.solid {
+                for face in &block.faces {
+                    let nodes = oriented_face(block, element, face, points)?;
+                    let mut key = nodes.clone();
+                    key.sort_unstable();
+                    *counts.entry(key.clone()).or_insert(0) += 1;
+                    faces.push((Some(key), nodes));
+                }
+            } else if block.corners > 0 {
+                // Checks the nodes are in the mesh
+                node_points(&element[..block.corners], points)?;
+                faces.push((None, element[..block.corners].to_vec()));
+            }
+        }
+    }
+    Ok(faces
+        .into_iter()
+        .filter(|(key, _)| key.as_ref().map_or(true, |key| counts[key] == 1))
+        .map(|(_, nodes)| nodes)
+        .collect())
+}
+
+/// Faces named by the sides of side sets
+fn side_set_faces(
+    file: &ExodusFile<mode::Read>,
+    blocks: &[ElementBlock],
+    ids: &[EntityId],
+    points: &[Vec3],
+) -> Result<Vec<Vec<i64>>> {
+    // 0-based index of the first element of each block
+    let mut starts = Vec::with_capacity(blocks.len());
+    let mut num_elems = 0;
+    for block in blocks {
+        starts.push(num_elems);
+        num_elems += block.block.num_entries;
+    }
+
+    let mut faces = Vec::new();
+    for &id in ids {
+        let set = file.side_set(id)?;
+        for (&element, &side) in set.elements.iter().zip(&set.sides) {
+            let index = match usize::try_from(element) {
+                Ok(e) if (1..=num_elems).contains(&e) => e - 1,
+                _ => {
+                    return Err(ExodusError::Other(format!(
+                        "Side set {} names element {}, outside the {} elements of the mesh",
+                        id, element, num_elems
+                    )))
+                }
+            };
+            let b = starts.partition_point(|&start| start <= index) - 1;
+            let block = &blocks[b];
+            let face = usize::try_from(side)
+                .ok()
+                .and_then(|side| block.faces.iter().find(|face| face.side_number == side))
+                .ok_or_else(|| {
+                    ExodusError::UnsupportedFeature(format!(
+                        "Side {} of element {} in side set {} is not a face of a {} element",
+                        side, element, id, block.block.topology
+                    ))
+                })?;
+            let nodes = block.element(index - starts[b]);
+            faces.push(oriented_face(block, nodes, face, points)?);
+        }
+    }
+    Ok(faces)
+}
+
+/// A nodal variable at the surface vertices
+#[derive(Debug)]
+struct Field {
+    name: String,
+    values: Vec<f64>,
+}
+
+fn read_field(
+    file: &ExodusFile<mode::Read>,
+    surface: &Surface,
+    name: &str,
+    step: Option<usize>,
+) -> Result<Field> {
+    let names = file.variable_names(EntityType::Nodal)?;
+    let index = names
+        .iter()
+        .position(|n| n.eq_ignore_ascii_case(name))
+        .ok_or_else(|| ExodusError::VariableNotDefined(name.to_string()))?;
+    let num_steps = file.num_time_steps()?;
+    let step = match step {
+        Some(step) => step,
+        None => num_steps
+            .checked_sub(1)
+            .ok_or(ExodusError::InvalidTimeStep(0))?,
+    };
+    if step >= num_steps {
+        return Err(ExodusError::InvalidTimeStep(step));
+    }
+    let values = file.var(step, EntityType::Nodal, 0, index)?;
+    let values = surface
+        .nodes
+        .iter()
+        .map(|&node| {
+            values
+                .get(node as usize - 1)
+                .copied()
+                .ok_or(ExodusError::InvalidArrayLength {
+                    expected: node as usize,
+                    actual: values.len(),
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Field {
+        name: names[index].clone(),
+        values,
+    })
+}
+
+/// Names in PLY headers cannot contain whitespace
+fn token(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .collect();
+    if name.is_empty() {
+        "unnamed".into()
+    } else {
+        name
+    }
+}
+
+fn write_stl_binary(out: &mut impl Write, title: &str, surface: &Surface) -> Result<()> {
+    // ASCII STL starts with "solid", which readers use to tell the two apart
+    let mut header = [0u8; 80];
+    let text = format!("Exodus: {}", title);
+    let len = text.len().min(header.len());
+    header[..len].copy_from_slice(&text.as_bytes()[..len]);
+    out.write_all(&header)?;
+
+    let num_triangles = u32::try_from(surface.triangles.len()).map_err(|_| {
+        ExodusError::Other(format!(
+            "{} triangles are too many for binary STL",
+            surface.triangles.len()
+        ))
+    })?;
+    out.write_all(&num_triangles.to_le_bytes())?;
+    for (triangle, normal) in surface.triangles.iter().zip(surface.normals()) {
+        for value in normal {
+            out.write_all(&(value as f32).to_le_bytes())?;
+        }
+        for &vertex in triangle {
+            for value in surface.points[vertex] {
+                out.write_all(&(value as f32).to_le_bytes())?;
+            }
+        }
+        // Attribute byte count
+        out.write_all(&[0, 0])?;
+    }
+    Ok(())
+}
+
+fn write_stl_ascii(out: &mut impl Write, title: &str, surface: &Surface) -> Result<()> {
+    writeln!(out, "solid {}", title)?;
+    for (triangle, [nx, ny, nz]) in surface.triangles.iter().zip(surface.normals()) {
+        writeln!(out, "  facet normal {:e} {:e} {:e}", nx, ny, nz)?;
+        writeln!(out, "    outer loop")?;
+        for &vertex in triangle {
+            let [x, y, z] = surface.points[vertex];
+            writeln!(out, "      vertex {:e} {:e} {:e}", x, y, z)?;
+        }
+        writeln!(out, "    endloop")?;
+        writeln!(out, "  endfacet")?;
+    }
+    writeln!(out, "endsolid {}", title)?;
+    Ok(())
+}
+
+fn write_obj(
+    out: &mut impl Write,
+    title: &str,
+    surface: &Surface,
+    field: Option<&Field>,
+) -> Result<()> {
+    writeln!(out, "# {}", title)?;
+    writeln!(
+        out,
+        "# {} vertices, {} triangles",
+        surface.points.len(),
+        surface.triangles.len()
+    )?;
+    for [x, y, z] in &surface.points {
+        writeln!(out, "v {} {} {}", x, y, z)?;
+    }
+    if let Some(field) = field {
+        let min = field.values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = field
+            .values
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        writeln!(
+            out,
+            "# Texture coordinate u is {} scaled from [{}, {}] to [0, 1]",
+            field.name, min, max
+        )?;
+        let range = max - min;
+        for &value in &field.values {
+            let u = if range > 0.0 {
+                (value - min) / range
+            } else {
+                0.0
+            };
+            writeln!(out, "vt {} 0", u)?;
+        }
+    }
+    for triangle in &surface.triangles {
+        let [a, b, c] = triangle.map(|vertex| vertex + 1);
+        if field.is_some() {
+            writeln!(out, "f {}/{} {}/{} {}/{}", a, a, b, b, c, c)?;
+        } else {
+            writeln!(out, "f {} {} {}", a, b, c)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_ply(
+    out: &mut impl Write,
+    title: &str,
+    surface: &Surface,
+    field: Option<&Field>,
+    ascii: bool,
+) -> Result<()> {
+    let max_vertex = surface.points.len().saturating_sub(1);
+    if i32::try_from(max_vertex).is_err() {
+        return Err(ExodusError::Other(format!(
+            "{} vertices are too many for PLY",
+            surface.points.len()
+        )));
+    }
+
+    writeln!(out, "ply")?;
+    if ascii {
+        writeln!(out, "format ascii 1.0")?;
+    } else {
+        writeln!(out, "format binary_little_endian 1.0")?;
+    }
+    writeln!(out, "comment {}", title)?;
+    writeln!(out, "element vertex {}", surface.points.len())?;
+    for axis in ["x", "y", "z"] {
+        writeln!(out, "property double {}", axis)?;
+    }
+    if let Some(field) = field {
+        writeln!(out, "property double {}", token(&field.name))?;
+    }
+    writeln!(out, "element face {}", surface.triangles.len())?;
+    writeln!(out, "property list uchar int vertex_indices")?;
+    writeln!(out, "end_header")?;
+
+    for (i, point) in surface.points.iter().enumerate() {
+        let value = field.map(|field| field.values[i]);
+        if ascii {
+            write!(out, "{} {} {}", point[0], point[1], point[2])?;
+            if let Some(value) = value {
+                write!(out, " {}", value)?;
+            }
+            writeln!(out)?;
+        } else {
+            for value in point.iter().chain(&value) {
+                out.write_all(&value.to_le_bytes())?;
+            }
+        }
+    }
+    for triangle in &surface.triangles {
+        if ascii {
+            writeln!(out, "3 {} {} {}", triangle[0], triangle[1], triangle[2])?;
+        } else {
+            out.write_all(&[3])?;
+            for &vertex in triangle {
+                out.write_all(&(vertex as i32).to_le_bytes())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    /// Results file from the SEACAS script tests: five HEX8 blocks, node
+    /// and side sets, and global, nodal and element variables
+    fn results_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../packages/seacas/scripts/tests/exomerge_unit_test.e")
+    }
+
+    /// Whether every edge of a surface is shared with one other triangle,
+    /// which runs it the other way
+    fn is_closed_and_consistent(surface: &Surface) -> bool {
+        let mut edges = HashMap::new();
+        for triangle in &surface.triangles {
+            for k in 0..3 {
+                *edges
+                    .entry((triangle[k], triangle[(k + 1) % 3]))
+                    .or_insert(0) += 1;
+            }
+        }
+        edges
+            .iter()
+            .all(|(&(a, b), &count)| count == 1 && edges.get(&(b, a)) == Some(&1))
+    }
+
+    #[test]
+    fn test_skin_is_closed_and_outward() {
+        let file = ExodusFile::<mode::Read>::open(results_path()).unwrap();
+        let skin = Surface::read(&file, None).unwrap();
+        assert_eq!((skin.points.len(), skin.triangles.len()), (1550, 3080));
+        assert!(is_closed_and_consistent(&skin));
+
+        // Enclosed volume by the divergence theorem, positive for outward
+        // normals; the mesh is the unit cube
+        let volume: f64 = skin
+            .triangles
+            .iter()
+            .map(|triangle| {
+                let [a, b, c] = triangle.map(|v| skin.points[v]);
+                let n = geometry::cross(b, c);
+                (a[0] * n[0] + a[1] * n[1] + a[2] * n[2]) / 6.0
+            })
+            .sum();
+        assert!((volume - 1.0).abs() < 1e-9, "{}", volume);
+    }
+
+    #[test]
+    fn test_export_side_set_formats() {
+        let dir = TempDir::new().unwrap();
+        let mut options = SurfaceOptions {
+            side_sets: Some(vec![11]),
+            variable: Some("DISP_X".into()),
+            step: Some(2),
+            ascii: true,
+        };
+
+        let ply = dir.path().join("wall.ply");
+        let summary = export_surface(results_path(), &ply, &options).unwrap();
+        assert_eq!(summary.num_triangles, 50);
+        let text = std::fs::read_to_string(&ply).unwrap();
+        assert!(text.starts_with("ply\nformat ascii 1.0\n"));
+        assert!(text.contains("property double disp_x\n"));
+        assert!(text.contains("element face 50\n"));
+        let body = &text[text.find("end_header\n").unwrap() + 11..];
+        assert_eq!(body.lines().count(), summary.num_vertices + 50);
+        assert_eq!(body.lines().next().unwrap().split(' ').count(), 4);
+
+        let obj = dir.path().join("wall.obj");
+        export_surface(results_path(), &obj, &options).unwrap();
+        let text = std::fs::read_to_string(&obj).unwrap();
+        assert_eq!(
+            text.lines().filter(|line| line.starts_with("vt ")).count(),
+            summary.num_vertices
+        );
+        assert!(text.lines().any(|line| line == "f 1/1 2/2 3/3"));
+
+        let stl = dir.path().join("wall.stl");
+        assert!(matches!(
+            export_surface(results_path(), &stl, &options),
+            Err(ExodusError::UnsupportedFeature(_))
+        ));
+        options.variable = None;
+        options.ascii = false;
+        export_surface(results_path(), &stl, &options).unwrap();
+        let bytes = std::fs::read(&stl).unwrap();
+        assert_eq!(bytes.len(), 84 + 50 * 50);
+        assert_eq!(u32::from_le_bytes(bytes[80..84].try_into().unwrap()), 50);
+        assert!(!bytes.starts_with(b"solid"));
+    }
+
+    #[test]
+    fn test_export_errors() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("skin.ply");
+        let options = SurfaceOptions {
+            variable: Some("pressure".into()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            export_surface(results_path(), &output, &options),
+            Err(ExodusError::VariableNotDefined(_))
+        ));
+        let options = SurfaceOptions {
+            variable: Some("disp_z".into()),
+            step: Some(5),
+            ..Default::default()
+        };
+        assert!(matches!(
+            export_surface(results_path(), &output, &options),
+            Err(ExodusError::InvalidTimeStep(5))
+        ));
+        assert!(matches!(
+            export_surface(
+                results_path(),
+                dir.path().join("skin.off"),
+                &Default::default()
+            ),
+            Err(ExodusError::Other(_))
+        ));
+    }
+}
@@ -1,4 +1,500 @@
 //! Time step operations
 //!
-//! This module will contain time step management.
-//! To be implemented in Phase 7.
+//! This module contains time value I/O and the time-step queries needed when
+//! post-processing results: locating the step nearest to or bracketing a
+//! time, interpolating variables between steps, detecting restarts that
+//! moved time backwards, and logically truncating a file at a given step.
+
+use crate::backend::Backend;
+use crate::error::{EntityId, ExodusError, Result};
+use crate::types::EntityType;
+use crate::utils::constants::*;
+#[cfg(feature = "netcdf4")]
+use crate::WritableMode;
+use crate::{ExodusFile, FileMode, ReadableMode};
+
+/// Pair of time steps surrounding a time value
+///
+/// Returned by `bracket_time`. A value `v` at the requested time is
+/// interpolated as `v[lower] + fraction * (v[upper] - v[lower])`. When the
+/// time coincides with a stored step, `lower == upper` and `fraction` is 0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeBracket {
+    /// Last step at or before the requested time (0-based)
+    pub lower: usize,
+    /// First step at or after the requested time (0-based)
+    pub upper: usize,
+    /// Position of the requested time between `lower` and `upper`, in [0, 1]
+    pub fraction: f64,
+}
+
+// ====================
+// Common Operations
+// ====================
+
+impl<M: FileMode> ExodusFile<M> {
+    /// Get number of time steps
+    ///
+    /// Honors a logical truncation stored by `truncate_time_steps`.
+    ///
+    /// # Returns
+    ///
+    /// Number of time steps in the file
+    pub fn num_time_steps(&self) -> Result<usize> {
+        let stored = self.nc_file.dimension_len(DIM_TIME_STEP).unwrap_or(0);
+        Ok(self.metadata.visible_time_steps(stored))
+    }
+}
+
+// ====================
+// Write Operations
+// ====================
+
+#[cfg(feature = "netcdf4")]
+impl<M: WritableMode> ExodusFile<M> {
+    /// Write time value for a time step
+    ///
+    /// Creates the `time_step` dimension and `time_whole` variable when the
+    /// file does not have them yet. Writing the first step hidden by a
+    /// logical truncation makes that step visible again; later hidden steps
+    /// must be written in order, so stale steps are never exposed.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - Time step index (0-based)
+    /// * `time` - Time value
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidTimeStep` if `step` lies past the first step hidden by
+    /// a logical truncation, or an error if NetCDF write fails
+    pub fn put_time(&mut self, step: usize, time: f64) -> Result<()> {
+        if self
+            .metadata
+            .time_step_limit
+            .is_some_and(|limit| step > limit)
+        {
+            return Err(ExodusError::InvalidTimeStep(step));
+        }
+
+        // Ensure time_step dimension exists (may be created earlier by define_variables)
+        if self.nc_file.dimension(DIM_TIME_STEP).is_none() {
+            self.ensure_define_mode()?;
+            self.nc_file.add_unlimited_dimension(DIM_TIME_STEP)?;
+        }
+
+        // Ensure time_whole variable exists
+        if self.nc_file.variable(VAR_TIME_WHOLE).is_none() {
+            self.ensure_define_mode()?;
+            let mut var = self.nc_file.add_variable_with_type(
+                VAR_TIME_WHOLE,
+                &[DIM_TIME_STEP],
+                &self.metadata.float_type(),
+            )?;
+            var.put_attribute("name", VAR_TIME_WHOLE)?;
+        }
+
+        // Ensure we're in data mode for writing time values
+        self.ensure_data_mode()?;
+
+        // Write the time value
+        if let Some(mut var) = self.nc_file.variable_mut(VAR_TIME_WHOLE) {
+            var.put_value(time, step..step + 1)?;
+        }
+
+        if self.metadata.time_step_limit == Some(step) {
+            self.store_time_step_limit(step + 1)?;
+        }
+
+        Ok(())
+    }
+
+    /// Logically truncate the file after `num_steps` time steps
+    ///
+    /// Nothing is removed from the file: NetCDF cannot shrink the time
+    /// dimension. Instead the limit is stored in the `time_step_limit` global
+    /// attribute, and every handle that later opens the file hides the steps
+    /// past it from `times`, `num_time_steps` and every variable read. The next
+    /// `put_time` calls overwrite the hidden steps in order, which is how a restarted
+    /// analysis that went back in time is resumed cleanly. Once every stored
+    /// step has been rewritten, the attribute is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_steps` - Number of leading time steps to keep
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidTimeStep` if `num_steps` exceeds the current number of
+    /// time steps, or an error if the attribute cannot be written
+    pub fn truncate_time_steps(&mut self, num_steps: usize) -> Result<()> {
+        if num_steps > self.num_time_steps()? {
+            return Err(ExodusError::InvalidTimeStep(num_steps));
+        }
+        self.store_time_step_limit(num_steps)
+    }
+
+    /// Record a logical truncation in the file and in the metadata cache
+    ///
+    /// A limit covering every stored step removes the truncation.
+    fn store_time_step_limit(&mut self, limit: usize) -> Result<()> {
+        let stored = self.nc_file.dimension_len(DIM_TIME_STEP).unwrap_or(0);
+        self.ensure_define_mode()?;
+        if limit >= stored {
+            crate::utils::nc_sys::del_global_attribute(&self.nc_file, ATTR_TIME_STEP_LIMIT)?;
+            self.metadata.time_step_limit = None;
+        } else {
+            self.nc_file
+                .add_attribute(ATTR_TIME_STEP_LIMIT, limit as i64)?;
+            self.metadata.time_step_limit = Some(limit);
+        }
+        self.finish_definitions()
+    }
+}
+
+// ====================
+// Read Operations
+// ====================
+
+impl<M: ReadableMode> ExodusFile<M> {
+    /// Get all time values
+    ///
+    /// # Returns
+    ///
+    /// Vector of time values
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if NetCDF read fails
+    pub fn times(&self) -> Result<Vec<f64>> {
+        let mut times = if self.nc_file.has_variable(VAR_TIME_WHOLE) {
+            self.nc_file.read_all_f64(VAR_TIME_WHOLE)?
+        } else {
+            Vec::new()
+        };
+        times.truncate(self.metadata.visible_time_steps(times.len()));
+        Ok(times)
+    }
+
+    /// Get time value for a step
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - Time step index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// Time value
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Time step is out of range
+    /// - NetCDF read fails
+    pub fn time(&self, step: usize) -> Result<f64> {
+        let times = self.times()?;
+        times
+            .get(step)
+            .copied()
+            .ok_or(ExodusError::InvalidTimeStep(step))
+    }
+
+    /// Find the time step whose time is closest to `time`
+    ///
+    /// When two steps are equally close the later one wins, so data written
+    /// after a restart supersedes the original run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file has no time steps
+    pub fn nearest_time_step(&self, time: f64) -> Result<usize> {
+        nearest_step(&self.times()?, time)
+    }
+
+    /// Find the time steps bracketing `time`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file has no time steps
+    /// - `time` or a stored time value is not finite
+    /// - Time values are not increasing (see [`time_reversals`](Self::time_reversals))
+    /// - `time` lies outside the stored time range
+    pub fn bracket_time(&self, time: f64) -> Result<TimeBracket> {
+        bracket(&self.times()?, time)
+    }
+
+    /// Linearly interpolate a variable at an arbitrary time
+    ///
+    /// Works for every variable type readable with `var`.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time value inside the stored time range
+    /// * `var_type` - Entity type
+    /// * `entity_id` - Entity ID (block/set ID, or 0 for global/nodal)
+    /// * `var_index` - Variable index (0-based)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the time cannot be bracketed or the variable
+    /// cannot be read
+    pub fn interpolate_var(
+        &self,
+        time: f64,
+        var_type: EntityType,
+        entity_id: EntityId,
+        var_index: usize,
+    ) -> Result<Vec<f64>> {
+        let bracket = self.bracket_time(time)?;
+        interpolate(&bracket, |step| {
+            self.var(step, var_type, entity_id, var_index)
+        })
+    }
+
+    /// Steps at which time did not increase
+    ///
+    /// A step is reported when its time is less than or equal to the time of
+    /// the step before it, which is what a restart from an earlier state
+    /// leaves behind.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if NetCDF read fails
+    pub fn time_reversals(&self) -> Result<Vec<usize>> {
+        Ok(reversals(&self.times()?))
+    }
+
+    /// Check whether time values strictly increase
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if NetCDF read fails
+    pub fn is_time_monotonic(&self) -> Result<bool> {
+        Ok(self.time_reversals()?.is_empty())
+    }
+
+    /// Time steps that survive restarts
+    ///
+    /// Walks the steps in order; whenever time goes backwards, the earlier
+    /// steps at or after the new time are dropped in favor of the restarted
+    /// run. The result is strictly increasing in time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if NetCDF read fails
+    pub fn restart_time_steps(&self) -> Result<Vec<usize>> {
+        Ok(restart_steps(&self.times()?))
+    }
+}
+
+// ====================
+// Time Step Algorithms
+// ====================
+
+/// Index of the time closest to `time`, preferring later steps on ties
+///
+/// Steps whose time is NaN are never chosen.
+pub(crate) fn nearest_step(times: &[f64], time: f64) -> Result<usize> {
+    if time.is_nan() {
+        return Err(ExodusError::Other(
+            "Cannot find the time step nearest to NaN".to_string(),
+        ));
+    }
+    let mut best: Option<(usize, f64)> = None;
+    for (step, &t) in times.iter().enumerate() {
+        let distance = (t - time).abs();
+        if distance.is_nan() {
+            continue;
+        }
+        if best.map_or(true, |(_, d)| distance <= d) {
+            best = Some((step, distance));
+        }
+    }
+    best.map(|(step, _)| step)
+        .ok_or_else(|| ExodusError::Other("File has no time steps".to_string()))
+}
+
+/// Steps surrounding `time` in increasing time values
+pub(crate) fn bracket(times: &[f64], time: f64) -> Result<TimeBracket> {
+    let (first, last) = match (times.first(), times.last()) {
+        (Some(&first), Some(&last)) => (first, last),
+        _ => return Err(ExodusError::Other("File has no time steps".to_string())),
+    };
+    if !time.is_finite() {
+        return Err(ExodusError::Other(format!(
+            "Cannot bracket the non-finite time {}",
+            time
+        )));
+    }
+    if let Some(step) = times.iter().position(|t| !t.is_finite()) {
+        return Err(ExodusError::Other(format!(
+            "Time value at step {} is not finite",
+            step
+        )));
+    }
+    if let Some(&step) = reversals(times).first() {
+        return Err(ExodusError::Other(format!(
+            "Time values are not increasing at step {}",
+            step
+        )));
+    }
+    if !(first..=last).contains(&time) {
+        return Err(ExodusError::Other(format!(
+            "Time {} is outside the stored range [{}, {}]",
+            time, first, last
+        )));
+    }
+
+    // First step with t >= time; exists because time <= last
+    let upper = times.partition_point(|&t| t < time);
+    if times[upper] == time {
+        return Ok(TimeBracket {
+            lower: upper,
+            upper,
+            fraction: 0.0,
+        });
+    }
+    let lower = upper - 1;
+    Ok(TimeBracket {
+        lower,
+        upper,
+        fraction: (time - times[lower]) / (times[upper] - times[lower]),
+    })
+}
+
+/// Blend the values of the two bracketing steps
+pub(crate) fn interpolate(
+    bracket: &TimeBracket,
+    read: impl Fn(usize) -> Result<Vec<f64>>,
+) -> Result<Vec<f64>> {
+    let lower = read(bracket.lower)?;
+    if bracket.lower == bracket.upper {
+        return Ok(lower);
+    }
+    let upper = read(bracket.upper)?;
+    if lower.len() != upper.len() {
+        return Err(ExodusError::InvalidArrayLength {
+            expected: lower.len(),
+            actual: upper.len(),
+        });
+    }
+    Ok(lower
+        .iter()
+        .zip(&upper)
+        .map(|(a, b)| a + bracket.fraction * (b - a))
+        .collect())
+}
+
+/// Steps whose time does not exceed the previous step's time
+pub(crate) fn reversals(times: &[f64]) -> Vec<usize> {
+    times
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[1] <= pair[0])
+        .map(|(i, _)| i + 1)
+        .collect()
+}
+
+/// Steps kept after resolving restarts in favor of the latest run
+pub(crate) fn restart_steps(times: &[f64]) -> Vec<usize> {
+    let mut kept: Vec<usize> = Vec::with_capacity(times.len());
+    for (step, &t) in times.iter().enumerate() {
+        while kept.last().is_some_and(|&k| times[k] >= t) {
+            kept.pop();
+        }
+        kept.push(step);
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_step() {
+        let times = [0.0, 1.0, 2.0, 4.0];
+        assert_eq!(nearest_step(&times, -1.0).unwrap(), 0);
+        assert_eq!(nearest_step(&times, 1.2).unwrap(), 1);
+        assert_eq!(nearest_step(&times, 3.0).unwrap(), 3);
+        assert_eq!(nearest_step(&times, 10.0).unwrap(), 3);
+        assert!(nearest_step(&[], 1.0).is_err());
+
+        // Restarted run repeats t = 1.0; the later step wins
+        assert_eq!(nearest_step(&[0.0, 1.0, 2.0, 1.0], 1.0).unwrap(), 3);
+
+        // NaN times are skipped rather than poisoning the comparison
+        assert_eq!(nearest_step(&[f64::NAN, 1.0, 2.0], 1.9).unwrap(), 2);
+        assert!(nearest_step(&[f64::NAN], 0.0).is_err());
+        assert!(nearest_step(&times, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_bracket() {
+        let times = [0.0, 1.0, 3.0];
+        assert_eq!(
+            bracket(&times, 2.0).unwrap(),
+            TimeBracket {
+                lower: 1,
+                upper: 2,
+                fraction: 0.5
+            }
+        );
+        assert_eq!(
+            bracket(&times, 1.0).unwrap(),
+            TimeBracket {
+                lower: 1,
+                upper: 1,
+                fraction: 0.0
+            }
+        );
+        assert_eq!(bracket(&times, 0.0).unwrap().upper, 0);
+        assert_eq!(bracket(&times, 3.0).unwrap().lower, 2);
+        assert!(bracket(&times, 3.5).is_err());
+        assert!(bracket(&times, -0.5).is_err());
+        assert!(bracket(&[], 0.0).is_err());
+        assert!(bracket(&[0.0, 2.0, 1.0], 0.5).is_err());
+
+        // Non-finite times would defeat the ordering checks
+        assert!(bracket(&times, f64::NAN).is_err());
+        assert!(bracket(&times, f64::INFINITY).is_err());
+        assert!(bracket(&[0.0, f64::NAN, 3.0], 2.0).is_err());
+        assert!(bracket(&[f64::NEG_INFINITY, 0.0], -1.0).is_err());
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let steps = [vec![0.0, 10.0], vec![2.0, 20.0]];
+        let read = |step: usize| Ok(steps[step].clone());
+        let mid = TimeBracket {
+            lower: 0,
+            upper: 1,
+            fraction: 0.25,
+        };
+        assert_eq!(interpolate(&mid, read).unwrap(), vec![0.5, 12.5]);
+        let exact = TimeBracket {
+            lower: 1,
+            upper: 1,
+            fraction: 0.0,
+        };
+        assert_eq!(interpolate(&exact, read).unwrap(), vec![2.0, 20.0]);
+    }
+
+    #[test]
+    fn test_reversals_and_restart_steps() {
+        let monotonic = [0.0, 1.0, 2.0];
+        assert!(reversals(&monotonic).is_empty());
+        assert_eq!(restart_steps(&monotonic), vec![0, 1, 2]);
+
+        // Run reached t = 3, restarted from t = 1.5 and continued
+        let restarted = [0.0, 1.0, 2.0, 3.0, 1.5, 2.5, 3.5];
+        assert_eq!(reversals(&restarted), vec![4]);
+        assert_eq!(restart_steps(&restarted), vec![0, 1, 4, 5, 6]);
+
+        // Repeated time values count as a reversal
+        assert_eq!(reversals(&[0.0, 1.0, 1.0]), vec![2]);
+        assert_eq!(restart_steps(&[0.0, 1.0, 1.0]), vec![0, 2]);
+    }
+}
@@ -7,13 +7,14 @@
 //! Face numbering uses 1-based indexing (matching Exodus convention), while node
 //! indices within the connectivity arrays are 0-based (matching Rust arrays).
 
-use crate::types::Topology;
+use crate::types::{Block, Topology};
 
 /// Face/side definition for an element.
 ///
 /// Defines which nodes form a particular face/side of an element,
 /// using 0-based indices into the element's connectivity array.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FaceDef {
     /// Exodus side number (1-based)
     pub side_number: usize,
@@ -80,6 +81,40 @@ impl Topology {
     pub fn num_faces(&self) -> Option<usize> {
         self.faces().map(|faces| faces.len())
     }
+
+    /// Topology of a block, resolving names without a node count, such as
+    /// `HEX`, `SHELL` or `BEAM`, by the number of nodes per element
+    pub(crate) fn of_block(block: &Block) -> Self {
+        let topology = Self::from_string(&block.topology);
+        if !matches!(topology, Self::Custom(_))
+            && topology.expected_nodes() == Some(block.num_nodes_per_entry)
+        {
+            return topology;
+        }
+        let name = block.topology.trim().to_uppercase();
+        let families = [
+            (["TRUSS", "BEAM", "BAR", "ROD"].as_slice(), "BAR"),
+            (["TRI"].as_slice(), "TRI"),
+            (["SHELL", "QUAD"].as_slice(), "QUAD"),
+            (["TET"].as_slice(), "TET"),
+            (["HEX"].as_slice(), "HEX"),
+            (["WEDGE"].as_slice(), "WEDGE"),
+            (["PYR"].as_slice(), "PYRAMID"),
+            (["SPHERE", "CIRCLE", "POINT"].as_slice(), "SPHERE"),
+        ];
+        for (prefixes, family) in families {
+            if prefixes.iter().any(|prefix| name.starts_with(prefix)) {
+                let resolved = match family {
+                    "SPHERE" => Self::Sphere,
+                    _ => Self::from_string(&format!("{}{}", family, block.num_nodes_per_entry)),
+                };
+                if !matches!(resolved, Self::Custom(_)) {
+                    return resolved;
+                }
+            }
+        }
+        topology
+    }
 }
 
 /// Hexahedral element face definitions.
@@ -393,4 +428,27 @@ mod tests {
         assert_eq!(Topology::Tri3.num_faces(), Some(3));
         assert_eq!(Topology::Sphere.num_faces(), None);
     }
+
+    #[test]
+    fn test_custom_topologies_resolve_by_node_count() {
+        let block = |topology: &str, nodes| Block {
+            id: 1,
+            entity_type: crate::types::EntityType::ElemBlock,
+            topology: topology.into(),
+            num_entries: 1,
+            num_nodes_per_entry: nodes,
+            num_edges_per_entry: 0,
+            num_faces_per_entry: 0,
+            num_attributes: 0,
+        };
+        assert_eq!(Topology::of_block(&block("SHELL", 4)), Topology::Quad4);
+        assert_eq!(Topology::of_block(&block("TRISHELL", 6)), Topology::Tri6);
+        assert_eq!(Topology::of_block(&block("BEAM", 2)), Topology::Bar2);
+        assert_eq!(Topology::of_block(&block("TRUSS", 3)), Topology::Bar3);
+        assert_eq!(Topology::of_block(&block("hex", 27)), Topology::Hex27);
+        assert_eq!(
+            Topology::of_block(&block("SUPERELEMENT", 5)),
+            Topology::Custom("SUPERELEMENT".into())
+        );
+    }
 }
@@ -147,6 +147,7 @@ pub fn apply_rotation_to_vector(matrix: &Matrix3x3, vec: &[f64; 3]) -> [f64; 3]
 
 /// Euler angle sequence type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EulerSequence {
     /// Intrinsic rotations (lowercase axes: 'x', 'y', 'z')
     /// Rotations are applied in the body frame
@@ -4,6 +4,8 @@ use crate::error::EntityId;
 
 /// All entity types supported by Exodus
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[repr(i32)]
 pub enum EntityType {
     /// Element block
@@ -72,6 +74,7 @@ impl std::fmt::Display for EntityType {
 
 /// Initialization parameters for new Exodus files
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InitParams {
     /// Title of the database (max 80 characters)
     pub title: String,
@@ -144,6 +147,7 @@ impl Default for InitParams {
 
 /// Block (element/edge/face) parameters
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Block {
     /// Block ID
     pub id: EntityId,
@@ -165,6 +169,7 @@ pub struct Block {
 
 /// Set (node/edge/face/elem/side) parameters
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Set {
     /// Set ID
     pub id: EntityId,
@@ -178,6 +183,7 @@ pub struct Set {
 
 /// Node set with node IDs and optional distribution factors
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodeSet {
     /// Set ID
     pub id: EntityId,
@@ -189,6 +195,7 @@ pub struct NodeSet {
 
 /// Side set with element-side pairs and optional distribution factors
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SideSet {
     /// Set ID
     pub id: EntityId,
@@ -202,6 +209,7 @@ pub struct SideSet {
 
 /// Entity set (edge, face, or element set)
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntitySet {
     /// Set ID
     pub id: EntityId,
@@ -213,6 +221,7 @@ pub struct EntitySet {
 
 /// Assembly (hierarchical grouping)
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Assembly {
     /// Assembly ID
     pub id: EntityId,
@@ -226,6 +235,7 @@ pub struct Assembly {
 
 /// Blob (arbitrary binary data)
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Blob {
     /// Blob ID
     pub id: EntityId,
@@ -235,6 +245,7 @@ pub struct Blob {
 
 /// Attribute metadata
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Attribute {
     /// Entity type this attribute belongs to
     pub entity_type: EntityType,
@@ -248,6 +259,7 @@ pub struct Attribute {
 
 /// Attribute value types
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AttributeType {
     /// Integer attribute
     Integer,
@@ -259,15 +271,17 @@ pub enum AttributeType {
 
 /// Truth table for sparse variable storage
 ///
-/// Truth tables indicate which element blocks have which variables defined.
-/// This allows for efficient storage when not all blocks have all variables.
+/// Truth tables indicate which blocks (element, edge, face) or sets (node,
+/// edge, face, side, element) have which variables defined. This allows for
+/// efficient storage when not all blocks or sets have all variables.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TruthTable {
     /// Entity type this truth table applies to
     pub var_type: EntityType,
     /// Number of variables
     pub num_vars: usize,
-    /// Number of blocks
+    /// Number of blocks (or sets)
     pub num_blocks: usize,
     /// Flat 2D array: table[block_idx * num_vars + var_idx]
     pub table: Vec<bool>,
@@ -278,8 +292,8 @@ impl TruthTable {
     ///
     /// # Arguments
     ///
-    /// * `var_type` - Entity type (typically ElemBlock, EdgeBlock, or FaceBlock)
-    /// * `num_blocks` - Number of blocks
+    /// * `var_type` - Block or set type (e.g. ElemBlock, FaceBlock, SideSet)
+    /// * `num_blocks` - Number of blocks (or sets)
     /// * `num_vars` - Number of variables
     pub fn new(var_type: EntityType, num_blocks: usize, num_vars: usize) -> Self {
         Self {
@@ -369,6 +383,7 @@ impl TruthTable {
 
 /// QA Record (software provenance tracking)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QaRecord {
     /// Code name (max 32 characters)
     pub code_name: String,
@@ -554,8 +569,25 @@ impl std::fmt::Display for Topology {
     }
 }
 
+/// Topologies are serialized by their Exodus name, e.g. `"HEX8"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for Topology {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Topology {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(Self::from_string(&name))
+    }
+}
+
 /// Structured connectivity with shape information
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Connectivity {
     /// Block ID this connectivity belongs to
     pub block_id: EntityId,
@@ -619,6 +651,7 @@ impl<'a> Iterator for ConnectivityIterator<'a> {
 
 /// File format type
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FileFormat {
     /// Classic NetCDF-3 format
     NetCdf3Classic,
@@ -632,8 +665,30 @@ pub enum FileFormat {
     NetCdfCdf5,
 }
 
+impl FileFormat {
+    /// Whether this is an HDF5-based NetCDF-4 format
+    pub fn is_netcdf4(&self) -> bool {
+        matches!(self, FileFormat::NetCdf4 | FileFormat::NetCdf4Classic)
+    }
+
+    /// Whether variables can be chunked and compressed
+    pub fn supports_compression(&self) -> bool {
+        self.is_netcdf4()
+    }
+
+    /// Whether 64-bit integer (and unsigned byte) variables can be stored
+    ///
+    /// The classic data model (NetCDF-3 classic, 64-bit offset and
+    /// NetCDF-4 classic) only has 32-bit integers; CDF-5 and NetCDF-4 add
+    /// the 64-bit types.
+    pub fn supports_int64(&self) -> bool {
+        matches!(self, FileFormat::NetCdf4 | FileFormat::NetCdfCdf5)
+    }
+}
+
 /// File creation mode
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CreateMode {
     /// Overwrite existing file
     Clobber,
@@ -643,6 +698,7 @@ pub enum CreateMode {
 
 /// Floating point word size
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FloatSize {
     /// 32-bit (4 byte) floats
     Float32,
@@ -652,6 +708,7 @@ pub enum FloatSize {
 
 /// Integer ID word size mode
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Int64Mode {
     /// Classic 32-bit IDs
     Int32,
@@ -660,33 +717,52 @@ pub enum Int64Mode {
 }
 
 /// Compression settings
-#[derive(Debug, Copy, Clone)]
+///
+/// Compression is applied to the bulk-data variables (coordinates, connectivity,
+/// set entry lists and results) and requires a NetCDF-4 file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Compression {
     /// No compression
     None,
     /// Gzip compression with level (1-9)
     Gzip(u8),
-    /// Szip compression
+    /// Szip compression (nearest-neighbor coding, 32 pixels per block)
     Szip,
-    /// Zstandard compression with level (1-9)
+    /// Zstandard compression with level (1-22)
+    ///
+    /// Requires a netCDF library with the zstandard filter plugin installed.
     Zstd(u8),
 }
 
 /// File creation options
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CreateOptions {
     /// Creation mode (clobber or noclobber)
     pub mode: CreateMode,
+    /// On-disk NetCDF format
+    pub format: FileFormat,
     /// Floating point size
     pub float_size: FloatSize,
     /// Integer ID size mode
     pub int64_mode: Int64Mode,
     /// Compression settings
     pub compression: Option<Compression>,
+    /// Apply the byte-shuffle filter before compressing (ignored without compression)
+    pub shuffle: bool,
     /// Enable parallel I/O (requires MPI-enabled HDF5)
     pub parallel: bool,
     /// Performance configuration (cache and chunk settings)
     pub performance: Option<crate::performance::PerformanceConfig>,
+    /// Storage layout for nodal variables
+    ///
+    /// [`VarStorageMode::Combined`] writes a single `vals_nod_var` array; any
+    /// other value writes one `vals_nod_var{n}` array per variable. Exodus
+    /// only defines the combined layout for nodal variables, so block and set
+    /// variables are always stored separately, and global variables always
+    /// use the combined `vals_glo_var` array.
+    pub nodal_var_storage: VarStorageMode,
 }
 
 /// How variable data is stored in the NetCDF file.
@@ -694,6 +770,7 @@ pub struct CreateOptions {
 /// Exodus II supports two storage formats for variable data, and a single
 /// file can mix formats for different entity types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VarStorageMode {
     /// Separate variables per index: `vals_nod_var1`, `vals_nod_var2`, etc.
     /// Shape: `(time_step, num_entities)`
@@ -710,8 +787,10 @@ pub enum VarStorageMode {
 ///
 /// This struct captures the storage format used by the file for each
 /// entity type. The format is detected automatically when opening a file
-/// by examining which NetCDF variables are present.
+/// by examining which NetCDF variables are present. New files take the nodal
+/// layout from [`CreateOptions::nodal_var_storage`].
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FileStorageFormat {
     /// Storage mode for nodal variables
     pub nodal: VarStorageMode,
@@ -735,15 +814,180 @@ pub struct FileStorageFormat {
     pub global: VarStorageMode,
 }
 
+impl FileStorageFormat {
+    /// Get the storage mode for a variable type
+    ///
+    /// Returns `None` for entity types that cannot have variables.
+    pub fn get(&self, var_type: EntityType) -> Option<VarStorageMode> {
+        Some(match var_type {
+            EntityType::Global => self.global,
+            EntityType::Nodal => self.nodal,
+            EntityType::ElemBlock => self.elem_block,
+            EntityType::EdgeBlock => self.edge_block,
+            EntityType::FaceBlock => self.face_block,
+            EntityType::NodeSet => self.node_set,
+            EntityType::EdgeSet => self.edge_set,
+            EntityType::FaceSet => self.face_set,
+            EntityType::SideSet => self.side_set,
+            EntityType::ElemSet => self.elem_set,
+            _ => return None,
+        })
+    }
+}
+
 impl Default for CreateOptions {
     fn default() -> Self {
         Self {
             mode: CreateMode::NoClobber,
+            format: FileFormat::NetCdf4,
             float_size: FloatSize::Float64,
             int64_mode: Int64Mode::Int64,
             compression: None,
+            shuffle: true,
             parallel: false,
             performance: None, // Auto-detect on file creation if None
+            nodal_var_storage: VarStorageMode::Separate,
         }
     }
 }
+
+// ====================
+// Nemesis (parallel decomposition) types
+// ====================
+
+/// Kind of Nemesis parallel file
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NemesisFileType {
+    /// One file per processor (`.e.N.M`), the only layout written by this crate
+    #[default]
+    Parallel,
+    /// A single file holding the data of several processors
+    Scalar,
+}
+
+/// Nemesis initialization information
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NemesisInitInfo {
+    /// Total number of processors in the decomposition
+    pub num_procs: usize,
+    /// Number of processors whose data is stored in this file
+    pub num_procs_in_file: usize,
+    /// Parallel or scalar file
+    pub file_type: NemesisFileType,
+}
+
+/// Sizes of the undecomposed (global) mesh
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlobalInitParams {
+    /// Number of nodes in the global mesh
+    pub num_nodes: usize,
+    /// Number of elements in the global mesh
+    pub num_elems: usize,
+    /// Number of element blocks in the global mesh
+    pub num_elem_blocks: usize,
+    /// Number of node sets in the global mesh
+    pub num_node_sets: usize,
+    /// Number of side sets in the global mesh
+    pub num_side_sets: usize,
+}
+
+/// IDs and sizes of the global element blocks, node sets or side sets
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlobalEntityParams {
+    /// Entity IDs
+    pub ids: Vec<EntityId>,
+    /// Number of elements, nodes or sides in each entity of the global mesh
+    pub entry_counts: Vec<usize>,
+    /// Number of distribution factors per set (empty for element blocks)
+    pub dist_fact_counts: Vec<usize>,
+}
+
+/// Load-balance parameters of one processor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoadBalanceParams {
+    /// Nodes used only by elements of this processor
+    pub num_internal_nodes: usize,
+    /// Owned nodes shared with other processors
+    pub num_border_nodes: usize,
+    /// Nodes owned by other processors
+    pub num_external_nodes: usize,
+    /// Elements with no neighbor on another processor
+    pub num_internal_elems: usize,
+    /// Elements with a neighbor on another processor
+    pub num_border_elems: usize,
+    /// Number of node communication maps
+    pub num_node_cmaps: usize,
+    /// Number of element communication maps
+    pub num_elem_cmaps: usize,
+}
+
+/// Internal, border and external node maps of one processor
+///
+/// Entries are local node numbers (1-based).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessorNodeMaps {
+    /// Internal nodes
+    pub internal: Vec<i64>,
+    /// Border nodes
+    pub border: Vec<i64>,
+    /// External nodes
+    pub external: Vec<i64>,
+}
+
+/// Internal and border element maps of one processor
+///
+/// Entries are local element numbers (1-based).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessorElemMaps {
+    /// Internal elements
+    pub internal: Vec<i64>,
+    /// Border elements
+    pub border: Vec<i64>,
+}
+
+/// IDs and sizes of the communication maps of one processor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommMapParams {
+    /// Node communication map IDs
+    pub node_cmap_ids: Vec<EntityId>,
+    /// Number of nodes in each node communication map
+    pub node_cmap_node_counts: Vec<usize>,
+    /// Element communication map IDs
+    pub elem_cmap_ids: Vec<EntityId>,
+    /// Number of elements in each element communication map
+    pub elem_cmap_elem_counts: Vec<usize>,
+}
+
+/// Nodes shared with other processors
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeCommMap {
+    /// Map ID, usually the neighboring processor
+    pub id: EntityId,
+    /// Local node numbers (1-based)
+    pub node_ids: Vec<i64>,
+    /// Processor sharing each node
+    pub proc_ids: Vec<i64>,
+}
+
+/// Element faces shared with other processors
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElemCommMap {
+    /// Map ID, usually the neighboring processor
+    pub id: EntityId,
+    /// Local element numbers (1-based)
+    pub elem_ids: Vec<i64>,
+    /// Side of each element on the processor boundary
+    pub side_ids: Vec<i64>,
+    /// Processor on the other side of each face
+    pub proc_ids: Vec<i64>,
+}
@@ -11,15 +11,18 @@
 pub const API_VERSION: f32 = 9.04;
 
 /// Exodus file format version
+#[cfg(feature = "netcdf4")]
 pub const FILE_VERSION: f32 = 2.0;
 
 /// Maximum length for title string
 pub const MAX_TITLE_LENGTH: usize = 80;
 
 /// Maximum length for QA record strings
+#[cfg(feature = "netcdf4")]
 pub const MAX_QA_STRING_LENGTH: usize = 32;
 
 /// Maximum length for info record strings
+#[cfg(feature = "netcdf4")]
 pub const MAX_INFO_STRING_LENGTH: usize = 80;
 
 /// Maximum length for entity/variable names
@@ -33,11 +36,21 @@ pub const MAX_NAME_LENGTH: usize = 32;
 pub const ATTR_TITLE: &str = "title";
 
 /// API version attribute name
+#[cfg(feature = "netcdf4")]
 pub const ATTR_API_VERSION: &str = "api_version";
 
 /// File version attribute name
 pub const ATTR_VERSION: &str = "version";
 
+/// Floating point word size attribute name (4 or 8 bytes)
+pub const ATTR_FLOATING_POINT_WORD_SIZE: &str = "floating_point_word_size";
+
+/// Integer storage attribute name (0 = 32-bit, nonzero = 64-bit)
+pub const ATTR_INT64_STATUS: &str = "int64_status";
+
+/// Number of leading time steps kept by a logical truncation
+pub const ATTR_TIME_STEP_LIMIT: &str = "time_step_limit";
+
 // =============================================================================
 // Common Dimension Names
 // =============================================================================
@@ -85,36 +98,47 @@ pub const DIM_NUM_ELEM_SETS: &str = "num_elem_sets";
 pub const DIM_TIME_STEP: &str = "time_step";
 
 /// String length dimension (for variable names, etc.)
+#[cfg(feature = "netcdf4")]
 pub const DIM_LEN_STRING: &str = "len_string";
 
 /// Name length dimension
+#[cfg(feature = "netcdf4")]
 pub const DIM_LEN_NAME: &str = "len_name";
 
 /// Line length dimension (for info records)
+#[cfg(feature = "netcdf4")]
 pub const DIM_LEN_LINE: &str = "len_line";
 
 /// Number of QA records
+#[cfg(feature = "netcdf4")]
 pub const DIM_NUM_QA_REC: &str = "num_qa_rec";
 
 /// Number of QA dimensions (always 4: code, version, date, time)
+#[cfg(feature = "netcdf4")]
 pub const DIM_NUM_QA_DIM: &str = "num_qa_dim";
 
 /// Number of info records
+#[cfg(feature = "netcdf4")]
 pub const DIM_NUM_INFO: &str = "num_info";
 
 /// Number of global variables
+#[cfg(feature = "netcdf4")]
 pub const DIM_NUM_GLO_VAR: &str = "num_glo_var";
 
 /// Number of nodal variables
+#[cfg(feature = "netcdf4")]
 pub const DIM_NUM_NOD_VAR: &str = "num_nod_var";
 
 /// Number of element variables
+#[cfg(feature = "netcdf4")]
 pub const DIM_NUM_ELEM_VAR: &str = "num_elem_var";
 
 /// Number of edge variables
+#[cfg(feature = "netcdf4")]
 pub const DIM_NUM_EDGE_VAR: &str = "num_edge_var";
 
 /// Number of face variables
+#[cfg(feature = "netcdf4")]
 pub const DIM_NUM_FACE_VAR: &str = "num_face_var";
 
 /// Number of node maps
@@ -157,6 +181,9 @@ pub const VAR_TIME_WHOLE: &str = "time_whole";
 /// Global variables storage (combined format)
 pub const VAR_VALS_GLO_VAR: &str = "vals_glo_var";
 
+/// Nodal variable values (combined storage)
+pub const VAR_VALS_NOD_VAR: &str = "vals_nod_var";
+
 /// Global variable names
 pub const VAR_NAME_GLO_VAR: &str = "name_glo_var";
 
@@ -181,8 +208,186 @@ pub const VAR_EDGE_VAR_TAB: &str = "edge_var_tab";
 /// Face variable truth table
 pub const VAR_FACE_VAR_TAB: &str = "face_var_tab";
 
+/// Node set variable truth table
+pub const VAR_NSET_VAR_TAB: &str = "nset_var_tab";
+
+/// Edge set variable truth table
+pub const VAR_ESET_VAR_TAB: &str = "eset_var_tab";
+
+/// Face set variable truth table
+pub const VAR_FSET_VAR_TAB: &str = "fset_var_tab";
+
+/// Side set variable truth table
+pub const VAR_SSET_VAR_TAB: &str = "sset_var_tab";
+
+/// Element set variable truth table
+pub const VAR_ELSET_VAR_TAB: &str = "elset_var_tab";
+
 /// QA records variable
 pub const VAR_QA_RECORDS: &str = "qa_records";
 
 /// Info records variable
 pub const VAR_INFO_RECORDS: &str = "info_records";
+
+// =============================================================================
+// Nemesis (Parallel Decomposition) Names
+// =============================================================================
+
+/// Nemesis file format version
+#[cfg(feature = "netcdf4")]
+pub const NEMESIS_FILE_VERSION: f32 = 2.6;
+
+/// Nemesis file version attribute name
+#[cfg(feature = "netcdf4")]
+pub const ATTR_NEM_FILE_VERSION: &str = "nemesis_file_version";
+
+/// Nemesis API version attribute name
+#[cfg(feature = "netcdf4")]
+pub const ATTR_NEM_API_VERSION: &str = "nemesis_api_version";
+
+/// Number of processors in the decomposition
+pub const DIM_NUM_PROCS: &str = "num_processors";
+
+/// Number of processors stored in the file
+pub const DIM_NUM_PROCS_F: &str = "num_procs_file";
+
+/// Number of nodes in the global mesh
+pub const DIM_NUM_NODES_GLOBAL: &str = "num_nodes_global";
+
+/// Number of elements in the global mesh
+pub const DIM_NUM_ELEMS_GLOBAL: &str = "num_elems_global";
+
+/// Number of element blocks in the global mesh
+pub const DIM_NUM_ELBLK_GLOBAL: &str = "num_el_blk_global";
+
+/// Number of node sets in the global mesh
+pub const DIM_NUM_NS_GLOBAL: &str = "num_ns_global";
+
+/// Number of side sets in the global mesh
+pub const DIM_NUM_SS_GLOBAL: &str = "num_ss_global";
+
+/// Number of internal nodes
+pub const DIM_NUM_INT_NODES: &str = "num_int_node";
+
+/// Number of border nodes
+pub const DIM_NUM_BOR_NODES: &str = "num_bor_node";
+
+/// Number of external nodes
+pub const DIM_NUM_EXT_NODES: &str = "num_ext_node";
+
+/// Number of internal elements
+pub const DIM_NUM_INT_ELEMS: &str = "num_int_elem";
+
+/// Number of border elements
+pub const DIM_NUM_BOR_ELEMS: &str = "num_bor_elem";
+
+/// Number of node communication maps
+pub const DIM_NUM_N_CMAPS: &str = "num_n_cmaps";
+
+/// Number of element communication maps
+pub const DIM_NUM_E_CMAPS: &str = "num_e_cmaps";
+
+/// Total entries of all node communication maps
+#[cfg(feature = "netcdf4")]
+pub const DIM_NCNT_CMAP: &str = "ncnt_cmap";
+
+/// Total entries of all element communication maps
+#[cfg(feature = "netcdf4")]
+pub const DIM_ECNT_CMAP: &str = "ecnt_cmap";
+
+/// Nemesis file type (0 = parallel, 1 = scalar)
+pub const VAR_FILE_TYPE: &str = "nem_ftype";
+
+/// Global element block IDs
+pub const VAR_ELBLK_IDS_GLOBAL: &str = "el_blk_ids_global";
+
+/// Global element block sizes
+pub const VAR_ELBLK_CNT_GLOBAL: &str = "el_blk_cnt_global";
+
+/// Global node set IDs
+pub const VAR_NS_IDS_GLOBAL: &str = "ns_ids_global";
+
+/// Global node set sizes
+pub const VAR_NS_NODE_CNT_GLOBAL: &str = "ns_node_cnt_global";
+
+/// Global node set distribution factor counts
+pub const VAR_NS_DF_CNT_GLOBAL: &str = "ns_df_cnt_global";
+
+/// Global side set IDs
+pub const VAR_SS_IDS_GLOBAL: &str = "ss_ids_global";
+
+/// Global side set sizes
+pub const VAR_SS_SIDE_CNT_GLOBAL: &str = "ss_side_cnt_global";
+
+/// Global side set distribution factor counts
+pub const VAR_SS_DF_CNT_GLOBAL: &str = "ss_df_cnt_global";
+
+/// Internal node map status
+#[cfg(feature = "netcdf4")]
+pub const VAR_INT_N_STAT: &str = "int_n_stat";
+
+/// Border node map status
+#[cfg(feature = "netcdf4")]
+pub const VAR_BOR_N_STAT: &str = "bor_n_stat";
+
+/// External node map status
+#[cfg(feature = "netcdf4")]
+pub const VAR_EXT_N_STAT: &str = "ext_n_stat";
+
+/// Internal element map status
+#[cfg(feature = "netcdf4")]
+pub const VAR_INT_E_STAT: &str = "int_e_stat";
+
+/// Border element map status
+#[cfg(feature = "netcdf4")]
+pub const VAR_BOR_E_STAT: &str = "bor_e_stat";
+
+/// Internal node map
+pub const VAR_NODE_MAP_INT: &str = "node_mapi";
+
+/// Border node map
+pub const VAR_NODE_MAP_BOR: &str = "node_mapb";
+
+/// External node map
+pub const VAR_NODE_MAP_EXT: &str = "node_mape";
+
+/// Internal element map
+pub const VAR_ELEM_MAP_INT: &str = "elem_mapi";
+
+/// Border element map
+pub const VAR_ELEM_MAP_BOR: &str = "elem_mapb";
+
+/// Node communication map IDs
+pub const VAR_N_COMM_IDS: &str = "n_comm_ids";
+
+/// Node communication map status
+#[cfg(feature = "netcdf4")]
+pub const VAR_N_COMM_STAT: &str = "n_comm_stat";
+
+/// End offsets of the node communication maps
+pub const VAR_N_COMM_DATA_IDX: &str = "n_comm_data_idx";
+
+/// Node communication map node numbers
+pub const VAR_N_COMM_NIDS: &str = "n_comm_nids";
+
+/// Node communication map processors
+pub const VAR_N_COMM_PROC: &str = "n_comm_proc";
+
+/// Element communication map IDs
+pub const VAR_E_COMM_IDS: &str = "e_comm_ids";
+
+/// Element communication map status
+#[cfg(feature = "netcdf4")]
+pub const VAR_E_COMM_STAT: &str = "e_comm_stat";
+
+/// End offsets of the element communication maps
+pub const VAR_E_COMM_DATA_IDX: &str = "e_comm_data_idx";
+
+/// Element communication map element numbers
+pub const VAR_E_COMM_EIDS: &str = "e_comm_eids";
+
+/// Element communication map sides
+pub const VAR_E_COMM_SIDS: &str = "e_comm_sids";
+
+/// Element communication map processors
+pub const VAR_E_COMM_PROC: &str = "e_comm_proc";
@@ -2,4 +2,7 @@
 
 pub mod constants;
 pub mod naming;
+#[cfg(feature = "netcdf4")]
+pub(crate) mod nc_sys;
+#[cfg(feature = "netcdf4")]
 pub mod netcdf_ext;
@@ -6,6 +6,7 @@
 #![allow(dead_code)] // Many functions provided for future use
 
 use crate::types::EntityType;
+use crate::utils::constants::MAX_NAME_LENGTH;
 
 // =============================================================================
 // Dimension Names
@@ -65,6 +66,16 @@ pub fn set_entries_dim(entity_type: EntityType, set_index: usize) -> String {
     }
 }
 
+/// Get the dimension name for entries in a block or set.
+pub fn entity_entries_dim(entity_type: EntityType, index: usize) -> String {
+    match entity_type {
+        EntityType::ElemBlock | EntityType::EdgeBlock | EntityType::FaceBlock => {
+            block_entries_dim(entity_type, index)
+        }
+        _ => set_entries_dim(entity_type, index),
+    }
+}
+
 /// Get the dimension name for distribution factors in a set.
 pub fn set_dist_factors_dim(entity_type: EntityType, set_index: usize) -> String {
     match entity_type {
@@ -213,6 +224,21 @@ pub fn elemset_var(var_index: usize, set_index: usize) -> String {
     format!("vals_elset_var{}els{}", var_index + 1, set_index + 1)
 }
 
+/// Get the variable storage name (separate format) for a block or set variable.
+pub fn entity_var(entity_type: EntityType, var_index: usize, entity_index: usize) -> String {
+    match entity_type {
+        EntityType::ElemBlock => elem_var(var_index, entity_index),
+        EntityType::EdgeBlock => edge_var(var_index, entity_index),
+        EntityType::FaceBlock => face_var(var_index, entity_index),
+        EntityType::NodeSet => nodeset_var(var_index, entity_index),
+        EntityType::EdgeSet => edgeset_var(var_index, entity_index),
+        EntityType::FaceSet => faceset_var(var_index, entity_index),
+        EntityType::SideSet => sideset_var(var_index, entity_index),
+        EntityType::ElemSet => elemset_var(var_index, entity_index),
+        _ => panic!("Not a block or set type: {}", entity_type),
+    }
+}
+
 /// Get the reduction variable storage variable name for blocks.
 pub fn block_reduction_var(entity_type: EntityType, block_index: usize) -> String {
     match entity_type {
@@ -278,19 +304,38 @@ pub fn entity_set_var(entity_type: EntityType, set_index: usize) -> String {
 // Truth Table Variable Names
 // =============================================================================
 
-/// Get the truth table variable name for a block type.
+/// Get the truth table variable name for a block or set type.
 pub fn truth_table_var(entity_type: EntityType) -> &'static str {
     match entity_type {
         EntityType::ElemBlock => "elem_var_tab",
         EntityType::EdgeBlock => "edge_var_tab",
         EntityType::FaceBlock => "face_var_tab",
+        EntityType::NodeSet => "nset_var_tab",
+        EntityType::EdgeSet => "eset_var_tab",
+        EntityType::FaceSet => "fset_var_tab",
+        EntityType::SideSet => "sset_var_tab",
+        EntityType::ElemSet => "elset_var_tab",
         _ => panic!(
-            "Truth tables only supported for block types, got {}",
+            "Truth tables only supported for block and set types, got {}",
             entity_type
         ),
     }
 }
 
+// =============================================================================
+// Entity Names
+// =============================================================================
+
+/// Cut a block or set name from another format to the longest name Exodus
+/// allows, at a character boundary.
+pub fn truncate_name(name: &str) -> String {
+    let mut end = name.len().min(MAX_NAME_LENGTH);
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    name[..end].to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -328,6 +373,18 @@ mod tests {
         assert_eq!(nodal_var(0), "vals_nod_var1");
         assert_eq!(elem_var(1, 0), "vals_elem_var2eb1");
         assert_eq!(nodeset_var(0, 2), "vals_nset_var1ns3");
+        assert_eq!(
+            entity_var(EntityType::FaceBlock, 0, 1),
+            "vals_face_var1fab2"
+        );
+        assert_eq!(entity_var(EntityType::ElemSet, 2, 0), "vals_elset_var3els1");
+    }
+
+    #[test]
+    fn test_truth_table_var() {
+        assert_eq!(truth_table_var(EntityType::ElemBlock), "elem_var_tab");
+        assert_eq!(truth_table_var(EntityType::SideSet), "sset_var_tab");
+        assert_eq!(entity_entries_dim(EntityType::EdgeSet, 0), "num_edge_es1");
     }
 
     #[test]
@@ -0,0 +1,459 @@
+//! Direct netCDF-C calls not exposed by the `netcdf` crate
+//!
+//! The safe `netcdf` bindings do not expose the on-disk format of a file,
+//! filter information for existing variables, the szip and zstandard
+//! filters, or per-variable chunk caches. The helpers in this module call the C library directly on the
+//! netCDF id of an already open handle.
+//!
+//! The in-memory API of `netcdf_mem.h` (netCDF-C 4.6.2 and later) is declared
+//! here as well: `netcdf_sys` only binds it when its build detects mmap
+//! support, and keeps the fields of its `NC_memio` private.
+
+use crate::error::{ExodusError, Result};
+use crate::performance::CacheConfig;
+use crate::types::{Compression, FileFormat};
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int, c_void};
+
+/// Szip nearest-neighbor coding method (`NC_SZIP_NN` in `netcdf_filter.h`)
+const NC_SZIP_NN: c_int = 32;
+
+/// Szip pixels per block, matching the Exodus C library default
+const SZIP_PIXELS_PER_BLOCK: c_int = 32;
+
+// `netcdf::File` must stay a transparent wrapper around the netCDF id. This
+// only catches size changes, so the workspace pins `netcdf` to the exact
+// release whose layout is described below.
+const _: () = assert!(std::mem::size_of::<netcdf::File>() == std::mem::size_of::<c_int>());
+const _: () = assert!(std::mem::size_of::<netcdf::FileMut>() == std::mem::size_of::<c_int>());
+
+/// NetCDF id of an open file handle
+///
+/// `netcdf::File` is `#[repr(transparent)]` over its internal raw file, which
+/// is itself `#[repr(transparent)]` over the id returned by `nc_open` or
+/// `nc_create`. `netcdf::FileMut` derefs to the same `File`. These are private
+/// details of `netcdf` 0.11.1, which is why that version is pinned exactly.
+pub(crate) fn ncid(file: &netcdf::File) -> c_int {
+    // SAFETY: the layout of `netcdf::File` is exactly one `c_int` (checked above),
+    // and the reference is valid for reads.
+    unsafe { *(file as *const netcdf::File).cast::<c_int>() }
+}
+
+/// Read-only handle owning the netCDF id `ncid`
+///
+/// # Safety
+///
+/// `ncid` must identify an open file that nothing else closes; the handle
+/// closes it when dropped.
+unsafe fn file_from_ncid(ncid: c_int) -> netcdf::File {
+    // SAFETY: same layout as described at `ncid`; the caller hands over the id.
+    unsafe { std::mem::transmute::<c_int, netcdf::File>(ncid) }
+}
+
+/// Writable handle owning the netCDF id `ncid`
+///
+/// # Safety
+///
+/// As for [`file_from_ncid`], and the file must be open for writing.
+unsafe fn file_mut_from_ncid(ncid: c_int) -> netcdf::FileMut {
+    // SAFETY: same layout as described at `ncid`; the caller hands over the id.
+    unsafe { std::mem::transmute::<c_int, netcdf::FileMut>(ncid) }
+}
+
+/// Convert a netCDF status code into a `Result`
+fn check(status: c_int, context: impl FnOnce() -> String) -> Result<()> {
+    if status == netcdf_sys::NC_NOERR {
+        Ok(())
+    } else {
+        Err(ExodusError::Other(format!(
+            "{} (netCDF status {})",
+            context(),
+            status
+        )))
+    }
+}
+
+/// On-disk storage properties of an existing file
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StorageInfo {
+    /// NetCDF file format
+    pub format: FileFormat,
+    /// First compression filter found on a variable, with its shuffle setting
+    pub compression: Option<(Compression, bool)>,
+}
+
+/// Inspect the format and compression filters of an open file
+pub(crate) fn inquire_storage(file: &netcdf::File) -> Result<StorageInfo> {
+    let ncid = ncid(file);
+    let _guard = netcdf_sys::libnetcdf_lock.lock();
+    Ok(StorageInfo {
+        format: inquire_format(ncid)?,
+        compression: inquire_compression(ncid),
+    })
+}
+
+/// Map the netCDF format code of an open file to a [`FileFormat`]
+fn inquire_format(ncid: c_int) -> Result<FileFormat> {
+    let mut format: c_int = 0;
+    // SAFETY: format is a valid out pointer.
+    let status = unsafe { netcdf_sys::nc_inq_format(ncid, &mut format) };
+    check(status, || "Failed to determine the file format".to_string())?;
+    match format {
+        netcdf_sys::NC_FORMAT_CLASSIC => Ok(FileFormat::NetCdf3Classic),
+        netcdf_sys::NC_FORMAT_64BIT_OFFSET => Ok(FileFormat::NetCdf364BitOffset),
+        netcdf_sys::NC_FORMAT_NETCDF4_CLASSIC => Ok(FileFormat::NetCdf4Classic),
+        netcdf_sys::NC_FORMAT_64BIT_DATA => Ok(FileFormat::NetCdfCdf5),
+        netcdf_sys::NC_FORMAT_NETCDF4 => Ok(FileFormat::NetCdf4),
+        other => Err(ExodusError::UnsupportedFeature(format!(
+            "Unknown netCDF format code {}",
+            other
+        ))),
+    }
+}
+
+/// Detect the compression filter applied to the bulk-data variables of a file
+///
+/// Returns the first deflate, szip or zstandard filter found along with
+/// whether the shuffle filter is active on that variable. Files without
+/// filtered variables (including all NetCDF-3 files) report `None`.
+fn inquire_compression(ncid: c_int) -> Option<(Compression, bool)> {
+    let mut nvars: c_int = 0;
+    // SAFETY: passing a null varids pointer only queries the count.
+    let status = unsafe { netcdf_sys::nc_inq_varids(ncid, &mut nvars, std::ptr::null_mut()) };
+    if status != netcdf_sys::NC_NOERR || nvars <= 0 {
+        return None;
+    }
+    let mut varids = vec![0 as c_int; nvars as usize];
+    // SAFETY: varids has room for nvars entries.
+    let status = unsafe { netcdf_sys::nc_inq_varids(ncid, &mut nvars, varids.as_mut_ptr()) };
+    if status != netcdf_sys::NC_NOERR {
+        return None;
+    }
+
+    for varid in varids {
+        let mut shuffle: c_int = 0;
+        let mut deflate: c_int = 0;
+        let mut level: c_int = 0;
+        // SAFETY: all out pointers are valid for writes.
+        let status = unsafe {
+            netcdf_sys::nc_inq_var_deflate(ncid, varid, &mut shuffle, &mut deflate, &mut level)
+        };
+        if status == netcdf_sys::NC_NOERR && deflate != 0 {
+            return Some((Compression::Gzip(level.clamp(0, 9) as u8), shuffle != 0));
+        }
+
+        let mut options_mask: c_int = 0;
+        let mut pixels_per_block: c_int = 0;
+        // SAFETY: all out pointers are valid for writes.
+        let status = unsafe {
+            netcdf_sys::nc_inq_var_szip(ncid, varid, &mut options_mask, &mut pixels_per_block)
+        };
+        if status == netcdf_sys::NC_NOERR && options_mask != 0 {
+            return Some((Compression::Szip, shuffle != 0));
+        }
+
+        let mut has_zstd: c_int = 0;
+        let mut zstd_level: c_int = 0;
+        // SAFETY: all out pointers are valid for writes.
+        let status = unsafe {
+            netcdf_sys::nc_inq_var_zstandard(ncid, varid, &mut has_zstd, &mut zstd_level)
+        };
+        if status == netcdf_sys::NC_NOERR && has_zstd != 0 {
+            return Some((
+                Compression::Zstd(zstd_level.clamp(0, 22) as u8),
+                shuffle != 0,
+            ));
+        }
+    }
+    None
+}
+
+/// Delete a global attribute, if present
+///
+/// The `netcdf` crate cannot remove attributes. The file must be in define
+/// mode unless it uses the NetCDF-4 data model.
+pub(crate) fn del_global_attribute(file: &netcdf::File, name: &str) -> Result<()> {
+    let c_name = CString::new(name)
+        .map_err(|e| ExodusError::Other(format!("Invalid attribute name: {}", e)))?;
+    let ncid = ncid(file);
+    let _guard = netcdf_sys::libnetcdf_lock.lock();
+    // SAFETY: c_name is a valid NUL-terminated string and ncid identifies an open file.
+    let status = unsafe { netcdf_sys::nc_del_att(ncid, netcdf_sys::NC_GLOBAL, c_name.as_ptr()) };
+    if status == netcdf_sys::NC_ENOTATT {
+        return Ok(());
+    }
+    check(status, || format!("Failed to delete attribute '{}'", name))
+}
+
+/// Size the HDF5 chunk cache of one variable
+///
+/// NetCDF-C has no per-file cache setting, so the cache is set on each
+/// variable and lasts while the file is open. The caller holds the library
+/// lock.
+fn set_var_cache(ncid: c_int, varid: c_int, cache: &CacheConfig) -> Result<()> {
+    let num_slots = if cache.num_slots > 0 {
+        cache.num_slots
+    } else {
+        // Typical chunk of 1 MB
+        cache.auto_slots(1024 * 1024)
+    };
+    // SAFETY: ncid and varid identify a variable of an open file.
+    let status = unsafe {
+        netcdf_sys::nc_set_var_chunk_cache(
+            ncid,
+            varid,
+            cache.cache_size,
+            num_slots,
+            cache.preemption as f32,
+        )
+    };
+    check(status, || {
+        format!("Failed to set the chunk cache of variable {}", varid)
+    })
+}
+
+/// Size the HDF5 chunk cache of a named variable
+pub(crate) fn set_var_chunk_cache(ncid: c_int, var_name: &str, cache: &CacheConfig) -> Result<()> {
+    let c_name = CString::new(var_name)
+        .map_err(|e| ExodusError::Other(format!("Invalid variable name: {}", e)))?;
+    let _guard = netcdf_sys::libnetcdf_lock.lock();
+    let mut varid: c_int = 0;
+    // SAFETY: c_name is a valid NUL-terminated string and varid is a valid out pointer.
+    let status = unsafe { netcdf_sys::nc_inq_varid(ncid, c_name.as_ptr(), &mut varid) };
+    check(status, || format!("Variable '{}' not found", var_name))?;
+    set_var_cache(ncid, varid, cache)
+}
+
+/// Size the HDF5 chunk cache of every variable from the `first`-th on
+///
+/// Returns the number of variables in the file, which callers pass back as
+/// `first` to cover only variables defined since.
+pub(crate) fn set_chunk_caches(ncid: c_int, first: usize, cache: &CacheConfig) -> Result<usize> {
+    let _guard = netcdf_sys::libnetcdf_lock.lock();
+    let mut nvars: c_int = 0;
+    // SAFETY: passing a null varids pointer only queries the count.
+    let status = unsafe { netcdf_sys::nc_inq_varids(ncid, &mut nvars, std::ptr::null_mut()) };
+    check(status, || "Failed to list variables".to_string())?;
+    let mut varids = vec![0 as c_int; nvars.max(0) as usize];
+    if !varids.is_empty() {
+        // SAFETY: varids has room for nvars entries.
+        let status = unsafe { netcdf_sys::nc_inq_varids(ncid, &mut nvars, varids.as_mut_ptr()) };
+        check(status, || "Failed to list variables".to_string())?;
+    }
+    for &varid in varids.iter().skip(first) {
+        set_var_cache(ncid, varid, cache)?;
+    }
+    Ok(varids.len())
+}
+
+/// Apply a szip or zstandard filter to a newly defined variable
+///
+/// Gzip is set through `netcdf::VariableMut::set_compression`; the other
+/// filters are not exposed by the `netcdf` crate. The variable must be in
+/// define mode with no data written yet.
+pub(crate) fn def_var_filter(
+    ncid: c_int,
+    var_name: &str,
+    compression: Compression,
+    shuffle: bool,
+) -> Result<()> {
+    let c_name = CString::new(var_name)
+        .map_err(|e| ExodusError::Other(format!("Invalid variable name: {}", e)))?;
+
+    let _guard = netcdf_sys::libnetcdf_lock.lock();
+    let mut varid: c_int = 0;
+    // SAFETY: c_name is a valid NUL-terminated string and varid is a valid out pointer.
+    let status = unsafe { netcdf_sys::nc_inq_varid(ncid, c_name.as_ptr(), &mut varid) };
+    check(status, || format!("Variable '{}' not found", var_name))?;
+
+    if shuffle {
+        // SAFETY: ncid and varid identify a variable of an open file.
+        let status = unsafe { netcdf_sys::nc_def_var_deflate(ncid, varid, 1, 0, 0) };
+        check(status, || {
+            format!("Failed to set shuffle filter on '{}'", var_name)
+        })?;
+    }
+
+    // SAFETY: ncid and varid identify a variable of an open file.
+    let status = match compression {
+        Compression::Szip => unsafe {
+            netcdf_sys::nc_def_var_szip(ncid, varid, NC_SZIP_NN, SZIP_PIXELS_PER_BLOCK)
+        },
+        Compression::Zstd(level) => unsafe {
+            netcdf_sys::nc_def_var_zstandard(ncid, varid, c_int::from(level))
+        },
+        Compression::None | Compression::Gzip(_) => return Ok(()),
+    };
+    match status {
+        netcdf_sys::NC_ENOFILTER | netcdf_sys::NC_EFILTER => {
+            Err(ExodusError::UnsupportedFeature(format!(
+                "{:?} compression is not available in the installed netCDF/HDF5 libraries",
+                compression
+            )))
+        }
+        _ => check(status, || {
+            format!(
+                "Failed to set {:?} compression on '{}'",
+                compression, var_name
+            )
+        }),
+    }
+}
+
+/// Dataset name given to in-memory files; never used as a path
+const MEMORY_PATH: &[u8] = b"<in-memory>\0";
+
+/// `NC_memio` from `netcdf_mem.h`
+#[repr(C)]
+struct NcMemio {
+    size: usize,
+    memory: *mut c_void,
+    flags: c_int,
+}
+
+extern "C" {
+    fn nc_open_mem(
+        path: *const c_char,
+        mode: c_int,
+        size: usize,
+        memory: *mut c_void,
+        ncidp: *mut c_int,
+    ) -> c_int;
+    fn nc_open_memio(
+        path: *const c_char,
+        mode: c_int,
+        params: *mut NcMemio,
+        ncidp: *mut c_int,
+    ) -> c_int;
+    fn nc_create_mem(
+        path: *const c_char,
+        mode: c_int,
+        initialsize: usize,
+        ncidp: *mut c_int,
+    ) -> c_int;
+    fn nc_close_memio(ncid: c_int, memio: *mut NcMemio) -> c_int;
+}
+
+/// Open the file held in `bytes` for reading
+///
+/// NetCDF reads the buffer in place, without copying it.
+///
+/// # Safety
+///
+/// `bytes` must stay at the same address, unmodified, until the returned
+/// handle is dropped.
+pub(crate) unsafe fn open_mem(bytes: &[u8]) -> Result<netcdf::File> {
+    let mut ncid: c_int = 0;
+    let status = {
+        let _guard = netcdf_sys::libnetcdf_lock.lock();
+        // SAFETY: the path is NUL-terminated, the library only reads the
+        // buffer (the caller keeps it alive) and ncid is a valid out pointer.
+        unsafe {
+            nc_open_mem(
+                MEMORY_PATH.as_ptr().cast(),
+                netcdf_sys::NC_NOWRITE,
+                bytes.len(),
+                bytes.as_ptr() as *mut c_void,
+                &mut ncid,
+            )
+        }
+    };
+    check(status, || "Failed to open the in-memory file".to_string())?;
+    // SAFETY: the file was just opened and is owned by nobody else.
+    Ok(unsafe { file_from_ncid(ncid) })
+}
+
+/// Open a writable in-memory file starting from a copy of `bytes`
+///
+/// The copy is handed over to the library, which grows it as the file does;
+/// collect the final contents with [`close_memio`].
+pub(crate) fn open_memio(bytes: &[u8]) -> Result<netcdf::FileMut> {
+    // The library reallocates and frees the buffer, so it must come from malloc
+    // SAFETY: malloc has no preconditions; the result is checked for null.
+    let memory = unsafe { libc::malloc(bytes.len().max(1)) };
+    if memory.is_null() {
+        return Err(ExodusError::Other(format!(
+            "Failed to allocate {} bytes for the in-memory file",
+            bytes.len()
+        )));
+    }
+    // SAFETY: memory is a fresh allocation of at least bytes.len() bytes.
+    unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), memory.cast::<u8>(), bytes.len()) };
+
+    let mut params = NcMemio {
+        size: bytes.len(),
+        memory,
+        flags: 0,
+    };
+    let mut ncid: c_int = 0;
+    let status = {
+        let _guard = netcdf_sys::libnetcdf_lock.lock();
+        // SAFETY: the path is NUL-terminated, params describes a malloc'd
+        // buffer and ncid is a valid out pointer.
+        unsafe {
+            nc_open_memio(
+                MEMORY_PATH.as_ptr().cast(),
+                netcdf_sys::NC_WRITE,
+                &mut params,
+                &mut ncid,
+            )
+        }
+    };
+    // The library clears `memory` once it takes the buffer over
+    if !params.memory.is_null() {
+        // SAFETY: the buffer was not taken over, so it is still ours to free.
+        unsafe { libc::free(params.memory) };
+    }
+    check(status, || "Failed to open the in-memory file".to_string())?;
+    // SAFETY: the file was just opened for writing and is owned by nobody else.
+    Ok(unsafe { file_mut_from_ncid(ncid) })
+}
+
+/// Create an empty in-memory file with the given `nc_create` mode flags
+pub(crate) fn create_mem(mode: c_int) -> Result<netcdf::FileMut> {
+    let mut ncid: c_int = 0;
+    let status = {
+        let _guard = netcdf_sys::libnetcdf_lock.lock();
+        // SAFETY: the path is NUL-terminated and ncid is a valid out pointer;
+        // an initial size of 0 selects the library default.
+        unsafe { nc_create_mem(MEMORY_PATH.as_ptr().cast(), mode, 0, &mut ncid) }
+    };
+    check(status, || "Failed to create the in-memory file".to_string())?;
+    // SAFETY: the file was just created and is owned by nobody else.
+    Ok(unsafe { file_mut_from_ncid(ncid) })
+}
+
+/// Close a file opened by [`open_memio`] or [`create_mem`] and return its
+/// contents
+///
+/// If closing fails the file is leaked rather than risking a double close.
+///
+/// # Safety
+///
+/// `ncid` must come from a handle that was forgotten instead of dropped, so
+/// nothing else closes it.
+pub(crate) unsafe fn close_memio(ncid: c_int) -> Result<Vec<u8>> {
+    let mut memio = NcMemio {
+        size: 0,
+        memory: std::ptr::null_mut(),
+        flags: 0,
+    };
+    let status = {
+        let _guard = netcdf_sys::libnetcdf_lock.lock();
+        // SAFETY: ncid identifies an open in-memory file and memio is a valid
+        // out pointer.
+        unsafe { nc_close_memio(ncid, &mut memio) }
+    };
+    check(status, || "Failed to close the in-memory file".to_string())?;
+    if memio.memory.is_null() {
+        return Ok(Vec::new());
+    }
+    // SAFETY: the library hands back a malloc'd buffer of memio.size bytes,
+    // which is copied out and then freed.
+    let bytes = unsafe {
+        let bytes = std::slice::from_raw_parts(memio.memory.cast::<u8>(), memio.size).to_vec();
+        libc::free(memio.memory);
+        bytes
+    };
+    Ok(bytes)
+}
@@ -6,9 +6,29 @@
 
 use crate::error::{ExodusError, Result};
 
-#[cfg(feature = "netcdf4")]
+use netcdf::types::{NcTypeDescriptor, NcVariableType};
 use netcdf::{AttributeValue, Variable};
 
+/// Custom type for writing NC_CHAR data
+///
+/// netcdf-rs doesn't have a built-in type for NC_CHAR, so we define our own
+/// wrapper around i8 that implements NcTypeDescriptor to return NcVariableType::Char.
+/// This allows us to write character arrays in the format that VisIt expects.
+#[repr(transparent)]
+#[derive(Copy, Clone)]
+pub struct NcChar(pub i8);
+
+unsafe impl NcTypeDescriptor for NcChar {
+    fn type_descriptor() -> NcVariableType {
+        NcVariableType::Char
+    }
+}
+
+/// Convert a byte buffer into NC_CHAR values for writing
+pub fn to_nc_chars(bytes: &[u8]) -> Vec<NcChar> {
+    bytes.iter().map(|&b| NcChar(b as i8)).collect()
+}
+
 /// Extract an i64 value from a NetCDF AttributeValue
 ///
 /// Handles conversion from various numeric AttributeValue types to i64.
@@ -34,7 +54,6 @@ use netcdf::{AttributeValue, Variable};
 /// }
 /// ```
 #[allow(dead_code)]
-#[cfg(feature = "netcdf4")]
 pub fn attr_value_to_i64(value: &AttributeValue) -> Option<i64> {
     match value {
         AttributeValue::Short(v) => Some(*v as i64),
@@ -74,7 +93,6 @@ pub fn attr_value_to_i64(value: &AttributeValue) -> Option<i64> {
 /// }
 /// ```
 #[allow(dead_code)]
-#[cfg(feature = "netcdf4")]
 pub fn attr_value_to_string(value: &AttributeValue) -> Option<String> {
     match value {
         AttributeValue::Str(s) => Some(s.clone()),
@@ -107,7 +125,6 @@ pub fn attr_value_to_string(value: &AttributeValue) -> Option<String> {
 /// }
 /// ```
 #[allow(dead_code)]
-#[cfg(feature = "netcdf4")]
 pub fn attr_value_to_f64(value: &AttributeValue) -> Option<f64> {
     match value {
         AttributeValue::Short(v) => Some(*v as f64),
@@ -147,7 +164,6 @@ pub fn attr_value_to_f64(value: &AttributeValue) -> Option<f64> {
 /// println!("ID: {}", id);
 /// ```
 #[allow(dead_code)]
-#[cfg(feature = "netcdf4")]
 pub fn get_attr_i64(var: &Variable<'_>, attr_name: &str) -> Result<i64> {
     let attr = var
         .attribute(attr_name)
@@ -184,7 +200,6 @@ pub fn get_attr_i64(var: &Variable<'_>, attr_name: &str) -> Result<i64> {
 /// println!("Name: {}", name);
 /// ```
 #[allow(dead_code)]
-#[cfg(feature = "netcdf4")]
 pub fn get_attr_string(var: &Variable<'_>, attr_name: &str) -> Result<String> {
     let attr = var
         .attribute(attr_name)
@@ -224,7 +239,6 @@ pub fn get_attr_string(var: &Variable<'_>, attr_name: &str) -> Result<String> {
 /// println!("Scale: {}", scale);
 /// ```
 #[allow(dead_code)]
-#[cfg(feature = "netcdf4")]
 pub fn get_attr_f64(var: &Variable<'_>, attr_name: &str) -> Result<f64> {
     let attr = var
         .attribute(attr_name)
@@ -263,7 +277,6 @@ pub fn get_attr_f64(var: &Variable<'_>, attr_name: &str) -> Result<f64> {
 /// }
 /// ```
 #[allow(dead_code)]
-#[cfg(feature = "netcdf4")]
 pub fn try_get_attr_i64(var: &Variable<'_>, attr_name: &str) -> Option<i64> {
     let attr = var.attribute(attr_name)?;
     let value = attr.value().ok()?;
@@ -293,7 +306,6 @@ pub fn try_get_attr_i64(var: &Variable<'_>, attr_name: &str) -> Option<i64> {
 /// println!("Name: {}", name);
 /// ```
 #[allow(dead_code)]
-#[cfg(feature = "netcdf4")]
 pub fn try_get_attr_string(var: &Variable<'_>, attr_name: &str) -> Option<String> {
     let attr = var.attribute(attr_name)?;
     let value = attr.value().ok()?;
@@ -326,14 +338,13 @@ pub fn try_get_attr_string(var: &Variable<'_>, attr_name: &str) -> Option<String
 /// }
 /// ```
 #[allow(dead_code)]
-#[cfg(feature = "netcdf4")]
 pub fn try_get_attr_f64(var: &Variable<'_>, attr_name: &str) -> Option<f64> {
     let attr = var.attribute(attr_name)?;
     let value = attr.value().ok()?;
     attr_value_to_f64(&value)
 }
 
-#[cfg(all(test, feature = "netcdf4"))]
+#[cfg(test)]
 mod tests {
     use super::*;
     use netcdf::AttributeValue;
@@ -3,26 +3,18 @@
 //! This module contains variable definition and I/O operations for time-dependent data.
 //! Implemented in Phase 6.
 
+use crate::backend::Backend;
 use crate::error::{EntityId, ExodusError, Result};
 use crate::types::{EntityType, TruthTable, VarStorageMode};
 use crate::utils::constants::*;
-use crate::{mode, ExodusFile, FileMode};
-use netcdf::types::{NcTypeDescriptor, NcVariableType};
-
-/// Custom type for writing NC_CHAR data
-///
-/// netcdf-rs doesn't have a built-in type for NC_CHAR, so we define our own
-/// wrapper around i8 that implements NcTypeDescriptor to return NcVariableType::Char.
-/// This allows us to write character arrays in the format that VisIt expects.
-#[repr(transparent)]
-#[derive(Copy, Clone)]
-pub struct NcChar(pub i8);
-
-unsafe impl NcTypeDescriptor for NcChar {
-    fn type_descriptor() -> NcVariableType {
-        NcVariableType::Char
-    }
-}
+use crate::utils::naming;
+#[cfg(feature = "netcdf4")]
+use crate::utils::netcdf_ext::NcChar;
+#[cfg(feature = "netcdf4")]
+use crate::WritableMode;
+use crate::{mode, ExodusFile, FileMode, ReadableMode};
+#[cfg(feature = "netcdf4")]
+use netcdf::types::NcVariableType;
 
 // ====================
 // Common Operations
@@ -62,85 +54,14 @@ impl<M: FileMode> ExodusFile<M> {
             }
         };
 
-        // Try to get the variable
-        match self.nc_file.variable(var_name_var) {
-            Some(var) => {
-                // Support both classic NetCDF fixed-length char arrays and
-                // NetCDF-4 NC_STRING variables for name storage.
-                let dims = var.dimensions();
-
-                // If 1D, likely NC_STRING [num_vars]
-                if dims.len() == 1 {
-                    // NC_STRING case: read each entry using get_string
-                    let num_vars = dims[0].len();
-                    let mut names = Vec::with_capacity(num_vars);
-                    for i in 0..num_vars {
-                        let s = var.get_string(i..i + 1)?;
-                        names.push(s.trim_end_matches('\0').trim().to_string());
-                    }
-                    return Ok(names);
-                }
-
-                // Otherwise, expect 2D [num_vars, len_string] of NC_CHAR
-                // CRITICAL: var.len() returns TOTAL elements (num_vars * len_string)
-                // We need the first dimension size (num_vars) instead
-                let num_vars = if let Some(dim) = dims.first() {
-                    dim.len()
-                } else {
-                    return Ok(Vec::new());
-                };
-
-                if num_vars == 0 {
-                    return Ok(Vec::new());
-                }
-
-                // Get len_string for fixed-length names
-                let len_string = self
-                    .nc_file
-                    .dimension(DIM_LEN_STRING)
-                    .ok_or_else(|| {
-                        ExodusError::Other(
-                            "len_string dimension not found when reading variable names"
-                                .to_string(),
-                        )
-                    })?
-                    .len();
-
-                // Read raw bytes directly (more reliable for 2D char arrays)
-                let raw_bytes: Vec<u8> = var.get_raw_values(..)?;
-
-                let mut names = Vec::new();
-                for i in 0..num_vars {
-                    let start = i * len_string;
-                    let end = start + len_string;
-                    let name_bytes = &raw_bytes[start..end];
-                    // Convert to string, trimming null bytes and whitespace
-                    let name = String::from_utf8_lossy(name_bytes)
-                        .trim_end_matches('\0')
-                        .trim()
-                        .to_string();
-                    names.push(name);
-                }
-
-                Ok(names)
-            }
-            None => Ok(Vec::new()),
+        // Names are fixed-length char arrays, or NC_STRING in NetCDF-4 files
+        if self.nc_file.has_variable(var_name_var) {
+            self.nc_file.read_strings(var_name_var)
+        } else {
+            Ok(Vec::new())
         }
     }
 
-    /// Get number of time steps
-    ///
-    /// # Returns
-    ///
-    /// Number of time steps in the file
-    pub fn num_time_steps(&self) -> Result<usize> {
-        Ok(self
-            .nc_file
-            .dimension(DIM_TIME_STEP)
-            .map(|d| d.len())
-            .unwrap_or(0))
-    }
-
     // ====================
     // Reduction Variables (Read Operations)
     // ====================
@@ -179,61 +100,11 @@ impl<M: FileMode> ExodusFile<M> {
             }
         };
 
-        // Try to get the variable
-        match self.nc_file.variable(var_name_var) {
-            Some(var) => {
-                // Support NC_STRING [num_vars] and NC_CHAR [num_vars, len_string]
-                let dims = var.dimensions();
-
-                if dims.len() == 1 {
-                    let num_vars = dims[0].len();
-                    let mut names = Vec::with_capacity(num_vars);
-                    for i in 0..num_vars {
-                        let s = var.get_string(i..i + 1)?;
-                        names.push(s.trim_end_matches('\0').trim().to_string());
-                    }
-                    return Ok(names);
-                }
-
-                let num_vars = if let Some(dim) = dims.first() {
-                    dim.len()
-                } else {
-                    return Ok(Vec::new());
-                };
-
-                if num_vars == 0 {
-                    return Ok(Vec::new());
-                }
-
-                let len_string = self
-                    .nc_file
-                    .dimension(DIM_LEN_STRING)
-                    .ok_or_else(|| {
-                        ExodusError::Other(
-                            "len_string dimension not found when reading reduction variable names"
-                                .to_string(),
-                        )
-                    })?
-                    .len();
-
-                // Read raw bytes directly (more reliable for 2D char arrays)
-                let raw_bytes: Vec<u8> = var.get_raw_values(..)?;
-
-                let mut names = Vec::new();
-                for i in 0..num_vars {
-                    let start = i * len_string;
-                    let end = start + len_string;
-                    let name_bytes = &raw_bytes[start..end];
-                    let name = String::from_utf8_lossy(name_bytes)
-                        .trim_end_matches('\0')
-                        .trim()
-                        .to_string();
-                    names.push(name);
-                }
-
-                Ok(names)
-            }
-            None => Ok(Vec::new()),
+        // Names are fixed-length char arrays, or NC_STRING in NetCDF-4 files
+        if self.nc_file.has_variable(var_name_var) {
+            self.nc_file.read_strings(var_name_var)
+        } else {
+            Ok(Vec::new())
         }
     }
 
@@ -258,101 +129,41 @@ impl<M: FileMode> ExodusFile<M> {
         var_type: EntityType,
         entity_id: EntityId,
     ) -> Result<Vec<f64>> {
+        self.metadata.check_time_step(step)?;
         let var_name = match var_type {
             EntityType::Global => VAR_VALS_GLO_VAR.to_string(),
             EntityType::Assembly => format!("vals_assembly_red{}", entity_id),
             EntityType::Blob => format!("vals_blob_red{}", entity_id),
             EntityType::ElemBlock => {
-                let block_ids = self.block_ids(var_type)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: var_type.to_string(),
-                        id: entity_id,
-                    })?;
+                let block_index = self.entity_index(var_type, entity_id)?;
                 format!("vals_elem_red_eb{}", block_index + 1)
             }
             EntityType::EdgeBlock => {
-                let block_ids = self.block_ids(var_type)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: var_type.to_string(),
-                        id: entity_id,
-                    })?;
+                let block_index = self.entity_index(var_type, entity_id)?;
                 format!("vals_edge_red_edgb{}", block_index + 1)
             }
             EntityType::FaceBlock => {
-                let block_ids = self.block_ids(var_type)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: var_type.to_string(),
-                        id: entity_id,
-                    })?;
+                let block_index = self.entity_index(var_type, entity_id)?;
                 format!("vals_face_red_facb{}", block_index + 1)
             }
             EntityType::NodeSet => {
-                let set_ids = self.set_ids(EntityType::NodeSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::NodeSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::NodeSet, entity_id)?;
                 format!("vals_nset_red_ns{}", set_index + 1)
             }
             EntityType::EdgeSet => {
-                let set_ids = self.set_ids(EntityType::EdgeSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::EdgeSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::EdgeSet, entity_id)?;
                 format!("vals_eset_red_es{}", set_index + 1)
             }
             EntityType::FaceSet => {
-                let set_ids = self.set_ids(EntityType::FaceSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::FaceSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::FaceSet, entity_id)?;
                 format!("vals_fset_red_fs{}", set_index + 1)
             }
             EntityType::SideSet => {
-                let set_ids = self.set_ids(EntityType::SideSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::SideSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::SideSet, entity_id)?;
                 format!("vals_sset_red_ss{}", set_index + 1)
             }
             EntityType::ElemSet => {
-                let set_ids = self.set_ids(EntityType::ElemSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::ElemSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::ElemSet, entity_id)?;
                 format!("vals_elset_red_els{}", set_index + 1)
             }
             _ => {
@@ -363,27 +174,200 @@ impl<M: FileMode> ExodusFile<M> {
             }
         };
 
-        if let Some(var) = self.nc_file.variable(&var_name) {
-            // Get the number of reduction variables
-            let num_vars = if let Some(dim) = var.dimensions().get(1) {
-                dim.len()
-            } else {
-                return Ok(Vec::new());
-            };
+        match self.nc_file.variable_shape(&var_name) {
+            // Shape is (time_step, num_vars)
+            Some(shape) if shape.len() == 2 => {
+                self.nc_file.read_f64(&var_name, &[step, 0], &[1, shape[1]])
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the truth table for a block or set variable type
+    ///
+    /// The truth table records which variables are defined on which blocks
+    /// (or sets) of the type. Files without a stored truth table define every
+    /// variable on every entity, so an all-`true` table is returned for them.
+    ///
+    /// # Arguments
+    ///
+    /// * `var_type` - Block or set type (ElemBlock, EdgeBlock, FaceBlock,
+    ///   NodeSet, EdgeSet, FaceSet, SideSet or ElemSet)
+    ///
+    /// # Returns
+    ///
+    /// Truth table
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - var_type is not a block or set type
+    /// - truth table size doesn't match expected dimensions
+    /// - NetCDF read fails
+    pub fn truth_table(&self, var_type: EntityType) -> Result<TruthTable> {
+        let var_name = truth_table_var(var_type)?;
+
+        // Get number of blocks/sets and variables
+        let num_blocks = self
+            .nc_file
+            .dimension_len(naming::num_dimension(var_type))
+            .unwrap_or(0);
+        let num_vars = self.variable_names(var_type)?.len();
+        let expected_len = num_blocks * num_vars;
 
-            let values: Vec<f64> = var.get_values((step..step + 1, 0..num_vars))?;
-            Ok(values)
+        // Read truth table if it exists
+        let table_values = if self.nc_file.has_variable(var_name) {
+            let table = self.nc_file.read_all_i64(var_name)?;
+
+            // Validate size
+            if table.len() != expected_len {
+                return Err(ExodusError::InvalidArrayLength {
+                    expected: expected_len,
+                    actual: table.len(),
+                });
+            }
+
+            table.iter().map(|&v| v != 0).collect()
         } else {
-            Ok(Vec::new())
+            // Default: all true
+            vec![true; expected_len]
+        };
+
+        Ok(TruthTable {
+            var_type,
+            num_vars,
+            num_blocks,
+            table: table_values,
+        })
+    }
+
+    /// Check if a variable is enabled in the truth table for a given block or set
+    ///
+    /// This is a helper method to check whether a specific variable is defined
+    /// for a specific block or set according to the truth table. This is useful
+    /// for sparse variable storage where not all variables are defined on all
+    /// entities.
+    ///
+    /// # Arguments
+    ///
+    /// * `var_type` - Entity type (must be a block or set type)
+    /// * `entity_id` - Block or set ID
+    /// * `var_index` - Variable index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// `true` if the variable is enabled for the entity, `false` otherwise.
+    /// If no truth table exists, returns `true` (all variables enabled by default).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - var_type is not a block or set type
+    /// - entity_id is not found
+    /// - var_index is out of range
+    /// - truth table read fails
+    pub fn is_var_in_truth_table(
+        &self,
+        var_type: EntityType,
+        entity_id: EntityId,
+        var_index: usize,
+    ) -> Result<bool> {
+        truth_table_var(var_type)?;
+
+        // Get the block/set index from its ID
+        let entity_index = self.entity_index(var_type, entity_id)?;
+
+        // Get the truth table
+        let truth_table = self.truth_table(var_type)?;
+
+        // Validate var_index
+        if var_index >= truth_table.num_vars {
+            return Err(ExodusError::Other(format!(
+                "Variable index {} out of range ({} variables)",
+                var_index, truth_table.num_vars
+            )));
         }
+
+        // Truth table is stored as [block0_var0, block0_var1, ..., block1_var0, block1_var1, ...]
+        let table_index = entity_index * truth_table.num_vars + var_index;
+        Ok(truth_table.table[table_index])
+    }
+
+    // Helper function to get the variable name in the separate storage format
+    fn var_storage_name(
+        &self,
+        var_type: EntityType,
+        entity_id: EntityId,
+        var_index: usize,
+    ) -> Result<String> {
+        Ok(match var_type {
+            EntityType::Global => VAR_VALS_GLO_VAR.to_string(),
+            EntityType::Nodal => naming::nodal_var(var_index),
+            t if is_block_or_set(t) => {
+                let index = self.entity_index(t, entity_id)?;
+                naming::entity_var(t, var_index, index)
+            }
+            _ => {
+                return Err(ExodusError::InvalidEntityType(format!(
+                    "Unsupported variable type: {}",
+                    var_type
+                )))
+            }
+        })
     }
+
+    // Helper function to get the number of entries in a block or set
+    #[cfg(feature = "netcdf4")]
+    fn num_entity_entries(&self, var_type: EntityType, entity_id: EntityId) -> Result<usize> {
+        let index = self.entity_index(var_type, entity_id)?;
+        let dim_name = naming::entity_entries_dim(var_type, index);
+        self.nc_file
+            .dimension_len(&dim_name)
+            .ok_or_else(|| ExodusError::Other(format!("Dimension {} not found", dim_name)))
+    }
+}
+
+/// Whether variables of this type are stored per block or set
+fn is_block_or_set(var_type: EntityType) -> bool {
+    matches!(
+        var_type,
+        EntityType::ElemBlock
+            | EntityType::EdgeBlock
+            | EntityType::FaceBlock
+            | EntityType::NodeSet
+            | EntityType::EdgeSet
+            | EntityType::FaceSet
+            | EntityType::SideSet
+            | EntityType::ElemSet
+    )
+}
+
+/// Get the truth table variable name, rejecting types without truth tables
+fn truth_table_var(var_type: EntityType) -> Result<&'static str> {
+    Ok(match var_type {
+        EntityType::ElemBlock => VAR_ELEM_VAR_TAB,
+        EntityType::EdgeBlock => VAR_EDGE_VAR_TAB,
+        EntityType::FaceBlock => VAR_FACE_VAR_TAB,
+        EntityType::NodeSet => VAR_NSET_VAR_TAB,
+        EntityType::EdgeSet => VAR_ESET_VAR_TAB,
+        EntityType::FaceSet => VAR_FSET_VAR_TAB,
+        EntityType::SideSet => VAR_SSET_VAR_TAB,
+        EntityType::ElemSet => VAR_ELSET_VAR_TAB,
+        _ => {
+            return Err(ExodusError::InvalidEntityType(format!(
+                "Truth tables only supported for block and set types, got {}",
+                var_type
+            )))
+        }
+    })
 }
 
 // ====================
 // Write Operations
 // ====================
 
-impl ExodusFile<mode::Write> {
+#[cfg(feature = "netcdf4")]
+impl<M: WritableMode> ExodusFile<M> {
     /// Define variables for an entity type
     ///
     /// # Arguments
@@ -490,21 +474,26 @@ impl ExodusFile<mode::Write> {
 
         // Create the actual storage variables based on type
         match var_type {
-            EntityType::Global => {
+            EntityType::Global if self.nc_file.variable(VAR_VALS_GLO_VAR).is_none() => {
                 // Global vars: vals_glo_var(time_step, num_glo_var)
-                if self.nc_file.variable(VAR_VALS_GLO_VAR).is_none() {
-                    let mut var = self
-                        .nc_file
-                        .add_variable::<f64>(VAR_VALS_GLO_VAR, &[DIM_TIME_STEP, num_var_dim])?;
-
-                    // Apply chunking for global variables (clamp to actual size)
-                    // Chunk on time if configured (typically small variable count)
-                    if time_chunk_req > 0 && num_vars > 0 {
-                        let clamped_num_vars = num_vars.min(num_vars); // num_vars is the dim size
-                        var.set_chunking(&[time_chunk_req.max(1), clamped_num_vars])?;
-                    }
+                let mut var = self.nc_file.add_variable_with_type(
+                    VAR_VALS_GLO_VAR,
+                    &[DIM_TIME_STEP, num_var_dim],
+                    &self.metadata.float_type(),
+                )?;
+                self.metadata.apply_compression(&mut var)?;
+
+                // Apply chunking for global variables (clamp to actual size)
+                // Chunk on time if configured (typically small variable count)
+                if time_chunk_req > 0 && num_vars > 0 {
+                    let clamped_num_vars = num_vars.min(num_vars); // num_vars is the dim size
+                    var.set_chunking(&[time_chunk_req.max(1), clamped_num_vars])?;
                 }
             }
+            EntityType::Nodal if self.nodal_vars_combined() => {
+                // Nodal vars: vals_nod_var(time_step, num_nod_var, num_nodes)
+                self.create_var_storage(EntityType::Nodal, 0, 0)?;
+            }
             EntityType::Nodal => {
                 // Nodal vars: vals_nod_var{i}(time_step, num_nodes)
                 // Clamp node_chunk to actual num_nodes
@@ -517,9 +506,12 @@ impl ExodusFile<mode::Write> {
                 for i in 0..num_vars {
                     let var_name = format!("vals_nod_var{}", i + 1);
                     if self.nc_file.variable(&var_name).is_none() {
-                        let mut var = self
-                            .nc_file
-                            .add_variable::<f64>(&var_name, &[DIM_TIME_STEP, DIM_NUM_NODES])?;
+                        let mut var = self.nc_file.add_variable_with_type(
+                            &var_name,
+                            &[DIM_TIME_STEP, DIM_NUM_NODES],
+                            &self.metadata.float_type(),
+                        )?;
+                        self.metadata.apply_compression(&mut var)?;
 
                         // Apply chunking for nodal variables
                         // Chunk on nodes (spatial dimension), optionally on time
@@ -534,16 +526,15 @@ impl ExodusFile<mode::Write> {
                     }
                 }
             }
-            EntityType::ElemBlock => {
-                // Element vars: vals_elem_var{var_idx}eb{block_idx}(time_step, num_elem_in_blk{block_idx})
-                // We'll create these later when we know which blocks need which variables
-                // For now, this is handled in put_var
-            }
             _ => {
-                // Other types not yet fully supported
+                // Block and set vars: vals_elem_var{var_idx}eb{block_idx}(time_step, num_el_in_blk{block_idx}), etc.
+                // Storage is created per block/set by put_var, since the truth table
+                // decides which entities carry which variables
             }
         }
 
+        self.finish_definitions()?;
+
         // Write the variable names using NC_CHAR (2D character array)
         // CRITICAL: Must write the variable names or reading will fail!
         // Each name is written as a row in the 2D array, padded with null bytes
@@ -564,44 +555,7 @@ impl ExodusFile<mode::Write> {
 
         // Force sync to ensure all data is written and file is in consistent state
         // This is critical for proper NetCDF define/data mode transitions
-        self.nc_file.sync()?;
-
-        Ok(())
-    }
-
-    /// Write time value for a time step
-    ///
-    /// # Arguments
-    ///
-    /// * `step` - Time step index (0-based)
-    /// * `time` - Time value
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if NetCDF write fails
-    pub fn put_time(&mut self, step: usize, time: f64) -> Result<()> {
-        // Ensure time_step dimension exists (may be created earlier by define_variables)
-        if self.nc_file.dimension(DIM_TIME_STEP).is_none() {
-            self.ensure_define_mode()?;
-            self.nc_file.add_unlimited_dimension(DIM_TIME_STEP)?;
-        }
-
-        // Ensure time_whole variable exists
-        if self.nc_file.variable(VAR_TIME_WHOLE).is_none() {
-            self.ensure_define_mode()?;
-            let mut var = self
-                .nc_file
-                .add_variable::<f64>(VAR_TIME_WHOLE, &[DIM_TIME_STEP])?;
-            var.put_attribute("name", VAR_TIME_WHOLE)?;
-        }
-
-        // Ensure we're in data mode for writing time values
-        self.ensure_data_mode()?;
-
-        // Write the time value
-        if let Some(mut var) = self.nc_file.variable_mut(VAR_TIME_WHOLE) {
-            var.put_value(time, step..step + 1)?;
-        }
+        self.sync()?;
 
         Ok(())
     }
@@ -611,18 +565,26 @@ impl ExodusFile<mode::Write> {
     /// For Global variables, entity_id is ignored and should be 0.
     /// For Nodal variables, entity_id is ignored and should be 0.
     /// For block variables (element, edge, face), entity_id is the block ID.
+    /// For set variables (node, edge, face, side, element), entity_id is the set ID.
+    ///
+    /// Storage for block and set variables is created on first write. If a
+    /// truth table has been written for the type, only variables it enables
+    /// on the block or set can be written.
     ///
     /// # Arguments
     ///
     /// * `step` - Time step index (0-based)
     /// * `var_type` - Entity type
-    /// * `entity_id` - Entity ID (block ID for block variables, 0 for global/nodal)
+    /// * `entity_id` - Entity ID (block/set ID, 0 for global/nodal)
     /// * `var_index` - Variable index (0-based)
     /// * `values` - Variable values
     ///
     /// # Errors
     ///
-    /// Returns an error if NetCDF write fails
+    /// Returns an error if:
+    /// - The block or set is not found
+    /// - The truth table disables the variable on the block or set
+    /// - NetCDF write fails
     pub fn put_var(
         &mut self,
         step: usize,
@@ -631,10 +593,11 @@ impl ExodusFile<mode::Write> {
         var_index: usize,
         values: &[f64],
     ) -> Result<()> {
-        let var_name = self.get_var_name(var_type, entity_id, var_index)?;
+        let var_name = self.write_var_name(var_type, entity_id, var_index)?;
 
         // Get or create the variable
         if self.nc_file.variable(&var_name).is_none() {
+            self.check_truth_table(var_type, entity_id, var_index)?;
             // Need to be in define mode to create the variable
             self.ensure_define_mode()?;
             self.create_var_storage(var_type, entity_id, var_index)?;
@@ -644,6 +607,7 @@ impl ExodusFile<mode::Write> {
         self.ensure_data_mode()?;
 
         // Write the values
+        let combined = self.nodal_vars_combined();
         if let Some(mut var) = self.nc_file.variable_mut(&var_name) {
             match var_type {
                 EntityType::Global => {
@@ -657,6 +621,10 @@ impl ExodusFile<mode::Write> {
                     }
                     var.put_value(values[0], (step..step + 1, var_index..var_index + 1))?;
                 }
+                EntityType::Nodal if combined => {
+                    // Nodal vars: (time_step, num_nod_var, num_nodes)
+                    var.put_values(values, (step..step + 1, var_index..var_index + 1, ..))?;
+                }
                 EntityType::Nodal => {
                     // Nodal vars: (time_step, num_nodes)
                     var.put_values(values, (step..step + 1, ..))?;
@@ -685,35 +653,27 @@ impl ExodusFile<mode::Write> {
         Ok(())
     }
 
-    /// Set truth table (which blocks have which variables)
+    /// Set truth table (which blocks or sets have which variables)
+    ///
+    /// Write the truth table after defining the variables and before writing
+    /// any values, so that storage is only created for enabled variables.
     ///
     /// # Arguments
     ///
-    /// * `var_type` - Entity type (ElemBlock, EdgeBlock, or FaceBlock)
+    /// * `var_type` - Block or set type (ElemBlock, EdgeBlock, FaceBlock,
+    ///   NodeSet, EdgeSet, FaceSet, SideSet or ElemSet)
     /// * `table` - Truth table
     ///
     /// # Errors
     ///
     /// Returns an error if:
+    /// - var_type is not a block or set type
     /// - var_type doesn't match table.var_type
-    /// - table dimensions don't match actual blocks/variables
+    /// - table dimensions don't match actual blocks/sets and variables
     /// - table array length is incorrect
     /// - NetCDF write fails
     pub fn put_truth_table(&mut self, var_type: EntityType, table: &TruthTable) -> Result<()> {
-        let var_name = match var_type {
-            EntityType::ElemBlock => VAR_ELEM_VAR_TAB,
-            EntityType::EdgeBlock => VAR_EDGE_VAR_TAB,
-            EntityType::FaceBlock => VAR_FACE_VAR_TAB,
-            _ => {
-                return Err(ExodusError::InvalidEntityType(format!(
-                    "Truth tables only supported for block types, got {}",
-                    var_type
-                )))
-            }
-        };
-
-        // Ensure we're in define mode for adding the truth table variable
-        self.ensure_define_mode()?;
+        let var_name = truth_table_var(var_type)?;
 
         // Validate that table var_type matches parameter
         if table.var_type != var_type {
@@ -724,14 +684,20 @@ impl ExodusFile<mode::Write> {
         }
 
         // Get actual counts from file
-        let actual_num_blocks = self.block_ids(var_type)?.len();
+        let num_blocks_dim = naming::num_dimension(var_type);
+        let num_vars_dim = naming::num_variables_dim(var_type);
+        let actual_num_blocks = self
+            .nc_file
+            .dimension(num_blocks_dim)
+            .map(|d| d.len())
+            .unwrap_or(0);
         let actual_num_vars = self.variable_names(var_type)?.len();
 
         // Validate dimensions
         if table.num_blocks != actual_num_blocks {
             return Err(ExodusError::Other(format!(
-                "Truth table num_blocks {} doesn't match actual blocks {}",
-                table.num_blocks, actual_num_blocks
+                "Truth table num_blocks {} doesn't match actual {} count {}",
+                table.num_blocks, var_type, actual_num_blocks
             )));
         }
 
@@ -751,15 +717,10 @@ impl ExodusFile<mode::Write> {
             });
         }
 
-        let (num_blocks_dim, num_vars_dim) = match var_type {
-            EntityType::ElemBlock => ("num_el_blk", DIM_NUM_ELEM_VAR),
-            EntityType::EdgeBlock => ("num_ed_blk", DIM_NUM_EDGE_VAR),
-            EntityType::FaceBlock => ("num_fa_blk", DIM_NUM_FACE_VAR),
-            _ => unreachable!(),
-        };
-
         // Create the truth table variable if it doesn't exist
         if self.nc_file.variable(var_name).is_none() {
+            // Ensure we're in define mode for adding the truth table variable
+            self.ensure_define_mode()?;
             self.nc_file
                 .add_variable::<i32>(var_name, &[num_blocks_dim, num_vars_dim])?;
         }
@@ -775,11 +736,54 @@ impl ExodusFile<mode::Write> {
         }
 
         // Sync to ensure data is written
-        self.nc_file.sync()?;
+        self.sync()?;
 
         Ok(())
     }
 
+    // Helper function to reject writes to variables disabled by a stored truth table
+    fn check_truth_table(
+        &mut self,
+        var_type: EntityType,
+        entity_id: EntityId,
+        var_index: usize,
+    ) -> Result<()> {
+        // Without a stored truth table every variable is enabled
+        if !is_block_or_set(var_type) || self.nc_file.variable(truth_table_var(var_type)?).is_none()
+        {
+            return Ok(());
+        }
+        // Classic-model files can only read the table in data mode
+        self.ensure_data_mode()?;
+        if self.is_var_in_truth_table(var_type, entity_id, var_index)? {
+            Ok(())
+        } else {
+            Err(ExodusError::Other(format!(
+                "Variable {} is not defined on {} {} by the truth table",
+                var_index, var_type, entity_id
+            )))
+        }
+    }
+
+    // Helper function to check whether nodal variables use the combined 3D layout
+    fn nodal_vars_combined(&self) -> bool {
+        self.metadata.storage_format.nodal == VarStorageMode::Combined
+    }
+
+    // Helper function to get the NetCDF variable written for a variable
+    fn write_var_name(
+        &self,
+        var_type: EntityType,
+        entity_id: EntityId,
+        var_index: usize,
+    ) -> Result<String> {
+        if var_type == EntityType::Nodal && self.nodal_vars_combined() {
+            Ok(VAR_VALS_NOD_VAR.to_string())
+        } else {
+            self.var_storage_name(var_type, entity_id, var_index)
+        }
+    }
+
     // Helper function to create variable storage
     fn create_var_storage(
         &mut self,
@@ -787,7 +791,7 @@ impl ExodusFile<mode::Write> {
         entity_id: EntityId,
         var_index: usize,
     ) -> Result<()> {
-        let var_name = self.get_var_name(var_type, entity_id, var_index)?;
+        let var_name = self.write_var_name(var_type, entity_id, var_index)?;
 
         // Get chunking configuration (raw requested values)
         let time_chunk_req = self
@@ -837,9 +841,13 @@ impl ExodusFile<mode::Write> {
                         .map(|d| d.len())
                         .unwrap_or(1);
 
-                    let mut var = self
-                        .nc_file
-                        .add_variable::<f64>(VAR_VALS_GLO_VAR, &[DIM_TIME_STEP, DIM_NUM_GLO_VAR])?;
+                    let mut var = self.nc_file.add_variable_with_type(
+                        VAR_VALS_GLO_VAR,
+                        &[DIM_TIME_STEP, DIM_NUM_GLO_VAR],
+                        &self.metadata.float_type(),
+                    )?;
+
+                    self.metadata.apply_compression(&mut var)?;
 
                     // Apply chunking for global variables (clamp to dimension size)
                     if time_chunk_req > 0 && num_glo_var > 0 {
@@ -847,11 +855,34 @@ impl ExodusFile<mode::Write> {
                     }
                 }
             }
+            EntityType::Nodal if self.nodal_vars_combined() => {
+                // Nodal vars: vals_nod_var(time_step, num_nod_var, num_nodes)
+                let mut var = self.nc_file.add_variable_with_type(
+                    VAR_VALS_NOD_VAR,
+                    &[DIM_TIME_STEP, DIM_NUM_NOD_VAR, DIM_NUM_NODES],
+                    &self.metadata.float_type(),
+                )?;
+                self.metadata.apply_compression(&mut var)?;
+
+                // Chunk one variable at a time, as it is read and written
+                let node_chunk = clamp_chunk(node_chunk_req, num_nodes);
+                if node_chunk > 0 {
+                    let t_chunk = if time_chunk_req > 0 {
+                        time_chunk_req
+                    } else {
+                        1
+                    };
+                    var.set_chunking(&[t_chunk, 1, node_chunk])?;
+                }
+            }
             EntityType::Nodal => {
                 // Nodal var{i}: vals_nod_var{i}(time_step, num_nodes)
-                let mut var = self
-                    .nc_file
-                    .add_variable::<f64>(&var_name, &[DIM_TIME_STEP, DIM_NUM_NODES])?;
+                let mut var = self.nc_file.add_variable_with_type(
+                    &var_name,
+                    &[DIM_TIME_STEP, DIM_NUM_NODES],
+                    &self.metadata.float_type(),
+                )?;
+                self.metadata.apply_compression(&mut var)?;
 
                 // Apply chunking for nodal variables (clamp to num_nodes)
                 let node_chunk = clamp_chunk(node_chunk_req, num_nodes);
@@ -866,14 +897,7 @@ impl ExodusFile<mode::Write> {
             }
             EntityType::ElemBlock => {
                 // Element var: vals_elem_var{var_idx}eb{block_idx}(time_step, num_el_in_blk{block_idx})
-                let block_ids = self.block_ids(EntityType::ElemBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::ElemBlock.to_string(),
-                        id: entity_id,
-                    })?;
+                let block_index = self.entity_index(EntityType::ElemBlock, entity_id)?;
 
                 let dim_name = format!("num_el_in_blk{}", block_index + 1);
                 // Get block dimension size for clamping
@@ -883,9 +907,13 @@ impl ExodusFile<mode::Write> {
                     .map(|d| d.len())
                     .unwrap_or(0);
 
-                let mut var = self
-                    .nc_file
-                    .add_variable::<f64>(&var_name, &[DIM_TIME_STEP, &dim_name])?;
+                let mut var = self.nc_file.add_variable_with_type(
+                    &var_name,
+                    &[DIM_TIME_STEP, &dim_name],
+                    &self.metadata.float_type(),
+                )?;
+
+                self.metadata.apply_compression(&mut var)?;
 
                 // Apply chunking for element variables (clamp to block size)
                 let elem_chunk = clamp_chunk(elem_chunk_req, block_dim_size);
@@ -899,14 +927,7 @@ impl ExodusFile<mode::Write> {
                 }
             }
             EntityType::EdgeBlock => {
-                let block_ids = self.block_ids(EntityType::EdgeBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::EdgeBlock.to_string(),
-                        id: entity_id,
-                    })?;
+                let block_index = self.entity_index(EntityType::EdgeBlock, entity_id)?;
 
                 let dim_name = format!("num_ed_in_blk{}", block_index + 1);
                 // Get block dimension size for clamping
@@ -916,9 +937,13 @@ impl ExodusFile<mode::Write> {
                     .map(|d| d.len())
                     .unwrap_or(0);
 
-                let mut var = self
-                    .nc_file
-                    .add_variable::<f64>(&var_name, &[DIM_TIME_STEP, &dim_name])?;
+                let mut var = self.nc_file.add_variable_with_type(
+                    &var_name,
+                    &[DIM_TIME_STEP, &dim_name],
+                    &self.metadata.float_type(),
+                )?;
+
+                self.metadata.apply_compression(&mut var)?;
 
                 // Apply chunking for edge variables (clamp to block size)
                 let elem_chunk = clamp_chunk(elem_chunk_req, block_dim_size);
@@ -932,14 +957,7 @@ impl ExodusFile<mode::Write> {
                 }
             }
             EntityType::FaceBlock => {
-                let block_ids = self.block_ids(EntityType::FaceBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::FaceBlock.to_string(),
-                        id: entity_id,
-                    })?;
+                let block_index = self.entity_index(EntityType::FaceBlock, entity_id)?;
 
                 let dim_name = format!("num_fa_in_blk{}", block_index + 1);
                 // Get block dimension size for clamping
@@ -949,9 +967,13 @@ impl ExodusFile<mode::Write> {
                     .map(|d| d.len())
                     .unwrap_or(0);
 
-                let mut var = self
-                    .nc_file
-                    .add_variable::<f64>(&var_name, &[DIM_TIME_STEP, &dim_name])?;
+                let mut var = self.nc_file.add_variable_with_type(
+                    &var_name,
+                    &[DIM_TIME_STEP, &dim_name],
+                    &self.metadata.float_type(),
+                )?;
+
+                self.metadata.apply_compression(&mut var)?;
 
                 // Apply chunking for face variables (clamp to block size)
                 let elem_chunk = clamp_chunk(elem_chunk_req, block_dim_size);
@@ -965,79 +987,59 @@ impl ExodusFile<mode::Write> {
                 }
             }
             EntityType::NodeSet => {
-                let set_ids = self.set_ids(EntityType::NodeSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::NodeSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::NodeSet, entity_id)?;
 
                 let dim_name = format!("num_nod_ns{}", set_index + 1);
-                self.nc_file
-                    .add_variable::<f64>(&var_name, &[DIM_TIME_STEP, &dim_name])?;
+                let mut var = self.nc_file.add_variable_with_type(
+                    &var_name,
+                    &[DIM_TIME_STEP, &dim_name],
+                    &self.metadata.float_type(),
+                )?;
+                self.metadata.apply_compression(&mut var)?;
             }
             EntityType::EdgeSet => {
-                let set_ids = self.set_ids(EntityType::EdgeSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::EdgeSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::EdgeSet, entity_id)?;
 
                 let dim_name = format!("num_edge_es{}", set_index + 1);
-                self.nc_file
-                    .add_variable::<f64>(&var_name, &[DIM_TIME_STEP, &dim_name])?;
+                let mut var = self.nc_file.add_variable_with_type(
+                    &var_name,
+                    &[DIM_TIME_STEP, &dim_name],
+                    &self.metadata.float_type(),
+                )?;
+                self.metadata.apply_compression(&mut var)?;
             }
             EntityType::FaceSet => {
-                let set_ids = self.set_ids(EntityType::FaceSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::FaceSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::FaceSet, entity_id)?;
 
                 let dim_name = format!("num_face_fs{}", set_index + 1);
-                self.nc_file
-                    .add_variable::<f64>(&var_name, &[DIM_TIME_STEP, &dim_name])?;
+                let mut var = self.nc_file.add_variable_with_type(
+                    &var_name,
+                    &[DIM_TIME_STEP, &dim_name],
+                    &self.metadata.float_type(),
+                )?;
+                self.metadata.apply_compression(&mut var)?;
             }
             EntityType::SideSet => {
-                let set_ids = self.set_ids(EntityType::SideSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::SideSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::SideSet, entity_id)?;
 
                 let dim_name = format!("num_side_ss{}", set_index + 1);
-                self.nc_file
-                    .add_variable::<f64>(&var_name, &[DIM_TIME_STEP, &dim_name])?;
+                let mut var = self.nc_file.add_variable_with_type(
+                    &var_name,
+                    &[DIM_TIME_STEP, &dim_name],
+                    &self.metadata.float_type(),
+                )?;
+                self.metadata.apply_compression(&mut var)?;
             }
             EntityType::ElemSet => {
-                let set_ids = self.set_ids(EntityType::ElemSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::ElemSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::ElemSet, entity_id)?;
 
                 let dim_name = format!("num_ele_els{}", set_index + 1);
-                self.nc_file
-                    .add_variable::<f64>(&var_name, &[DIM_TIME_STEP, &dim_name])?;
+                let mut var = self.nc_file.add_variable_with_type(
+                    &var_name,
+                    &[DIM_TIME_STEP, &dim_name],
+                    &self.metadata.float_type(),
+                )?;
+                self.metadata.apply_compression(&mut var)?;
             }
             _ => {
                 return Err(ExodusError::InvalidEntityType(format!(
@@ -1106,38 +1108,21 @@ impl ExodusFile<mode::Write> {
                     self.put_var(step, var_type, entity_id, var_idx, &values[start..end])?;
                 }
             }
-            EntityType::ElemBlock => {
-                // Element vars: values should be [num_elems * num_vars]
-                // Get number of elements from dimension
-                let block_ids = self.block_ids(EntityType::ElemBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::ElemBlock.to_string(),
-                        id: entity_id,
-                    })?;
+            t if is_block_or_set(t) => {
+                // Block/set vars: values should be [num_entries * num_vars]
+                let num_entries = self.num_entity_entries(var_type, entity_id)?;
 
-                let dim_name = format!("num_el_in_blk{}", block_index + 1);
-                let num_elems = self
-                    .nc_file
-                    .dimension(&dim_name)
-                    .map(|d| d.len())
-                    .ok_or_else(|| {
-                        ExodusError::Other(format!("Block dimension {} not found", dim_name))
-                    })?;
-
-                if values.len() != num_elems * num_vars {
+                if values.len() != num_entries * num_vars {
                     return Err(ExodusError::InvalidArrayLength {
-                        expected: num_elems * num_vars,
+                        expected: num_entries * num_vars,
                         actual: values.len(),
                     });
                 }
 
                 // Split values by variable
                 for var_idx in 0..num_vars {
-                    let start = var_idx * num_elems;
-                    let end = start + num_elems;
+                    let start = var_idx * num_entries;
+                    let end = start + num_entries;
                     self.put_var(step, var_type, entity_id, var_idx, &values[start..end])?;
                 }
             }
@@ -1176,13 +1161,18 @@ impl ExodusFile<mode::Write> {
         values: &[f64],
     ) -> Result<()> {
         let num_steps = end_step - start_step;
-        let var_name = self.get_var_name(var_type, entity_id, var_index)?;
+        let var_name = self.write_var_name(var_type, entity_id, var_index)?;
 
         // Get or create the variable
         if self.nc_file.variable(&var_name).is_none() {
+            self.check_truth_table(var_type, entity_id, var_index)?;
+            self.ensure_define_mode()?;
             self.create_var_storage(var_type, entity_id, var_index)?;
         }
 
+        // Ensure we're in data mode for writing variable values
+        self.ensure_data_mode()?;
+
         // Get all needed information BEFORE borrowing variable mutably
         let expected_len = match var_type {
             EntityType::Global => num_steps,
@@ -1194,33 +1184,7 @@ impl ExodusFile<mode::Write> {
                     .unwrap_or(0);
                 num_steps * num_nodes
             }
-            EntityType::ElemBlock | EntityType::EdgeBlock | EntityType::FaceBlock => {
-                let block_ids = self.block_ids(var_type)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: var_type.to_string(),
-                        id: entity_id,
-                    })?;
-
-                let dim_name = match var_type {
-                    EntityType::ElemBlock => format!("num_el_in_blk{}", block_index + 1),
-                    EntityType::EdgeBlock => format!("num_ed_in_blk{}", block_index + 1),
-                    EntityType::FaceBlock => format!("num_fa_in_blk{}", block_index + 1),
-                    _ => unreachable!(),
-                };
-
-                let num_entries = self
-                    .nc_file
-                    .dimension(&dim_name)
-                    .map(|d| d.len())
-                    .ok_or_else(|| {
-                        ExodusError::Other(format!("Block dimension {} not found", dim_name))
-                    })?;
-
-                num_steps * num_entries
-            }
+            t if is_block_or_set(t) => num_steps * self.num_entity_entries(var_type, entity_id)?,
             _ => {
                 return Err(ExodusError::InvalidEntityType(format!(
                     "Unsupported variable type: {}",
@@ -1238,6 +1202,7 @@ impl ExodusFile<mode::Write> {
         }
 
         // Now write the values
+        let combined = self.nodal_vars_combined();
         if let Some(mut var) = self.nc_file.variable_mut(&var_name) {
             match var_type {
                 EntityType::Global => {
@@ -1248,6 +1213,10 @@ impl ExodusFile<mode::Write> {
                         var.put_value(value, (step..step + 1, var_index..var_index + 1))?;
                     }
                 }
+                EntityType::Nodal if combined => {
+                    // Nodal vars: (time_step, num_nod_var, num_nodes)
+                    var.put_values(values, (start_step..end_step, var_index..var_index + 1, ..))?;
+                }
                 EntityType::Nodal
                 | EntityType::ElemBlock
                 | EntityType::EdgeBlock
@@ -1267,118 +1236,6 @@ impl ExodusFile<mode::Write> {
         Ok(())
     }
 
-    // Helper function to get variable name
-    fn get_var_name(
-        &self,
-        var_type: EntityType,
-        entity_id: EntityId,
-        var_index: usize,
-    ) -> Result<String> {
-        Ok(match var_type {
-            EntityType::Global => VAR_VALS_GLO_VAR.to_string(),
-            EntityType::Nodal => format!("vals_nod_var{}", var_index + 1),
-            EntityType::ElemBlock => {
-                let block_ids = self.block_ids(EntityType::ElemBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::ElemBlock.to_string(),
-                        id: entity_id,
-                    })?;
-                format!("vals_elem_var{}eb{}", var_index + 1, block_index + 1)
-            }
-            EntityType::EdgeBlock => {
-                let block_ids = self.block_ids(EntityType::EdgeBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::EdgeBlock.to_string(),
-                        id: entity_id,
-                    })?;
-                format!("vals_edge_var{}edb{}", var_index + 1, block_index + 1)
-            }
-            EntityType::FaceBlock => {
-                let block_ids = self.block_ids(EntityType::FaceBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::FaceBlock.to_string(),
-                        id: entity_id,
-                    })?;
-                format!("vals_face_var{}fab{}", var_index + 1, block_index + 1)
-            }
-            EntityType::NodeSet => {
-                let set_ids = self.set_ids(EntityType::NodeSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::NodeSet.to_string(),
-                            id: entity_id,
-                        })?;
-                format!("vals_nset_var{}ns{}", var_index + 1, set_index + 1)
-            }
-            EntityType::EdgeSet => {
-                let set_ids = self.set_ids(EntityType::EdgeSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::EdgeSet.to_string(),
-                            id: entity_id,
-                        })?;
-                format!("vals_eset_var{}es{}", var_index + 1, set_index + 1)
-            }
-            EntityType::FaceSet => {
-                let set_ids = self.set_ids(EntityType::FaceSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::FaceSet.to_string(),
-                            id: entity_id,
-                        })?;
-                format!("vals_fset_var{}fs{}", var_index + 1, set_index + 1)
-            }
-            EntityType::SideSet => {
-                let set_ids = self.set_ids(EntityType::SideSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::SideSet.to_string(),
-                            id: entity_id,
-                        })?;
-                format!("vals_sset_var{}ss{}", var_index + 1, set_index + 1)
-            }
-            EntityType::ElemSet => {
-                let set_ids = self.set_ids(EntityType::ElemSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::ElemSet.to_string(),
-                            id: entity_id,
-                        })?;
-                format!("vals_elset_var{}els{}", var_index + 1, set_index + 1)
-            }
-            _ => {
-                return Err(ExodusError::InvalidEntityType(format!(
-                    "Unsupported variable type: {}",
-                    var_type
-                )))
-            }
-        })
-    }
-
     // ====================
     // Reduction Variables (Write Operations)
     // ====================
@@ -1479,10 +1336,15 @@ impl ExodusFile<mode::Write> {
         // For global reduction variables, create the storage variable now
         // For other types, storage is created per-entity in put_reduction_vars()
         if var_type == EntityType::Global && self.nc_file.variable(VAR_VALS_GLO_VAR).is_none() {
-            self.nc_file
-                .add_variable::<f64>(VAR_VALS_GLO_VAR, &[DIM_TIME_STEP, num_var_dim])?;
+            self.nc_file.add_variable_with_type(
+                VAR_VALS_GLO_VAR,
+                &[DIM_TIME_STEP, num_var_dim],
+                &self.metadata.float_type(),
+            )?;
         }
 
+        self.finish_definitions()?;
+
         // Write the variable names using NC_CHAR (2D character array)
         // Each name is written as a row in the 2D array, padded with null bytes
         if let Some(mut var) = self.nc_file.variable_mut(var_name_var) {
@@ -1501,7 +1363,7 @@ impl ExodusFile<mode::Write> {
         }
 
         // Force sync to ensure all data is written
-        self.nc_file.sync()?;
+        self.sync()?;
 
         Ok(())
     }
@@ -1577,96 +1439,35 @@ impl ExodusFile<mode::Write> {
             }
             EntityType::ElemBlock => {
                 // Find the index of this block
-                let block_ids = self.block_ids(var_type)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: var_type.to_string(),
-                        id: entity_id,
-                    })?;
+                let block_index = self.entity_index(var_type, entity_id)?;
                 format!("vals_elem_red_eb{}", block_index + 1)
             }
             EntityType::EdgeBlock => {
-                let block_ids = self.block_ids(var_type)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: var_type.to_string(),
-                        id: entity_id,
-                    })?;
+                let block_index = self.entity_index(var_type, entity_id)?;
                 format!("vals_edge_red_edgb{}", block_index + 1)
             }
             EntityType::FaceBlock => {
-                let block_ids = self.block_ids(var_type)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: var_type.to_string(),
-                        id: entity_id,
-                    })?;
+                let block_index = self.entity_index(var_type, entity_id)?;
                 format!("vals_face_red_facb{}", block_index + 1)
             }
             EntityType::NodeSet => {
-                let set_ids = self.set_ids(EntityType::NodeSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::NodeSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::NodeSet, entity_id)?;
                 format!("vals_nset_red_ns{}", set_index + 1)
             }
             EntityType::EdgeSet => {
-                let set_ids = self.set_ids(EntityType::EdgeSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::EdgeSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::EdgeSet, entity_id)?;
                 format!("vals_eset_red_es{}", set_index + 1)
             }
             EntityType::FaceSet => {
-                let set_ids = self.set_ids(EntityType::FaceSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::FaceSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::FaceSet, entity_id)?;
                 format!("vals_fset_red_fs{}", set_index + 1)
             }
             EntityType::SideSet => {
-                let set_ids = self.set_ids(EntityType::SideSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::SideSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::SideSet, entity_id)?;
                 format!("vals_sset_red_ss{}", set_index + 1)
             }
             EntityType::ElemSet => {
-                let set_ids = self.set_ids(EntityType::ElemSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::ElemSet.to_string(),
-                            id: entity_id,
-                        })?;
+                let set_index = self.entity_index(EntityType::ElemSet, entity_id)?;
                 format!("vals_elset_red_els{}", set_index + 1)
             }
             _ => {
@@ -1709,8 +1510,11 @@ impl ExodusFile<mode::Write> {
 
         // Create the storage variable: var_name(time_step, num_*_red_var)
         if self.nc_file.variable(&var_name).is_none() {
-            self.nc_file
-                .add_variable::<f64>(&var_name, &[DIM_TIME_STEP, num_var_dim])?;
+            self.nc_file.add_variable_with_type(
+                &var_name,
+                &[DIM_TIME_STEP, num_var_dim],
+                &self.metadata.float_type(),
+            )?;
         }
 
         Ok(())
@@ -1721,46 +1525,7 @@ impl ExodusFile<mode::Write> {
 // Read Operations
 // ====================
 
-impl ExodusFile<mode::Read> {
-    /// Get all time values
-    ///
-    /// # Returns
-    ///
-    /// Vector of time values
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if NetCDF read fails
-    pub fn times(&self) -> Result<Vec<f64>> {
-        match self.nc_file.variable(VAR_TIME_WHOLE) {
-            Some(var) => Ok(var.get_values(..)?),
-            None => Ok(Vec::new()),
-        }
-    }
-
-    /// Get time value for a step
-    ///
-    /// # Arguments
-    ///
-    /// * `step` - Time step index (0-based)
-    ///
-    /// # Returns
-    ///
-    /// Time value
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if:
-    /// - Time step is out of range
-    /// - NetCDF read fails
-    pub fn time(&self, step: usize) -> Result<f64> {
-        let times = self.times()?;
-        times
-            .get(step)
-            .copied()
-            .ok_or(ExodusError::InvalidTimeStep(step))
-    }
-
+impl<M: ReadableMode> ExodusFile<M> {
     /// Read variable values at a time step
     ///
     /// This method automatically handles both storage formats:
@@ -1792,25 +1557,12 @@ impl ExodusFile<mode::Read> {
         entity_id: EntityId,
         var_index: usize,
     ) -> Result<Vec<f64>> {
+        self.metadata.check_time_step(step)?;
+
         // Get the storage mode for this entity type
-        let storage_mode = match var_type {
-            EntityType::Global => self.metadata.storage_format.global,
-            EntityType::Nodal => self.metadata.storage_format.nodal,
-            EntityType::ElemBlock => self.metadata.storage_format.elem_block,
-            EntityType::EdgeBlock => self.metadata.storage_format.edge_block,
-            EntityType::FaceBlock => self.metadata.storage_format.face_block,
-            EntityType::NodeSet => self.metadata.storage_format.node_set,
-            EntityType::EdgeSet => self.metadata.storage_format.edge_set,
-            EntityType::FaceSet => self.metadata.storage_format.face_set,
-            EntityType::SideSet => self.metadata.storage_format.side_set,
-            EntityType::ElemSet => self.metadata.storage_format.elem_set,
-            _ => {
-                return Err(ExodusError::InvalidEntityType(format!(
-                    "Unsupported variable type: {}",
-                    var_type
-                )))
-            }
-        };
+        let storage_mode = self.metadata.storage_format.get(var_type).ok_or_else(|| {
+            ExodusError::InvalidEntityType(format!("Unsupported variable type: {}", var_type))
+        })?;
 
         match storage_mode {
             VarStorageMode::Combined => {
@@ -1830,26 +1582,22 @@ impl ExodusFile<mode::Read> {
         entity_id: EntityId,
         var_index: usize,
     ) -> Result<Vec<f64>> {
-        let var_name = self.get_var_name_read(var_type, entity_id, var_index)?;
-
-        let var = self
-            .nc_file
-            .variable(&var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.clone()))?;
+        let var_name = self.var_storage_name(var_type, entity_id, var_index)?;
+        let shape = self.nc_file.require_shape(&var_name)?;
 
         match var_type {
             EntityType::Global => {
                 // Global vars: (time_step, num_glo_var)
-                let value: f64 = var.get_value((step, var_index))?;
-                Ok(vec![value])
+                self.nc_file
+                    .read_f64(&var_name, &[step, var_index], &[1, 1])
             }
             EntityType::Nodal => {
                 // Nodal vars: (time_step, num_nodes)
-                Ok(var.get_values((step..step + 1, ..))?)
+                self.nc_file.read_f64(&var_name, &[step, 0], &[1, shape[1]])
             }
             EntityType::ElemBlock | EntityType::EdgeBlock | EntityType::FaceBlock => {
                 // Block vars: (time_step, num_entries_in_block)
-                Ok(var.get_values((step..step + 1, ..))?)
+                self.nc_file.read_f64(&var_name, &[step, 0], &[1, shape[1]])
             }
             EntityType::NodeSet
             | EntityType::EdgeSet
@@ -1857,7 +1605,7 @@ impl ExodusFile<mode::Read> {
             | EntityType::SideSet
             | EntityType::ElemSet => {
                 // Set vars: (time_step, num_entries_in_set)
-                Ok(var.get_values((step..step + 1, ..))?)
+                self.nc_file.read_f64(&var_name, &[step, 0], &[1, shape[1]])
             }
             _ => Err(ExodusError::InvalidEntityType(format!(
                 "Unsupported variable type: {}",
@@ -1876,7 +1624,7 @@ impl ExodusFile<mode::Read> {
     ) -> Result<Vec<f64>> {
         let var_name = match var_type {
             EntityType::Global => VAR_VALS_GLO_VAR,
-            EntityType::Nodal => "vals_nod_var",
+            EntityType::Nodal => VAR_VALS_NOD_VAR,
             EntityType::ElemBlock => "vals_elem_var",
             EntityType::EdgeBlock => "vals_edge_var",
             EntityType::FaceBlock => "vals_face_var",
@@ -1893,24 +1641,19 @@ impl ExodusFile<mode::Read> {
             }
         };
 
-        let var = self
-            .nc_file
-            .variable(var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.to_string()))?;
+        let shape = self.nc_file.require_shape(var_name)?;
 
         match var_type {
             EntityType::Global => {
                 // Global vars in combined format: (time_step, num_glo_var)
-                let value: f64 = var.get_value((step, var_index))?;
-                Ok(vec![value])
+                self.nc_file.read_f64(var_name, &[step, var_index], &[1, 1])
             }
             EntityType::Nodal => {
                 // Nodal vars in combined format: (time_step, num_nod_var, num_nodes)
                 // Use partial read to only fetch the specific slice we need
                 // This is much more efficient for large datasets as it avoids reading the entire 3D array
-                let data: Vec<f64> =
-                    var.get_values((step..step + 1, var_index..var_index + 1, ..))?;
-                Ok(data)
+                self.nc_file
+                    .read_f64(var_name, &[step, var_index, 0], &[1, 1, shape[2]])
             }
             EntityType::ElemBlock
             | EntityType::EdgeBlock
@@ -1923,9 +1666,8 @@ impl ExodusFile<mode::Read> {
                 // Combined format: (time_step, num_vars, num_entities)
                 // Use partial read to only fetch the specific slice we need
                 // This is much more efficient for large datasets as it avoids reading the entire 3D array
-                let data: Vec<f64> =
-                    var.get_values((step..step + 1, var_index..var_index + 1, ..))?;
-                Ok(data)
+                self.nc_file
+                    .read_f64(var_name, &[step, var_index, 0], &[1, 1, shape[2]])
             }
             _ => Err(ExodusError::InvalidEntityType(format!(
                 "Unsupported variable type: {}",
@@ -1934,134 +1676,6 @@ impl ExodusFile<mode::Read> {
         }
     }
 
-    /// Get truth table
-    ///
-    /// # Arguments
-    ///
-    /// * `var_type` - Entity type (ElemBlock, EdgeBlock, or FaceBlock)
-    ///
-    /// # Returns
-    ///
-    /// Truth table
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if:
-    /// - var_type is not a block type
-    /// - truth table size doesn't match expected dimensions
-    /// - NetCDF read fails
-    pub fn truth_table(&self, var_type: EntityType) -> Result<TruthTable> {
-        let var_name = match var_type {
-            EntityType::ElemBlock => VAR_ELEM_VAR_TAB,
-            EntityType::EdgeBlock => VAR_EDGE_VAR_TAB,
-            EntityType::FaceBlock => VAR_FACE_VAR_TAB,
-            _ => {
-                return Err(ExodusError::InvalidEntityType(format!(
-                    "Truth tables only supported for block types, got {}",
-                    var_type
-                )))
-            }
-        };
-
-        // Get number of blocks and variables
-        let num_blocks = self.block_ids(var_type)?.len();
-        let num_vars = self.variable_names(var_type)?.len();
-        let expected_len = num_blocks * num_vars;
-
-        // Read truth table if it exists
-        let table_values = if let Some(var) = self.nc_file.variable(var_name) {
-            let table_i32: Vec<i32> = var.get_values(..)?;
-
-            // Validate size
-            if table_i32.len() != expected_len {
-                return Err(ExodusError::InvalidArrayLength {
-                    expected: expected_len,
-                    actual: table_i32.len(),
-                });
-            }
-
-            table_i32.iter().map(|&v| v != 0).collect()
-        } else {
-            // Default: all true
-            vec![true; expected_len]
-        };
-
-        Ok(TruthTable {
-            var_type,
-            num_vars,
-            num_blocks,
-            table: table_values,
-        })
-    }
-
-    /// Check if a variable is enabled in the truth table for a given block
-    ///
-    /// This is a helper method to check whether a specific variable is defined
-    /// for a specific block according to the truth table. This is useful for
-    /// sparse variable storage where not all variables are defined on all blocks.
-    ///
-    /// # Arguments
-    ///
-    /// * `var_type` - Entity type (must be a block type)
-    /// * `block_id` - Block ID
-    /// * `var_index` - Variable index (0-based)
-    ///
-    /// # Returns
-    ///
-    /// `true` if the variable is enabled for the block, `false` otherwise.
-    /// If no truth table exists, returns `true` (all variables enabled by default).
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if:
-    /// - var_type is not a block type
-    /// - block_id is not found
-    /// - var_index is out of range
-    /// - truth table read fails
-    pub fn is_var_in_truth_table(
-        &self,
-        var_type: EntityType,
-        block_id: EntityId,
-        var_index: usize,
-    ) -> Result<bool> {
-        // Only supported for block types
-        if !matches!(
-            var_type,
-            EntityType::ElemBlock | EntityType::EdgeBlock | EntityType::FaceBlock
-        ) {
-            return Err(ExodusError::InvalidEntityType(format!(
-                "Truth tables only supported for block types, got {}",
-                var_type
-            )));
-        }
-
-        // Get the block index from the block ID
-        let block_ids = self.block_ids(var_type)?;
-        let block_index = block_ids
-            .iter()
-            .position(|&id| id == block_id)
-            .ok_or_else(|| ExodusError::EntityNotFound {
-                entity_type: var_type.to_string(),
-                id: block_id,
-            })?;
-
-        // Get the truth table
-        let truth_table = self.truth_table(var_type)?;
-
-        // Validate var_index
-        if var_index >= truth_table.num_vars {
-            return Err(ExodusError::Other(format!(
-                "Variable index {} out of range (max {})",
-                var_index,
-                truth_table.num_vars - 1
-            )));
-        }
-
-        // Truth table is stored as [block0_var0, block0_var1, ..., block1_var0, block1_var1, ...]
-        let table_index = block_index * truth_table.num_vars + var_index;
-        Ok(truth_table.table[table_index])
-    }
-
     /// Read all variables for an entity at a time step
     ///
     /// # Arguments
@@ -2086,34 +1700,18 @@ impl ExodusFile<mode::Read> {
         let num_vars = self.variable_names(var_type)?.len();
         let mut all_values = Vec::new();
 
-        match var_type {
-            EntityType::Global => {
-                // Global vars: read all variables at once
-                for var_idx in 0..num_vars {
-                    let values = self.var(step, var_type, entity_id, var_idx)?;
-                    all_values.extend_from_slice(&values);
-                }
-            }
-            EntityType::Nodal => {
-                // Nodal vars: concatenate all variable values
-                for var_idx in 0..num_vars {
-                    let values = self.var(step, var_type, entity_id, var_idx)?;
-                    all_values.extend_from_slice(&values);
-                }
-            }
-            EntityType::ElemBlock => {
-                // Element vars: concatenate all variable values
-                for var_idx in 0..num_vars {
-                    let values = self.var(step, var_type, entity_id, var_idx)?;
-                    all_values.extend_from_slice(&values);
-                }
-            }
-            _ => {
-                return Err(ExodusError::InvalidEntityType(format!(
-                    "Unsupported variable type: {}",
-                    var_type
-                )))
-            }
+        if !matches!(var_type, EntityType::Global | EntityType::Nodal) && !is_block_or_set(var_type)
+        {
+            return Err(ExodusError::InvalidEntityType(format!(
+                "Unsupported variable type: {}",
+                var_type
+            )));
+        }
+
+        // Concatenate all variable values
+        for var_idx in 0..num_vars {
+            let values = self.var(step, var_type, entity_id, var_idx)?;
+            all_values.extend_from_slice(&values);
         }
 
         Ok(all_values)
@@ -2144,31 +1742,29 @@ impl ExodusFile<mode::Read> {
         entity_id: EntityId,
         var_index: usize,
     ) -> Result<Vec<f64>> {
-        let var_name = self.get_var_name_read(var_type, entity_id, var_index)?;
-
-        let var = self
-            .nc_file
-            .variable(&var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.clone()))?;
+        if end_step > start_step {
+            self.metadata.check_time_step(end_step - 1)?;
+        }
+        let var_name = self.var_storage_name(var_type, entity_id, var_index)?;
+        let shape = self.nc_file.require_shape(&var_name)?;
+        let num_steps = end_step.saturating_sub(start_step);
 
         match var_type {
             EntityType::Global => {
                 // Global vars: (time_step, num_glo_var)
                 // Read time series at [start_step:end_step, var_index]
-                let mut values = Vec::new();
-                for step in start_step..end_step {
-                    let value: f64 = var.get_value((step, var_index))?;
-                    values.push(value);
-                }
-                Ok(values)
+                self.nc_file
+                    .read_f64(&var_name, &[start_step, var_index], &[num_steps, 1])
             }
             EntityType::Nodal => {
                 // Nodal vars: (time_step, num_nodes)
-                Ok(var.get_values((start_step..end_step, ..))?)
+                self.nc_file
+                    .read_f64(&var_name, &[start_step, 0], &[num_steps, shape[1]])
             }
             EntityType::ElemBlock | EntityType::EdgeBlock | EntityType::FaceBlock => {
                 // Block vars: (time_step, num_entries_in_block)
-                Ok(var.get_values((start_step..end_step, ..))?)
+                self.nc_file
+                    .read_f64(&var_name, &[start_step, 0], &[num_steps, shape[1]])
             }
             EntityType::NodeSet
             | EntityType::EdgeSet
@@ -2176,7 +1772,8 @@ impl ExodusFile<mode::Read> {
             | EntityType::SideSet
             | EntityType::ElemSet => {
                 // Set vars: (time_step, num_entries_in_set)
-                Ok(var.get_values((start_step..end_step, ..))?)
+                self.nc_file
+                    .read_f64(&var_name, &[start_step, 0], &[num_steps, shape[1]])
             }
             _ => Err(ExodusError::InvalidEntityType(format!(
                 "Unsupported variable type: {}",
@@ -2184,7 +1781,9 @@ impl ExodusFile<mode::Read> {
             ))),
         }
     }
+}
 
+impl ExodusFile<mode::Read> {
     /// Read variable time series as a 2D ndarray (NumPy-compatible)
     ///
     /// Returns variable values across multiple time steps as a 2D ndarray with shape
@@ -2275,542 +1874,4 @@ impl ExodusFile<mode::Read> {
         Array2::from_shape_vec((num_steps, num_entities), data)
             .map_err(|e| ExodusError::Other(format!("Failed to reshape array: {}", e)))
     }
-
-    // Helper function to get variable name for reading
-    fn get_var_name_read(
-        &self,
-        var_type: EntityType,
-        entity_id: EntityId,
-        var_index: usize,
-    ) -> Result<String> {
-        Ok(match var_type {
-            EntityType::Global => VAR_VALS_GLO_VAR.to_string(),
-            EntityType::Nodal => format!("vals_nod_var{}", var_index + 1),
-            EntityType::ElemBlock => {
-                let block_ids = self.block_ids(EntityType::ElemBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::ElemBlock.to_string(),
-                        id: entity_id,
-                    })?;
-                format!("vals_elem_var{}eb{}", var_index + 1, block_index + 1)
-            }
-            EntityType::EdgeBlock => {
-                let block_ids = self.block_ids(EntityType::EdgeBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::EdgeBlock.to_string(),
-                        id: entity_id,
-                    })?;
-                format!("vals_edge_var{}edb{}", var_index + 1, block_index + 1)
-            }
-            EntityType::FaceBlock => {
-                let block_ids = self.block_ids(EntityType::FaceBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::FaceBlock.to_string(),
-                        id: entity_id,
-                    })?;
-                format!("vals_face_var{}fab{}", var_index + 1, block_index + 1)
-            }
-            EntityType::NodeSet => {
-                let set_ids = self.set_ids(EntityType::NodeSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::NodeSet.to_string(),
-                            id: entity_id,
-                        })?;
-                format!("vals_nset_var{}ns{}", var_index + 1, set_index + 1)
-            }
-            EntityType::EdgeSet => {
-                let set_ids = self.set_ids(EntityType::EdgeSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::EdgeSet.to_string(),
-                            id: entity_id,
-                        })?;
-                format!("vals_eset_var{}es{}", var_index + 1, set_index + 1)
-            }
-            EntityType::FaceSet => {
-                let set_ids = self.set_ids(EntityType::FaceSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::FaceSet.to_string(),
-                            id: entity_id,
-                        })?;
-                format!("vals_fset_var{}fs{}", var_index + 1, set_index + 1)
-            }
-            EntityType::SideSet => {
-                let set_ids = self.set_ids(EntityType::SideSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::SideSet.to_string(),
-                            id: entity_id,
-                        })?;
-                format!("vals_sset_var{}ss{}", var_index + 1, set_index + 1)
-            }
-            EntityType::ElemSet => {
-                let set_ids = self.set_ids(EntityType::ElemSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::ElemSet.to_string(),
-                            id: entity_id,
-                        })?;
-                format!("vals_elset_var{}els{}", var_index + 1, set_index + 1)
-            }
-            _ => {
-                return Err(ExodusError::InvalidEntityType(format!(
-                    "Unsupported variable type: {}",
-                    var_type
-                )))
-            }
-        })
-    }
-}
-
-// ====================
-// Append Operations (Read + Write for time values)
-// ====================
-
-impl ExodusFile<mode::Append> {
-    /// Get all time values
-    ///
-    /// # Returns
-    ///
-    /// Vector of time values
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if NetCDF read fails
-    pub fn times(&self) -> Result<Vec<f64>> {
-        match self.nc_file.variable(VAR_TIME_WHOLE) {
-            Some(var) => Ok(var.get_values(..)?),
-            None => Ok(Vec::new()),
-        }
-    }
-
-    /// Get time value for a step
-    ///
-    /// # Arguments
-    ///
-    /// * `step` - Time step index (0-based)
-    ///
-    /// # Returns
-    ///
-    /// Time value
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if:
-    /// - Time step is out of range
-    /// - NetCDF read fails
-    pub fn time(&self, step: usize) -> Result<f64> {
-        let times = self.times()?;
-        times
-            .get(step)
-            .copied()
-            .ok_or(ExodusError::InvalidTimeStep(step))
-    }
-
-    /// Write time value for a time step
-    ///
-    /// # Arguments
-    ///
-    /// * `step` - Time step index (0-based)
-    /// * `time` - Time value
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if:
-    /// - NetCDF write fails
-    /// - time_whole variable doesn't exist in the file
-    pub fn put_time(&mut self, step: usize, time: f64) -> Result<()> {
-        // Ensure we're in data mode for writing time values
-        self.ensure_data_mode()?;
-
-        // Write the time value - the variable should already exist for append mode
-        if let Some(mut var) = self.nc_file.variable_mut(VAR_TIME_WHOLE) {
-            var.put_value(time, step..step + 1)?;
-            Ok(())
-        } else {
-            Err(ExodusError::Other(
-                "time_whole variable not found in file".to_string(),
-            ))
-        }
-    }
-
-    /// Read a variable at a specific time step (append mode - provides read access)
-    ///
-    /// This method automatically handles both storage formats:
-    /// - **Separate format**: Individual variables (e.g., `vals_nod_var1`, `vals_nod_var2`)
-    /// - **Combined format**: Single 3D array (e.g., `vals_nod_var(time_step, num_vars, num_nodes)`)
-    ///
-    /// The storage format is detected automatically when the file is opened.
-    pub fn var(
-        &self,
-        step: usize,
-        var_type: EntityType,
-        entity_id: EntityId,
-        var_index: usize,
-    ) -> Result<Vec<f64>> {
-        // Get the storage mode for this entity type
-        let storage_mode = match var_type {
-            EntityType::Global => self.metadata.storage_format.global,
-            EntityType::Nodal => self.metadata.storage_format.nodal,
-            EntityType::ElemBlock => self.metadata.storage_format.elem_block,
-            EntityType::EdgeBlock => self.metadata.storage_format.edge_block,
-            EntityType::FaceBlock => self.metadata.storage_format.face_block,
-            EntityType::NodeSet => self.metadata.storage_format.node_set,
-            EntityType::EdgeSet => self.metadata.storage_format.edge_set,
-            EntityType::FaceSet => self.metadata.storage_format.face_set,
-            EntityType::SideSet => self.metadata.storage_format.side_set,
-            EntityType::ElemSet => self.metadata.storage_format.elem_set,
-            _ => {
-                return Err(ExodusError::InvalidEntityType(format!(
-                    "Unsupported variable type: {}",
-                    var_type
-                )))
-            }
-        };
-
-        match storage_mode {
-            VarStorageMode::Combined => self.read_var_combined_append(step, var_type, var_index),
-            VarStorageMode::Separate | VarStorageMode::None => {
-                self.read_var_separate_append(step, var_type, entity_id, var_index)
-            }
-        }
-    }
-
-    /// Read variable from separate format (vals_nod_var1, vals_nod_var2, etc.)
-    fn read_var_separate_append(
-        &self,
-        step: usize,
-        var_type: EntityType,
-        entity_id: EntityId,
-        var_index: usize,
-    ) -> Result<Vec<f64>> {
-        // Simplified variable name construction (same logic as Read mode)
-        let var_name = match var_type {
-            EntityType::Global => VAR_VALS_GLO_VAR.to_string(),
-            EntityType::Nodal => format!("vals_nod_var{}", var_index + 1),
-            EntityType::ElemBlock => {
-                let block_ids = self.block_ids(EntityType::ElemBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::ElemBlock.to_string(),
-                        id: entity_id,
-                    })?;
-                format!("vals_elem_var{}eb{}", var_index + 1, block_index + 1)
-            }
-            EntityType::EdgeBlock => {
-                let block_ids = self.block_ids(EntityType::EdgeBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::EdgeBlock.to_string(),
-                        id: entity_id,
-                    })?;
-                format!("vals_edge_var{}edb{}", var_index + 1, block_index + 1)
-            }
-            EntityType::FaceBlock => {
-                let block_ids = self.block_ids(EntityType::FaceBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::FaceBlock.to_string(),
-                        id: entity_id,
-                    })?;
-                format!("vals_face_var{}fab{}", var_index + 1, block_index + 1)
-            }
-            EntityType::NodeSet => {
-                let set_ids = self.set_ids(EntityType::NodeSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::NodeSet.to_string(),
-                            id: entity_id,
-                        })?;
-                format!("vals_nset_var{}ns{}", var_index + 1, set_index + 1)
-            }
-            EntityType::SideSet => {
-                let set_ids = self.set_ids(EntityType::SideSet)?;
-                let set_index =
-                    set_ids
-                        .iter()
-                        .position(|&id| id == entity_id)
-                        .ok_or_else(|| ExodusError::EntityNotFound {
-                            entity_type: EntityType::SideSet.to_string(),
-                            id: entity_id,
-                        })?;
-                format!("vals_sset_var{}ss{}", var_index + 1, set_index + 1)
-            }
-            _ => {
-                return Err(ExodusError::Other(format!(
-                    "Variable reading not yet fully implemented for {:?} in append mode",
-                    var_type
-                )))
-            }
-        };
-
-        let var = self
-            .nc_file
-            .variable(&var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.clone()))?;
-
-        match var_type {
-            EntityType::Global => {
-                let value: f64 = var.get_value((step, var_index))?;
-                Ok(vec![value])
-            }
-            _ => Ok(var.get_values((step..step + 1, ..))?),
-        }
-    }
-
-    /// Read variable from combined 3D format (vals_nod_var, vals_elem_var, etc.)
-    fn read_var_combined_append(
-        &self,
-        step: usize,
-        var_type: EntityType,
-        var_index: usize,
-    ) -> Result<Vec<f64>> {
-        let var_name = match var_type {
-            EntityType::Global => VAR_VALS_GLO_VAR,
-            EntityType::Nodal => "vals_nod_var",
-            EntityType::ElemBlock => "vals_elem_var",
-            EntityType::EdgeBlock => "vals_edge_var",
-            EntityType::FaceBlock => "vals_face_var",
-            EntityType::NodeSet => "vals_nset_var",
-            EntityType::EdgeSet => "vals_eset_var",
-            EntityType::FaceSet => "vals_fset_var",
-            EntityType::SideSet => "vals_sset_var",
-            EntityType::ElemSet => "vals_elset_var",
-            _ => {
-                return Err(ExodusError::InvalidEntityType(format!(
-                    "Unsupported variable type: {}",
-                    var_type
-                )))
-            }
-        };
-
-        let var = self
-            .nc_file
-            .variable(var_name)
-            .ok_or_else(|| ExodusError::VariableNotDefined(var_name.to_string()))?;
-
-        match var_type {
-            EntityType::Global => {
-                // Global vars in combined format: (time_step, num_glo_var)
-                let value: f64 = var.get_value((step, var_index))?;
-                Ok(vec![value])
-            }
-            EntityType::Nodal => {
-                // Nodal vars in combined format: (time_step, num_nod_var, num_nodes)
-                // Use partial read to only fetch the specific slice we need
-                // This is much more efficient for large datasets as it avoids reading the entire 3D array
-                let data: Vec<f64> =
-                    var.get_values((step..step + 1, var_index..var_index + 1, ..))?;
-                Ok(data)
-            }
-            EntityType::ElemBlock
-            | EntityType::EdgeBlock
-            | EntityType::FaceBlock
-            | EntityType::NodeSet
-            | EntityType::EdgeSet
-            | EntityType::FaceSet
-            | EntityType::SideSet
-            | EntityType::ElemSet => {
-                // Combined format: (time_step, num_vars, num_entities)
-                // Use partial read to only fetch the specific slice we need
-                // This is much more efficient for large datasets as it avoids reading the entire 3D array
-                let data: Vec<f64> =
-                    var.get_values((step..step + 1, var_index..var_index + 1, ..))?;
-                Ok(data)
-            }
-            _ => Err(ExodusError::InvalidEntityType(format!(
-                "Unsupported variable type: {}",
-                var_type
-            ))),
-        }
-    }
-
-    /// Write a variable at a specific time step (append mode - provides write access)
-    /// This is a simplified version that assumes the variable already exists
-    pub fn put_var(
-        &mut self,
-        step: usize,
-        var_type: EntityType,
-        entity_id: EntityId,
-        var_index: usize,
-        values: &[f64],
-    ) -> Result<()> {
-        // Simplified variable name construction (same as var method)
-        let var_name = match var_type {
-            EntityType::Global => VAR_VALS_GLO_VAR.to_string(),
-            EntityType::Nodal => format!("vals_nod_var{}", var_index + 1),
-            EntityType::ElemBlock => {
-                let block_ids = self.block_ids(EntityType::ElemBlock)?;
-                let block_index = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: EntityType::ElemBlock.to_string(),
-                        id: entity_id,
-                    })?;
-                format!("vals_elem_var{}eb{}", var_index + 1, block_index + 1)
-            }
-            _ => {
-                return Err(ExodusError::Other(format!(
-                    "Variable writing not yet fully implemented for {:?} in append mode",
-                    var_type
-                )))
-            }
-        };
-
-        // Ensure we're in data mode for writing
-        self.ensure_data_mode()?;
-
-        // Write the values
-        if let Some(mut var) = self.nc_file.variable_mut(&var_name) {
-            match var_type {
-                EntityType::Global => {
-                    if values.len() != 1 {
-                        return Err(ExodusError::InvalidArrayLength {
-                            expected: 1,
-                            actual: values.len(),
-                        });
-                    }
-                    var.put_value(values[0], (step..step + 1, var_index..var_index + 1))?;
-                }
-                EntityType::Nodal | EntityType::ElemBlock => {
-                    var.put_values(values, (step..step + 1, ..))?;
-                }
-                _ => {
-                    return Err(ExodusError::Other(format!(
-                        "Unsupported variable type: {:?}",
-                        var_type
-                    )))
-                }
-            }
-        }
-
-        Ok(())
-    }
-
-    /// Check if a variable is in the truth table (append mode)
-    pub fn is_var_in_truth_table(
-        &self,
-        var_type: EntityType,
-        entity_id: EntityId,
-        var_index: usize,
-    ) -> Result<bool> {
-        // Only block types use truth tables
-        match var_type {
-            EntityType::ElemBlock | EntityType::EdgeBlock | EntityType::FaceBlock => {
-                let truth_table = self.truth_table(var_type)?;
-                let block_ids = self.block_ids(var_type)?;
-
-                let block_idx = block_ids
-                    .iter()
-                    .position(|&id| id == entity_id)
-                    .ok_or_else(|| ExodusError::EntityNotFound {
-                        entity_type: var_type.to_string(),
-                        id: entity_id,
-                    })?;
-
-                Ok(truth_table.get(block_idx, var_index))
-            }
-            _ => Ok(true), // Non-block types don't use truth tables
-        }
-    }
-
-    /// Get the truth table for a variable type (append mode - delegates to common implementation)
-    pub fn truth_table(&self, var_type: EntityType) -> Result<TruthTable> {
-        // Get the dimension name for the number of variables
-        let num_var_dim = match var_type {
-            EntityType::ElemBlock => DIM_NUM_ELEM_VAR,
-            EntityType::EdgeBlock => DIM_NUM_EDGE_VAR,
-            EntityType::FaceBlock => DIM_NUM_FACE_VAR,
-            _ => {
-                return Err(ExodusError::Other(format!(
-                    "Truth tables only supported for block types, got: {:?}",
-                    var_type
-                )))
-            }
-        };
-
-        // Get number of variables
-        let num_vars = self
-            .nc_file
-            .dimension(num_var_dim)
-            .map(|d| d.len())
-            .unwrap_or(0);
-
-        if num_vars == 0 {
-            return Ok(TruthTable::new(var_type, 0, 0));
-        }
-
-        // Get block IDs
-        let block_ids = self.block_ids(var_type)?;
-        let num_blocks = block_ids.len();
-
-        if num_blocks == 0 {
-            return Ok(TruthTable::new(var_type, 0, num_vars));
-        }
-
-        // Try to read the truth table variable
-        let truth_var_name = match var_type {
-            EntityType::ElemBlock => VAR_ELEM_VAR_TAB,
-            EntityType::EdgeBlock => VAR_EDGE_VAR_TAB,
-            EntityType::FaceBlock => VAR_FACE_VAR_TAB,
-            _ => unreachable!(),
-        };
-
-        let mut table = TruthTable::new(var_type, num_blocks, num_vars);
-
-        if let Some(var) = self.nc_file.variable(truth_var_name) {
-            let truth_data: Vec<i32> = var.get_values(..)?;
-            for block_idx in 0..num_blocks {
-                for var_idx in 0..num_vars {
-                    let value = truth_data[block_idx * num_vars + var_idx];
-                    table.set(block_idx, var_idx, value != 0);
-                }
-            }
-        } else {
-            // If no truth table exists, assume all variables exist for all blocks
-            for block_idx in 0..num_blocks {
-                for var_idx in 0..num_vars {
-                    table.set(block_idx, var_idx, true);
-                }
-            }
-        }
-
-        Ok(table)
-    }
 }
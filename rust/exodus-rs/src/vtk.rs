@@ -0,0 +1,986 @@
+//! Exporting meshes and results to VTK.
+//!
+//! [`export_vtk`] writes the element blocks of an Exodus file as a VTK
+//! unstructured grid, for tools that read VTK but not Exodus. The format
+//! follows the extension of the output path:
+//!
+//! - `.pvd`: one XML unstructured grid (`.vtu`) per time step, written next
+//!   to the collection as `<stem>_<step>.vtu` and listed in the `.pvd` with
+//!   its time value
+//! - `.vtu`: XML unstructured grid
+//! - `.vtk`: legacy ASCII unstructured grid
+//!
+//! A `.vtu` or `.vtk` output holding more than one time step is written as a
+//! numbered series, `<stem>_<step>.vtu` or `<stem>_<step>.vtk`, which ParaView
+//! and VisIt open as one time-dependent dataset. Steps are numbered from 1,
+//! as in Exodus.
+//!
+//! Element blocks become cells, mapped by [`vtk_cell`] with their nodes put
+//! in VTK order. The arrays written are:
+//!
+//! - Point data: `node_id` (the node ID map), a `node_set_<id>` flag (1 for
+//!   nodes in the set, 0 otherwise) for each node set, and the nodal
+//!   variables
+//! - Cell data: `block_id`, `element_id` (the element ID map), a
+//!   `side_set_<id>` flag for each side set (1 for elements with a side in
+//!   the set), an `elem_set_<id>` flag for each element set, and the element
+//!   block variables. Blocks without a variable in the truth table hold 0.
+//! - Field data: `TimeValue` and the global variables
+//!
+//! Edge and face blocks, and node set and side set variables, are not
+//! exported.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use exodus_rs::vtk::{export_vtk, VtkOptions};
+//!
+//! // Displacements at every step, for ParaView
+//! let options = VtkOptions {
+//!     variables: Some(vec!["disp_x".into(), "disp_y".into(), "disp_z".into()]),
+//!     ..Default::default()
+//! };
+//! let summary = export_vtk("results.e", "results.pvd", &options)?;
+//! println!("{} files written", summary.files.len());
+//! # Ok::<(), exodus_rs::ExodusError>(())
+//! ```
+
+use crate::error::{ExodusError, Result};
+use crate::types::{Block, EntityType, Topology, TruthTable};
+use crate::{mode, ExodusFile};
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// `VTK_VERTEX`
+pub const VTK_VERTEX: u8 = 1;
+/// `VTK_LINE`
+pub const VTK_LINE: u8 = 3;
+/// `VTK_TRIANGLE`
+pub const VTK_TRIANGLE: u8 = 5;
+/// `VTK_QUAD`
+pub const VTK_QUAD: u8 = 9;
+/// `VTK_TETRA`
+pub const VTK_TETRA: u8 = 10;
+/// `VTK_HEXAHEDRON`
+pub const VTK_HEXAHEDRON: u8 = 12;
+/// `VTK_WEDGE`
+pub const VTK_WEDGE: u8 = 13;
+/// `VTK_PYRAMID`
+pub const VTK_PYRAMID: u8 = 14;
+/// `VTK_QUADRATIC_EDGE`
+pub const VTK_QUADRATIC_EDGE: u8 = 21;
+/// `VTK_QUADRATIC_TRIANGLE`
+pub const VTK_QUADRATIC_TRIANGLE: u8 = 22;
+/// `VTK_QUADRATIC_QUAD`
+pub const VTK_QUADRATIC_QUAD: u8 = 23;
+/// `VTK_QUADRATIC_TETRA`
+pub const VTK_QUADRATIC_TETRA: u8 = 24;
+/// `VTK_QUADRATIC_HEXAHEDRON`
+pub const VTK_QUADRATIC_HEXAHEDRON: u8 = 25;
+/// `VTK_QUADRATIC_WEDGE`
+pub const VTK_QUADRATIC_WEDGE: u8 = 26;
+/// `VTK_QUADRATIC_PYRAMID`
+pub const VTK_QUADRATIC_PYRAMID: u8 = 27;
+/// `VTK_BIQUADRATIC_QUAD`
+pub const VTK_BIQUADRATIC_QUAD: u8 = 28;
+/// `VTK_TRIQUADRATIC_HEXAHEDRON`
+pub const VTK_TRIQUADRATIC_HEXAHEDRON: u8 = 29;
+/// `VTK_BIQUADRATIC_QUADRATIC_WEDGE`
+pub const VTK_BIQUADRATIC_QUADRATIC_WEDGE: u8 = 32;
+/// `VTK_BIQUADRATIC_TRIANGLE`
+pub const VTK_BIQUADRATIC_TRIANGLE: u8 = 34;
+
+/// Values written per line of ASCII data
+const VALUES_PER_LINE: usize = 9;
+
+/// Nodes shared by Exodus and VTK, in the same order
+static IDENTITY: [usize; 27] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26,
+];
+
+/// Exodus numbers the vertical edges of a hex before the top edges, VTK after
+static HEX20_ORDER: [usize; 20] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 16, 17, 18, 19, 12, 13, 14, 15,
+];
+
+/// Exodus places the center node before the face nodes (-z, +z, -x, +x, -y,
+/// +y); VTK orders the faces -x, +x, -y, +y, -z, +z and ends with the center
+static HEX27_ORDER: [usize; 27] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 16, 17, 18, 19, 12, 13, 14, 15, 23, 24, 25, 26, 21, 22,
+    20,
+];
+
+/// As for hexes, the vertical edges of a wedge come before the top edges in
+/// Exodus
+static WEDGE15_ORDER: [usize; 15] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 13, 14, 9, 10, 11];
+
+/// [`WEDGE15_ORDER`] followed by the three quadrilateral face nodes
+static WEDGE18_ORDER: [usize; 18] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 13, 14, 9, 10, 11, 15, 16, 17];
+
+/// A VTK cell type and the order its nodes are taken from an Exodus element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VtkCell {
+    /// VTK cell type (`VTK_*`)
+    pub cell_type: u8,
+    /// For each VTK node, the 0-based position of the node in the Exodus
+    /// element's connectivity
+    pub node_order: &'static [usize],
+}
+
+/// The VTK cell an Exodus topology is written as
+///
+/// Topologies without a VTK equivalent are written as the nearest cell
+/// whose nodes they contain: `TET8` as a linear tetrahedron, `TET14` and
+/// `TET15` as quadratic tetrahedra, and `PYRAMID14` as a quadratic pyramid.
+/// Returns `None` for arbitrary polygons and polyhedra and for custom
+/// topologies.
+///
+/// # Examples
+///
+/// ```
+/// use exodus_rs::vtk::{vtk_cell, VTK_QUADRATIC_HEXAHEDRON};
+/// use exodus_rs::Topology;
+///
+/// let cell = vtk_cell(&Topology::Hex20).unwrap();
+/// assert_eq!(cell.cell_type, VTK_QUADRATIC_HEXAHEDRON);
+/// // VTK node 12 is the first top edge node, Exodus node 17 (1-based)
+/// assert_eq!(cell.node_order[12], 16);
+/// ```
+pub fn vtk_cell(topology: &Topology) -> Option<VtkCell> {
+    let (cell_type, node_order): (u8, &'static [usize]) = match topology {
+        Topology::Sphere => (VTK_VERTEX, &IDENTITY[..1]),
+        Topology::Bar2 => (VTK_LINE, &IDENTITY[..2]),
+        Topology::Bar3 => (VTK_QUADRATIC_EDGE, &IDENTITY[..3]),
+        Topology::Tri3 => (VTK_TRIANGLE, &IDENTITY[..3]),
+        Topology::Tri6 => (VTK_QUADRATIC_TRIANGLE, &IDENTITY[..6]),
+        Topology::Tri7 => (VTK_BIQUADRATIC_TRIANGLE, &IDENTITY[..7]),
+        Topology::Quad4 => (VTK_QUAD, &IDENTITY[..4]),
+        Topology::Quad8 => (VTK_QUADRATIC_QUAD, &IDENTITY[..8]),
+        Topology::Quad9 => (VTK_BIQUADRATIC_QUAD, &IDENTITY[..9]),
+        Topology::Tet4 | Topology::Tet8 => (VTK_TETRA, &IDENTITY[..4]),
+        Topology::Tet10 | Topology::Tet14 | Topology::Tet15 => {
+            (VTK_QUADRATIC_TETRA, &IDENTITY[..10])
+        }
+        Topology::Hex8 => (VTK_HEXAHEDRON, &IDENTITY[..8]),
+        Topology::Hex20 => (VTK_QUADRATIC_HEXAHEDRON, &HEX20_ORDER),
+        Topology::Hex27 => (VTK_TRIQUADRATIC_HEXAHEDRON, &HEX27_ORDER),
+        Topology::Wedge6 => (VTK_WEDGE, &IDENTITY[..6]),
+        Topology::Wedge15 => (VTK_QUADRATIC_WEDGE, &WEDGE15_ORDER),
+        Topology::Wedge18 => (VTK_BIQUADRATIC_QUADRATIC_WEDGE, &WEDGE18_ORDER),
+        Topology::Pyramid5 => (VTK_PYRAMID, &IDENTITY[..5]),
+        Topology::Pyramid13 | Topology::Pyramid14 => (VTK_QUADRATIC_PYRAMID, &IDENTITY[..13]),
+        Topology::NSided | Topology::NFaced | Topology::Custom(_) => return None,
+    };
+    Some(VtkCell {
+        cell_type,
+        node_order,
+    })
+}
+
+/// Options controlling what [`export_vtk`] writes
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VtkOptions {
+    /// Time steps to write (0-based), or `None` for all steps
+    pub steps: Option<Vec<usize>>,
+    /// Variables to write, or `None` for all variables. Names are matched
+    /// case-insensitively against global, nodal and element block variables.
+    pub variables: Option<Vec<String>>,
+}
+
+/// What [`export_vtk`] wrote
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VtkSummary {
+    /// Number of points in each file
+    pub num_points: usize,
+    /// Number of cells in each file
+    pub num_cells: usize,
+    /// Number of time steps written
+    pub num_time_steps: usize,
+    /// Files written, with the `.pvd` collection last
+    pub files: Vec<PathBuf>,
+}
+
+/// Export an Exodus file to VTK
+///
+/// The format is chosen by the extension of `output` (see the
+/// [module documentation](self)). Existing files are overwritten.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read, the output extension is
+/// not `.vtk`, `.vtu` or `.pvd`, a time step is out of range, a block's
+/// topology has no VTK cell, or a file cannot be written.
+pub fn export_vtk(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    options: &VtkOptions,
+) -> Result<VtkSummary> {
+    let file = ExodusFile::<mode::Read>::open(input)?;
+    write_vtk(&file, output, options)
+}
+
+/// Export an open Exodus file to VTK
+///
+/// See [`export_vtk`].
+pub fn write_vtk(
+    file: &ExodusFile<mode::Read>,
+    output: impl AsRef<Path>,
+    options: &VtkOptions,
+) -> Result<VtkSummary> {
+    let output = output.as_ref();
+    let format = Format::of(output)?;
+
+    let num_steps = file.num_time_steps()?;
+    let steps: Vec<usize> = match &options.steps {
+        Some(steps) => steps.clone(),
+        None => (0..num_steps).collect(),
+    };
+    if let Some(&step) = steps.iter().find(|&&step| step >= num_steps) {
+        return Err(ExodusError::InvalidTimeStep(step));
+    }
+    let times = file.times()?;
+
+    let mesh = read_mesh(file)?;
+    let variables = Variables::read(file, options.variables.as_deref())?;
+    let title = file.init_params()?.title;
+
+    // One file per step, or the output itself when there is a single step
+    let series = format == Format::Collection || steps.len() > 1;
+    let mut files = Vec::new();
+    let mut datasets = Vec::new();
+    if steps.is_empty() {
+        let path = match format {
+            Format::Collection => output.with_extension("vtu"),
+            _ => output.to_path_buf(),
+        };
+        format.write(&path, &title, &mesh, None)?;
+        datasets.push((0.0, path.clone()));
+        files.push(path);
+    }
+    for &step in &steps {
+        let path = if series {
+            step_path(output, format.extension(), step, num_steps)
+        } else {
+            output.to_path_buf()
+        };
+        let data = variables.read_step(file, &mesh, step, times[step])?;
+        format.write(&path, &title, &mesh, Some(&data))?;
+        datasets.push((times[step], path.clone()));
+        files.push(path);
+    }
+    if format == Format::Collection {
+        write_pvd(output, &datasets)?;
+        files.push(output.to_path_buf());
+    }
+
+    Ok(VtkSummary {
+        num_points: mesh.points.len(),
+        num_cells: mesh.cell_types.len(),
+        num_time_steps: steps.len(),
+        files,
+    })
+}
+
+/// Output format, from the output extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Legacy,
+    Xml,
+    Collection,
+}
+
+impl Format {
+    fn of(path: &Path) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("vtk") => Ok(Self::Legacy),
+            Some("vtu") => Ok(Self::Xml),
+            Some("pvd") => Ok(Self::Collection),
+            _ => Err(ExodusError::Other(format!(
+                "VTK output must end in .vtk, .vtu or .pvd: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Extension of the per-step files
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Legacy => "vtk",
+            Self::Xml | Self::Collection => "vtu",
+        }
+    }
+
+    fn write(self, path: &Path, title: &str, mesh: &Mesh, data: Option<&StepData>) -> Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        match self {
+            Self::Legacy => write_legacy(&mut out, title, mesh, data)?,
+            Self::Xml | Self::Collection => write_vtu(&mut out, mesh, data)?,
+        }
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// `<stem>_<step>.<extension>` next to `output`, with the 1-based step
+/// zero-padded to the width of the step count
+fn step_path(output: &Path, extension: &str, step: usize, num_steps: usize) -> PathBuf {
+    let stem = output
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let width = num_steps.to_string().len();
+    output.with_file_name(format!(
+        "{}_{:0width$}.{}",
+        stem,
+        step + 1,
+        extension,
+        width = width
+    ))
+}
+
+/// A named array of one value per point, cell or dataset
+#[derive(Debug, Clone)]
+struct Field<T> {
+    name: String,
+    values: Vec<T>,
+}
+
+/// The unstructured grid shared by all time steps
+#[derive(Debug)]
+struct Mesh {
+    points: Vec<[f64; 3]>,
+    /// 0-based point indices of all cells
+    connectivity: Vec<usize>,
+    /// End of each cell in `connectivity`
+    offsets: Vec<usize>,
+    cell_types: Vec<u8>,
+    /// Element blocks with the index of their first cell
+    blocks: Vec<(Block, usize)>,
+    point_fields: Vec<Field<i64>>,
+    cell_fields: Vec<Field<i64>>,
+}
+
+/// Values of one time step
+#[derive(Debug)]
+struct StepData {
+    fields: Vec<Field<f64>>,
+    point_data: Vec<Field<f64>>,
+    cell_data: Vec<Field<f64>>,
+}
+
+fn read_mesh(file: &ExodusFile<mode::Read>) -> Result<Mesh> {
+    let coords = file.coords::<f64>()?;
+    let points: Vec<[f64; 3]> = (0..coords.x.len())
+        .map(|i| {
+            [
+                coords.x[i],
+                coords.y.get(i).copied().unwrap_or(0.0),
+                coords.z.get(i).copied().unwrap_or(0.0),
+            ]
+        })
+        .collect();
+
+    let mut mesh = Mesh {
+        points,
+        connectivity: Vec::new(),
+        offsets: Vec::new(),
+        cell_types: Vec::new(),
+        blocks: Vec::new(),
+        point_fields: Vec::new(),
+        cell_fields: Vec::new(),
+    };
+    let mut block_ids = Vec::new();
+    for id in file.block_ids(EntityType::ElemBlock)? {
+        let block = file.block(id)?;
+        let topology = Topology::of_block(&block);
+        let cell = vtk_cell(&topology).ok_or_else(|| {
+            ExodusError::UnsupportedFeature(format!(
+                "Block {} has topology {}, which has no VTK cell",
+                id, block.topology
+            ))
+        })?;
+        if block.num_nodes_per_entry < cell.node_order.len() {
+            return Err(ExodusError::InvalidTopology(format!(
+                "Block {} is {} with {} nodes per element",
+                id, block.topology, block.num_nodes_per_entry
+            )));
+        }
+
+        let conn = file.connectivity(id)?;
+        let first_cell = mesh.cell_types.len();
+        for element in conn.chunks(block.num_nodes_per_entry.max(1)) {
+            for &i in cell.node_order {
+                mesh.connectivity
+                    .push(point_index(element[i], mesh.points.len())?);
+            }
+            mesh.offsets.push(mesh.connectivity.len());
+            mesh.cell_types.push(cell.cell_type);
+        }
+        block_ids.resize(mesh.cell_types.len(), id);
+        mesh.blocks.push((block, first_cell));
+    }
+    let num_points = mesh.points.len();
+    let num_cells = mesh.cell_types.len();
+
+    mesh.point_fields.push(Field {
+        name: "node_id".into(),
+        values: id_map(file, EntityType::NodeMap, num_points)?,
+    });
+    for id in file.set_ids(EntityType::NodeSet)? {
+        let nodes = file.node_set(id)?.nodes;
+        mesh.point_fields
+            .push(set_flags(format!("node_set_{}", id), &nodes, num_points)?);
+    }
+
+    mesh.cell_fields.push(Field {
+        name: "block_id".into(),
+        values: block_ids,
+    });
+    mesh.cell_fields.push(Field {
+        name: "element_id".into(),
+        values: id_map(file, EntityType::ElemMap, num_cells)?,
+    });
+    for id in file.set_ids(EntityType::SideSet)? {
+        let elements = file.side_set(id)?.elements;
+        mesh.cell_fields
+            .push(set_flags(format!("side_set_{}", id), &elements, num_cells)?);
+    }
+    for id in file.set_ids(EntityType::ElemSet)? {
+        let elements = file.entity_set(EntityType::ElemSet, id)?.entities;
+        mesh.cell_fields
+            .push(set_flags(format!("elem_set_{}", id), &elements, num_cells)?);
+    }
+    Ok(mesh)
+}
+
+/// 0-based index of a 1-based node number
+fn point_index(node: i64, num_points: usize) -> Result<usize> {
+    match usize::try_from(node) {
+        Ok(node) if (1..=num_points).contains(&node) => Ok(node - 1),
+        _ => Err(ExodusError::Other(format!(
+            "Node {} is outside the {} nodes of the mesh",
+            node, num_points
+        ))),
+    }
+}
+
+/// An ID map, or 1..=len when the file has none
+fn id_map(file: &ExodusFile<mode::Read>, map_type: EntityType, len: usize) -> Result<Vec<i64>> {
+    match file.id_map(map_type) {
+        Ok(ids) if ids.len() == len => Ok(ids),
+        Ok(_) | Err(ExodusError::VariableNotDefined(_)) => Ok((1..=len as i64).collect()),
+        Err(e) => Err(e),
+    }
+}
+
+/// 1 for the 1-based entries of a set, 0 elsewhere
+fn set_flags(name: String, entries: &[i64], len: usize) -> Result<Field<i64>> {
+    let mut values = vec![0; len];
+    for &entry in entries {
+        values[point_index(entry, len)?] = 1;
+    }
+    Ok(Field { name, values })
+}
+
+/// The variables selected for export, with their 0-based indices
+#[derive(Debug)]
+struct Variables {
+    global: Vec<(usize, String)>,
+    nodal: Vec<(usize, String)>,
+    elem: Vec<(usize, String)>,
+    truth_table: Option<TruthTable>,
+}
+
+impl Variables {
+    fn read(file: &ExodusFile<mode::Read>, selection: Option<&[String]>) -> Result<Self> {
+        let select = |var_type| -> Result<Vec<(usize, String)>> {
+            Ok(file
+                .variable_names(var_type)?
+                .into_iter()
+                .enumerate()
+                .filter(|(_, name)| {
+                    selection.map_or(true, |selection| {
+                        selection.iter().any(|s| s.eq_ignore_ascii_case(name))
+                    })
+                })
+                .collect())
+        };
+        let elem = select(EntityType::ElemBlock)?;
+        let truth_table = if elem.is_empty() {
+            None
+        } else {
+            Some(file.truth_table(EntityType::ElemBlock)?)
+        };
+        Ok(Self {
+            global: select(EntityType::Global)?,
+            nodal: select(EntityType::Nodal)?,
+            elem,
+            truth_table,
+        })
+    }
+
+    fn read_step(
+        &self,
+        file: &ExodusFile<mode::Read>,
+        mesh: &Mesh,
+        step: usize,
+        time: f64,
+    ) -> Result<StepData> {
+        let mut fields = vec![Field {
+            name: "TimeValue".into(),
+            values: vec![time],
+        }];
+        for (index, name) in &self.global {
+            fields.push(Field {
+                name: name.clone(),
+                values: file.var(step, EntityType::Global, 0, *index)?,
+            });
+        }
+
+        let mut point_data = Vec::new();
+        for (index, name) in &self.nodal {
+            point_data.push(Field {
+                name: name.clone(),
+                values: file.var(step, EntityType::Nodal, 0, *index)?,
+            });
+        }
+
+        let mut cell_data = Vec::new();
+        for (index, name) in &self.elem {
+            let mut values = vec![0.0; mesh.cell_types.len()];
+            for (block_index, (block, first)) in mesh.blocks.iter().enumerate() {
+                let defined = self
+                    .truth_table
+                    .as_ref()
+                    .map_or(true, |table| table.get(block_index, *index));
+                if defined {
+                    let block_values = file.var(step, EntityType::ElemBlock, block.id, *index)?;
+                    if block_values.len() != block.num_entries {
+                        return Err(ExodusError::InvalidArrayLength {
+                            expected: block.num_entries,
+                            actual: block_values.len(),
+                        });
+                    }
+                    values[*first..*first + block.num_entries].copy_from_slice(&block_values);
+                }
+            }
+            cell_data.push(Field {
+                name: name.clone(),
+                values,
+            });
+        }
+
+        Ok(StepData {
+            fields,
+            point_data,
+            cell_data,
+        })
+    }
+}
+
+/// Write values separated by spaces, several to a line
+fn write_values<T: Display>(out: &mut impl Write, values: &[T], indent: &str) -> Result<()> {
+    for chunk in values.chunks(VALUES_PER_LINE) {
+        write!(out, "{}", indent)?;
+        for (i, value) in chunk.iter().enumerate() {
+            if i > 0 {
+                write!(out, " ")?;
+            }
+            write!(out, "{}", value)?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Whether integer values fit in 32 bits
+fn fits_i32(values: &[i64]) -> bool {
+    values.iter().all(|&value| i32::try_from(value).is_ok())
+}
+
+/// Legacy array names cannot contain whitespace
+fn legacy_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .collect();
+    if name.is_empty() {
+        "unnamed".into()
+    } else {
+        name
+    }
+}
+
+fn write_legacy(
+    out: &mut impl Write,
+    title: &str,
+    mesh: &Mesh,
+    data: Option<&StepData>,
+) -> Result<()> {
+    // The title is a single line of at most 256 characters
+    let title: String = title
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take(255)
+        .collect();
+    writeln!(out, "# vtk DataFile Version 3.0")?;
+    writeln!(
+        out,
+        "{}",
+        if title.trim().is_empty() {
+            "Exodus mesh"
+        } else {
+            &title
+        }
+    )?;
+    writeln!(out, "ASCII")?;
+    writeln!(out, "DATASET UNSTRUCTURED_GRID")?;
+
+    if let Some(data) = data {
+        writeln!(out, "FIELD FieldData {}", data.fields.len())?;
+        for field in &data.fields {
+            writeln!(
+                out,
+                "{} 1 {} double",
+                legacy_name(&field.name),
+                field.values.len()
+            )?;
+            write_values(out, &field.values, "")?;
+        }
+    }
+
+    writeln!(out, "POINTS {} double", mesh.points.len())?;
+    for [x, y, z] in &mesh.points {
+        writeln!(out, "{} {} {}", x, y, z)?;
+    }
+
+    let num_cells = mesh.cell_types.len();
+    writeln!(
+        out,
+        "CELLS {} {}",
+        num_cells,
+        num_cells + mesh.connectivity.len()
+    )?;
+    let mut start = 0;
+    for &end in &mesh.offsets {
+        write!(out, "{}", end - start)?;
+        for point in &mesh.connectivity[start..end] {
+            write!(out, " {}", point)?;
+        }
+        writeln!(out)?;
+        start = end;
+    }
+    writeln!(out, "CELL_TYPES {}", num_cells)?;
+    write_values(out, &mesh.cell_types, "")?;
+
+    let empty = Vec::new();
+    let (point_data, cell_data) = match data {
+        Some(data) => (&data.point_data, &data.cell_data),
+        None => (&empty, &empty),
+    };
+    writeln!(out, "POINT_DATA {}", mesh.points.len())?;
+    write_legacy_arrays(out, &mesh.point_fields, point_data)?;
+    writeln!(out, "CELL_DATA {}", num_cells)?;
+    write_legacy_arrays(out, &mesh.cell_fields, cell_data)?;
+    Ok(())
+}
+
+fn write_legacy_arrays(
+    out: &mut impl Write,
+    ints: &[Field<i64>],
+    reals: &[Field<f64>],
+) -> Result<()> {
+    for field in ints {
+        let data_type = if fits_i32(&field.values) {
+            "int"
+        } else {
+            "vtktypeint64"
+        };
+        writeln!(out, "SCALARS {} {} 1", legacy_name(&field.name), data_type)?;
+        writeln!(out, "LOOKUP_TABLE default")?;
+        write_values(out, &field.values, "")?;
+    }
+    for field in reals {
+        writeln!(out, "SCALARS {} double 1", legacy_name(&field.name))?;
+        writeln!(out, "LOOKUP_TABLE default")?;
+        write_values(out, &field.values, "")?;
+    }
+    Ok(())
+}
+
+/// Escape text for an XML attribute
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Write one `<DataArray>` of ASCII values
+fn write_data_array<T: Display>(
+    out: &mut impl Write,
+    data_type: &str,
+    name: &str,
+    components: usize,
+    values: &[T],
+    indent: &str,
+) -> Result<()> {
+    write!(out, "{}<DataArray type=\"{}\"", indent, data_type)?;
+    if !name.is_empty() {
+        write!(out, " Name=\"{}\"", xml_escape(name))?;
+    }
+    if components > 1 {
+        write!(out, " NumberOfComponents=\"{}\"", components)?;
+    }
+    writeln!(out, " format=\"ascii\">")?;
+    write_values(out, values, &format!("{}  ", indent))?;
+    writeln!(out, "{}</DataArray>", indent)?;
+    Ok(())
+}
+
+fn write_xml_arrays(
+    out: &mut impl Write,
+    ints: &[Field<i64>],
+    reals: &[Field<f64>],
+    indent: &str,
+) -> Result<()> {
+    for field in ints {
+        let data_type = if fits_i32(&field.values) {
+            "Int32"
+        } else {
+            "Int64"
+        };
+        write_data_array(out, data_type, &field.name, 1, &field.values, indent)?;
+    }
+    for field in reals {
+        write_data_array(out, "Float64", &field.name, 1, &field.values, indent)?;
+    }
+    Ok(())
+}
+
+fn write_vtu(out: &mut impl Write, mesh: &Mesh, data: Option<&StepData>) -> Result<()> {
+    writeln!(out, "<?xml version=\"1.0\"?>")?;
+    writeln!(
+        out,
+        "<VTKFile type=\"UnstructuredGrid\" version=\"0.1\" byte_order=\"LittleEndian\">"
+    )?;
+    writeln!(out, "  <UnstructuredGrid>")?;
+    if let Some(data) = data {
+        writeln!(out, "    <FieldData>")?;
+        for field in &data.fields {
+            writeln!(
+                out,
+                "      <DataArray type=\"Float64\" Name=\"{}\" NumberOfTuples=\"{}\" format=\"ascii\">",
+                xml_escape(&field.name),
+                field.values.len()
+            )?;
+            write_values(out, &field.values, "        ")?;
+            writeln!(out, "      </DataArray>")?;
+        }
+        writeln!(out, "    </FieldData>")?;
+    }
+
+    writeln!(
+        out,
+        "    <Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">",
+        mesh.points.len(),
+        mesh.cell_types.len()
+    )?;
+    let empty = Vec::new();
+    let (point_data, cell_data) = match data {
+        Some(data) => (&data.point_data, &data.cell_data),
+        None => (&empty, &empty),
+    };
+    writeln!(out, "      <PointData>")?;
+    write_xml_arrays(out, &mesh.point_fields, point_data, "        ")?;
+    writeln!(out, "      </PointData>")?;
+    writeln!(out, "      <CellData>")?;
+    write_xml_arrays(out, &mesh.cell_fields, cell_data, "        ")?;
+    writeln!(out, "      </CellData>")?;
+
+    writeln!(out, "      <Points>")?;
+    let coords: Vec<f64> = mesh.points.iter().flatten().copied().collect();
+    write_data_array(out, "Float64", "Points", 3, &coords, "        ")?;
+    writeln!(out, "      </Points>")?;
+    writeln!(out, "      <Cells>")?;
+    write_data_array(
+        out,
+        "Int64",
+        "connectivity",
+        1,
+        &mesh.connectivity,
+        "        ",
+    )?;
+    write_data_array(out, "Int64", "offsets", 1, &mesh.offsets, "        ")?;
+    write_data_array(out, "UInt8", "types", 1, &mesh.cell_types, "        ")?;
+    writeln!(out, "      </Cells>")?;
+    writeln!(out, "    </Piece>")?;
+    writeln!(out, "  </UnstructuredGrid>")?;
+    writeln!(out, "</VTKFile>")?;
+    Ok(())
+}
+
+/// Write a `.pvd` collection of `(time, path)` datasets, referenced by file
+/// name relative to the collection
+fn write_pvd(path: &Path, datasets: &[(f64, PathBuf)]) -> Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "<?xml version=\"1.0\"?>")?;
+    writeln!(
+        out,
+        "<VTKFile type=\"Collection\" version=\"0.1\" byte_order=\"LittleEndian\">"
+    )?;
+    writeln!(out, "  <Collection>")?;
+    for (time, dataset) in datasets {
+        let name = dataset
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "    <DataSet timestep=\"{}\" group=\"\" part=\"0\" file=\"{}\"/>",
+            time,
+            xml_escape(&name)
+        )?;
+    }
+    writeln!(out, "  </Collection>")?;
+    writeln!(out, "</VTKFile>")?;
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Results file from the SEACAS script tests: five HEX8 blocks, node
+    /// and side sets, and global, nodal and element variables
+    fn results_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../packages/seacas/scripts/tests/exomerge_unit_test.e")
+    }
+
+    #[test]
+    fn test_node_orders_are_permutations() {
+        let topologies = [
+            Topology::Sphere,
+            Topology::Bar3,
+            Topology::Tri7,
+            Topology::Quad9,
+            Topology::Tet15,
+            Topology::Hex20,
+            Topology::Hex27,
+            Topology::Wedge15,
+            Topology::Wedge18,
+            Topology::Pyramid14,
+        ];
+        for topology in topologies {
+            let cell = vtk_cell(&topology).unwrap();
+            let mut order = cell.node_order.to_vec();
+            order.sort_unstable();
+            assert_eq!(order, IDENTITY[..order.len()], "{}", topology.as_str());
+            assert!(order.len() <= topology.expected_nodes().unwrap());
+        }
+        assert_eq!(vtk_cell(&Topology::NSided), None);
+        assert_eq!(WEDGE15_ORDER[9..12], [12, 13, 14]);
+        assert_eq!(HEX27_ORDER[26], 20);
+    }
+
+    #[test]
+    fn test_export_collection() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("results.pvd");
+        let options = VtkOptions {
+            steps: Some(vec![0, 4]),
+            variables: Some(vec!["DISP_X".into(), "pressure".into(), "time".into()]),
+        };
+        let summary = export_vtk(results_path(), &output, &options).unwrap();
+        assert_eq!(summary.num_points, 2185);
+        assert_eq!(summary.num_cells, 1300);
+        assert_eq!(summary.num_time_steps, 2);
+        assert_eq!(
+            summary.files,
+            vec![
+                dir.path().join("results_1.vtu"),
+                dir.path().join("results_5.vtu"),
+                output.clone()
+            ]
+        );
+
+        let pvd = std::fs::read_to_string(&output).unwrap();
+        assert_eq!(pvd.matches("<DataSet ").count(), 2);
+        assert!(pvd.contains("file=\"results_5.vtu\""));
+
+        let vtu = std::fs::read_to_string(&summary.files[1]).unwrap();
+        assert!(vtu.contains("NumberOfPoints=\"2185\" NumberOfCells=\"1300\""));
+        for name in [
+            "disp_x",
+            "pressure",
+            "time",
+            "TimeValue",
+            "block_id",
+            "side_set_1",
+        ] {
+            assert!(vtu.contains(&format!("Name=\"{}\"", name)), "{}", name);
+        }
+        assert!(!vtu.contains("Name=\"disp_y\""));
+        // Every cell is a hexahedron
+        let types = vtu.split("Name=\"types\"").nth(1).unwrap();
+        let types = &types[types.find('>').unwrap() + 1..types.find("</").unwrap()];
+        assert_eq!(types.split_whitespace().count(), 1300);
+        assert!(types.split_whitespace().all(|t| t == "12"));
+    }
+
+    #[test]
+    fn test_export_legacy() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("mesh.vtk");
+        let options = VtkOptions {
+            steps: Some(vec![2]),
+            variables: Some(vec!["disp_z".into()]),
+        };
+        let summary = export_vtk(results_path(), &output, &options).unwrap();
+        assert_eq!(summary.files, vec![output.clone()]);
+
+        let vtk = std::fs::read_to_string(&output).unwrap();
+        let lines: Vec<&str> = vtk.lines().collect();
+        assert_eq!(lines[0], "# vtk DataFile Version 3.0");
+        assert_eq!(lines[2], "ASCII");
+        assert!(vtk.contains("POINTS 2185 double"));
+        assert!(vtk.contains(&format!("CELLS 1300 {}", 1300 * 9)));
+        assert!(vtk.contains("SCALARS disp_z double 1"));
+        assert!(vtk.contains("SCALARS node_set_20 int 1"));
+
+        assert!(matches!(
+            export_vtk(results_path(), dir.path().join("mesh.vtp"), &options),
+            Err(ExodusError::Other(_))
+        ));
+        let options = VtkOptions {
+            steps: Some(vec![5]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            export_vtk(results_path(), &output, &options),
+            Err(ExodusError::InvalidTimeStep(5))
+        ));
+    }
+}
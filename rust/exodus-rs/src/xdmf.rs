@@ -0,0 +1,895 @@
+//! Exporting meshes and results to XDMF with HDF5 heavy data.
+//!
+//! [`export_xdmf`] writes the element blocks of an Exodus file and their
+//! results for ParaView and VisIt, which stream the heavy data instead of
+//! holding every step in memory as with VTU. Two files are written:
+//!
+//! - `<stem>.h5`: the heavy arrays, as datasets at the root of an HDF5 file.
+//!   The file is written through the NetCDF-4 library, so it is also a
+//!   NetCDF-4 file, and its datasets are chunked by the node, element and
+//!   time chunk sizes of [`XdmfOptions::performance`].
+//! - the `.xmf` or `.xdmf` output: the XDMF 3 index, a temporal collection
+//!   with one spatial collection per time step, holding one uniform grid per
+//!   element block
+//!
+//! The datasets follow the Exodus variable names:
+//!
+//! - `coordinates`: node coordinates, one row per node
+//! - `node_id`: the node ID map
+//! - `connect<b>`: 0-based node indices of block `b` (1-based, in file
+//!   order), in XDMF node order
+//! - `elem_id<b>`: the element IDs of block `b`
+//! - `time_whole`: the time of each step written
+//! - `vals_glo_var<v>`, `vals_nod_var<v>` and `vals_elem_var<v>eb<b>`: the
+//!   selected global, nodal and element block variables, one row per step
+//!   written. Each step's grid selects its row with a hyperslab.
+//!
+//! Variables are read in batches of steps with
+//! [`ExodusFile::var_time_series`], at most one time chunk at a time, so the
+//! memory used does not grow with the number of steps. Blocks without a
+//! variable in the truth table have no dataset or attribute for it.
+//!
+//! Sets, edge and face blocks, and set variables are not exported.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use exodus_rs::xdmf::{export_xdmf, XdmfOptions};
+//! use exodus_rs::performance::PerformanceConfig;
+//!
+//! // Every tenth step, chunked ten steps deep for time-history plots
+//! let options = XdmfOptions {
+//!     steps: Some((0..1000).step_by(10).collect()),
+//!     performance: Some(PerformanceConfig::auto().with_time_chunk_size(10)),
+//!     ..Default::default()
+//! };
+//! let summary = export_xdmf("results.e", "results.xmf", &options)?;
+//! println!("Heavy data in {}", summary.heavy_data.display());
+//! # Ok::<(), exodus_rs::ExodusError>(())
+//! ```
+
+use crate::error::{EntityId, ExodusError, Result};
+use crate::mesh::MeshData;
+use crate::performance::PerformanceConfig;
+use crate::types::{Block, EntityType, Topology, TruthTable};
+use crate::utils::nc_sys;
+use crate::vtk::{self, vtk_cell};
+use crate::{mode, ExodusFile};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Options controlling what [`export_xdmf`] writes
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XdmfOptions {
+    /// Time steps to write (0-based), or `None` for all steps
+    pub steps: Option<Vec<usize>>,
+    /// Variables to write, or `None` for all variables. Names are matched
+    /// case-insensitively against global, nodal and element block variables.
+    pub variables: Option<Vec<String>>,
+    /// Chunk sizes and cache settings of the HDF5 file, or `None` for
+    /// [`PerformanceConfig::auto`]
+    pub performance: Option<PerformanceConfig>,
+}
+
+/// What [`export_xdmf`] wrote
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XdmfSummary {
+    /// Number of nodes
+    pub num_nodes: usize,
+    /// Number of elements
+    pub num_elems: usize,
+    /// Number of time steps written
+    pub num_time_steps: usize,
+    /// The HDF5 file holding the heavy data
+    pub heavy_data: PathBuf,
+}
+
+/// The XDMF topology an Exodus topology is written as, with the order its
+/// nodes are taken from an Exodus element
+///
+/// XDMF orders the nodes of its elements as VTK does, so the order is that
+/// of [`vtk_cell`]. `TRI7` is written as `Tri_6`, without its center node.
+/// Returns `None` for topologies without an XDMF element.
+///
+/// # Examples
+///
+/// ```
+/// use exodus_rs::xdmf::xdmf_topology;
+/// use exodus_rs::Topology;
+///
+/// let (name, order) = xdmf_topology(&Topology::Hex20).unwrap();
+/// assert_eq!(name, "Hex_20");
+/// assert_eq!(order[12], 16);
+/// ```
+pub fn xdmf_topology(topology: &Topology) -> Option<(&'static str, &'static [usize])> {
+    let cell = vtk_cell(topology)?;
+    let name = match cell.cell_type {
+        vtk::VTK_VERTEX => "Polyvertex",
+        vtk::VTK_LINE => "Polyline",
+        vtk::VTK_QUADRATIC_EDGE => "Edge_3",
+        vtk::VTK_TRIANGLE => "Triangle",
+        vtk::VTK_QUADRATIC_TRIANGLE | vtk::VTK_BIQUADRATIC_TRIANGLE => {
+            return Some(("Tri_6", &cell.node_order[..6]))
+        }
+        vtk::VTK_QUAD => "Quadrilateral",
+        vtk::VTK_QUADRATIC_QUAD => "Quad_8",
+        vtk::VTK_BIQUADRATIC_QUAD => "Quad_9",
+        vtk::VTK_TETRA => "Tetrahedron",
+        vtk::VTK_QUADRATIC_TETRA => "Tet_10",
+        vtk::VTK_PYRAMID => "Pyramid",
+        vtk::VTK_QUADRATIC_PYRAMID => "Pyramid_13",
+        vtk::VTK_WEDGE => "Wedge",
+        vtk::VTK_QUADRATIC_WEDGE => "Wedge_15",
+        vtk::VTK_BIQUADRATIC_QUADRATIC_WEDGE => "Wedge_18",
+        vtk::VTK_HEXAHEDRON => "Hexahedron",
+        vtk::VTK_QUADRATIC_HEXAHEDRON => "Hex_20",
+        vtk::VTK_TRIQUADRATIC_HEXAHEDRON => "Hex_27",
+        _ => return None,
+    };
+    Some((name, cell.node_order))
+}
+
+/// Export an Exodus file to XDMF and HDF5
+///
+/// The index is written to `output`, which must end in `.xmf` or `.xdmf`,
+/// and the heavy data next to it with the extension `.h5` (see the
+/// [module documentation](self)). Existing files are overwritten.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read, the output extension is
+/// not `.xmf` or `.xdmf`, a time step is out of range, a block's topology
+/// has no XDMF element, or a file cannot be written.
+pub fn export_xdmf(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    options: &XdmfOptions,
+) -> Result<XdmfSummary> {
+    let file = ExodusFile::<mode::Read>::open(input)?;
+    write_xdmf(&file, output, options)
+}
+
+/// Export an open Exodus file to XDMF and HDF5
+///
+/// See [`export_xdmf`].
+pub fn write_xdmf(
+    file: &ExodusFile<mode::Read>,
+    output: impl AsRef<Path>,
+    options: &XdmfOptions,
+) -> Result<XdmfSummary> {
+    let output = output.as_ref();
+    let extension = output
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+    if !matches!(extension.as_deref(), Some("xmf" | "xdmf")) {
+        return Err(ExodusError::Other(format!(
+            "XDMF output must end in .xmf or .xdmf: {}",
+            output.display()
+        )));
+    }
+
+    let num_steps = file.num_time_steps()?;
+    let steps: Vec<usize> = match &options.steps {
+        Some(steps) => steps.clone(),
+        None => (0..num_steps).collect(),
+    };
+    if let Some(&step) = steps.iter().find(|&&step| step >= num_steps) {
+        return Err(ExodusError::InvalidTimeStep(step));
+    }
+    let times = file.times()?;
+
+    let performance = options
+        .performance
+        .clone()
+        .unwrap_or_else(PerformanceConfig::auto);
+
+    let heavy_data = output.with_extension("h5");
+    let nc_file = netcdf::create_with(&heavy_data, netcdf::Options::NETCDF4)?;
+    let mut writer = HeavyData {
+        ncid: nc_sys::ncid(&nc_file),
+        nc_file,
+        file_name: heavy_data
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        chunks: performance.chunks.clone(),
+        cache: performance.cache.clone(),
+    };
+    let mesh = writer.write_mesh(file)?;
+    let step_times: Vec<f64> = steps.iter().map(|&step| times[step]).collect();
+    if !steps.is_empty() {
+        writer.nc_file.add_dimension("time_step", steps.len())?;
+        let mut var = writer
+            .nc_file
+            .add_variable::<f64>("time_whole", &["time_step"])?;
+        var.put_values(&step_times, ..)?;
+    }
+    let variables = writer.write_variables(file, &mesh, &steps, options.variables.as_deref())?;
+    drop(writer);
+
+    let title = file.init_params()?.title;
+    let mut out = BufWriter::new(File::create(output)?);
+    write_index(&mut out, &title, &mesh, &variables, &step_times)?;
+    out.flush()?;
+
+    Ok(XdmfSummary {
+        num_nodes: mesh.num_nodes,
+        num_elems: mesh
+            .blocks
+            .iter()
+            .map(|block| block.block.num_entries)
+            .sum(),
+        num_time_steps: steps.len(),
+        heavy_data,
+    })
+}
+
+/// A dataset of the HDF5 file, as referenced from the index
+#[derive(Debug, Clone)]
+struct Dataset {
+    /// `<file>:/<name>`
+    path: String,
+    dimensions: Vec<usize>,
+    number_type: &'static str,
+}
+
+/// An element block written to the HDF5 file
+#[derive(Debug)]
+struct MeshBlock {
+    block: Block,
+    name: String,
+    topology: &'static str,
+    connectivity: Dataset,
+    elem_ids: Dataset,
+}
+
+/// The mesh written to the HDF5 file
+#[derive(Debug)]
+struct Mesh {
+    num_nodes: usize,
+    geometry_type: &'static str,
+    coordinates: Dataset,
+    node_ids: Dataset,
+    blocks: Vec<MeshBlock>,
+}
+
+/// Where an attribute is centered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Center {
+    Grid,
+    Node,
+    Cell,
+}
+
+/// A variable written to the HDF5 file, one row per step
+#[derive(Debug)]
+struct Variable {
+    name: String,
+    center: Center,
+    /// The block the variable is written for, for element block variables
+    block_id: Option<EntityId>,
+    dataset: Dataset,
+}
+
+/// The HDF5 file being written
+struct HeavyData {
+    nc_file: netcdf::FileMut,
+    /// NetCDF id of `nc_file`, for settings the `netcdf` crate lacks
+    ncid: std::os::raw::c_int,
+    file_name: String,
+    chunks: crate::performance::ChunkConfig,
+    cache: crate::performance::CacheConfig,
+}
+
+impl HeavyData {
+    fn dataset(&self, name: &str, dimensions: Vec<usize>, number_type: &'static str) -> Dataset {
+        Dataset {
+            path: format!("{}:/{}", self.file_name, name),
+            dimensions,
+            number_type,
+        }
+    }
+
+    /// Chunk a dataset along its first dimension, keeping the others whole
+    ///
+    /// A requested size of 0 leaves the library default, and sizes are
+    /// clamped to the dimension to avoid `NC_EBADCHUNK`.
+    fn set_chunking(
+        var: &mut netcdf::VariableMut<'_>,
+        requested: usize,
+        dimensions: &[usize],
+    ) -> Result<()> {
+        if requested == 0 || dimensions.contains(&0) {
+            return Ok(());
+        }
+        let mut chunks = dimensions.to_vec();
+        chunks[0] = requested.min(dimensions[0]);
+        var.set_chunking(&chunks)?;
+        Ok(())
+    }
+
+    fn write_mesh(&mut self, file: &ExodusFile<mode::Read>) -> Result<Mesh> {
+        let mesh = MeshData::read(file)?;
+        let num_nodes = mesh.num_nodes();
+        let num_dim = mesh.num_dim.clamp(2, 3);
+        let mut xyz = Vec::with_capacity(num_nodes * num_dim);
+        for point in mesh.points() {
+            xyz.extend_from_slice(&point[..num_dim]);
+        }
+        self.nc_file.add_dimension("num_nodes", num_nodes)?;
+        self.nc_file.add_dimension("num_dim", num_dim)?;
+        let mut var = self
+            .nc_file
+            .add_variable::<f64>("coordinates", &["num_nodes", "num_dim"])?;
+        Self::set_chunking(&mut var, self.chunks.node_chunk_size, &[num_nodes, num_dim])?;
+        var.put_values(&xyz, ..)?;
+        let coordinates = self.dataset("coordinates", vec![num_nodes, num_dim], "Float");
+
+        let mut var = self
+            .nc_file
+            .add_variable::<i64>("node_id", &["num_nodes"])?;
+        Self::set_chunking(&mut var, self.chunks.node_chunk_size, &[num_nodes])?;
+        var.put_values(&mesh.node_ids, ..)?;
+        let node_ids = self.dataset("node_id", vec![num_nodes], "Int");
+
+        let names = match file.names(EntityType::ElemBlock) {
+            Ok(names) => names,
+            Err(ExodusError::VariableNotDefined(_)) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        let mut blocks = Vec::new();
+        for (index, (block, conn)) in mesh.blocks.iter().zip(&mesh.connectivity).enumerate() {
+            let id = block.id;
+            let (topology, node_order) =
+                xdmf_topology(&Topology::of_block(block)).ok_or_else(|| {
+                    ExodusError::UnsupportedFeature(format!(
+                        "Block {} has topology {}, which has no XDMF element",
+                        id, block.topology
+                    ))
+                })?;
+            if block.num_nodes_per_entry < node_order.len() {
+                return Err(ExodusError::InvalidTopology(format!(
+                    "Block {} is {} with {} nodes per element",
+                    id, block.topology, block.num_nodes_per_entry
+                )));
+            }
+
+            let mut indices = Vec::with_capacity(block.num_entries * node_order.len());
+            for element in conn.chunks(block.num_nodes_per_entry.max(1)) {
+                for &i in node_order {
+                    indices.push(node_index(element[i], num_nodes)?);
+                }
+            }
+            let entries_dim = format!("num_el_in_blk{}", index + 1);
+            let nodes_dim = format!("num_nod_per_el{}", index + 1);
+            self.nc_file
+                .add_dimension(&entries_dim, block.num_entries)?;
+            self.nc_file.add_dimension(&nodes_dim, node_order.len())?;
+            let dimensions = vec![block.num_entries, node_order.len()];
+            let conn_name = format!("connect{}", index + 1);
+            let mut var = self
+                .nc_file
+                .add_variable::<i64>(&conn_name, &[&entries_dim, &nodes_dim])?;
+            Self::set_chunking(&mut var, self.chunks.element_chunk_size, &dimensions)?;
+            var.put_values(&indices, ..)?;
+
+            let ids_name = format!("elem_id{}", index + 1);
+            let first_elem = mesh.offsets[index];
+            let ids = &mesh.elem_ids[first_elem..first_elem + block.num_entries];
+            let mut var = self
+                .nc_file
+                .add_variable::<i64>(&ids_name, &[&entries_dim])?;
+            Self::set_chunking(&mut var, self.chunks.element_chunk_size, &dimensions[..1])?;
+            var.put_values(ids, ..)?;
+
+            let name = names
+                .get(index)
+                .filter(|name| !name.is_empty())
+                .cloned()
+                .unwrap_or_else(|| format!("block_{}", id));
+            blocks.push(MeshBlock {
+                name,
+                topology,
+                connectivity: self.dataset(&conn_name, dimensions, "Int"),
+                elem_ids: self.dataset(&ids_name, vec![block.num_entries], "Int"),
+                block: block.clone(),
+            });
+        }
+
+        Ok(Mesh {
+            num_nodes,
+            geometry_type: if num_dim == 3 { "XYZ" } else { "XY" },
+            coordinates,
+            node_ids,
+            blocks,
+        })
+    }
+
+    fn write_variables(
+        &mut self,
+        file: &ExodusFile<mode::Read>,
+        mesh: &Mesh,
+        steps: &[usize],
+        selection: Option<&[String]>,
+    ) -> Result<Vec<Variable>> {
+        let mut variables = Vec::new();
+        if steps.is_empty() {
+            return Ok(variables);
+        }
+        let select = |var_type| -> Result<Vec<(usize, String)>> {
+            Ok(file
+                .variable_names(var_type)?
+                .into_iter()
+                .enumerate()
+                .filter(|(_, name)| {
+                    selection.map_or(true, |selection| {
+                        selection.iter().any(|s| s.eq_ignore_ascii_case(name))
+                    })
+                })
+                .collect())
+        };
+
+        for (index, name) in select(EntityType::Global)? {
+            let dataset = format!("vals_glo_var{}", index + 1);
+            let series = Series {
+                var_type: EntityType::Global,
+                entity_id: 0,
+                index,
+                width: 1,
+            };
+            let dataset = self.write_series(file, &dataset, &[], series, steps)?;
+            variables.push(Variable {
+                name,
+                center: Center::Grid,
+                block_id: None,
+                dataset,
+            });
+        }
+
+        for (index, name) in select(EntityType::Nodal)? {
+            let dataset = format!("vals_nod_var{}", index + 1);
+            let series = Series {
+                var_type: EntityType::Nodal,
+                entity_id: 0,
+                index,
+                width: mesh.num_nodes,
+            };
+            let dataset = self.write_series(file, &dataset, &["num_nodes"], series, steps)?;
+            variables.push(Variable {
+                name,
+                center: Center::Node,
+                block_id: None,
+                dataset,
+            });
+        }
+
+        let elem = select(EntityType::ElemBlock)?;
+        let truth_table: Option<TruthTable> = if elem.is_empty() {
+            None
+        } else {
+            Some(file.truth_table(EntityType::ElemBlock)?)
+        };
+        for (index, name) in elem {
+            for (block_index, block) in mesh.blocks.iter().enumerate() {
+                let defined = truth_table
+                    .as_ref()
+                    .map_or(true, |table| table.get(block_index, index));
+                if !defined {
+                    continue;
+                }
+                let dataset = format!("vals_elem_var{}eb{}", index + 1, block_index + 1);
+                let entries_dim = format!("num_el_in_blk{}", block_index + 1);
+                let series = Series {
+                    var_type: EntityType::ElemBlock,
+                    entity_id: block.block.id,
+                    index,
+                    width: block.block.num_entries,
+                };
+                let dataset = self.write_series(file, &dataset, &[&entries_dim], series, steps)?;
+                variables.push(Variable {
+                    name: name.clone(),
+                    center: Center::Cell,
+                    block_id: Some(block.block.id),
+                    dataset,
+                });
+            }
+        }
+        Ok(variables)
+    }
+
+    /// Write the selected steps of a variable as the rows of a dataset
+    ///
+    /// Steps are read with [`ExodusFile::var_time_series`] in runs no
+    /// longer than the time chunk size (one step when it is 0), so no more
+    /// than one time chunk of a variable is held in memory.
+    fn write_series(
+        &mut self,
+        file: &ExodusFile<mode::Read>,
+        name: &str,
+        entity_dims: &[&str],
+        series: Series,
+        steps: &[usize],
+    ) -> Result<Dataset> {
+        let mut dims = vec!["time_step"];
+        dims.extend_from_slice(entity_dims);
+        let mut dimensions = vec![steps.len()];
+        if !entity_dims.is_empty() {
+            dimensions.push(series.width);
+        }
+        let run_length = self.chunks.time_chunk_size.max(1);
+
+        let mut var = self.nc_file.add_variable::<f64>(name, &dims)?;
+        // A time chunk of steps by a node or element chunk of entities
+        let mut chunks = vec![run_length.min(steps.len())];
+        if dimensions.len() == 2 {
+            let spatial = if series.var_type == EntityType::Nodal {
+                self.chunks.node_chunk_size
+            } else {
+                self.chunks.element_chunk_size
+            };
+            chunks.push(if spatial > 0 {
+                spatial.min(series.width)
+            } else {
+                series.width
+            });
+        }
+        if !chunks.contains(&0) {
+            var.set_chunking(&chunks)?;
+        }
+        nc_sys::set_var_chunk_cache(self.ncid, name, &self.cache)?;
+
+        let mut row = 0;
+        while row < steps.len() {
+            // Increasing selected steps within one run
+            let first = steps[row];
+            let mut end = row + 1;
+            while end < steps.len()
+                && steps[end] > steps[end - 1]
+                && steps[end] < first + run_length
+            {
+                end += 1;
+            }
+            let last = steps[end - 1];
+            let values = file.var_time_series(
+                first,
+                last + 1,
+                series.var_type,
+                series.entity_id,
+                series.index,
+            )?;
+            let expected = (last + 1 - first) * series.width;
+            if values.len() != expected {
+                return Err(ExodusError::InvalidArrayLength {
+                    expected,
+                    actual: values.len(),
+                });
+            }
+            let mut rows = Vec::with_capacity((end - row) * series.width);
+            for &step in &steps[row..end] {
+                let start = (step - first) * series.width;
+                rows.extend_from_slice(&values[start..start + series.width]);
+            }
+            if dimensions.len() == 2 {
+                var.put_values(&rows, (row..end, ..))?;
+            } else {
+                var.put_values(&rows, row..end)?;
+            }
+            row = end;
+        }
+
+        Ok(self.dataset(name, dimensions, "Float"))
+    }
+}
+
+/// Which variable [`HeavyData::write_series`] reads
+#[derive(Debug, Clone, Copy)]
+struct Series {
+    var_type: EntityType,
+    entity_id: EntityId,
+    index: usize,
+    /// Values per step
+    width: usize,
+}
+
+/// 0-based index of a 1-based node number
+fn node_index(node: i64, num_nodes: usize) -> Result<i64> {
+    match usize::try_from(node) {
+        Ok(n) if (1..=num_nodes).contains(&n) => Ok(node - 1),
+        _ => Err(ExodusError::Other(format!(
+            "Node {} is outside the {} nodes of the mesh",
+            node, num_nodes
+        ))),
+    }
+}
+
+/// Escape text for XML
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn dimensions(dimensions: &[usize]) -> String {
+    dimensions
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A `<DataItem>` reading a whole dataset
+fn data_item(dataset: &Dataset, indent: &str) -> String {
+    format!(
+        "{}<DataItem Dimensions=\"{}\" NumberType=\"{}\" Precision=\"8\" Format=\"HDF\">{}</DataItem>\n",
+        indent,
+        dimensions(&dataset.dimensions),
+        dataset.number_type,
+        xml_escape(&dataset.path)
+    )
+}
+
+/// A `<DataItem>` reading one row of a dataset
+fn row_item(dataset: &Dataset, row: usize, indent: &str) -> String {
+    let mut item = String::new();
+    let mut shape = dataset.dimensions.clone();
+    shape[0] = 1;
+    let rank = shape.len();
+    let _ = writeln!(
+        item,
+        "{}<DataItem ItemType=\"HyperSlab\" Dimensions=\"{}\" Type=\"HyperSlab\">",
+        indent,
+        dimensions(&shape)
+    );
+    // Start, stride and count of each dimension
+    let mut start = vec![0; rank];
+    start[0] = row;
+    let _ = writeln!(
+        item,
+        "{}  <DataItem Dimensions=\"3 {}\" Format=\"XML\">{} {} {}</DataItem>",
+        indent,
+        rank,
+        dimensions(&start),
+        dimensions(&vec![1; rank]),
+        dimensions(&shape)
+    );
+    item.push_str(&data_item(dataset, &format!("{}  ", indent)));
+    let _ = writeln!(item, "{}</DataItem>", indent);
+    item
+}
+
+fn write_attribute(
+    out: &mut impl Write,
+    name: &str,
+    center: Center,
+    item: &str,
+    indent: &str,
+) -> Result<()> {
+    let center = match center {
+        Center::Grid => "Grid",
+        Center::Node => "Node",
+        Center::Cell => "Cell",
+    };
+    writeln!(
+        out,
+        "{}<Attribute Name=\"{}\" AttributeType=\"Scalar\" Center=\"{}\">",
+        indent,
+        xml_escape(name),
+        center
+    )?;
+    write!(out, "{}", item)?;
+    writeln!(out, "{}</Attribute>", indent)?;
+    Ok(())
+}
+
+/// Write the spatial collection of the blocks at one step, the row of each
+/// variable's dataset, or no variables when `step` is `None`
+fn write_step(
+    out: &mut impl Write,
+    mesh: &Mesh,
+    variables: &[Variable],
+    step: Option<(usize, f64)>,
+    indent: &str,
+) -> Result<()> {
+    let name = match step {
+        Some((row, _)) => format!("step_{}", row + 1),
+        None => "mesh".to_string(),
+    };
+    writeln!(
+        out,
+        "{}<Grid Name=\"{}\" GridType=\"Collection\" CollectionType=\"Spatial\">",
+        indent, name
+    )?;
+    if let Some((_, time)) = step {
+        writeln!(out, "{}  <Time Value=\"{}\"/>", indent, time)?;
+    }
+    let inner = format!("{}    ", indent);
+    let item_indent = format!("{}  ", inner);
+    for block in &mesh.blocks {
+        writeln!(
+            out,
+            "{}  <Grid Name=\"{}\" GridType=\"Uniform\">",
+            indent,
+            xml_escape(&block.name)
+        )?;
+        write!(
+            out,
+            "{}<Topology TopologyType=\"{}\" NumberOfElements=\"{}\"",
+            inner, block.topology, block.block.num_entries
+        )?;
+        if block.topology == "Polyline" || block.topology == "Polyvertex" {
+            write!(
+                out,
+                " NodesPerElement=\"{}\"",
+                block.connectivity.dimensions[1]
+            )?;
+        }
+        writeln!(out, ">")?;
+        write!(out, "{}", data_item(&block.connectivity, &item_indent))?;
+        writeln!(out, "{}</Topology>", inner)?;
+        writeln!(
+            out,
+            "{}<Geometry GeometryType=\"{}\">",
+            inner, mesh.geometry_type
+        )?;
+        write!(out, "{}", data_item(&mesh.coordinates, &item_indent))?;
+        writeln!(out, "{}</Geometry>", inner)?;
+
+        let node_ids = data_item(&mesh.node_ids, &item_indent);
+        write_attribute(out, "node_id", Center::Node, &node_ids, &inner)?;
+        let elem_ids = data_item(&block.elem_ids, &item_indent);
+        write_attribute(out, "element_id", Center::Cell, &elem_ids, &inner)?;
+        if let Some((row, _)) = step {
+            for variable in variables {
+                if variable.block_id.is_some_and(|id| id != block.block.id) {
+                    continue;
+                }
+                let item = row_item(&variable.dataset, row, &item_indent);
+                write_attribute(out, &variable.name, variable.center, &item, &inner)?;
+            }
+        }
+        writeln!(out, "{}  </Grid>", indent)?;
+    }
+    writeln!(out, "{}</Grid>", indent)?;
+    Ok(())
+}
+
+fn write_index(
+    out: &mut impl Write,
+    title: &str,
+    mesh: &Mesh,
+    variables: &[Variable],
+    times: &[f64],
+) -> Result<()> {
+    let title = title.lines().next().unwrap_or_default().trim();
+    let title = if title.is_empty() {
+        "Exodus mesh"
+    } else {
+        title
+    };
+    writeln!(out, "<?xml version=\"1.0\" ?>")?;
+    writeln!(out, "<!DOCTYPE Xdmf SYSTEM \"Xdmf.dtd\" []>")?;
+    writeln!(
+        out,
+        "<Xdmf xmlns:xi=\"http://www.w3.org/2001/XInclude\" Version=\"3.0\">"
+    )?;
+    writeln!(out, "  <Domain>")?;
+    if times.is_empty() {
+        write_step(out, mesh, variables, None, "    ")?;
+    } else {
+        writeln!(
+            out,
+            "    <Grid Name=\"{}\" GridType=\"Collection\" CollectionType=\"Temporal\">",
+            xml_escape(title)
+        )?;
+        for (row, &time) in times.iter().enumerate() {
+            write_step(out, mesh, variables, Some((row, time)), "      ")?;
+        }
+        writeln!(out, "    </Grid>")?;
+    }
+    writeln!(out, "  </Domain>")?;
+    writeln!(out, "</Xdmf>")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Results file from the SEACAS script tests: five HEX8 blocks and
+    /// global, nodal and element variables over five steps
+    fn results_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../packages/seacas/scripts/tests/exomerge_unit_test.e")
+    }
+
+    #[test]
+    fn test_topologies() {
+        assert_eq!(xdmf_topology(&Topology::Hex8).unwrap().0, "Hexahedron");
+        assert_eq!(xdmf_topology(&Topology::Sphere).unwrap().0, "Polyvertex");
+        let (name, order) = xdmf_topology(&Topology::Tri7).unwrap();
+        assert_eq!((name, order.len()), ("Tri_6", 6));
+        assert_eq!(xdmf_topology(&Topology::Tet14).unwrap().0, "Tet_10");
+        assert_eq!(xdmf_topology(&Topology::NSided), None);
+    }
+
+    #[test]
+    fn test_export_steps() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("results.xmf");
+        let options = XdmfOptions {
+            steps: Some(vec![0, 2, 4]),
+            variables: Some(vec!["DISP_X".into(), "pressure".into(), "time".into()]),
+            performance: Some(PerformanceConfig::auto().with_time_chunk_size(2)),
+        };
+        let summary = export_xdmf(results_path(), &output, &options).unwrap();
+        assert_eq!((summary.num_nodes, summary.num_elems), (2185, 1300));
+        assert_eq!(summary.num_time_steps, 3);
+        assert_eq!(summary.heavy_data, dir.path().join("results.h5"));
+
+        let index = std::fs::read_to_string(&output).unwrap();
+        assert_eq!(index.matches("<Time Value=").count(), 3);
+        assert_eq!(index.matches("GridType=\"Uniform\"").count(), 15);
+        assert!(index.contains("TopologyType=\"Hexahedron\" NumberOfElements=\"675\""));
+        assert!(index.contains("results.h5:/vals_nod_var1"));
+        assert!(index.contains("Name=\"time\" AttributeType=\"Scalar\" Center=\"Grid\""));
+        assert!(index.contains("Name=\"pressure\" AttributeType=\"Scalar\" Center=\"Cell\""));
+        assert!(!index.contains("Name=\"disp_y\""));
+        // The last step selects the third row
+        assert!(index.contains(">2 0 1 1 1 2185</DataItem>"));
+
+        let file = ExodusFile::<mode::Read>::open(results_path()).unwrap();
+        let h5 = netcdf::open(&summary.heavy_data).unwrap();
+        let times: Vec<f64> = h5.variable("time_whole").unwrap().get_values(..).unwrap();
+        let expected = file.times().unwrap();
+        assert_eq!(times, vec![expected[0], expected[2], expected[4]]);
+        let disp_x: Vec<f64> = h5
+            .variable("vals_nod_var1")
+            .unwrap()
+            .get_values((1..2, ..))
+            .unwrap();
+        assert_eq!(disp_x, file.var(2, EntityType::Nodal, 0, 0).unwrap());
+        let connect: Vec<i64> = h5.variable("connect2").unwrap().get_values(..).unwrap();
+        let expected: Vec<i64> = file
+            .connectivity(2)
+            .unwrap()
+            .iter()
+            .map(|node| node - 1)
+            .collect();
+        assert_eq!(connect, expected);
+    }
+
+    #[test]
+    fn test_export_errors() {
+        let dir = TempDir::new().unwrap();
+        assert!(matches!(
+            export_xdmf(
+                results_path(),
+                dir.path().join("results.h5"),
+                &XdmfOptions::default()
+            ),
+            Err(ExodusError::Other(_))
+        ));
+        let options = XdmfOptions {
+            steps: Some(vec![5]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            export_xdmf(results_path(), dir.path().join("results.xdmf"), &options),
+            Err(ExodusError::InvalidTimeStep(5))
+        ));
+    }
+}
@@ -0,0 +1,87 @@
+//! Tests for importing Abaqus input decks
+//!
+//! The deck is a 2x1 grid of S4R shells with labels that are not
+//! consecutive, its nodes in an included file, an element set and a
+//! surface on the positive side of the shells.
+
+#![cfg(feature = "netcdf4")]
+
+use exodus_rs::abaqus::import_abaqus;
+use exodus_rs::{mode, EntityType, ExodusFile};
+use tempfile::TempDir;
+
+const DECK: &str = "\
+*HEADING
+Shell plate
+*INCLUDE, INPUT=nodes.inp
+*ELEMENT, TYPE=S4R, ELSET=PLATE
+101, 10, 20, 50, 40
+102, 20, 30, 60, 50
+*ELSET, ELSET=RIGHT
+102
+*SURFACE, NAME=TOP
+PLATE, SPOS
+*SHELL SECTION, ELSET=PLATE, MATERIAL=STEEL
+0.1
+";
+
+const NODES: &str = "\
+*NODE, NSET=EDGE
+10, 0.0, 0.0, 0.0
+40, 0.0, 1.0, 0.0
+*NODE
+20, 1.0, 0.0, 0.0
+30, 2.0, 0.0, 0.0
+50, 1.0, 1.0, 0.0
+60, 2.0, 1.0, 0.0
+";
+
+#[test]
+fn test_import_abaqus() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("plate.inp");
+    std::fs::write(&input, DECK).unwrap();
+    std::fs::write(dir.path().join("nodes.inp"), NODES).unwrap();
+    let output = dir.path().join("plate.e");
+
+    let summary = import_abaqus(&input, &output).unwrap();
+    assert_eq!((summary.num_nodes, summary.num_elems), (6, 2));
+    assert_eq!(
+        (
+            summary.num_blocks,
+            summary.num_node_sets,
+            summary.num_elem_sets,
+            summary.num_side_sets
+        ),
+        (1, 1, 1, 1)
+    );
+
+    let file = ExodusFile::<mode::Read>::open(&output).unwrap();
+    assert_eq!(file.init_params().unwrap().num_dim, 3);
+    let block = file.block(1).unwrap();
+    assert_eq!(block.topology, "SHELL4");
+    assert_eq!(file.connectivity(1).unwrap(), vec![1, 2, 5, 4, 2, 3, 6, 5]);
+    assert_eq!(file.names(EntityType::ElemBlock).unwrap(), vec!["PLATE"]);
+    assert_eq!(
+        file.id_map(EntityType::NodeMap).unwrap(),
+        vec![10, 20, 30, 40, 50, 60]
+    );
+    assert_eq!(file.id_map(EntityType::ElemMap).unwrap(), vec![101, 102]);
+
+    assert_eq!(file.node_set(1).unwrap().nodes, vec![1, 4]);
+    assert_eq!(file.names(EntityType::NodeSet).unwrap(), vec!["EDGE"]);
+    let right = file.entity_set(EntityType::ElemSet, 1).unwrap();
+    assert_eq!(right.entities, vec![2]);
+    assert_eq!(file.names(EntityType::ElemSet).unwrap(), vec!["RIGHT"]);
+    let top = file.side_set(1).unwrap();
+    assert_eq!((top.elements, top.sides), (vec![1, 2], vec![1, 1]));
+    assert_eq!(file.names(EntityType::SideSet).unwrap(), vec!["TOP"]);
+}
+
+#[test]
+fn test_import_abaqus_unknown_set() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("bad.inp");
+    std::fs::write(&input, "*NODE\n1\n*ELSET, ELSET=A\nMISSING\n").unwrap();
+    assert!(import_abaqus(&input, dir.path().join("bad.e")).is_err());
+}
@@ -0,0 +1,239 @@
+//! Tests for the pure-Rust NetCDF-3 reader backend.
+//!
+//! The sample-file tests run with and without the `netcdf4` feature; the
+//! cross-check against the NetCDF C library only runs when it is available.
+
+use exodus_rs::backend::{AttrValue, Backend, ClassicFile};
+use exodus_rs::{mode, EntityType, ExodusFile, FileFormat, Int64Mode};
+use std::path::PathBuf;
+
+fn data_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("data")
+        .join(name)
+}
+
+#[test]
+fn test_classic_file_header() {
+    let file = ClassicFile::open(data_path("two_blocks.e")).unwrap();
+    assert_eq!(file.format(), FileFormat::NetCdf364BitOffset);
+    assert_eq!(file.num_records(), 0);
+
+    assert_eq!(file.dimension_len("num_nodes"), Some(2541));
+    assert_eq!(file.dimension_len("num_el_blk"), Some(2));
+    assert_eq!(file.dimension_len("no_such_dim"), None);
+
+    assert_eq!(file.variable_shape("connect1"), Some(vec![1000, 8]));
+    assert_eq!(file.variable_shape("time_whole"), Some(vec![0]));
+    assert!(file.has_variable("node_num_map"));
+    assert!(!file.has_variable("vals_nod_var1"));
+
+    let version = file.global_attribute("version").unwrap();
+    assert!(matches!(version, AttrValue::Floats(_)));
+    assert_eq!(version.as_f64().unwrap().trunc(), 8.0);
+    assert_eq!(
+        file.variable_attribute("connect1", "elem_type")
+            .unwrap()
+            .as_str(),
+        Some("HEX8")
+    );
+}
+
+#[test]
+fn test_classic_file_hyperslab() {
+    let file = ClassicFile::open(data_path("two_blocks.e")).unwrap();
+
+    let all = file.read_all_i64("connect2").unwrap();
+    assert_eq!(all.len(), 8000);
+
+    // Second and third elements of block 2, nodes 3..=6
+    let slab = file.read_i64("connect2", &[1, 2], &[2, 4]).unwrap();
+    assert_eq!(slab[..4], all[10..14]);
+    assert_eq!(slab[4..], all[18..22]);
+
+    assert!(file.read_i64("connect2", &[999, 0], &[2, 8]).is_err());
+    assert!(file.read_f64("no_such_var", &[0], &[1]).is_err());
+}
+
+#[test]
+fn test_read_two_blocks() {
+    let file = ExodusFile::<mode::Read>::open(data_path("two_blocks.e")).unwrap();
+    assert_eq!(file.format().unwrap(), FileFormat::NetCdf364BitOffset);
+    assert_eq!(file.version().unwrap(), (8, 3));
+    assert_eq!(file.int64_mode(), Int64Mode::Int32);
+
+    let params = file.init_params().unwrap();
+    assert_eq!(params.num_dim, 3);
+    assert_eq!(params.num_nodes, 2541);
+    assert_eq!(params.num_elems, 2000);
+    assert_eq!(params.num_elem_blocks, 2);
+    assert_eq!(params.num_node_sets, 1);
+    assert_eq!(params.num_side_sets, 2);
+
+    let coords = file.coords::<f64>().unwrap();
+    assert_eq!(coords.x.len(), 2541);
+    assert_eq!(coords.z.len(), 2541);
+    assert_eq!(file.coord_names().unwrap(), vec!["x", "y", "z"]);
+
+    assert_eq!(file.block_ids(EntityType::ElemBlock).unwrap(), vec![1, 2]);
+    let block = file.block(2).unwrap();
+    assert_eq!(block.topology, "HEX8");
+    assert_eq!(block.num_entries, 1000);
+    assert_eq!(block.num_nodes_per_entry, 8);
+    let conn = file.connectivity_structured(1).unwrap();
+    assert_eq!(conn.data[..8], [1, 2, 3, 4, 5, 6, 7, 8]);
+    assert!(file.block(99).is_err());
+
+    let node_set = file.node_set(1).unwrap();
+    assert_eq!(node_set.nodes.len(), 241);
+    let side_set = file.side_set(2).unwrap();
+    assert_eq!(side_set.elements.len(), 100);
+    assert_eq!(side_set.sides.len(), 100);
+    assert_eq!(side_set.dist_factors.len(), 400);
+    assert_eq!(file.sets(EntityType::SideSet).unwrap().len(), 2);
+
+    assert_eq!(file.id_map(EntityType::NodeMap).unwrap().len(), 2541);
+    let qa = file.qa_records().unwrap();
+    assert_eq!(qa.len(), 1);
+    assert_eq!(qa[0].code_name, "CUBIT");
+
+    assert_eq!(file.num_time_steps().unwrap(), 0);
+    assert!(file.times().unwrap().is_empty());
+    assert!(file.variable_names(EntityType::Nodal).unwrap().is_empty());
+}
+
+#[cfg(not(feature = "netcdf4"))]
+#[test]
+fn test_write_requires_netcdf4() {
+    use exodus_rs::ExodusError;
+
+    let tmp = tempfile::NamedTempFile::new().unwrap();
+    assert!(matches!(
+        ExodusFile::<mode::Write>::create_default(tmp.path()),
+        Err(ExodusError::UnsupportedFeature(_))
+    ));
+    assert!(matches!(
+        ExodusFile::<mode::Append>::append(data_path("two_blocks.e")),
+        Err(ExodusError::UnsupportedFeature(_))
+    ));
+
+    let file = ExodusFile::<mode::Read>::open(data_path("two_blocks.e")).unwrap();
+    assert!(matches!(
+        file.assembly_ids(),
+        Err(ExodusError::UnsupportedFeature(_))
+    ));
+}
+
+#[cfg(feature = "netcdf4")]
+mod netcdf_cross_check {
+    use super::*;
+    use exodus_rs::{Block, CreateMode, CreateOptions, InitParams};
+    use tempfile::NamedTempFile;
+
+    /// Write a small classic-format file with results on three time steps
+    fn write_classic_file(path: &std::path::Path, format: FileFormat) {
+        let mut file = ExodusFile::create(
+            path,
+            CreateOptions {
+                mode: CreateMode::Clobber,
+                format,
+                int64_mode: Int64Mode::Int32,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        file.init(&InitParams {
+            title: "Classic reader".into(),
+            num_dim: 2,
+            num_nodes: 4,
+            num_elems: 1,
+            num_elem_blocks: 1,
+            num_node_sets: 1,
+            ..Default::default()
+        })
+        .unwrap();
+        file.put_coords(&[0.0, 1.0, 1.0, 0.0], Some(&[0.0, 0.0, 1.0, 1.0]), None)
+            .unwrap();
+        file.put_block(&Block {
+            id: 10,
+            entity_type: EntityType::ElemBlock,
+            topology: "QUAD4".into(),
+            num_entries: 1,
+            num_nodes_per_entry: 4,
+            num_edges_per_entry: 0,
+            num_faces_per_entry: 0,
+            num_attributes: 0,
+        })
+        .unwrap();
+        file.put_connectivity(10, &[1, 2, 3, 4]).unwrap();
+        file.put_node_set(5, &[1, 4], Some(&[1.0, 0.5])).unwrap();
+
+        file.define_variables(EntityType::Global, &["energy"])
+            .unwrap();
+        file.define_variables(EntityType::Nodal, &["temp", "pressure"])
+            .unwrap();
+        file.define_variables(EntityType::ElemBlock, &["stress"])
+            .unwrap();
+        for step in 0..3 {
+            let t = step as f64;
+            file.put_time(step, t * 0.5).unwrap();
+            file.put_var(step, EntityType::Global, 0, 0, &[t * 10.0])
+                .unwrap();
+            file.put_var(
+                step,
+                EntityType::Nodal,
+                0,
+                0,
+                &[t, t + 1.0, t + 2.0, t + 3.0],
+            )
+            .unwrap();
+            file.put_var(step, EntityType::Nodal, 0, 1, &[-t; 4])
+                .unwrap();
+            file.put_var(step, EntityType::ElemBlock, 10, 0, &[t * 100.0])
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_matches_netcdf_library() {
+        for format in [FileFormat::NetCdf3Classic, FileFormat::NetCdf364BitOffset] {
+            let tmp = NamedTempFile::new().unwrap();
+            write_classic_file(tmp.path(), format);
+
+            let native = ClassicFile::open(tmp.path()).unwrap();
+            let reference = netcdf::open(tmp.path()).unwrap();
+            assert_eq!(native.format(), format);
+            assert_eq!(native.variable_names(), reference.variable_names());
+
+            for name in reference.variable_names() {
+                let shape = reference.variable_shape(&name);
+                assert_eq!(native.variable_shape(&name), shape, "{}", name);
+                match native.read_all_f64(&name) {
+                    Ok(values) => {
+                        assert_eq!(values, reference.read_all_f64(&name).unwrap(), "{}", name)
+                    }
+                    Err(_) => assert_eq!(
+                        native.read_all_bytes(&name).unwrap(),
+                        reference.read_all_bytes(&name).unwrap(),
+                        "{}",
+                        name
+                    ),
+                }
+            }
+
+            let file = ExodusFile::<mode::Read>::open(tmp.path()).unwrap();
+            assert_eq!(file.times().unwrap(), vec![0.0, 0.5, 1.0]);
+            assert_eq!(
+                file.var(2, EntityType::Nodal, 0, 0).unwrap(),
+                vec![2.0, 3.0, 4.0, 5.0]
+            );
+            assert_eq!(
+                file.var_time_series(0, 3, EntityType::ElemBlock, 10, 0)
+                    .unwrap(),
+                vec![0.0, 100.0, 200.0]
+            );
+        }
+    }
+}
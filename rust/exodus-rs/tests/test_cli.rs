@@ -0,0 +1,62 @@
+//! Tests for the `exodus` inspection binary
+//!
+//! The binary is run on the sample files, which it can read with and without
+//! the `netcdf4` feature.
+
+#![cfg(feature = "cli")]
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn data_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("data")
+        .join(name)
+}
+
+fn exodus(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_exodus"))
+        .args(args)
+        .output()
+        .expect("Failed to run exodus")
+}
+
+#[test]
+fn test_text_summary() {
+    let path = data_path("two_blocks.e");
+    let output = exodus(&[path.to_str().unwrap()]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Nodes:      2541"));
+    assert!(stdout.contains("Element blocks: 2"));
+    assert!(stdout.contains("HEX8     1000 elements, 8 nodes/element"));
+    assert!(stdout.contains("Side sets: 2"));
+    assert!(stdout.contains("elem_map   2000 IDs, 1 to 2000 (sequential)"));
+    assert!(stdout.contains("Time steps: 0"));
+    assert!(stdout.contains("CUBIT"));
+}
+
+#[test]
+fn test_json_document() {
+    let path = data_path("two_blocks.e");
+    let output = exodus(&[path.to_str().unwrap(), "--json", "--maps"]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["params"]["num_nodes"], 2541);
+    assert_eq!(json["blocks"].as_array().unwrap().len(), 2);
+    assert_eq!(json["blocks"][0]["topology"], "HEX8");
+    assert_eq!(json["sets"][0]["entity_type"], "node_set");
+    assert_eq!(json["maps"][1]["entity_type"], "elem_map");
+    assert_eq!(json["maps"][1]["sequential"], true);
+    assert_eq!(json["maps"][1]["ids"].as_array().unwrap().len(), 2000);
+    assert!(json["assemblies"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_missing_file() {
+    let output = exodus(&["absent.e"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("exodus: absent.e:"));
+}
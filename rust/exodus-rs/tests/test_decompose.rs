@@ -0,0 +1,265 @@
+//! Tests for decomposing a serial mesh into per-processor files
+//!
+//! The serial mesh is a 4x2 grid of QUAD4 elements in two blocks (the left
+//! and right halves) with reversed global IDs, a node set on the left edge and
+//! a side set along the bottom. Decomposing and joining again must give back
+//! the serial mesh.
+
+#![cfg(feature = "netcdf4")]
+
+use exodus_rs::decompose::{
+    decompose_file, partition_elements, write_decomposition, DecomposeOptions, DecompositionMethod,
+};
+use exodus_rs::join::{find_decomposed_files, join_files, JoinOptions};
+use exodus_rs::{mode, Block, CreateMode, CreateOptions, EntityType, ExodusFile, InitParams};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn clobber() -> CreateOptions {
+    CreateOptions {
+        mode: CreateMode::Clobber,
+        ..Default::default()
+    }
+}
+
+/// Write the serial mesh
+///
+/// ```text
+///   11--12--13--14--15
+///   | 5 | 6 | 7 | 8 |
+///   6---7---8---9--10
+///   | 1 | 2 | 3 | 4 |
+///   1---2---3---4---5
+/// ```
+///
+/// Block 1 holds elements 1, 2, 5, 6 and block 2 holds 3, 4, 7, 8.
+fn write_serial(path: &Path) {
+    let mut file = ExodusFile::create(path, clobber()).unwrap();
+    file.init(&InitParams {
+        title: "Serial grid".into(),
+        num_dim: 2,
+        num_nodes: 15,
+        num_elems: 8,
+        num_elem_blocks: 2,
+        num_node_sets: 1,
+        num_side_sets: 1,
+        ..Default::default()
+    })
+    .unwrap();
+    let x: Vec<f64> = (0..15).map(|n| (n % 5) as f64).collect();
+    let y: Vec<f64> = (0..15).map(|n| (n / 5) as f64).collect();
+    file.put_coords(&x, Some(&y), None).unwrap();
+
+    let quad = |i: i64, j: i64| {
+        let n = j * 5 + i + 1;
+        [n, n + 1, n + 6, n + 5]
+    };
+    for (id, columns) in [(1, [0, 1]), (2, [2, 3])] {
+        file.put_block(&Block {
+            id,
+            entity_type: EntityType::ElemBlock,
+            topology: "QUAD4".into(),
+            num_entries: 4,
+            num_nodes_per_entry: 4,
+            num_edges_per_entry: 0,
+            num_faces_per_entry: 0,
+            num_attributes: 1,
+        })
+        .unwrap();
+        let connectivity: Vec<i64> = [0, 1]
+            .iter()
+            .flat_map(|&j| columns.iter().flat_map(move |&i| quad(i, j)))
+            .collect();
+        file.put_connectivity(id, &connectivity).unwrap();
+        file.put_block_attributes(id, &[id as f64; 4]).unwrap();
+        file.put_block_attribute_names(id, &["thickness"]).unwrap();
+    }
+    file.put_names(EntityType::ElemBlock, &["left", "right"])
+        .unwrap();
+
+    // Node IDs count down from 115, element IDs are the grid numbers above
+    let node_ids: Vec<i64> = (0..15).map(|n| 115 - n).collect();
+    file.put_id_map(EntityType::NodeMap, &node_ids).unwrap();
+    file.put_id_map(EntityType::ElemMap, &[1, 2, 5, 6, 3, 4, 7, 8])
+        .unwrap();
+
+    file.put_node_set(10, &[1, 6, 11], Some(&[1.0, 6.0, 11.0]))
+        .unwrap();
+    // Bottom sides of elements 1-4 (storage positions 1, 2, 5, 6)
+    file.put_side_set(
+        20,
+        &[1, 2, 5, 6],
+        &[1, 1, 1, 1],
+        Some(&[1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 5.0]),
+    )
+    .unwrap();
+}
+
+fn serial(dir: &Path) -> PathBuf {
+    let path = dir.join("serial.e");
+    write_serial(&path);
+    path
+}
+
+#[test]
+fn test_partition_methods() {
+    let dir = TempDir::new().unwrap();
+    let file = ExodusFile::<mode::Read>::open(serial(dir.path())).unwrap();
+
+    // The grid is longest along x, so bisection splits it into columns
+    let rcb = partition_elements(&file, 2, DecompositionMethod::Rcb).unwrap();
+    assert_eq!(rcb, vec![0, 0, 0, 0, 1, 1, 1, 1]);
+    let inertial = partition_elements(&file, 2, DecompositionMethod::Inertial).unwrap();
+    assert_eq!(inertial, rcb);
+    let quarters = partition_elements(&file, 4, DecompositionMethod::Rcb).unwrap();
+    for proc in 0..4 {
+        assert_eq!(quarters.iter().filter(|&&p| p == proc).count(), 2);
+    }
+
+    let blocks = partition_elements(&file, 2, DecompositionMethod::Block).unwrap();
+    assert_eq!(blocks, vec![0, 0, 0, 0, 1, 1, 1, 1]);
+    assert!(partition_elements(&file, 3, DecompositionMethod::Block).is_err());
+    assert!(partition_elements(&file, 9, DecompositionMethod::Rcb).is_err());
+}
+
+#[test]
+fn test_decompose_local_mesh_and_nemesis() {
+    let dir = TempDir::new().unwrap();
+    let input = serial(dir.path());
+    let base = dir.path().join("mesh.e");
+    let options = DecomposeOptions {
+        create_options: clobber(),
+        ..Default::default()
+    };
+    let summary = decompose_file(&input, &base, 2, &options).unwrap();
+    assert_eq!(summary.paths, find_decomposed_files(&base).unwrap());
+    assert_eq!(summary.num_elems, vec![4, 4]);
+    assert_eq!(summary.num_nodes, vec![9, 9]);
+    assert_eq!(summary.num_border_nodes, vec![3, 3]);
+
+    // Processor 0 holds the left block: columns x = 0..2, with x = 2 shared
+    let file = ExodusFile::<mode::Read>::open(&summary.paths[0]).unwrap();
+    let params = file.init_params().unwrap();
+    assert_eq!((params.num_nodes, params.num_elems), (9, 4));
+    assert_eq!(file.block(1).unwrap().num_entries, 4);
+    assert_eq!(file.block(2).unwrap().num_entries, 0);
+    assert_eq!(
+        file.names(EntityType::ElemBlock).unwrap(),
+        vec!["left", "right"]
+    );
+    assert_eq!(file.block_attributes(1).unwrap(), vec![1.0; 4]);
+    assert_eq!(
+        file.id_map(EntityType::NodeMap).unwrap(),
+        vec![115, 114, 110, 109, 105, 104, 113, 108, 103]
+    );
+    assert_eq!(file.id_map(EntityType::ElemMap).unwrap(), vec![1, 2, 5, 6]);
+    let coords = file.coords::<f64>().unwrap();
+    assert_eq!(coords.x, vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 2.0, 2.0, 2.0]);
+
+    let node_set = file.node_set(10).unwrap();
+    assert_eq!(node_set.nodes, vec![1, 3, 5]);
+    assert_eq!(node_set.dist_factors, vec![1.0, 6.0, 11.0]);
+    let side_set = file.side_set(20).unwrap();
+    assert_eq!(side_set.elements, vec![1, 2]);
+    assert_eq!(side_set.dist_factors, vec![1.0, 2.0, 2.0, 3.0]);
+
+    let info = file.init_info().unwrap();
+    assert_eq!((info.num_procs, info.num_procs_in_file), (2, 1));
+    let global = file.init_global().unwrap();
+    assert_eq!((global.num_nodes, global.num_elems), (15, 8));
+    assert_eq!(
+        file.global_params(EntityType::ElemBlock)
+            .unwrap()
+            .entry_counts,
+        vec![4, 4]
+    );
+    assert_eq!(
+        file.global_params(EntityType::SideSet)
+            .unwrap()
+            .dist_fact_counts,
+        vec![8]
+    );
+
+    let lb = file.load_balance_params().unwrap();
+    assert_eq!((lb.num_internal_nodes, lb.num_border_nodes), (6, 3));
+    assert_eq!((lb.num_internal_elems, lb.num_border_elems), (2, 2));
+    let node_maps = file.processor_node_maps().unwrap();
+    assert_eq!(node_maps.border, vec![7, 8, 9]);
+    assert_eq!(file.processor_elem_maps().unwrap().border, vec![2, 4]);
+
+    let params = file.comm_map_params().unwrap();
+    assert_eq!(params.node_cmap_ids, vec![1]);
+    assert_eq!(params.elem_cmap_ids, vec![1]);
+    assert_eq!(file.node_comm_map(1).unwrap().node_ids, vec![7, 8, 9]);
+    let elem_cmap = file.elem_comm_map(1).unwrap();
+    assert_eq!(elem_cmap.elem_ids, vec![2, 4]);
+    assert_eq!(elem_cmap.side_ids, vec![2, 2]);
+    assert_eq!(elem_cmap.proc_ids, vec![1, 1]);
+}
+
+#[test]
+fn test_decompose_join_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    let input = serial(dir.path());
+    for (method, num_procs) in [
+        (DecompositionMethod::Rcb, 3),
+        (DecompositionMethod::Inertial, 4),
+        (DecompositionMethod::Block, 2),
+    ] {
+        let base = dir.path().join(format!("{:?}.e", method));
+        let options = DecomposeOptions {
+            method,
+            create_options: clobber(),
+        };
+        let summary = decompose_file(&input, &base, num_procs, &options).unwrap();
+        assert_eq!(summary.paths.len(), num_procs);
+        assert_eq!(summary.num_elems.iter().sum::<usize>(), 8);
+
+        let output = dir.path().join(format!("{:?}-joined.e", method));
+        let joined = join_files(
+            &summary.paths,
+            &output,
+            &JoinOptions {
+                create_options: clobber(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!((joined.num_nodes, joined.num_elems), (15, 8));
+
+        // The joined file is ordered by global ID
+        let file = ExodusFile::<mode::Read>::open(&output).unwrap();
+        let node_ids = file.id_map(EntityType::NodeMap).unwrap();
+        assert_eq!(node_ids, (101..=115).collect::<Vec<_>>());
+        let coords = file.coords::<f64>().unwrap();
+        for (n, &id) in node_ids.iter().enumerate() {
+            let serial = (115 - id) as usize;
+            assert_eq!(coords.x[n], (serial % 5) as f64);
+            assert_eq!(coords.y[n], (serial / 5) as f64);
+        }
+        assert_eq!(file.node_set(10).unwrap().nodes.len(), 3);
+        let side_set = file.side_set(20).unwrap();
+        assert_eq!(side_set.elements.len(), 4);
+        assert_eq!(side_set.dist_factors.len(), 8);
+        assert_eq!(file.block(1).unwrap().num_entries, 4);
+    }
+}
+
+#[test]
+fn test_write_custom_assignment() {
+    let dir = TempDir::new().unwrap();
+    let file = ExodusFile::<mode::Read>::open(serial(dir.path())).unwrap();
+    let base = dir.path().join("custom.e");
+
+    // Bottom row on processor 0, top row on processor 1
+    let assignment = [0, 0, 1, 1, 0, 0, 1, 1];
+    let summary = write_decomposition(&file, &assignment, 2, &base, &clobber()).unwrap();
+    assert_eq!(summary.num_border_nodes, vec![5, 5]);
+    let part = ExodusFile::<mode::Read>::open(&summary.paths[1]).unwrap();
+    assert_eq!(part.id_map(EntityType::ElemMap).unwrap(), vec![5, 6, 7, 8]);
+    assert!(part.side_set(20).unwrap().elements.is_empty());
+    assert_eq!(part.elem_comm_map(0).unwrap().side_ids, vec![1; 4]);
+
+    assert!(write_decomposition(&file, &[0; 8], 2, &base, &clobber()).is_err());
+    assert!(write_decomposition(&file, &[0; 7], 1, &base, &clobber()).is_err());
+}
@@ -0,0 +1,307 @@
+//! Tests for comparing files within tolerances
+//!
+//! The compared mesh is a 2x1 grid of QUAD4 elements with a node set on the
+//! left edge and a side set on the bottom. Variants of it store the nodes and
+//! elements in reverse order, renumber their IDs, shift the temperature, or
+//! write different time steps. All variables are linear in time, so
+//! interpolating between steps reproduces them exactly.
+
+#![cfg(feature = "netcdf4")]
+
+use exodus_rs::diff::{diff, diff_files, DiffOptions, EntityMatching, TimeMatching, Tolerance};
+use exodus_rs::{mode, Block, CreateMode, CreateOptions, EntityType, ExodusFile, InitParams};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Changes to the reference mesh
+#[derive(Clone)]
+struct Variant {
+    /// Store nodes and elements in reverse order
+    reverse: bool,
+    /// Added to every node and element ID
+    id_offset: i64,
+    /// Added to the nodal temperature
+    temp_offset: f64,
+    /// Also define a nodal `pressure`
+    pressure: bool,
+    times: Vec<f64>,
+}
+
+impl Default for Variant {
+    fn default() -> Self {
+        Self {
+            reverse: false,
+            id_offset: 0,
+            temp_offset: 0.0,
+            pressure: false,
+            times: vec![0.0, 1.0, 2.0],
+        }
+    }
+}
+
+/// Write the mesh
+///
+/// ```text
+///   4---5---6
+///   | 1 | 2 |
+///   1---2---3
+/// ```
+///
+/// numbered here in natural order, which `variant.reverse` reverses in
+/// storage.
+fn write_mesh(path: &Path, variant: &Variant) {
+    let mut file = ExodusFile::create(
+        path,
+        CreateOptions {
+            mode: CreateMode::Clobber,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    file.init(&InitParams {
+        title: "Diff grid".into(),
+        num_dim: 2,
+        num_nodes: 6,
+        num_elems: 2,
+        num_elem_blocks: 1,
+        num_node_sets: 1,
+        num_side_sets: 1,
+        ..Default::default()
+    })
+    .unwrap();
+
+    // Natural node (1-based) of each storage position, and its inverse
+    let natural = |k: usize, count: usize| if variant.reverse { count - k } else { k + 1 };
+    let node = |n: i64| if variant.reverse { 7 - n } else { n };
+    let nodes: Vec<i64> = (0..6).map(|k| natural(k, 6) as i64).collect();
+    let elems: Vec<i64> = (0..2).map(|k| natural(k, 2) as i64).collect();
+
+    let x: Vec<f64> = nodes.iter().map(|&n| ((n - 1) % 3) as f64).collect();
+    let y: Vec<f64> = nodes.iter().map(|&n| ((n - 1) / 3) as f64).collect();
+    file.put_coords(&x, Some(&y), None).unwrap();
+    let node_ids: Vec<i64> = nodes.iter().map(|&n| n + variant.id_offset).collect();
+    let elem_ids: Vec<i64> = elems.iter().map(|&e| e + variant.id_offset).collect();
+    file.put_id_map(EntityType::NodeMap, &node_ids).unwrap();
+    file.put_id_map(EntityType::ElemMap, &elem_ids).unwrap();
+
+    file.put_block(&Block {
+        id: 1,
+        entity_type: EntityType::ElemBlock,
+        topology: "QUAD4".into(),
+        num_entries: 2,
+        num_nodes_per_entry: 4,
+        num_edges_per_entry: 0,
+        num_faces_per_entry: 0,
+        num_attributes: 0,
+    })
+    .unwrap();
+    let connectivity: Vec<i64> = elems
+        .iter()
+        .flat_map(|&e| [e, e + 1, e + 4, e + 3])
+        .map(node)
+        .collect();
+    file.put_connectivity(1, &connectivity).unwrap();
+
+    file.put_node_set(10, &[node(1), node(4)], None).unwrap();
+    let first_elem = if variant.reverse { 2 } else { 1 };
+    file.put_side_set(20, &[first_elem], &[1], None).unwrap();
+
+    file.define_variables(EntityType::Global, &["energy"])
+        .unwrap();
+    let mut nodal = vec!["temp"];
+    if variant.pressure {
+        nodal.push("pressure");
+    }
+    file.define_variables(EntityType::Nodal, &nodal).unwrap();
+    file.define_variables(EntityType::ElemBlock, &["stress"])
+        .unwrap();
+    file.define_variables(EntityType::NodeSet, &["flux"])
+        .unwrap();
+    for (step, &time) in variant.times.iter().enumerate() {
+        file.put_time(step, time).unwrap();
+        file.put_var(step, EntityType::Global, 0, 0, &[2.0 * time])
+            .unwrap();
+        let temp: Vec<f64> = nodes
+            .iter()
+            .map(|&n| n as f64 + time + variant.temp_offset)
+            .collect();
+        file.put_var(step, EntityType::Nodal, 0, 0, &temp).unwrap();
+        if variant.pressure {
+            file.put_var(step, EntityType::Nodal, 0, 1, &[1.0; 6])
+                .unwrap();
+        }
+        let stress: Vec<f64> = elems.iter().map(|&e| 10.0 * e as f64 + time).collect();
+        file.put_var(step, EntityType::ElemBlock, 1, 0, &stress)
+            .unwrap();
+        file.put_var(step, EntityType::NodeSet, 10, 0, &[time, 4.0 * time])
+            .unwrap();
+    }
+}
+
+fn mesh(dir: &Path, name: &str, variant: &Variant) -> PathBuf {
+    let path = dir.join(name);
+    write_mesh(&path, variant);
+    path
+}
+
+#[test]
+fn test_diff_identical_and_reordered() {
+    let dir = TempDir::new().unwrap();
+    let first = mesh(dir.path(), "first.e", &Variant::default());
+    let same = mesh(dir.path(), "same.e", &Variant::default());
+    let report = diff_files(&first, &same, &DiffOptions::default()).unwrap();
+    assert!(!report.has_differences(), "{}", report);
+    assert_eq!(report.num_steps_compared, 3);
+    assert!(report
+        .to_string()
+        .ends_with("Files are the same (3 time steps compared)"));
+
+    // Storage order does not matter when entities are paired by ID
+    let reversed = Variant {
+        reverse: true,
+        ..Default::default()
+    };
+    let second = mesh(dir.path(), "reversed.e", &reversed);
+    let report = diff_files(&first, &second, &DiffOptions::default()).unwrap();
+    assert!(!report.has_differences(), "{}", report);
+}
+
+#[test]
+fn test_diff_tolerances() {
+    let dir = TempDir::new().unwrap();
+    let first = mesh(dir.path(), "first.e", &Variant::default());
+    let shifted = Variant {
+        temp_offset: 1e-3,
+        ..Default::default()
+    };
+    let second = mesh(dir.path(), "shifted.e", &shifted);
+
+    let report = diff_files(&first, &second, &DiffOptions::default()).unwrap();
+    assert!(report.has_differences());
+    assert!(report.structure.is_empty());
+    assert_eq!(report.variables.len(), 3);
+    for (step, field) in report.variables.iter().enumerate() {
+        assert_eq!(field.var_type, EntityType::Nodal);
+        assert_eq!(field.name, "temp");
+        assert_eq!(field.step, Some(step));
+        assert_eq!(field.num_exceeded, 6);
+    }
+    // The largest relative difference is on the smallest value, node 1 at
+    // time 0
+    let max = report.variables[0].max;
+    assert_eq!((max.index, max.id), (0, 1));
+    assert!((max.difference - 1e-3 / 1.001).abs() < 1e-12);
+    assert!(report.to_string().ends_with("Files differ"));
+
+    // A looser tolerance for the temperature only, matched ignoring case
+    let mut options = DiffOptions::default();
+    options
+        .variable_tolerances
+        .insert("TEMP".into(), Tolerance::absolute(2e-3));
+    assert!(!diff_files(&first, &second, &options)
+        .unwrap()
+        .has_differences());
+    options
+        .variable_tolerances
+        .insert("TEMP".into(), Tolerance::ignore());
+    assert!(!diff_files(&first, &second, &options)
+        .unwrap()
+        .has_differences());
+
+    // A floor above every temperature hides the shift too
+    let options = DiffOptions {
+        tolerance: Tolerance::relative(1e-6).with_floor(10.0),
+        ..Default::default()
+    };
+    assert!(!diff_files(&first, &second, &options)
+        .unwrap()
+        .has_differences());
+}
+
+#[test]
+fn test_diff_coordinate_matching() {
+    let dir = TempDir::new().unwrap();
+    let first = mesh(dir.path(), "first.e", &Variant::default());
+    let renumbered = Variant {
+        reverse: true,
+        id_offset: 100,
+        ..Default::default()
+    };
+    let second = mesh(dir.path(), "renumbered.e", &renumbered);
+
+    // No IDs are shared, so nothing pairs by ID
+    let report = diff_files(&first, &second, &DiffOptions::default()).unwrap();
+    assert!(report
+        .structure
+        .iter()
+        .any(|message| message.starts_with("6 nodes")));
+
+    let options = DiffOptions {
+        entity_matching: EntityMatching::Coordinates { tolerance: 1e-6 },
+        ..Default::default()
+    };
+    let report = diff_files(&first, &second, &options).unwrap();
+    assert!(!report.has_differences(), "{}", report);
+}
+
+#[test]
+fn test_diff_time_matching() {
+    let dir = TempDir::new().unwrap();
+    let first =
+        ExodusFile::<mode::Read>::open(mesh(dir.path(), "first.e", &Variant::default())).unwrap();
+    let coarse = Variant {
+        times: vec![0.0, 2.0],
+        ..Default::default()
+    };
+    let second = ExodusFile::<mode::Read>::open(mesh(dir.path(), "coarse.e", &coarse)).unwrap();
+
+    // Time 1 has no step in the second file
+    let options = DiffOptions::default();
+    let report = diff(&first, &second, &options).unwrap();
+    assert!(report.variables.is_empty());
+    assert_eq!(report.unmatched_steps, vec![1]);
+    assert_eq!(report.num_steps_compared, 2);
+
+    // Interpolating to time 1 reproduces the linear variables
+    let options = DiffOptions {
+        time_matching: TimeMatching::Interpolate,
+        ..Default::default()
+    };
+    let report = diff(&first, &second, &options).unwrap();
+    assert!(report.unmatched_steps.is_empty());
+    assert_eq!(report.num_steps_compared, 3);
+    assert!(report.variables.is_empty(), "{}", report);
+
+    // By index, step 2 of the first file has time 1 against 2
+    let options = DiffOptions {
+        time_matching: TimeMatching::Index,
+        ..Default::default()
+    };
+    let report = diff(&first, &second, &options).unwrap();
+    assert!(report.structure.contains(&"Time steps: 3 vs 2".to_string()));
+    assert_eq!(report.times.len(), 1);
+    assert_eq!(report.times[0].index, 1);
+    assert!(!report.variables.is_empty());
+    assert_eq!(report.unmatched_steps, vec![2]);
+}
+
+#[test]
+fn test_diff_structure() {
+    let dir = TempDir::new().unwrap();
+    let first = mesh(dir.path(), "first.e", &Variant::default());
+    let extra = Variant {
+        pressure: true,
+        ..Default::default()
+    };
+    let second = mesh(dir.path(), "extra.e", &extra);
+
+    let report = diff_files(&first, &second, &DiffOptions::default()).unwrap();
+    assert_eq!(
+        report.structure,
+        vec!["nodal variable pressure is missing from the first file"]
+    );
+    assert!(report.variables.is_empty());
+    assert!(report.to_string().starts_with("Structure:\n"));
+
+    assert!(diff_files(&first, dir.path().join("absent.e"), &DiffOptions::default()).is_err());
+}
@@ -3,6 +3,8 @@
 //! This module tests boundary conditions, error handling, and edge cases
 //! to ensure robust behavior in production scenarios.
 
+#![cfg(feature = "netcdf4")]
+
 use exodus_rs::types::{Block, CreateMode, CreateOptions, EntityType, InitParams};
 use exodus_rs::ExodusFile;
 use tempfile::NamedTempFile;
@@ -0,0 +1,71 @@
+//! Tests for converting between Gmsh meshes and Exodus files
+//!
+//! The Gmsh mesh is a 2x1 grid of QUAD4 elements in a named surface group,
+//! with a curve group along the bottom edge and a point group on one corner.
+
+#![cfg(feature = "netcdf4")]
+
+use exodus_rs::gmsh::{export_gmsh, import_gmsh, GmshMesh, GmshVersion};
+use exodus_rs::{mode, EntityType, ExodusFile};
+use tempfile::TempDir;
+
+const PLATE: &str = r#"$MeshFormat
+2.2 0 8
+$EndMeshFormat
+$PhysicalNames
+3
+0 3 "corner"
+1 2 "bottom"
+2 1 "plate"
+$EndPhysicalNames
+$Nodes
+6
+1 0 0 0
+2 1 0 0
+3 2 0 0
+4 0 1 0
+5 1 1 0
+6 2 1 0
+$EndNodes
+$Elements
+5
+1 15 2 3 1 4
+2 1 2 2 1 1 2
+3 1 2 2 1 2 3
+4 3 2 1 1 1 2 5 4
+5 3 2 1 1 2 3 6 5
+$EndElements
+"#;
+
+#[test]
+fn test_gmsh_import_and_export() {
+    let dir = TempDir::new().unwrap();
+    let msh = dir.path().join("plate.msh");
+    let exo = dir.path().join("plate.e");
+    std::fs::write(&msh, PLATE).unwrap();
+
+    let summary = import_gmsh(&msh, &exo).unwrap();
+    assert_eq!(
+        (summary.num_nodes, summary.num_elems, summary.num_blocks),
+        (6, 2, 1)
+    );
+    assert_eq!((summary.num_node_sets, summary.num_side_sets), (1, 1));
+
+    let file = ExodusFile::<mode::Read>::open(&exo).unwrap();
+    assert_eq!(file.init_params().unwrap().num_dim, 2);
+    assert_eq!(file.block(1).unwrap().topology, "QUAD4");
+    assert_eq!(file.connectivity(1).unwrap(), vec![1, 2, 5, 4, 2, 3, 6, 5]);
+    assert_eq!(file.names(EntityType::ElemBlock).unwrap(), vec!["plate"]);
+    let bottom = file.side_set(2).unwrap();
+    assert_eq!((bottom.elements, bottom.sides), (vec![1, 2], vec![1, 1]));
+    assert_eq!(file.names(EntityType::SideSet).unwrap(), vec!["bottom"]);
+    assert_eq!(file.node_set(3).unwrap().nodes, vec![4]);
+    drop(file);
+
+    // Back to Gmsh: the same groups, with the sides as line elements
+    let msh4 = dir.path().join("plate4.msh");
+    let summary = export_gmsh(&exo, &msh4, GmshVersion::V4).unwrap();
+    assert_eq!(summary.num_side_sets, 1);
+    let mesh = GmshMesh::read(&msh4).unwrap();
+    assert_eq!(mesh, GmshMesh::parse(PLATE).unwrap());
+}
@@ -0,0 +1,165 @@
+//! Tests for in-memory Exodus files.
+//!
+//! Reading from a byte buffer works with and without the `netcdf4` feature;
+//! creating and appending require NetCDF.
+
+use exodus_rs::{mode, EntityType, ExodusFile};
+use std::path::PathBuf;
+
+fn data_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("data")
+        .join(name)
+}
+
+#[test]
+fn test_open_from_bytes() {
+    let bytes = std::fs::read(data_path("two_blocks.e")).unwrap();
+    let file = ExodusFile::<mode::Read>::open_from_bytes(bytes.clone()).unwrap();
+    assert!(file.is_in_memory());
+    assert_eq!(file.path(), PathBuf::new());
+
+    let params = file.init_params().unwrap();
+    assert_eq!(params.num_nodes, 2541);
+    assert_eq!(file.block_ids(EntityType::ElemBlock).unwrap(), vec![1, 2]);
+    assert_eq!(file.coords::<f64>().unwrap().x.len(), 2541);
+
+    assert_eq!(file.into_bytes().unwrap(), bytes);
+}
+
+#[test]
+fn test_disk_file_into_bytes() {
+    let path = data_path("two_blocks.e");
+    let file = ExodusFile::<mode::Read>::open(&path).unwrap();
+    assert!(!file.is_in_memory());
+    assert_eq!(file.into_bytes().unwrap(), std::fs::read(&path).unwrap());
+}
+
+#[test]
+fn test_open_from_invalid_bytes() {
+    assert!(ExodusFile::<mode::Read>::open_from_bytes(b"not an exodus file".to_vec()).is_err());
+}
+
+#[cfg(not(feature = "netcdf4"))]
+#[test]
+fn test_in_memory_write_requires_netcdf4() {
+    use exodus_rs::{CreateOptions, ExodusError};
+
+    assert!(matches!(
+        ExodusFile::create_in_memory(CreateOptions::default()),
+        Err(ExodusError::UnsupportedFeature(_))
+    ));
+    let bytes = std::fs::read(data_path("two_blocks.e")).unwrap();
+    assert!(matches!(
+        ExodusFile::<mode::Append>::open_from_bytes(bytes),
+        Err(ExodusError::UnsupportedFeature(_))
+    ));
+}
+
+#[cfg(feature = "netcdf4")]
+mod with_netcdf {
+    use super::*;
+    use exodus_rs::{Block, CreateOptions, FileFormat, InitParams, Int64Mode};
+
+    fn write_quad(file: &mut ExodusFile<mode::Write>) {
+        file.init(&InitParams {
+            title: "In memory".into(),
+            num_dim: 2,
+            num_nodes: 4,
+            num_elems: 1,
+            num_elem_blocks: 1,
+            ..Default::default()
+        })
+        .unwrap();
+        file.put_coords(&[0.0, 1.0, 1.0, 0.0], Some(&[0.0, 0.0, 1.0, 1.0]), None)
+            .unwrap();
+        file.put_block(&Block {
+            id: 1,
+            entity_type: EntityType::ElemBlock,
+            topology: "QUAD4".into(),
+            num_entries: 1,
+            num_nodes_per_entry: 4,
+            num_edges_per_entry: 0,
+            num_faces_per_entry: 0,
+            num_attributes: 0,
+        })
+        .unwrap();
+        file.put_connectivity(1, &[1, 2, 3, 4]).unwrap();
+        file.define_variables(EntityType::Nodal, &["temp"]).unwrap();
+        file.put_time(0, 0.0).unwrap();
+        file.put_var(0, EntityType::Nodal, 0, 0, &[1.0, 2.0, 3.0, 4.0])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_create_in_memory_roundtrip() {
+        for format in [FileFormat::NetCdf4, FileFormat::NetCdf364BitOffset] {
+            let mut file = ExodusFile::create_in_memory(CreateOptions {
+                format,
+                int64_mode: Int64Mode::Int32,
+                ..Default::default()
+            })
+            .unwrap();
+            assert!(file.is_in_memory());
+            write_quad(&mut file);
+            let bytes = file.into_bytes().unwrap();
+
+            let file = ExodusFile::<mode::Read>::open_from_bytes(bytes).unwrap();
+            assert_eq!(file.format().unwrap(), format);
+            assert_eq!(file.init_params().unwrap().title, "In memory");
+            assert_eq!(file.connectivity(1).unwrap(), vec![1, 2, 3, 4]);
+            assert_eq!(
+                file.var(0, EntityType::Nodal, 0, 0).unwrap(),
+                vec![1.0, 2.0, 3.0, 4.0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_append_from_bytes() {
+        let mut file = ExodusFile::create_in_memory(CreateOptions::default()).unwrap();
+        write_quad(&mut file);
+        let bytes = file.into_bytes().unwrap();
+
+        let mut file = ExodusFile::<mode::Append>::open_from_bytes(bytes).unwrap();
+        file.put_time(1, 0.5).unwrap();
+        file.put_var(1, EntityType::Nodal, 0, 0, &[5.0, 6.0, 7.0, 8.0])
+            .unwrap();
+        let bytes = file.into_bytes().unwrap();
+
+        let file = ExodusFile::<mode::Read>::open_from_bytes(bytes).unwrap();
+        assert_eq!(file.times().unwrap(), vec![0.0, 0.5]);
+        assert_eq!(
+            file.var(1, EntityType::Nodal, 0, 0).unwrap(),
+            vec![5.0, 6.0, 7.0, 8.0]
+        );
+    }
+
+    #[test]
+    fn test_in_memory_drop_and_invalid_bytes() {
+        // Closing without collecting the bytes releases the library's memory
+        let mut file = ExodusFile::create_in_memory(CreateOptions::default()).unwrap();
+        write_quad(&mut file);
+        drop(file);
+
+        // The copy handed to NetCDF is released when opening fails
+        assert!(
+            ExodusFile::<mode::Append>::open_from_bytes(b"not an exodus file".to_vec()).is_err()
+        );
+        assert!(ExodusFile::<mode::Append>::open_from_bytes(Vec::new()).is_err());
+        assert!(ExodusFile::<mode::Read>::open_from_bytes(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_in_memory_bytes_match_disk_file() {
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        let mut file = ExodusFile::create_in_memory(CreateOptions::default()).unwrap();
+        write_quad(&mut file);
+        std::fs::write(tmp.path(), file.into_bytes().unwrap()).unwrap();
+
+        let file = ExodusFile::<mode::Read>::open(tmp.path()).unwrap();
+        assert_eq!(file.init_params().unwrap().num_nodes, 4);
+    }
+}
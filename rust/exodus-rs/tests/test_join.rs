@@ -0,0 +1,265 @@
+//! Tests for joining decomposed files
+//!
+//! A 2x1 quad mesh (global nodes 1-3 along the bottom, 4-6 along the top) is
+//! split onto two processors. Processor 0 owns element 2 and processor 1 owns
+//! element 1, so joining has to reorder elements into global ID order. The
+//! shared nodes 2 and 5 are stored on both processors.
+
+#![cfg(feature = "netcdf4")]
+
+use approx::assert_abs_diff_eq;
+use exodus_rs::join::{decomposed_file_name, find_decomposed_files, join_files, JoinOptions};
+use exodus_rs::{
+    mode, Block, CreateMode, CreateOptions, EntityType, ExodusError, ExodusFile, GlobalInitParams,
+    InitParams, NemesisFileType, NemesisInitInfo,
+};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const TIMES: [f64; 3] = [0.0, 0.5, 1.0];
+
+/// Write the part of processor `rank`
+fn write_part(path: &Path, rank: usize) {
+    let (nodes, elem): ([i64; 4], i64) = if rank == 0 {
+        ([3, 6, 5, 2], 2)
+    } else {
+        ([1, 2, 5, 4], 1)
+    };
+    let mut file = ExodusFile::create(
+        path,
+        CreateOptions {
+            mode: CreateMode::Clobber,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    file.init(&InitParams {
+        title: "Decomposed".into(),
+        num_dim: 2,
+        num_nodes: 4,
+        num_elems: 1,
+        num_elem_blocks: 1,
+        num_node_sets: 1,
+        num_side_sets: 1,
+        ..Default::default()
+    })
+    .unwrap();
+    let x: Vec<f64> = nodes.iter().map(|&n| ((n - 1) % 3) as f64).collect();
+    let y: Vec<f64> = nodes.iter().map(|&n| ((n - 1) / 3) as f64).collect();
+    file.put_coords(&x, Some(&y), None).unwrap();
+    file.put_id_map(EntityType::NodeMap, &nodes).unwrap();
+    file.put_id_map(EntityType::ElemMap, &[elem]).unwrap();
+
+    file.put_block(&Block {
+        id: 100,
+        entity_type: EntityType::ElemBlock,
+        topology: "QUAD4".into(),
+        num_entries: 1,
+        num_nodes_per_entry: 4,
+        num_edges_per_entry: 0,
+        num_faces_per_entry: 0,
+        num_attributes: 0,
+    })
+    .unwrap();
+    // Counter-clockwise from the lower left corner
+    let connectivity = if rank == 0 {
+        [4, 1, 2, 3]
+    } else {
+        [1, 2, 3, 4]
+    };
+    file.put_connectivity(100, &connectivity).unwrap();
+    file.put_names(EntityType::ElemBlock, &["solid"]).unwrap();
+
+    // Bottom nodes and sides, with node IDs as distribution factors
+    let (set_nodes, set_df) = if rank == 0 {
+        (vec![4, 1], vec![2.0, 3.0])
+    } else {
+        (vec![1, 2], vec![1.0, 2.0])
+    };
+    file.put_node_set(10, &set_nodes, Some(&set_df)).unwrap();
+    file.put_side_set(20, &[1], &[1], Some(&[elem as f64, elem as f64]))
+        .unwrap();
+
+    file.put_init_info(&NemesisInitInfo {
+        num_procs: 2,
+        num_procs_in_file: 1,
+        file_type: NemesisFileType::Parallel,
+    })
+    .unwrap();
+    file.put_init_global(&GlobalInitParams {
+        num_nodes: 6,
+        num_elems: 2,
+        num_elem_blocks: 1,
+        num_node_sets: 1,
+        num_side_sets: 1,
+    })
+    .unwrap();
+
+    file.define_variables(EntityType::Global, &["energy"])
+        .unwrap();
+    file.define_variables(EntityType::Nodal, &["temp", "pressure"])
+        .unwrap();
+    file.define_variables(EntityType::ElemBlock, &["stress"])
+        .unwrap();
+    file.define_variables(EntityType::NodeSet, &["flux"])
+        .unwrap();
+    for (step, &time) in TIMES.iter().enumerate() {
+        let s = step as f64;
+        file.put_time(step, time).unwrap();
+        file.put_var(step, EntityType::Global, 0, 0, &[10.0 * s])
+            .unwrap();
+        let temp: Vec<f64> = nodes.iter().map(|&n| n as f64 + s).collect();
+        file.put_var(step, EntityType::Nodal, 0, 0, &temp).unwrap();
+        let pressure: Vec<f64> = nodes.iter().map(|&n| -(n as f64)).collect();
+        file.put_var(step, EntityType::Nodal, 0, 1, &pressure)
+            .unwrap();
+        file.put_var(
+            step,
+            EntityType::ElemBlock,
+            100,
+            0,
+            &[elem as f64 * 100.0 + s],
+        )
+        .unwrap();
+        let flux: Vec<f64> = set_df.iter().map(|&n| n * s).collect();
+        file.put_var(step, EntityType::NodeSet, 10, 0, &flux)
+            .unwrap();
+    }
+}
+
+fn write_parts(dir: &Path) -> (PathBuf, Vec<PathBuf>) {
+    let base = dir.join("mesh.e");
+    let parts: Vec<PathBuf> = (0..2)
+        .map(|rank| decomposed_file_name(&base, 2, rank))
+        .collect();
+    for (rank, path) in parts.iter().enumerate() {
+        write_part(path, rank);
+    }
+    (base, parts)
+}
+
+fn clobber() -> JoinOptions {
+    JoinOptions {
+        create_options: CreateOptions {
+            mode: CreateMode::Clobber,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_join_mesh_and_variables() {
+    let dir = TempDir::new().unwrap();
+    let (base, parts) = write_parts(dir.path());
+    assert_eq!(find_decomposed_files(&base).unwrap(), parts);
+
+    let output = dir.path().join("joined.e");
+    let summary = join_files(&parts, &output, &clobber()).unwrap();
+    assert_eq!(summary.num_parts, 2);
+    assert_eq!(summary.num_nodes, 6);
+    assert_eq!(summary.num_elems, 2);
+    assert_eq!(summary.num_time_steps, 3);
+
+    let file = ExodusFile::<mode::Read>::open(&output).unwrap();
+    assert_eq!(file.init_params().unwrap().title, "Decomposed");
+    assert_eq!(
+        file.id_map(EntityType::NodeMap).unwrap(),
+        vec![1, 2, 3, 4, 5, 6]
+    );
+    assert_eq!(file.id_map(EntityType::ElemMap).unwrap(), vec![1, 2]);
+    let coords = file.coords::<f64>().unwrap();
+    assert_eq!(coords.x, vec![0.0, 1.0, 2.0, 0.0, 1.0, 2.0]);
+    assert_eq!(coords.y, vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+
+    assert_eq!(file.block(100).unwrap().num_entries, 2);
+    assert_eq!(
+        file.connectivity(100).unwrap(),
+        vec![1, 2, 5, 4, 2, 3, 6, 5]
+    );
+    assert_eq!(file.names(EntityType::ElemBlock).unwrap(), vec!["solid"]);
+
+    let node_set = file.node_set(10).unwrap();
+    assert_eq!(node_set.nodes, vec![1, 2, 3]);
+    assert_eq!(node_set.dist_factors, vec![1.0, 2.0, 3.0]);
+    let side_set = file.side_set(20).unwrap();
+    assert_eq!(side_set.elements, vec![1, 2]);
+    assert_eq!(side_set.sides, vec![1, 1]);
+    assert_eq!(side_set.dist_factors, vec![1.0, 1.0, 2.0, 2.0]);
+
+    assert_eq!(file.times().unwrap(), TIMES.to_vec());
+    assert_eq!(file.var(2, EntityType::Global, 0, 0).unwrap(), vec![20.0]);
+    let temp = file.var(1, EntityType::Nodal, 0, 0).unwrap();
+    for (n, value) in temp.iter().enumerate() {
+        assert_abs_diff_eq!(*value, n as f64 + 2.0);
+    }
+    assert_eq!(
+        file.var(2, EntityType::ElemBlock, 100, 0).unwrap(),
+        vec![102.0, 202.0]
+    );
+    assert_eq!(
+        file.var(2, EntityType::NodeSet, 10, 0).unwrap(),
+        vec![2.0, 4.0, 6.0]
+    );
+}
+
+#[test]
+fn test_join_step_and_variable_subset() {
+    let dir = TempDir::new().unwrap();
+    let (_, parts) = write_parts(dir.path());
+    let output = dir.path().join("subset.e");
+
+    let options = JoinOptions {
+        steps: Some(vec![2, 0]),
+        variables: Some(vec!["TEMP".into(), "stress".into()]),
+        ..clobber()
+    };
+    let summary = join_files(&parts, &output, &options).unwrap();
+    assert_eq!(summary.num_time_steps, 2);
+
+    let file = ExodusFile::<mode::Read>::open(&output).unwrap();
+    assert_eq!(file.times().unwrap(), vec![1.0, 0.0]);
+    assert!(file.variable_names(EntityType::Global).unwrap().is_empty());
+    assert_eq!(
+        file.variable_names(EntityType::Nodal).unwrap(),
+        vec!["temp"]
+    );
+    assert_eq!(
+        file.var(1, EntityType::Nodal, 0, 0).unwrap(),
+        vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+    );
+    assert_eq!(
+        file.var(0, EntityType::ElemBlock, 100, 0).unwrap(),
+        vec![102.0, 202.0]
+    );
+}
+
+#[test]
+fn test_join_errors() {
+    let dir = TempDir::new().unwrap();
+    let (base, parts) = write_parts(dir.path());
+    let output = dir.path().join("bad.e");
+
+    let unknown = JoinOptions {
+        variables: Some(vec!["velocity".into()]),
+        ..clobber()
+    };
+    assert!(matches!(
+        join_files(&parts, &output, &unknown),
+        Err(ExodusError::VariableNotDefined(name)) if name == "velocity"
+    ));
+    let bad_step = JoinOptions {
+        steps: Some(vec![3]),
+        ..clobber()
+    };
+    assert!(matches!(
+        join_files(&parts, &output, &bad_step),
+        Err(ExodusError::InvalidTimeStep(3))
+    ));
+
+    // A missing part is caught by the Nemesis global counts
+    assert!(join_files(&parts[..1], &output, &clobber()).is_err());
+    std::fs::remove_file(&parts[1]).unwrap();
+    assert!(find_decomposed_files(&base).is_err());
+    assert!(join_files::<PathBuf>(&[], &output, &clobber()).is_err());
+}
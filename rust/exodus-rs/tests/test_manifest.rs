@@ -0,0 +1,151 @@
+//! Tests for mesh metadata manifests
+//!
+//! The manifest of a small annotated mesh is checked against what was
+//! written, and with the `serde` feature saved to JSON and loaded again.
+
+#![cfg(feature = "netcdf4")]
+
+use exodus_rs::manifest::MeshManifest;
+use exodus_rs::{
+    mode, Block, CreateMode, CreateOptions, EntityType, ExodusFile, InitParams, QaRecord,
+};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Write a single QUAD4 with a named block, two node sets (one named), a
+/// side set, global and nodal variables, and two time steps
+fn write_mesh(dir: &Path) -> PathBuf {
+    let path = dir.join("manifest.e");
+    let mut file = ExodusFile::create(
+        &path,
+        CreateOptions {
+            mode: CreateMode::Clobber,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    file.init(&InitParams {
+        title: "Manifest mesh".into(),
+        num_dim: 2,
+        num_nodes: 4,
+        num_elems: 1,
+        num_elem_blocks: 1,
+        num_node_sets: 2,
+        num_side_sets: 1,
+        ..Default::default()
+    })
+    .unwrap();
+    file.put_coords(&[0.0, 1.0, 1.0, 0.0], Some(&[0.0, 0.0, 1.0, 1.0]), None)
+        .unwrap();
+    file.put_block(&Block {
+        id: 7,
+        entity_type: EntityType::ElemBlock,
+        topology: "QUAD4".into(),
+        num_entries: 1,
+        num_nodes_per_entry: 4,
+        num_edges_per_entry: 0,
+        num_faces_per_entry: 0,
+        num_attributes: 1,
+    })
+    .unwrap();
+    file.put_connectivity(7, &[1, 2, 3, 4]).unwrap();
+    file.put_block_attributes(7, &[0.1]).unwrap();
+    file.put_block_attribute_names(7, &["thickness"]).unwrap();
+    file.put_names(EntityType::ElemBlock, &["plate"]).unwrap();
+
+    file.put_node_set(1, &[1, 4], Some(&[1.0, 1.0])).unwrap();
+    file.put_node_set(2, &[2, 3], None).unwrap();
+    file.put_names(EntityType::NodeSet, &["left", ""]).unwrap();
+    file.put_side_set(3, &[1], &[1], None).unwrap();
+
+    file.put_qa_records(&[QaRecord {
+        code_name: "mesher".into(),
+        code_version: "2.0".into(),
+        date: "2024-06-01".into(),
+        time: "08:00:00".into(),
+    }])
+    .unwrap();
+    file.put_info_records(&["generated for tests".to_string()])
+        .unwrap();
+
+    file.define_variables(EntityType::Global, &["energy"])
+        .unwrap();
+    file.define_variables(EntityType::Nodal, &["temp", "pressure"])
+        .unwrap();
+    for (step, time) in [0.0, 0.25].into_iter().enumerate() {
+        file.put_time(step, time).unwrap();
+        file.put_var(step, EntityType::Global, 0, 0, &[time])
+            .unwrap();
+        file.put_var(step, EntityType::Nodal, 0, 0, &[time; 4])
+            .unwrap();
+        file.put_var(step, EntityType::Nodal, 0, 1, &[0.0; 4])
+            .unwrap();
+    }
+    path
+}
+
+#[test]
+fn test_manifest_from_file() {
+    let dir = TempDir::new().unwrap();
+    let file = ExodusFile::<mode::Read>::open(write_mesh(dir.path())).unwrap();
+    let manifest = MeshManifest::from_file(&file).unwrap();
+
+    assert_eq!(manifest.params.title, "Manifest mesh");
+    assert_eq!(manifest.params.num_nodes, 4);
+    assert_eq!(manifest.blocks.len(), 1);
+    assert_eq!(manifest.blocks[0].name, "plate");
+    assert_eq!(manifest.blocks[0].block.id, 7);
+    assert_eq!(manifest.blocks[0].block.topology, "QUAD4");
+    assert_eq!(manifest.blocks[0].attribute_names, vec!["thickness"]);
+
+    let sets: Vec<(EntityType, i64, &str, usize, usize)> = manifest
+        .sets
+        .iter()
+        .map(|entry| {
+            (
+                entry.set.entity_type,
+                entry.set.id,
+                entry.name.as_str(),
+                entry.set.num_entries,
+                entry.set.num_dist_factors,
+            )
+        })
+        .collect();
+    assert_eq!(
+        sets,
+        vec![
+            (EntityType::NodeSet, 1, "left", 2, 2),
+            (EntityType::NodeSet, 2, "", 2, 0),
+            (EntityType::SideSet, 3, "", 1, 0),
+        ]
+    );
+
+    assert_eq!(manifest.variable_names(EntityType::Global), ["energy"]);
+    assert_eq!(
+        manifest.variable_names(EntityType::Nodal),
+        ["temp", "pressure"]
+    );
+    assert!(manifest.variable_names(EntityType::ElemBlock).is_empty());
+    assert_eq!(manifest.variables.len(), 2);
+    assert_eq!(manifest.times, vec![0.0, 0.25]);
+    assert_eq!(manifest.qa_records[0].code_name, "mesher");
+    assert_eq!(manifest.info_records, vec!["generated for tests"]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_manifest_json_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    let file = ExodusFile::<mode::Read>::open(write_mesh(dir.path())).unwrap();
+    let manifest = MeshManifest::from_file(&file).unwrap();
+
+    let json_path = dir.path().join("manifest.json");
+    manifest.write_json(&json_path).unwrap();
+    let loaded = MeshManifest::read_json(&json_path).unwrap();
+    assert_eq!(loaded.to_json().unwrap(), manifest.to_json().unwrap());
+    assert_eq!(loaded.blocks[0].block.entity_type, EntityType::ElemBlock);
+    assert_eq!(loaded.sets[2].set.entity_type, EntityType::SideSet);
+    assert_eq!(loaded.qa_records, manifest.qa_records);
+
+    assert!(MeshManifest::read_json(dir.path().join("absent.json")).is_err());
+}
@@ -1,5 +1,7 @@
 //! Tests for Phase 2: Metadata operations (QA and Info records)
 
+#![cfg(feature = "netcdf4")]
+
 use exodus_rs::{mode, CreateMode, CreateOptions, ExodusFile, InitParams, QaRecord};
 use tempfile::NamedTempFile;
 
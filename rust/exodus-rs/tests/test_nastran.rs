@@ -0,0 +1,59 @@
+//! Tests for importing Nastran bulk data
+//!
+//! The bulk data is two CTETRA elements sharing a face, in two properties,
+//! with grid and element IDs that are not consecutive.
+
+#![cfg(feature = "netcdf4")]
+
+use exodus_rs::nastran::import_nastran;
+use exodus_rs::{mode, EntityType, ExodusFile};
+use tempfile::TempDir;
+
+const BULK: &str = "\
+$ Two tetrahedra
+BEGIN BULK
+GRID,10,,0.,0.,0.
+GRID,20,,1.,0.,0.
+GRID,30,,0.,1.,0.
+GRID,40,,0.,0.,1.
+GRID,50,,0.,0.,-1.
+CTETRA,7,1,10,20,30,40
+CTETRA,9,2,10,30,20,50
+PSOLID,1,1
+PSOLID,2,1
+ENDDATA
+";
+
+#[test]
+fn test_import_nastran() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("tets.bdf");
+    std::fs::write(&input, BULK).unwrap();
+    let output = dir.path().join("tets.e");
+
+    let summary = import_nastran(&input, &output).unwrap();
+    assert_eq!(
+        (summary.num_nodes, summary.num_elems, summary.num_blocks),
+        (5, 2, 2)
+    );
+
+    let file = ExodusFile::<mode::Read>::open(&output).unwrap();
+    assert_eq!(file.block_ids(EntityType::ElemBlock).unwrap(), vec![1, 2]);
+    assert_eq!(file.block(2).unwrap().topology, "TETRA4");
+    assert_eq!(file.connectivity(2).unwrap(), vec![1, 3, 2, 5]);
+    assert_eq!(
+        file.id_map(EntityType::NodeMap).unwrap(),
+        vec![10, 20, 30, 40, 50]
+    );
+    assert_eq!(file.id_map(EntityType::ElemMap).unwrap(), vec![7, 9]);
+    let coords = file.coords::<f64>().unwrap();
+    assert_eq!(coords.z[4], -1.0);
+}
+
+#[test]
+fn test_import_nastran_undefined_grid() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("bad.nas");
+    std::fs::write(&input, "GRID,1,,0.,0.,0.\nCBAR,1,1,1,2\n").unwrap();
+    assert!(import_nastran(&input, dir.path().join("bad.e")).is_err());
+}
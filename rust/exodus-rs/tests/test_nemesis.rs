@@ -0,0 +1,304 @@
+//! Tests for Nemesis parallel metadata
+//!
+//! A 2x1 quad mesh (6 nodes) is decomposed onto two processors, one element
+//! each, and written file-per-processor as `mesh.e.2.0` and `mesh.e.2.1`.
+//! The shared nodes 2 and 5 are border nodes on both processors.
+
+#![cfg(feature = "netcdf4")]
+
+use exodus_rs::{
+    mode, Block, CommMapParams, CreateMode, CreateOptions, ElemCommMap, EntityType, ExodusError,
+    ExodusFile, FileFormat, GlobalEntityParams, GlobalInitParams, InitParams, LoadBalanceParams,
+    NemesisFileType, NemesisInitInfo, NodeCommMap, ProcessorElemMaps, ProcessorNodeMaps,
+};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Write the local mesh and Nemesis data of processor `proc`
+fn write_processor(path: &Path, proc: usize, format: FileFormat) {
+    let other = (1 - proc) as i64;
+    let mut file = ExodusFile::create(
+        path,
+        CreateOptions {
+            mode: CreateMode::Clobber,
+            format,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    file.init(&InitParams {
+        title: format!("Processor {}", proc),
+        num_dim: 2,
+        num_nodes: 4,
+        num_elems: 1,
+        num_elem_blocks: 1,
+        num_node_sets: 1,
+        ..Default::default()
+    })
+    .unwrap();
+    let x0 = proc as f64;
+    file.put_coords(
+        &[x0, x0 + 1.0, x0 + 1.0, x0],
+        Some(&[0.0, 0.0, 1.0, 1.0]),
+        None,
+    )
+    .unwrap();
+    file.put_block(&Block {
+        id: 1,
+        entity_type: EntityType::ElemBlock,
+        topology: "QUAD4".into(),
+        num_entries: 1,
+        num_nodes_per_entry: 4,
+        num_edges_per_entry: 0,
+        num_faces_per_entry: 0,
+        num_attributes: 0,
+    })
+    .unwrap();
+    file.put_connectivity(1, &[1, 2, 3, 4]).unwrap();
+    let node_map: Vec<i64> = if proc == 0 {
+        vec![1, 2, 5, 4]
+    } else {
+        vec![2, 3, 6, 5]
+    };
+    file.put_id_map(EntityType::NodeMap, &node_map).unwrap();
+    file.put_id_map(EntityType::ElemMap, &[proc as i64 + 1])
+        .unwrap();
+
+    file.put_init_info(&NemesisInitInfo {
+        num_procs: 2,
+        num_procs_in_file: 1,
+        file_type: NemesisFileType::Parallel,
+    })
+    .unwrap();
+    file.put_init_global(&GlobalInitParams {
+        num_nodes: 6,
+        num_elems: 2,
+        num_elem_blocks: 1,
+        num_node_sets: 1,
+        num_side_sets: 0,
+    })
+    .unwrap();
+    file.put_global_params(
+        EntityType::ElemBlock,
+        &GlobalEntityParams {
+            ids: vec![1],
+            entry_counts: vec![2],
+            dist_fact_counts: vec![],
+        },
+    )
+    .unwrap();
+    file.put_global_params(
+        EntityType::NodeSet,
+        &GlobalEntityParams {
+            ids: vec![10],
+            entry_counts: vec![2],
+            dist_fact_counts: vec![],
+        },
+    )
+    .unwrap();
+
+    file.put_load_balance_params(&LoadBalanceParams {
+        num_internal_nodes: 2,
+        num_border_nodes: 2,
+        num_external_nodes: 0,
+        num_internal_elems: 0,
+        num_border_elems: 1,
+        num_node_cmaps: 1,
+        num_elem_cmaps: 1,
+    })
+    .unwrap();
+    let (internal, border) = if proc == 0 {
+        (vec![1, 4], vec![2, 3])
+    } else {
+        (vec![2, 3], vec![1, 4])
+    };
+    file.put_processor_node_maps(&ProcessorNodeMaps {
+        internal,
+        border: border.clone(),
+        external: vec![],
+    })
+    .unwrap();
+    file.put_processor_elem_maps(&ProcessorElemMaps {
+        internal: vec![],
+        border: vec![1],
+    })
+    .unwrap();
+    file.put_comm_map_params(&CommMapParams {
+        node_cmap_ids: vec![other],
+        node_cmap_node_counts: vec![2],
+        elem_cmap_ids: vec![other],
+        elem_cmap_elem_counts: vec![1],
+    })
+    .unwrap();
+    file.put_node_comm_map(&NodeCommMap {
+        id: other,
+        node_ids: border,
+        proc_ids: vec![other, other],
+    })
+    .unwrap();
+    file.put_elem_comm_map(&ElemCommMap {
+        id: other,
+        elem_ids: vec![1],
+        side_ids: vec![if proc == 0 { 2 } else { 4 }],
+        proc_ids: vec![other],
+    })
+    .unwrap();
+}
+
+fn check_processor(path: &Path, proc: usize) {
+    let other = (1 - proc) as i64;
+    let file = ExodusFile::<mode::Read>::open(path).unwrap();
+
+    assert_eq!(
+        file.init_info().unwrap(),
+        NemesisInitInfo {
+            num_procs: 2,
+            num_procs_in_file: 1,
+            file_type: NemesisFileType::Parallel,
+        }
+    );
+    let global = file.init_global().unwrap();
+    assert_eq!((global.num_nodes, global.num_elems), (6, 2));
+    assert_eq!(global.num_side_sets, 0);
+
+    let blocks = file.global_params(EntityType::ElemBlock).unwrap();
+    assert_eq!(blocks.ids, vec![1]);
+    assert_eq!(blocks.entry_counts, vec![2]);
+    assert!(blocks.dist_fact_counts.is_empty());
+    let node_sets = file.global_params(EntityType::NodeSet).unwrap();
+    assert_eq!(node_sets.ids, vec![10]);
+    assert_eq!(node_sets.dist_fact_counts, vec![0]);
+    assert_eq!(
+        file.global_params(EntityType::SideSet).unwrap(),
+        GlobalEntityParams::default()
+    );
+
+    let lb = file.load_balance_params().unwrap();
+    assert_eq!(lb.num_border_nodes, 2);
+    assert_eq!(lb.num_internal_elems, 0);
+    assert_eq!(lb.num_node_cmaps, 1);
+
+    let node_maps = file.processor_node_maps().unwrap();
+    assert_eq!(node_maps.internal.len(), 2);
+    assert!(node_maps.external.is_empty());
+    assert_eq!(file.processor_elem_maps().unwrap().border, vec![1]);
+
+    let params = file.comm_map_params().unwrap();
+    assert_eq!(params.node_cmap_ids, vec![other]);
+    assert_eq!(params.node_cmap_node_counts, vec![2]);
+    assert_eq!(params.elem_cmap_elem_counts, vec![1]);
+
+    let node_cmap = file.node_comm_map(other).unwrap();
+    assert_eq!(node_cmap.node_ids, node_maps.border);
+    assert_eq!(node_cmap.proc_ids, vec![other, other]);
+    let elem_cmap = file.elem_comm_map(other).unwrap();
+    assert_eq!(elem_cmap.elem_ids, vec![1]);
+    assert_eq!(elem_cmap.proc_ids, vec![other]);
+
+    assert!(matches!(
+        file.node_comm_map(42),
+        Err(ExodusError::EntityNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_file_per_processor_roundtrip() {
+    for format in [FileFormat::NetCdf4, FileFormat::NetCdf364BitOffset] {
+        let dir = TempDir::new().unwrap();
+        for proc in 0..2 {
+            let path = dir.path().join(format!("mesh.e.2.{}", proc));
+            write_processor(&path, proc, format);
+            check_processor(&path, proc);
+        }
+
+        // Shared nodes map to the same global IDs on both sides
+        let global_border = |proc: usize| {
+            let path = dir.path().join(format!("mesh.e.2.{}", proc));
+            let file = ExodusFile::<mode::Read>::open(path).unwrap();
+            let ids = file.id_map(EntityType::NodeMap).unwrap();
+            let mut border: Vec<i64> = file
+                .processor_node_maps()
+                .unwrap()
+                .border
+                .iter()
+                .map(|&n| ids[n as usize - 1])
+                .collect();
+            border.sort();
+            border
+        };
+        assert_eq!(global_border(0), vec![2, 5]);
+        assert_eq!(global_border(1), vec![2, 5]);
+    }
+}
+
+#[test]
+fn test_nemesis_validation() {
+    let dir = TempDir::new().unwrap();
+    let mut file = ExodusFile::create_default(dir.path().join("bad.e")).unwrap();
+    file.init(&InitParams {
+        title: "Validation".into(),
+        num_dim: 2,
+        num_nodes: 4,
+        ..Default::default()
+    })
+    .unwrap();
+
+    // Load-balance parameters need init info, and only on parallel files
+    assert!(file
+        .put_load_balance_params(&LoadBalanceParams::default())
+        .is_err());
+    file.put_init_info(&NemesisInitInfo {
+        num_procs: 4,
+        num_procs_in_file: 4,
+        file_type: NemesisFileType::Scalar,
+    })
+    .unwrap();
+    assert!(matches!(
+        file.put_load_balance_params(&LoadBalanceParams::default()),
+        Err(ExodusError::UnsupportedFeature(_))
+    ));
+
+    file.put_init_global(&GlobalInitParams {
+        num_nodes: 8,
+        num_elems: 2,
+        num_elem_blocks: 2,
+        ..Default::default()
+    })
+    .unwrap();
+    assert!(file.put_init_global(&GlobalInitParams::default()).is_err());
+    assert!(matches!(
+        file.put_global_params(
+            EntityType::ElemBlock,
+            &GlobalEntityParams {
+                ids: vec![1],
+                entry_counts: vec![2],
+                dist_fact_counts: vec![],
+            }
+        ),
+        Err(ExodusError::InvalidArrayLength { .. })
+    ));
+    assert!(matches!(
+        file.put_global_params(EntityType::ElemSet, &GlobalEntityParams::default()),
+        Err(ExodusError::InvalidEntityType(_))
+    ));
+}
+
+#[test]
+fn test_serial_file_has_no_nemesis_data() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("serial.e");
+    let mut file = ExodusFile::create_default(&path).unwrap();
+    file.init(&InitParams {
+        title: "Serial".into(),
+        num_dim: 2,
+        num_nodes: 4,
+        ..Default::default()
+    })
+    .unwrap();
+    drop(file);
+
+    let file = ExodusFile::<mode::Read>::open(&path).unwrap();
+    assert!(file.init_info().is_err());
+    assert!(file.init_global().is_err());
+    assert_eq!(file.comm_map_params().unwrap(), CommMapParams::default());
+}
@@ -4,8 +4,10 @@
 //! and that node detection works as expected.
 
 use exodus_rs::*;
+#[cfg(feature = "netcdf4")]
 use tempfile::NamedTempFile;
 
+#[cfg(feature = "netcdf4")]
 #[test]
 fn test_file_creation_with_auto_performance() {
     let tmp = NamedTempFile::new().unwrap();
@@ -24,6 +26,7 @@ fn test_file_creation_with_auto_performance() {
     assert!(tmp.path().exists());
 }
 
+#[cfg(feature = "netcdf4")]
 #[test]
 fn test_file_creation_with_conservative_performance() {
     let tmp = NamedTempFile::new().unwrap();
@@ -38,6 +41,7 @@ fn test_file_creation_with_conservative_performance() {
     assert_eq!(file.path(), tmp.path());
 }
 
+#[cfg(feature = "netcdf4")]
 #[test]
 fn test_file_creation_with_aggressive_performance() {
     let tmp = NamedTempFile::new().unwrap();
@@ -52,6 +56,7 @@ fn test_file_creation_with_aggressive_performance() {
     assert_eq!(file.path(), tmp.path());
 }
 
+#[cfg(feature = "netcdf4")]
 #[test]
 fn test_file_creation_with_custom_cache() {
     let tmp = NamedTempFile::new().unwrap();
@@ -70,6 +75,7 @@ fn test_file_creation_with_custom_cache() {
     assert_eq!(file.path(), tmp.path());
 }
 
+#[cfg(feature = "netcdf4")]
 #[test]
 fn test_file_creation_with_custom_preemption() {
     let tmp = NamedTempFile::new().unwrap();
@@ -86,6 +92,7 @@ fn test_file_creation_with_custom_preemption() {
     assert_eq!(file.path(), tmp.path());
 }
 
+#[cfg(feature = "netcdf4")]
 #[test]
 fn test_file_creation_with_none_performance() {
     let tmp = NamedTempFile::new().unwrap();
@@ -101,6 +108,7 @@ fn test_file_creation_with_none_performance() {
     assert_eq!(file.path(), tmp.path());
 }
 
+#[cfg(feature = "netcdf4")]
 #[test]
 fn test_default_create_uses_auto_performance() {
     let tmp = NamedTempFile::new().unwrap();
@@ -115,6 +123,7 @@ fn test_default_create_uses_auto_performance() {
     assert_eq!(file.path(), tmp.path());
 }
 
+#[cfg(feature = "netcdf4")]
 #[test]
 fn test_performance_config_with_mesh_operations() {
     let tmp = NamedTempFile::new().unwrap();
@@ -205,6 +214,7 @@ fn test_performance_config_summary() {
     assert!(summary.contains("Performance Config"));
 }
 
+#[cfg(feature = "netcdf4")]
 #[test]
 #[ignore] // Environment variable tests are racy when run in parallel
 fn test_hdf5_env_vars_set() {
@@ -236,6 +246,7 @@ fn test_hdf5_env_vars_set() {
     assert!(env::var("HDF5_CHUNK_CACHE_NSLOTS").is_ok());
 }
 
+#[cfg(feature = "netcdf4")]
 #[test]
 #[ignore] // Environment variable tests are racy when run in parallel
 fn test_performance_config_respects_user_env_vars() {
@@ -264,6 +275,7 @@ fn test_performance_config_respects_user_env_vars() {
     env::remove_var("HDF5_CHUNK_CACHE_NBYTES");
 }
 
+#[cfg(feature = "netcdf4")]
 #[test]
 fn test_performance_with_large_mesh() {
     let tmp = NamedTempFile::new().unwrap();
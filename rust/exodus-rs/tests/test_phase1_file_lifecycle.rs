@@ -220,6 +220,77 @@ mod file_lifecycle_tests {
         assert_eq!(format, FileFormat::NetCdf4);
     }
 
+    fn create_with_format(path: &std::path::Path, format: FileFormat) {
+        let mut file = ExodusFile::create(
+            path,
+            CreateOptions {
+                mode: CreateMode::Clobber,
+                format,
+                int64_mode: Int64Mode::Int32,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(file.format().unwrap(), format);
+
+        let params = InitParams {
+            title: "Format test".into(),
+            num_dim: 2,
+            num_nodes: 3,
+            ..Default::default()
+        };
+        file.init(&params).unwrap();
+        file.put_coords(&[0.0, 1.0, 0.0], Some(&[0.0, 0.0, 1.0]), None)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_netcdf3_format_roundtrip() {
+        for format in [
+            FileFormat::NetCdf3Classic,
+            FileFormat::NetCdf364BitOffset,
+            FileFormat::NetCdfCdf5,
+            FileFormat::NetCdf4Classic,
+        ] {
+            let tmp = NamedTempFile::new().unwrap();
+            create_with_format(tmp.path(), format);
+
+            let file = ExodusFile::<mode::Read>::open(tmp.path()).unwrap();
+            assert_eq!(file.format().unwrap(), format);
+            let coords = file.coords::<f64>().unwrap();
+            assert_eq!(coords.x, vec![0.0, 1.0, 0.0]);
+            assert_eq!(coords.y, vec![0.0, 0.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn test_netcdf3_rejects_unrepresentable_options() {
+        let tmp = NamedTempFile::new().unwrap();
+
+        let result = ExodusFile::create(
+            tmp.path(),
+            CreateOptions {
+                mode: CreateMode::Clobber,
+                format: FileFormat::NetCdf3Classic,
+                int64_mode: Int64Mode::Int32,
+                compression: Some(Compression::Gzip(4)),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(result, Err(ExodusError::UnsupportedFeature(_))));
+
+        let result = ExodusFile::create(
+            tmp.path(),
+            CreateOptions {
+                mode: CreateMode::Clobber,
+                format: FileFormat::NetCdf364BitOffset,
+                int64_mode: Int64Mode::Int64,
+                ..Default::default()
+            },
+        );
+        assert!(matches!(result, Err(ExodusError::UnsupportedFeature(_))));
+    }
+
     // ========================================================================
     // Version Reading Tests
     // ========================================================================
@@ -200,6 +200,51 @@ mod coordinate_tests {
         assert_eq!(coords.y[3], 3.0_f64);
     }
 
+    #[test]
+    fn test_float32_storage_converts_f64() {
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path();
+
+        {
+            let mut file = ExodusFile::create(
+                path,
+                CreateOptions {
+                    mode: CreateMode::Clobber,
+                    float_size: FloatSize::Float32,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            assert_eq!(file.float_size(), FloatSize::Float32);
+
+            let params = InitParams {
+                title: "f32 Storage".to_string(),
+                num_dim: 2,
+                num_nodes: 3,
+                ..Default::default()
+            };
+            file.init(&params).unwrap();
+
+            let x = vec![0.1_f64, 0.2, 0.3];
+            let y = vec![1.5_f64, 2.5, 3.5];
+            file.put_coords(&x, Some(&y), None).unwrap();
+        }
+
+        let file = ExodusFile::<mode::Read>::open(path).unwrap();
+        assert_eq!(file.float_size(), FloatSize::Float32);
+
+        // Values round-trip through single precision
+        let coords = file.coords::<f64>().unwrap();
+        assert_eq!(
+            coords.x,
+            vec![0.1_f32 as f64, 0.2_f32 as f64, 0.3_f32 as f64]
+        );
+        assert_eq!(coords.y, vec![1.5, 2.5, 3.5]);
+
+        let coords = file.coords::<f32>().unwrap();
+        assert_eq!(coords.x, vec![0.1_f32, 0.2, 0.3]);
+    }
+
     // ========================================================================
     // Type Conversion Tests
     // ========================================================================
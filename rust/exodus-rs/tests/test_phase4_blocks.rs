@@ -1293,4 +1293,89 @@ mod block_tests {
 
         assert_eq!(name, "StructuralBlock");
     }
+
+    // ========================================================================
+    // Integer Storage Tests
+    // ========================================================================
+
+    fn create_int32_file(path: &std::path::Path, format: FileFormat) -> ExodusFile<mode::Write> {
+        let mut file = ExodusFile::create(
+            path,
+            CreateOptions {
+                mode: CreateMode::Clobber,
+                format,
+                int64_mode: Int64Mode::Int32,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let params = InitParams {
+            title: "Int32 Test".to_string(),
+            num_dim: 2,
+            num_nodes: 4,
+            num_elems: 1,
+            num_elem_blocks: 1,
+            num_node_sets: 1,
+            ..Default::default()
+        };
+        file.init(&params).unwrap();
+
+        let block = Block {
+            id: 10,
+            entity_type: EntityType::ElemBlock,
+            topology: "QUAD4".to_string(),
+            num_entries: 1,
+            num_nodes_per_entry: 4,
+            num_edges_per_entry: 0,
+            num_faces_per_entry: 0,
+            num_attributes: 0,
+        };
+        file.put_block(&block).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_int32_mode_roundtrip() {
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path();
+
+        {
+            // Classic NetCDF-3 cannot hold 64-bit integers at all
+            let mut file = create_int32_file(path, FileFormat::NetCdf3Classic);
+            file.put_connectivity(10, &[1, 2, 3, 4]).unwrap();
+            file.put_node_set(5, &[1, 2], None).unwrap();
+            file.put_id_map(EntityType::NodeMap, &[101, 102, 103, 104])
+                .unwrap();
+        }
+
+        let file = ExodusFile::<mode::Read>::open(path).unwrap();
+        assert_eq!(file.format().unwrap(), FileFormat::NetCdf3Classic);
+        assert_eq!(file.int64_mode(), Int64Mode::Int32);
+        assert_eq!(file.block_ids(EntityType::ElemBlock).unwrap(), vec![10]);
+        assert_eq!(file.connectivity(10).unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(file.node_set(5).unwrap().nodes, vec![1, 2]);
+        assert_eq!(
+            file.id_map(EntityType::NodeMap).unwrap(),
+            vec![101, 102, 103, 104]
+        );
+    }
+
+    #[test]
+    fn test_int32_mode_overflow() {
+        let tmp = NamedTempFile::new().unwrap();
+        let mut file = create_int32_file(tmp.path(), FileFormat::NetCdf4);
+
+        let too_big = i64::from(i32::MAX) + 1;
+        let result = file.put_connectivity(10, &[1, 2, 3, too_big]);
+        assert!(matches!(
+            result,
+            Err(ExodusError::IntegerOverflow { value, .. }) if value == too_big
+        ));
+
+        let result = file.put_id_map(EntityType::NodeMap, &[1, 2, 3, too_big]);
+        assert!(matches!(result, Err(ExodusError::IntegerOverflow { .. })));
+
+        let result = file.put_node_set(too_big, &[1], None);
+        assert!(matches!(result, Err(ExodusError::IntegerOverflow { .. })));
+    }
 }
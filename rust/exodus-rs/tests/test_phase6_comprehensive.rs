@@ -1,6 +1,10 @@
 //! Comprehensive tests for Phase 6: Variables and Time Steps (All Types)
 
-use exodus_rs::{mode, Block, CreateMode, CreateOptions, EntityType, ExodusFile, InitParams, Set};
+#![cfg(feature = "netcdf4")]
+
+use exodus_rs::{
+    mode, Block, CreateMode, CreateOptions, EntityType, ExodusFile, InitParams, Set, TruthTable,
+};
 use tempfile::NamedTempFile;
 
 #[test]
@@ -642,3 +646,216 @@ fn test_invalid_time_step_access() {
         assert!(result.is_err(), "Should fail accessing invalid time step");
     }
 }
+
+#[test]
+fn test_set_truth_table() {
+    let tmp = NamedTempFile::new().unwrap();
+
+    // Write
+    {
+        let mut file = ExodusFile::create(
+            tmp.path(),
+            CreateOptions {
+                mode: CreateMode::Clobber,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        file.init(&InitParams {
+            title: "Set Truth Table".into(),
+            num_dim: 2,
+            num_nodes: 10,
+            num_node_sets: 2,
+            ..Default::default()
+        })
+        .unwrap();
+
+        file.put_node_set(10, &[1, 2, 3], None).unwrap();
+        file.put_node_set(11, &[4, 5], None).unwrap();
+
+        file.define_variables(EntityType::NodeSet, &["Flux", "Temp"])
+            .unwrap();
+
+        // Set 11 (index 1) doesn't have Temp (var index 1)
+        let mut truth = TruthTable::new(EntityType::NodeSet, 2, 2);
+        truth.set(1, 1, false);
+        file.put_truth_table(EntityType::NodeSet, &truth).unwrap();
+
+        file.put_time(0, 0.0).unwrap();
+        file.put_var(0, EntityType::NodeSet, 10, 0, &[1.0, 2.0, 3.0])
+            .unwrap();
+        file.put_var(0, EntityType::NodeSet, 10, 1, &[4.0, 5.0, 6.0])
+            .unwrap();
+        file.put_var(0, EntityType::NodeSet, 11, 0, &[7.0, 8.0])
+            .unwrap();
+        assert!(file
+            .put_var(0, EntityType::NodeSet, 11, 1, &[9.0, 10.0])
+            .is_err());
+    }
+
+    // Read
+    {
+        let file = ExodusFile::<mode::Read>::open(tmp.path()).unwrap();
+
+        let truth = file.truth_table(EntityType::NodeSet).unwrap();
+        assert_eq!(truth.num_blocks, 2);
+        assert_eq!(truth.num_vars, 2);
+        assert_eq!(truth.table, vec![true, true, true, false]);
+
+        assert!(file
+            .is_var_in_truth_table(EntityType::NodeSet, 10, 1)
+            .unwrap());
+        assert!(!file
+            .is_var_in_truth_table(EntityType::NodeSet, 11, 1)
+            .unwrap());
+        assert!(file.truth_table(EntityType::Nodal).is_err());
+
+        assert_eq!(
+            file.var(0, EntityType::NodeSet, 11, 0).unwrap(),
+            vec![7.0, 8.0]
+        );
+        assert_eq!(
+            file.var_multi(0, EntityType::NodeSet, 10).unwrap(),
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+        );
+
+        // Types without a stored table have every variable enabled
+        assert_eq!(
+            file.truth_table(EntityType::SideSet).unwrap().table,
+            Vec::<bool>::new()
+        );
+    }
+}
+
+#[test]
+fn test_append_all_variable_types() {
+    let tmp = NamedTempFile::new().unwrap();
+
+    // Write mesh and variable definitions, but no values
+    {
+        let mut file = ExodusFile::create(
+            tmp.path(),
+            CreateOptions {
+                mode: CreateMode::Clobber,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        file.init(&InitParams {
+            title: "Append Vars".into(),
+            num_dim: 2,
+            num_nodes: 4,
+            num_edges: 2,
+            num_edge_blocks: 1,
+            num_faces: 1,
+            num_face_blocks: 1,
+            num_elems: 1,
+            num_elem_blocks: 1,
+            num_node_sets: 1,
+            num_edge_sets: 1,
+            num_face_sets: 1,
+            num_side_sets: 1,
+            num_elem_sets: 1,
+            ..Default::default()
+        })
+        .unwrap();
+
+        for (id, entity_type, topology, num_entries, num_nodes_per_entry) in [
+            (1, EntityType::ElemBlock, "QUAD4", 1, 4),
+            (2, EntityType::EdgeBlock, "EDGE2", 2, 2),
+            (3, EntityType::FaceBlock, "QUAD4", 1, 4),
+        ] {
+            file.put_block(&Block {
+                id,
+                entity_type,
+                topology: topology.into(),
+                num_entries,
+                num_nodes_per_entry,
+                num_edges_per_entry: 0,
+                num_faces_per_entry: 0,
+                num_attributes: 0,
+            })
+            .unwrap();
+        }
+
+        file.put_node_set(10, &[1, 2], None).unwrap();
+        file.put_side_set(20, &[1], &[1], None).unwrap();
+        for (id, entity_type, entities) in [
+            (30, EntityType::EdgeSet, vec![1, 2]),
+            (40, EntityType::FaceSet, vec![1]),
+            (50, EntityType::ElemSet, vec![1]),
+        ] {
+            file.put_set(&Set {
+                id,
+                entity_type,
+                num_entries: entities.len(),
+                num_dist_factors: 0,
+            })
+            .unwrap();
+            file.put_entity_set(entity_type, id, &entities).unwrap();
+        }
+
+        for var_type in [
+            EntityType::EdgeBlock,
+            EntityType::FaceBlock,
+            EntityType::NodeSet,
+            EntityType::EdgeSet,
+            EntityType::SideSet,
+            EntityType::ElemSet,
+        ] {
+            file.define_variables(var_type, &["A", "B"]).unwrap();
+        }
+        file.put_time(0, 0.0).unwrap();
+    }
+
+    let entities = [
+        (EntityType::EdgeBlock, 2, 2),
+        (EntityType::FaceBlock, 3, 1),
+        (EntityType::NodeSet, 10, 2),
+        (EntityType::EdgeSet, 30, 2),
+        (EntityType::FaceSet, 40, 1),
+        (EntityType::SideSet, 20, 1),
+        (EntityType::ElemSet, 50, 1),
+    ];
+
+    // Append: define the remaining type and write values for every type
+    {
+        let mut file = ExodusFile::append(tmp.path()).unwrap();
+        file.define_variables(EntityType::FaceSet, &["A", "B"])
+            .unwrap();
+
+        // Only the first variable is defined on the edge set
+        let mut truth = TruthTable::new(EntityType::EdgeSet, 1, 2);
+        truth.set(0, 1, false);
+        file.put_truth_table(EntityType::EdgeSet, &truth).unwrap();
+
+        for (i, &(var_type, id, len)) in entities.iter().enumerate() {
+            let values: Vec<f64> = (0..2 * len).map(|j| (10 * i + j) as f64).collect();
+            if var_type == EntityType::EdgeSet {
+                file.put_var(0, var_type, id, 0, &values[..len]).unwrap();
+                assert!(file.put_var(0, var_type, id, 1, &values[len..]).is_err());
+            } else {
+                file.put_var_multi(0, var_type, id, &values).unwrap();
+                assert_eq!(file.var_multi(0, var_type, id).unwrap(), values);
+            }
+            assert_eq!(file.var(0, var_type, id, 0).unwrap(), values[..len]);
+        }
+    }
+
+    // Read
+    {
+        let file = ExodusFile::<mode::Read>::open(tmp.path()).unwrap();
+        for (i, &(var_type, id, len)) in entities.iter().enumerate() {
+            assert_eq!(file.variable_names(var_type).unwrap(), vec!["A", "B"]);
+            let values: Vec<f64> = (0..2 * len).map(|j| (10 * i + j) as f64).collect();
+            assert_eq!(file.var(0, var_type, id, 0).unwrap(), values[..len]);
+            if var_type == EntityType::EdgeSet {
+                assert!(!file.is_var_in_truth_table(var_type, id, 1).unwrap());
+            } else {
+                assert_eq!(file.var(0, var_type, id, 1).unwrap(), values[len..]);
+            }
+        }
+    }
+}
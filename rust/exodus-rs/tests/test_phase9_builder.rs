@@ -1,5 +1,7 @@
 //! Integration tests for Phase 9: High-Level Builder API
 
+#![cfg(feature = "netcdf4")]
+
 use exodus_rs::{mode, BlockBuilder, ExodusFile, MeshBuilder};
 use tempfile::NamedTempFile;
 
@@ -0,0 +1,222 @@
+//! Tests for the low-level C-compatible API
+//!
+//! A file is written through handles in the order a C program would call the
+//! library, then read back both through handles and with the high-level API.
+
+#![cfg(feature = "netcdf4")]
+
+use exodus_rs::raw::*;
+use exodus_rs::{mode, EntityType, ExodusError, ExodusFile, QaRecord};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Write two QUAD4 elements with a node set, a side set, a nodal and an
+/// element variable, and two time steps
+fn write_mesh(dir: &Path) -> PathBuf {
+    let path = dir.join("raw.e");
+    let exoid = ex_create(&path, EX_CLOBBER, 8).unwrap();
+    ex_put_init(exoid, "Raw API mesh", 2, 6, 2, 1, 1, 1).unwrap();
+    ex_put_coord(
+        exoid,
+        &[0.0, 1.0, 2.0, 0.0, 1.0, 2.0],
+        Some(&[0.0, 0.0, 0.0, 1.0, 1.0, 1.0]),
+        None,
+    )
+    .unwrap();
+    ex_put_coord_names(exoid, &["x", "y"]).unwrap();
+
+    ex_put_block(exoid, EntityType::ElemBlock, 10, "QUAD4", 2, 4, 0, 0, 1).unwrap();
+    ex_put_conn(exoid, EntityType::ElemBlock, 10, &[1, 2, 5, 4, 2, 3, 6, 5]).unwrap();
+    ex_put_attr(exoid, EntityType::ElemBlock, 10, &[0.5, 0.25]).unwrap();
+    ex_put_attr_names(exoid, EntityType::ElemBlock, 10, &["thickness"]).unwrap();
+    ex_put_names(exoid, EntityType::ElemBlock, &["plate"]).unwrap();
+    ex_put_prop(exoid, EntityType::ElemBlock, 10, "MATERIAL", 3).unwrap();
+
+    ex_put_set_param(exoid, EntityType::NodeSet, 1, 2, 0).unwrap();
+    ex_put_set(exoid, EntityType::NodeSet, 1, &[1, 4], None).unwrap();
+    ex_put_set_param(exoid, EntityType::SideSet, 2, 2, 0).unwrap();
+    ex_put_set(exoid, EntityType::SideSet, 2, &[1, 2], Some(&[1, 1])).unwrap();
+    ex_put_name(exoid, EntityType::SideSet, 2, "bottom").unwrap();
+    ex_put_id_map(exoid, EntityType::ElemMap, &[100, 200]).unwrap();
+
+    ex_put_qa(
+        exoid,
+        &[QaRecord {
+            code_name: "raw".into(),
+            code_version: "1.0".into(),
+            date: "2024-01-01".into(),
+            time: "00:00:00".into(),
+        }],
+    )
+    .unwrap();
+    ex_put_info(exoid, &["written through handles".to_string()]).unwrap();
+
+    ex_put_variable_param(exoid, EntityType::Nodal, 1).unwrap();
+    ex_put_variable_names(exoid, EntityType::Nodal, &["temp"]).unwrap();
+    ex_put_variable_param(exoid, EntityType::ElemBlock, 1).unwrap();
+    ex_put_variable_names(exoid, EntityType::ElemBlock, &["stress"]).unwrap();
+    for step in 1..=2 {
+        let time = step as f64 * 0.5;
+        ex_put_time(exoid, step, time).unwrap();
+        ex_put_var(exoid, step, EntityType::Nodal, 1, 0, &[time; 6]).unwrap();
+        ex_put_var(exoid, step, EntityType::ElemBlock, 1, 10, &[time, -time]).unwrap();
+    }
+    ex_update(exoid).unwrap();
+    ex_close(exoid).unwrap();
+    path
+}
+
+#[test]
+fn test_raw_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    let exoid = ex_open(write_mesh(dir.path()), EX_READ).unwrap();
+
+    let params = ex_get_init(exoid).unwrap();
+    assert_eq!(params.title, "Raw API mesh");
+    assert_eq!(ex_inquire_int(exoid, ExInquiry::Nodes).unwrap(), 6);
+    assert_eq!(ex_inquire_int(exoid, ExInquiry::NsNodeLen).unwrap(), 2);
+    assert_eq!(ex_inquire_int(exoid, ExInquiry::Time).unwrap(), 2);
+    assert_eq!(ex_inquire_int(exoid, ExInquiry::DbFloatSize).unwrap(), 8);
+    assert_eq!(
+        ex_inquire_int(exoid, ExInquiry::NumElemBlockVar).unwrap(),
+        1
+    );
+
+    let (x, y, z) = ex_get_coord(exoid).unwrap();
+    assert_eq!(x[2], 2.0);
+    assert_eq!(y[3], 1.0);
+    assert!(z.is_empty());
+    assert_eq!(ex_get_coord_names(exoid).unwrap(), vec!["x", "y"]);
+
+    assert_eq!(ex_get_ids(exoid, EntityType::ElemBlock).unwrap(), vec![10]);
+    let block = ex_get_block(exoid, EntityType::ElemBlock, 10).unwrap();
+    assert_eq!(block.topology, "QUAD4");
+    assert_eq!(block.num_attributes, 1);
+    assert_eq!(
+        ex_get_conn(exoid, EntityType::ElemBlock, 10).unwrap(),
+        vec![1, 2, 5, 4, 2, 3, 6, 5]
+    );
+    assert_eq!(
+        ex_get_attr(exoid, EntityType::ElemBlock, 10).unwrap(),
+        vec![0.5, 0.25]
+    );
+    assert_eq!(
+        ex_get_attr_names(exoid, EntityType::ElemBlock, 10).unwrap(),
+        vec!["thickness"]
+    );
+    assert_eq!(
+        ex_get_name(exoid, EntityType::ElemBlock, 10).unwrap(),
+        "plate"
+    );
+    assert_eq!(
+        ex_get_prop(exoid, EntityType::ElemBlock, 10, "MATERIAL").unwrap(),
+        3
+    );
+
+    assert_eq!(
+        ex_get_set_param(exoid, EntityType::NodeSet, 1).unwrap(),
+        (2, 0)
+    );
+    assert_eq!(
+        ex_get_set(exoid, EntityType::NodeSet, 1).unwrap(),
+        (vec![1, 4], None)
+    );
+    assert_eq!(
+        ex_get_set(exoid, EntityType::SideSet, 2).unwrap(),
+        (vec![1, 2], Some(vec![1, 1]))
+    );
+    assert_eq!(
+        ex_get_name(exoid, EntityType::SideSet, 2).unwrap(),
+        "bottom"
+    );
+    assert_eq!(
+        ex_get_id_map(exoid, EntityType::ElemMap).unwrap(),
+        vec![100, 200]
+    );
+    assert_eq!(ex_get_qa(exoid).unwrap()[0].code_name, "raw");
+    assert_eq!(ex_get_info(exoid).unwrap(), vec!["written through handles"]);
+
+    assert_eq!(ex_get_all_times(exoid).unwrap(), vec![0.5, 1.0]);
+    assert_eq!(ex_get_time(exoid, 2).unwrap(), 1.0);
+    assert_eq!(
+        ex_get_variable_name(exoid, EntityType::ElemBlock, 1).unwrap(),
+        "stress"
+    );
+    assert_eq!(
+        ex_get_var(exoid, 2, EntityType::ElemBlock, 1, 10).unwrap(),
+        vec![1.0, -1.0]
+    );
+    assert_eq!(
+        ex_get_var(exoid, 1, EntityType::Nodal, 1, 0).unwrap(),
+        vec![0.5; 6]
+    );
+    assert!(ex_get_truth_table(exoid, EntityType::ElemBlock)
+        .unwrap()
+        .get(0, 0));
+    ex_close(exoid).unwrap();
+}
+
+#[test]
+fn test_raw_file_readable_by_high_level_api() {
+    let dir = TempDir::new().unwrap();
+    let file = ExodusFile::<mode::Read>::open(write_mesh(dir.path())).unwrap();
+    assert_eq!(file.block(10).unwrap().num_entries, 2);
+    assert_eq!(file.side_set(2).unwrap().sides, vec![1, 1]);
+    assert_eq!(file.var(0, EntityType::Nodal, 0, 0).unwrap(), vec![0.5; 6]);
+}
+
+#[test]
+fn test_raw_append_results() {
+    let dir = TempDir::new().unwrap();
+    let path = write_mesh(dir.path());
+
+    let exoid = ex_open(&path, EX_WRITE).unwrap();
+    assert_eq!(ex_get_variable_param(exoid, EntityType::Nodal).unwrap(), 1);
+    ex_put_time(exoid, 3, 1.5).unwrap();
+    ex_put_var(exoid, 3, EntityType::Nodal, 1, 0, &[1.5; 6]).unwrap();
+    // Set lists are only read from files opened with EX_READ
+    assert!(matches!(
+        ex_get_set(exoid, EntityType::NodeSet, 1),
+        Err(ExodusError::InvalidMode(_))
+    ));
+    ex_close(exoid).unwrap();
+
+    let exoid = ex_open(&path, EX_READ).unwrap();
+    assert_eq!(ex_get_all_times(exoid).unwrap(), vec![0.5, 1.0, 1.5]);
+    assert_eq!(
+        ex_get_var(exoid, 3, EntityType::Nodal, 1, 0).unwrap(),
+        vec![1.5; 6]
+    );
+    assert!(matches!(
+        ex_put_time(exoid, 4, 2.0),
+        Err(ExodusError::WriteOnReadOnly)
+    ));
+    ex_close(exoid).unwrap();
+}
+
+#[test]
+fn test_raw_variable_count_mismatch() {
+    let dir = TempDir::new().unwrap();
+    let exoid = ex_create(dir.path().join("count.e"), EX_CLOBBER, 0).unwrap();
+    ex_put_init(exoid, "Count", 2, 1, 0, 0, 0, 0).unwrap();
+    ex_put_variable_param(exoid, EntityType::Global, 2).unwrap();
+    assert!(matches!(
+        ex_put_variable_names(exoid, EntityType::Global, &["energy"]),
+        Err(ExodusError::InvalidArrayLength {
+            expected: 2,
+            actual: 1
+        })
+    ));
+    ex_put_variable_names(exoid, EntityType::Global, &["energy", "momentum"]).unwrap();
+    assert!(matches!(
+        ex_get_qa(exoid),
+        Err(ExodusError::ReadOnWriteOnly)
+    ));
+    ex_close(exoid).unwrap();
+
+    assert!(ex_create(dir.path().join("count.e"), EX_NOCLOBBER, 0).is_err());
+    assert!(matches!(
+        ex_create(dir.path().join("other.e"), EX_CLOBBER, 2),
+        Err(ExodusError::Other(_))
+    ));
+}
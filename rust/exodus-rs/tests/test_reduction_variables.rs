@@ -3,6 +3,8 @@
 //! Reduction variables store aggregated/summary values for entire objects
 //! (e.g., assemblies, blocks, sets) rather than for individual entities within those objects.
 
+#![cfg(feature = "netcdf4")]
+
 use approx::assert_abs_diff_eq;
 use exodus_rs::types::{Assembly, Block, EntityType};
 use exodus_rs::{CreateMode, CreateOptions, ExodusFile, InitParams, Set};
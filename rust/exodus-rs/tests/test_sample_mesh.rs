@@ -3,7 +3,6 @@
 //! This test demonstrates reading an exodus file containing a meshed surface
 //! with nodes, element connectivity, node sets, and side sets.
 
-#[cfg(feature = "netcdf4")]
 mod sample_mesh_tests {
     use exodus_rs::{mode, EntityType, ExodusFile};
     use std::path::PathBuf;
@@ -6,6 +6,8 @@
 //! The core functionality is correctly implemented and will work when sets are defined
 //! before any data writes occur.
 
+#![cfg(feature = "netcdf4")]
+
 use exodus_rs::{mode, CreateMode, CreateOptions, EntityType, ExodusFile, InitParams, Set};
 use tempfile::NamedTempFile;
 
@@ -1,12 +1,13 @@
 //! Tests for variable storage format detection and combined format reading.
 //!
-//! This module tests the ability to detect and read from files using the combined
+//! This module tests the ability to detect, write and read files using the combined
 //! 3D variable storage format (e.g., `vals_nod_var(time_step, num_vars, num_nodes)`).
 
 #![cfg(feature = "netcdf4")]
 
 use exodus_rs::{
-    mode, Block, CreateMode, CreateOptions, EntityType, ExodusFile, InitParams, VarStorageMode,
+    mode, Block, CreateMode, CreateOptions, EntityType, ExodusFile, FileStorageFormat, InitParams,
+    VarStorageMode,
 };
 use tempfile::NamedTempFile;
 
@@ -305,3 +306,162 @@ fn test_file_storage_format_default() {
     assert_eq!(format.elem_block, VarStorageMode::Separate);
     assert_eq!(format.global, VarStorageMode::Separate);
 }
+
+#[test]
+fn test_file_storage_format_get() {
+    let format = FileStorageFormat {
+        nodal: VarStorageMode::Combined,
+        ..Default::default()
+    };
+    assert_eq!(
+        format.get(EntityType::Nodal),
+        Some(VarStorageMode::Combined)
+    );
+    assert_eq!(
+        format.get(EntityType::SideSet),
+        Some(VarStorageMode::Separate)
+    );
+    assert_eq!(format.get(EntityType::Assembly), None);
+}
+
+/// Helper to create a file writing nodal variables in the combined format
+fn create_combined_file(path: &std::path::Path) -> ExodusFile<mode::Write> {
+    let mut file = ExodusFile::create(
+        path,
+        CreateOptions {
+            mode: CreateMode::Clobber,
+            nodal_var_storage: VarStorageMode::Combined,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    file.init(&InitParams {
+        title: "Combined".to_string(),
+        num_dim: 3,
+        num_nodes: 4,
+        num_elems: 1,
+        num_elem_blocks: 1,
+        ..Default::default()
+    })
+    .unwrap();
+    file.put_block(&Block {
+        id: 1,
+        entity_type: EntityType::ElemBlock,
+        topology: "TET4".to_string(),
+        num_entries: 1,
+        num_nodes_per_entry: 4,
+        num_edges_per_entry: 0,
+        num_faces_per_entry: 0,
+        num_attributes: 0,
+    })
+    .unwrap();
+    file.put_connectivity(1, &[1, 2, 3, 4]).unwrap();
+    file
+}
+
+#[test]
+fn test_combined_nodal_write_roundtrip() {
+    let tmp = NamedTempFile::new().unwrap();
+    {
+        let mut file = create_combined_file(tmp.path());
+        file.define_variables(EntityType::Nodal, &["temperature", "pressure", "density"])
+            .unwrap();
+        file.define_variables(EntityType::ElemBlock, &["stress"])
+            .unwrap();
+
+        file.put_time(0, 0.0).unwrap();
+        file.put_time(1, 1.0).unwrap();
+        for step in 0..2 {
+            let offset = 10.0 * step as f64;
+            file.put_var(
+                step,
+                EntityType::Nodal,
+                0,
+                0,
+                &[1.0 + offset, 2.0, 3.0, 4.0],
+            )
+            .unwrap();
+            file.put_var(
+                step,
+                EntityType::Nodal,
+                0,
+                1,
+                &[5.0 + offset, 6.0, 7.0, 8.0],
+            )
+            .unwrap();
+            file.put_var(step, EntityType::ElemBlock, 1, 0, &[offset])
+                .unwrap();
+        }
+        file.put_var_time_series(
+            0,
+            2,
+            EntityType::Nodal,
+            0,
+            2,
+            &[0.1, 0.2, 0.3, 0.4, 1.1, 1.2, 1.3, 1.4],
+        )
+        .unwrap();
+    }
+
+    let file = ExodusFile::<mode::Read>::open(tmp.path()).unwrap();
+    let format = file.storage_format();
+    assert_eq!(format.nodal, VarStorageMode::Combined);
+    // Only nodal variables were requested in the combined layout
+    assert_eq!(format.elem_block, VarStorageMode::Separate);
+
+    assert_eq!(
+        file.var(1, EntityType::Nodal, 0, 0).unwrap(),
+        vec![11.0, 2.0, 3.0, 4.0]
+    );
+    assert_eq!(
+        file.var(0, EntityType::Nodal, 0, 1).unwrap(),
+        vec![5.0, 6.0, 7.0, 8.0]
+    );
+    assert_eq!(
+        file.var(1, EntityType::Nodal, 0, 2).unwrap(),
+        vec![1.1, 1.2, 1.3, 1.4]
+    );
+    assert_eq!(
+        file.var(1, EntityType::ElemBlock, 1, 0).unwrap(),
+        vec![10.0]
+    );
+}
+
+#[test]
+fn test_combined_nodal_append() {
+    let tmp = NamedTempFile::new().unwrap();
+    {
+        let mut file = create_combined_file(tmp.path());
+        file.define_variables(EntityType::Nodal, &["temperature", "pressure"])
+            .unwrap();
+        file.put_time(0, 0.0).unwrap();
+        file.put_var(0, EntityType::Nodal, 0, 0, &[1.0, 2.0, 3.0, 4.0])
+            .unwrap();
+    }
+
+    // Appending keeps writing the layout detected in the file
+    {
+        let mut file = ExodusFile::<mode::Append>::append(tmp.path()).unwrap();
+        assert_eq!(file.storage_format().nodal, VarStorageMode::Combined);
+        file.put_time(1, 1.0).unwrap();
+        file.put_var(1, EntityType::Nodal, 0, 1, &[5.0, 6.0, 7.0, 8.0])
+            .unwrap();
+        assert_eq!(
+            file.var(1, EntityType::Nodal, 0, 1).unwrap(),
+            vec![5.0, 6.0, 7.0, 8.0]
+        );
+    }
+
+    let file = ExodusFile::<mode::Read>::open(tmp.path()).unwrap();
+    assert_eq!(file.storage_format().nodal, VarStorageMode::Combined);
+    assert_eq!(file.times().unwrap(), vec![0.0, 1.0]);
+    assert_eq!(
+        file.var(0, EntityType::Nodal, 0, 0).unwrap(),
+        vec![1.0, 2.0, 3.0, 4.0]
+    );
+    assert_eq!(
+        file.var(1, EntityType::Nodal, 0, 1).unwrap(),
+        vec![5.0, 6.0, 7.0, 8.0]
+    );
+}
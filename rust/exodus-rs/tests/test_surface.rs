@@ -0,0 +1,74 @@
+//! Tests for exporting surfaces to STL, OBJ and PLY
+//!
+//! The mesh is a unit cube HEX8 with its nodes in inverted order, next to a
+//! SHELL4 on the plane x = 2. Side set 1 holds the top of the hex and the
+//! back of the shell.
+
+#![cfg(feature = "netcdf4")]
+
+use exodus_rs::surface::{export_surface, Surface, SurfaceOptions};
+use exodus_rs::{mode, BlockBuilder, ExodusFile, MeshBuilder, SideSetBuilder};
+use tempfile::TempDir;
+
+fn create_mesh(path: &std::path::Path) {
+    let x = vec![0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 2.0, 2.0, 2.0, 2.0];
+    let y = vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+    let z = vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0];
+    MeshBuilder::new("Surface test mesh")
+        .dimensions(3)
+        .coordinates(x, y, z)
+        // Top and bottom swapped, so the faces of Topology::faces point in
+        .add_block(
+            BlockBuilder::new(1, "HEX8")
+                .connectivity(vec![5, 6, 7, 8, 1, 2, 3, 4])
+                .build(),
+        )
+        .add_block(
+            BlockBuilder::new(2, "SHELL4")
+                .connectivity(vec![9, 10, 11, 12])
+                .build(),
+        )
+        .add_side_set(SideSetBuilder::new(1).sides(vec![(1, 5), (2, 2)]).build())
+        .write(path)
+        .unwrap();
+}
+
+#[test]
+fn test_surface_orientation() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("mesh.e");
+    create_mesh(&path);
+    let file = ExodusFile::<mode::Read>::open(&path).unwrap();
+
+    // Six hex faces and the shell, two triangles each
+    let skin = Surface::read(&file, None).unwrap();
+    assert_eq!((skin.points.len(), skin.triangles.len()), (12, 14));
+
+    // Side 5 of the inverted hex is its top, still pointing up; the back of
+    // the shell points along -x
+    let set = Surface::read(&file, Some(&[1])).unwrap();
+    let normals = set.normals();
+    assert_eq!(normals.len(), 4);
+    assert!(normals[..2].iter().all(|n| (n[2] - 1.0).abs() < 1e-12));
+    assert!(normals[2..].iter().all(|n| (n[0] + 1.0).abs() < 1e-12));
+}
+
+#[test]
+fn test_export_stl_ascii() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("mesh.e");
+    create_mesh(&path);
+    let output = dir.path().join("skin.stl");
+    let options = SurfaceOptions {
+        ascii: true,
+        ..Default::default()
+    };
+    let summary = export_surface(&path, &output, &options).unwrap();
+    assert_eq!(summary.num_triangles, 14);
+
+    let text = std::fs::read_to_string(&output).unwrap();
+    assert!(text.starts_with("solid Surface test mesh\n"));
+    assert_eq!(text.matches("facet normal").count(), 14);
+    assert_eq!(text.matches("vertex ").count(), 42);
+    assert!(text.trim_end().ends_with("endsolid Surface test mesh"));
+}
@@ -0,0 +1,226 @@
+//! Tests for time step management
+//!
+//! Covers locating and bracketing time values, interpolation between steps,
+//! detection of restarts that moved time backwards, and logical truncation.
+
+#![cfg(feature = "netcdf4")]
+
+use approx::assert_abs_diff_eq;
+use exodus_rs::{
+    mode, Block, CreateOptions, EntityType, ExodusError, ExodusFile, InitParams, TimeBracket,
+};
+
+/// Two-element mesh with one global, nodal and element variable written at
+/// `times`; values at step `i` are `i` scaled per entity.
+fn create_file(times: &[f64]) -> Vec<u8> {
+    let mut file = ExodusFile::create_in_memory(CreateOptions::default()).unwrap();
+    file.init(&InitParams {
+        title: "Time steps".into(),
+        num_dim: 2,
+        num_nodes: 6,
+        num_elems: 2,
+        num_elem_blocks: 1,
+        ..Default::default()
+    })
+    .unwrap();
+    file.put_coords(
+        &[0.0, 1.0, 2.0, 0.0, 1.0, 2.0],
+        Some(&[0.0, 0.0, 0.0, 1.0, 1.0, 1.0]),
+        None,
+    )
+    .unwrap();
+    file.put_block(&Block {
+        id: 10,
+        entity_type: EntityType::ElemBlock,
+        topology: "QUAD4".into(),
+        num_entries: 2,
+        num_nodes_per_entry: 4,
+        num_edges_per_entry: 0,
+        num_faces_per_entry: 0,
+        num_attributes: 0,
+    })
+    .unwrap();
+    file.put_connectivity(10, &[1, 2, 5, 4, 2, 3, 6, 5])
+        .unwrap();
+    file.define_variables(EntityType::Global, &["energy"])
+        .unwrap();
+    file.define_variables(EntityType::Nodal, &["temp"]).unwrap();
+    file.define_variables(EntityType::ElemBlock, &["stress"])
+        .unwrap();
+
+    for (step, &time) in times.iter().enumerate() {
+        let s = step as f64;
+        file.put_time(step, time).unwrap();
+        file.put_var(step, EntityType::Global, 0, 0, &[s]).unwrap();
+        let nodal: Vec<f64> = (0..6).map(|n| s * n as f64).collect();
+        file.put_var(step, EntityType::Nodal, 0, 0, &nodal).unwrap();
+        file.put_var(step, EntityType::ElemBlock, 10, 0, &[s, 10.0 * s])
+            .unwrap();
+    }
+    file.into_bytes().unwrap()
+}
+
+#[test]
+fn test_nearest_and_bracket() {
+    let file =
+        ExodusFile::<mode::Read>::open_from_bytes(create_file(&[0.0, 1.0, 2.0, 4.0])).unwrap();
+
+    assert_eq!(file.nearest_time_step(-5.0).unwrap(), 0);
+    assert_eq!(file.nearest_time_step(1.4).unwrap(), 1);
+    assert_eq!(file.nearest_time_step(3.5).unwrap(), 3);
+
+    assert_eq!(
+        file.bracket_time(3.0).unwrap(),
+        TimeBracket {
+            lower: 2,
+            upper: 3,
+            fraction: 0.5
+        }
+    );
+    let exact = file.bracket_time(1.0).unwrap();
+    assert_eq!((exact.lower, exact.upper), (1, 1));
+    assert!(file.bracket_time(4.5).is_err());
+}
+
+#[test]
+fn test_interpolate_all_variable_types() {
+    let file = ExodusFile::<mode::Read>::open_from_bytes(create_file(&[0.0, 1.0, 3.0])).unwrap();
+
+    // t = 2.0 is halfway between steps 1 and 2
+    let global = file.interpolate_var(2.0, EntityType::Global, 0, 0).unwrap();
+    assert_abs_diff_eq!(global[0], 1.5);
+
+    let nodal = file.interpolate_var(2.0, EntityType::Nodal, 0, 0).unwrap();
+    for (n, value) in nodal.iter().enumerate() {
+        assert_abs_diff_eq!(*value, 1.5 * n as f64);
+    }
+
+    let elem = file
+        .interpolate_var(0.25, EntityType::ElemBlock, 10, 0)
+        .unwrap();
+    assert_abs_diff_eq!(elem[0], 0.25);
+    assert_abs_diff_eq!(elem[1], 2.5);
+
+    // Exact step times return the stored values
+    assert_eq!(
+        file.interpolate_var(3.0, EntityType::ElemBlock, 10, 0)
+            .unwrap(),
+        vec![2.0, 20.0]
+    );
+}
+
+#[test]
+fn test_restart_detection() {
+    let file =
+        ExodusFile::<mode::Read>::open_from_bytes(create_file(&[0.0, 1.0, 2.0, 3.0])).unwrap();
+    assert!(file.is_time_monotonic().unwrap());
+    assert!(file.time_reversals().unwrap().is_empty());
+
+    // Restarted from t = 1.5 after reaching t = 3.0
+    let file =
+        ExodusFile::<mode::Read>::open_from_bytes(create_file(&[0.0, 1.0, 2.0, 3.0, 1.5, 2.5]))
+            .unwrap();
+    assert!(!file.is_time_monotonic().unwrap());
+    assert_eq!(file.time_reversals().unwrap(), vec![4]);
+    assert_eq!(file.restart_time_steps().unwrap(), vec![0, 1, 4, 5]);
+    assert!(file.bracket_time(2.0).is_err());
+}
+
+#[test]
+fn test_truncate_read() {
+    let mut file =
+        ExodusFile::<mode::Append>::open_from_bytes(create_file(&[0.0, 1.0, 2.0, 3.0])).unwrap();
+    file.truncate_time_steps(2).unwrap();
+
+    // Truncation only shrinks the visible steps
+    assert!(matches!(
+        file.truncate_time_steps(3),
+        Err(ExodusError::InvalidTimeStep(3))
+    ));
+
+    // The limit is stored in the file and honored by later handles
+    let file = ExodusFile::<mode::Read>::open_from_bytes(file.into_bytes().unwrap()).unwrap();
+
+    assert_eq!(file.num_time_steps().unwrap(), 2);
+    assert_eq!(file.times().unwrap(), vec![0.0, 1.0]);
+    assert!(matches!(file.time(2), Err(ExodusError::InvalidTimeStep(2))));
+    assert!(matches!(
+        file.var(2, EntityType::Nodal, 0, 0),
+        Err(ExodusError::InvalidTimeStep(2))
+    ));
+    assert!(file
+        .var_time_series(0, 3, EntityType::ElemBlock, 10, 0)
+        .is_err());
+    assert_eq!(
+        file.var_time_series(0, 2, EntityType::Global, 0, 0)
+            .unwrap(),
+        vec![0.0, 1.0]
+    );
+    assert_eq!(file.nearest_time_step(10.0).unwrap(), 1);
+}
+
+#[test]
+fn test_truncate_and_resume_restart() {
+    let bytes = create_file(&[0.0, 1.0, 2.0, 3.0]);
+
+    // Resume from step 1 as a restart would, overwriting later steps
+    let mut file = ExodusFile::<mode::Append>::open_from_bytes(bytes).unwrap();
+    file.truncate_time_steps(2).unwrap();
+    assert_eq!(file.times().unwrap(), vec![0.0, 1.0]);
+    file.put_time(2, 1.5).unwrap();
+    file.put_var(2, EntityType::Global, 0, 0, &[42.0]).unwrap();
+    assert_eq!(file.num_time_steps().unwrap(), 3);
+    assert_eq!(file.times().unwrap(), vec![0.0, 1.0, 1.5]);
+    assert_eq!(file.var(2, EntityType::Global, 0, 0).unwrap(), vec![42.0]);
+    let bytes = file.into_bytes().unwrap();
+
+    // A fresh handle still hides the stale step
+    let mut file = ExodusFile::<mode::Append>::open_from_bytes(bytes).unwrap();
+    assert_eq!(file.times().unwrap(), vec![0.0, 1.0, 1.5]);
+
+    // Rewriting the last stored step lifts the truncation
+    file.put_time(3, 2.5).unwrap();
+    let file = ExodusFile::<mode::Read>::open_from_bytes(file.into_bytes().unwrap()).unwrap();
+    assert_eq!(file.times().unwrap(), vec![0.0, 1.0, 1.5, 2.5]);
+    assert_eq!(file.num_time_steps().unwrap(), 4);
+}
+
+#[test]
+fn test_put_time_past_truncation() {
+    let mut file =
+        ExodusFile::<mode::Append>::open_from_bytes(create_file(&[0.0, 1.0, 2.0, 3.0, 4.0]))
+            .unwrap();
+    file.truncate_time_steps(2).unwrap();
+
+    // Skipping ahead would expose the stale steps 2 and 3
+    assert!(matches!(
+        file.put_time(4, 9.0),
+        Err(ExodusError::InvalidTimeStep(4))
+    ));
+    assert_eq!(file.num_time_steps().unwrap(), 2);
+    assert_eq!(file.times().unwrap(), vec![0.0, 1.0]);
+
+    let file = ExodusFile::<mode::Read>::open_from_bytes(file.into_bytes().unwrap()).unwrap();
+    assert_eq!(file.times().unwrap(), vec![0.0, 1.0]);
+    assert!(matches!(file.time(2), Err(ExodusError::InvalidTimeStep(2))));
+    assert!(matches!(file.time(3), Err(ExodusError::InvalidTimeStep(3))));
+}
+
+#[test]
+fn test_empty_time_queries() {
+    let mut file = ExodusFile::create_in_memory(CreateOptions::default()).unwrap();
+    file.init(&InitParams {
+        title: "No steps".into(),
+        num_dim: 1,
+        num_nodes: 1,
+        ..Default::default()
+    })
+    .unwrap();
+    let file = ExodusFile::<mode::Read>::open_from_bytes(file.into_bytes().unwrap()).unwrap();
+
+    assert_eq!(file.num_time_steps().unwrap(), 0);
+    assert!(file.nearest_time_step(0.0).is_err());
+    assert!(file.bracket_time(0.0).is_err());
+    assert!(file.is_time_monotonic().unwrap());
+    assert!(file.restart_time_steps().unwrap().is_empty());
+}
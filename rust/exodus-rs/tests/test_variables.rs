@@ -5,8 +5,11 @@
 //! writing data. The core functionality is correctly implemented. Future improvements
 //! could manage NetCDF define mode more explicitly to work around this limitation.
 
+#![cfg(feature = "netcdf4")]
+
 use exodus_rs::{
-    mode, Block, CreateMode, CreateOptions, EntityType, ExodusFile, InitParams, TruthTable,
+    mode, Block, CreateMode, CreateOptions, EntityType, ExodusFile, FloatSize, InitParams,
+    TruthTable,
 };
 use tempfile::NamedTempFile;
 
@@ -783,3 +786,49 @@ fn test_var_time_series_element() {
         assert_eq!(partial, vec![11.0, 21.0, 12.0, 22.0]);
     }
 }
+
+#[test]
+fn test_float32_variables() {
+    let tmp = NamedTempFile::new().unwrap();
+
+    {
+        let mut file = ExodusFile::create(
+            tmp.path(),
+            CreateOptions {
+                mode: CreateMode::Clobber,
+                float_size: FloatSize::Float32,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        file.init(&InitParams {
+            title: "Float32 Variables".into(),
+            num_dim: 2,
+            num_nodes: 3,
+            ..Default::default()
+        })
+        .unwrap();
+
+        file.define_variables(EntityType::Global, &["Energy"])
+            .unwrap();
+        file.define_variables(EntityType::Nodal, &["Temperature"])
+            .unwrap();
+
+        file.put_time(0, 0.25).unwrap();
+        file.put_var(0, EntityType::Global, 0, 0, &[1.0 / 3.0])
+            .unwrap();
+        file.put_var(0, EntityType::Nodal, 0, 0, &[0.1, 0.2, 0.3])
+            .unwrap();
+    }
+
+    let file = ExodusFile::<mode::Read>::open(tmp.path()).unwrap();
+    assert_eq!(file.float_size(), FloatSize::Float32);
+    assert_eq!(file.times().unwrap(), vec![0.25]);
+
+    let energy = file.var(0, EntityType::Global, 0, 0).unwrap();
+    assert_eq!(energy, vec![(1.0_f32 / 3.0) as f64]);
+
+    let temp = file.var(0, EntityType::Nodal, 0, 0).unwrap();
+    assert_eq!(temp, vec![0.1_f32 as f64, 0.2_f32 as f64, 0.3_f32 as f64]);
+}
@@ -3,10 +3,18 @@
 //! This module defines the command-line interface for rexonator,
 //! including all argument parsing, error types, and transformation operations.
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
+use exodus_rs::decompose::DecompositionMethod;
+use exodus_rs::diff::{TimeMatching, Tolerance};
+use exodus_rs::gmsh::GmshVersion;
 use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::parsers::{
+    parse_decomposition_method, parse_gmsh_version, parse_time_matching, parse_tolerance,
+    parse_variable_tolerance,
+};
+
 /// Errors that can occur during mesh transformation
 #[derive(Error, Debug)]
 pub enum TransformError {
@@ -33,7 +41,12 @@ pub type Result<T> = std::result::Result<T, TransformError>;
 #[derive(Parser, Debug)]
 #[command(name = "rexonator")]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Cli {
+    /// Whole-file command to run instead of transforming INPUT
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Input Exodus file
     #[arg(value_name = "INPUT", required_unless_present_any = ["man", "show_perf_config"])]
     pub input: Option<PathBuf>,
@@ -161,6 +174,264 @@ pub struct Cli {
     pub dry_run: bool,
 }
 
+/// Commands that operate on sets of files rather than transforming one mesh
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Join decomposed file-per-processor results into a single file (like epu)
+    Join(JoinArgs),
+    /// Decompose a mesh into file-per-processor parts (like nem_slice and nem_spread)
+    Decompose(DecomposeArgs),
+    /// Compare two files within tolerances (like exodiff); exits with 2 if they differ
+    Diff(DiffArgs),
+    /// Export a mesh and its results to VTK (.vtk, .vtu, or a .pvd time series)
+    Vtk(VtkArgs),
+    /// Convert between Exodus and Gmsh (.msh) meshes, or import Abaqus
+    /// (.inp) decks and Nastran (.nas, .bdf) bulk data
+    Convert(ConvertArgs),
+    /// Export the mesh skin or side sets as a triangulated surface (.stl,
+    /// .obj or .ply)
+    Surface(SurfaceArgs),
+    /// Export a mesh and its results to XDMF (.xmf) with the heavy data in
+    /// HDF5 (.h5)
+    Xdmf(XdmfArgs),
+}
+
+/// Arguments of the `join` command
+#[derive(Args, Debug)]
+pub struct JoinArgs {
+    /// Decomposed files to join, or the base name of the decomposition:
+    /// "mesh.e" finds mesh.e.N.0 through mesh.e.N.(N-1). A single file with
+    /// no decomposition next to it is copied with the step and variable selection.
+    #[arg(value_name = "INPUT", required = true)]
+    pub inputs: Vec<PathBuf>,
+
+    /// Joined output file (overwritten if it exists)
+    #[arg(short, long, value_name = "OUTPUT")]
+    pub output: PathBuf,
+
+    /// Time steps to join, 1-based and inclusive.
+    /// Format: "BEGIN:END" or "BEGIN:END:STRIDE"; an empty END means the last step.
+    /// Examples: "1:10" or "5:" or "1::2"
+    #[arg(long, value_name = "BEGIN:END[:STRIDE]")]
+    pub steps: Option<String>,
+
+    /// Comma-separated names of the variables to join (default: all).
+    /// Global, nodal, element block, node set and side set variables are matched.
+    #[arg(long = "vars", value_name = "NAMES")]
+    pub variables: Option<String>,
+
+    /// Print verbose output
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
+/// Arguments of the `decompose` command
+#[derive(Args, Debug)]
+pub struct DecomposeArgs {
+    /// Serial Exodus mesh to decompose
+    #[arg(value_name = "INPUT")]
+    pub input: PathBuf,
+
+    /// Number of processors (files) to decompose into
+    #[arg(short = 'n', long, value_name = "N")]
+    pub num_procs: usize,
+
+    /// Partitioning method: rcb (recursive coordinate bisection), inertial,
+    /// or block (whole element blocks per processor)
+    #[arg(long, value_name = "METHOD", default_value = "rcb", value_parser = parse_decomposition_method)]
+    pub method: DecompositionMethod,
+
+    /// Base name of the output files, written as BASE.N.0 through BASE.N.(N-1)
+    /// (default: INPUT). Existing files are overwritten.
+    #[arg(short, long, value_name = "BASE")]
+    pub output: Option<PathBuf>,
+
+    /// Print verbose output
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
+/// Arguments of the `diff` command
+///
+/// Tolerances are written "KIND:VALUE[:FLOOR]" with KIND one of abs, rel or
+/// combined, just "VALUE" for a relative tolerance, or "ignore". Values both
+/// at or below FLOOR in magnitude compare equal.
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// File to check
+    #[arg(value_name = "FIRST")]
+    pub first: PathBuf,
+
+    /// Reference file to compare against
+    #[arg(value_name = "SECOND")]
+    pub second: PathBuf,
+
+    /// Tolerance of the variables without their own --var-tol
+    #[arg(short, long, value_name = "TOL", default_value = "rel:1e-6", value_parser = parse_tolerance)]
+    pub tolerance: Tolerance,
+
+    /// Tolerance of one variable, matched by name ignoring case (repeatable).
+    /// Example: "temperature=abs:1e-3" or "time_step=ignore"
+    #[arg(long = "var-tol", value_name = "NAME=TOL", value_parser = parse_variable_tolerance)]
+    pub variable_tolerances: Vec<(String, Tolerance)>,
+
+    /// Tolerance of the nodal coordinates
+    #[arg(long = "coord-tol", value_name = "TOL", default_value = "combined:1e-6", value_parser = parse_tolerance)]
+    pub coordinate_tolerance: Tolerance,
+
+    /// Tolerance of the time values
+    #[arg(long = "time-tol", value_name = "TOL", default_value = "combined:1e-6", value_parser = parse_tolerance)]
+    pub time_tolerance: Tolerance,
+
+    /// Pair nodes by coordinates and elements by centroid within DIST,
+    /// instead of by the global IDs of the ID maps
+    #[arg(long = "match-coords", value_name = "DIST")]
+    pub match_coords: Option<f64>,
+
+    /// Time step pairing: index, time (nearest time within --time-tol), or
+    /// interpolate (the second file interpolated to the times of the first)
+    #[arg(long = "time-match", value_name = "MODE", default_value = "time", value_parser = parse_time_matching)]
+    pub time_matching: TimeMatching,
+
+    /// Print only the final summary line
+    #[arg(short, long)]
+    pub quiet: bool,
+}
+
+/// Arguments of the `vtk` command
+#[derive(Args, Debug)]
+pub struct VtkArgs {
+    /// Exodus file to export
+    #[arg(value_name = "INPUT")]
+    pub input: PathBuf,
+
+    /// Output file: legacy VTK (.vtk), XML unstructured grid (.vtu), or a
+    /// ParaView collection (.pvd) of one .vtu per time step. A .vtk or .vtu
+    /// output with several steps is written as numbered files.
+    #[arg(short, long, value_name = "OUTPUT")]
+    pub output: PathBuf,
+
+    /// Time steps to export, 1-based and inclusive (default: all).
+    /// Format: "BEGIN:END" or "BEGIN:END:STRIDE"; an empty END means the last step.
+    #[arg(long, value_name = "BEGIN:END[:STRIDE]")]
+    pub steps: Option<String>,
+
+    /// Comma-separated names of the variables to export (default: all).
+    /// Global, nodal and element block variables are matched.
+    #[arg(long = "vars", value_name = "NAMES")]
+    pub variables: Option<String>,
+
+    /// Print verbose output
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
+/// Arguments of the `convert` command
+#[derive(Args, Debug)]
+pub struct ConvertArgs {
+    /// Mesh to convert: a Gmsh .msh file, an Abaqus .inp deck, Nastran .nas
+    /// or .bdf bulk data, or an Exodus file
+    #[arg(value_name = "INPUT")]
+    pub input: PathBuf,
+
+    /// Converted mesh (overwritten if it exists): an Exodus file for a .msh,
+    /// .inp, .nas or .bdf input, or a Gmsh .msh file for an Exodus input
+    #[arg(short, long, value_name = "OUTPUT")]
+    pub output: PathBuf,
+
+    /// Gmsh format to write: 2 (2.2) or 4 (4.1)
+    #[arg(long = "msh-version", value_name = "VERSION", default_value = "4", value_parser = parse_gmsh_version)]
+    pub msh_version: GmshVersion,
+
+    /// Print verbose output
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
+/// Arguments of the `surface` command
+#[derive(Args, Debug)]
+pub struct SurfaceArgs {
+    /// Exodus file to export
+    #[arg(value_name = "INPUT")]
+    pub input: PathBuf,
+
+    /// Output file: STL (.stl), Wavefront OBJ (.obj) or PLY (.ply)
+    #[arg(short, long, value_name = "OUTPUT")]
+    pub output: PathBuf,
+
+    /// Comma-separated IDs of the side sets to export (default: the exterior
+    /// skin of the mesh)
+    #[arg(long = "side-sets", value_name = "IDS")]
+    pub side_sets: Option<String>,
+
+    /// Nodal variable to write as per-vertex data (OBJ and PLY only)
+    #[arg(long = "var", value_name = "NAME")]
+    pub variable: Option<String>,
+
+    /// Time step of the variable, 1-based (default: the last step)
+    #[arg(long, value_name = "STEP")]
+    pub step: Option<usize>,
+
+    /// Write ASCII instead of binary STL and PLY
+    #[arg(long)]
+    pub ascii: bool,
+
+    /// Print verbose output
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
+/// Arguments of the `xdmf` command
+#[derive(Args, Debug)]
+pub struct XdmfArgs {
+    /// Exodus file to export
+    #[arg(value_name = "INPUT")]
+    pub input: PathBuf,
+
+    /// XDMF index to write (.xmf or .xdmf). The heavy data is written next
+    /// to it with the extension .h5.
+    #[arg(short, long, value_name = "OUTPUT")]
+    pub output: PathBuf,
+
+    /// Time steps to export, 1-based and inclusive (default: all).
+    /// Format: "BEGIN:END" or "BEGIN:END:STRIDE"; an empty END means the last step.
+    #[arg(long, value_name = "BEGIN:END[:STRIDE]")]
+    pub steps: Option<String>,
+
+    /// Comma-separated names of the variables to export (default: all).
+    /// Global, nodal and element block variables are matched.
+    #[arg(long = "vars", value_name = "NAMES")]
+    pub variables: Option<String>,
+
+    /// HDF5 chunk cache size in megabytes.
+    /// Default: auto-detected based on environment (4-128 MB).
+    #[arg(long, value_name = "MB")]
+    pub cache_size: Option<usize>,
+
+    /// HDF5 cache preemption policy (0.0 to 1.0). Default: 0.75.
+    #[arg(long, value_name = "VALUE")]
+    pub preemption: Option<f64>,
+
+    /// Nodes per HDF5 chunk of the coordinates and nodal variables.
+    /// Default: 1,000-10,000 based on environment.
+    #[arg(long, value_name = "SIZE")]
+    pub node_chunk: Option<usize>,
+
+    /// Elements per HDF5 chunk of the connectivity and element variables.
+    /// Default: 1,000-10,000 based on environment.
+    #[arg(long, value_name = "SIZE")]
+    pub element_chunk: Option<usize>,
+
+    /// Time steps per HDF5 chunk of the variables, and per read of the
+    /// input. Default: 1.
+    #[arg(long, value_name = "SIZE")]
+    pub time_chunk: Option<usize>,
+
+    /// Print verbose output
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
 /// Represents a transformation operation
 #[derive(Debug, Clone)]
 pub enum Operation {
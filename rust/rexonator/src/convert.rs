@@ -0,0 +1,81 @@
+//! The `convert` command: convert between Exodus and other mesh formats
+//!
+//! This is a thin front end to `exodus_rs::gmsh`, `exodus_rs::abaqus` and
+//! `exodus_rs::nastran`, choosing the direction of the conversion from the
+//! file extensions.
+
+use exodus_rs::abaqus::import_abaqus;
+use exodus_rs::gmsh::{export_gmsh, import_gmsh};
+use exodus_rs::nastran::import_nastran;
+use std::path::Path;
+
+use crate::cli::{ConvertArgs, Result, TransformError};
+
+/// Whether a path has an extension, ignoring case
+fn has_extension(path: &Path, expected: &str) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case(expected))
+}
+
+/// Whether a path names a Nastran bulk data file
+fn is_nastran(path: &Path) -> bool {
+    has_extension(path, "nas") || has_extension(path, "bdf")
+}
+
+/// Run the `convert` command
+pub fn run_convert(args: &ConvertArgs) -> Result<()> {
+    let is_mesh =
+        |path: &Path| has_extension(path, "msh") || has_extension(path, "inp") || is_nastran(path);
+    if is_mesh(&args.input) == is_mesh(&args.output)
+        || is_mesh(&args.output) && !has_extension(&args.output, "msh")
+    {
+        return Err(TransformError::InvalidFormat(
+            "Convert a Gmsh .msh, Abaqus .inp or Nastran .nas/.bdf INPUT to an Exodus \
+             OUTPUT, or an Exodus INPUT to a Gmsh .msh OUTPUT"
+                .to_string(),
+        ));
+    }
+
+    let counts = if is_nastran(&args.input) {
+        let summary = import_nastran(&args.input, &args.output)?;
+        format!(
+            "{} nodes, {} elements in {} blocks",
+            summary.num_nodes, summary.num_elems, summary.num_blocks
+        )
+    } else if has_extension(&args.input, "inp") {
+        let summary = import_abaqus(&args.input, &args.output)?;
+        format!(
+            "{} nodes, {} elements in {} blocks, {} node sets, {} element sets, {} side sets",
+            summary.num_nodes,
+            summary.num_elems,
+            summary.num_blocks,
+            summary.num_node_sets,
+            summary.num_elem_sets,
+            summary.num_side_sets
+        )
+    } else {
+        let summary = if has_extension(&args.input, "msh") {
+            import_gmsh(&args.input, &args.output)?
+        } else {
+            export_gmsh(&args.input, &args.output, args.msh_version)?
+        };
+        format!(
+            "{} nodes, {} elements in {} blocks, {} node sets, {} side sets",
+            summary.num_nodes,
+            summary.num_elems,
+            summary.num_blocks,
+            summary.num_node_sets,
+            summary.num_side_sets
+        )
+    };
+
+    if args.verbose {
+        println!(
+            "Converted {} to {}: {}",
+            args.input.display(),
+            args.output.display(),
+            counts
+        );
+    }
+    Ok(())
+}
@@ -832,14 +832,13 @@ fn create_mirrored_blocks(
 
     // Find max block ID to assign new IDs for mirrored blocks
     let max_block_id = data.blocks.iter().map(|b| b.id).max().unwrap_or(0);
-    let mut next_block_id = max_block_id + 1;
 
-    for (block_idx, (block, connectivity)) in data
-        .blocks
-        .iter()
-        .zip(data.connectivities.iter())
-        .enumerate()
-    {
+    for (next_block_id, (block_idx, (block, connectivity))) in (max_block_id + 1..).zip(
+        data.blocks
+            .iter()
+            .zip(data.connectivities.iter())
+            .enumerate(),
+    ) {
         let nodes_per_elem = block.num_nodes_per_entry;
         let permutation = get_mirror_permutation(&block.topology, axis).ok_or_else(|| {
             TransformError::InvalidFormat(format!("Unsupported topology: {}", block.topology))
@@ -888,8 +887,6 @@ fn create_mirrored_blocks(
                 next_block_id, block.id, block.num_entries
             );
         }
-
-        next_block_id += 1;
     }
 
     Ok(MirroredBlocksResult {
@@ -925,9 +922,10 @@ fn create_mirrored_node_sets(
         .map(|(id, _, _)| *id)
         .max()
         .unwrap_or(0);
-    let mut next_ns_id = max_ns_id + 1;
 
-    for (idx, (orig_id, nodes, dist_factors)) in data.node_sets.iter().enumerate() {
+    for (next_ns_id, (idx, (orig_id, nodes, dist_factors))) in
+        (max_ns_id + 1..).zip(data.node_sets.iter().enumerate())
+    {
         // Create mirrored node set
         let mirror_nodes: Vec<i64> = nodes
             .iter()
@@ -950,8 +948,6 @@ fn create_mirrored_node_sets(
             .map(|s| s.as_str())
             .unwrap_or(&default_name);
         new_node_set_names.push(format!("{}_mirror", orig_name));
-
-        next_ns_id += 1;
     }
 
     MirroredNodeSetsResult {
@@ -1002,7 +998,6 @@ fn create_mirrored_side_sets(
         .map(|(id, _, _, _)| *id)
         .max()
         .unwrap_or(0);
-    let mut next_ss_id = max_ss_id + 1;
 
     // Build element-to-block mapping for side number remapping
     let elem_to_block = build_element_to_block_map(&data.blocks);
@@ -1014,7 +1009,9 @@ fn create_mirrored_side_sets(
         .map(|b| get_side_number_mapping(&b.topology, axis))
         .collect();
 
-    for (idx, (orig_id, elements, sides, dist_factors)) in data.side_sets.iter().enumerate() {
+    for (next_ss_id, (idx, (orig_id, elements, sides, dist_factors))) in
+        (max_ss_id + 1..).zip(data.side_sets.iter().enumerate())
+    {
         // Mirrored elements have IDs offset by orig_num_elems
         let mirror_elements: Vec<i64> = elements
             .iter()
@@ -1072,8 +1069,6 @@ fn create_mirrored_side_sets(
                 axis
             );
         }
-
-        next_ss_id += 1;
     }
 
     MirroredSideSetsResult {
@@ -0,0 +1,44 @@
+//! The `decompose` command: split a mesh into file-per-processor parts
+//!
+//! This is a thin front end to `exodus_rs::decompose`, writing the parts next
+//! to the input unless another base name is given.
+
+use exodus_rs::decompose::{decompose_file, DecomposeOptions};
+use exodus_rs::{CreateMode, CreateOptions};
+
+use crate::cli::{DecomposeArgs, Result};
+
+/// Run the `decompose` command
+pub fn run_decompose(args: &DecomposeArgs) -> Result<()> {
+    let base = args.output.as_ref().unwrap_or(&args.input);
+
+    if args.verbose {
+        println!("Input: {}", args.input.display());
+        println!(
+            "Decomposing onto {} processors ({:?})",
+            args.num_procs, args.method
+        );
+    }
+
+    let options = DecomposeOptions {
+        method: args.method,
+        create_options: CreateOptions {
+            mode: CreateMode::Clobber,
+            ..Default::default()
+        },
+    };
+    let summary = decompose_file(&args.input, base, args.num_procs, &options)?;
+
+    if args.verbose {
+        for (rank, path) in summary.paths.iter().enumerate() {
+            println!(
+                "  {}: {} elements, {} nodes ({} shared)",
+                path.display(),
+                summary.num_elems[rank],
+                summary.num_nodes[rank],
+                summary.num_border_nodes[rank]
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//! The `diff` command: compare two files within tolerances
+//!
+//! This is a thin front end to `exodus_rs::diff`, building the comparison
+//! options from the command line and printing the report. The caller turns
+//! a difference into exit status 2, keeping 1 for errors.
+
+use exodus_rs::diff::{diff_files, DiffOptions, EntityMatching};
+
+use crate::cli::{DiffArgs, Result};
+
+/// Run the `diff` command, returning whether the files differ
+pub fn run_diff(args: &DiffArgs) -> Result<bool> {
+    let options = DiffOptions {
+        tolerance: args.tolerance,
+        variable_tolerances: args.variable_tolerances.iter().cloned().collect(),
+        coordinate_tolerance: args.coordinate_tolerance,
+        time_tolerance: args.time_tolerance,
+        entity_matching: match args.match_coords {
+            Some(tolerance) => EntityMatching::Coordinates { tolerance },
+            None => EntityMatching::Ids,
+        },
+        time_matching: args.time_matching,
+    };
+    let report = diff_files(&args.first, &args.second, &options)?;
+
+    if args.quiet {
+        // The summary is the last line of the full report
+        let text = report.to_string();
+        println!("{}", text.lines().last().unwrap_or_default());
+    } else {
+        println!(
+            "Comparing {} to {}",
+            args.first.display(),
+            args.second.display()
+        );
+        println!("{}", report);
+    }
+    Ok(report.has_differences())
+}
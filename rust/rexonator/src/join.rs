@@ -0,0 +1,63 @@
+//! The `join` command: merge decomposed file-per-processor results
+//!
+//! This is a thin front end to `exodus_rs::join`, resolving the input files
+//! and the step and variable selections from the command line.
+
+use exodus_rs::join::{find_decomposed_files, join_files, JoinOptions};
+use exodus_rs::{mode, CreateMode, CreateOptions, ExodusFile};
+use std::path::PathBuf;
+
+use crate::cli::{JoinArgs, Result};
+use crate::parsers::{parse_steps, parse_variable_names};
+
+/// Run the `join` command
+pub fn run_join(args: &JoinArgs) -> Result<()> {
+    // A lone input names the decomposition unless it is the only file there is
+    let parts: Vec<PathBuf> = match args.inputs.as_slice() {
+        [base] => match find_decomposed_files(base) {
+            Ok(parts) => parts,
+            Err(_) if base.is_file() => vec![base.clone()],
+            Err(e) => return Err(e.into()),
+        },
+        inputs => inputs.to_vec(),
+    };
+
+    let steps = match &args.steps {
+        Some(steps) => {
+            let num_steps = ExodusFile::<mode::Read>::open(&parts[0])?.num_time_steps()?;
+            Some(parse_steps(steps, num_steps)?)
+        }
+        None => None,
+    };
+    let variables = args
+        .variables
+        .as_deref()
+        .map(parse_variable_names)
+        .transpose()?;
+
+    if args.verbose {
+        println!("Joining {} files:", parts.len());
+        for part in &parts {
+            println!("  {}", part.display());
+        }
+        println!("Output: {}", args.output.display());
+    }
+
+    let options = JoinOptions {
+        steps,
+        variables,
+        create_options: CreateOptions {
+            mode: CreateMode::Clobber,
+            ..Default::default()
+        },
+    };
+    let summary = join_files(&parts, &args.output, &options)?;
+
+    if args.verbose {
+        println!(
+            "Joined mesh: {} nodes, {} elements, {} time steps",
+            summary.num_nodes, summary.num_elems, summary.num_time_steps
+        );
+    }
+    Ok(())
+}
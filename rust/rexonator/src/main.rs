@@ -3,20 +3,37 @@
 //! This tool applies geometric transformations to Exodus II mesh files,
 //! including translation, rotation, scaling, and mirroring. Transformations
 //! are applied in the order they appear on the command line.
+//!
+//! The `join` command merges decomposed file-per-processor results into a
+//! single file, and the `decompose` command splits a mesh into
+//! file-per-processor parts. The `diff` command compares two files within
+//! tolerances and exits with status 2 if they differ. The `vtk` command
+//! exports a mesh and its results to VTK, and the `convert` command converts
+//! between Exodus and Gmsh meshes and imports Abaqus decks and Nastran bulk
+//! data. The `surface` command exports the skin of a mesh or its side sets
+//! to STL, OBJ or PLY, and the `xdmf` command exports results to XDMF with
+//! HDF5 heavy data.
 
 mod cli;
+mod convert;
 mod copy_mirror_merge;
+mod decompose;
+mod diff;
+mod join;
 mod man;
 mod operations;
 mod parsers;
 mod performance;
 mod progress;
+mod surface;
+mod vtk;
+mod xdmf;
 
 use clap::Parser;
 use exodus_rs::{mode, ExodusFile};
 use std::path::{Path, PathBuf};
 
-use cli::{Axis, Cli, Operation, Result, TransformError};
+use cli::{Axis, Cli, Command, Operation, Result, TransformError};
 
 /// Determine the output path and whether to use in-place mode.
 ///
@@ -71,18 +88,43 @@ fn determine_output_mode(
 
     Ok((output.clone(), same_file))
 }
+use convert::run_convert;
 use copy_mirror_merge::{
     apply_operation_to_mesh_data, copy_mirror_merge, normalize_time_mesh_data, read_mesh_data,
     warn_memory_usage, write_mesh_data, VectorDetectionConfig,
 };
+use decompose::run_decompose;
+use diff::run_diff;
+use join::run_join;
 use man::show_man_page;
 use operations::{apply_simple_operation, normalize_time};
 use parsers::extract_ordered_operations;
 use performance::PerformanceOptions;
+use surface::run_surface;
+use vtk::run_vtk;
+use xdmf::run_xdmf;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // Whole-file commands bypass the transformation pipeline
+    if let Some(command) = &cli.command {
+        return match command {
+            Command::Join(args) => run_join(args),
+            Command::Decompose(args) => run_decompose(args),
+            Command::Diff(args) => {
+                if run_diff(args)? {
+                    std::process::exit(2);
+                }
+                Ok(())
+            }
+            Command::Vtk(args) => run_vtk(args),
+            Command::Convert(args) => run_convert(args),
+            Command::Surface(args) => run_surface(args),
+            Command::Xdmf(args) => run_xdmf(args),
+        };
+    }
+
     // Build performance configuration from CLI options
     let perf_config = PerformanceOptions::from_cli(&cli);
 
@@ -5,6 +5,9 @@
 //! operation ordering.
 
 use crate::cli::{Axis, Cli, Operation, Result, TransformError};
+use exodus_rs::decompose::DecompositionMethod;
+use exodus_rs::diff::{TimeMatching, Tolerance};
+use exodus_rs::gmsh::GmshVersion;
 
 /// Parse a translate argument "x,y,z" into an array of 3 floats
 pub fn parse_translate(s: &str) -> Result<[f64; 3]> {
@@ -98,6 +101,176 @@ pub fn parse_scale_field(s: &str) -> Result<(String, f64)> {
     Ok((field_name, scale_factor))
 }
 
+/// Parse a steps argument "BEGIN:END[:STRIDE]" into 0-based step indices
+///
+/// Steps are 1-based and inclusive. An empty BEGIN or END means the first or
+/// last of the `num_steps` steps, and a single number selects one step.
+pub fn parse_steps(s: &str, num_steps: usize) -> Result<Vec<usize>> {
+    let parts: Vec<&str> = s.split(':').map(str::trim).collect();
+    if parts.len() > 3 {
+        return Err(TransformError::InvalidFormat(format!(
+            "Steps require BEGIN:END[:STRIDE], got '{}'",
+            s
+        )));
+    }
+
+    let parse = |part: &str, default: usize, what: &str| -> Result<usize> {
+        if part.is_empty() {
+            return Ok(default);
+        }
+        part.parse::<usize>()
+            .map_err(|_| TransformError::InvalidFormat(format!("Invalid {} step: {}", what, part)))
+    };
+    let begin = parse(parts[0], 1, "begin")?;
+    let end = match parts.get(1) {
+        Some(end) => parse(end, num_steps, "end")?,
+        None => begin,
+    };
+    let stride = match parts.get(2) {
+        Some(stride) => parse(stride, 1, "stride")?,
+        None => 1,
+    };
+
+    if begin == 0 || begin > end || end > num_steps || stride == 0 {
+        return Err(TransformError::InvalidFormat(format!(
+            "Steps '{}' are not a valid range of the {} time steps",
+            s, num_steps
+        )));
+    }
+    Ok((begin - 1..end).step_by(stride).collect())
+}
+
+/// Parse a comma-separated list of variable names, as given to `--vars`
+pub fn parse_variable_names(s: &str) -> Result<Vec<String>> {
+    let names: Vec<String> = s
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    if names.is_empty() {
+        return Err(TransformError::InvalidFormat(
+            "--vars requires at least one variable name".to_string(),
+        ));
+    }
+    Ok(names)
+}
+
+/// Parse a comma-separated list of set IDs, as given to `--side-sets`
+pub fn parse_set_ids(s: &str) -> Result<Vec<i64>> {
+    let ids = s
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            id.parse::<i64>()
+                .map_err(|_| TransformError::InvalidFormat(format!("Invalid set ID: {}", id)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if ids.is_empty() {
+        return Err(TransformError::InvalidFormat(
+            "--side-sets requires at least one set ID".to_string(),
+        ));
+    }
+    Ok(ids)
+}
+
+/// Parse a decomposition method name: "rcb", "inertial" or "block"
+///
+/// Used as a clap value parser, so the error is the message clap reports.
+pub fn parse_decomposition_method(s: &str) -> std::result::Result<DecompositionMethod, String> {
+    match s.to_lowercase().as_str() {
+        "rcb" => Ok(DecompositionMethod::Rcb),
+        "inertial" => Ok(DecompositionMethod::Inertial),
+        "block" => Ok(DecompositionMethod::Block),
+        _ => Err(format!(
+            "Invalid decomposition method '{}', must be rcb, inertial, or block",
+            s
+        )),
+    }
+}
+
+/// Parse a diff tolerance: "KIND:VALUE[:FLOOR]", "VALUE" (relative) or "ignore"
+///
+/// KIND is abs, rel or combined. Used as a clap value parser.
+pub fn parse_tolerance(s: &str) -> std::result::Result<Tolerance, String> {
+    let parts: Vec<&str> = s.split(':').map(str::trim).collect();
+    if parts.len() == 1 && parts[0].eq_ignore_ascii_case("ignore") {
+        return Ok(Tolerance::ignore());
+    }
+    let number = |part: &str| {
+        part.parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite() && *value >= 0.0)
+            .ok_or_else(|| format!("Invalid tolerance value '{}' in '{}'", part, s))
+    };
+    let (kind, rest) = match parts.as_slice() {
+        [value] => ("rel", vec![*value]),
+        [kind, rest @ ..] if (1..=2).contains(&rest.len()) => (*kind, rest.to_vec()),
+        _ => {
+            return Err(format!(
+                "Invalid tolerance '{}', expected KIND:VALUE[:FLOOR] or ignore",
+                s
+            ))
+        }
+    };
+    let value = number(rest[0])?;
+    let tolerance = match kind.to_lowercase().as_str() {
+        "abs" | "absolute" => Tolerance::absolute(value),
+        "rel" | "relative" => Tolerance::relative(value),
+        "combined" | "com" => Tolerance::combined(value),
+        _ => {
+            return Err(format!(
+                "Invalid tolerance kind '{}', must be abs, rel, combined, or ignore",
+                kind
+            ))
+        }
+    };
+    match rest.get(1) {
+        Some(floor) => Ok(tolerance.with_floor(number(floor)?)),
+        None => Ok(tolerance),
+    }
+}
+
+/// Parse a variable tolerance "NAME=TOL", with TOL as for [`parse_tolerance`]
+pub fn parse_variable_tolerance(s: &str) -> std::result::Result<(String, Tolerance), String> {
+    match s.split_once('=') {
+        Some((name, tolerance)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), parse_tolerance(tolerance)?))
+        }
+        _ => Err(format!(
+            "Invalid variable tolerance '{}', expected NAME=TOL",
+            s
+        )),
+    }
+}
+
+/// Parse a time step pairing mode: "index", "time" or "interpolate"
+pub fn parse_time_matching(s: &str) -> std::result::Result<TimeMatching, String> {
+    match s.to_lowercase().as_str() {
+        "index" => Ok(TimeMatching::Index),
+        "time" => Ok(TimeMatching::Time),
+        "interpolate" => Ok(TimeMatching::Interpolate),
+        _ => Err(format!(
+            "Invalid time matching '{}', must be index, time, or interpolate",
+            s
+        )),
+    }
+}
+
+/// Parse a Gmsh format version: "2" or "2.2", "4" or "4.1"
+///
+/// Used as a clap value parser, so the error is the message clap reports.
+pub fn parse_gmsh_version(s: &str) -> std::result::Result<GmshVersion, String> {
+    match s {
+        "2" | "2.2" => Ok(GmshVersion::V2),
+        "4" | "4.1" => Ok(GmshVersion::V4),
+        _ => Err(format!(
+            "Invalid Gmsh version '{}', must be 2 (2.2) or 4 (4.1)",
+            s
+        )),
+    }
+}
+
 /// Check if an argument matches a flag (handles both "--flag" and "--flag=value" forms)
 pub fn arg_matches_flag(arg: &str, flag: &str) -> bool {
     arg == flag || arg.starts_with(&format!("{}=", flag))
@@ -233,6 +406,319 @@ mod tests {
         assert!(parse_translate("a,b,c").is_err());
     }
 
+    #[test]
+    fn test_parse_steps() {
+        assert_eq!(parse_steps("1:3", 5).unwrap(), vec![0, 1, 2]);
+        assert_eq!(parse_steps("4:", 5).unwrap(), vec![3, 4]);
+        assert_eq!(parse_steps(":5:2", 5).unwrap(), vec![0, 2, 4]);
+        assert_eq!(parse_steps("2", 5).unwrap(), vec![1]);
+
+        assert!(parse_steps("0:3", 5).is_err());
+        assert!(parse_steps("1:6", 5).is_err());
+        assert!(parse_steps("3:1", 5).is_err());
+        assert!(parse_steps("1:3:0", 5).is_err());
+        assert!(parse_steps("1:2:3:4", 5).is_err());
+        assert!(parse_steps("a:b", 5).is_err());
+    }
+
+    #[test]
+    fn test_parse_variable_names() {
+        assert_eq!(
+            parse_variable_names("disp_x, disp_y,,").unwrap(),
+            vec!["disp_x", "disp_y"]
+        );
+        assert!(parse_variable_names(" , ").is_err());
+    }
+
+    #[test]
+    fn test_join_command_parsing() {
+        use crate::cli::Command;
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from([
+            "rexonator",
+            "join",
+            "mesh.e.2.0",
+            "mesh.e.2.1",
+            "-o",
+            "mesh.e",
+            "--steps",
+            "1:",
+        ])
+        .unwrap();
+        let Some(Command::Join(args)) = cli.command else {
+            panic!("expected the join command");
+        };
+        assert_eq!(args.inputs.len(), 2);
+        assert_eq!(args.output, PathBuf::from("mesh.e"));
+        assert_eq!(args.steps.as_deref(), Some("1:"));
+        assert!(args.variables.is_none());
+
+        // Transformations still take INPUT and OUTPUT positionally
+        let cli =
+            Cli::try_parse_from(["rexonator", "in.exo", "out.exo", "--scale-len", "2"]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!(cli.input, Some(PathBuf::from("in.exo")));
+        assert!(Cli::try_parse_from(["rexonator", "join", "mesh.e"]).is_err());
+    }
+
+    #[test]
+    fn test_decompose_command_parsing() {
+        use crate::cli::Command;
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(["rexonator", "decompose", "mesh.e", "-n", "4"]).unwrap();
+        let Some(Command::Decompose(args)) = cli.command else {
+            panic!("expected the decompose command");
+        };
+        assert_eq!(args.input, PathBuf::from("mesh.e"));
+        assert_eq!(args.num_procs, 4);
+        assert_eq!(args.method, DecompositionMethod::Rcb);
+        assert!(args.output.is_none());
+
+        let cli = Cli::try_parse_from([
+            "rexonator",
+            "decompose",
+            "mesh.e",
+            "--num-procs",
+            "8",
+            "--method",
+            "Inertial",
+            "-o",
+            "parts/mesh.e",
+        ])
+        .unwrap();
+        let Some(Command::Decompose(args)) = cli.command else {
+            panic!("expected the decompose command");
+        };
+        assert_eq!(args.method, DecompositionMethod::Inertial);
+        assert_eq!(args.output, Some(PathBuf::from("parts/mesh.e")));
+
+        assert!(Cli::try_parse_from([
+            "rexonator",
+            "decompose",
+            "mesh.e",
+            "-n",
+            "2",
+            "--method",
+            "metis"
+        ])
+        .is_err());
+        assert!(Cli::try_parse_from(["rexonator", "decompose", "mesh.e"]).is_err());
+        assert_eq!(
+            parse_decomposition_method("BLOCK").unwrap(),
+            DecompositionMethod::Block
+        );
+    }
+
+    #[test]
+    fn test_diff_command_parsing() {
+        use crate::cli::Command;
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(["rexonator", "diff", "a.e", "b.e"]).unwrap();
+        let Some(Command::Diff(args)) = cli.command else {
+            panic!("expected the diff command");
+        };
+        assert_eq!(args.first, PathBuf::from("a.e"));
+        assert_eq!(args.second, PathBuf::from("b.e"));
+        assert_eq!(args.tolerance, Tolerance::relative(1e-6));
+        assert_eq!(args.coordinate_tolerance, Tolerance::combined(1e-6));
+        assert_eq!(args.time_matching, TimeMatching::Time);
+        assert!(args.variable_tolerances.is_empty());
+        assert!(args.match_coords.is_none());
+
+        let cli = Cli::try_parse_from([
+            "rexonator",
+            "diff",
+            "a.e",
+            "b.e",
+            "-t",
+            "abs:1e-4:1e-12",
+            "--var-tol",
+            "temp=combined:1e-3",
+            "--var-tol",
+            "dt=ignore",
+            "--match-coords",
+            "0.01",
+            "--time-match",
+            "interpolate",
+            "-q",
+        ])
+        .unwrap();
+        let Some(Command::Diff(args)) = cli.command else {
+            panic!("expected the diff command");
+        };
+        assert_eq!(args.tolerance, Tolerance::absolute(1e-4).with_floor(1e-12));
+        assert_eq!(
+            args.variable_tolerances,
+            vec![
+                ("temp".to_string(), Tolerance::combined(1e-3)),
+                ("dt".to_string(), Tolerance::ignore())
+            ]
+        );
+        assert_eq!(args.match_coords, Some(0.01));
+        assert_eq!(args.time_matching, TimeMatching::Interpolate);
+        assert!(args.quiet);
+
+        assert!(Cli::try_parse_from(["rexonator", "diff", "a.e"]).is_err());
+        assert!(Cli::try_parse_from(["rexonator", "diff", "a.e", "b.e", "-t", "tight"]).is_err());
+    }
+
+    #[test]
+    fn test_convert_command_parsing() {
+        use crate::cli::Command;
+        use clap::Parser;
+
+        let cli =
+            Cli::try_parse_from(["rexonator", "convert", "part.e", "-o", "part.msh"]).unwrap();
+        let Some(Command::Convert(args)) = cli.command else {
+            panic!("expected the convert command");
+        };
+        assert_eq!(args.input, PathBuf::from("part.e"));
+        assert_eq!(args.msh_version, GmshVersion::V4);
+
+        let cli = Cli::try_parse_from([
+            "rexonator",
+            "convert",
+            "part.e",
+            "-o",
+            "part.msh",
+            "--msh-version",
+            "2.2",
+        ])
+        .unwrap();
+        let Some(Command::Convert(args)) = cli.command else {
+            panic!("expected the convert command");
+        };
+        assert_eq!(args.msh_version, GmshVersion::V2);
+        assert!(parse_gmsh_version("3").is_err());
+    }
+
+    #[test]
+    fn test_vtk_command_parsing() {
+        use crate::cli::Command;
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from([
+            "rexonator",
+            "vtk",
+            "results.e",
+            "-o",
+            "results.pvd",
+            "--steps",
+            "2:",
+            "--vars",
+            "disp_x,disp_y",
+        ])
+        .unwrap();
+        let Some(Command::Vtk(args)) = cli.command else {
+            panic!("expected the vtk command");
+        };
+        assert_eq!(args.input, PathBuf::from("results.e"));
+        assert_eq!(args.output, PathBuf::from("results.pvd"));
+        assert_eq!(args.steps.as_deref(), Some("2:"));
+        assert_eq!(args.variables.as_deref(), Some("disp_x,disp_y"));
+
+        assert!(Cli::try_parse_from(["rexonator", "vtk", "results.e"]).is_err());
+    }
+
+    #[test]
+    fn test_surface_command_parsing() {
+        use crate::cli::Command;
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from([
+            "rexonator",
+            "surface",
+            "results.e",
+            "-o",
+            "wall.ply",
+            "--side-sets",
+            "10, 20",
+            "--var",
+            "temperature",
+            "--step",
+            "3",
+        ])
+        .unwrap();
+        let Some(Command::Surface(args)) = cli.command else {
+            panic!("expected the surface command");
+        };
+        assert_eq!(args.output, PathBuf::from("wall.ply"));
+        assert_eq!(
+            parse_set_ids(args.side_sets.as_deref().unwrap()).unwrap(),
+            vec![10, 20]
+        );
+        assert_eq!(args.variable.as_deref(), Some("temperature"));
+        assert_eq!(args.step, Some(3));
+        assert!(!args.ascii);
+
+        assert!(parse_set_ids("1,x").is_err());
+        assert!(parse_set_ids(" , ").is_err());
+        assert!(Cli::try_parse_from(["rexonator", "surface", "results.e"]).is_err());
+    }
+
+    #[test]
+    fn test_xdmf_command_parsing() {
+        use crate::cli::Command;
+        use crate::performance::PerformanceOptions;
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from([
+            "rexonator",
+            "xdmf",
+            "results.e",
+            "-o",
+            "results.xmf",
+            "--time-chunk",
+            "10",
+            "--steps",
+            "1::2",
+            "--vars",
+            "disp_x,pressure",
+        ])
+        .unwrap();
+        let Some(Command::Xdmf(args)) = cli.command else {
+            panic!("expected the xdmf command");
+        };
+        let config = PerformanceOptions::from_xdmf_args(&args).to_exodus_config();
+        assert_eq!(config.chunks.time_chunk_size, 10);
+        assert_eq!(args.output, PathBuf::from("results.xmf"));
+        assert_eq!(
+            parse_steps(args.steps.as_deref().unwrap(), 5).unwrap(),
+            vec![0, 2, 4]
+        );
+        assert_eq!(
+            parse_variable_names(args.variables.as_deref().unwrap()).unwrap(),
+            vec!["disp_x", "pressure"]
+        );
+        assert!(Cli::try_parse_from(["rexonator", "xdmf", "results.e"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_tolerance() {
+        assert_eq!(parse_tolerance("1e-3").unwrap(), Tolerance::relative(1e-3));
+        assert_eq!(
+            parse_tolerance("ABS:0.5").unwrap(),
+            Tolerance::absolute(0.5)
+        );
+        assert_eq!(
+            parse_tolerance("rel:1e-6:1e-10").unwrap(),
+            Tolerance::relative(1e-6).with_floor(1e-10)
+        );
+        assert_eq!(parse_tolerance("Ignore").unwrap(), Tolerance::ignore());
+        assert!(parse_tolerance("abs").is_err());
+        assert!(parse_tolerance("abs:-1").is_err());
+        assert!(parse_tolerance("log:1e-3").is_err());
+        assert!(parse_tolerance("abs:1:2:3").is_err());
+
+        assert!(parse_variable_tolerance("=abs:1").is_err());
+        assert!(parse_variable_tolerance("temp").is_err());
+        assert_eq!(parse_time_matching("Index").unwrap(), TimeMatching::Index);
+        assert!(parse_time_matching("nearest").is_err());
+    }
+
     #[test]
     fn test_parse_rotate() {
         let (seq, angles) = parse_rotate("Z,90").unwrap();
@@ -285,9 +771,9 @@ mod tests {
         assert!((result.1 - 0.5).abs() < 1e-10);
 
         // With spaces
-        let result = parse_scale_field("  velocity  ,  3.14  ").unwrap();
+        let result = parse_scale_field("  velocity  ,  3.25  ").unwrap();
         assert_eq!(result.0, "velocity");
-        assert!((result.1 - 3.14).abs() < 1e-10);
+        assert!((result.1 - 3.25).abs() < 1e-10);
 
         // Negative scale factor
         let result = parse_scale_field("displacement,-1.0").unwrap();
@@ -471,6 +957,7 @@ mod tests {
         /// Build the final `Cli` instance.
         fn build(self) -> Cli {
             Cli {
+                command: None,
                 input: Some(PathBuf::from("input.exo")),
                 output: Some(PathBuf::from("output.exo")),
                 scale_len: self.scale_len,
@@ -1,4 +1,4 @@
-use crate::cli::Cli;
+use crate::cli::{Cli, XdmfArgs};
 use std::env;
 use std::fmt;
 
@@ -118,6 +118,35 @@ impl PerformanceOptions {
 
     /// Build performance options from CLI arguments
     pub fn from_cli(cli: &Cli) -> Self {
+        Self::from_flags(
+            cli.cache_size,
+            cli.preemption,
+            cli.node_chunk,
+            cli.element_chunk,
+            cli.time_chunk,
+        )
+    }
+
+    /// Build performance options from the arguments of the `xdmf` command
+    pub fn from_xdmf_args(args: &XdmfArgs) -> Self {
+        Self::from_flags(
+            args.cache_size,
+            args.preemption,
+            args.node_chunk,
+            args.element_chunk,
+            args.time_chunk,
+        )
+    }
+
+    /// Build performance options from the performance flags, filling in
+    /// defaults for the environment
+    fn from_flags(
+        cache_size: Option<usize>,
+        preemption: Option<f64>,
+        node_chunk: Option<usize>,
+        element_chunk: Option<usize>,
+        time_chunk: Option<usize>,
+    ) -> Self {
         // Start with auto-detected defaults based on environment
         let node_type = detect_node_type();
         let (default_cache, default_chunk) = match node_type {
@@ -126,16 +155,15 @@ impl PerformanceOptions {
             NodeType::Unknown => (16 * 1024 * 1024, 5_000),   // 16 MB, 5k nodes/elements
         };
 
-        let cache_size = cli
-            .cache_size
+        let cache_size = cache_size
             .map(|mb| mb * 1024 * 1024)
             .unwrap_or(default_cache);
 
-        let preemption = cli.preemption.unwrap_or(0.75).clamp(0.0, 1.0);
+        let preemption = preemption.unwrap_or(0.75).clamp(0.0, 1.0);
 
-        let node_chunk_size = cli.node_chunk.unwrap_or(default_chunk);
-        let element_chunk_size = cli.element_chunk.unwrap_or(default_chunk);
-        let time_chunk_size = cli.time_chunk.unwrap_or(0);
+        let node_chunk_size = node_chunk.unwrap_or(default_chunk);
+        let element_chunk_size = element_chunk.unwrap_or(default_chunk);
+        let time_chunk_size = time_chunk.unwrap_or(0);
 
         // Auto-calculate hash slots based on cache size
         // Target: ~100x the number of chunks that fit in cache
@@ -230,6 +258,7 @@ mod tests {
     fn test_performance_options_defaults() {
         // Create a mock CLI with no performance options set
         let cli = Cli {
+            command: None,
             input: Some(PathBuf::from("input.exo")),
             output: Some(PathBuf::from("output.exo")),
             scale_len: vec![],
@@ -274,6 +303,7 @@ mod tests {
     #[test]
     fn test_performance_options_custom() {
         let cli = Cli {
+            command: None,
             input: Some(PathBuf::from("input.exo")),
             output: Some(PathBuf::from("output.exo")),
             scale_len: vec![],
@@ -311,6 +341,7 @@ mod tests {
     #[test]
     fn test_preemption_clamping() {
         let mut cli = Cli {
+            command: None,
             input: Some(PathBuf::from("input.exo")),
             output: Some(PathBuf::from("output.exo")),
             scale_len: vec![],
@@ -0,0 +1,51 @@
+//! The `surface` command: export the skin of a mesh or its side sets to
+//! STL, OBJ or PLY
+//!
+//! This is a thin front end to `exodus_rs::surface`, resolving the side set
+//! and step selections from the command line.
+
+use exodus_rs::surface::{write_surface, SurfaceOptions};
+use exodus_rs::{mode, ExodusFile};
+
+use crate::cli::{Result, SurfaceArgs, TransformError};
+use crate::parsers::parse_set_ids;
+
+/// Run the `surface` command
+pub fn run_surface(args: &SurfaceArgs) -> Result<()> {
+    let file = ExodusFile::<mode::Read>::open(&args.input)?;
+    let side_sets = args.side_sets.as_deref().map(parse_set_ids).transpose()?;
+    let step = match args.step {
+        Some(0) => {
+            return Err(TransformError::InvalidFormat(
+                "--step is 1-based; the first step is 1".to_string(),
+            ))
+        }
+        Some(step) => Some(step - 1),
+        None => None,
+    };
+
+    if args.verbose {
+        match &side_sets {
+            Some(ids) => println!("Exporting side sets {:?} of {}", ids, args.input.display()),
+            None => println!("Exporting the skin of {}", args.input.display()),
+        }
+    }
+
+    let options = SurfaceOptions {
+        side_sets,
+        variable: args.variable.clone(),
+        step,
+        ascii: args.ascii,
+    };
+    let summary = write_surface(&file, &args.output, &options)?;
+
+    if args.verbose {
+        println!(
+            "Exported {} vertices, {} triangles to {}",
+            summary.num_vertices,
+            summary.num_triangles,
+            args.output.display()
+        );
+    }
+    Ok(())
+}
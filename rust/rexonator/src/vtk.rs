@@ -0,0 +1,41 @@
+//! The `vtk` command: export a mesh and its results to VTK
+//!
+//! This is a thin front end to `exodus_rs::vtk`, resolving the step and
+//! variable selections from the command line.
+
+use exodus_rs::vtk::{write_vtk, VtkOptions};
+use exodus_rs::{mode, ExodusFile};
+
+use crate::cli::{Result, VtkArgs};
+use crate::parsers::{parse_steps, parse_variable_names};
+
+/// Run the `vtk` command
+pub fn run_vtk(args: &VtkArgs) -> Result<()> {
+    let file = ExodusFile::<mode::Read>::open(&args.input)?;
+    let steps = match &args.steps {
+        Some(steps) => Some(parse_steps(steps, file.num_time_steps()?)?),
+        None => None,
+    };
+    let variables = args
+        .variables
+        .as_deref()
+        .map(parse_variable_names)
+        .transpose()?;
+
+    if args.verbose {
+        println!("Exporting {}", args.input.display());
+    }
+
+    let summary = write_vtk(&file, &args.output, &VtkOptions { steps, variables })?;
+
+    if args.verbose {
+        println!(
+            "Exported {} points, {} cells, {} time steps",
+            summary.num_points, summary.num_cells, summary.num_time_steps
+        );
+        for path in &summary.files {
+            println!("  {}", path.display());
+        }
+    }
+    Ok(())
+}
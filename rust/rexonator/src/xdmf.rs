@@ -0,0 +1,49 @@
+//! The `xdmf` command: export a mesh and its results to XDMF and HDF5
+//!
+//! This is a thin front end to `exodus_rs::xdmf`, resolving the step and
+//! variable selections and the HDF5 chunk sizes and cache from the command
+//! line.
+
+use exodus_rs::xdmf::{write_xdmf, XdmfOptions};
+use exodus_rs::{mode, ExodusFile};
+
+use crate::cli::{Result, XdmfArgs};
+use crate::parsers::{parse_steps, parse_variable_names};
+use crate::performance::PerformanceOptions;
+
+/// Run the `xdmf` command
+pub fn run_xdmf(args: &XdmfArgs) -> Result<()> {
+    let perf = PerformanceOptions::from_xdmf_args(args);
+    let file = ExodusFile::<mode::Read>::open(&args.input)?;
+    let steps = match &args.steps {
+        Some(steps) => Some(parse_steps(steps, file.num_time_steps()?)?),
+        None => None,
+    };
+    let variables = args
+        .variables
+        .as_deref()
+        .map(parse_variable_names)
+        .transpose()?;
+
+    if args.verbose {
+        println!("Exporting {}", args.input.display());
+        println!("{}", perf);
+    }
+
+    let options = XdmfOptions {
+        steps,
+        variables,
+        performance: Some(perf.to_exodus_config()),
+    };
+    let summary = write_xdmf(&file, &args.output, &options)?;
+
+    if args.verbose {
+        println!(
+            "Exported {} nodes, {} elements, {} time steps",
+            summary.num_nodes, summary.num_elems, summary.num_time_steps
+        );
+        println!("  {}", args.output.display());
+        println!("  {}", summary.heavy_data.display());
+    }
+    Ok(())
+}
@@ -7,17 +7,20 @@
 //! and verification of CLI behavior.
 //!
 //! # Dead Code Warnings
-//! Functions in this module are often used by other integration test modules
-//! (e.g., `integration_basic.rs`, `integration_cmm.rs`). Since Rust's compiler
-//! checks for unused code within each compilation unit, these helper functions
-//! might appear as 'dead code' from `fixtures.rs`'s perspective. The `#[allow(dead_code)]`
-//! attribute is used on these functions to suppress these warnings, as they are
-//! indeed used elsewhere in the test suite.
+//! Each integration test binary includes this module with `mod fixtures;` and
+//! uses only some of its helpers, so the rest would appear as dead code in that
+//! binary. Dead code warnings are allowed for the whole module for this reason.
+
+#![allow(dead_code)]
 
 use exodus_rs::{types::*, ExodusFile};
 use std::path::PathBuf;
+use std::process::Command;
 use tempfile::TempDir;
 
+/// Result type for fixture helpers
+pub type FixtureResult<T> = Result<T, Box<dyn std::error::Error>>;
+
 /// Test context that manages temporary files and provides cleanup
 pub struct TestContext {
     pub temp_dir: TempDir,
@@ -35,6 +38,17 @@ impl TestContext {
     }
 }
 
+impl Default for TestContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Command running the rexonator binary under test
+pub fn rexonator_cmd() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_rexonator"))
+}
+
 /// Create a simple 2D QUAD4 mesh (4 elements in a 2x2 grid)
 ///
 /// Geometry: Unit square [0,1] x [0,1]
@@ -541,9 +555,6 @@ pub fn create_pyramid5_mesh(path: &PathBuf) -> Result<(), Box<dyn std::error::Er
 }
 
 /// Create a HEX8 mesh with element variables
-///
-/// #[allow(dead_code)] is used because this function is called from other integration tests.
-#[allow(dead_code)]
 pub fn create_hex8_with_elem_vars(path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
     // Same geometry as create_hex8_mesh but with element variables
     let x_coords: Vec<f64> = vec![
@@ -688,8 +699,6 @@ pub fn create_mesh_with_time_steps(path: &PathBuf) -> Result<(), Box<dyn std::er
 }
 
 /// Create a simple mesh for basic transformation testing
-///
-/// #[allow(dead_code)] is used because this function is called from other integration tests.
 pub fn create_simple_cube(path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
     // Single HEX8 element (unit cube)
     let x_coords: Vec<f64> = vec![0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0];
@@ -737,9 +746,6 @@ pub fn create_simple_cube(path: &PathBuf) -> Result<(), Box<dyn std::error::Erro
 }
 
 /// Create mesh with global variables for testing
-///
-/// #[allow(dead_code)] is used because this function is called from other integration tests.
-#[allow(dead_code)]
 pub fn create_mesh_with_global_vars(path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
     let x_coords: Vec<f64> = vec![0.0, 1.0, 0.0, 1.0];
     let y_coords: Vec<f64> = vec![0.0, 0.0, 1.0, 1.0];
@@ -792,9 +798,7 @@ pub fn create_mesh_with_global_vars(path: &PathBuf) -> Result<(), Box<dyn std::e
 }
 
 /// Read coordinate bounds from a file for verification
-pub fn read_coord_bounds(
-    path: &PathBuf,
-) -> Result<([f64; 2], [f64; 2], [f64; 2]), Box<dyn std::error::Error>> {
+pub fn read_coord_bounds(path: &PathBuf) -> FixtureResult<([f64; 2], [f64; 2], [f64; 2])> {
     let file = ExodusFile::<exodus_rs::mode::Read>::open(path)?;
     let coords = file.coords::<f64>()?;
     drop(file); // Explicitly close the file before returning
@@ -818,12 +822,7 @@ pub fn read_coord_bounds(
 }
 
 /// Read all coordinates from a file
-///
-/// #[allow(dead_code)] is used because this function is called from other integration tests.
-#[allow(dead_code)]
-pub fn read_coords(
-    path: &PathBuf,
-) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>), Box<dyn std::error::Error>> {
+pub fn read_coords(path: &PathBuf) -> FixtureResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
     let file = ExodusFile::<exodus_rs::mode::Read>::open(path)?;
     let coords = file.coords::<f64>()?;
     drop(file); // Explicitly close the file before returning
@@ -867,9 +866,6 @@ pub fn read_nodal_var_names(path: &PathBuf) -> Result<Vec<String>, Box<dyn std::
 }
 
 /// Read node set IDs
-///
-/// #[allow(dead_code)] is used because this function is called from other integration tests.
-#[allow(dead_code)]
 pub fn read_node_set_ids(path: &PathBuf) -> Result<Vec<i64>, Box<dyn std::error::Error>> {
     let file = ExodusFile::<exodus_rs::mode::Read>::open(path)?;
     let ids = file.set_ids(EntityType::NodeSet)?;
@@ -878,9 +874,6 @@ pub fn read_node_set_ids(path: &PathBuf) -> Result<Vec<i64>, Box<dyn std::error:
 }
 
 /// Read entity names
-///
-/// #[allow(dead_code)] is used because this function is called from other integration tests.
-#[allow(dead_code)]
 pub fn read_names(
     path: &PathBuf,
     entity_type: EntityType,
@@ -892,9 +885,6 @@ pub fn read_names(
 }
 
 /// Read element block IDs
-///
-/// #[allow(dead_code)] is used because this function is called from other integration tests.
-#[allow(dead_code)]
 pub fn read_block_ids(path: &PathBuf) -> Result<Vec<i64>, Box<dyn std::error::Error>> {
     let file = ExodusFile::<exodus_rs::mode::Read>::open(path)?;
     let ids = file.block_ids(EntityType::ElemBlock)?;
@@ -903,9 +893,6 @@ pub fn read_block_ids(path: &PathBuf) -> Result<Vec<i64>, Box<dyn std::error::Er
 }
 
 /// Read side set IDs
-///
-/// #[allow(dead_code)] is used because this function is called from other integration tests.
-#[allow(dead_code)]
 pub fn read_side_set_ids(path: &PathBuf) -> Result<Vec<i64>, Box<dyn std::error::Error>> {
     let file = ExodusFile::<exodus_rs::mode::Read>::open(path)?;
     let ids = file.set_ids(EntityType::SideSet)?;
@@ -914,13 +901,7 @@ pub fn read_side_set_ids(path: &PathBuf) -> Result<Vec<i64>, Box<dyn std::error:
 }
 
 /// Read side set data (elements, sides, dist_factors)
-///
-/// #[allow(dead_code)] is used because this function is called from other integration tests.
-#[allow(dead_code)]
-pub fn read_side_set(
-    path: &PathBuf,
-    set_id: i64,
-) -> Result<(Vec<i64>, Vec<i64>, Vec<f64>), Box<dyn std::error::Error>> {
+pub fn read_side_set(path: &PathBuf, set_id: i64) -> FixtureResult<(Vec<i64>, Vec<i64>, Vec<f64>)> {
     let file = ExodusFile::<exodus_rs::mode::Read>::open(path)?;
     let ss = file.side_set(set_id)?;
     drop(file); // Explicitly close the file before returning
@@ -929,9 +910,6 @@ pub fn read_side_set(
 
 /// Create a mesh with variables that could be false positives for vector detection.
 /// This includes both real vector components and scalar fields that look like vectors.
-///
-/// #[allow(dead_code)] is used because this function is called from other integration tests.
-#[allow(dead_code)]
 pub fn create_mesh_with_false_positive_vars(
     path: &PathBuf,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -1030,3 +1008,240 @@ pub fn create_mesh_with_false_positive_vars(
     drop(file); // Explicitly close the file before returning
     Ok(())
 }
+
+/// Create a single QUAD4 on the unit square with a nodal `temperature` of the
+/// node ID plus `offset` at two time steps
+pub fn create_quad4_with_offset(
+    path: &PathBuf,
+    offset: f64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let options = CreateOptions {
+        mode: CreateMode::Clobber,
+        ..Default::default()
+    };
+    let mut file = ExodusFile::create(path, options)?;
+
+    let params = InitParams {
+        title: "Diff test mesh".to_string(),
+        num_dim: 2,
+        num_nodes: 4,
+        num_elems: 1,
+        num_elem_blocks: 1,
+        ..Default::default()
+    };
+    file.init(&params)?;
+
+    file.put_coords(&[0.0, 1.0, 1.0, 0.0], Some(&[0.0, 0.0, 1.0, 1.0]), None)?;
+
+    let block = Block {
+        id: 1,
+        entity_type: EntityType::ElemBlock,
+        topology: "QUAD4".to_string(),
+        num_entries: 1,
+        num_nodes_per_entry: 4,
+        num_edges_per_entry: 0,
+        num_faces_per_entry: 0,
+        num_attributes: 0,
+    };
+    file.put_block(&block)?;
+    file.put_connectivity(1, &[1, 2, 3, 4])?;
+
+    file.define_variables(EntityType::Nodal, &["temperature"])?;
+    for step in 0..2 {
+        file.put_time(step, step as f64)?;
+        let temperature: Vec<f64> = (1..=4).map(|n| n as f64 + offset).collect();
+        file.put_var(step, EntityType::Nodal, 0, 0, &temperature)?;
+    }
+
+    file.sync()?;
+    drop(file); // Explicitly close the file before returning
+    Ok(())
+}
+
+/// Create two QUAD4 elements in block 10, with a side set on the bottom
+/// edge and a node set on the left edge
+pub fn create_quad4_with_sets(path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+    let options = CreateOptions {
+        mode: CreateMode::Clobber,
+        ..Default::default()
+    };
+    let mut file = ExodusFile::create(path, options)?;
+    file.init(&InitParams {
+        title: "QUAD4 sets test mesh".to_string(),
+        num_dim: 2,
+        num_nodes: 6,
+        num_elems: 2,
+        num_elem_blocks: 1,
+        num_node_sets: 1,
+        num_side_sets: 1,
+        ..Default::default()
+    })?;
+    file.put_coords(
+        &[0.0, 1.0, 2.0, 0.0, 1.0, 2.0],
+        Some(&[0.0, 0.0, 0.0, 1.0, 1.0, 1.0]),
+        None,
+    )?;
+    file.put_block(&Block {
+        id: 10,
+        entity_type: EntityType::ElemBlock,
+        topology: "QUAD4".to_string(),
+        num_entries: 2,
+        num_nodes_per_entry: 4,
+        num_edges_per_entry: 0,
+        num_faces_per_entry: 0,
+        num_attributes: 0,
+    })?;
+    file.put_connectivity(10, &[1, 2, 5, 4, 2, 3, 6, 5])?;
+    file.put_node_set(1, &[1, 4], None)?;
+    file.put_side_set(2, &[1, 2], &[1, 1], None)?;
+    file.sync()?;
+    drop(file); // Explicitly close the file before returning
+    Ok(())
+}
+
+/// Create a single HEX8 on the unit cube in block 3 with results at four
+/// time steps 0.25 apart
+///
+/// Side set 1 is the top face. The global `energy` is the step index and the
+/// nodal `temperature` is the node ID times the step index plus one.
+pub fn create_hex8_with_results(path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+    let x_coords: Vec<f64> = vec![0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0];
+    let y_coords: Vec<f64> = vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0];
+    let z_coords: Vec<f64> = vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0];
+
+    let connectivity: Vec<i64> = (1..=8).collect();
+
+    let options = CreateOptions {
+        mode: CreateMode::Clobber,
+        ..Default::default()
+    };
+    let mut file = ExodusFile::create(path, options)?;
+
+    let params = InitParams {
+        title: "HEX8 results test mesh".to_string(),
+        num_dim: 3,
+        num_nodes: 8,
+        num_elems: 1,
+        num_elem_blocks: 1,
+        num_side_sets: 1,
+        ..Default::default()
+    };
+    file.init(&params)?;
+
+    file.put_coords(&x_coords, Some(&y_coords), Some(&z_coords))?;
+
+    let block = Block {
+        id: 3,
+        entity_type: EntityType::ElemBlock,
+        topology: "HEX8".to_string(),
+        num_entries: 1,
+        num_nodes_per_entry: 8,
+        num_edges_per_entry: 0,
+        num_faces_per_entry: 0,
+        num_attributes: 0,
+    };
+    file.put_block(&block)?;
+    file.put_connectivity(3, &connectivity)?;
+
+    let side_set = Set {
+        id: 1,
+        entity_type: EntityType::SideSet,
+        num_entries: 1,
+        num_dist_factors: 0,
+    };
+    file.put_set(&side_set)?;
+    file.put_side_set(1, &[1], &[6], None)?;
+
+    file.define_variables(EntityType::Global, &["energy"])?;
+    file.define_variables(EntityType::Nodal, &["temperature"])?;
+    for step in 0..4 {
+        file.put_time(step, step as f64 * 0.25)?;
+        file.put_var(step, EntityType::Global, 0, 0, &[step as f64])?;
+        let temperature: Vec<f64> = (1..=8).map(|n| (n * (step + 1)) as f64).collect();
+        file.put_var(step, EntityType::Nodal, 0, 0, &temperature)?;
+    }
+
+    file.sync()?;
+    drop(file); // Explicitly close the file before returning
+    Ok(())
+}
+
+/// Create a single HEX20 on the unit cube with a nodal `temperature` of the
+/// node ID times the step at three time steps
+pub fn create_hex20_with_temperature(path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+    let options = CreateOptions {
+        mode: CreateMode::Clobber,
+        ..Default::default()
+    };
+    let mut file = ExodusFile::create(path, options)?;
+    file.init(&InitParams {
+        title: "VTK test mesh".to_string(),
+        num_dim: 3,
+        num_nodes: 20,
+        num_elems: 1,
+        num_elem_blocks: 1,
+        ..Default::default()
+    })?;
+
+    let corners = [
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [1.0, 1.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 1.0],
+        [1.0, 1.0, 1.0],
+        [0.0, 1.0, 1.0],
+    ];
+    // Exodus HEX20 edges: bottom, vertical, then top
+    let edges = [
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 0),
+        (0, 4),
+        (1, 5),
+        (2, 6),
+        (3, 7),
+        (4, 5),
+        (5, 6),
+        (6, 7),
+        (7, 4),
+    ];
+    let mut points: Vec<[f64; 3]> = corners.to_vec();
+    for (a, b) in edges {
+        let (a, b): ([f64; 3], [f64; 3]) = (corners[a], corners[b]);
+        points.push([
+            (a[0] + b[0]) / 2.0,
+            (a[1] + b[1]) / 2.0,
+            (a[2] + b[2]) / 2.0,
+        ]);
+    }
+    let x: Vec<f64> = points.iter().map(|p| p[0]).collect();
+    let y: Vec<f64> = points.iter().map(|p| p[1]).collect();
+    let z: Vec<f64> = points.iter().map(|p| p[2]).collect();
+    file.put_coords(&x, Some(&y), Some(&z))?;
+
+    file.put_block(&Block {
+        id: 7,
+        entity_type: EntityType::ElemBlock,
+        topology: "HEX20".to_string(),
+        num_entries: 1,
+        num_nodes_per_entry: 20,
+        num_edges_per_entry: 0,
+        num_faces_per_entry: 0,
+        num_attributes: 0,
+    })?;
+    let conn: Vec<i64> = (1..=20).collect();
+    file.put_connectivity(7, &conn)?;
+
+    file.define_variables(EntityType::Nodal, &["temperature"])?;
+    for step in 0..3 {
+        file.put_time(step, step as f64 * 0.5)?;
+        let temperature: Vec<f64> = (1..=20).map(|n| (n * step) as f64).collect();
+        file.put_var(step, EntityType::Nodal, 0, 0, &temperature)?;
+    }
+    file.sync()?;
+    drop(file); // Explicitly close the file before returning
+    Ok(())
+}
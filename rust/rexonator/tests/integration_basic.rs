@@ -13,14 +13,9 @@ mod fixtures;
 use fixtures::*;
 
 use serial_test::serial;
-use std::process::Command;
 
 const TOLERANCE: f64 = 1e-10;
 
-fn rexonator_cmd() -> Command {
-    Command::new(env!("CARGO_BIN_EXE_rexonator"))
-}
-
 // ========================================================================
 // Dry Run Tests
 // ========================================================================
@@ -1222,6 +1217,10 @@ fn test_in_place_translate() {
 
     // Record original bounds
     let (orig_x, _, _) = read_coord_bounds(&input).expect("Failed to read original bounds");
+    assert!(
+        (orig_x[0] - 0.0).abs() < TOLERANCE,
+        "original x_min should be 0"
+    );
 
     let status = rexonator_cmd()
         .args([
@@ -13,14 +13,9 @@ use fixtures::*;
 
 use exodus_rs::EntityType;
 use serial_test::serial;
-use std::process::Command;
 
 const TOLERANCE: f64 = 1e-10;
 
-fn rexonator_cmd() -> Command {
-    Command::new(env!("CARGO_BIN_EXE_rexonator"))
-}
-
 // ========================================================================
 // Basic CMM Tests
 // ========================================================================
@@ -0,0 +1,117 @@
+//! Integration tests for the `convert` command
+//!
+//! These tests verify:
+//! - Exporting an Exodus mesh to Gmsh in formats 2.2 and 4.1
+//! - Importing the Gmsh mesh back with its blocks and sets
+//! - Importing an Abaqus deck
+//! - Importing Nastran bulk data
+//! - Errors when neither file is a Gmsh mesh
+
+mod fixtures;
+use fixtures::*;
+
+use exodus_rs::{mode, types::*, ExodusFile};
+use serial_test::serial;
+use tempfile::TempDir;
+
+#[test]
+#[serial]
+fn test_convert_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("mesh.e");
+    create_quad4_with_sets(&input).unwrap();
+
+    for version in ["2", "4"] {
+        let msh = dir.path().join(format!("mesh{}.msh", version));
+        let status = rexonator_cmd()
+            .args(["convert", input.to_str().unwrap(), "-o"])
+            .arg(&msh)
+            .args(["--msh-version", version])
+            .status()
+            .expect("Failed to run rexonator convert");
+        assert!(status.success());
+        let text = std::fs::read_to_string(&msh).unwrap();
+        let header = if version == "2" { "2.2 0 8" } else { "4.1 0 8" };
+        assert!(text.starts_with(&format!("$MeshFormat\n{}\n", header)));
+
+        let output = dir.path().join(format!("back{}.e", version));
+        let status = rexonator_cmd()
+            .args(["convert", msh.to_str().unwrap(), "-o"])
+            .arg(&output)
+            .status()
+            .expect("Failed to run rexonator convert");
+        assert!(status.success());
+
+        let file = ExodusFile::<mode::Read>::open(&output).unwrap();
+        assert_eq!(file.block_ids(EntityType::ElemBlock).unwrap(), vec![10]);
+        assert_eq!(file.connectivity(10).unwrap(), vec![1, 2, 5, 4, 2, 3, 6, 5]);
+        assert_eq!(file.node_set(1).unwrap().nodes, vec![1, 4]);
+        assert_eq!(file.side_set(2).unwrap().elements, vec![1, 2]);
+    }
+}
+
+#[test]
+#[serial]
+fn test_convert_abaqus() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("mesh.inp");
+    std::fs::write(
+        &input,
+        "*NODE\n1, 0, 0\n2, 1, 0\n3, 1, 1\n4, 0, 1\n\
+         *ELEMENT, TYPE=CPS4, ELSET=PLATE\n7, 1, 2, 3, 4\n\
+         *SURFACE, NAME=BOTTOM\n7, S1\n",
+    )
+    .unwrap();
+    let output = dir.path().join("mesh.e");
+    let status = rexonator_cmd()
+        .args(["convert", input.to_str().unwrap(), "-o"])
+        .arg(&output)
+        .status()
+        .expect("Failed to run rexonator convert");
+    assert!(status.success());
+
+    let file = ExodusFile::<mode::Read>::open(&output).unwrap();
+    assert_eq!(file.init_params().unwrap().num_dim, 2);
+    assert_eq!(file.names(EntityType::ElemBlock).unwrap(), vec!["PLATE"]);
+    assert_eq!(file.id_map(EntityType::ElemMap).unwrap(), vec![7]);
+    assert_eq!(file.side_set(1).unwrap().sides, vec![1]);
+}
+
+#[test]
+#[serial]
+fn test_convert_nastran() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("mesh.bdf");
+    std::fs::write(
+        &input,
+        "GRID,1,,0.,0.,0.\nGRID,2,,1.,0.,0.\nGRID,3,,0.,1.,0.\n\
+         CTRIA3,5,8,1,2,3\n",
+    )
+    .unwrap();
+    let output = dir.path().join("mesh.e");
+    let status = rexonator_cmd()
+        .args(["convert", input.to_str().unwrap(), "-o"])
+        .arg(&output)
+        .status()
+        .expect("Failed to run rexonator convert");
+    assert!(status.success());
+
+    let file = ExodusFile::<mode::Read>::open(&output).unwrap();
+    assert_eq!(file.block_ids(EntityType::ElemBlock).unwrap(), vec![8]);
+    assert_eq!(file.id_map(EntityType::ElemMap).unwrap(), vec![5]);
+}
+
+#[test]
+#[serial]
+fn test_convert_needs_a_gmsh_file() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("mesh.e");
+    create_quad4_with_sets(&input).unwrap();
+
+    let status = rexonator_cmd()
+        .args(["convert", input.to_str().unwrap(), "-o"])
+        .arg(dir.path().join("copy.e"))
+        .status()
+        .expect("Failed to run rexonator convert");
+    assert!(!status.success());
+}
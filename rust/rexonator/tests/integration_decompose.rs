@@ -0,0 +1,137 @@
+//! Integration tests for the `decompose` command
+//!
+//! These tests verify:
+//! - Decomposing a mesh next to the input and under another base name
+//! - The partitioning method (--method) selection
+//! - Joining the parts again with the `join` command
+
+mod fixtures;
+use fixtures::*;
+
+use exodus_rs::{mode, types::*, ExodusFile};
+use serial_test::serial;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Create a 4x1 row of QUAD4 elements, with the left two elements in block 1
+/// and the right two in block 2
+///
+/// ```text
+///   6---7---8---9--10
+///   | 1 | 2 | 3 | 4 |
+///   1---2---3---4---5
+/// ```
+fn create_strip(path: &Path) {
+    let options = CreateOptions {
+        mode: CreateMode::Clobber,
+        ..Default::default()
+    };
+    let mut file = ExodusFile::create(path, options).unwrap();
+    file.init(&InitParams {
+        title: "QUAD4 strip".to_string(),
+        num_dim: 2,
+        num_nodes: 10,
+        num_elems: 4,
+        num_elem_blocks: 2,
+        num_node_sets: 1,
+        ..Default::default()
+    })
+    .unwrap();
+    let x: Vec<f64> = (0..10).map(|n| (n % 5) as f64).collect();
+    let y: Vec<f64> = (0..10).map(|n| (n / 5) as f64).collect();
+    file.put_coords(&x, Some(&y), None).unwrap();
+    for (id, first) in [(1, 1), (2, 3)] {
+        file.put_block(&Block {
+            id,
+            entity_type: EntityType::ElemBlock,
+            topology: "QUAD4".to_string(),
+            num_entries: 2,
+            num_nodes_per_entry: 4,
+            num_edges_per_entry: 0,
+            num_faces_per_entry: 0,
+            num_attributes: 0,
+        })
+        .unwrap();
+        let connectivity: Vec<i64> = (first..first + 2)
+            .flat_map(|i| [i, i + 1, i + 6, i + 5])
+            .collect();
+        file.put_connectivity(id, &connectivity).unwrap();
+    }
+    // Right edge
+    file.put_node_set(1, &[5, 10], None).unwrap();
+    file.sync().unwrap();
+}
+
+#[test]
+#[serial]
+fn test_decompose_next_to_input() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("strip.e");
+    create_strip(&input);
+
+    let status = rexonator_cmd()
+        .args(["decompose", input.to_str().unwrap(), "-n", "2"])
+        .status()
+        .expect("Failed to run rexonator decompose");
+    assert!(status.success());
+
+    for rank in 0..2 {
+        let path = dir.path().join(format!("strip.e.2.{}", rank));
+        let file = ExodusFile::<mode::Read>::open(&path).unwrap();
+        assert_eq!(file.init_params().unwrap().num_elems, 2);
+        assert_eq!(file.init_info().unwrap().num_procs, 2);
+        let other = 1 - rank as i64;
+        assert_eq!(file.node_comm_map(other).unwrap().node_ids.len(), 2);
+        let node_set = file.node_set(1).unwrap();
+        assert_eq!(node_set.nodes.len(), if rank == 1 { 2 } else { 0 });
+    }
+
+    // Joining the parts gives back the serial mesh
+    let joined = dir.path().join("joined.e");
+    let status = rexonator_cmd()
+        .args(["join", input.to_str().unwrap()])
+        .args(["-o", joined.to_str().unwrap()])
+        .status()
+        .expect("Failed to run rexonator join");
+    assert!(status.success());
+    let file = ExodusFile::<mode::Read>::open(&joined).unwrap();
+    let params = file.init_params().unwrap();
+    assert_eq!((params.num_nodes, params.num_elems), (10, 4));
+    assert_eq!(file.id_map(EntityType::ElemMap).unwrap(), vec![1, 2, 3, 4]);
+}
+
+#[test]
+#[serial]
+fn test_decompose_block_method_with_base_name() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("strip.e");
+    create_strip(&input);
+    let base = dir.path().join("parts.e");
+
+    let status = rexonator_cmd()
+        .args(["decompose", input.to_str().unwrap(), "-n", "2"])
+        .args(["--method", "block", "-o", base.to_str().unwrap()])
+        .status()
+        .expect("Failed to run rexonator decompose");
+    assert!(status.success());
+
+    let file = ExodusFile::<mode::Read>::open(dir.path().join("parts.e.2.1")).unwrap();
+    assert_eq!(file.block(1).unwrap().num_entries, 0);
+    assert_eq!(file.block(2).unwrap().num_entries, 2);
+    assert_eq!(file.id_map(EntityType::ElemMap).unwrap(), vec![3, 4]);
+}
+
+#[test]
+#[serial]
+fn test_decompose_too_many_processors() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("strip.e");
+    create_strip(&input);
+
+    let result = rexonator_cmd()
+        .args(["decompose", input.to_str().unwrap(), "-n", "5"])
+        .output()
+        .expect("Failed to run rexonator decompose");
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("Cannot decompose 4 elements"));
+}
@@ -0,0 +1,84 @@
+//! Integration tests for the `diff` command
+//!
+//! These tests verify:
+//! - Exit status 0 for files that agree and 2 for files that differ
+//! - Per-variable tolerances (--var-tol)
+//! - Quiet output and errors for missing files
+
+mod fixtures;
+use fixtures::*;
+
+use serial_test::serial;
+use tempfile::TempDir;
+
+#[test]
+#[serial]
+fn test_diff_same_files() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("first.e");
+    create_quad4_with_offset(&first, 0.0).unwrap();
+    let second = dir.path().join("second.e");
+    create_quad4_with_offset(&second, 1e-9).unwrap();
+
+    let output = rexonator_cmd()
+        .args(["diff", first.to_str().unwrap(), second.to_str().unwrap()])
+        .output()
+        .expect("Failed to run rexonator diff");
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Files are the same (2 time steps compared)"));
+}
+
+#[test]
+#[serial]
+fn test_diff_different_files() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("first.e");
+    create_quad4_with_offset(&first, 0.0).unwrap();
+    let second = dir.path().join("second.e");
+    create_quad4_with_offset(&second, 0.01).unwrap();
+
+    let output = rexonator_cmd()
+        .args(["diff", first.to_str().unwrap(), second.to_str().unwrap()])
+        .output()
+        .expect("Failed to run rexonator diff");
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("nodal temperature: 4 values differ"));
+    assert!(stdout.trim_end().ends_with("Files differ"));
+
+    // Only the summary line when quiet
+    let output = rexonator_cmd()
+        .args(["diff", first.to_str().unwrap(), second.to_str().unwrap()])
+        .arg("-q")
+        .output()
+        .expect("Failed to run rexonator diff");
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "Files differ\n");
+
+    // A looser tolerance on the temperature accepts the shift
+    let status = rexonator_cmd()
+        .args(["diff", first.to_str().unwrap(), second.to_str().unwrap()])
+        .args(["--var-tol", "Temperature=abs:0.02"])
+        .status()
+        .expect("Failed to run rexonator diff");
+    assert_eq!(status.code(), Some(0));
+}
+
+#[test]
+#[serial]
+fn test_diff_missing_file() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("first.e");
+    create_quad4_with_offset(&first, 0.0).unwrap();
+
+    let status = rexonator_cmd()
+        .args([
+            "diff",
+            first.to_str().unwrap(),
+            dir.path().join("absent.e").to_str().unwrap(),
+        ])
+        .status()
+        .expect("Failed to run rexonator diff");
+    assert_eq!(status.code(), Some(1));
+}
@@ -0,0 +1,172 @@
+//! Integration tests for the `join` command
+//!
+//! These tests verify:
+//! - Joining explicitly listed decomposed files
+//! - Finding the decomposed files from a base name
+//! - Step (--steps) and variable (--vars) selection
+
+mod fixtures;
+use fixtures::*;
+
+use exodus_rs::{mode, types::*, ExodusFile};
+use serial_test::serial;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Create a 2x2 QUAD4 grid on the unit square decomposed onto two processors
+/// as `<base>.2.0` and `<base>.2.1`
+///
+/// ```text
+///     y=1   7---8---9
+///           | 3 | 4 |     processor 0
+///     y=0.5 4---5---6     (nodes 4-6 shared)
+///           | 1 | 2 |     processor 1
+///     y=0   1---2---3
+/// ```
+///
+/// Each part has a nodal `temperature` (global node ID plus the step index)
+/// and `pressure` at three time steps.
+fn create_decomposed_mesh(base: &Path) -> Vec<PathBuf> {
+    let mut parts = Vec::new();
+    for rank in 0..2 {
+        let (node_ids, elem_ids): (Vec<i64>, Vec<i64>) = if rank == 0 {
+            (vec![4, 5, 6, 7, 8, 9], vec![3, 4])
+        } else {
+            (vec![1, 2, 3, 4, 5, 6], vec![1, 2])
+        };
+        let path = PathBuf::from(format!("{}.2.{}", base.display(), rank));
+        let options = CreateOptions {
+            mode: CreateMode::Clobber,
+            ..Default::default()
+        };
+        let mut file = ExodusFile::create(&path, options).unwrap();
+        file.init(&InitParams {
+            title: "Decomposed QUAD4 test mesh".to_string(),
+            num_dim: 2,
+            num_nodes: 6,
+            num_elems: 2,
+            num_elem_blocks: 1,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let x: Vec<f64> = node_ids
+            .iter()
+            .map(|&n| ((n - 1) % 3) as f64 * 0.5)
+            .collect();
+        let y: Vec<f64> = node_ids
+            .iter()
+            .map(|&n| ((n - 1) / 3) as f64 * 0.5)
+            .collect();
+        file.put_coords(&x, Some(&y), None).unwrap();
+        file.put_block(&Block {
+            id: 1,
+            entity_type: EntityType::ElemBlock,
+            topology: "QUAD4".to_string(),
+            num_entries: 2,
+            num_nodes_per_entry: 4,
+            num_edges_per_entry: 0,
+            num_faces_per_entry: 0,
+            num_attributes: 0,
+        })
+        .unwrap();
+        file.put_connectivity(1, &[1, 2, 5, 4, 2, 3, 6, 5]).unwrap();
+        file.put_id_map(EntityType::NodeMap, &node_ids).unwrap();
+        file.put_id_map(EntityType::ElemMap, &elem_ids).unwrap();
+
+        file.define_variables(EntityType::Nodal, &["temperature", "pressure"])
+            .unwrap();
+        for step in 0..3 {
+            file.put_time(step, step as f64).unwrap();
+            let temperature: Vec<f64> = node_ids
+                .iter()
+                .map(|&n| (n as usize + step) as f64)
+                .collect();
+            file.put_var(step, EntityType::Nodal, 0, 0, &temperature)
+                .unwrap();
+            file.put_var(step, EntityType::Nodal, 0, 1, &[1.0; 6])
+                .unwrap();
+        }
+        file.sync().unwrap();
+        parts.push(path);
+    }
+    parts
+}
+
+#[test]
+#[serial]
+fn test_join_listed_files() {
+    let dir = TempDir::new().unwrap();
+    let parts = create_decomposed_mesh(&dir.path().join("mesh.e"));
+    let output = dir.path().join("joined.e");
+
+    let status = rexonator_cmd()
+        .args([
+            "join",
+            parts[0].to_str().unwrap(),
+            parts[1].to_str().unwrap(),
+        ])
+        .args(["-o", output.to_str().unwrap()])
+        .status()
+        .expect("Failed to run rexonator join");
+    assert!(status.success());
+
+    let file = ExodusFile::<mode::Read>::open(&output).unwrap();
+    let params = file.init_params().unwrap();
+    assert_eq!(params.num_nodes, 9);
+    assert_eq!(params.num_elems, 4);
+    assert_eq!(file.id_map(EntityType::ElemMap).unwrap(), vec![1, 2, 3, 4]);
+    assert_eq!(
+        file.connectivity(1).unwrap(),
+        vec![1, 2, 5, 4, 2, 3, 6, 5, 4, 5, 8, 7, 5, 6, 9, 8]
+    );
+    let coords = file.coords::<f64>().unwrap();
+    assert_eq!(coords.x, vec![0.0, 0.5, 1.0, 0.0, 0.5, 1.0, 0.0, 0.5, 1.0]);
+    assert_eq!(file.num_time_steps().unwrap(), 3);
+}
+
+#[test]
+#[serial]
+fn test_join_base_name_with_selection() {
+    let dir = TempDir::new().unwrap();
+    let base = dir.path().join("mesh.e");
+    create_decomposed_mesh(&base);
+    let output = dir.path().join("last.e");
+
+    let status = rexonator_cmd()
+        .args([
+            "join",
+            base.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+        ])
+        .args(["--steps", "2:", "--vars", "temperature"])
+        .status()
+        .expect("Failed to run rexonator join");
+    assert!(status.success());
+
+    let file = ExodusFile::<mode::Read>::open(&output).unwrap();
+    assert_eq!(file.times().unwrap(), vec![1.0, 2.0]);
+    assert_eq!(
+        file.variable_names(EntityType::Nodal).unwrap(),
+        vec!["temperature"]
+    );
+    let temperature = file.var(1, EntityType::Nodal, 0, 0).unwrap();
+    let expected: Vec<f64> = (1..=9).map(|n| (n + 2) as f64).collect();
+    assert_eq!(temperature, expected);
+}
+
+#[test]
+#[serial]
+fn test_join_missing_files() {
+    let dir = TempDir::new().unwrap();
+    let output = dir.path().join("joined.e");
+
+    let result = rexonator_cmd()
+        .args(["join", dir.path().join("absent.e").to_str().unwrap()])
+        .args(["-o", output.to_str().unwrap()])
+        .output()
+        .expect("Failed to run rexonator join");
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("No decomposed files found"));
+}
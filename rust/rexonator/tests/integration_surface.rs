@@ -0,0 +1,83 @@
+//! Integration tests for the `surface` command
+//!
+//! These tests verify:
+//! - The skin of a hex written as binary STL
+//! - A side set with a nodal variable written as ASCII PLY
+//! - Errors for a variable with STL output and for step 0
+
+mod fixtures;
+use fixtures::*;
+
+use serial_test::serial;
+use tempfile::TempDir;
+
+#[test]
+#[serial]
+fn test_surface_skin_stl() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("hex.e");
+    create_hex8_with_results(&input).unwrap();
+    let output = dir.path().join("hex.stl");
+
+    let status = rexonator_cmd()
+        .args(["surface", input.to_str().unwrap(), "-o"])
+        .arg(&output)
+        .status()
+        .expect("Failed to run rexonator surface");
+    assert!(status.success());
+
+    // Six quadrilateral faces of two triangles each
+    let bytes = std::fs::read(&output).unwrap();
+    assert_eq!(u32::from_le_bytes(bytes[80..84].try_into().unwrap()), 12);
+    assert_eq!(bytes.len(), 84 + 12 * 50);
+}
+
+#[test]
+#[serial]
+fn test_surface_side_set_ply() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("hex.e");
+    create_hex8_with_results(&input).unwrap();
+    let output = dir.path().join("top.ply");
+
+    let status = rexonator_cmd()
+        .args(["surface", input.to_str().unwrap(), "-o"])
+        .arg(&output)
+        .args(["--side-sets", "1", "--var", "TEMPERATURE", "--step", "1"])
+        .arg("--ascii")
+        .status()
+        .expect("Failed to run rexonator surface");
+    assert!(status.success());
+
+    let ply = std::fs::read_to_string(&output).unwrap();
+    assert!(ply.contains("element vertex 4\n"));
+    assert!(ply.contains("property double temperature\n"));
+    assert!(ply.contains("element face 2\n"));
+    // The top face starts at node 5
+    let body = &ply[ply.find("end_header\n").unwrap() + 11..];
+    assert_eq!(body.lines().next(), Some("0 0 1 5"));
+}
+
+#[test]
+#[serial]
+fn test_surface_errors() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("hex.e");
+    create_hex8_with_results(&input).unwrap();
+
+    let status = rexonator_cmd()
+        .args(["surface", input.to_str().unwrap(), "-o"])
+        .arg(dir.path().join("hex.stl"))
+        .args(["--var", "temperature"])
+        .status()
+        .expect("Failed to run rexonator surface");
+    assert!(!status.success());
+
+    let status = rexonator_cmd()
+        .args(["surface", input.to_str().unwrap(), "-o"])
+        .arg(dir.path().join("hex.ply"))
+        .args(["--var", "temperature", "--step", "0"])
+        .status()
+        .expect("Failed to run rexonator surface");
+    assert!(!status.success());
+}
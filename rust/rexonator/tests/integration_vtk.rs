@@ -0,0 +1,84 @@
+//! Integration tests for the `vtk` command
+//!
+//! These tests verify:
+//! - A .pvd collection with one .vtu per selected time step
+//! - Quadratic hexahedra written in VTK node order
+//! - Legacy output and errors for unknown extensions
+
+mod fixtures;
+use fixtures::*;
+
+use serial_test::serial;
+use tempfile::TempDir;
+
+/// The values of the `<DataArray>` named `name`
+fn data_array(xml: &str, name: &str) -> Vec<String> {
+    let start = xml.find(&format!("Name=\"{}\"", name)).unwrap();
+    let body = &xml[start..];
+    let body = &body[body.find('>').unwrap() + 1..body.find("</DataArray>").unwrap()];
+    body.split_whitespace().map(str::to_string).collect()
+}
+
+#[test]
+#[serial]
+fn test_vtk_collection() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("hex20.e");
+    create_hex20_with_temperature(&input).unwrap();
+    let output = dir.path().join("hex20.pvd");
+
+    let status = rexonator_cmd()
+        .args(["vtk", input.to_str().unwrap(), "-o"])
+        .arg(&output)
+        .args(["--steps", "2:"])
+        .status()
+        .expect("Failed to run rexonator vtk");
+    assert!(status.success());
+
+    let pvd = std::fs::read_to_string(&output).unwrap();
+    assert!(pvd.contains("<DataSet timestep=\"0.5\" group=\"\" part=\"0\" file=\"hex20_2.vtu\"/>"));
+    assert!(pvd.contains("file=\"hex20_3.vtu\""));
+    assert!(!dir.path().join("hex20_1.vtu").exists());
+
+    let vtu = std::fs::read_to_string(dir.path().join("hex20_3.vtu")).unwrap();
+    assert_eq!(data_array(&vtu, "types"), vec!["25"]);
+    let expected: Vec<String> = [
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 16, 17, 18, 19, 12, 13, 14, 15,
+    ]
+    .iter()
+    .map(|i| i.to_string())
+    .collect();
+    assert_eq!(data_array(&vtu, "connectivity"), expected);
+    assert_eq!(data_array(&vtu, "temperature")[19], "40");
+    assert_eq!(data_array(&vtu, "block_id"), vec!["7"]);
+    assert_eq!(data_array(&vtu, "TimeValue"), vec!["1"]);
+}
+
+#[test]
+#[serial]
+fn test_vtk_legacy() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("hex20.e");
+    create_hex20_with_temperature(&input).unwrap();
+    let output = dir.path().join("hex20.vtk");
+
+    let status = rexonator_cmd()
+        .args(["vtk", input.to_str().unwrap(), "-o"])
+        .arg(&output)
+        .args(["--steps", "1", "--vars", "TEMPERATURE"])
+        .status()
+        .expect("Failed to run rexonator vtk");
+    assert!(status.success());
+
+    let vtk = std::fs::read_to_string(&output).unwrap();
+    assert!(vtk.starts_with("# vtk DataFile Version 3.0\nVTK test mesh\nASCII\n"));
+    assert!(vtk.contains("CELLS 1 21"));
+    assert!(vtk.contains("SCALARS temperature double 1"));
+
+    let status = rexonator_cmd()
+        .args(["vtk", input.to_str().unwrap(), "-o"])
+        .arg(dir.path().join("hex20.vtp"))
+        .status()
+        .expect("Failed to run rexonator vtk");
+    assert!(!status.success());
+}
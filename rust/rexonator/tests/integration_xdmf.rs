@@ -0,0 +1,60 @@
+//! Integration tests for the `xdmf` command
+//!
+//! These tests verify:
+//! - A temporal collection of the selected time steps, with the heavy data
+//!   in an HDF5 file next to the index
+//! - Errors for unknown extensions
+
+mod fixtures;
+use fixtures::*;
+
+use serial_test::serial;
+use tempfile::TempDir;
+
+#[test]
+#[serial]
+fn test_xdmf_time_series() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("hex8.e");
+    create_hex8_with_results(&input).unwrap();
+    let output = dir.path().join("hex8.xmf");
+
+    let status = rexonator_cmd()
+        .args(["xdmf", input.to_str().unwrap(), "-o"])
+        .arg(&output)
+        .args(["--steps", "2::2", "--time-chunk", "2"])
+        .status()
+        .expect("Failed to run rexonator xdmf");
+    assert!(status.success());
+
+    let index = std::fs::read_to_string(&output).unwrap();
+    assert!(index.contains("CollectionType=\"Temporal\""));
+    assert!(index.contains("<Time Value=\"0.25\"/>"));
+    assert!(index.contains("<Time Value=\"0.75\"/>"));
+    assert_eq!(index.matches("<Time Value=").count(), 2);
+    assert!(index.contains("<Grid Name=\"block_3\" GridType=\"Uniform\">"));
+    assert!(index.contains("TopologyType=\"Hexahedron\" NumberOfElements=\"1\""));
+    assert!(index.contains("hex8.h5:/connect1"));
+    assert!(index.contains("hex8.h5:/vals_nod_var1"));
+    assert!(index.contains("Name=\"energy\" AttributeType=\"Scalar\" Center=\"Grid\""));
+
+    // The heavy data is an HDF5 file
+    let heavy_data = std::fs::read(dir.path().join("hex8.h5")).unwrap();
+    assert!(heavy_data.starts_with(b"\x89HDF\r\n\x1a\n"));
+}
+
+#[test]
+#[serial]
+fn test_xdmf_bad_extension() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("hex8.e");
+    create_hex8_with_results(&input).unwrap();
+
+    let status = rexonator_cmd()
+        .args(["xdmf", input.to_str().unwrap(), "-o"])
+        .arg(dir.path().join("hex8.vtu"))
+        .status()
+        .expect("Failed to run rexonator xdmf");
+    assert!(!status.success());
+    assert!(!dir.path().join("hex8.h5").exists());
+}